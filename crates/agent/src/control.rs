@@ -0,0 +1,266 @@
+//! Runtime log configuration for the flux daemon.
+//!
+//! Flux reads `flux.conf` from the flux directory (`~/.apx/logs/flux.conf`) when it
+//! starts and again whenever it receives `SIGHUP`, so its own verbosity and the
+//! ingest severity threshold can change without dropping exporter connections.
+//!
+//! The file holds `key = value` lines; blank lines and `#` comments are ignored:
+//!
+//! ```text
+//! # Flux's own log filter, in APX_LOG syntax
+//! log = apx_agent=debug
+//! # Drop ingested records below this level (name or OTLP severity number)
+//! min_severity = info
+//! ```
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicI32, Ordering};
+use tracing::{info, warn};
+use tracing_subscriber::{
+    EnvFilter, Registry, layer::SubscriberExt, reload, util::SubscriberInitExt,
+};
+
+/// Name of the control file inside the flux directory.
+pub const CONTROL_FILE: &str = "flux.conf";
+
+/// Environment variable holding the startup log filter (same as the main binary).
+const LOG_ENV: &str = "APX_LOG";
+
+/// Log filter used when neither the control file nor `APX_LOG` sets one.
+const DEFAULT_LOG_FILTER: &str = "apx_agent=info";
+
+/// Settings read from the control file. Unset keys fall back to their startup defaults.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RuntimeSettings {
+    /// Filter directives for flux's own logs, e.g. `apx_agent=debug`.
+    pub log: Option<String>,
+    /// Minimum OTLP severity number of ingested records. Records below it are
+    /// dropped; records without a severity are always kept.
+    pub min_severity: Option<i32>,
+}
+
+impl RuntimeSettings {
+    /// Parse the `key = value` contents of a control file.
+    pub fn parse(contents: &str) -> Result<Self, String> {
+        let mut settings = Self::default();
+        for (index, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| format!("line {}: expected `key = value`", index + 1))?;
+            let value = value.trim();
+            match key.trim() {
+                "log" => settings.log = Some(value.to_string()),
+                "min_severity" => {
+                    settings.min_severity = Some(
+                        parse_severity(value).map_err(|e| format!("line {}: {e}", index + 1))?,
+                    );
+                }
+                other => return Err(format!("line {}: unknown key `{other}`", index + 1)),
+            }
+        }
+        Ok(settings)
+    }
+
+    /// Read settings from `path`. A missing file yields the defaults.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        match std::fs::read_to_string(path) {
+            Ok(contents) => Self::parse(&contents),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(format!("Failed to read {}: {e}", path.display())),
+        }
+    }
+}
+
+/// Parse a severity level name (`debug`, `warn`, ...) or an OTLP severity number.
+fn parse_severity(value: &str) -> Result<i32, String> {
+    if let Ok(number) = value.parse::<i32>() {
+        return if (0..=24).contains(&number) {
+            Ok(number)
+        } else {
+            Err(format!("severity number {number} is outside 0..=24"))
+        };
+    }
+    match value.to_uppercase().as_str() {
+        "TRACE" | "DEBUG" | "INFO" | "WARN" | "WARNING" | "ERROR" | "FATAL" | "CRITICAL" => {
+            Ok(i32::from(apx_common::format::severity_to_number(value)))
+        }
+        _ => Err(format!("unknown severity `{value}`")),
+    }
+}
+
+/// Handle for re-applying flux's log filter and ingest threshold at runtime.
+#[derive(Debug)]
+pub struct LogControl {
+    filter: reload::Handle<EnvFilter, Registry>,
+    path: Option<PathBuf>,
+}
+
+/// Install the global tracing subscriber with a reloadable filter.
+///
+/// The initial filter comes from `APX_LOG`; the control file is applied once the
+/// server starts.
+pub fn init_tracing() -> LogControl {
+    let (filter, handle) = reload::Layer::new(startup_filter());
+    tracing_subscriber::registry()
+        .with(filter)
+        .with(
+            tracing_subscriber::fmt::layer()
+                .with_writer(std::io::stderr)
+                .with_target(true)
+                .with_line_number(true)
+                .with_file(true),
+        )
+        .init();
+
+    LogControl {
+        filter: handle,
+        path: apx_common::flux_dir()
+            .ok()
+            .map(|dir| dir.join(CONTROL_FILE)),
+    }
+}
+
+/// Filter from `APX_LOG`, or the default when it is unset or invalid.
+fn startup_filter() -> EnvFilter {
+    EnvFilter::try_from_env(LOG_ENV).unwrap_or_else(|_| DEFAULT_LOG_FILTER.into())
+}
+
+impl LogControl {
+    /// Re-read the control file and apply it. An unreadable or invalid file
+    /// leaves the current configuration in place.
+    pub(crate) fn reload(&self, min_severity: &AtomicI32) {
+        let Some(path) = &self.path else {
+            return;
+        };
+        match RuntimeSettings::load(path) {
+            Ok(settings) => self.apply(&settings, min_severity),
+            Err(e) => warn!("Keeping current log configuration: {e}"),
+        }
+    }
+
+    /// Apply the ingest threshold and the log filter. They are independent, so an
+    /// invalid `log` value or a failed filter reload still updates the threshold.
+    fn apply(&self, settings: &RuntimeSettings, min_severity: &AtomicI32) {
+        let threshold = settings.min_severity.unwrap_or(0);
+        min_severity.store(threshold, Ordering::Relaxed);
+        info!("Ingest min severity set to {threshold}");
+
+        let filter = match &settings.log {
+            Some(directives) => match EnvFilter::try_new(directives) {
+                Ok(filter) => filter,
+                Err(e) => {
+                    warn!("Ignoring invalid log filter {directives:?}: {e}");
+                    return;
+                }
+            },
+            None => startup_filter(),
+        };
+        if let Err(e) = self.filter.reload(filter) {
+            warn!("Failed to apply log filter: {e}");
+            return;
+        }
+        info!(
+            "Log filter applied: {}",
+            settings.log.as_deref().unwrap_or("APX_LOG")
+        );
+    }
+
+    /// Re-apply the control file on every `SIGHUP` until the process exits.
+    #[cfg(unix)]
+    pub(crate) async fn watch(self, min_severity: Arc<AtomicI32>) {
+        use tokio::signal::unix::{SignalKind, signal};
+
+        let mut hangups = match signal(SignalKind::hangup()) {
+            Ok(hangups) => hangups,
+            Err(e) => {
+                warn!("Runtime log reconfiguration disabled: {e}");
+                return;
+            }
+        };
+        while hangups.recv().await.is_some() {
+            info!("Received SIGHUP, reloading log configuration");
+            self.reload(&min_severity);
+        }
+    }
+
+    /// Signals are unavailable, so the control file is only read at startup.
+    #[cfg(not(unix))]
+    pub(crate) async fn watch(self, _min_severity: Arc<AtomicI32>) {}
+}
+
+#[cfg(test)]
+// Reason: panicking on failure is idiomatic in tests
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_settings() {
+        let settings = RuntimeSettings::parse(
+            "# verbose while debugging ingest\n\nlog = apx_agent=debug,apx_db=trace\nmin_severity = warn\n",
+        )
+        .unwrap();
+        assert_eq!(
+            settings,
+            RuntimeSettings {
+                log: Some("apx_agent=debug,apx_db=trace".to_string()),
+                min_severity: Some(13),
+            }
+        );
+
+        assert_eq!(
+            RuntimeSettings::parse("min_severity=17")
+                .unwrap()
+                .min_severity,
+            Some(17)
+        );
+        assert_eq!(
+            RuntimeSettings::parse("").unwrap(),
+            RuntimeSettings::default()
+        );
+    }
+
+    #[test]
+    fn test_parse_settings_rejects_bad_lines() {
+        assert!(RuntimeSettings::parse("log").is_err());
+        assert!(RuntimeSettings::parse("level = debug").is_err());
+        assert!(RuntimeSettings::parse("min_severity = loud").is_err());
+        assert!(RuntimeSettings::parse("min_severity = 99").is_err());
+    }
+
+    #[test]
+    fn test_load_missing_file_is_default() {
+        let path =
+            std::env::temp_dir().join(format!("apx-flux-missing-{}.conf", std::process::id()));
+        assert_eq!(
+            RuntimeSettings::load(&path).unwrap(),
+            RuntimeSettings::default()
+        );
+    }
+
+    #[test]
+    fn test_bad_log_filter_still_updates_threshold() {
+        let (layer, handle) = reload::Layer::new(EnvFilter::new(DEFAULT_LOG_FILTER));
+        let control = LogControl {
+            filter: handle,
+            path: None,
+        };
+        let min_severity = AtomicI32::new(0);
+
+        let settings = RuntimeSettings::parse("log = apx_agent=loud\nmin_severity = warn").unwrap();
+        control.apply(&settings, &min_severity);
+        assert_eq!(min_severity.load(Ordering::Relaxed), 13);
+
+        // A filter reload that fails once the layer is gone does not block it either
+        drop(layer);
+        let settings =
+            RuntimeSettings::parse("log = apx_agent=debug\nmin_severity = error").unwrap();
+        control.apply(&settings, &min_severity);
+        assert_eq!(min_severity.load(Ordering::Relaxed), 17);
+    }
+}
@@ -3,6 +3,8 @@
 //! This crate provides the `apx-agent` binary, a standalone OpenTelemetry
 //! log collector that receives OTLP logs and stores them in `SQLite`.
 
+pub mod control;
 pub mod server;
 
-pub use server::run_server;
+pub use control::LogControl;
+pub use server::{FluxConfig, run_server};
@@ -4,7 +4,6 @@
 //! via HTTP and storing them in a local SQLite database.
 
 use clap::Parser;
-use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 #[derive(Parser)]
 #[command(name = "apx-agent", version, about = "APX OTLP log collector agent")]
@@ -21,25 +20,14 @@ enum Command {
 
 #[tokio::main]
 async fn main() {
-    // Initialize tracing — use APX_LOG env var (same as main binary)
-    tracing_subscriber::registry()
-        .with(
-            tracing_subscriber::EnvFilter::try_from_env("APX_LOG")
-                .unwrap_or_else(|_| "apx_agent=info".into()),
-        )
-        .with(
-            tracing_subscriber::fmt::layer()
-                .with_writer(std::io::stderr)
-                .with_target(true)
-                .with_line_number(true)
-                .with_file(true),
-        )
-        .init();
+    // Initialize tracing — use APX_LOG env var (same as main binary), reloadable on SIGHUP
+    let log_control = apx_agent::control::init_tracing();
 
     let _args = Args::parse();
 
     // Run server (default behavior regardless of subcommand)
-    if let Err(e) = apx_agent::run_server().await {
+    let config = apx_agent::FluxConfig::from_env();
+    if let Err(e) = apx_agent::run_server(config, log_control).await {
         eprintln!("Error: {e}");
         std::process::exit(1);
     }
@@ -3,38 +3,438 @@
 //! This module implements an Axum HTTP server that receives OpenTelemetry logs
 //! via OTLP HTTP protocol, supporting both JSON and Protobuf content types.
 
+use crate::control::LogControl;
 use apx_common::{FLUX_PORT, LogRecord};
-use apx_db::LogsDb;
+use apx_db::{LogFilter, LogsDb};
 use axum::{
     Router,
-    body::Bytes,
-    extract::State,
-    http::{HeaderMap, StatusCode},
-    response::IntoResponse,
+    body::{Body, Bytes},
+    extract::{Query, Request, State},
+    http::{
+        HeaderMap, HeaderValue, StatusCode,
+        header::{
+            ACCEPT_ENCODING, AUTHORIZATION, CONTENT_ENCODING, CONTENT_TYPE, RETRY_AFTER, VARY,
+            WWW_AUTHENTICATE,
+        },
+    },
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
     routing::{get, post},
 };
+use flate2::{Compression, read::GzDecoder, write::GzEncoder};
 use opentelemetry_proto::tonic::collector::logs::v1::ExportLogsServiceRequest;
 use prost::Message;
-use std::time::Duration;
-use tokio::net::TcpListener;
-use tracing::{debug, error, info};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::io::{self, Read, Write};
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicI32, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore, broadcast, mpsc, oneshot, watch};
+use tracing::{debug, error, info, warn};
+
+/// Environment variable overriding [`FluxConfig::max_connections`].
+const MAX_CONNECTIONS_ENV: &str = "APX_FLUX_MAX_CONNECTIONS";
+/// Environment variable overriding [`FluxConfig::tcp_keepalive`] (seconds, `0` disables).
+const KEEPALIVE_ENV: &str = "APX_FLUX_KEEPALIVE_SECS";
+/// Environment variable setting [`FluxConfig::max_records_per_sec`] (`0` disables).
+const MAX_RPS_ENV: &str = "APX_FLUX_MAX_RPS";
+/// Environment variable overriding [`FluxConfig::queue_depth`].
+const QUEUE_DEPTH_ENV: &str = "APX_FLUX_QUEUE_DEPTH";
+/// Environment variable setting [`FluxConfig::dedup_window`] (milliseconds, `0` disables).
+const DEDUP_WINDOW_ENV: &str = "APX_FLUX_DEDUP_WINDOW_MS";
+
+/// Seconds exporters are asked to wait before retrying when the write queue is full.
+const QUEUE_FULL_RETRY_AFTER_SECS: &str = "1";
+
+/// JSON responses smaller than this are sent uncompressed, where gzip saves little.
+const GZIP_MIN_BYTES: usize = 1024;
+
+/// Largest gzip-encoded export accepted once decompressed, guarding against gzip bombs.
+const MAX_DECODED_BYTES: u64 = 64 * 1024 * 1024;
+
+/// Connection tunables for the flux OTLP server.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FluxConfig {
+    /// Maximum number of connections served at once. Further connections wait
+    /// in the accept backlog until a slot frees up instead of being dropped.
+    pub max_connections: usize,
+    /// TCP keep-alive idle time for accepted connections (`None` disables it).
+    pub tcp_keepalive: Option<Duration>,
+    /// Maximum log records ingested per second (`None` disables rate limiting).
+    /// Records beyond the budget are dropped and counted in `/stats`.
+    pub max_records_per_sec: Option<u32>,
+    /// Maximum number of export requests waiting to be written to the database.
+    /// Requests arriving while the queue is full are answered with `429 Too Many Requests`.
+    pub queue_depth: usize,
+    /// How long consecutive identical records of a service are collected into a
+    /// single stored row (`None` stores every record as received).
+    pub dedup_window: Option<Duration>,
+}
+
+impl Default for FluxConfig {
+    fn default() -> Self {
+        Self {
+            max_connections: 1024,
+            tcp_keepalive: Some(Duration::from_secs(60)),
+            max_records_per_sec: None,
+            queue_depth: 256,
+            dedup_window: None,
+        }
+    }
+}
+
+impl FluxConfig {
+    /// Build a config from `APX_FLUX_MAX_CONNECTIONS`, `APX_FLUX_KEEPALIVE_SECS`,
+    /// `APX_FLUX_MAX_RPS`, `APX_FLUX_QUEUE_DEPTH` and `APX_FLUX_DEDUP_WINDOW_MS`,
+    /// falling back to the defaults for unset or invalid values.
+    pub fn from_env() -> Self {
+        Self::from_vars(
+            std::env::var(MAX_CONNECTIONS_ENV).ok().as_deref(),
+            std::env::var(KEEPALIVE_ENV).ok().as_deref(),
+            std::env::var(MAX_RPS_ENV).ok().as_deref(),
+            std::env::var(QUEUE_DEPTH_ENV).ok().as_deref(),
+            std::env::var(DEDUP_WINDOW_ENV).ok().as_deref(),
+        )
+    }
+
+    fn from_vars(
+        max_connections: Option<&str>,
+        keepalive_secs: Option<&str>,
+        max_rps: Option<&str>,
+        queue_depth: Option<&str>,
+        dedup_window_ms: Option<&str>,
+    ) -> Self {
+        let default = Self::default();
+
+        let max_connections = match max_connections.map(|v| v.trim().parse::<usize>()) {
+            Some(Ok(n)) if n > 0 => n,
+            Some(_) => {
+                warn!("Ignoring invalid {MAX_CONNECTIONS_ENV} value {max_connections:?}");
+                default.max_connections
+            }
+            None => default.max_connections,
+        };
+
+        let tcp_keepalive = match keepalive_secs.map(|v| v.trim().parse::<u64>()) {
+            Some(Ok(0)) => None,
+            Some(Ok(secs)) => Some(Duration::from_secs(secs)),
+            Some(Err(_)) => {
+                warn!("Ignoring invalid {KEEPALIVE_ENV} value {keepalive_secs:?}");
+                default.tcp_keepalive
+            }
+            None => default.tcp_keepalive,
+        };
+
+        let max_records_per_sec = match max_rps.map(|v| v.trim().parse::<u32>()) {
+            Some(Ok(0)) => None,
+            Some(Ok(n)) => Some(n),
+            Some(Err(_)) => {
+                warn!("Ignoring invalid {MAX_RPS_ENV} value {max_rps:?}");
+                default.max_records_per_sec
+            }
+            None => default.max_records_per_sec,
+        };
+
+        let queue_depth = match queue_depth.map(|v| v.trim().parse::<usize>()) {
+            Some(Ok(n)) if n > 0 => n,
+            Some(_) => {
+                warn!("Ignoring invalid {QUEUE_DEPTH_ENV} value {queue_depth:?}");
+                default.queue_depth
+            }
+            None => default.queue_depth,
+        };
+
+        let dedup_window = match dedup_window_ms.map(|v| v.trim().parse::<u64>()) {
+            Some(Ok(0)) => None,
+            Some(Ok(ms)) => Some(Duration::from_millis(ms)),
+            Some(Err(_)) => {
+                warn!("Ignoring invalid {DEDUP_WINDOW_ENV} value {dedup_window_ms:?}");
+                default.dedup_window
+            }
+            None => default.dedup_window,
+        };
+
+        Self {
+            max_connections,
+            tcp_keepalive,
+            max_records_per_sec,
+            queue_depth,
+            dedup_window,
+        }
+    }
+}
+
+/// Token bucket limiting how many log records are ingested per second.
+///
+/// The bucket holds up to one second worth of tokens and refills continuously,
+/// so short bursts up to the configured rate pass while sustained floods are cut.
+#[derive(Debug)]
+struct RateLimiter {
+    rate: f64,
+    state: Mutex<BucketState>,
+}
+
+#[derive(Debug)]
+struct BucketState {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl RateLimiter {
+    fn new(records_per_sec: u32, now: Instant) -> Self {
+        let rate = f64::from(records_per_sec);
+        Self {
+            rate,
+            state: Mutex::new(BucketState {
+                tokens: rate,
+                last_refill: now,
+            }),
+        }
+    }
+
+    /// Take up to `requested` tokens at `now`, returning how many were granted.
+    fn take(&self, requested: usize, now: Instant) -> usize {
+        let mut state = self
+            .state
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let elapsed = now.saturating_duration_since(state.last_refill);
+        state.tokens = elapsed
+            .as_secs_f64()
+            .mul_add(self.rate, state.tokens)
+            .min(self.rate);
+        state.last_refill = now;
+
+        // Reason: tokens is non-negative and bounded by a u32 rate
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let available = state.tokens.floor() as usize;
+        let granted = requested.min(available);
+        // Reason: granted is bounded by a u32 rate, exactly representable in f64
+        #[allow(clippy::cast_precision_loss)]
+        {
+            state.tokens -= granted as f64;
+        }
+        granted
+    }
+}
+
+/// Parsed records waiting for the database writer, with a channel for the write result.
+#[derive(Debug)]
+struct IngestBatch {
+    records: Vec<LogRecord>,
+    done: oneshot::Sender<Result<usize, String>>,
+}
+
+/// Record waiting for further identical lines before it is stored.
+#[derive(Debug)]
+struct PendingRecord {
+    record: LogRecord,
+    /// When the window opened by the first occurrence closes.
+    deadline: Instant,
+}
+
+/// Collapses consecutive identical records of a service into one record with a
+/// `repeat_count`.
+///
+/// Each (app, service) stream holds at most one pending record. A differing line
+/// or the end of the window releases it to be stored.
+#[derive(Debug)]
+struct Deduplicator {
+    window: Duration,
+    pending: HashMap<(Option<String>, Option<String>), PendingRecord>,
+}
+
+impl Deduplicator {
+    fn new(window: Duration) -> Self {
+        Self {
+            window,
+            pending: HashMap::new(),
+        }
+    }
+
+    /// Fold `records` received at `now` into the pending ones, returning the
+    /// records that are ready to be stored.
+    fn push(&mut self, records: Vec<LogRecord>, now: Instant) -> Vec<LogRecord> {
+        let mut ready = self.take_expired(now);
+        for record in records {
+            let stream = (record.app_path.clone(), record.service_name.clone());
+            match self.pending.entry(stream) {
+                Entry::Occupied(mut entry) if is_repeat(&entry.get().record, &record) => {
+                    let pending = &mut entry.get_mut().record;
+                    pending.repeat_count = pending.repeat_count.saturating_add(record.repeat_count);
+                }
+                Entry::Occupied(mut entry) => {
+                    let deadline = now + self.window;
+                    let released =
+                        std::mem::replace(entry.get_mut(), PendingRecord { record, deadline });
+                    ready.push(released.record);
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(PendingRecord {
+                        record,
+                        deadline: now + self.window,
+                    });
+                }
+            }
+        }
+        ready
+    }
+
+    /// Release the records whose window has closed by `now`.
+    fn take_expired(&mut self, now: Instant) -> Vec<LogRecord> {
+        self.pending
+            .extract_if(|_, pending| pending.deadline <= now)
+            .map(|(_, pending)| pending.record)
+            .collect()
+    }
+
+    /// Release every pending record.
+    fn take_all(&mut self) -> Vec<LogRecord> {
+        self.pending
+            .drain()
+            .map(|(_, pending)| pending.record)
+            .collect()
+    }
+
+    /// When the next pending window closes, if any record is pending.
+    fn next_deadline(&self) -> Option<Instant> {
+        self.pending.values().map(|pending| pending.deadline).min()
+    }
+}
+
+/// Whether `record` repeats `pending`: same severity and body.
+fn is_repeat(pending: &LogRecord, record: &LogRecord) -> bool {
+    pending.severity_number == record.severity_number
+        && pending.severity_text == record.severity_text
+        && pending.body == record.body
+}
+
+/// Store records released by the deduplicator, logging failures since no exporter waits on them.
+async fn store_released(storage: &LogsDb, records: &[LogRecord]) {
+    if let Err(e) = storage.insert_logs(records).await {
+        error!(
+            "Failed to store {} collapsed log records: {e}",
+            records.len()
+        );
+    }
+}
+
+/// Spawn the single task writing queued batches to `storage`.
+///
+/// At most `queue_depth` batches wait in the returned queue; handlers fail fast
+/// instead of piling up work in memory when the writer falls behind. With a
+/// `dedup_window`, batches are acknowledged once their records are stored or
+/// held for collapsing. Once every sender is dropped, the writer stores the
+/// records it still holds and exits, which closes the returned watch channel.
+fn spawn_writer(
+    storage: LogsDb,
+    queue_depth: usize,
+    dedup_window: Option<Duration>,
+) -> (mpsc::Sender<IngestBatch>, watch::Receiver<()>) {
+    let (tx, mut rx) = mpsc::channel::<IngestBatch>(queue_depth);
+    let (stopped_tx, stopped) = watch::channel(());
+    tokio::spawn(async move {
+        // Dropped when the task ends, closing `stopped`
+        let _stopped_tx = stopped_tx;
+        let Some(window) = dedup_window else {
+            while let Some(batch) = rx.recv().await {
+                let result = storage.insert_logs(&batch.records).await;
+                // The exporter may have disconnected while waiting; the records are stored anyway
+                let _ = batch.done.send(result);
+            }
+            return;
+        };
+
+        let mut dedup = Deduplicator::new(window);
+        loop {
+            let received = match dedup.next_deadline() {
+                Some(deadline) => {
+                    tokio::select! {
+                        batch = rx.recv() => batch,
+                        () = tokio::time::sleep_until(deadline.into()) => {
+                            let expired = dedup.take_expired(Instant::now());
+                            store_released(&storage, &expired).await;
+                            continue;
+                        }
+                    }
+                }
+                None => rx.recv().await,
+            };
+            let Some(batch) = received else {
+                store_released(&storage, &dedup.take_all()).await;
+                break;
+            };
+
+            let count = batch.records.len();
+            let ready = dedup.push(batch.records, Instant::now());
+            let result = storage.insert_logs(&ready).await.map(|_| count);
+            let _ = batch.done.send(result);
+        }
+    });
+    (tx, stopped)
+}
 
 /// Application state shared across handlers.
 #[derive(Clone, Debug)]
 struct AppState {
     storage: LogsDb,
+    limiter: Option<Arc<RateLimiter>>,
+    dropped_records: Arc<AtomicU64>,
+    /// Minimum severity number of ingested records (`0` keeps everything).
+    min_severity: Arc<AtomicI32>,
+    /// Records dropped for being below `min_severity`.
+    filtered_records: Arc<AtomicU64>,
+    /// Records refused because the write queue was full.
+    rejected_records: Arc<AtomicU64>,
+    ingest: mpsc::Sender<IngestBatch>,
+    /// Closed once the writer has stored everything and exited.
+    writer_stopped: watch::Receiver<()>,
+    /// Bearer token required on the OTLP and query endpoints (`None` leaves them open).
+    token: Option<Arc<str>>,
+    /// Set once the server starts shutting down, ending live log streams.
+    stopping: Arc<watch::Sender<bool>>,
+}
+
+impl AppState {
+    fn new(storage: LogsDb, config: &FluxConfig) -> Self {
+        let (ingest, writer_stopped) =
+            spawn_writer(storage.clone(), config.queue_depth, config.dedup_window);
+        Self {
+            ingest,
+            writer_stopped,
+            storage,
+            limiter: config
+                .max_records_per_sec
+                .map(|rps| Arc::new(RateLimiter::new(rps, Instant::now()))),
+            dropped_records: Arc::new(AtomicU64::new(0)),
+            min_severity: Arc::new(AtomicI32::new(0)),
+            filtered_records: Arc::new(AtomicU64::new(0)),
+            rejected_records: Arc::new(AtomicU64::new(0)),
+            token: None,
+            stopping: Arc::new(watch::Sender::new(false)),
+        }
+    }
 }
 
 /// Run the flux server (entry point for `apx-agent`).
 ///
-/// This function initializes storage, starts the cleanup scheduler,
-/// and runs the HTTP server. It blocks forever (or until error).
+/// This function initializes storage, starts the cleanup scheduler, applies the
+/// control file and re-applies it on `SIGHUP` through `log_control`, and runs the
+/// HTTP server. It blocks until `SIGTERM` or Ctrl-C, then stores the records held
+/// for collapsing and returns.
 ///
 /// # Errors
 ///
 /// Returns an error if storage initialization fails or the HTTP server
 /// cannot bind to the configured address.
-pub async fn run_server() -> Result<(), String> {
+pub async fn run_server(config: FluxConfig, log_control: LogControl) -> Result<(), String> {
     info!("Flux daemon starting...");
 
     // Open storage
@@ -49,8 +449,21 @@ pub async fn run_server() -> Result<(), String> {
         run_cleanup_loop(storage_for_cleanup).await;
     });
 
+    let token = apx_common::flux_token();
+    if token.is_some() {
+        info!("Requiring a bearer token on OTLP and query endpoints");
+    }
+    let state = AppState {
+        token: token.map(Arc::from),
+        ..AppState::new(storage, &config)
+    };
+
+    // Apply the control file now and on every SIGHUP
+    log_control.reload(&state.min_severity);
+    tokio::spawn(log_control.watch(Arc::clone(&state.min_severity)));
+
     // Run the HTTP server
-    run_http_server(storage).await
+    run_http_server(state, config).await
 }
 
 /// Log the result of a cleanup operation.
@@ -76,34 +489,614 @@ async fn run_cleanup_loop(storage: LogsDb) {
     }
 }
 
-/// Start the flux HTTP server with the given storage.
-async fn run_http_server(storage: LogsDb) -> Result<(), String> {
-    let state = AppState { storage };
+/// Start the flux HTTP server with the given state.
+async fn run_http_server(state: AppState, config: FluxConfig) -> Result<(), String> {
+    let addr = format!("{}:{FLUX_PORT}", apx_common::hosts::BIND_HOST);
+    info!(
+        "Starting flux OTLP receiver on {} (max connections: {}, keep-alive: {:?}, queue depth: {})",
+        addr, config.max_connections, config.tcp_keepalive, config.queue_depth
+    );
+
+    let listener = TcpListener::bind(&addr)
+        .await
+        .map_err(|e| format!("Failed to bind to {addr}: {e}"))?;
+
+    serve(listener, state, config, shutdown_signal()).await
+}
 
+/// Resolve once the daemon is asked to stop with `SIGTERM` or Ctrl-C.
+async fn shutdown_signal() {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{SignalKind, signal};
+
+        match signal(SignalKind::terminate()) {
+            Ok(mut terminate) => {
+                tokio::select! {
+                    _ = terminate.recv() => {}
+                    _ = tokio::signal::ctrl_c() => {}
+                }
+                return;
+            }
+            Err(e) => warn!("Failed to install SIGTERM handler: {e}"),
+        }
+    }
+    // Without a handler, stay up until Ctrl-C or a hard kill
+    let _ = tokio::signal::ctrl_c().await;
+}
+
+/// Serve the OTLP routes on an already bound listener until `shutdown` resolves.
+///
+/// Health checks stay open so liveness probes work without the token. On
+/// shutdown, in-flight requests finish and records held for collapsing are
+/// stored before this returns.
+async fn serve(
+    listener: TcpListener,
+    state: AppState,
+    config: FluxConfig,
+    shutdown: impl Future<Output = ()> + Send + 'static,
+) -> Result<(), String> {
+    let mut writer_stopped = state.writer_stopped.clone();
+    let stopping = Arc::clone(&state.stopping);
+    let shutdown = async move {
+        shutdown.await;
+        // Live streams never end on their own and would hold up the graceful shutdown
+        stopping.send_replace(true);
+    };
     let app = Router::new()
         .route("/v1/logs", post(handle_logs))
+        .route("/v1/logs/stats", get(log_stats))
+        .route("/v1/logs/export", get(export_logs))
+        .route("/v1/logs/live", get(live_logs))
+        .route("/stats", get(stats))
+        .route_layer(middleware::from_fn_with_state(state.clone(), require_token))
         .route("/health", get(health_check))
+        .route("/healthz", get(healthz))
         .with_state(state);
 
-    let addr = format!("{}:{FLUX_PORT}", apx_common::hosts::BIND_HOST);
-    info!("Starting flux OTLP receiver on {}", addr);
-
-    let listener = TcpListener::bind(&addr)
-        .await
-        .map_err(|e| format!("Failed to bind to {addr}: {e}"))?;
+    let listener = LimitedListener {
+        inner: listener,
+        permits: Arc::new(Semaphore::new(config.max_connections)),
+        tcp_keepalive: config.tcp_keepalive,
+    };
 
     axum::serve(listener, app)
+        .with_graceful_shutdown(shutdown)
         .await
         .map_err(|e| format!("Server error: {e}"))?;
 
+    // The router and its senders are gone, so the writer drains and exits
+    info!("Flux shutting down, storing pending records");
+    let _ = writer_stopped.changed().await;
     Ok(())
 }
 
+/// Reject requests lacking the configured bearer token with `401 Unauthorized`.
+async fn require_token(State(state): State<AppState>, request: Request, next: Next) -> Response {
+    let Some(token) = &state.token else {
+        return next.run(request).await;
+    };
+    let provided = request
+        .headers()
+        .get(AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "));
+    if provided.is_some_and(|provided| tokens_match(provided, token)) {
+        return next.run(request).await;
+    }
+
+    debug!(
+        "Rejected unauthenticated request to {}",
+        request.uri().path()
+    );
+    (StatusCode::UNAUTHORIZED, [(WWW_AUTHENTICATE, "Bearer")]).into_response()
+}
+
+/// Compare tokens without stopping at the first differing byte, so response
+/// times don't reveal how much of a guess was right.
+fn tokens_match(provided: &str, expected: &str) -> bool {
+    provided.len() == expected.len()
+        && provided
+            .bytes()
+            .zip(expected.bytes())
+            .fold(0, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+/// TCP listener that caps the number of live connections and applies keep-alive.
+#[derive(Debug)]
+struct LimitedListener {
+    inner: TcpListener,
+    permits: Arc<Semaphore>,
+    tcp_keepalive: Option<Duration>,
+}
+
+impl axum::serve::Listener for LimitedListener {
+    type Io = LimitedStream;
+    type Addr = SocketAddr;
+
+    async fn accept(&mut self) -> (Self::Io, Self::Addr) {
+        // Wait for a free slot before accepting, so excess exporters queue in the
+        // backlog rather than being accepted and starved. The semaphore is never
+        // closed, so the permit is always present.
+        let permit = Arc::clone(&self.permits).acquire_owned().await.ok();
+
+        loop {
+            match self.inner.accept().await {
+                Ok((stream, addr)) => {
+                    if let Some(idle) = self.tcp_keepalive {
+                        let keepalive = socket2::TcpKeepalive::new().with_time(idle);
+                        if let Err(e) =
+                            socket2::SockRef::from(&stream).set_tcp_keepalive(&keepalive)
+                        {
+                            debug!("Failed to set TCP keep-alive for {addr}: {e}");
+                        }
+                    }
+                    return (
+                        LimitedStream {
+                            stream,
+                            _permit: permit,
+                        },
+                        addr,
+                    );
+                }
+                Err(e) => {
+                    // Same policy as axum's TcpListener: back off on resource errors
+                    error!("Failed to accept connection: {e}");
+                    tokio::time::sleep(Duration::from_millis(100)).await;
+                }
+            }
+        }
+    }
+
+    fn local_addr(&self) -> io::Result<Self::Addr> {
+        self.inner.local_addr()
+    }
+}
+
+/// Accepted connection holding a concurrency slot until it is dropped.
+#[derive(Debug)]
+struct LimitedStream {
+    stream: TcpStream,
+    _permit: Option<OwnedSemaphorePermit>,
+}
+
+impl AsyncRead for LimitedStream {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.stream).poll_read(cx, buf)
+    }
+}
+
+impl AsyncWrite for LimitedStream {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.stream).poll_write(cx, buf)
+    }
+
+    fn poll_write_vectored(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[io::IoSlice<'_>],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.stream).poll_write_vectored(cx, bufs)
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        self.stream.is_write_vectored()
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.stream).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.stream).poll_shutdown(cx)
+    }
+}
+
 /// Health check endpoint.
 async fn health_check() -> impl IntoResponse {
     StatusCode::OK
 }
 
+/// How long `/healthz` waits for the log database before reporting unhealthy.
+const HEALTHZ_DB_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Deep health check: unlike `/health`, fails when the log database stops answering.
+async fn healthz(State(state): State<AppState>) -> impl IntoResponse {
+    match tokio::time::timeout(HEALTHZ_DB_TIMEOUT, state.storage.get_latest_id()).await {
+        Ok(Ok(_)) => StatusCode::OK,
+        Ok(Err(e)) => {
+            warn!("Health check failed: {e}");
+            StatusCode::SERVICE_UNAVAILABLE
+        }
+        Err(_) => {
+            warn!("Health check timed out waiting for the log database");
+            StatusCode::SERVICE_UNAVAILABLE
+        }
+    }
+}
+
+/// Ingest statistics endpoint.
+async fn stats(State(state): State<AppState>, headers: HeaderMap) -> Response {
+    json_response(
+        &headers,
+        &serde_json::json!({
+            "dropped_records": state.dropped_records.load(Ordering::Relaxed),
+            "filtered_records": state.filtered_records.load(Ordering::Relaxed),
+            "rejected_records": state.rejected_records.load(Ordering::Relaxed),
+            "queued_batches": state.ingest.max_capacity() - state.ingest.capacity(),
+        }),
+    )
+}
+
+/// Query parameters of `GET /v1/logs/stats` and `GET /v1/logs/export`,
+/// mirroring [`LogFilter`].
+#[derive(Debug, Deserialize)]
+struct LogQuery {
+    /// Absolute path of the app whose logs are read.
+    app_path: String,
+    #[serde(default)]
+    since_ns: i64,
+    until_ns: Option<i64>,
+    service: Option<String>,
+    min_severity: Option<i32>,
+    severity_text: Option<String>,
+}
+
+impl LogQuery {
+    /// Split into the app path and the filter, adding the `attr.<key>=<value>`
+    /// filters found in `params`.
+    fn into_parts(self, params: Vec<(String, String)>) -> (String, LogFilter) {
+        let filter = LogFilter {
+            since_ns: self.since_ns,
+            until_ns: self.until_ns,
+            service: self.service,
+            min_severity: self.min_severity,
+            severity_text: self.severity_text,
+            attributes: attributes_from_query(params),
+        };
+        (self.app_path, filter)
+    }
+}
+
+/// Attribute filters from `attr.<key>=<value>` query parameters.
+fn attributes_from_query(params: Vec<(String, String)>) -> Vec<(String, String)> {
+    params
+        .into_iter()
+        .filter_map(|(name, value)| Some((name.strip_prefix("attr.")?.to_string(), value)))
+        .collect()
+}
+
+/// Log count summary endpoint: total, per severity level and per service.
+///
+/// Any number of `attr.<key>=<value>` parameters narrow the counts to logs
+/// carrying those log or resource attributes.
+async fn log_stats(
+    State(state): State<AppState>,
+    Query(query): Query<LogQuery>,
+    Query(params): Query<Vec<(String, String)>>,
+    headers: HeaderMap,
+) -> Response {
+    let (app_path, filter) = query.into_parts(params);
+    match state.storage.log_stats(&app_path, &filter).await {
+        Ok(stats) => json_response(
+            &headers,
+            &serde_json::json!({
+            "total": stats.total,
+            "by_severity": stats
+                .by_severity
+                .into_iter()
+                .map(|(level, count)| serde_json::json!({ "level": level, "count": count }))
+                .collect::<Vec<_>>(),
+            "by_service": stats
+                .by_service
+                .into_iter()
+                .map(|(service, count)| serde_json::json!({ "service": service, "count": count }))
+                .collect::<Vec<_>>(),
+            }),
+        ),
+        Err(e) => {
+            error!("Failed to compute log stats: {e}");
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
+
+/// Serialize `value` as a JSON response, gzip-compressed when the client sends
+/// `Accept-Encoding: gzip` and the body is at least [`GZIP_MIN_BYTES`].
+fn json_response(headers: &HeaderMap, value: &serde_json::Value) -> Response {
+    let body = match serde_json::to_vec(value) {
+        Ok(body) => body,
+        Err(e) => {
+            error!("Failed to serialize response: {e}");
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+
+    if body.len() >= GZIP_MIN_BYTES && accepts_gzip(headers) {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::fast());
+        match encoder.write_all(&body).and_then(|()| encoder.finish()) {
+            Ok(compressed) => {
+                return (
+                    [
+                        (CONTENT_TYPE, "application/json"),
+                        (CONTENT_ENCODING, "gzip"),
+                        (VARY, "accept-encoding"),
+                    ],
+                    compressed,
+                )
+                    .into_response();
+            }
+            Err(e) => warn!("Sending response uncompressed: {e}"),
+        }
+    }
+    (
+        [
+            (CONTENT_TYPE, "application/json"),
+            (VARY, "accept-encoding"),
+        ],
+        body,
+    )
+        .into_response()
+}
+
+/// Whether `Accept-Encoding` allows gzip (`gzip` or `*`, not refused with `q=0`).
+fn accepts_gzip(headers: &HeaderMap) -> bool {
+    headers
+        .get_all(ACCEPT_ENCODING)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .flat_map(|v| v.split(','))
+        .any(|coding| {
+            let mut parts = coding.split(';').map(str::trim);
+            let name = parts.next().unwrap_or_default();
+            let refused = parts.any(|p| {
+                p.strip_prefix("q=")
+                    .and_then(|q| q.parse::<f32>().ok())
+                    .is_some_and(|q| q <= 0.0)
+            });
+            (name.eq_ignore_ascii_case("gzip") || name == "*") && !refused
+        })
+}
+
+/// Export endpoint: every log matching the query as JSON Lines, in insertion order.
+///
+/// Takes the same parameters as `GET /v1/logs/stats`. The body is streamed one
+/// database batch at a time, so memory stays bounded however many logs match.
+/// It is gzip-compressed on the fly when the client accepts gzip, unless the
+/// first batch shows the whole export is below [`GZIP_MIN_BYTES`].
+async fn export_logs(
+    State(state): State<AppState>,
+    Query(query): Query<LogQuery>,
+    Query(params): Query<Vec<(String, String)>>,
+    headers: HeaderMap,
+) -> Response {
+    let (app_path, filter) = query.into_parts(params);
+    let first = match state.storage.export_batch(&app_path, &filter, 0).await {
+        Ok(records) => records,
+        Err(e) => {
+            error!("Failed to export logs: {e}");
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+
+    let pending = json_lines(&first);
+    let gzip = pending.len() >= GZIP_MIN_BYTES && accepts_gzip(&headers);
+    let export = LogExport {
+        storage: state.storage,
+        app_path,
+        filter,
+        after_seq: first.last().map(|record| record.seq),
+        pending,
+        encoder: gzip.then(|| GzEncoder::new(Vec::new(), Compression::fast())),
+    };
+    let chunks = futures_util::stream::unfold(export, |mut export| async move {
+        let chunk = export.next_chunk().await?;
+        Some((chunk, export))
+    });
+
+    let mut response = (
+        [
+            (CONTENT_TYPE, "application/x-ndjson"),
+            (VARY, "accept-encoding"),
+        ],
+        Body::from_stream(chunks),
+    )
+        .into_response();
+    if gzip {
+        response
+            .headers_mut()
+            .insert(CONTENT_ENCODING, HeaderValue::from_static("gzip"));
+    }
+    response
+}
+
+/// Serialize `records` as JSON Lines.
+fn json_lines(records: &[LogRecord]) -> Vec<u8> {
+    let mut lines = Vec::new();
+    for record in records {
+        lines.extend_from_slice(record.to_json().to_string().as_bytes());
+        lines.push(b'\n');
+    }
+    lines
+}
+
+/// State of a streamed `GET /v1/logs/export` body.
+#[derive(Debug)]
+struct LogExport {
+    storage: LogsDb,
+    app_path: String,
+    filter: LogFilter,
+    /// Sequence number the next batch starts after (`None` once the database is exhausted).
+    after_seq: Option<i64>,
+    /// JSON lines fetched but not yet sent.
+    pending: Vec<u8>,
+    /// Compresses the body when the client accepts gzip (`None` once finished).
+    encoder: Option<GzEncoder<Vec<u8>>>,
+}
+
+impl LogExport {
+    /// Produce the next body chunk, or `None` once the export is complete.
+    async fn next_chunk(&mut self) -> Option<io::Result<Bytes>> {
+        loop {
+            if self.pending.is_empty() {
+                let Some(after_seq) = self.after_seq else {
+                    // Everything is sent; flush the gzip trailer once
+                    return self
+                        .encoder
+                        .take()
+                        .map(|encoder| encoder.finish().map(Bytes::from));
+                };
+                match self
+                    .storage
+                    .export_batch(&self.app_path, &self.filter, after_seq)
+                    .await
+                {
+                    Ok(records) => {
+                        self.after_seq = records.last().map(|record| record.seq);
+                        self.pending = json_lines(&records);
+                    }
+                    Err(e) => {
+                        error!("Failed to export logs: {e}");
+                        // Abort the body so the client sees a truncated export
+                        self.after_seq = None;
+                        self.encoder = None;
+                        return Some(Err(io::Error::other(e)));
+                    }
+                }
+                continue;
+            }
+
+            let lines = std::mem::take(&mut self.pending);
+            let Some(encoder) = &mut self.encoder else {
+                return Some(Ok(Bytes::from(lines)));
+            };
+            if let Err(e) = encoder.write_all(&lines) {
+                self.after_seq = None;
+                self.encoder = None;
+                return Some(Err(e));
+            }
+            // The encoder may hold everything back until it has a full block
+            let compressed = std::mem::take(encoder.get_mut());
+            if !compressed.is_empty() {
+                return Some(Ok(Bytes::from(compressed)));
+            }
+        }
+    }
+}
+
+/// Query parameters of `GET /v1/logs/live`.
+#[derive(Debug, Deserialize)]
+struct LiveQuery {
+    /// Absolute path of the app whose logs are streamed.
+    app_path: String,
+    service: Option<String>,
+    min_severity: Option<i32>,
+}
+
+/// Live log endpoint: records of an app as JSON Lines, as flux stores them.
+///
+/// Fed by [`LogsDb::subscribe`], so followers never poll the database. A client
+/// reading too slowly gets a `{"lagged": <n>}` line counting the records it
+/// missed, then the stream continues. It ends when flux shuts down.
+async fn live_logs(State(state): State<AppState>, Query(query): Query<LiveQuery>) -> Response {
+    let tail = LiveTail {
+        records: state.storage.subscribe(),
+        stopping: state.stopping.subscribe(),
+        app_path: apx_db::canonical_app_path(&query.app_path),
+        service: query.service,
+        min_severity: query.min_severity,
+    };
+    let lines = futures_util::stream::unfold(tail, |mut tail| async move {
+        let line = tail.next_line().await?;
+        Some((Ok::<_, io::Error>(Bytes::from(line)), tail))
+    });
+    (
+        [(CONTENT_TYPE, "application/x-ndjson")],
+        Body::from_stream(lines),
+    )
+        .into_response()
+}
+
+/// State of a streamed `GET /v1/logs/live` body.
+#[derive(Debug)]
+struct LiveTail {
+    records: broadcast::Receiver<LogRecord>,
+    stopping: watch::Receiver<bool>,
+    /// Canonical app path, as published by [`LogsDb::subscribe`].
+    app_path: String,
+    service: Option<String>,
+    min_severity: Option<i32>,
+}
+
+impl LiveTail {
+    /// Whether `record` passes the query. Records without a severity count as INFO (9).
+    fn matches(&self, record: &LogRecord) -> bool {
+        record.app_path.as_deref() == Some(self.app_path.as_str())
+            && self
+                .service
+                .as_ref()
+                .is_none_or(|service| record.service_name.as_ref() == Some(service))
+            && self
+                .min_severity
+                .is_none_or(|min| record.severity_number.unwrap_or(9) >= min)
+    }
+
+    /// Wait for the next line to send, or `None` once flux shuts down.
+    async fn next_line(&mut self) -> Option<String> {
+        loop {
+            let received = tokio::select! {
+                received = self.records.recv() => received,
+                _ = self.stopping.wait_for(|stopping| *stopping) => return None,
+            };
+            match received {
+                Ok(record) if self.matches(&record) => {
+                    return Some(format!("{}\n", record.to_json()));
+                }
+                Ok(_) => {}
+                Err(broadcast::error::RecvError::Lagged(missed)) => {
+                    return Some(format!("{}\n", serde_json::json!({ "lagged": missed })));
+                }
+                Err(broadcast::error::RecvError::Closed) => return None,
+            }
+        }
+    }
+}
+
+/// Undo a `Content-Encoding: gzip` applied by the exporter.
+fn decode_request_body(headers: &HeaderMap, body: Bytes) -> Result<Bytes, String> {
+    let encoding = headers
+        .get(CONTENT_ENCODING)
+        .and_then(|v| v.to_str().ok())
+        .map_or("", str::trim);
+    if encoding.is_empty() || encoding.eq_ignore_ascii_case("identity") {
+        return Ok(body);
+    }
+    if !encoding.eq_ignore_ascii_case("gzip") {
+        return Err(format!("Unsupported content encoding '{encoding}'"));
+    }
+
+    let mut decoded = Vec::new();
+    GzDecoder::new(body.as_ref())
+        .take(MAX_DECODED_BYTES + 1)
+        .read_to_end(&mut decoded)
+        .map_err(|e| format!("Invalid gzip body: {e}"))?;
+    if decoded.len() as u64 > MAX_DECODED_BYTES {
+        return Err(format!(
+            "Decompressed body exceeds {MAX_DECODED_BYTES} bytes"
+        ));
+    }
+    Ok(Bytes::from(decoded))
+}
+
 /// Dispatch log parsing based on content type.
 fn parse_request_logs(content_type: &str, body: &[u8]) -> Result<Vec<LogRecord>, String> {
     if content_type.contains("application/x-protobuf") {
@@ -113,39 +1106,118 @@ fn parse_request_logs(content_type: &str, body: &[u8]) -> Result<Vec<LogRecord>,
     }
 }
 
-/// Handle incoming OTLP logs (JSON or Protobuf).
-async fn handle_logs(
-    State(state): State<AppState>,
-    headers: HeaderMap,
-    body: Bytes,
-) -> impl IntoResponse {
+/// Handle incoming OTLP logs (JSON or Protobuf, optionally gzip-encoded).
+///
+/// Records are handed to the database writer through a bounded queue. When it is
+/// full the request is refused with `429` and `Retry-After`, which OTLP exporters
+/// treat as retryable.
+async fn handle_logs(State(state): State<AppState>, headers: HeaderMap, body: Bytes) -> Response {
     let content_type = headers
         .get("content-type")
         .and_then(|v| v.to_str().ok())
         .unwrap_or("application/json");
 
-    let records = match parse_request_logs(content_type, &body) {
+    // Inflating up to MAX_DECODED_BYTES is CPU-bound, so keep it off the async workers
+    let decode_headers = headers.clone();
+    let decoded =
+        tokio::task::spawn_blocking(move || decode_request_body(&decode_headers, body)).await;
+    let body = match decoded {
+        Ok(Ok(body)) => body,
+        Ok(Err(e)) => {
+            error!("Failed to decode logs: {e}");
+            return StatusCode::BAD_REQUEST.into_response();
+        }
+        Err(e) => {
+            error!("Failed to decode logs: {e}");
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+
+    let mut records = match parse_request_logs(content_type, &body) {
         Ok(r) => r,
         Err(e) => {
             error!("Failed to parse logs: {e}");
-            return StatusCode::BAD_REQUEST;
+            return StatusCode::BAD_REQUEST.into_response();
         }
     };
 
     if records.is_empty() {
-        return StatusCode::OK;
+        return StatusCode::OK.into_response();
     }
 
     debug!("Received {} log records", records.len());
 
-    match state.storage.insert_logs(&records).await {
-        Ok(count) => {
+    let min_severity = state.min_severity.load(Ordering::Relaxed);
+    if min_severity > 0 {
+        let received = records.len();
+        // Severity 0 is UNSPECIFIED, which is kept like a missing severity
+        records.retain(|r| {
+            r.severity_number
+                .is_none_or(|n| n == 0 || n >= min_severity)
+        });
+        let filtered = received - records.len();
+        if filtered > 0 {
+            state
+                .filtered_records
+                .fetch_add(filtered as u64, Ordering::Relaxed);
+            debug!("Dropped {filtered} records below severity {min_severity}");
+        }
+        if records.is_empty() {
+            return StatusCode::OK.into_response();
+        }
+    }
+
+    if let Some(limiter) = &state.limiter {
+        let granted = limiter.take(records.len(), Instant::now());
+        let dropped = records.len() - granted;
+        if dropped > 0 {
+            let total = state
+                .dropped_records
+                .fetch_add(dropped as u64, Ordering::Relaxed)
+                + dropped as u64;
+            debug!("Rate limit exceeded: dropped {dropped} records ({total} total)");
+            records.truncate(granted);
+        }
+        if records.is_empty() {
+            return StatusCode::OK.into_response();
+        }
+    }
+
+    let (done, written) = oneshot::channel();
+    let count = records.len();
+    if let Err(e) = state.ingest.try_send(IngestBatch { records, done }) {
+        let total = state
+            .rejected_records
+            .fetch_add(count as u64, Ordering::Relaxed)
+            + count as u64;
+        return match e {
+            mpsc::error::TrySendError::Full(_) => {
+                warn!("Write queue full: rejected {count} records ({total} total)");
+                (
+                    StatusCode::TOO_MANY_REQUESTS,
+                    [(RETRY_AFTER, QUEUE_FULL_RETRY_AFTER_SECS)],
+                )
+                    .into_response()
+            }
+            mpsc::error::TrySendError::Closed(_) => {
+                error!("Log writer stopped: rejected {count} records");
+                StatusCode::SERVICE_UNAVAILABLE.into_response()
+            }
+        };
+    }
+
+    match written.await {
+        Ok(Ok(count)) => {
             debug!("Stored {} log records", count);
-            StatusCode::OK
+            StatusCode::OK.into_response()
         }
-        Err(e) => {
+        Ok(Err(e)) => {
             error!("Failed to store logs: {e}");
-            StatusCode::INTERNAL_SERVER_ERROR
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+        Err(_) => {
+            error!("Log writer stopped before storing {count} records");
+            StatusCode::SERVICE_UNAVAILABLE.into_response()
         }
     }
 }
@@ -246,6 +1318,8 @@ fn parse_json_logs(body: &[u8]) -> Result<Vec<LogRecord>, String> {
                     log_attributes: log_attrs,
                     trace_id,
                     span_id,
+                    repeat_count: 1,
+                    seq: 0,
                 });
             }
         }
@@ -355,6 +1429,8 @@ fn parse_protobuf_logs(body: &[u8]) -> Result<Vec<LogRecord>, String> {
                     log_attributes: log_attrs_json,
                     trace_id,
                     span_id,
+                    repeat_count: 1,
+                    seq: 0,
                 });
             }
         }
@@ -478,3 +1554,630 @@ fn any_value_to_json(
         None => serde_json::Value::Null,
     }
 }
+
+#[cfg(test)]
+// Reason: panicking on failure is idiomatic in tests
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    async fn temp_db() -> LogsDb {
+        let dir = std::env::temp_dir().join(format!(
+            "apx-agent-test-{}-{}",
+            std::process::id(),
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_nanos()
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+        LogsDb::open_at(&dir.join("test.db")).await.unwrap()
+    }
+
+    #[test]
+    fn test_config_from_vars() {
+        assert_eq!(
+            FluxConfig::from_vars(None, None, None, None, None),
+            FluxConfig::default()
+        );
+
+        let config =
+            FluxConfig::from_vars(Some("16"), Some("0"), Some("500"), Some("8"), Some("250"));
+        assert_eq!(config.max_connections, 16);
+        assert_eq!(config.tcp_keepalive, None);
+        assert_eq!(config.max_records_per_sec, Some(500));
+        assert_eq!(config.queue_depth, 8);
+        assert_eq!(config.dedup_window, Some(Duration::from_millis(250)));
+
+        let config = FluxConfig::from_vars(
+            Some("0"),
+            Some("soon"),
+            Some("fast"),
+            Some("0"),
+            Some("often"),
+        );
+        assert_eq!(config, FluxConfig::default());
+        assert_eq!(
+            FluxConfig::from_vars(None, None, Some("0"), None, Some("0")).max_records_per_sec,
+            None
+        );
+    }
+
+    #[test]
+    fn test_rate_limiter_allows_rate_and_drops_excess() {
+        let start = Instant::now();
+        let limiter = RateLimiter::new(100, start);
+
+        // A full second of budget is available up front, then nothing more
+        assert_eq!(limiter.take(60, start), 60);
+        assert_eq!(limiter.take(60, start), 40);
+        assert_eq!(limiter.take(10, start), 0);
+
+        // Refills at the configured rate, capped at one second of budget
+        assert_eq!(limiter.take(100, start + Duration::from_millis(500)), 50);
+        assert_eq!(limiter.take(500, start + Duration::from_secs(10)), 100);
+    }
+
+    #[test]
+    fn test_deduplicator_collapses_consecutive_repeats() {
+        let line = |service: &str, body: &str| LogRecord {
+            timestamp_ns: 1,
+            observed_timestamp_ns: 1,
+            severity_number: Some(17),
+            body: Some(body.to_string()),
+            service_name: Some(service.to_string()),
+            app_path: Some("/tmp/app".to_string()),
+            ..LogRecord::default()
+        };
+        let start = Instant::now();
+        let mut dedup = Deduplicator::new(Duration::from_secs(1));
+
+        // Repeats are held back; another service's lines do not break the run
+        let ready = dedup.push(
+            vec![
+                line("api", "retrying"),
+                line("web", "ready"),
+                line("api", "retrying"),
+                line("api", "retrying"),
+            ],
+            start,
+        );
+        assert!(ready.is_empty());
+
+        // A different line releases the collapsed run
+        let ready = dedup.push(vec![line("api", "gave up")], start);
+        assert_eq!(ready.len(), 1);
+        assert_eq!(ready[0].body.as_deref(), Some("retrying"));
+        assert_eq!(ready[0].repeat_count, 3);
+
+        // Closing the window releases what is left
+        assert_eq!(dedup.next_deadline(), Some(start + Duration::from_secs(1)));
+        assert!(dedup.take_expired(start).is_empty());
+        let mut expired = dedup.take_expired(start + Duration::from_secs(1));
+        expired.sort_by(|a, b| a.body.cmp(&b.body));
+        let bodies: Vec<_> = expired.iter().map(|r| r.body.as_deref()).collect();
+        assert_eq!(bodies, vec![Some("gave up"), Some("ready")]);
+        assert_eq!(dedup.next_deadline(), None);
+    }
+
+    #[tokio::test]
+    async fn test_dropped_records_are_counted_in_stats() {
+        const BODY: &str = r#"{"resourceLogs":[{"scopeLogs":[{"logRecords":[{"body":{"stringValue":"a"}},{"body":{"stringValue":"b"}},{"body":{"stringValue":"c"}}]}]}]}"#;
+
+        let config = FluxConfig {
+            max_records_per_sec: Some(2),
+            ..FluxConfig::default()
+        };
+        let state = AppState::new(temp_db().await, &config);
+
+        let status = handle_logs(
+            State(state.clone()),
+            HeaderMap::new(),
+            Bytes::from_static(BODY.as_bytes()),
+        )
+        .await
+        .into_response()
+        .status();
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(state.dropped_records.load(Ordering::Relaxed), 1);
+
+        handle_logs(
+            State(state.clone()),
+            HeaderMap::new(),
+            Bytes::from_static(BODY.as_bytes()),
+        )
+        .await;
+        assert!(state.dropped_records.load(Ordering::Relaxed) >= 3);
+    }
+
+    #[tokio::test]
+    async fn test_records_below_min_severity_are_filtered() {
+        const BODY: &str = r#"{"resourceLogs":[{"scopeLogs":[{"logRecords":[{"severityNumber":5,"body":{"stringValue":"debug"}},{"severityNumber":13,"body":{"stringValue":"warn"}},{"body":{"stringValue":"plain"}},{"severityNumber":0,"body":{"stringValue":"unspecified"}}]}]}]}"#;
+
+        let state = AppState::new(temp_db().await, &FluxConfig::default());
+        state.min_severity.store(9, Ordering::Relaxed);
+
+        let status = handle_logs(
+            State(state.clone()),
+            HeaderMap::new(),
+            Bytes::from_static(BODY.as_bytes()),
+        )
+        .await
+        .status();
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(state.filtered_records.load(Ordering::Relaxed), 1);
+
+        // Lowering the threshold at runtime takes effect on the next request
+        state.min_severity.store(0, Ordering::Relaxed);
+        handle_logs(
+            State(state.clone()),
+            HeaderMap::new(),
+            Bytes::from_static(BODY.as_bytes()),
+        )
+        .await;
+        assert_eq!(state.filtered_records.load(Ordering::Relaxed), 1);
+    }
+
+    #[tokio::test]
+    async fn test_full_write_queue_rejects_with_retry_after() {
+        const BODY: &str = r#"{"resourceLogs":[{"scopeLogs":[{"logRecords":[{"body":{"stringValue":"a"}},{"body":{"stringValue":"b"}}]}]}]}"#;
+
+        // A queue nobody drains stands in for a writer that has fallen behind
+        let (ingest, _pending) = mpsc::channel(1);
+        let state = AppState {
+            ingest,
+            ..AppState::new(temp_db().await, &FluxConfig::default())
+        };
+        let (done, _written) = oneshot::channel();
+        state
+            .ingest
+            .try_send(IngestBatch {
+                records: Vec::new(),
+                done,
+            })
+            .unwrap();
+
+        let response = handle_logs(
+            State(state.clone()),
+            HeaderMap::new(),
+            Bytes::from_static(BODY.as_bytes()),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(response.headers()[RETRY_AFTER], "1");
+        assert_eq!(state.rejected_records.load(Ordering::Relaxed), 2);
+
+        let response = stats(State(state)).await.into_response();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let stats: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(stats["rejected_records"], 2);
+        assert_eq!(stats["queued_batches"], 1);
+    }
+
+    #[tokio::test]
+    async fn test_log_stats_endpoint() {
+        let storage = temp_db().await;
+        let record = |offset: i64, service: &str, severity: i32| LogRecord {
+            timestamp_ns: 1_000 + offset,
+            observed_timestamp_ns: 1_000 + offset,
+            severity_number: Some(severity),
+            service_name: Some(service.to_string()),
+            app_path: Some("/tmp/app".to_string()),
+            log_attributes: Some(format!(
+                r#"[{{"key":"code.filepath","value":{{"stringValue":"{service}.py"}}}}]"#
+            )),
+            ..LogRecord::default()
+        };
+        storage
+            .insert_logs(&[
+                record(0, "web", 9),
+                record(10, "api", 17),
+                record(20, "api", 17),
+            ])
+            .await
+            .unwrap();
+        let state = AppState::new(storage, &FluxConfig::default());
+
+        let query = LogQuery {
+            app_path: "/tmp/app".to_string(),
+            since_ns: 1_005,
+            until_ns: None,
+            service: None,
+            min_severity: None,
+            severity_text: None,
+        };
+        let response = log_stats(
+            State(state.clone()),
+            Query(query),
+            Query(Vec::new()),
+            HeaderMap::new(),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let stats: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(
+            stats,
+            serde_json::json!({
+                "total": 2,
+                "by_severity": [{ "level": "ERROR", "count": 2 }],
+                "by_service": [{ "service": "api", "count": 2 }],
+            })
+        );
+
+        let query = LogQuery {
+            app_path: "/tmp/app".to_string(),
+            since_ns: 0,
+            until_ns: None,
+            service: None,
+            min_severity: None,
+            severity_text: None,
+        };
+        let params = vec![
+            ("app_path".to_string(), "/tmp/app".to_string()),
+            ("attr.code.filepath".to_string(), "web.py".to_string()),
+        ];
+        let response = log_stats(State(state), Query(query), Query(params), HeaderMap::new()).await;
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let stats: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(stats["total"], 1);
+        assert_eq!(
+            stats["by_service"],
+            serde_json::json!([{ "service": "web", "count": 1 }])
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_sustains_concurrent_exporters() {
+        const EXPORTERS: usize = 64;
+        const BODY: &str = r#"{"resourceLogs":[{"resource":{"attributes":[{"key":"service.name","value":{"stringValue":"load"}}]},"scopeLogs":[{"logRecords":[{"timeUnixNano":"1","body":{"stringValue":"hello"}}]}]}]}"#;
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let config = FluxConfig {
+            max_connections: 8,
+            tcp_keepalive: Some(Duration::from_secs(30)),
+            max_records_per_sec: None,
+            queue_depth: 256,
+            dedup_window: None,
+        };
+        tokio::spawn(serve(
+            listener,
+            AppState::new(temp_db().await, &config),
+            config,
+            std::future::pending(),
+        ));
+
+        let exporters: Vec<_> = (0..EXPORTERS)
+            .map(|_| {
+                tokio::spawn(async move {
+                    let mut stream = TcpStream::connect(addr).await?;
+                    let request = format!(
+                        "POST /v1/logs HTTP/1.1\r\nHost: {addr}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{BODY}",
+                        BODY.len()
+                    );
+                    stream.write_all(request.as_bytes()).await?;
+                    let mut response = String::new();
+                    stream.read_to_string(&mut response).await?;
+                    Ok::<_, io::Error>(response)
+                })
+            })
+            .collect();
+
+        for exporter in exporters {
+            let response = exporter.await.unwrap().unwrap();
+            assert!(
+                response.starts_with("HTTP/1.1 200"),
+                "unexpected response: {response}"
+            );
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_shutdown_stores_collapsed_records() {
+        const BODY: &str = r#"{"resourceLogs":[{"resource":{"attributes":[{"key":"service.name","value":{"stringValue":"web"}}]},"scopeLogs":[{"logRecords":[{"timeUnixNano":"1","body":{"stringValue":"hello"}}]}]}]}"#;
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let config = FluxConfig {
+            dedup_window: Some(Duration::from_secs(60)),
+            ..FluxConfig::default()
+        };
+        let storage = temp_db().await;
+        let (shutdown_tx, shutdown_rx) = oneshot::channel::<()>();
+        let server = tokio::spawn(serve(
+            listener,
+            AppState::new(storage.clone(), &config),
+            config,
+            async {
+                let _ = shutdown_rx.await;
+            },
+        ));
+
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        let request = format!(
+            "POST /v1/logs HTTP/1.1\r\nHost: {addr}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{BODY}",
+            BODY.len()
+        );
+        stream.write_all(request.as_bytes()).await.unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        assert!(response.starts_with("HTTP/1.1 200"), "{response}");
+
+        // Acknowledged, but held until the 60s window closes
+        assert_eq!(storage.count_logs().await.unwrap(), 0);
+
+        shutdown_tx.send(()).unwrap();
+        server.await.unwrap().unwrap();
+        assert_eq!(storage.count_logs().await.unwrap(), 1);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_healthz_reports_healthy() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let config = FluxConfig {
+            max_connections: 8,
+            tcp_keepalive: None,
+            max_records_per_sec: None,
+            queue_depth: 256,
+            dedup_window: None,
+        };
+        tokio::spawn(serve(
+            listener,
+            AppState::new(temp_db().await, &config),
+            config,
+            std::future::pending(),
+        ));
+
+        let healthy = tokio::task::spawn_blocking(move || apx_common::is_flux_healthy(port))
+            .await
+            .unwrap();
+        assert!(healthy);
+
+        let unused = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let closed_port = unused.local_addr().unwrap().port();
+        drop(unused);
+        let healthy = tokio::task::spawn_blocking(move || apx_common::is_flux_healthy(closed_port))
+            .await
+            .unwrap();
+        assert!(!healthy);
+    }
+
+    /// Send a bodyless request and return the response status line.
+    async fn status_line(
+        addr: SocketAddr,
+        method: &str,
+        path: &str,
+        token: Option<&str>,
+    ) -> String {
+        let auth = token.map_or_else(String::new, |t| format!("Authorization: Bearer {t}\r\n"));
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        let request = format!(
+            "{method} {path} HTTP/1.1\r\nHost: {addr}\r\n{auth}Content-Length: 0\r\nConnection: close\r\n\r\n"
+        );
+        stream.write_all(request.as_bytes()).await.unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        response.lines().next().unwrap_or_default().to_string()
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_token_guards_otlp_and_query_endpoints() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let config = FluxConfig::default();
+        let state = AppState {
+            token: Some(Arc::from("s3cret")),
+            ..AppState::new(temp_db().await, &config)
+        };
+        tokio::spawn(serve(listener, state, config, std::future::pending()));
+
+        for (method, path) in [("POST", "/v1/logs"), ("GET", "/stats")] {
+            for token in [None, Some("wrong")] {
+                assert_eq!(
+                    status_line(addr, method, path, token).await,
+                    "HTTP/1.1 401 Unauthorized",
+                    "{method} {path} with {token:?}"
+                );
+            }
+        }
+        assert!(
+            status_line(addr, "GET", "/stats", Some("s3cret"))
+                .await
+                .starts_with("HTTP/1.1 200")
+        );
+        assert!(
+            status_line(addr, "GET", "/healthz", None)
+                .await
+                .starts_with("HTTP/1.1 200")
+        );
+    }
+
+    #[test]
+    fn test_accepts_gzip() {
+        let accepts = |value: &str| {
+            let mut headers = HeaderMap::new();
+            headers.insert(ACCEPT_ENCODING, value.parse().unwrap());
+            accepts_gzip(&headers)
+        };
+        assert!(accepts("gzip"));
+        assert!(accepts("br, GZIP;q=0.8"));
+        assert!(accepts("*"));
+        assert!(!accepts("br, deflate"));
+        assert!(!accepts("gzip;q=0"));
+        assert!(!accepts_gzip(&HeaderMap::new()));
+    }
+
+    #[tokio::test]
+    async fn test_json_response_gzips_large_bodies() {
+        let mut headers = HeaderMap::new();
+        headers.insert(ACCEPT_ENCODING, "gzip".parse().unwrap());
+        let services: Vec<_> = (0..100)
+            .map(|i| serde_json::json!({ "service": format!("svc-{i}"), "count": i }))
+            .collect();
+        let large = serde_json::json!({ "by_service": services });
+
+        let response = json_response(&headers, &large);
+        assert_eq!(response.headers()[CONTENT_ENCODING], "gzip");
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let mut json = String::new();
+        GzDecoder::new(body.as_ref())
+            .read_to_string(&mut json)
+            .unwrap();
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&json).unwrap(),
+            large
+        );
+
+        let small = json_response(&headers, &serde_json::json!({ "total": 1 }));
+        assert!(small.headers().get(CONTENT_ENCODING).is_none());
+        let uncompressed = json_response(&HeaderMap::new(), &large);
+        assert!(uncompressed.headers().get(CONTENT_ENCODING).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_export_streams_gzipped_json_lines() {
+        let storage = temp_db().await;
+        let records: Vec<_> = (0..2_500)
+            .map(|i| LogRecord {
+                timestamp_ns: 1_000 + i,
+                observed_timestamp_ns: 1_000 + i,
+                severity_number: Some(9),
+                body: Some(format!("line {i}")),
+                service_name: Some(if i == 0 { "web" } else { "api" }.to_string()),
+                app_path: Some("/tmp/app".to_string()),
+                ..LogRecord::default()
+            })
+            .collect();
+        storage.insert_logs(&records).await.unwrap();
+        let state = AppState::new(storage, &FluxConfig::default());
+        let query = |service: Option<&str>| LogQuery {
+            app_path: "/tmp/app".to_string(),
+            since_ns: 0,
+            until_ns: None,
+            service: service.map(str::to_string),
+            min_severity: None,
+            severity_text: None,
+        };
+        let mut headers = HeaderMap::new();
+        headers.insert(ACCEPT_ENCODING, "gzip".parse().unwrap());
+
+        // Spans several database batches
+        let response = export_logs(
+            State(state.clone()),
+            Query(query(None)),
+            Query(Vec::new()),
+            headers.clone(),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[CONTENT_ENCODING], "gzip");
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let mut lines = String::new();
+        GzDecoder::new(body.as_ref())
+            .read_to_string(&mut lines)
+            .unwrap();
+        let bodies: Vec<_> = lines
+            .lines()
+            .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap()["body"].clone())
+            .collect();
+        assert_eq!(bodies.len(), 2_500);
+        assert_eq!(bodies[0], "line 0");
+        assert_eq!(bodies[2_499], "line 2499");
+
+        // A single small line skips compression
+        let response = export_logs(
+            State(state),
+            Query(query(Some("web"))),
+            Query(Vec::new()),
+            headers,
+        )
+        .await;
+        assert!(response.headers().get(CONTENT_ENCODING).is_none());
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let line: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(line["body"], "line 0");
+    }
+
+    #[tokio::test]
+    async fn test_live_logs_stream_stored_records() {
+        use futures_util::StreamExt;
+
+        let state = AppState::new(temp_db().await, &FluxConfig::default());
+        let query = LiveQuery {
+            app_path: "/tmp/app".to_string(),
+            service: Some("api".to_string()),
+            min_severity: None,
+        };
+        let response = live_logs(State(state.clone()), Query(query)).await;
+        assert_eq!(response.headers()[CONTENT_TYPE], "application/x-ndjson");
+        let mut body = response.into_body().into_data_stream();
+
+        let record = |app: &str, service: &str, body: &str| LogRecord {
+            timestamp_ns: 1_000,
+            observed_timestamp_ns: 1_000,
+            body: Some(body.to_string()),
+            service_name: Some(service.to_string()),
+            app_path: Some(app.to_string()),
+            ..LogRecord::default()
+        };
+        state
+            .storage
+            .insert_logs(&[
+                record("/tmp/other", "api", "other app"),
+                record("/tmp/app", "web", "other service"),
+                record("/tmp/app", "api", "wanted"),
+            ])
+            .await
+            .unwrap();
+
+        let line = body.next().await.unwrap().unwrap();
+        let value: serde_json::Value = serde_json::from_slice(&line).unwrap();
+        assert_eq!(value["body"], "wanted");
+        assert_eq!(value["seq"], 3);
+
+        // Shutting down ends the stream instead of holding the connection open
+        state.stopping.send_replace(true);
+        assert!(body.next().await.is_none());
+    }
+
+    #[test]
+    fn test_decode_gzip_request_body() {
+        const BODY: &[u8] = br#"{"resourceLogs":[]}"#;
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(BODY).unwrap();
+        let compressed = Bytes::from(encoder.finish().unwrap());
+
+        let mut headers = HeaderMap::new();
+        assert_eq!(
+            decode_request_body(&headers, Bytes::from_static(BODY)).unwrap(),
+            BODY
+        );
+        headers.insert(CONTENT_ENCODING, "gzip".parse().unwrap());
+        assert_eq!(decode_request_body(&headers, compressed).unwrap(), BODY);
+        assert!(decode_request_body(&headers, Bytes::from_static(BODY)).is_err());
+        headers.insert(CONTENT_ENCODING, "br".parse().unwrap());
+        assert!(decode_request_body(&headers, Bytes::from_static(BODY)).is_err());
+    }
+
+    #[test]
+    fn test_tokens_match() {
+        assert!(tokens_match("s3cret", "s3cret"));
+        assert!(!tokens_match("s3cre", "s3cret"));
+        assert!(!tokens_match("s3creT", "s3cret"));
+    }
+}
@@ -1,18 +1,44 @@
 use clap::Args;
 use std::path::PathBuf;
 
-use apx_core::api_generator::generate_openapi;
+use apx_core::api_generator::{GeneratedClient, generate_openapi_client, watch_openapi};
 
 #[derive(Args, Debug, Clone)]
 pub struct GenerateOpenapiArgs {
     #[arg(long = "app-dir", value_name = "APP_PATH")]
     pub app_dir: PathBuf,
+    /// Exit with an error if the generator reports any warnings
+    #[arg(long)]
+    pub fail_on_warning: bool,
+    /// Keep running and regenerate whenever a Python file in the app changes
+    #[arg(long)]
+    pub watch: bool,
 }
 
 pub async fn run(args: GenerateOpenapiArgs) -> i32 {
-    match generate_openapi(&args.app_dir).await {
-        Ok(()) => {
-            println!("regenerated");
+    if args.watch {
+        return run_watch(&args).await;
+    }
+    match generate_openapi_client(&args.app_dir).await {
+        Ok(client) => {
+            for warning in &client.warnings {
+                eprintln!("warning: {warning}");
+            }
+            if args.fail_on_warning && !client.warnings.is_empty() {
+                eprintln!(
+                    "OpenAPI generation produced {} warning(s)",
+                    client.warnings.len()
+                );
+                return 1;
+            }
+            println!(
+                "{}",
+                if client.changed {
+                    "regenerated"
+                } else {
+                    "unchanged"
+                }
+            );
             0
         }
         Err(err) => {
@@ -21,3 +47,92 @@ pub async fn run(args: GenerateOpenapiArgs) -> i32 {
         }
     }
 }
+
+/// Regenerate on every Python change, printing one summary line per run.
+///
+/// Failed runs are reported and the watch continues.
+async fn run_watch(args: &GenerateOpenapiArgs) -> i32 {
+    let mut previous: Option<Vec<String>> = None;
+    let result = watch_openapi(&args.app_dir, |result| match result {
+        Ok(client) => {
+            for warning in &client.warnings {
+                eprintln!("warning: {warning}");
+            }
+            println!("{}", summarize(previous.as_deref(), &client));
+            previous = Some(client.operations);
+        }
+        Err(err) => eprintln!("{err}"),
+    })
+    .await;
+
+    match result {
+        Ok(()) => 0,
+        Err(err) => {
+            eprintln!("{err}");
+            1
+        }
+    }
+}
+
+/// One-line summary of a generation, listing operations added (`+`) and
+/// removed (`-`) since the `previous` run.
+fn summarize(previous: Option<&[String]>, client: &GeneratedClient) -> String {
+    if !client.changed {
+        return "unchanged".to_string();
+    }
+    let Some(previous) = previous else {
+        return format!("regenerated ({} operation(s))", client.operations.len());
+    };
+
+    let added = client
+        .operations
+        .iter()
+        .filter(|op| !previous.contains(op))
+        .map(|op| format!("+{op}"));
+    let removed = previous
+        .iter()
+        .filter(|op| !client.operations.contains(op))
+        .map(|op| format!("-{op}"));
+    let changes: Vec<String> = added.chain(removed).collect();
+    if changes.is_empty() {
+        "regenerated (no operations added or removed)".to_string()
+    } else {
+        format!("regenerated: {}", changes.join(" "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn client(operations: &[&str], changed: bool) -> GeneratedClient {
+        GeneratedClient {
+            warnings: Vec::new(),
+            operations: operations.iter().map(ToString::to_string).collect(),
+            changed,
+            files: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_summarize_lists_added_and_removed_operations() {
+        let previous = ["listItems".to_string(), "deleteItem".to_string()];
+        assert_eq!(
+            summarize(Some(&previous), &client(&["listItems", "createItem"], true)),
+            "regenerated: +createItem -deleteItem"
+        );
+        assert_eq!(
+            summarize(Some(&previous), &client(&["deleteItem", "listItems"], true)),
+            "regenerated (no operations added or removed)"
+        );
+    }
+
+    #[test]
+    fn test_summarize_first_and_unchanged_runs() {
+        assert_eq!(
+            summarize(None, &client(&["listItems"], true)),
+            "regenerated (1 operation(s))"
+        );
+        assert_eq!(summarize(None, &client(&["listItems"], false)), "unchanged");
+    }
+}
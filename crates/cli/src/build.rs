@@ -31,6 +31,11 @@ pub struct BuildArgs {
     pub build_path: PathBuf,
     #[arg(long = "skip-ui-build", help = "Skip the UI build step")]
     pub skip_ui_build: bool,
+    #[arg(
+        long = "fail-on-warning",
+        help = "Fail the build if OpenAPI client generation reports warnings"
+    )]
+    pub fail_on_warning: bool,
 }
 
 pub async fn run(args: BuildArgs) -> i32 {
@@ -56,7 +61,16 @@ async fn run_inner(args: BuildArgs) -> Result<(), String> {
     fs::write(build_dir.join(".gitignore"), "*\n")
         .map_err(|err| format!("Failed to write build .gitignore: {err}"))?;
 
-    generate_openapi(&app_path).await?;
+    let warnings = generate_openapi(&app_path).await?;
+    for warning in &warnings {
+        eprintln!("⚠️  OpenAPI warning: {warning}");
+    }
+    if args.fail_on_warning && !warnings.is_empty() {
+        return Err(format!(
+            "OpenAPI generation produced {} warning(s) and --fail-on-warning is set",
+            warnings.len()
+        ));
+    }
 
     if args.skip_ui_build {
         println!("Skipping UI build");
@@ -11,7 +11,7 @@ use apx_core::components::utils::format_relative_path;
 use apx_core::components::{AddPlan, UiConfig, plan_add};
 
 // Re-export from core so init.rs and other CLI code can use these
-pub use apx_core::components::add::{ComponentInput, add_components};
+pub use apx_core::components::add::{ComponentInput, add_components, typecheck_component_files};
 
 fn print_plan_summary(plan: &AddPlan) {
     println!("Components:");
@@ -62,6 +62,19 @@ pub struct ComponentsAddArgs {
     #[arg(long)]
     pub dry_run: bool,
 
+    /// Re-download registry data instead of using the local cache
+    #[arg(long)]
+    pub refresh: bool,
+
+    /// Local JSON file with registries: a registries.json catalog (array) replacing the
+    /// network catalog, or a name-to-config object overriding the project's registries
+    #[arg(long, value_name = "PATH")]
+    pub registry_file: Option<PathBuf>,
+
+    /// Type-check the added component files with tsc after installing (slow)
+    #[arg(long)]
+    pub json_schema_check: bool,
+
     #[arg(
         value_name = "APP_PATH",
         help = "The path to the app. Defaults to current working directory"
@@ -80,7 +93,14 @@ pub async fn run_inner(args: ComponentsAddArgs) -> Result<(), String> {
 
     // Handle dry-run separately since API doesn't support it
     if args.dry_run {
-        return run_dry_run(&app_dir, &args.component, args.registry.as_deref()).await;
+        return run_dry_run(
+            &app_dir,
+            &args.component,
+            args.registry.as_deref(),
+            args.refresh,
+            args.registry_file.as_deref(),
+        )
+        .await;
     }
 
     // Parse component name for display
@@ -104,7 +124,14 @@ pub async fn run_inner(args: ComponentsAddArgs) -> Result<(), String> {
     };
 
     let dep_spinner = spinner("📦 Installing dependencies...");
-    let result = add_components(&app_dir, &[input], args.force).await?;
+    let result = add_components(
+        &app_dir,
+        &[input],
+        args.force,
+        args.refresh,
+        args.registry_file.as_deref(),
+    )
+    .await?;
     dep_spinner.finish_and_clear();
 
     // Print dependencies installed
@@ -129,6 +156,14 @@ pub async fn run_inner(args: ComponentsAddArgs) -> Result<(), String> {
         eprintln!("\n⚠️  WARNING: {warning}");
     }
 
+    if args.json_schema_check {
+        let check_spinner = spinner("🔎 Type-checking component files...");
+        let check = typecheck_component_files(&app_dir, &result.written_paths).await;
+        check_spinner.finish_and_clear();
+        check?;
+        println!("✅ Component files type-check");
+    }
+
     println!(
         "\n🎉 Component added in {}\n",
         format_elapsed_ms(start_time)
@@ -142,6 +177,8 @@ async fn run_dry_run(
     app_dir: &Path,
     component: &str,
     registry: Option<&str>,
+    refresh: bool,
+    registry_file: Option<&Path>,
 ) -> Result<(), String> {
     let metadata = read_project_metadata(app_dir)?;
     let cfg = UiConfig::from_metadata(&metadata, app_dir)?;
@@ -158,11 +195,20 @@ async fn run_dry_run(
         (registry, component)
     };
 
-    let plan = plan_add(&client, app_dir, &cfg, resolved_registry, component_name).await?;
+    let plan = plan_add(
+        &client,
+        app_dir,
+        &cfg,
+        resolved_registry,
+        component_name,
+        refresh,
+        registry_file,
+    )
+    .await?;
     print_plan_summary(&plan);
 
     // Sync registry indexes silently
-    let _ = sync_registry_indexes(app_dir, false).await;
+    let _ = sync_registry_indexes(app_dir, refresh).await;
 
     Ok(())
 }
@@ -9,7 +9,7 @@ use apx_core::app_state::set_app_dir;
 use apx_core::common::read_project_metadata;
 use apx_core::dev::common::{
     BACKEND_PORT_END, BACKEND_PORT_START, DB_PORT_END, DB_PORT_START, FRONTEND_PORT_END,
-    FRONTEND_PORT_START, find_random_port_in_range,
+    FRONTEND_PORT_START, pinned_or_random_port, read_pinned_ports,
 };
 use apx_core::dev::server::{ServerConfig, resolve_databricks_profile, run_server};
 use apx_core::dev::token;
@@ -71,17 +71,23 @@ async fn run_inner(args: InternalRunServerArgs) -> Result<(), String> {
         let listener = tokio::net::TcpListener::from_std(std_listener)
             .map_err(|e| format!("Failed to convert to tokio listener: {e}"))?;
 
-        // Use randomized port selection for subprocess ports to reduce collision probability
-        // when multiple dev servers start simultaneously
-        let backend_port =
-            find_random_port_in_range(&args.host, BACKEND_PORT_START, BACKEND_PORT_END)?;
-        let db_port = find_random_port_in_range(&args.host, DB_PORT_START, DB_PORT_END)?;
+        // Use ports pinned via `apx dev start --*-port`, otherwise randomized port selection
+        // to reduce collision probability when multiple dev servers start simultaneously
+        let pinned = read_pinned_ports(&args.app_dir)?;
+        let backend_port = pinned_or_random_port(
+            &args.host,
+            pinned.backend,
+            BACKEND_PORT_START,
+            BACKEND_PORT_END,
+        )?;
+        let db_port = pinned_or_random_port(&args.host, pinned.db, DB_PORT_START, DB_PORT_END)?;
 
         // Only allocate a frontend port if the project has a UI
         let metadata = read_project_metadata(&args.app_dir)?;
         let frontend_port = if metadata.has_ui() {
-            Some(find_random_port_in_range(
+            Some(pinned_or_random_port(
                 &args.host,
+                pinned.frontend,
                 FRONTEND_PORT_START,
                 FRONTEND_PORT_END,
             )?)
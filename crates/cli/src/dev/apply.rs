@@ -353,7 +353,7 @@ async fn apply_single_addon(
         if !components.is_empty() {
             let components_start = Instant::now();
             let sp = spinner("🎨 Adding components...");
-            let result = add_components(app_dir, &components, true).await?;
+            let result = add_components(app_dir, &components, true, false, None).await?;
             sp.finish_and_clear();
             println!(
                 "✅ Components added ({})",
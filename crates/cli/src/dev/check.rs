@@ -13,6 +13,11 @@ pub struct CheckArgs {
         help = "The path to the app. Defaults to current working directory"
     )]
     pub app_path: Option<PathBuf>,
+    #[arg(
+        long = "client",
+        help = "Regenerate the API client from the OpenAPI spec and type-check it with tsc"
+    )]
+    pub client: bool,
 }
 
 pub async fn run(args: CheckArgs) -> i32 {
@@ -22,5 +27,5 @@ pub async fn run(args: CheckArgs) -> i32 {
 async fn run_inner(args: CheckArgs) -> Result<(), String> {
     let app_dir = find_app_dir(args.app_path)?;
 
-    run_check(&app_dir, OutputMode::Interactive).await
+    run_check(&app_dir, OutputMode::Interactive, args.client).await
 }
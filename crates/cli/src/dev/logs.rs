@@ -3,17 +3,26 @@
 //! Reads logs from ~/.apx/logs/db which is maintained by flux.
 
 use clap::Args;
-use std::path::PathBuf;
+use regex::{Regex, RegexBuilder};
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 use tracing::debug;
 
 use crate::common::find_app_dir;
 use crate::run_cli_async_helper;
-use apx_common::format::{format_aggregated_record, format_log_record};
-use apx_common::{LogAggregator, should_skip_log};
+use apx_common::format::{format_aggregated_record, format_log_record, severity_to_number};
+use apx_common::{AggregatedRecord, LogAggregator, LogRecord, should_skip_log};
 use apx_core::dev::common::{lock_path, read_lock};
-use apx_core::ops::logs::{DEFAULT_LOG_DURATION, parse_duration, since_timestamp_nanos};
-use apx_db::LogsDb;
+use apx_core::ops::logs::{
+    DEFAULT_LOG_DURATION, parse_duration, parse_time_bound, since_timestamp_nanos,
+};
+use apx_db::{LogFilter, LogsDb};
+
+/// Number of stored logs printed before following when `--tail` is not given.
+/// `--output-file` writes every log in the time range instead.
+const DEFAULT_TAIL: usize = 200;
 
 #[derive(Args, Debug, Clone)]
 pub struct LogsArgs {
@@ -30,8 +39,125 @@ pub struct LogsArgs {
         help = "Duration to look back (e.g. 30s, 10m, 1h)"
     )]
     pub duration: String,
+    #[arg(
+        long = "since",
+        value_name = "TIME",
+        conflicts_with = "duration",
+        help = "Show logs from this time: a duration ago (e.g. 5m) or an RFC 3339 timestamp"
+    )]
+    pub since: Option<String>,
+    #[arg(
+        long = "until",
+        value_name = "TIME",
+        conflicts_with = "follow",
+        help = "Show logs up to this time: a duration ago (e.g. 5m) or an RFC 3339 timestamp"
+    )]
+    pub until: Option<String>,
+    #[arg(
+        long = "service",
+        value_name = "NAME",
+        help = "Only show logs from this service (e.g. myapp_app)"
+    )]
+    pub service: Option<String>,
+    #[arg(
+        long = "severity",
+        value_name = "LEVEL",
+        value_parser = ["trace", "debug", "info", "warn", "error", "fatal"],
+        ignore_case = true,
+        help = "Only show logs at or above this severity"
+    )]
+    pub severity: Option<String>,
+    #[arg(
+        long = "severity-text",
+        value_name = "TEXT",
+        help = "Only show logs whose severity text is exactly this, ignoring case (e.g. WARNING)"
+    )]
+    pub severity_text: Option<String>,
+    #[arg(
+        long = "attr",
+        value_name = "KEY=VALUE",
+        help = "Only show logs with this log or resource attribute (repeatable)"
+    )]
+    pub attributes: Vec<String>,
+    #[arg(
+        long = "grep",
+        value_name = "PATTERN",
+        help = "Only show logs whose body matches this regular expression"
+    )]
+    pub grep: Option<String>,
+    #[arg(
+        short = 'i',
+        long = "ignore-case",
+        requires = "grep",
+        help = "Match --grep case-insensitively"
+    )]
+    pub ignore_case: bool,
+    #[arg(
+        short = 'n',
+        long = "tail",
+        value_name = "N",
+        help = "Show at most the last N matching logs, then follow if requested (`all` for no limit). Defaults to 200 on the terminal and to all logs with --output-file"
+    )]
+    pub tail: Option<String>,
     #[arg(short = 'f', long = "follow", help = "Follow logs until Ctrl+C")]
     pub follow: bool,
+    #[arg(
+        short = 'o',
+        long = "output-file",
+        value_name = "PATH",
+        help = "Write logs to a file (without colors) instead of stdout"
+    )]
+    pub output_file: Option<PathBuf>,
+}
+
+/// Totals reported after writing logs to `--output-file`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+struct WriteSummary {
+    records: usize,
+    bytes: u64,
+}
+
+/// Destination for formatted log lines, tracking how much was written.
+struct LogSink<W: Write> {
+    out: W,
+    colorize: bool,
+    summary: WriteSummary,
+}
+
+impl<W: Write> LogSink<W> {
+    const fn new(out: W, colorize: bool) -> Self {
+        Self {
+            out,
+            colorize,
+            summary: WriteSummary {
+                records: 0,
+                bytes: 0,
+            },
+        }
+    }
+
+    fn record(&mut self, record: &LogRecord) -> Result<(), String> {
+        let line = format_log_record(record, self.colorize);
+        self.line(&line)
+    }
+
+    fn aggregated(&mut self, agg: &AggregatedRecord) -> Result<(), String> {
+        let line = format_aggregated_record(agg, self.colorize);
+        self.line(&line)
+    }
+
+    fn line(&mut self, line: &str) -> Result<(), String> {
+        writeln!(self.out, "{line}").map_err(|e| format!("Failed to write logs: {e}"))?;
+        self.summary.records += 1;
+        self.summary.bytes += line.len() as u64 + 1;
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<(), String> {
+        self.out
+            .flush()
+            .map_err(|e| format!("Failed to write logs: {e}"))
+    }
 }
 
 pub async fn run(args: LogsArgs) -> i32 {
@@ -40,6 +166,8 @@ pub async fn run(args: LogsArgs) -> i32 {
 
 async fn run_async(args: LogsArgs) -> Result<(), String> {
     let app_dir = find_app_dir(args.app_path.clone())?;
+    let grep = body_pattern(&args)?;
+    let tail = tail_limit(&args)?;
 
     // Canonicalize path for matching
     let app_path_canonical = app_dir
@@ -70,64 +198,234 @@ async fn run_async(args: LogsArgs) -> Result<(), String> {
         .await
         .map_err(|e| format!("Failed to open logs database: {e}"))?;
 
-    let duration = parse_duration(&args.duration)?;
-    let since_ns = since_timestamp_nanos(duration);
-
-    if args.follow {
-        println!("📜 Streaming logs... (Ctrl+C to stop)\n");
-        follow_logs(&storage, &app_path_canonical, since_ns, &lock_path).await
+    let filter = log_filter(&args)?;
+    let grep = grep.as_ref();
+
+    if let Some(output_file) = &args.output_file {
+        let file = File::create(output_file)
+            .map_err(|e| format!("Failed to create {}: {e}", output_file.display()))?;
+        let mut sink = LogSink::new(BufWriter::new(file), false);
+        if args.follow {
+            println!(
+                "📜 Streaming logs to {}... (Ctrl+C to stop)\n",
+                output_file.display()
+            );
+            follow_logs(
+                &storage,
+                &app_path_canonical,
+                &filter,
+                grep,
+                tail,
+                &lock_path,
+                &mut sink,
+            )
+            .await?;
+        } else {
+            read_logs(
+                &storage,
+                &app_path_canonical,
+                &filter,
+                grep,
+                tail,
+                &mut sink,
+            )
+            .await?;
+        }
+        sink.flush()?;
+        print_summary(sink.summary, output_file);
+        Ok(())
     } else {
-        read_logs(&storage, &app_path_canonical, since_ns).await
+        let mut sink = LogSink::new(io::stdout(), true);
+        if args.follow {
+            println!("📜 Streaming logs... (Ctrl+C to stop)\n");
+            follow_logs(
+                &storage,
+                &app_path_canonical,
+                &filter,
+                grep,
+                tail,
+                &lock_path,
+                &mut sink,
+            )
+            .await
+        } else {
+            read_logs(
+                &storage,
+                &app_path_canonical,
+                &filter,
+                grep,
+                tail,
+                &mut sink,
+            )
+            .await
+        }
     }
 }
 
-/// Read logs from database, filtered by app path and timestamp
-async fn read_logs(storage: &LogsDb, app_path: &str, since_ns: i64) -> Result<(), String> {
-    let records = storage.query_logs(Some(app_path), since_ns, None).await?;
+fn print_summary(summary: WriteSummary, path: &Path) {
+    println!(
+        "✅ Wrote {} log line(s) ({} bytes) to {}",
+        summary.records,
+        summary.bytes,
+        path.display()
+    );
+}
 
-    let filtered: Vec<_> = records.iter().filter(|r| !should_skip_log(r)).collect();
+/// Translate the time range, service, severity and attribute options into a query filter.
+fn log_filter(args: &LogsArgs) -> Result<LogFilter, String> {
+    let since_ns = match &args.since {
+        Some(since) => parse_time_bound(since)?,
+        None => since_timestamp_nanos(parse_duration(&args.duration)?),
+    };
+    let until_ns = args.until.as_deref().map(parse_time_bound).transpose()?;
+    if until_ns.is_some_and(|until| until < since_ns) {
+        return Err("--until must not be earlier than the start of the time range.".to_string());
+    }
+    let attributes = args
+        .attributes
+        .iter()
+        .map(|attr| {
+            attr.split_once('=')
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .ok_or_else(|| format!("Invalid --attr '{attr}': expected KEY=VALUE."))
+        })
+        .collect::<Result<_, _>>()?;
+    Ok(LogFilter {
+        since_ns,
+        until_ns,
+        service: args.service.clone(),
+        min_severity: args
+            .severity
+            .as_deref()
+            .map(|level| i32::from(severity_to_number(level))),
+        severity_text: args.severity_text.clone(),
+        attributes,
+    })
+}
+
+/// Number of stored logs to show: `--tail` when given, otherwise [`DEFAULT_TAIL`]
+/// on the terminal and no limit when writing to `--output-file`.
+fn tail_limit(args: &LogsArgs) -> Result<Option<usize>, String> {
+    match (&args.tail, &args.output_file) {
+        (Some(tail), _) => tail_count(tail),
+        (None, Some(_)) => Ok(None),
+        (None, None) => Ok(Some(DEFAULT_TAIL)),
+    }
+}
+
+/// Parse `--tail`: a record count, or `all` for no limit.
+fn tail_count(tail: &str) -> Result<Option<usize>, String> {
+    let tail = tail.trim();
+    if tail.eq_ignore_ascii_case("all") {
+        return Ok(None);
+    }
+    tail.parse()
+        .map(Some)
+        .map_err(|_| format!("Invalid --tail '{tail}': expected a number of logs or `all`."))
+}
+
+/// Compile the `--grep` pattern, so an invalid one is reported before querying.
+fn body_pattern(args: &LogsArgs) -> Result<Option<Regex>, String> {
+    args.grep
+        .as_deref()
+        .map(|pattern| {
+            RegexBuilder::new(pattern)
+                .case_insensitive(args.ignore_case)
+                .build()
+                .map_err(|e| format!("Invalid --grep pattern '{pattern}':\n{e}"))
+        })
+        .transpose()
+}
+
+/// Whether `record` should be shown: not internal noise, and matching `grep` if given.
+fn is_visible(record: &LogRecord, grep: Option<&Regex>) -> bool {
+    !should_skip_log(record)
+        && grep.is_none_or(|pattern| {
+            record
+                .body
+                .as_deref()
+                .is_some_and(|body| pattern.is_match(body))
+        })
+}
+
+/// Read logs from database, filtered by app path, time range, service and severity,
+/// keeping only the newest `tail` of them when set
+async fn read_logs<W: Write>(
+    storage: &LogsDb,
+    app_path: &str,
+    filter: &LogFilter,
+    grep: Option<&Regex>,
+    tail: Option<usize>,
+    sink: &mut LogSink<W>,
+) -> Result<(), String> {
+    if tail == Some(0) {
+        return Ok(());
+    }
+    // Body matching happens here, so with --grep the newest matches may be older
+    // than the newest `tail` records
+    let records = match (tail, grep) {
+        (Some(count), None) => {
+            storage
+                .query_recent_logs_filtered(app_path, filter, count)
+                .await?
+        }
+        _ => storage.query_logs_filtered(app_path, filter, None).await?,
+    };
+
+    let mut filtered: Vec<_> = records.iter().filter(|r| is_visible(r, grep)).collect();
+    if let Some(count) = tail {
+        filtered.drain(..filtered.len().saturating_sub(count));
+    }
 
     if filtered.is_empty() {
         println!("No logs found for the specified time range.");
         return Ok(());
     }
 
+    write_records(&filtered, sink)
+}
+
+/// Write records to `sink`, collapsing repetitive messages.
+fn write_records<W: Write>(records: &[&LogRecord], sink: &mut LogSink<W>) -> Result<(), String> {
     // Use aggregator for repetitive messages
     let mut aggregator = LogAggregator::new();
 
-    for record in &filtered {
+    for record in records {
         let timestamp_ms = record.effective_timestamp_ms();
 
         // Flush expired aggregations before processing this record
         for agg in aggregator.flush_expired(timestamp_ms) {
-            println!("{}", format_aggregated_record(&agg, true));
+            sink.aggregated(&agg)?;
         }
 
         // Try to aggregate, if not aggregatable print directly
         if !aggregator.add(record) {
-            println!("{}", format_log_record(record, true));
+            sink.record(record)?;
         }
     }
 
     // Flush any remaining aggregations
     for agg in aggregator.flush_all() {
-        println!("{}", format_aggregated_record(&agg, true));
+        sink.aggregated(&agg)?;
     }
 
     Ok(())
 }
 
 /// Follow logs for new entries
-async fn follow_logs(
+async fn follow_logs<W: Write>(
     storage: &LogsDb,
     app_path: &str,
-    since_ns: i64,
-    lock_path: &std::path::Path,
+    filter: &LogFilter,
+    grep: Option<&Regex>,
+    tail: Option<usize>,
+    lock_path: &Path,
+    sink: &mut LogSink<W>,
 ) -> Result<(), String> {
     use chrono::Utc;
 
     // First, read existing logs
-    read_logs(storage, app_path, since_ns).await?;
+    read_logs(storage, app_path, filter, grep, tail, sink).await?;
 
     // Track last seen ID for incremental queries
     let mut last_id = storage.get_latest_id().await?;
@@ -144,7 +442,7 @@ async fn follow_logs(
                 debug!("Received Ctrl+C, stopping logs stream.");
                 // Flush remaining aggregations
                 for agg in aggregator.flush_all() {
-                    println!("{}", format_aggregated_record(&agg, true));
+                    sink.aggregated(&agg)?;
                 }
                 break;
             }
@@ -153,21 +451,26 @@ async fn follow_logs(
 
                 // Flush expired aggregations
                 for agg in aggregator.flush_expired(current_time_ms) {
-                    println!("{}", format_aggregated_record(&agg, true));
+                    sink.aggregated(&agg)?;
                 }
 
                 // Poll for new logs
-                let new_records = storage.query_logs_after_id(Some(app_path), last_id).await?;
+                let new_records = storage
+                    .query_logs_after_id_filtered(app_path, last_id, filter)
+                    .await?;
 
                 for record in &new_records {
-                    if !should_skip_log(record) {
+                    if is_visible(record, grep) {
                         // Try to aggregate, if not aggregatable print directly
                         if !aggregator.add(record) {
-                            println!("{}", format_log_record(record, true));
+                            sink.record(record)?;
                         }
                     }
                 }
 
+                // Keep file output current while following
+                sink.flush()?;
+
                 // Update last_id
                 if let Ok(new_id) = storage.get_latest_id().await
                     && new_id > last_id
@@ -180,7 +483,7 @@ async fn follow_logs(
                     debug!("Dev server stopped (lockfile removed), exiting logs follow.");
                     // Flush remaining aggregations
                     for agg in aggregator.flush_all() {
-                        println!("{}", format_aggregated_record(&agg, true));
+                        sink.aggregated(&agg)?;
                     }
                     println!("\n📭 Dev server stopped.");
                     break;
@@ -191,3 +494,191 @@ async fn follow_logs(
 
     Ok(())
 }
+
+#[cfg(test)]
+// Reason: panicking on failure is idiomatic in tests
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    fn record(offset_ms: i64, service: &str, body: &str) -> LogRecord {
+        let timestamp_ns = (1_767_225_600_000 + offset_ms) * 1_000_000;
+        LogRecord {
+            timestamp_ns,
+            observed_timestamp_ns: timestamp_ns,
+            severity_number: Some(9),
+            severity_text: Some("INFO".to_string()),
+            body: Some(body.to_string()),
+            service_name: Some(service.to_string()),
+            app_path: Some("/tmp/app".to_string()),
+            ..LogRecord::default()
+        }
+    }
+
+    #[test]
+    fn test_output_file_receives_plain_records() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("logs.txt");
+        let records = [
+            record(0, "demo_app", "Application startup complete."),
+            record(5, "demo_ui", "VITE ready in 312 ms"),
+            record(9, "demo_app", "Uvicorn running on http://127.0.0.1:8000"),
+        ];
+        let refs: Vec<&LogRecord> = records.iter().collect();
+
+        let mut sink = LogSink::new(BufWriter::new(File::create(&path).unwrap()), false);
+        write_records(&refs, &mut sink).unwrap();
+        sink.flush().unwrap();
+
+        let expected: String = records
+            .iter()
+            .map(|r| format!("{}\n", format_log_record(r, false)))
+            .collect();
+        let written = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(written, expected);
+        assert!(
+            !written.contains('\x1b'),
+            "file output must not be colorized"
+        );
+        assert_eq!(
+            sink.summary,
+            WriteSummary {
+                records: 3,
+                bytes: expected.len() as u64,
+            }
+        );
+    }
+
+    #[test]
+    fn test_log_filter_from_args() {
+        let args = LogsArgs {
+            app_path: None,
+            duration: DEFAULT_LOG_DURATION.to_string(),
+            since: Some("2026-01-01T00:00:00Z".to_string()),
+            until: Some("2026-01-01T01:00:00+01:00".to_string()),
+            service: Some("demo_app".to_string()),
+            severity: Some("WARN".to_string()),
+            severity_text: Some("WARNING".to_string()),
+            attributes: vec!["code.filepath=app/main.py".to_string()],
+            grep: None,
+            ignore_case: false,
+            tail: None,
+            follow: false,
+            output_file: None,
+        };
+        assert_eq!(
+            log_filter(&args).unwrap(),
+            LogFilter {
+                since_ns: 1_767_225_600_000_000_000,
+                until_ns: Some(1_767_225_600_000_000_000),
+                service: Some("demo_app".to_string()),
+                min_severity: Some(13),
+                severity_text: Some("WARNING".to_string()),
+                attributes: vec![("code.filepath".to_string(), "app/main.py".to_string())],
+            }
+        );
+
+        let no_value = LogsArgs {
+            attributes: vec!["code.filepath".to_string()],
+            ..args.clone()
+        };
+        assert!(log_filter(&no_value).is_err());
+
+        let reversed = LogsArgs {
+            since: Some("1m".to_string()),
+            until: Some("1h".to_string()),
+            ..args.clone()
+        };
+        assert!(log_filter(&reversed).is_err());
+
+        let invalid = LogsArgs {
+            since: Some("yesterday".to_string()),
+            ..args
+        };
+        assert!(log_filter(&invalid).is_err());
+    }
+
+    #[test]
+    fn test_grep_matches_body() {
+        let args = LogsArgs {
+            app_path: None,
+            duration: DEFAULT_LOG_DURATION.to_string(),
+            since: None,
+            until: None,
+            service: None,
+            severity: None,
+            severity_text: None,
+            attributes: Vec::new(),
+            grep: Some(r"timeout after \d+ms".to_string()),
+            ignore_case: false,
+            tail: None,
+            follow: false,
+            output_file: None,
+        };
+        let timeout = record(0, "demo_app", "Request Timeout after 500ms");
+        let startup = record(1, "demo_app", "Application startup complete.");
+        let mut empty = record(2, "demo_app", "");
+        empty.body = None;
+
+        let pattern = body_pattern(&args).unwrap().unwrap();
+        assert!(!is_visible(&timeout, Some(&pattern)));
+
+        let insensitive = LogsArgs {
+            ignore_case: true,
+            ..args.clone()
+        };
+        let pattern = body_pattern(&insensitive).unwrap().unwrap();
+        assert!(is_visible(&timeout, Some(&pattern)));
+        assert!(!is_visible(&startup, Some(&pattern)));
+        assert!(!is_visible(&empty, Some(&pattern)));
+        assert!(is_visible(&startup, None));
+
+        let invalid = LogsArgs {
+            grep: Some("(unclosed".to_string()),
+            ..args
+        };
+        let error = body_pattern(&invalid).unwrap_err();
+        assert!(error.starts_with("Invalid --grep pattern '(unclosed'"));
+    }
+
+    #[test]
+    fn test_tail_count() {
+        assert_eq!(tail_count("200").unwrap(), Some(200));
+        assert_eq!(tail_count("0").unwrap(), Some(0));
+        assert_eq!(tail_count(" ALL ").unwrap(), None);
+        assert!(tail_count("-5").is_err());
+        assert!(tail_count("lots").is_err());
+    }
+
+    #[test]
+    fn test_tail_defaults_to_all_logs_for_output_file() {
+        let args = LogsArgs {
+            app_path: None,
+            duration: DEFAULT_LOG_DURATION.to_string(),
+            since: None,
+            until: None,
+            service: None,
+            severity: None,
+            severity_text: None,
+            attributes: Vec::new(),
+            grep: None,
+            ignore_case: false,
+            tail: None,
+            follow: false,
+            output_file: None,
+        };
+        assert_eq!(tail_limit(&args).unwrap(), Some(DEFAULT_TAIL));
+
+        let export = LogsArgs {
+            output_file: Some(PathBuf::from("logs.txt")),
+            ..args
+        };
+        assert_eq!(tail_limit(&export).unwrap(), None);
+
+        let limited = LogsArgs {
+            tail: Some("5".to_string()),
+            ..export
+        };
+        assert_eq!(tail_limit(&limited).unwrap(), Some(5));
+    }
+}
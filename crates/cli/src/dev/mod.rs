@@ -3,6 +3,7 @@ pub mod apply;
 pub mod check;
 pub mod logs;
 pub mod mcp;
+pub mod reindex_docs;
 pub mod restart;
 pub mod start;
 pub mod status;
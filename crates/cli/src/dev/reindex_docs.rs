@@ -0,0 +1,56 @@
+use clap::Args;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use crate::run_cli_async_helper;
+use apx_core::databricks_sdk_doc::{SDKSource, fetch_latest_sdk_version};
+use apx_core::interop::get_databricks_sdk_version;
+use apx_core::search::HashingEmbedder;
+use apx_core::search::docs_index::SDKDocsIndex;
+use apx_db::DevDb;
+
+#[derive(Args, Debug, Clone)]
+pub struct ReindexDocsArgs {
+    #[arg(
+        value_name = "APP_PATH",
+        help = "The path to the app used to detect the installed SDK version. Defaults to current working directory"
+    )]
+    pub app_path: Option<PathBuf>,
+    /// Databricks SDK version to index (defaults to the app's installed version, then latest)
+    #[arg(long = "sdk-version", value_name = "VERSION")]
+    pub sdk_version: Option<String>,
+    /// Seed a new version's index from this previously indexed version
+    #[arg(long = "from-version", value_name = "VERSION")]
+    pub from_version: Option<String>,
+}
+
+pub async fn run(args: ReindexDocsArgs) -> i32 {
+    run_cli_async_helper(|| run_inner(args)).await
+}
+
+async fn run_inner(args: ReindexDocsArgs) -> Result<(), String> {
+    let version = match args.sdk_version {
+        Some(version) => version,
+        None => match get_databricks_sdk_version(args.app_path.as_deref()).await? {
+            Some(version) => version,
+            None => fetch_latest_sdk_version().await?,
+        },
+    };
+
+    let dev_db = DevDb::open().await?;
+    let mut index = SDKDocsIndex::new(dev_db.pool().clone())
+        .with_embedder(Arc::new(HashingEmbedder::default()));
+    let stats = index
+        .reindex_incremental(
+            &SDKSource::DatabricksSdkPython,
+            &version,
+            args.from_version.as_deref(),
+        )
+        .await?;
+
+    println!(
+        "Reindexed Databricks SDK docs {version}: {} added, {} updated, {} removed, {} unchanged",
+        stats.added, stats.updated, stats.removed, stats.unchanged
+    );
+    Ok(())
+}
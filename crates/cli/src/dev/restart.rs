@@ -4,7 +4,8 @@ use std::path::PathBuf;
 use crate::common::find_app_dir;
 use crate::run_cli_async_helper;
 use apx_core::common::OutputMode;
-use apx_core::ops::dev::restart_dev_server;
+use apx_core::dev::process::DevService;
+use apx_core::ops::dev::{RestartMode, restart_dev_server, restart_dev_service};
 
 #[derive(Args, Debug, Clone)]
 pub struct RestartArgs {
@@ -18,6 +19,24 @@ pub struct RestartArgs {
         help = "Skip waiting for the dev server to become healthy before returning"
     )]
     pub skip_healthcheck: bool,
+    #[arg(
+        long = "hard",
+        help = "Also restart flux and the embedded database. Flux is shared by all apps, so their log collection pauses briefly"
+    )]
+    pub hard: bool,
+    #[arg(
+        long = "clear-logs",
+        requires = "hard",
+        help = "With --hard, permanently delete this app's stored logs"
+    )]
+    pub clear_logs: bool,
+    #[arg(
+        long = "service",
+        value_enum,
+        conflicts_with = "hard",
+        help = "Restart only this process and leave the others running. Restarting `db` discards its in-memory data"
+    )]
+    pub service: Option<DevService>,
 }
 
 pub async fn run(args: RestartArgs) -> i32 {
@@ -27,6 +46,31 @@ pub async fn run(args: RestartArgs) -> i32 {
 async fn run_inner(args: RestartArgs) -> Result<(), String> {
     let app_dir = find_app_dir(args.app_path)?;
 
-    restart_dev_server(&app_dir, args.skip_healthcheck, OutputMode::Interactive).await?;
+    if let Some(service) = args.service {
+        restart_dev_service(
+            &app_dir,
+            service,
+            args.skip_healthcheck,
+            OutputMode::Interactive,
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let restart_mode = if args.hard {
+        RestartMode::Hard {
+            clear_logs: args.clear_logs,
+        }
+    } else {
+        RestartMode::Soft
+    };
+
+    restart_dev_server(
+        &app_dir,
+        restart_mode,
+        args.skip_healthcheck,
+        OutputMode::Interactive,
+    )
+    .await?;
     Ok(())
 }
@@ -1,11 +1,14 @@
 use clap::Args;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use crate::common::find_app_dir;
 use crate::run_cli_async_helper;
 use apx_core::common::OutputMode;
+use apx_core::dev::common::{PinnedPorts, read_pinned_ports};
 use apx_core::ops::dev::stop_dev_server;
-use apx_core::ops::dev::{ServerLauncher, prepare_server_launch, resolve_existing_server};
+use apx_core::ops::dev::{
+    ServerLauncher, pin_ports, prepare_server_launch, resolve_existing_server, rotate_dev_token,
+};
 
 #[derive(Args, Debug, Clone)]
 pub struct StartArgs {
@@ -37,6 +40,53 @@ pub struct StartArgs {
         help = "Skip waiting for the dev server to become healthy before returning"
     )]
     pub skip_healthcheck: bool,
+    #[arg(
+        long = "frontend-port",
+        value_name = "PORT",
+        help = "Pin the frontend dev server to this port (persisted in .apx/ports.json)"
+    )]
+    pub frontend_port: Option<u16>,
+    #[arg(
+        long = "backend-port",
+        value_name = "PORT",
+        help = "Pin the backend server to this port (persisted in .apx/ports.json)"
+    )]
+    pub backend_port: Option<u16>,
+    #[arg(
+        long = "db-port",
+        value_name = "PORT",
+        help = "Pin the embedded database to this port (persisted in .apx/ports.json)"
+    )]
+    pub db_port: Option<u16>,
+    #[arg(
+        long = "rotate-token",
+        help = "Generate a new dev token instead of reusing the one in .apx/dev.json"
+    )]
+    pub rotate_token: bool,
+}
+
+impl StartArgs {
+    const fn port_overrides(&self) -> PinnedPorts {
+        PinnedPorts {
+            frontend: self.frontend_port,
+            backend: self.backend_port,
+            db: self.db_port,
+        }
+    }
+}
+
+/// Warn about port flags that cannot apply because a dev server is already running.
+fn warn_ignored_ports(app_dir: &Path, requested: PinnedPorts, mode: OutputMode) {
+    if requested.is_empty() {
+        return;
+    }
+    let running = read_pinned_ports(app_dir).unwrap_or_default();
+    for mismatch in running.mismatches(requested) {
+        apx_core::common::emit(
+            mode,
+            &format!("⚠️  {mismatch}. Run `apx dev stop` first to change ports."),
+        );
+    }
 }
 
 pub async fn run(args: StartArgs) -> i32 {
@@ -51,11 +101,12 @@ pub async fn run(args: StartArgs) -> i32 {
 }
 
 async fn run_detached(args: StartArgs) -> Result<(), String> {
-    let app_dir = find_app_dir(args.app_path)?;
+    let app_dir = find_app_dir(args.app_path.clone())?;
     let mode = OutputMode::Interactive;
 
     // Check for existing server first
     if let Some(port) = resolve_existing_server(&app_dir, mode).await? {
+        warn_ignored_ports(&app_dir, args.port_overrides(), mode);
         apx_core::common::emit(
             mode,
             &format!(
@@ -66,6 +117,10 @@ async fn run_detached(args: StartArgs) -> Result<(), String> {
         return Ok(());
     }
 
+    pin_ports(&app_dir, args.port_overrides())?;
+    if args.rotate_token {
+        rotate_dev_token(&app_dir)?;
+    }
     let server = prepare_server_launch(&app_dir, None, mode).await?;
     let launcher = ServerLauncher::Detached {
         app_dir: app_dir.clone(),
@@ -79,21 +134,36 @@ async fn run_detached(args: StartArgs) -> Result<(), String> {
 }
 
 async fn run_attached(args: StartArgs) -> Result<(), String> {
-    let app_dir = find_app_dir(args.app_path)?;
+    let app_dir = find_app_dir(args.app_path.clone())?;
     let mode = OutputMode::Interactive;
 
     // If detached server already running, fall back to log tailing
     if resolve_existing_server(&app_dir, mode).await?.is_some() {
+        warn_ignored_ports(&app_dir, args.port_overrides(), mode);
         let logs_args = super::logs::LogsArgs {
             app_path: Some(app_dir.clone()),
             duration: "10m".to_string(),
+            since: None,
+            until: None,
+            service: None,
+            severity: None,
+            severity_text: None,
+            attributes: Vec::new(),
+            grep: None,
+            ignore_case: false,
+            tail: None,
             follow: true,
+            output_file: None,
         };
         let _ = super::logs::run(logs_args).await;
         stop_dev_server(&app_dir, mode).await?;
         return Ok(());
     }
 
+    pin_ports(&app_dir, args.port_overrides())?;
+    if args.rotate_token {
+        rotate_dev_token(&app_dir)?;
+    }
     let server = prepare_server_launch(&app_dir, None, mode).await?;
     let launcher = ServerLauncher::Attached {
         app_dir: app_dir.clone(),
@@ -1,10 +1,12 @@
 use clap::Args;
-use std::path::PathBuf;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
 
 use crate::common::find_app_dir;
 use crate::run_cli_async_helper;
 use apx_core::dev::client::status as get_status;
 use apx_core::dev::common::{lock_path, read_lock};
+use apx_core::flux::{self, FluxState, FluxStatus};
 use tracing::debug;
 
 #[derive(Args, Debug, Clone)]
@@ -14,6 +16,49 @@ pub struct StatusArgs {
         help = "The path to the app. Defaults to current working directory"
     )]
     pub app_path: Option<PathBuf>,
+    #[arg(long = "json", help = "Print status as JSON")]
+    pub json: bool,
+}
+
+/// Whether the app's dev server is running and answering status requests.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum DevServerState {
+    /// Answering the status endpoint.
+    Running,
+    /// A lockfile exists but the status endpoint cannot be reached.
+    Unreachable,
+    /// No lockfile.
+    NotRunning,
+}
+
+/// State of the app's dev server.
+#[derive(Debug, Serialize)]
+struct DevServerStatus {
+    state: DevServerState,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    port: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    frontend: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    backend: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    db: Option<String>,
+    /// Milliseconds each process took to become ready since its last (re)start.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    frontend_ready_ms: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    backend_ready_ms: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    db_ready_ms: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct StatusReport {
+    dev_server: DevServerStatus,
+    flux: FluxStatus,
 }
 
 pub async fn run(args: StatusArgs) -> i32 {
@@ -23,13 +68,44 @@ pub async fn run(args: StatusArgs) -> i32 {
 async fn run_inner(args: StatusArgs) -> Result<(), String> {
     let app_dir = find_app_dir(args.app_path)?;
 
-    let lock_path = lock_path(&app_dir);
+    let report = StatusReport {
+        dev_server: dev_server_status(&app_dir).await?,
+        flux: flux::status(),
+    };
+
+    if args.json {
+        let json = serde_json::to_string_pretty(&report)
+            .map_err(|e| format!("Failed to serialize status: {e}"))?;
+        println!("{json}");
+    } else {
+        print_report(&report);
+    }
+
+    match report.dev_server.error {
+        Some(err) => Err(err),
+        None => Ok(()),
+    }
+}
+
+async fn dev_server_status(app_dir: &Path) -> Result<DevServerStatus, String> {
+    let lock_path = lock_path(app_dir);
     debug!(path = %lock_path.display(), "Checking for dev server lockfile.");
 
+    let mut status = DevServerStatus {
+        state: DevServerState::NotRunning,
+        port: None,
+        frontend: None,
+        backend: None,
+        db: None,
+        frontend_ready_ms: None,
+        backend_ready_ms: None,
+        db_ready_ms: None,
+        error: None,
+    };
+
     if !lock_path.exists() {
         debug!("No dev server lockfile found.");
-        println!("Dev Server: not running");
-        return Ok(());
+        return Ok(status);
     }
 
     let lock = read_lock(&lock_path)?;
@@ -38,24 +114,145 @@ async fn run_inner(args: StatusArgs) -> Result<(), String> {
         pid = lock.pid,
         "Loaded dev server lockfile."
     );
+    status.port = Some(lock.port);
 
     // Query the health endpoint
     match get_status(lock.port).await {
-        Ok(status) => {
+        Ok(response) => {
+            status.state = DevServerState::Running;
+            status.frontend = Some(response.frontend_status);
+            status.backend = Some(response.backend_status);
+            status.db = Some(response.db_status);
+            status.frontend_ready_ms = response.frontend_ready_ms;
+            status.backend_ready_ms = response.backend_ready_ms;
+            status.db_ready_ms = response.db_ready_ms;
+        }
+        Err(err) => {
+            debug!(error = %err, "Failed to get status from dev server.");
+            status.state = DevServerState::Unreachable;
+            status.error = Some(err.to_string());
+        }
+    }
+    Ok(status)
+}
+
+fn print_report(report: &StatusReport) {
+    let dev = &report.dev_server;
+    match dev.state {
+        DevServerState::Running => {
             println!(
                 "🚀 Dev server is running at http://{}:{}",
                 apx_common::hosts::BROWSER_HOST,
-                lock.port
+                dev.port.unwrap_or_default()
+            );
+            println!(
+                "Frontend: {}",
+                process_line(dev.frontend.as_deref(), dev.frontend_ready_ms)
+            );
+            println!(
+                "Backend: {}",
+                process_line(dev.backend.as_deref(), dev.backend_ready_ms)
+            );
+            println!(
+                "Database: {}",
+                process_line(dev.db.as_deref(), dev.db_ready_ms)
             );
-            println!("Frontend: {}", status.frontend_status);
-            println!("Backend: {}", status.backend_status);
-            Ok(())
         }
-        Err(err) => {
-            debug!(error = %err, "Failed to get status from dev server.");
+        DevServerState::Unreachable => {
             println!("Dev Server: running (but unreachable)");
-            println!("Error: {err}");
-            Err(err.to_string())
+            if let Some(err) = &dev.error {
+                println!("Error: {err}");
+            }
+        }
+        DevServerState::NotRunning => println!("Dev Server: not running"),
+    }
+
+    let flux = &report.flux;
+    match flux.state {
+        FluxState::NotRunning if dev.state == DevServerState::NotRunning => {
+            println!("Flux: not running");
+        }
+        FluxState::NotRunning => println!(
+            "⚠️  Flux: not running — dev server logs are being lost. Start it with `apx flux start`."
+        ),
+        state => {
+            let mut details = Vec::new();
+            if let Some(pid) = flux.pid {
+                details.push(format!("pid {pid}"));
+            }
+            details.push(format!("port {}", flux.port));
+            if let Some(uptime) = flux.uptime_secs {
+                details.push(format!("up {}", format_uptime(uptime)));
+            }
+            if state == FluxState::Unhealthy {
+                details.push("failing health checks".to_string());
+            }
+            println!("Flux: running ({})", details.join(", "));
         }
     }
 }
+
+/// Process status with its startup time when known, e.g. `healthy (ready in 4.2s)`.
+fn process_line(status: Option<&str>, ready_ms: Option<u64>) -> String {
+    let status = status.unwrap_or("unknown");
+    match ready_ms {
+        Some(ms) => format!("{status} (ready in {:.1}s)", ms as f64 / 1000.0),
+        None => status.to_string(),
+    }
+}
+
+/// Format seconds as the two most significant units, e.g. `2h 5m` or `45s`.
+pub(crate) fn format_uptime(secs: i64) -> String {
+    let secs = secs.max(0);
+    let (days, hours, minutes, seconds) = (
+        secs / 86_400,
+        secs % 86_400 / 3_600,
+        secs % 3_600 / 60,
+        secs % 60,
+    );
+    match (days, hours, minutes) {
+        (0, 0, 0) => format!("{seconds}s"),
+        (0, 0, _) => format!("{minutes}m {seconds}s"),
+        (0, _, _) => format!("{hours}h {minutes}m"),
+        _ => format!("{days}d {hours}h"),
+    }
+}
+
+#[cfg(test)]
+// Reason: panicking on failure is idiomatic in tests
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_uptime() {
+        assert_eq!(format_uptime(0), "0s");
+        assert_eq!(format_uptime(45), "45s");
+        assert_eq!(format_uptime(125), "2m 5s");
+        assert_eq!(format_uptime(7_500), "2h 5m");
+        assert_eq!(format_uptime(90_061), "1d 1h");
+        assert_eq!(format_uptime(-5), "0s");
+    }
+
+    #[test]
+    fn test_dev_server_state_serializes_snake_case() {
+        assert_eq!(
+            serde_json::to_value(DevServerState::NotRunning).unwrap(),
+            "not_running"
+        );
+        assert_eq!(
+            serde_json::to_value(DevServerState::Unreachable).unwrap(),
+            "unreachable"
+        );
+    }
+
+    #[test]
+    fn test_process_line() {
+        assert_eq!(
+            process_line(Some("healthy"), Some(4_210)),
+            "healthy (ready in 4.2s)"
+        );
+        assert_eq!(process_line(Some("starting"), None), "starting");
+        assert_eq!(process_line(None, None), "unknown");
+    }
+}
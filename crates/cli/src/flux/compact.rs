@@ -0,0 +1,72 @@
+//! Compact the flux logs database.
+
+use clap::Args;
+use std::path::Path;
+use std::time::Instant;
+
+use crate::run_cli_async_helper;
+use apx_core::common::{format_elapsed_ms, spinner};
+use apx_core::flux;
+use apx_db::LogsDb;
+
+#[derive(Args, Debug, Clone)]
+pub struct CompactArgs {}
+
+pub async fn run(_args: CompactArgs) -> i32 {
+    run_cli_async_helper(run_inner).await
+}
+
+async fn run_inner() -> Result<(), String> {
+    let db_path = apx_db::logs_db_path()?;
+    if !db_path.exists() {
+        println!("⚠️  No logs database found at {}\n", db_path.display());
+        return Ok(());
+    }
+
+    // The daemon holds the database open and writes continuously; compacting
+    // underneath it would race with its inserts. Holding the startup lock keeps
+    // a daemon from starting until compaction is done.
+    let Some(_startup) = flux::try_lock_startup()? else {
+        return Err(
+            "Flux is starting or another compaction is running. Try again in a moment.".to_string(),
+        );
+    };
+    let daemon_running = flux::is_running()
+        || flux::read_lock()?.is_some_and(|lock| flux::is_flux_listening(lock.port));
+    if daemon_running {
+        return Err(
+            "Flux is running. Stop it with `apx flux stop` before compacting the logs database."
+                .to_string(),
+        );
+    }
+
+    let before = db_size(&db_path);
+    let start_time = Instant::now();
+    let compact_spinner = spinner("Compacting logs database...");
+
+    let storage = LogsDb::open_at(&db_path).await?;
+    storage.compact().await?;
+    drop(storage);
+
+    compact_spinner.finish_and_clear();
+    let after = db_size(&db_path);
+    println!(
+        "✅ Compacted {} from {} to {} bytes in {}\n",
+        db_path.display(),
+        before,
+        after,
+        format_elapsed_ms(start_time)
+    );
+    Ok(())
+}
+
+/// Size of the database file plus its write-ahead log, in bytes.
+fn db_size(db_path: &Path) -> u64 {
+    let mut wal = db_path.as_os_str().to_owned();
+    wal.push("-wal");
+    [db_path, Path::new(&wal)]
+        .iter()
+        .filter_map(|path| std::fs::metadata(path).ok())
+        .map(|meta| meta.len())
+        .sum()
+}
@@ -0,0 +1,291 @@
+//! Export stored flux logs as JSON Lines or CSV.
+
+use clap::{Args, ValueEnum};
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::PathBuf;
+
+use crate::common::find_app_dir;
+use crate::run_cli_async_helper;
+use apx_common::LogRecord;
+use apx_core::ops::logs::{DEFAULT_LOG_DURATION, parse_time_bound};
+use apx_db::{LogFilter, LogsDb};
+
+/// Output format for `flux export`.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// One JSON object per line
+    Jsonl,
+    /// Comma-separated values with a header row
+    Csv,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct ExportArgs {
+    #[arg(
+        value_name = "APP_PATH",
+        help = "The path to the app. Defaults to current working directory"
+    )]
+    pub app_path: Option<PathBuf>,
+    #[arg(
+        long = "format",
+        value_enum,
+        default_value = "jsonl",
+        help = "Output format"
+    )]
+    pub format: ExportFormat,
+    #[arg(
+        long = "since",
+        default_value = DEFAULT_LOG_DURATION,
+        hide_default_value = true,
+        value_name = "TIME",
+        help = "Export logs from this time: a duration ago (e.g. 30s, 1h) or an RFC 3339 timestamp. Defaults to the last 10 minutes"
+    )]
+    pub since: String,
+    #[arg(
+        long = "service",
+        value_name = "NAME",
+        help = "Only export logs from this service"
+    )]
+    pub service: Option<String>,
+    #[arg(
+        long = "attribute",
+        value_name = "KEY",
+        help = "Log or resource attribute to add as a CSV column (repeatable)"
+    )]
+    pub attributes: Vec<String>,
+    #[arg(
+        short = 'o',
+        long = "output-file",
+        value_name = "PATH",
+        help = "Write to a file instead of stdout"
+    )]
+    pub output_file: Option<PathBuf>,
+}
+
+pub async fn run(args: ExportArgs) -> i32 {
+    run_cli_async_helper(|| run_async(args)).await
+}
+
+async fn run_async(args: ExportArgs) -> Result<(), String> {
+    let app_dir = find_app_dir(args.app_path.clone())?;
+    let app_path_canonical = app_dir
+        .canonicalize()
+        .unwrap_or_else(|_| app_dir.clone())
+        .display()
+        .to_string();
+
+    let db_path = apx_db::logs_db_path()?;
+    if !db_path.exists() {
+        return Err(format!("No logs database found at {}", db_path.display()));
+    }
+    let storage = LogsDb::open_read_only(&db_path)
+        .await
+        .map_err(|e| format!("Failed to open logs database: {e}"))?;
+
+    let since_ns = parse_time_bound(&args.since)?;
+
+    if let Some(output_file) = &args.output_file {
+        let file = File::create(output_file)
+            .map_err(|e| format!("Failed to create {}: {e}", output_file.display()))?;
+        let count = export(
+            &storage,
+            &app_path_canonical,
+            since_ns,
+            &args,
+            BufWriter::new(file),
+        )
+        .await?;
+        println!(
+            "✅ Exported {count} log record(s) to {}",
+            output_file.display()
+        );
+        Ok(())
+    } else {
+        export(
+            &storage,
+            &app_path_canonical,
+            since_ns,
+            &args,
+            BufWriter::new(io::stdout()),
+        )
+        .await
+        .map(|_| ())
+    }
+}
+
+/// Stream matching records into `out`, returning how many were written.
+async fn export<W: Write>(
+    storage: &LogsDb,
+    app_path: &str,
+    since_ns: i64,
+    args: &ExportArgs,
+    mut out: W,
+) -> Result<usize, String> {
+    let write_err = |e: io::Error| format!("Failed to write export: {e}");
+    let filter = LogFilter {
+        since_ns,
+        service: args.service.clone(),
+        ..LogFilter::default()
+    };
+
+    if args.format == ExportFormat::Csv {
+        writeln!(out, "{}", csv_header(&args.attributes)).map_err(write_err)?;
+    }
+    let count = storage
+        .export_logs(app_path, &filter, |batch| {
+            for record in batch {
+                let line = match args.format {
+                    ExportFormat::Jsonl => jsonl_line(record),
+                    ExportFormat::Csv => csv_row(record, &args.attributes),
+                };
+                writeln!(out, "{line}").map_err(write_err)?;
+            }
+            Ok(())
+        })
+        .await?;
+    out.flush().map_err(write_err)?;
+    Ok(count)
+}
+
+/// Serialize a record as a single JSON Lines entry.
+fn jsonl_line(record: &LogRecord) -> String {
+    record.to_json().to_string()
+}
+
+const CSV_COLUMNS: [&str; 7] = [
+    "timestamp",
+    "severity",
+    "service",
+    "body",
+    "trace_id",
+    "span_id",
+    "repeat_count",
+];
+
+fn csv_header(attributes: &[String]) -> String {
+    CSV_COLUMNS
+        .iter()
+        .copied()
+        .chain(attributes.iter().map(String::as_str))
+        .map(csv_field)
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+fn csv_row(record: &LogRecord, attributes: &[String]) -> String {
+    let fixed = [
+        record.timestamp_rfc3339(),
+        record.severity_label().to_string(),
+        record.service_name.clone().unwrap_or_default(),
+        record.body.clone().unwrap_or_default(),
+        record.trace_id.clone().unwrap_or_default(),
+        record.span_id.clone().unwrap_or_default(),
+        record.repeat_count.to_string(),
+    ];
+    fixed
+        .into_iter()
+        .chain(
+            attributes
+                .iter()
+                .map(|key| attribute_value(record, key).unwrap_or_default()),
+        )
+        .map(|value| csv_field(&value))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Quote a CSV field when it contains a delimiter, quote or line break (RFC 4180).
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Look up an OTLP attribute by key, preferring log attributes over resource attributes.
+fn attribute_value(record: &LogRecord, key: &str) -> Option<String> {
+    [&record.log_attributes, &record.resource_attributes]
+        .into_iter()
+        .filter_map(|raw| raw.as_deref())
+        .filter_map(|raw| serde_json::from_str::<Vec<serde_json::Value>>(raw).ok())
+        .flatten()
+        .find(|attr| attr.get("key").and_then(serde_json::Value::as_str) == Some(key))
+        .and_then(|attr| attr.get("value").map(any_value_to_string))
+}
+
+/// Render an OTLP `AnyValue` as plain text, falling back to its JSON form.
+fn any_value_to_string(value: &serde_json::Value) -> String {
+    ["stringValue", "intValue", "doubleValue", "boolValue"]
+        .iter()
+        .find_map(|field| value.get(field))
+        .map_or_else(
+            || value.to_string(),
+            |v| {
+                v.as_str()
+                    .map_or_else(|| v.to_string(), ToString::to_string)
+            },
+        )
+}
+
+#[cfg(test)]
+// Reason: panicking on failure is idiomatic in tests
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    fn record() -> LogRecord {
+        LogRecord {
+            timestamp_ns: 1_767_225_600_000_000_000,
+            observed_timestamp_ns: 1_767_225_600_000_000_000,
+            severity_number: Some(17),
+            severity_text: Some("ERROR".to_string()),
+            body: Some("failed: \"db\", retrying\nstack".to_string()),
+            service_name: Some("demo_app".to_string()),
+            app_path: Some("/tmp/app".to_string()),
+            resource_attributes: Some(
+                r#"[{"key":"service.name","value":{"stringValue":"demo_app"}}]"#.to_string(),
+            ),
+            log_attributes: Some(
+                r#"[{"key":"http.status","value":{"intValue":"500"}},{"key":"retry","value":{"boolValue":true}}]"#
+                    .to_string(),
+            ),
+            ..LogRecord::default()
+        }
+    }
+
+    #[test]
+    fn test_csv_quotes_and_attributes() {
+        let attributes = vec![
+            "http.status".to_string(),
+            "retry".to_string(),
+            "service.name".to_string(),
+            "missing".to_string(),
+        ];
+        assert_eq!(
+            csv_header(&attributes),
+            "timestamp,severity,service,body,trace_id,span_id,repeat_count,http.status,retry,service.name,missing"
+        );
+        let repeated = LogRecord {
+            repeat_count: 3,
+            ..record()
+        };
+        assert_eq!(
+            csv_row(&repeated, &attributes),
+            "2026-01-01T00:00:00.000000000Z,ERROR,demo_app,\"failed: \"\"db\"\", retrying\nstack\",,,3,500,true,demo_app,"
+        );
+    }
+
+    #[test]
+    fn test_jsonl_parses_attributes() {
+        let line = jsonl_line(&record());
+        assert!(!line.contains('\n'));
+        let value: serde_json::Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(value["timestamp"], "2026-01-01T00:00:00.000000000Z");
+        assert_eq!(value["service_name"], "demo_app");
+        assert_eq!(value["log_attributes"][0]["key"], "http.status");
+        assert_eq!(value["trace_id"], serde_json::Value::Null);
+        assert_eq!(value["repeat_count"], 1);
+    }
+}
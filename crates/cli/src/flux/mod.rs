@@ -1,2 +1,7 @@
+pub mod compact;
+pub mod export;
+pub mod restart;
 pub mod start;
+pub mod status;
 pub mod stop;
+pub mod trace;
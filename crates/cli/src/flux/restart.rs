@@ -0,0 +1,31 @@
+//! Restart the flux OTEL collector daemon.
+
+use clap::Args;
+use std::time::Instant;
+
+use crate::run_cli_async_helper;
+use apx_core::common::{format_elapsed_ms, spinner};
+use apx_core::flux;
+
+#[derive(Args, Debug, Clone)]
+pub struct RestartArgs {}
+
+pub async fn run(_args: RestartArgs) -> i32 {
+    run_cli_async_helper(run_inner).await
+}
+
+async fn run_inner() -> Result<(), String> {
+    let start_time = Instant::now();
+    let restart_spinner = spinner("Restarting flux daemon...");
+
+    let result = flux::restart_async().await;
+    restart_spinner.finish_and_clear();
+    result?;
+
+    println!(
+        "✅ Flux restarted at http://127.0.0.1:{} in {}\n",
+        flux::FLUX_PORT,
+        format_elapsed_ms(start_time)
+    );
+    Ok(())
+}
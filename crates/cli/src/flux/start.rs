@@ -27,7 +27,7 @@ async fn run_inner() -> Result<(), String> {
     let start_time = Instant::now();
     let start_spinner = spinner("Starting flux daemon...");
 
-    flux::start()?;
+    flux::start_async().await?;
 
     start_spinner.finish_and_clear();
     println!(
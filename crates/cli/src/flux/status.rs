@@ -0,0 +1,133 @@
+//! Show the state of the flux OTEL collector daemon.
+
+use clap::Args;
+use serde::Serialize;
+
+use crate::dev::status::format_uptime;
+use apx_core::flux::{self, FluxState, FluxStatus};
+use apx_db::LogsDb;
+use tracing::debug;
+
+#[derive(Args, Debug, Clone)]
+pub struct StatusArgs {
+    #[arg(long = "json", help = "Print status as JSON")]
+    pub json: bool,
+}
+
+/// State of the flux daemon and its logs database.
+#[derive(Debug, Serialize)]
+struct StatusReport {
+    #[serde(flatten)]
+    flux: FluxStatus,
+    db_path: String,
+    /// Stored log records, or `None` when the database is missing or unreadable.
+    row_count: Option<i64>,
+}
+
+impl StatusReport {
+    /// Exit code for scripts: 0 only when flux is running and healthy.
+    fn exit_code(&self) -> i32 {
+        i32::from(self.flux.state != FluxState::Healthy)
+    }
+}
+
+pub async fn run(args: StatusArgs) -> i32 {
+    let status = match flux_status().await {
+        Ok(status) => status,
+        Err(err) => {
+            eprintln!("{err}");
+            return 1;
+        }
+    };
+
+    if args.json {
+        match serde_json::to_string_pretty(&status) {
+            Ok(json) => println!("{json}"),
+            Err(e) => {
+                eprintln!("Failed to serialize status: {e}");
+                return 1;
+            }
+        }
+    } else {
+        print_status(&status);
+    }
+    status.exit_code()
+}
+
+async fn flux_status() -> Result<StatusReport, String> {
+    let flux = flux::status();
+
+    let db_path = apx_db::logs_db_path()?;
+    let row_count = if db_path.exists() {
+        match LogsDb::open_read_only(&db_path).await {
+            Ok(storage) => storage.count_logs().await.ok(),
+            Err(e) => {
+                debug!("Failed to open logs database: {e}");
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    Ok(StatusReport {
+        flux,
+        db_path: db_path.display().to_string(),
+        row_count,
+    })
+}
+
+fn print_status(status: &StatusReport) {
+    let flux = &status.flux;
+    match flux.state {
+        FluxState::Healthy => println!("✅ Flux is running on port {}", flux.port),
+        FluxState::Unhealthy => println!(
+            "⚠️  Flux is listening on port {} but failing health checks. Restart it with `apx flux restart`.",
+            flux.port
+        ),
+        FluxState::NotRunning => println!("Flux is not running. Start it with `apx flux start`."),
+    }
+    if let Some(pid) = flux.pid {
+        println!("PID: {pid}");
+    }
+    if let Some(uptime) = flux.uptime_secs {
+        println!("Uptime: {}", format_uptime(uptime));
+    }
+    if let Some(version) = &flux.version {
+        println!("Version: {version}");
+    }
+    println!("Database: {}", status.db_path);
+    match status.row_count {
+        Some(count) => println!("Stored logs: {count}"),
+        None => println!("Stored logs: unknown"),
+    }
+}
+
+#[cfg(test)]
+// Reason: panicking on failure is idiomatic in tests
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exit_code_reflects_health() {
+        let status = |state| StatusReport {
+            flux: FluxStatus {
+                state,
+                pid: None,
+                port: flux::FLUX_PORT,
+                uptime_secs: None,
+                version: None,
+            },
+            db_path: String::new(),
+            row_count: None,
+        };
+        assert_eq!(status(FluxState::Healthy).exit_code(), 0);
+        assert_eq!(status(FluxState::Unhealthy).exit_code(), 1);
+        assert_eq!(status(FluxState::NotRunning).exit_code(), 1);
+
+        let json = serde_json::to_value(status(FluxState::NotRunning)).unwrap();
+        assert_eq!(json["state"], "not_running");
+        assert_eq!(json["port"], flux::FLUX_PORT);
+    }
+}
@@ -23,7 +23,7 @@ async fn run_inner() -> Result<(), String> {
     let start_time = Instant::now();
     let stop_spinner = spinner("Stopping flux daemon...");
 
-    flux::stop()?;
+    flux::stop_async().await?;
 
     stop_spinner.finish_and_clear();
     println!("✅ Flux stopped in {}\n", format_elapsed_ms(start_time));
@@ -0,0 +1,245 @@
+//! Print the stored logs of one trace as a timeline grouped by span.
+//!
+//! Flux stores logs only, so spans are reconstructed from the `span_id` of
+//! their logs: each group shows the span's service, its offset from the start
+//! of the trace and the time between its first and last log.
+
+use clap::Args;
+use std::fmt::Write;
+use std::path::PathBuf;
+
+use crate::common::find_app_dir;
+use crate::run_cli_async_helper;
+use apx_common::{LogRecord, ServiceKind};
+use apx_db::LogsDb;
+
+const BOLD: &str = "\x1b[1m";
+const DIM: &str = "\x1b[2m";
+const RED: &str = "\x1b[31m";
+const YELLOW: &str = "\x1b[33m";
+const RESET: &str = "\x1b[0m";
+
+/// Indentation of log lines below a span header.
+const LOG_INDENT: &str = "      ";
+
+#[derive(Args, Debug, Clone)]
+pub struct TraceArgs {
+    #[arg(value_name = "TRACE_ID", help = "Hex ID of the trace to show")]
+    pub trace_id: String,
+    #[arg(
+        value_name = "APP_PATH",
+        help = "The path to the app. Defaults to current working directory"
+    )]
+    pub app_path: Option<PathBuf>,
+    #[arg(long = "no-color", help = "Print without ANSI colors")]
+    pub no_color: bool,
+}
+
+pub async fn run(args: TraceArgs) -> i32 {
+    run_cli_async_helper(|| run_async(args)).await
+}
+
+async fn run_async(args: TraceArgs) -> Result<(), String> {
+    let app_dir = find_app_dir(args.app_path.clone())?;
+    let app_path_canonical = app_dir
+        .canonicalize()
+        .unwrap_or_else(|_| app_dir.clone())
+        .display()
+        .to_string();
+
+    let db_path = apx_db::logs_db_path()?;
+    if !db_path.exists() {
+        return Err(format!("No logs database found at {}", db_path.display()));
+    }
+    let storage = LogsDb::open_read_only(&db_path)
+        .await
+        .map_err(|e| format!("Failed to open logs database: {e}"))?;
+
+    let trace_id = args.trace_id.trim();
+    let records = storage
+        .query_logs_by_trace(&app_path_canonical, trace_id)
+        .await?;
+    if records.is_empty() {
+        return Err(format!("No logs found for trace {trace_id}"));
+    }
+    print!("{}", render_timeline(trace_id, &records, !args.no_color));
+    Ok(())
+}
+
+/// Logs sharing one `span_id` (or having none), in time order.
+struct SpanGroup<'a> {
+    span_id: Option<&'a str>,
+    records: Vec<&'a LogRecord>,
+}
+
+/// Group time-ordered records by span, ordering groups by their first log.
+fn group_by_span(records: &[LogRecord]) -> Vec<SpanGroup<'_>> {
+    let mut groups: Vec<SpanGroup<'_>> = Vec::new();
+    for record in records {
+        let span_id = record.span_id.as_deref().filter(|id| !id.is_empty());
+        match groups.iter_mut().find(|g| g.span_id == span_id) {
+            Some(group) => group.records.push(record),
+            None => groups.push(SpanGroup {
+                span_id,
+                records: vec![record],
+            }),
+        }
+    }
+    groups
+}
+
+/// Render the timeline of a trace's logs.
+///
+/// ```text
+/// Trace 4bf92f… 3 log(s), 2 span(s), 120.0ms
+///   ▸ span 00f067aa web +0.0ms (12.5ms)
+///       +0.0ms INFO  request started
+///       +12.5ms ERROR boom
+///   ▸ span 9c1e2f3a api +100.0ms (0.0ms)
+///       +100.0ms WARN  slow query
+/// ```
+///
+/// Logs without any span ID are listed directly below the header.
+fn render_timeline(trace_id: &str, records: &[LogRecord], colorize: bool) -> String {
+    let paint = |color: &str, text: &str| {
+        if colorize {
+            format!("{color}{text}{RESET}")
+        } else {
+            text.to_string()
+        }
+    };
+    let start = records.iter().map(effective_ts).min().unwrap_or(0);
+    let end = records.iter().map(effective_ts).max().unwrap_or(start);
+    let groups = group_by_span(records);
+    let spans = groups.iter().filter(|g| g.span_id.is_some()).count();
+
+    let mut out = format!(
+        "{} {} log(s), {spans} span(s), {}\n",
+        paint(BOLD, &format!("Trace {trace_id}")),
+        records.len(),
+        format_duration_ns(end - start)
+    );
+
+    let log_line = |out: &mut String, record: &LogRecord, indent: &str| {
+        let level = record.severity_label().to_uppercase();
+        let level_color = match level.as_str() {
+            "ERROR" | "FATAL" => RED,
+            "WARN" => YELLOW,
+            _ => DIM,
+        };
+        let body = record
+            .body
+            .as_deref()
+            .unwrap_or("")
+            .replace('\n', &format!("\n{indent}  "));
+        let _ = writeln!(
+            out,
+            "{indent}+{} {} {body}",
+            format_duration_ns(effective_ts(record) - start),
+            paint(level_color, &format!("{level:<5}")),
+        );
+    };
+
+    if spans == 0 {
+        for record in records {
+            log_line(&mut out, record, "  ");
+        }
+        return out;
+    }
+
+    for group in &groups {
+        let first = group.records.first().map_or(start, |r| effective_ts(r));
+        let last = group.records.last().map_or(first, |r| effective_ts(r));
+        let service = group
+            .records
+            .iter()
+            .find_map(|r| r.service_name.as_deref())
+            .unwrap_or("unknown");
+        let label = group
+            .span_id
+            .map_or_else(|| "no span".to_string(), |id| format!("span {id}"));
+        let _ = writeln!(
+            out,
+            "  ▸ {} {} +{} ({})",
+            paint(BOLD, &label),
+            paint(
+                ServiceKind::from_service_name(service).ansi_color(),
+                service
+            ),
+            format_duration_ns(first - start),
+            format_duration_ns(last - first)
+        );
+        for record in &group.records {
+            log_line(&mut out, record, LOG_INDENT);
+        }
+    }
+    out
+}
+
+const fn effective_ts(record: &LogRecord) -> i64 {
+    if record.timestamp_ns == 0 {
+        record.observed_timestamp_ns
+    } else {
+        record.timestamp_ns
+    }
+}
+
+/// Format a nanosecond duration as milliseconds with one decimal, e.g. `12.5ms`.
+fn format_duration_ns(ns: i64) -> String {
+    let ns = ns.max(0);
+    format!("{}.{}ms", ns / 1_000_000, ns % 1_000_000 / 100_000)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(offset_us: i64, span_id: Option<&str>, service: &str, level: &str) -> LogRecord {
+        LogRecord {
+            timestamp_ns: 1_000_000_000 + offset_us * 1_000,
+            severity_text: Some(level.to_string()),
+            body: Some(format!("{service} {level}")),
+            service_name: Some(service.to_string()),
+            trace_id: Some("abc".to_string()),
+            span_id: span_id.map(ToString::to_string),
+            ..LogRecord::default()
+        }
+    }
+
+    #[test]
+    fn test_render_groups_logs_by_span() {
+        let records = [
+            record(0, Some("s1"), "web", "info"),
+            record(12_500, Some("s2"), "api", "warn"),
+            record(20_000, Some("s1"), "web", "error"),
+            record(120_000, None, "web", "debug"),
+        ];
+        assert_eq!(
+            render_timeline("abc", &records, false),
+            "Trace abc 4 log(s), 2 span(s), 120.0ms
+  ▸ span s1 web +0.0ms (20.0ms)
+      +0.0ms INFO  web info
+      +20.0ms ERROR web error
+  ▸ span s2 api +12.5ms (0.0ms)
+      +12.5ms WARN  api warn
+  ▸ no span web +120.0ms (0.0ms)
+      +120.0ms DEBUG web debug
+"
+        );
+    }
+
+    #[test]
+    fn test_render_without_spans_lists_logs() {
+        let records = [
+            record(0, None, "web", "info"),
+            record(1_000, Some(""), "web", "error"),
+        ];
+        assert_eq!(
+            render_timeline("abc", &records, false),
+            "Trace abc 2 log(s), 0 span(s), 1.0ms
+  +0.0ms INFO  web info
+  +1.0ms ERROR web error
+"
+        );
+    }
+}
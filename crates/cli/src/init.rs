@@ -542,7 +542,7 @@ async fn install_addon_components(
         let components_start = Instant::now();
         let sp = spinner("🎨 Adding components...");
 
-        let result = add_components(app_path, &all_components, true).await?;
+        let result = add_components(app_path, &all_components, true, false, None).await?;
 
         sp.finish_and_clear();
         println!(
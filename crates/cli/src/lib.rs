@@ -99,6 +99,8 @@ enum DevCommands {
     Check(dev::check::CheckArgs),
     /// Apply an addon to an existing project
     Apply(dev::apply::ApplyArgs),
+    /// Refresh the Databricks SDK docs search index, re-indexing only changed pages
+    ReindexDocs(dev::reindex_docs::ReindexDocsArgs),
     /// Internal: run dev server
     #[command(name = "__internal__run_server", hide = true)]
     InternalRunServer(dev::__internal_run_server::InternalRunServerArgs),
@@ -116,6 +118,16 @@ enum FluxCommands {
     Start(flux::start::StartArgs),
     /// Stop the flux OTEL collector daemon
     Stop(flux::stop::StopArgs),
+    /// Show whether the flux OTEL collector daemon is running and healthy
+    Status(flux::status::StatusArgs),
+    /// Restart the flux OTEL collector daemon, e.g. to pick up a new apx version
+    Restart(flux::restart::RestartArgs),
+    /// Rebuild indexes and reclaim free space in the flux logs database
+    Compact(flux::compact::CompactArgs),
+    /// Export stored logs as JSON Lines or CSV
+    Export(flux::export::ExportArgs),
+    /// Show the stored logs of one trace as a timeline grouped by span
+    Trace(flux::trace::TraceArgs),
 }
 
 /// Standard Unix exit code for processes terminated by SIGINT (128 + signal number 2).
@@ -200,11 +212,17 @@ async fn run_command(args: Vec<String>) -> i32 {
             DevCommands::Logs(args) => dev::logs::run(args).await,
             DevCommands::Check(args) => dev::check::run(args).await,
             DevCommands::Apply(args) => dev::apply::run(args).await,
+            DevCommands::ReindexDocs(args) => dev::reindex_docs::run(args).await,
             DevCommands::InternalRunServer(args) => dev::__internal_run_server::run(args).await,
         },
         Some(Commands::Flux(flux_cmd)) => match flux_cmd {
             FluxCommands::Start(args) => flux::start::run(args).await,
             FluxCommands::Stop(args) => flux::stop::run(args).await,
+            FluxCommands::Status(args) => flux::status::run(args).await,
+            FluxCommands::Restart(args) => flux::restart::run(args).await,
+            FluxCommands::Compact(args) => flux::compact::run(args).await,
+            FluxCommands::Export(args) => flux::export::run(args).await,
+            FluxCommands::Trace(args) => flux::trace::run(args).await,
         },
         Some(Commands::Skill(skill_cmd)) => match skill_cmd {
             SkillCommands::Install(args) => skill::install::run(args).await,
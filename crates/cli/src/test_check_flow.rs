@@ -33,11 +33,22 @@ mod tests {
     async fn apx_check(app_path: &Path) {
         let code = crate::dev::check::run(CheckArgs {
             app_path: Some(app_path.to_path_buf()),
+            client: false,
         })
         .await;
         assert_eq!(code, 0, "apx dev check failed (exit code {code})");
     }
 
+    /// Run `apx dev check --client` on the given path, asserting exit code 0.
+    async fn apx_check_client(app_path: &Path) {
+        let code = crate::dev::check::run(CheckArgs {
+            app_path: Some(app_path.to_path_buf()),
+            client: true,
+        })
+        .await;
+        assert_eq!(code, 0, "apx dev check --client failed (exit code {code})");
+    }
+
     /// Run `apx dev apply <addon>` on the given path, asserting exit code 0.
     async fn apx_apply(app_path: &Path, addon: &str) {
         let code = crate::dev::apply::run(ApplyArgs {
@@ -79,6 +90,8 @@ mod tests {
         );
         // Full check: tsc + ty
         apx_check(&app_path).await;
+        // Regenerate the API client from the OpenAPI spec and type-check it
+        apx_check_client(&app_path).await;
 
         // Step 3: Apply each backend addon, checking after each.
         // Addon configs are validated during lifespan (not import), so no env vars needed.
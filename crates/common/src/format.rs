@@ -3,6 +3,8 @@
 //! All user-facing timestamps use the **local** timezone and a consistent pattern.
 //! This module is the single source of truth for log presentation across all APX crates.
 
+use std::borrow::Cow;
+
 use chrono::{Local, TimeZone, Utc};
 
 use crate::{AggregatedRecord, LogRecord, ServiceKind};
@@ -51,18 +53,29 @@ pub fn format_short_timestamp(timestamp_ms: i64) -> String {
 
 /// Format a log record for terminal display.
 ///
-/// Output: `2026-01-28 16:09:02.413 | app | <message>`
+/// Output: `2026-01-28 16:09:02.413 | app | <message>`, with a `(×N)` suffix
+/// when flux collapsed `N` identical lines into the record.
 #[must_use]
 pub fn format_log_record(record: &LogRecord, colorize: bool) -> String {
     let kind = ServiceKind::from_service_name(record.service_name.as_deref().unwrap_or("unknown"));
     format_line(
         &format_timestamp(record.effective_timestamp_ms()),
         kind,
-        record.body.as_deref().unwrap_or(""),
+        &record_message(record),
         colorize,
     )
 }
 
+/// Body of a record, suffixed with its repeat count when it stands for several lines.
+fn record_message(record: &LogRecord) -> Cow<'_, str> {
+    let body = record.body.as_deref().unwrap_or("");
+    if record.repeat_count > 1 {
+        Cow::Owned(format!("{body} (×{})", record.repeat_count))
+    } else {
+        Cow::Borrowed(body)
+    }
+}
+
 /// Format an aggregated record for terminal display.
 #[must_use]
 pub fn format_aggregated_record(agg: &AggregatedRecord, colorize: bool) -> String {
@@ -82,13 +95,12 @@ pub fn format_startup_log(record: &LogRecord) -> String {
     let timestamp = format_timestamp(record.effective_timestamp_ms());
     let kind = ServiceKind::from_service_name(record.service_name.as_deref().unwrap_or("unknown"));
 
-    let severity = record.severity_text.as_deref().unwrap_or("INFO");
-    let channel = match severity.to_uppercase().as_str() {
+    let channel = match record.severity_label().to_uppercase().as_str() {
         "ERROR" | "FATAL" | "CRITICAL" => "err",
         _ => "out",
     };
 
-    let message = record.body.as_deref().unwrap_or("");
+    let message = record_message(record);
     let label = kind.label();
     let color = kind.ansi_color();
 
@@ -140,6 +152,21 @@ pub fn severity_to_number(level: &str) -> u8 {
     }
 }
 
+/// Convert an OTLP severity number to its level name (`TRACE` .. `FATAL`).
+///
+/// Each level spans four numbers, e.g. 13..=16 are all `WARN`.
+#[must_use]
+pub const fn severity_from_number(number: i32) -> &'static str {
+    match number {
+        21.. => "FATAL",
+        17..=20 => "ERROR",
+        13..=16 => "WARN",
+        9..=12 => "INFO",
+        5..=8 => "DEBUG",
+        _ => "TRACE",
+    }
+}
+
 /// Parse severity from a Python/uvicorn log line.
 ///
 /// Matches patterns like:
@@ -190,6 +217,31 @@ mod tests {
         assert_eq!(severity_to_number("unknown"), 9);
     }
 
+    #[test]
+    fn test_format_log_record_shows_repeats() {
+        let mut record = LogRecord {
+            severity_number: Some(17),
+            body: Some("connection refused".to_string()),
+            service_name: Some("myapp_app".to_string()),
+            ..LogRecord::default()
+        };
+        assert!(format_log_record(&record, false).ends_with("| app | connection refused"));
+
+        record.repeat_count = 142;
+        assert!(format_log_record(&record, false).ends_with("| app | connection refused (×142)"));
+    }
+
+    #[test]
+    fn test_severity_from_number() {
+        assert_eq!(severity_from_number(1), "TRACE");
+        assert_eq!(severity_from_number(8), "DEBUG");
+        assert_eq!(severity_from_number(9), "INFO");
+        assert_eq!(severity_from_number(14), "WARN");
+        assert_eq!(severity_from_number(20), "ERROR");
+        assert_eq!(severity_from_number(24), "FATAL");
+        assert_eq!(severity_from_number(0), "TRACE");
+    }
+
     #[test]
     fn test_parse_python_severity_uvicorn() {
         assert_eq!(
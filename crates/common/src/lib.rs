@@ -14,6 +14,7 @@ pub mod storage;
 
 use serde::{Deserialize, Serialize};
 use std::fs;
+use std::io::{Read, Write};
 use std::net::TcpStream;
 use std::path::PathBuf;
 use std::time::Duration;
@@ -30,9 +31,25 @@ pub const VERSION: &str = env!("CARGO_PKG_VERSION");
 /// Flux port for OTLP HTTP receiver
 pub const FLUX_PORT: u16 = 11111;
 
+/// Environment variable holding the bearer token flux requires on its OTLP and
+/// query endpoints. Unset or empty leaves them open on localhost.
+pub const FLUX_TOKEN_ENV: &str = "APX_FLUX_TOKEN";
+
+/// Bearer token from `APX_FLUX_TOKEN`, if set to a non-empty value.
+#[must_use]
+pub fn flux_token() -> Option<String> {
+    std::env::var(FLUX_TOKEN_ENV)
+        .ok()
+        .map(|token| token.trim().to_string())
+        .filter(|token| !token.is_empty())
+}
+
 /// Lock filename
 const LOCK_FILENAME: &str = "agent.lock";
 
+/// Lock filename serializing daemon startup with maintenance of the logs database
+const STARTUP_LOCK_FILENAME: &str = "agent.startup.lock";
+
 /// Log filename for daemon output
 const LOG_FILENAME: &str = "agent.log";
 
@@ -138,6 +155,55 @@ pub fn remove_lock() -> Result<(), String> {
     Ok(())
 }
 
+/// Exclusive hold on the flux startup lock, released when dropped.
+///
+/// Flux startup takes it while spawning the daemon, and maintenance such as
+/// `apx flux compact` holds it for the whole operation, so a daemon cannot come
+/// up halfway through and write to the database underneath it. The lock is
+/// advisory and released by the OS if the holder dies.
+#[derive(Debug)]
+pub struct StartupLock {
+    _file: fs::File,
+}
+
+/// Open the startup lock file, creating it and its directory if needed.
+fn open_startup_lock() -> Result<fs::File, String> {
+    let dir = flux_dir()?;
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create lock directory: {e}"))?;
+    fs::OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(dir.join(STARTUP_LOCK_FILENAME))
+        .map_err(|e| format!("Failed to open flux startup lock: {e}"))
+}
+
+/// Take the flux startup lock, waiting for the current holder to release it.
+///
+/// # Errors
+///
+/// Returns an error if the lock file cannot be opened or locked.
+pub fn lock_startup() -> Result<StartupLock, String> {
+    let file = open_startup_lock()?;
+    file.lock()
+        .map_err(|e| format!("Failed to take flux startup lock: {e}"))?;
+    Ok(StartupLock { _file: file })
+}
+
+/// Take the flux startup lock, or return `None` when another process holds it.
+///
+/// # Errors
+///
+/// Returns an error if the lock file cannot be opened or locked.
+pub fn try_lock_startup() -> Result<Option<StartupLock>, String> {
+    let file = open_startup_lock()?;
+    match file.try_lock() {
+        Ok(()) => Ok(Some(StartupLock { _file: file })),
+        Err(fs::TryLockError::WouldBlock) => Ok(None),
+        Err(fs::TryLockError::Error(e)) => Err(format!("Failed to take flux startup lock: {e}")),
+    }
+}
+
 /// Check if flux is accepting connections at the given port.
 #[must_use]
 pub fn is_flux_listening(port: u16) -> bool {
@@ -145,6 +211,37 @@ pub fn is_flux_listening(port: u16) -> bool {
     TcpStream::connect_timeout(&addr, Duration::from_millis(500)).is_ok()
 }
 
+/// Timeout for each step of the [`is_flux_healthy`] probe.
+const HEALTH_PROBE_TIMEOUT: Duration = Duration::from_millis(1500);
+
+/// Check if flux answers `GET /healthz` with `200 OK` at the given port.
+///
+/// Unlike [`is_flux_listening`], this catches a wedged daemon that still holds
+/// the port but can no longer store logs.
+#[must_use]
+pub fn is_flux_healthy(port: u16) -> bool {
+    let addr = std::net::SocketAddr::from((hosts::CLIENT_HOST_OCTETS, port));
+    let Ok(mut stream) = TcpStream::connect_timeout(&addr, HEALTH_PROBE_TIMEOUT) else {
+        return false;
+    };
+    if stream.set_read_timeout(Some(HEALTH_PROBE_TIMEOUT)).is_err()
+        || stream
+            .set_write_timeout(Some(HEALTH_PROBE_TIMEOUT))
+            .is_err()
+    {
+        return false;
+    }
+
+    let request = format!(
+        "GET /healthz HTTP/1.1\r\nHost: {}:{port}\r\nConnection: close\r\n\r\n",
+        hosts::CLIENT_HOST
+    );
+    let mut status_line = [0_u8; 12];
+    stream.write_all(request.as_bytes()).is_ok()
+        && stream.read_exact(&mut status_line).is_ok()
+        && status_line == *b"HTTP/1.1 200"
+}
+
 /// Check if flux is currently running by testing TCP connectivity.
 #[must_use]
 pub fn is_running() -> bool {
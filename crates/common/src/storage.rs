@@ -34,6 +34,33 @@ pub struct LogRecord {
     pub trace_id: Option<String>,
     /// Span identifier within a trace.
     pub span_id: Option<String>,
+    /// Number of identical consecutive records this one stands for (1 unless
+    /// flux collapsed repeats at ingest).
+    pub repeat_count: u32,
+    /// Storage sequence number, increasing in ingest order. Breaks ties between
+    /// records with the same timestamp; 0 for records not yet stored.
+    pub seq: i64,
+}
+
+impl Default for LogRecord {
+    /// A single occurrence with zero timestamps and every optional field unset.
+    fn default() -> Self {
+        Self {
+            timestamp_ns: 0,
+            observed_timestamp_ns: 0,
+            severity_number: None,
+            severity_text: None,
+            body: None,
+            service_name: None,
+            app_path: None,
+            resource_attributes: None,
+            log_attributes: None,
+            trace_id: None,
+            span_id: None,
+            repeat_count: 1,
+            seq: 0,
+        }
+    }
 }
 
 impl LogRecord {
@@ -42,12 +69,61 @@ impl LogRecord {
     /// tracing bridge logs).
     #[must_use]
     pub const fn effective_timestamp_ms(&self) -> i64 {
-        let ns = if self.timestamp_ns == 0 {
+        self.effective_timestamp_ns() / 1_000_000
+    }
+
+    /// Return the effective timestamp in nanoseconds, with the same fallback as
+    /// [`Self::effective_timestamp_ms`].
+    #[must_use]
+    pub const fn effective_timestamp_ns(&self) -> i64 {
+        if self.timestamp_ns == 0 {
             self.observed_timestamp_ns
         } else {
             self.timestamp_ns
+        }
+    }
+
+    /// Effective timestamp as RFC 3339 in UTC with nanosecond precision.
+    #[must_use]
+    pub fn timestamp_rfc3339(&self) -> String {
+        chrono::DateTime::from_timestamp_nanos(self.effective_timestamp_ns())
+            .to_rfc3339_opts(chrono::SecondsFormat::Nanos, true)
+    }
+
+    /// Serialize the record as a single JSON object, as written by log exports.
+    /// Attributes are parsed back into JSON (`null` when missing or invalid).
+    #[must_use]
+    pub fn to_json(&self) -> serde_json::Value {
+        let attributes = |raw: Option<&str>| {
+            raw.and_then(|s| serde_json::from_str::<serde_json::Value>(s).ok())
+                .unwrap_or(serde_json::Value::Null)
         };
-        ns / 1_000_000
+        serde_json::json!({
+            "timestamp": self.timestamp_rfc3339(),
+            "timestamp_ns": self.timestamp_ns,
+            "observed_timestamp_ns": self.observed_timestamp_ns,
+            "severity_number": self.severity_number,
+            "severity_text": self.severity_text,
+            "service_name": self.service_name,
+            "app_path": self.app_path,
+            "body": self.body,
+            "trace_id": self.trace_id,
+            "span_id": self.span_id,
+            "repeat_count": self.repeat_count,
+            "seq": self.seq,
+            "resource_attributes": attributes(self.resource_attributes.as_deref()),
+            "log_attributes": attributes(self.log_attributes.as_deref()),
+        })
+    }
+
+    /// Severity level for display: `severity_text` when present, otherwise the
+    /// level name derived from `severity_number` (INFO when both are missing).
+    #[must_use]
+    pub fn severity_label(&self) -> &str {
+        match self.severity_text.as_deref() {
+            Some(text) if !text.is_empty() => text,
+            _ => crate::format::severity_from_number(self.severity_number.unwrap_or(9)),
+        }
     }
 
     /// Derive a short source label from `service_name`.
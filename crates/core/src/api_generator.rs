@@ -15,9 +15,35 @@ use crate::dev::watcher::{PollingWatcher, spawn_polling_watcher};
 use crate::external::uv::Uv;
 use crate::interop::generate_openapi_spec;
 use crate::openapi;
+use crate::openapi::{GenerateResult, GenerateWarning};
+
+/// Outcome of generating the TypeScript client for a project.
+#[derive(Debug, Clone)]
+pub struct GeneratedClient {
+    /// Non-fatal warnings reported by the generator.
+    pub warnings: Vec<GenerateWarning>,
+    /// Names of the generated operations (e.g. `listItems`).
+    pub operations: Vec<String>,
+    /// Whether any output file was written; `false` when the content was already up to date.
+    pub changed: bool,
+    /// Paths of the generated TypeScript files.
+    pub files: Vec<PathBuf>,
+}
+
+/// Generate the OpenAPI spec and TypeScript client for a project.
+///
+/// Returns the non-fatal warnings reported by the generator.
+pub async fn generate_openapi(project_root: &Path) -> Result<Vec<GenerateWarning>, String> {
+    generate_openapi_client(project_root)
+        .await
+        .map(|client| client.warnings)
+}
 
 /// Generate the OpenAPI spec and TypeScript client for a project.
-pub async fn generate_openapi(project_root: &Path) -> Result<(), String> {
+///
+/// Output files are only rewritten when their content changes, so an
+/// unchanged spec doesn't trigger bundler reloads.
+pub async fn generate_openapi_client(project_root: &Path) -> Result<GeneratedClient, String> {
     let metadata = read_project_metadata(project_root)?;
     let app_slug = metadata.app_slug.clone();
     let app_entrypoint = metadata.app_entrypoint.clone();
@@ -43,7 +69,14 @@ pub async fn generate_openapi(project_root: &Path) -> Result<(), String> {
 
     // Generate TypeScript code from OpenAPI spec
     debug!("Generating TypeScript API client from OpenAPI spec.");
-    let ts_code = openapi::generate(&spec_json)?;
+    let GenerateResult {
+        code: ts_code,
+        warnings,
+        msw_handlers,
+        operations,
+        modules,
+        examples,
+    } = openapi::generate_detailed(&spec_json, &metadata.api_options)?;
 
     // Ensure the output directory exists
     if let Some(parent) = api_ts_path.parent() {
@@ -51,18 +84,128 @@ pub async fn generate_openapi(project_root: &Path) -> Result<(), String> {
             .map_err(|err| format!("Failed to create api.ts directory: {err}"))?;
     }
 
-    // Write the generated TypeScript code
-    fs::write(&api_ts_path, &ts_code).map_err(|err| format!("Failed to write api.ts: {err}"))?;
+    // Write the generated TypeScript code. Split modules go into `lib/api/`, whose
+    // `index.ts` keeps `@/lib/api` imports working once the single file is gone.
+    let mut files = Vec::new();
+    let mut changed = if let Some(modules) = modules {
+        let api_dir = api_ts_path.with_extension("");
+        fs::create_dir_all(&api_dir)
+            .map_err(|err| format!("Failed to create api directory: {err}"))?;
+        let mut changed = false;
+        for (name, content) in &modules {
+            let path = api_dir.join(name);
+            changed |= write_if_changed(&path, content)?;
+            files.push(path);
+        }
+        if api_ts_path.exists() {
+            fs::remove_file(&api_ts_path)
+                .map_err(|err| format!("Failed to remove api.ts: {err}"))?;
+            changed = true;
+        }
+        changed
+    } else {
+        files.push(api_ts_path.clone());
+        write_if_changed(&api_ts_path, &ts_code)?
+    };
+
+    // Write the MSW handlers next to the client, which they import types from
+    if let Some(handlers) = msw_handlers {
+        let path = api_ts_path.with_file_name("handlers.ts");
+        changed |= write_if_changed(&path, &handlers)?;
+        files.push(path);
+    }
+    if let Some(examples) = examples {
+        let path = api_ts_path.with_file_name("examples.ts");
+        changed |= write_if_changed(&path, &examples)?;
+        files.push(path);
+    }
 
     debug!(
         api_ts_path = %api_ts_path.display(),
         ts_code_len = ts_code.len(),
+        warnings = warnings.len(),
+        changed,
         "TypeScript API client generated successfully."
     );
 
+    Ok(GeneratedClient {
+        warnings,
+        operations,
+        changed,
+        files,
+    })
+}
+
+/// Write `content` to `path` unless the file already holds exactly that content.
+///
+/// Returns whether the file was written.
+fn write_if_changed(path: &Path, content: &str) -> Result<bool, String> {
+    if fs::read_to_string(path).is_ok_and(|existing| existing == content) {
+        return Ok(false);
+    }
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    fs::write(path, content).map_err(|err| format!("Failed to write {name}: {err}"))?;
+    Ok(true)
+}
+
+/// Regenerate the TypeScript client whenever a Python file in the project changes.
+///
+/// Generates once up front, then again once a burst of changes has been quiet
+/// for [`OPENAPI_WATCH_DEBOUNCE_MS`]. Every outcome, including failures, is
+/// passed to `on_generate` and the watch keeps going. Returns when the
+/// filesystem watcher stops.
+pub async fn watch_openapi(
+    project_root: &Path,
+    mut on_generate: impl FnMut(Result<GeneratedClient, String>),
+) -> Result<(), String> {
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    let mut notify_watcher = notify::recommended_watcher(move |result| {
+        let _ = tx.send(result);
+    })
+    .map_err(|err| format!("Failed to create file watcher: {err}"))?;
+    notify_watcher
+        .watch(project_root, RecursiveMode::Recursive)
+        .map_err(|err| format!("Failed to watch app dir: {err}"))?;
+
+    on_generate(generate_openapi_client(project_root).await);
+    while let Some(result) = rx.recv().await {
+        if !touches_python(&result) {
+            continue;
+        }
+        // Wait until a debounce period passes without further Python changes
+        loop {
+            tokio::time::sleep(Duration::from_millis(OPENAPI_WATCH_DEBOUNCE_MS)).await;
+            let mut more = false;
+            while let Ok(result) = rx.try_recv() {
+                more |= touches_python(&result);
+            }
+            if !more {
+                break;
+            }
+        }
+        on_generate(generate_openapi_client(project_root).await);
+    }
     Ok(())
 }
 
+/// Whether a filesystem event changes a non-ignored Python file.
+fn touches_python(result: &notify::Result<notify::Event>) -> bool {
+    match result {
+        // Importing the app reads its modules; only writes should trigger a run
+        Ok(event) => {
+            !matches!(event.kind, notify::EventKind::Access(_))
+                && event
+                    .paths
+                    .iter()
+                    .any(|path| !is_ignored_path(path) && is_python_path(path))
+        }
+        Err(err) => {
+            warn!("OpenAPI watcher error: {err}");
+            false
+        }
+    }
+}
+
 /// Debounce period after a Python file change before regenerating the OpenAPI spec.
 /// Prevents rapid-fire regeneration during batch saves.
 const OPENAPI_WATCH_DEBOUNCE_MS: u64 = 100;
@@ -223,6 +366,10 @@ fn log_generation_result(
     match output {
         Ok(Ok(result)) if result.status.success() => {
             let stdout = String::from_utf8_lossy(&result.stdout);
+            let stderr = String::from_utf8_lossy(&result.stderr);
+            for warning in stderr.lines().filter_map(|l| l.strip_prefix("warning: ")) {
+                warn!("OpenAPI {label}: {warning}");
+            }
             if is_initial {
                 if stdout.contains("regenerated") {
                     info!("Initial OpenAPI generated successfully");
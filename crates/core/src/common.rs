@@ -11,6 +11,7 @@ use tokio::process::Command;
 
 use crate::api_generator::generate_openapi;
 use crate::external::{Bun, Uv};
+use crate::openapi::GenerateOptions;
 use crate::python_logging::{DevConfig, parse_dev_config};
 
 // Re-exports for ergonomic access from other crates.
@@ -66,6 +67,8 @@ pub struct ProjectMetadata {
     pub ui_registries: Option<HashMap<String, String>>,
     /// Dev server configuration parsed from `[tool.apx.dev]`.
     pub dev_config: DevConfig,
+    /// TypeScript client options parsed from `[tool.apx.api]`.
+    pub api_options: GenerateOptions,
 }
 
 impl ProjectMetadata {
@@ -134,6 +137,7 @@ pub fn read_project_metadata(project_root: &Path) -> Result<ProjectMetadata, Str
 
     // Parse dev configuration
     let dev_config = parse_dev_config(&pyproject_value, project_root)?;
+    let api_options = GenerateOptions::from_pyproject(&pyproject_value)?;
 
     Ok(ProjectMetadata {
         app_name,
@@ -144,6 +148,7 @@ pub fn read_project_metadata(project_root: &Path) -> Result<ProjectMetadata, Str
         ui_root,
         ui_registries,
         dev_config,
+        api_options,
     })
 }
 
@@ -106,11 +106,19 @@ pub struct AddComponentsResult {
 /// - Installing npm dependencies (once, batched)
 /// - Updating CSS with required variables/rules
 ///
+/// When `refresh` is set, registry items and indexes are re-downloaded instead of
+/// being served from the local cache (the cache is updated with the new data).
+///
+/// When `registry_file` is set, registries are also read from that local JSON file
+/// (see [`super::load_registry_file`]).
+///
 /// The caller is responsible for console output (spinners, success messages, etc.)
 pub async fn add_components(
     app_dir: &Path,
     components: &[ComponentInput],
     force: bool,
+    refresh: bool,
+    registry_file: Option<&Path>,
 ) -> Result<AddComponentsResult, String> {
     if components.is_empty() {
         return Ok(AddComponentsResult::default());
@@ -141,7 +149,16 @@ pub async fn add_components(
             (input.registry.clone(), input.name.clone())
         };
 
-        let plan = plan_add(&client, app_dir, &cfg, registry.as_deref(), &component_name).await?;
+        let plan = plan_add(
+            &client,
+            app_dir,
+            &cfg,
+            registry.as_deref(),
+            &component_name,
+            refresh,
+            registry_file,
+        )
+        .await?;
 
         // Deduplicate files across components
         for file in plan.files_to_write {
@@ -212,7 +229,7 @@ pub async fn add_components(
     }
 
     // Sync registry indexes silently
-    let _ = sync_registry_indexes(app_dir, false).await;
+    let _ = sync_registry_indexes(app_dir, refresh).await;
 
     Ok(result)
 }
@@ -242,6 +259,80 @@ fn read_package_json_deps(app_dir: &Path) -> HashSet<String> {
     deps
 }
 
+/// Name of the temporary tsconfig written by [`typecheck_files`].
+const TYPECHECK_TSCONFIG: &str = "tsconfig.apx-components.json";
+
+/// Type-check component files with `tsc --noEmit` in the context of the project.
+///
+/// See [`typecheck_files`].
+pub async fn typecheck_component_files(app_dir: &Path, files: &[PathBuf]) -> Result<(), String> {
+    typecheck_files(app_dir, files, "added components").await
+}
+
+/// Type-check TypeScript files with `tsc --noEmit` in the context of the project.
+///
+/// A temporary tsconfig extending the project's compiler options is written to `app_dir`
+/// and restricted to `files`, so pre-existing errors elsewhere in the project are not
+/// reported. Requires `typescript` to be installed in the project's `node_modules`.
+/// `what` names the files in the error message.
+pub async fn typecheck_files(app_dir: &Path, files: &[PathBuf], what: &str) -> Result<(), String> {
+    let sources: Vec<String> = files
+        .iter()
+        .filter(|path| {
+            path.extension()
+                .and_then(|ext| ext.to_str())
+                .is_some_and(|ext| matches!(ext, "ts" | "tsx"))
+        })
+        .map(|path| path.display().to_string())
+        .collect();
+    if sources.is_empty() {
+        return Ok(());
+    }
+
+    // Vite projects keep their compiler options in tsconfig.app.json and use
+    // tsconfig.json only for project references.
+    let mut config = serde_json::json!({
+        "compilerOptions": {
+            "noEmit": true,
+            "composite": false,
+            "incremental": false,
+        },
+        "include": [],
+        "files": sources,
+    });
+    if let Some(base) = ["tsconfig.app.json", "tsconfig.json"]
+        .into_iter()
+        .find(|name| app_dir.join(name).exists())
+    {
+        config["extends"] = serde_json::Value::String(format!("./{base}"));
+    }
+
+    let config_path = app_dir.join(TYPECHECK_TSCONFIG);
+    let contents = serde_json::to_string_pretty(&config)
+        .map_err(|e| format!("Failed to serialize {TYPECHECK_TSCONFIG}: {e}"))?;
+    std::fs::write(&config_path, contents)
+        .map_err(|e| format!("Failed to write {}: {e}", config_path.display()))?;
+
+    let output = match Bun::new().await {
+        Ok(bun) => bun
+            .run_script(app_dir, "tsc", &["-p", TYPECHECK_TSCONFIG])
+            .await
+            .map_err(|e| format!("Failed to run tsc: {e}")),
+        Err(e) => Err(e),
+    };
+    let _ = std::fs::remove_file(&config_path);
+    let output = output?;
+
+    if output.exit_code == Some(0) {
+        Ok(())
+    } else {
+        Err(format!(
+            "Type check failed for {what}:\n{}{}",
+            output.stdout, output.stderr
+        ))
+    }
+}
+
 /// Install npm packages via bun into the project.
 pub async fn bun_add(app_dir: &Path, deps: &[String]) -> Result<(), String> {
     if deps.is_empty() {
@@ -257,3 +348,53 @@ pub async fn bun_add(app_dir: &Path, deps: &[String]) -> Result<(), String> {
 
     Ok(())
 }
+
+#[cfg(test)]
+// Reason: panicking on failure is idiomatic in tests
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    /// Create a minimal project with `typescript` installed (requires bun).
+    async fn typescript_project() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("package.json"),
+            r#"{ "name": "apx-components-typecheck", "private": true, "devDependencies": { "typescript": "^5" } }"#,
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("tsconfig.json"),
+            r#"{ "compilerOptions": { "strict": true, "skipLibCheck": true, "target": "ES2020", "module": "ESNext", "moduleResolution": "bundler", "jsx": "preserve" } }"#,
+        )
+        .unwrap();
+        let bun = Bun::new().await.expect("bun is required for this test");
+        bun.install(dir.path())
+            .await
+            .expect("bun install failed")
+            .check("bun")
+            .expect("bun install failed");
+        dir
+    }
+
+    #[tokio::test]
+    async fn test_typecheck_reports_invalid_component() {
+        let project = typescript_project().await;
+        let ui_dir = project.path().join("src/components/ui");
+        std::fs::create_dir_all(&ui_dir).unwrap();
+
+        let valid = ui_dir.join("valid.ts");
+        std::fs::write(&valid, "export const size: number = 1;\n").unwrap();
+        typecheck_component_files(project.path(), &[valid])
+            .await
+            .expect("valid component should type-check");
+
+        let broken = ui_dir.join("broken.tsx");
+        std::fs::write(&broken, "export const Broken = (: number) => ;\n").unwrap();
+        let err = typecheck_component_files(project.path(), &[broken])
+            .await
+            .unwrap_err();
+        assert!(err.contains("broken.tsx"), "unexpected error: {err}");
+        assert!(!project.path().join(TYPECHECK_TSCONFIG).exists());
+    }
+}
@@ -1,59 +1,27 @@
 use super::models::{RegistryCatalogEntry, RegistryConfig, RegistryItem, UiConfig};
+use super::{FetchError, check_status, fetch_with_retry};
 use crate::common::read_project_metadata;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
-use std::future::Future;
 use std::hash::BuildHasher;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::time::{SystemTime, UNIX_EPOCH};
 use tokio::sync::Mutex;
 use tracing::warn;
 
 /// Current cache format version
 const CACHE_VERSION: u8 = 2;
 
-/// Cache TTL in hours
+/// Cache TTL in hours for registry indexes and the registry catalog
 const CACHE_TTL_HOURS: i64 = 1;
 
-/// Retry configuration for HTTP requests
-const MAX_RETRIES: u32 = 5;
-const INITIAL_DELAY_MS: u64 = 125;
+/// Cache TTL in hours for individual registry items
+const COMPONENT_CACHE_TTL_HOURS: i64 = 24;
 
-/// Execute an async operation with exponential backoff retry.
-///
-/// Retries up to 5 times with delays: 125ms, 250ms, 500ms, 1000ms, 2000ms (~4 seconds total).
-async fn fetch_with_retry<T, F, Fut>(operation: F, operation_name: &str) -> Result<T, String>
-where
-    F: Fn() -> Fut,
-    Fut: Future<Output = Result<T, String>>,
-{
-    let mut last_error = String::new();
-    for attempt in 0..MAX_RETRIES {
-        match operation().await {
-            Ok(result) => return Ok(result),
-            Err(e) => {
-                last_error = e;
-                if attempt < MAX_RETRIES - 1 {
-                    let delay = INITIAL_DELAY_MS * (1 << attempt);
-                    warn!(
-                        attempt = attempt + 1,
-                        max_retries = MAX_RETRIES,
-                        delay_ms = delay,
-                        operation = operation_name,
-                        error = %last_error,
-                        "HTTP request failed, retrying"
-                    );
-                    tokio::time::sleep(Duration::from_millis(delay)).await;
-                }
-            }
-        }
-    }
-    Err(format!(
-        "{operation_name}: {last_error} (after {MAX_RETRIES} retries)"
-    ))
-}
+/// Environment variable overriding all registry cache TTLs (in hours)
+const CACHE_TTL_ENV: &str = "APX_REGISTRY_CACHE_TTL_HOURS";
 
 /// Cached component item
 #[derive(Debug, Serialize, Deserialize)]
@@ -94,6 +62,12 @@ pub struct RegistryIndexItem {
     /// Other registry components this item depends on.
     #[serde(default, rename = "registryDependencies")]
     pub registry_dependencies: Vec<String>,
+    /// Item type (e.g. `registry:ui`, `registry:hook`).
+    #[serde(default, rename = "type")]
+    pub item_type: Option<String>,
+    /// Registry-defined categories.
+    #[serde(default)]
+    pub categories: Vec<String>,
 }
 
 /// Get the base cache directory path
@@ -156,6 +130,29 @@ fn get_component_cache_path(
     Ok(registry_dir.join(filename))
 }
 
+/// Resolve a cache TTL, honoring the APX_REGISTRY_CACHE_TTL_HOURS override.
+fn cache_ttl_hours(default_hours: i64) -> i64 {
+    parse_ttl_hours(std::env::var(CACHE_TTL_ENV).ok().as_deref(), default_hours)
+}
+
+/// Parse a TTL override, falling back to `default_hours` when unset or invalid.
+///
+/// Negative values and values whose length in seconds overflows an `i64` are
+/// rejected as invalid.
+fn parse_ttl_hours(value: Option<&str>, default_hours: i64) -> i64 {
+    match value.map(|v| v.trim().parse::<i64>()) {
+        Some(Ok(hours)) if hours >= 0 && hours.checked_mul(3600).is_some() => hours,
+        Some(_) => {
+            warn!(
+                "Ignoring invalid {CACHE_TTL_ENV} value {:?}, using {default_hours}h",
+                value.unwrap_or_default()
+            );
+            default_hours
+        }
+        None => default_hours,
+    }
+}
+
 /// Check if a cache file is fresh based on mtime
 fn is_file_fresh(path: &Path, ttl_hours: i64) -> bool {
     if !path.exists() {
@@ -210,8 +207,11 @@ pub fn load_cached_component(
         return Ok(None);
     }
 
-    // Check if cache is fresh (24 hour TTL for registry items)
-    if !is_cache_fresh(cached.fetched_at, 24) {
+    // Check if cache is fresh (24 hour default TTL for registry items)
+    if !is_cache_fresh(
+        cached.fetched_at,
+        cache_ttl_hours(COMPONENT_CACHE_TTL_HOURS),
+    ) {
         return Ok(None);
     }
 
@@ -264,7 +264,7 @@ pub fn save_cached_component(
 /// Load cached registry catalog (shadcn directory)
 pub fn load_cached_registry_catalog() -> Result<Option<Vec<RegistryCatalogEntry>>, String> {
     let cache_path = get_registries_catalog_path()?;
-    if !is_file_fresh(&cache_path, CACHE_TTL_HOURS) {
+    if !is_file_fresh(&cache_path, cache_ttl_hours(CACHE_TTL_HOURS)) {
         return Ok(None);
     }
     let content = fs::read_to_string(&cache_path).map_err(|e| e.to_string())?;
@@ -304,7 +304,7 @@ fn load_cached_registry_index(
     registry_name: Option<&str>,
 ) -> Result<Option<Vec<RegistryIndexItem>>, String> {
     let cache_path = get_registry_index_path(registry_name)?;
-    if !is_file_fresh(&cache_path, CACHE_TTL_HOURS) {
+    if !is_file_fresh(&cache_path, cache_ttl_hours(CACHE_TTL_HOURS)) {
         return Ok(None);
     }
     let content = fs::read_to_string(&cache_path).map_err(|e| e.to_string())?;
@@ -375,9 +375,10 @@ async fn fetch_and_cache_registry_index(
     registry_name: Option<&str>,
     registry_config: Option<&RegistryConfig>,
     style: &str,
+    refresh: bool,
 ) -> Result<Vec<RegistryIndexItem>, String> {
-    // Check cache first
-    if let Ok(Some(items)) = load_cached_registry_index(registry_name) {
+    // Check cache first (unless a refresh was requested)
+    if !refresh && let Ok(Some(items)) = load_cached_registry_index(registry_name) {
         tracing::debug!("Using cached registry index for {:?}", registry_name);
         return Ok(items);
     }
@@ -392,16 +393,15 @@ async fn fetch_and_cache_registry_index(
         || {
             let url = url.clone();
             async move {
-                client
-                    .get(&url)
-                    .send()
-                    .await
-                    .map_err(|e| format!("Failed to fetch registry index {url}: {e}"))?
-                    .error_for_status()
-                    .map_err(|e| format!("Registry index {url} returned error: {e}"))?
+                let response = client.get(&url).send().await.map_err(|e| {
+                    FetchError::other(format!("Failed to fetch registry index {url}: {e}"))
+                })?;
+                check_status(response, "Registry index")?
                     .json::<serde_json::Value>()
                     .await
-                    .map_err(|e| format!("Invalid JSON from registry index {url}: {e}"))
+                    .map_err(|e| {
+                        FetchError::other(format!("Invalid JSON from registry index {url}: {e}"))
+                    })
             }
         },
         &format!("fetch registry index from {url}"),
@@ -443,6 +443,19 @@ async fn fetch_and_cache_registry_index(
                                 .collect()
                         })
                         .unwrap_or_default(),
+                    item_type: item
+                        .get("type")
+                        .and_then(|v| v.as_str())
+                        .map(|s| s.to_string()),
+                    categories: item
+                        .get("categories")
+                        .and_then(|v| v.as_array())
+                        .map(|arr| {
+                            arr.iter()
+                                .filter_map(|v| v.as_str().map(|s| s.to_string()))
+                                .collect()
+                        })
+                        .unwrap_or_default(),
                 })
             })
             .collect(),
@@ -461,20 +474,21 @@ async fn fetch_and_cache_registry_index(
     Ok(items)
 }
 
-/// Check if any registry.json files need refresh (older than 1 hour)
+/// Check if any registry.json files need refresh (older than the cache TTL, 1 hour by default)
 pub fn needs_registry_refresh<S: BuildHasher>(
     registries: &HashMap<String, RegistryConfig, S>,
 ) -> bool {
+    let ttl_hours = cache_ttl_hours(CACHE_TTL_HOURS);
     // Check default registry
     if let Ok(path) = get_registry_index_path(None)
-        && !is_file_fresh(&path, CACHE_TTL_HOURS)
+        && !is_file_fresh(&path, ttl_hours)
     {
         return true;
     }
     // Check custom registries
     for registry_name in registries.keys() {
         if let Ok(path) = get_registry_index_path(Some(registry_name))
-            && !is_file_fresh(&path, CACHE_TTL_HOURS)
+            && !is_file_fresh(&path, ttl_hours)
         {
             return true;
         }
@@ -489,13 +503,14 @@ pub async fn sync_registry_indexes(app_dir: &Path, force: bool) -> Result<bool,
     let cfg = UiConfig::from_metadata(&metadata, app_dir)?;
     let client = reqwest::Client::new();
     let style = cfg.style();
+    let ttl_hours = cache_ttl_hours(CACHE_TTL_HOURS);
     let mut refreshed = false;
 
     // Sync default registry index (registry.json only, individual items fetched on-demand)
     let default_path = get_registry_index_path(None)?;
-    if force || !is_file_fresh(&default_path, CACHE_TTL_HOURS) {
+    if force || !is_file_fresh(&default_path, ttl_hours) {
         tracing::debug!("Fetching default registry index");
-        match fetch_and_cache_registry_index(&client, None, None, style).await {
+        match fetch_and_cache_registry_index(&client, None, None, style, force).await {
             Ok(items) => {
                 tracing::debug!("Cached {} items in default registry index", items.len());
                 refreshed = true;
@@ -507,13 +522,14 @@ pub async fn sync_registry_indexes(app_dir: &Path, force: bool) -> Result<bool,
     // Sync custom registry indexes (registry.json only, no item prefetch)
     for (registry_name, registry_config) in &cfg.registries {
         let path = get_registry_index_path(Some(registry_name))?;
-        if force || !is_file_fresh(&path, CACHE_TTL_HOURS) {
+        if force || !is_file_fresh(&path, ttl_hours) {
             tracing::debug!("Fetching registry index for {}", registry_name);
             match fetch_and_cache_registry_index(
                 &client,
                 Some(registry_name),
                 Some(registry_config),
                 style,
+                force,
             )
             .await
             {
@@ -584,3 +600,21 @@ pub type SharedCacheState = Arc<Mutex<CachePopulationState>>;
 pub fn new_cache_state() -> SharedCacheState {
     Arc::new(Mutex::new(CachePopulationState::default()))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ttl_hours() {
+        assert_eq!(parse_ttl_hours(None, 24), 24);
+        assert_eq!(parse_ttl_hours(Some("6"), 24), 6);
+        assert_eq!(parse_ttl_hours(Some(" 0 "), 1), 0);
+        assert_eq!(parse_ttl_hours(Some("-3"), 1), 1);
+        assert_eq!(parse_ttl_hours(Some("soon"), 1), 1);
+        let max_hours = i64::MAX / 3600;
+        assert_eq!(parse_ttl_hours(Some(&max_hours.to_string()), 1), max_hours);
+        assert_eq!(parse_ttl_hours(Some(&(max_hours + 1).to_string()), 1), 1);
+        assert_eq!(parse_ttl_hours(Some("99999999999999999999"), 1), 1);
+    }
+}
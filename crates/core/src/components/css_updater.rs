@@ -1,8 +1,9 @@
-use std::collections::HashSet;
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::fmt;
+use std::ops::Range;
 
 use biome_css_parser::{CssParserOptions, parse_css};
-use biome_css_syntax::CssRoot;
+use biome_css_syntax::{CssAtRule, CssRoot};
 use biome_rowan::AstNode;
 
 /// Errors that can occur while parsing or updating CSS.
@@ -55,14 +56,15 @@ pub enum CssMutation {
     },
 }
 
-/// Append-only CSS updater.
+/// Incremental CSS updater.
 /// Uses Biome CST only to *analyze* what already exists.
-/// Mutations are applied by appending raw text to the original source.
+/// Mutations are applied as text edits on the original source.
 ///
 /// Guarantees:
-/// - no overwrites
 /// - no deletions
-/// - no reformatting
+/// - no reformatting of untouched rules
+/// - `:root` / `.dark` variables that are already defined are kept
+/// - `@theme` mappings and `@keyframes` blocks with a new value are updated in place
 /// - idempotent application
 #[derive(Debug)]
 pub struct CssUpdater {
@@ -73,120 +75,149 @@ pub struct CssUpdater {
 impl CssUpdater {
     /// Parse CSS source into an updater.
     pub fn new(source: &str) -> Result<Self> {
-        let parsed = parse_css(source, CssParserOptions::default());
-
-        if parsed.has_errors() {
-            // Diagnostics are not Display; render via Debug for now.
-            return Err(CssUpdateError::ParseError(format!(
-                "{:?}",
-                parsed.diagnostics()
-            )));
-        }
-
         Ok(Self {
             source: source.to_string(),
-            root: parsed.tree(),
+            root: parse_root(source)?,
         })
     }
 
-    /// Apply mutations (append-only).
+    /// Apply mutations.
     ///
     /// Returns `true` if any mutation changed the document.
     pub fn apply(&mut self, mutations: &[CssMutation]) -> Result<bool> {
-        // Build "what exists" sets once from the original CST…
-        // and keep them updated as we append new content in this run.
-        let mut existing_theme_vars = self.collect_existing_theme_vars();
-        let mut existing_root_vars = self.collect_existing_vars(":root");
-        let mut existing_dark_vars = self.collect_existing_vars(".dark");
-
-        let mut existing_at_rules = self.collect_existing_at_rules();
-
         let mut changed = false;
 
         for mutation in mutations {
-            match mutation {
-                CssMutation::AddCssBlock { at_rule, body } => {
-                    if !existing_at_rules.contains(at_rule) {
-                        self.append_css_block(at_rule, body);
-                        existing_at_rules.insert(at_rule.clone());
-                        changed = true;
-                    }
-                }
-
+            let mutated = match mutation {
+                CssMutation::AddCssBlock { at_rule, body } => self.apply_css_block(at_rule, body),
                 CssMutation::AddCssVars { selector, vars } => {
-                    let target_set: &mut HashSet<String> = match selector.as_str() {
-                        ":root" => &mut existing_root_vars,
-                        ".dark" => &mut existing_dark_vars,
-                        // Under your assumptions, only these two are expected.
-                        // If you later extend selectors, add more buckets here.
-                        _ => {
-                            // Still support it: compute existing vars for this selector once,
-                            // then track additions in a local set for idempotency within the run.
-                            // (We don't persist that set across runs, but your inputs are controlled.)
-                            // For now, just treat as empty and always append if vars non-empty.
-                            // If you want, we can add a HashMap<String, HashSet<String>>.
-                            let mut local = HashSet::new();
-                            let appended = self.append_css_vars_block(selector, vars, &mut local);
-                            if appended {
-                                changed = true;
-                            }
-                            continue;
-                        }
-                    };
-
-                    let appended = self.append_css_vars_block(selector, vars, target_set);
-                    if appended {
-                        changed = true;
-                    }
+                    self.append_css_vars_block(selector, vars)
                 }
+                CssMutation::AddThemeMappings { vars } => self.apply_theme_mappings(vars),
+            };
 
-                CssMutation::AddThemeMappings { vars } => {
-                    let appended = self.append_theme_inline_block(vars, &mut existing_theme_vars);
-                    if appended {
-                        changed = true;
-                    }
-                }
+            // Re-parse so the next mutation sees (and can locate) what this one wrote
+            if mutated {
+                self.root = parse_root(&self.source)?;
+                changed = true;
             }
         }
 
         Ok(changed)
     }
 
+    /// Custom properties set by `mutations` that the document already defines.
+    ///
+    /// [`Self::apply`] never overwrites `:root` / `.dark` variables, so these are the
+    /// variables whose new values would be skipped. Each name is prefixed with its
+    /// selector, and the result is sorted.
+    pub fn defined_vars(&self, mutations: &[CssMutation]) -> Vec<String> {
+        let root_vars = self.collect_existing_vars(":root");
+        let dark_vars = self.collect_existing_vars(".dark");
+
+        let mut defined = BTreeSet::new();
+        for mutation in mutations {
+            let (scope, existing, vars) = match mutation {
+                CssMutation::AddCssVars { selector, vars } if selector == ":root" => {
+                    (":root", &root_vars, vars)
+                }
+                CssMutation::AddCssVars { selector, vars } if selector == ".dark" => {
+                    (".dark", &dark_vars, vars)
+                }
+                CssMutation::AddCssVars { .. }
+                | CssMutation::AddCssBlock { .. }
+                | CssMutation::AddThemeMappings { .. } => continue,
+            };
+            defined.extend(
+                vars.iter()
+                    .filter(|(name, _)| existing.contains(name))
+                    .map(|(name, _)| format!("{scope} {name}")),
+            );
+        }
+        defined.into_iter().collect()
+    }
+
+    /// `@theme` mappings set by `mutations` that the document defines with another value.
+    ///
+    /// [`Self::apply`] replaces these values in place. Each name is prefixed with
+    /// `@theme`, and the result is sorted.
+    pub fn overwritten_theme_vars(&self, mutations: &[CssMutation]) -> Vec<String> {
+        let existing = self.collect_existing_theme_values();
+
+        let mut overwritten = BTreeSet::new();
+        for mutation in mutations {
+            let CssMutation::AddThemeMappings { vars } = mutation else {
+                continue;
+            };
+            overwritten.extend(
+                vars.iter()
+                    .filter(|(name, value)| {
+                        existing
+                            .get(name)
+                            .is_some_and(|range| self.source[range.clone()] != *value)
+                    })
+                    .map(|(name, _)| format!("@theme {name}")),
+            );
+        }
+        overwritten.into_iter().collect()
+    }
+
     /// Consume the updater and return the final CSS source.
     pub fn finish(self) -> String {
         self.source
     }
 
     // ------------------------------------------------------------
-    // Append-only writers
+    // Writers
     // ------------------------------------------------------------
 
-    fn append_css_block(&mut self, at_rule: &str, body: &str) {
-        self.source.push('\n');
-        self.source.push_str(at_rule);
-        self.source.push_str(" {\n");
-        self.source.push_str(body);
-        if !body.ends_with('\n') {
-            self.source.push('\n');
+    /// Add an at-rule block unless an equivalent one already exists.
+    ///
+    /// `@keyframes` are unique by name: a block with the same name but different frames
+    /// is replaced in place.
+    fn apply_css_block(&mut self, at_rule: &str, body: &str) -> bool {
+        let wanted = normalized_rules(body);
+
+        if let Some(name) = keyframes_name(at_rule) {
+            let existing = self
+                .at_rule_ranges("@keyframes")
+                .into_iter()
+                .rev()
+                .find(|range| {
+                    keyframes_name(at_rule_header(&self.source[range.clone()])) == Some(name)
+                });
+            if let Some(range) = existing {
+                if normalized_rules(block_body(&self.source[range.clone()])) == wanted {
+                    return false;
+                }
+                self.source
+                    .replace_range(range, &render_block(at_rule, body));
+                return true;
+            }
+        } else {
+            let header = normalize_whitespace(at_rule);
+            let exists = self.at_rule_ranges("@").into_iter().any(|range| {
+                let text = &self.source[range];
+                normalize_whitespace(at_rule_header(text)) == header
+                    && normalized_rules(block_body(text)) == wanted
+            });
+            if exists {
+                return false;
+            }
         }
-        self.source.push_str("}\n");
+
+        self.source.push('\n');
+        self.source.push_str(&render_block(at_rule, body));
+        self.source.push('\n');
+        true
     }
 
-    fn append_css_vars_block(
-        &mut self,
-        selector: &str,
-        vars: &[(String, String)],
-        existing: &mut HashSet<String>,
-    ) -> bool {
+    fn append_css_vars_block(&mut self, selector: &str, vars: &[(String, String)]) -> bool {
+        let mut existing = self.collect_existing_vars(selector);
         let mut lines = String::new();
         for (k, v) in vars {
-            if !existing.contains(k) {
-                existing.insert(k.clone());
-                lines.push_str("  ");
-                lines.push_str(k);
-                lines.push_str(": ");
-                lines.push_str(v);
-                lines.push_str(";\n");
+            if existing.insert(k.clone()) {
+                push_declaration(&mut lines, k, v);
             }
         }
 
@@ -202,30 +233,42 @@ impl CssUpdater {
         true
     }
 
-    fn append_theme_inline_block(
-        &mut self,
-        vars: &[(String, String)],
-        existing: &mut HashSet<String>,
-    ) -> bool {
+    /// Add `@theme inline` mappings, updating the value of mappings that already exist.
+    fn apply_theme_mappings(&mut self, vars: &[(String, String)]) -> bool {
+        let existing = self.collect_existing_theme_values();
+        let mut added = HashSet::new();
+        let mut updates = Vec::new();
         let mut lines = String::new();
         for (k, v) in vars {
-            if !existing.contains(k) {
-                existing.insert(k.clone());
-                lines.push_str("  ");
-                lines.push_str(k);
-                lines.push_str(": ");
-                lines.push_str(v);
-                lines.push_str(";\n");
+            match existing.get(k) {
+                Some(range) if &self.source[range.clone()] != v.as_str() => {
+                    updates.push((range.clone(), v.as_str()));
+                }
+                Some(_) => {}
+                None => {
+                    if added.insert(k.as_str()) {
+                        push_declaration(&mut lines, k, v);
+                    }
+                }
             }
         }
 
-        if lines.is_empty() {
+        if updates.is_empty() && lines.is_empty() {
             return false;
         }
 
-        self.source.push_str("\n@theme inline {\n");
-        self.source.push_str(&lines);
-        self.source.push_str("}\n");
+        // Replace back to front so earlier ranges stay valid
+        updates.sort_by_key(|(range, _)| std::cmp::Reverse(range.start));
+        updates.dedup_by_key(|(range, _)| range.start);
+        for (range, value) in updates {
+            self.source.replace_range(range, value);
+        }
+
+        if !lines.is_empty() {
+            self.source.push_str("\n@theme inline {\n");
+            self.source.push_str(&lines);
+            self.source.push_str("}\n");
+        }
         true
     }
 
@@ -233,23 +276,18 @@ impl CssUpdater {
     // CST analysis helpers
     // ------------------------------------------------------------
 
-    fn collect_existing_at_rules(&self) -> HashSet<String> {
-        // We only use this for the "at_rule exists" check in AddCssBlock.
-        // We consider an at-rule "existing" if its header text appears as a node's trimmed text.
-        // Under your constraints, this is sufficient and fast.
-        let mut out = HashSet::new();
-        for node in self.root.syntax().descendants() {
-            let t = node.text_trimmed().to_string();
-            if t.starts_with("@layer ")
-                || t.starts_with("@keyframes ")
-                || t.starts_with("@utility ")
-                || t.starts_with("@plugin ")
-                || t.starts_with("@theme")
-            {
-                out.insert(t);
-            }
-        }
-        out
+    /// Byte ranges of the at-rules whose text starts with `prefix`, in document order.
+    fn at_rule_ranges(&self, prefix: &str) -> Vec<Range<usize>> {
+        self.root
+            .syntax()
+            .descendants()
+            .filter(|node| CssAtRule::can_cast(node.kind()))
+            .map(|node| {
+                let range = node.text_trimmed_range();
+                usize::from(range.start())..usize::from(range.end())
+            })
+            .filter(|range| self.source[range.clone()].starts_with(prefix))
+            .collect()
     }
 
     fn collect_existing_vars(&self, selector: &str) -> HashSet<String> {
@@ -278,26 +316,317 @@ impl CssUpdater {
         vars
     }
 
-    fn collect_existing_theme_vars(&self) -> HashSet<String> {
-        let mut vars = HashSet::new();
-
-        for node in self.root.syntax().descendants() {
-            let t = node.text_trimmed().to_string();
-            if !t.starts_with("@theme") {
-                continue;
+    /// Byte range of the value of each custom property declared in an `@theme` block.
+    ///
+    /// When a property is declared more than once, the last declaration wins, as it
+    /// does in the cascade.
+    fn collect_existing_theme_values(&self) -> HashMap<String, Range<usize>> {
+        let mut values = HashMap::new();
+
+        for range in self.at_rule_ranges("@theme") {
+            let text = &self.source[range.clone()];
+            for (name, value) in block_declarations(text) {
+                values.insert(name, range.start + value.start..range.start + value.end);
             }
+        }
 
-            for decl in node.descendants() {
-                let dt = decl.text_trimmed().to_string();
-                if let Some((name, _)) = dt.split_once(':') {
-                    let name = name.trim();
-                    if name.starts_with("--") {
-                        vars.insert(name.to_string());
+        values
+    }
+}
+
+fn parse_root(source: &str) -> Result<CssRoot> {
+    let parsed = parse_css(source, CssParserOptions::default());
+
+    if parsed.has_errors() {
+        // Diagnostics are not Display; render via Debug for now.
+        return Err(CssUpdateError::ParseError(format!(
+            "{:?}",
+            parsed.diagnostics()
+        )));
+    }
+
+    Ok(parsed.tree())
+}
+
+fn push_declaration(out: &mut String, name: &str, value: &str) {
+    out.push_str("  ");
+    out.push_str(name);
+    out.push_str(": ");
+    out.push_str(value);
+    out.push_str(";\n");
+}
+
+fn render_block(at_rule: &str, body: &str) -> String {
+    let mut out = format!("{at_rule} {{\n{body}");
+    if !body.ends_with('\n') {
+        out.push('\n');
+    }
+    out.push('}');
+    out
+}
+
+/// Name of a `@keyframes <name>` at-rule header.
+fn keyframes_name(header: &str) -> Option<&str> {
+    header
+        .strip_prefix("@keyframes")
+        .filter(|rest| rest.starts_with(char::is_whitespace))
+        .map(str::trim)
+}
+
+/// The text of an at-rule before its `{`.
+fn at_rule_header(text: &str) -> &str {
+    text.split_once('{')
+        .map_or(text, |(header, _)| header)
+        .trim()
+}
+
+/// The text between an at-rule's outer braces.
+fn block_body(text: &str) -> &str {
+    match (text.find('{'), text.rfind('}')) {
+        (Some(open), Some(close)) if open < close => &text[open + 1..close],
+        _ => "",
+    }
+}
+
+fn normalize_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Byte ranges of the top-level statements of a block body: `;`-terminated
+/// declarations and `{ ... }` rules.
+///
+/// Braces and semicolons inside comments and strings are ignored, and comments
+/// between statements are not part of either.
+fn top_level_statements(body: &str) -> Vec<Range<usize>> {
+    let mut statements = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0usize;
+    let mut chars = body.char_indices().peekable();
+
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '/' if chars.peek().is_some_and(|&(_, next)| next == '*') => {
+                let end = body[i + 2..]
+                    .find("*/")
+                    .map_or(body.len(), |close| i + 2 + close + 2);
+                while chars.next_if(|&(j, _)| j < end).is_some() {}
+                if depth == 0 && body[start..i].trim().is_empty() {
+                    start = end;
+                }
+            }
+            '"' | '\'' => {
+                let mut escaped = false;
+                for (_, s) in chars.by_ref() {
+                    match s {
+                        _ if escaped => escaped = false,
+                        '\\' => escaped = true,
+                        _ if s == c => break,
+                        _ => {}
                     }
                 }
             }
+            '{' | '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            '}' => {
+                depth = depth.saturating_sub(1);
+                if depth == 0 {
+                    statements.push(start..i + 1);
+                    start = i + 1;
+                }
+            }
+            ';' if depth == 0 => {
+                statements.push(start..i);
+                start = i + 1;
+            }
+            _ => {}
         }
+    }
+    statements.push(start..body.len());
 
-        vars
+    statements
+}
+
+/// Whitespace-normalized top-level statements of a block body, sorted so that blocks
+/// rendered from unordered registry data compare equal.
+fn normalized_rules(body: &str) -> Vec<String> {
+    let mut rules: Vec<String> = top_level_statements(body)
+        .into_iter()
+        .map(|range| normalize_whitespace(&body[range]))
+        .filter(|rule| !rule.is_empty())
+        .collect();
+    rules.sort();
+    rules
+}
+
+/// Custom property declarations in the block of `text`, with the byte range of each
+/// (trimmed) value relative to `text`.
+fn block_declarations(text: &str) -> Vec<(String, Range<usize>)> {
+    let body = block_body(text);
+    let offset = text.find('{').map_or(0, |open| open + 1);
+
+    top_level_statements(body)
+        .into_iter()
+        .filter_map(|range| {
+            let statement = &body[range.clone()];
+            let colon = statement.find(':')?;
+            let name = statement[..colon].trim();
+            let value = &statement[colon + 1..];
+            if !name.starts_with("--") || value.contains('{') || value.trim().is_empty() {
+                return None;
+            }
+            let start = offset + range.start + colon + 1 + (value.len() - value.trim_start().len());
+            Some((name.to_string(), start..start + value.trim().len()))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+// Reason: panicking on failure is idiomatic in tests
+#[allow(clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    const BASE_CSS: &str = "@import \"tailwindcss\";\n\n@theme inline {\n  --color-background: var(--background);\n}\n\n:root {\n  --background: oklch(1 0 0);\n}\n";
+
+    fn vars(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    fn mutations() -> Vec<CssMutation> {
+        vec![
+            CssMutation::AddThemeMappings {
+                vars: vars(&[
+                    ("--color-background", "var(--background)"),
+                    ("--color-sidebar", "var(--sidebar)"),
+                    ("--animate-fade-in", "fade-in 0.2s ease-out"),
+                ]),
+            },
+            CssMutation::AddCssVars {
+                selector: ":root".to_string(),
+                vars: vars(&[("--sidebar", "oklch(0.98 0 0)")]),
+            },
+            CssMutation::AddCssBlock {
+                at_rule: "@keyframes fade-in".to_string(),
+                body: "  from {\n    opacity: 0;\n  }\n  to {\n    opacity: 1;\n  }\n".to_string(),
+            },
+            CssMutation::AddCssBlock {
+                at_rule: "@layer base".to_string(),
+                body: "body {\n  @apply bg-background;\n}".to_string(),
+            },
+        ]
+    }
+
+    fn apply(source: &str, mutations: &[CssMutation]) -> (bool, String) {
+        let mut updater = CssUpdater::new(source).expect("valid CSS");
+        let changed = updater.apply(mutations).expect("mutations apply");
+        (changed, updater.finish())
+    }
+
+    #[test]
+    fn test_apply_twice_is_stable() {
+        let (changed, once) = apply(BASE_CSS, &mutations());
+        assert!(changed);
+        assert_eq!(once.matches("--color-sidebar").count(), 1);
+        assert_eq!(once.matches("@keyframes fade-in").count(), 1);
+        assert_eq!(once.matches("@layer base").count(), 1);
+
+        let (changed, twice) = apply(&once, &mutations());
+        assert!(!changed);
+        assert_eq!(twice, once);
+
+        // Applying the set twice within one run is stable too
+        let doubled: Vec<_> = mutations().into_iter().chain(mutations()).collect();
+        assert_eq!(apply(BASE_CSS, &doubled).1, once);
+    }
+
+    #[test]
+    fn test_conflicting_theme_mapping_updates_in_place() {
+        let mutations = [CssMutation::AddThemeMappings {
+            vars: vars(&[("--color-background", "var(--surface)")]),
+        }];
+        let (changed, out) = apply(BASE_CSS, &mutations);
+        assert!(changed);
+        assert_eq!(
+            out,
+            BASE_CSS.replace(
+                "--color-background: var(--background)",
+                "--color-background: var(--surface)"
+            )
+        );
+        assert!(!apply(&out, &mutations).0);
+    }
+
+    #[test]
+    fn test_keyframes_compare_by_name() {
+        let (_, out) = apply(BASE_CSS, &mutations());
+
+        // Same frames in another order and layout are unchanged
+        let reordered = [CssMutation::AddCssBlock {
+            at_rule: "@keyframes fade-in".to_string(),
+            body: "to { opacity: 1; }\nfrom { opacity: 0; }".to_string(),
+        }];
+        assert!(!apply(&out, &reordered).0);
+
+        // Different frames replace the existing block
+        let changed_frames = [CssMutation::AddCssBlock {
+            at_rule: "@keyframes fade-in".to_string(),
+            body: "  from {\n    opacity: 0.5;\n  }\n  to {\n    opacity: 1;\n  }\n".to_string(),
+        }];
+        let (changed, updated) = apply(&out, &changed_frames);
+        assert!(changed);
+        assert_eq!(updated.matches("@keyframes fade-in").count(), 1);
+        assert_eq!(updated, out.replace("opacity: 0;", "opacity: 0.5;"));
+    }
+
+    #[test]
+    fn test_overwritten_theme_vars_are_reported() {
+        let mutations = [CssMutation::AddThemeMappings {
+            vars: vars(&[
+                ("--color-background", "var(--surface)"),
+                ("--color-sidebar", "var(--sidebar)"),
+            ]),
+        }];
+        let updater = CssUpdater::new(BASE_CSS).expect("valid CSS");
+        assert_eq!(
+            updater.overwritten_theme_vars(&mutations),
+            ["@theme --color-background"]
+        );
+
+        let (_, out) = apply(BASE_CSS, &mutations);
+        let updater = CssUpdater::new(&out).expect("valid CSS");
+        assert!(updater.overwritten_theme_vars(&mutations).is_empty());
+    }
+
+    #[test]
+    fn test_comments_and_strings_do_not_split_statements() {
+        let css = "@theme inline {\n  /* colors; see {docs} */\n  --color-background: var(--background);\n  --font-sans: \"a;b}\", sans-serif; /* trailing */\n}\n";
+        let mutations = [CssMutation::AddThemeMappings {
+            vars: vars(&[
+                ("--color-background", "var(--background)"),
+                ("--font-sans", "\"a;b}\", sans-serif"),
+            ]),
+        }];
+        let (changed, out) = apply(css, &mutations);
+        assert!(!changed, "got:\n{out}");
+
+        assert_eq!(
+            normalized_rules("/* a { b; } */ --x: 1; --y: 'c;d' ;"),
+            ["--x: 1", "--y: 'c;d'"]
+        );
+    }
+
+    #[test]
+    fn test_existing_root_vars_are_kept() {
+        let mutations = [CssMutation::AddCssVars {
+            selector: ":root".to_string(),
+            vars: vars(&[("--background", "white")]),
+        }];
+        assert!(!apply(BASE_CSS, &mutations).0);
+
+        let updater = CssUpdater::new(BASE_CSS).expect("valid CSS");
+        assert_eq!(updater.defined_vars(&mutations), [":root --background"]);
     }
 }
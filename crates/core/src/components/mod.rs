@@ -2,7 +2,7 @@
 pub mod add;
 /// Registry index caching and refresh logic.
 pub mod cache;
-/// Append-only CSS updater for component CSS variables and theme mappings.
+/// Idempotent CSS updater for component CSS variables and theme mappings.
 pub mod css_updater;
 /// Data models for registry items, UI config, and related types.
 pub mod models;
@@ -13,7 +13,8 @@ pub mod utils;
 
 // Re-export models for easier access
 pub use models::{
-    CssRules, RegistryCatalogEntry, RegistryConfig, RegistryFile, RegistryItem, UiConfig,
+    CssRules, ImportAliases, RegistryCatalogEntry, RegistryConfig, RegistryFile, RegistryItem,
+    UiConfig,
 };
 
 // Re-export cache functions
@@ -22,6 +23,7 @@ pub use cache::{
     sync_registry_indexes,
 };
 
+use futures_util::{StreamExt, stream};
 use serde_json::Value;
 use std::collections::{BTreeSet, HashMap, HashSet};
 use std::future::Future;
@@ -32,7 +34,7 @@ use tracing::{debug, warn};
 use url::Url;
 
 use crate::components::css_updater::{CssMutation, CssUpdater};
-use crate::components::models::TailwindConfig;
+use crate::components::models::{RegistryItemType, TailwindConfig};
 
 /// Scan planned component files for 3rd-party npm imports that may not be listed
 /// in the registry spec's `dependencies` array.
@@ -163,31 +165,111 @@ pub const SHADCN_REGISTRY_ITEM_TEMPLATE: &str =
 const MAX_RETRIES: u32 = 5;
 const INITIAL_DELAY_MS: u64 = 125;
 
+/// Upper bound on a delay requested through `Retry-After`.
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(30);
+
+/// Failure of a single attempt made by [`fetch_with_retry`].
+#[derive(Debug)]
+struct FetchError {
+    message: String,
+    /// HTTP status of the response, if one was received.
+    status: Option<reqwest::StatusCode>,
+    /// Delay requested by the response's `Retry-After` header.
+    retry_after: Option<Duration>,
+}
+
+impl FetchError {
+    /// A failure without an HTTP error status (connection or body decoding).
+    fn other(message: String) -> Self {
+        Self {
+            message,
+            status: None,
+            retry_after: None,
+        }
+    }
+
+    /// Whether another attempt could succeed.
+    ///
+    /// Client errors other than `429 Too Many Requests` will fail again, so only
+    /// `429`, server errors and failures without a status are retried.
+    fn is_retryable(&self) -> bool {
+        self.status.is_none_or(|status| {
+            status == reqwest::StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+        })
+    }
+}
+
+/// Pass successful responses through; turn error statuses into a [`FetchError`]
+/// carrying the status and any `Retry-After` delay.
+fn check_status(
+    response: reqwest::Response,
+    context: &str,
+) -> Result<reqwest::Response, FetchError> {
+    let status = response.status();
+    if !status.is_client_error() && !status.is_server_error() {
+        return Ok(response);
+    }
+    let retry_after = response
+        .headers()
+        .get(reqwest::header::RETRY_AFTER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(parse_retry_after);
+    Err(FetchError {
+        message: format!("{context} returned {status} for {}", response.url()),
+        status: Some(status),
+        retry_after,
+    })
+}
+
+/// Parse a `Retry-After` value: either delay seconds or an HTTP date.
+///
+/// Dates in the past yield a zero delay.
+fn parse_retry_after(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let date = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    Some(
+        (date.with_timezone(&chrono::Utc) - chrono::Utc::now())
+            .to_std()
+            .unwrap_or_default(),
+    )
+}
+
 /// Execute an async operation with exponential backoff retry.
 ///
 /// Retries up to 5 times with delays: 125ms, 250ms, 500ms, 1000ms, 2000ms (~4 seconds total).
+/// Errors that cannot succeed on retry (see [`FetchError::is_retryable`]) fail immediately,
+/// and a `Retry-After` header replaces the scheduled delay, capped at 30 seconds.
 async fn fetch_with_retry<T, F, Fut>(operation: F, operation_name: &str) -> Result<T, String>
 where
     F: Fn() -> Fut,
-    Fut: Future<Output = Result<T, String>>,
+    Fut: Future<Output = Result<T, FetchError>>,
 {
     let mut last_error = String::new();
     for attempt in 0..MAX_RETRIES {
         match operation().await {
             Ok(result) => return Ok(result),
+            Err(e) if !e.is_retryable() => {
+                return Err(format!("{operation_name}: {}", e.message));
+            }
             Err(e) => {
-                last_error = e;
+                let delay = e.retry_after.map_or_else(
+                    || Duration::from_millis(INITIAL_DELAY_MS * (1 << attempt)),
+                    |requested| requested.min(MAX_RETRY_AFTER),
+                );
+                last_error = e.message;
                 if attempt < MAX_RETRIES - 1 {
-                    let delay = INITIAL_DELAY_MS * (1 << attempt);
                     warn!(
                         attempt = attempt + 1,
                         max_retries = MAX_RETRIES,
-                        delay_ms = delay,
+                        delay_ms = delay.as_millis(),
                         operation = operation_name,
                         error = %last_error,
                         "HTTP request failed, retrying"
                     );
-                    tokio::time::sleep(Duration::from_millis(delay)).await;
+                    tokio::time::sleep(delay).await;
                 }
             }
         }
@@ -197,12 +279,59 @@ where
     ))
 }
 
+/// Load a registry catalog (same format as `registries.json`) from a local file.
+pub fn load_registry_catalog_file(path: &Path) -> Result<Vec<RegistryCatalogEntry>, String> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read registry file {}: {e}", path.display()))?;
+    serde_json::from_str(&content)
+        .map_err(|e| format!("Invalid registry catalog JSON in {}: {e}", path.display()))
+}
+
+/// Registries supplied through a registry file (`--registry-file`).
+#[derive(Debug, Clone)]
+pub enum RegistryFileContents {
+    /// A JSON array in the `registries.json` format, replacing the upstream catalog.
+    Catalog(Vec<RegistryCatalogEntry>),
+    /// A JSON object mapping registry names to configs, taking precedence over the
+    /// project config.
+    Registries(HashMap<String, RegistryConfig>),
+}
+
+/// Load a registry file, which is either a catalog (JSON array) or a mapping of
+/// registry name to [`RegistryConfig`] (JSON object).
+pub fn load_registry_file(path: &Path) -> Result<RegistryFileContents, String> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read registry file {}: {e}", path.display()))?;
+    let value: Value = serde_json::from_str(&content)
+        .map_err(|e| format!("Invalid JSON in registry file {}: {e}", path.display()))?;
+    if value.is_object() {
+        serde_json::from_value(value)
+            .map(RegistryFileContents::Registries)
+            .map_err(|e| format!("Invalid registry configs in {}: {e}", path.display()))
+    } else {
+        serde_json::from_value(value)
+            .map(RegistryFileContents::Catalog)
+            .map_err(|e| format!("Invalid registry catalog JSON in {}: {e}", path.display()))
+    }
+}
+
 /// Fetch the upstream shadcn registry catalog, using cache when available.
+///
+/// When `refresh` is set the cache is bypassed, but the fresh result is still written back.
+/// When `registry_file` is set the catalog is read from that file instead, touching
+/// neither the cache nor the network.
 pub async fn fetch_registry_catalog_impl(
     client: &reqwest::Client,
+    refresh: bool,
+    registry_file: Option<&Path>,
 ) -> Result<Vec<RegistryCatalogEntry>, String> {
-    // Try cache first
-    if let Ok(Some(catalog)) = cache::load_cached_registry_catalog() {
+    if let Some(path) = registry_file {
+        debug!(path = %path.display(), "Using local registry catalog file");
+        return load_registry_catalog_file(path);
+    }
+
+    // Try cache first (unless a refresh was requested)
+    if !refresh && let Ok(Some(catalog)) = cache::load_cached_registry_catalog() {
         return Ok(catalog);
     }
 
@@ -210,16 +339,14 @@ pub async fn fetch_registry_catalog_impl(
     let url = "https://ui.shadcn.com/r/registries.json";
     let catalog = fetch_with_retry(
         || async {
-            client
-                .get(url)
-                .send()
-                .await
-                .map_err(|e| format!("Failed to fetch registry catalog: {e}"))?
-                .error_for_status()
-                .map_err(|e| format!("Registry catalog returned error: {e}"))?
+            let response =
+                client.get(url).send().await.map_err(|e| {
+                    FetchError::other(format!("Failed to fetch registry catalog: {e}"))
+                })?;
+            check_status(response, "Registry catalog")?
                 .json::<Vec<RegistryCatalogEntry>>()
                 .await
-                .map_err(|e| format!("Invalid registry catalog JSON: {e}"))
+                .map_err(|e| FetchError::other(format!("Invalid registry catalog JSON: {e}")))
         },
         "fetch registry catalog",
     )
@@ -427,14 +554,18 @@ pub fn resolve_component_request(
 }
 
 /// Fetch a single component spec from its resolved request, using cache when available.
+///
+/// When `refresh` is set the cache is bypassed, but the fresh result is still written back.
 pub async fn fetch_component_impl(
     client: &reqwest::Client,
     req: &ResolvedRequest,
     registry_name: Option<&str>,
     component_name: Option<&str>,
+    refresh: bool,
 ) -> Result<(RegistryItem, Vec<String>), String> {
-    // Try cache first if we have component name
-    if let Some(component_name_val) = component_name
+    // Try cache first if we have component name (unless a refresh was requested)
+    if !refresh
+        && let Some(component_name_val) = component_name
         && let Ok(Some((item, warnings))) =
             cache::load_cached_component(component_name_val, registry_name)
     {
@@ -476,14 +607,14 @@ pub(crate) async fn fetch_http_component(
                 for (k, v) in &headers {
                     rb = rb.header(k, v);
                 }
-                rb.send()
+                let response = rb
+                    .send()
                     .await
-                    .map_err(|e| format!("Failed to fetch component: {e}"))?
-                    .error_for_status()
-                    .map_err(|e| format!("Registry returned error: {e}"))?
+                    .map_err(|e| FetchError::other(format!("Failed to fetch component: {e}")))?;
+                check_status(response, "Registry")?
                     .json::<Value>()
                     .await
-                    .map_err(|e| format!("Invalid component spec: {e}"))
+                    .map_err(|e| FetchError::other(format!("Invalid component spec: {e}")))
             }
         },
         &format!("fetch component from {url_str}"),
@@ -525,12 +656,101 @@ async fn fetch_file_component(
     Ok((item, warnings))
 }
 
+/// Maximum number of registry item fetches in flight while resolving a closure.
+const MAX_CONCURRENT_FETCHES: usize = 8;
+
+/// Key identifying a component within the closure (`{registry}::{component}`).
+fn closure_key(registry: Option<&str>, component: &str) -> String {
+    format!("{}::{}", registry.unwrap_or("_default"), component)
+}
+
+/// Fetch every component reachable from the root, one dependency level at a time.
+///
+/// Each level (frontier) is fetched concurrently, capped at `MAX_CONCURRENT_FETCHES`.
+/// Results are keyed by `closure_key`, so completion order does not matter.
+async fn fetch_closure_specs(
+    client: &reqwest::Client,
+    cfg: &UiConfig,
+    registry: Option<&str>,
+    root_component: &str,
+    refresh: bool,
+) -> Result<HashMap<String, (RegistryItem, Vec<String>)>, String> {
+    let mut fetched: HashMap<String, (RegistryItem, Vec<String>)> = HashMap::new();
+    let mut seen: HashSet<String> = HashSet::from([closure_key(registry, root_component)]);
+    let mut frontier: Vec<(Option<String>, String)> = vec![(
+        registry.map(|value| value.to_string()),
+        root_component.to_string(),
+    )];
+
+    while !frontier.is_empty() {
+        let mut requests = Vec::with_capacity(frontier.len());
+        for (current_registry, component) in &frontier {
+            debug!(
+                component = component.as_str(),
+                registry = ?current_registry,
+                "Resolving component in closure"
+            );
+
+            let req = resolve_component_request(cfg, current_registry.as_deref(), component)?;
+
+            debug!(
+                url = req.url.as_str(),
+                headers_count = req.headers.len(),
+                "Resolved component request"
+            );
+            requests.push(req);
+        }
+
+        // `buffered` yields results in input order, keeping error reporting deterministic
+        let results: Vec<_> = stream::iter(frontier.iter().zip(&requests))
+            .map(|((current_registry, component), req)| {
+                fetch_component_impl(
+                    client,
+                    req,
+                    current_registry.as_deref(),
+                    Some(component.as_str()),
+                    refresh,
+                )
+            })
+            .buffered(MAX_CONCURRENT_FETCHES)
+            .collect()
+            .await;
+
+        let mut next_frontier = Vec::new();
+        for ((current_registry, component), result) in frontier.iter().zip(results) {
+            let (spec, warnings) = result?;
+
+            for dep in &spec.registry_dependencies {
+                let (dep_registry, dep_component) =
+                    parse_registry_dependency(dep, current_registry.as_deref());
+                if seen.insert(closure_key(dep_registry.as_deref(), &dep_component)) {
+                    next_frontier.push((dep_registry, dep_component));
+                }
+            }
+
+            fetched.insert(
+                closure_key(current_registry.as_deref(), component),
+                (spec, warnings),
+            );
+        }
+
+        frontier = next_frontier;
+    }
+
+    Ok(fetched)
+}
+
 /// Recursively resolve a component and all its transitive dependencies.
+///
+/// Specs are fetched concurrently up front (see `fetch_closure_specs`); the
+/// dependency walk itself is a serial DFS, so `ordered` is always topological
+/// (dependencies before dependents) and independent of fetch completion order.
 pub async fn resolve_component_closure(
     client: &reqwest::Client,
     cfg: &UiConfig,
     registry: Option<&str>,
     root_component: &str,
+    refresh: bool,
 ) -> Result<Vec<ResolvedComponent>, String> {
     debug!(
         registry = ?registry,
@@ -538,6 +758,16 @@ pub async fn resolve_component_closure(
         "Starting component closure resolution"
     );
 
+    let fetched = fetch_closure_specs(client, cfg, registry, root_component, refresh).await?;
+    order_closure(fetched, registry, root_component)
+}
+
+/// Order fetched closure specs topologically (dependencies before dependents) via a serial DFS.
+fn order_closure(
+    mut fetched: HashMap<String, (RegistryItem, Vec<String>)>,
+    registry: Option<&str>,
+    root_component: &str,
+) -> Result<Vec<ResolvedComponent>, String> {
     #[derive(Clone)]
     enum VisitState {
         Enter,
@@ -555,13 +785,7 @@ pub async fn resolve_component_closure(
     let mut component_deps: BTreeSet<String> = BTreeSet::new();
 
     while let Some((state, current_registry, component)) = stack.pop() {
-        let key = format!(
-            "{}::{}",
-            current_registry
-                .clone()
-                .unwrap_or_else(|| "_default".to_string()),
-            component
-        );
+        let key = closure_key(current_registry.as_deref(), &component);
 
         match state {
             VisitState::Enter => {
@@ -570,31 +794,9 @@ pub async fn resolve_component_closure(
                 }
                 visited.insert(key.clone());
 
-                debug!(
-                    component = component.as_str(),
-                    registry = ?current_registry,
-                    "Resolving component in closure"
-                );
-
-                let req = resolve_component_request(
-                    cfg,
-                    current_registry.as_deref(),
-                    component.as_str(),
-                )?;
-
-                debug!(
-                    url = req.url.as_str(),
-                    headers_count = req.headers.len(),
-                    "Resolved component request"
-                );
-
-                let (spec, warnings) = fetch_component_impl(
-                    client,
-                    &req,
-                    current_registry.as_deref(),
-                    Some(component.as_str()),
-                )
-                .await?;
+                let Some((spec, warnings)) = fetched.remove(&key) else {
+                    return Err(format!("Component {key} was not fetched"));
+                };
 
                 for dep in &spec.dependencies {
                     component_deps.insert(dep.clone());
@@ -605,24 +807,17 @@ pub async fn resolve_component_closure(
                     current_registry.clone(),
                     component.clone(),
                 ));
-                specs.insert(key.clone(), (spec, current_registry.clone(), warnings));
-
-                if let Some((spec, _, _)) = specs.get(&key) {
-                    for dep in &spec.registry_dependencies {
-                        let (dep_registry, dep_component) =
-                            parse_registry_dependency(dep, current_registry.as_deref());
-                        let dep_key = format!(
-                            "{}::{}",
-                            dep_registry
-                                .clone()
-                                .unwrap_or_else(|| "_default".to_string()),
-                            dep_component
-                        );
-                        if !visited.contains(&dep_key) {
-                            stack.push((VisitState::Enter, dep_registry, dep_component));
-                        }
+
+                for dep in &spec.registry_dependencies {
+                    let (dep_registry, dep_component) =
+                        parse_registry_dependency(dep, current_registry.as_deref());
+                    let dep_key = closure_key(dep_registry.as_deref(), &dep_component);
+                    if !visited.contains(&dep_key) {
+                        stack.push((VisitState::Enter, dep_registry, dep_component));
                     }
                 }
+
+                specs.insert(key, (spec, current_registry, warnings));
             }
             VisitState::Exit => {
                 if let Some((spec, spec_registry, warnings)) = specs.remove(&key) {
@@ -648,12 +843,18 @@ pub async fn resolve_component_closure(
 }
 
 /// Build an add-component plan without writing any files.
+///
+/// `registry_file` is loaded with [`load_registry_file`]: a catalog replaces the
+/// upstream catalog, while registry configs override the project's registries
+/// for this plan only.
 pub async fn plan_add(
     client: &reqwest::Client,
     _app_dir: &Path,
     cfg: &UiConfig,
     registry: Option<&str>,
     component: &str,
+    refresh: bool,
+    registry_file: Option<&Path>,
 ) -> Result<AddPlan, String> {
     debug!(
         registry = ?registry,
@@ -664,9 +865,22 @@ pub async fn plan_add(
     let components_base_dir = cfg.components_dir();
     let lib_base_dir = cfg.lib_dir();
     let hooks_base_dir = cfg.hooks_dir();
-
-    let discovered = fetch_registry_catalog_impl(client).await?;
-    let merged_registries = merge_registries(&cfg.registries, &discovered);
+    let routes_base_dir = cfg.routes_dir();
+
+    // Precedence: registry file configs > project config > discovered catalog
+    let (discovered, file_registries) = match registry_file.map(load_registry_file).transpose()? {
+        Some(RegistryFileContents::Catalog(entries)) => (entries, HashMap::new()),
+        Some(RegistryFileContents::Registries(registries)) => (
+            fetch_registry_catalog_impl(client, refresh, None).await?,
+            registries,
+        ),
+        None => (
+            fetch_registry_catalog_impl(client, refresh, None).await?,
+            HashMap::new(),
+        ),
+    };
+    let mut merged_registries = merge_registries(&cfg.registries, &discovered);
+    merged_registries.extend(file_registries);
 
     debug!(
         local_registries = ?cfg.registries.keys().collect::<Vec<_>>(),
@@ -681,9 +895,11 @@ pub async fn plan_add(
     let merged_cfg = UiConfig {
         root: cfg.root.clone(),
         registries: merged_registries,
+        aliases: cfg.aliases.clone(),
     };
 
-    let components = resolve_component_closure(client, &merged_cfg, registry, component).await?;
+    let components =
+        resolve_component_closure(client, &merged_cfg, registry, component, refresh).await?;
 
     let path_map = build_path_map(&components);
 
@@ -700,10 +916,8 @@ pub async fn plan_add(
         for file in &resolved.spec.files {
             let root = determine_output_root(file.file_type.as_deref());
 
-            let file_name = match root {
-                OutputRoot::Components => format!("{}.tsx", resolved.name),
-                OutputRoot::Lib | OutputRoot::Hooks => format!("{}.ts", resolved.name),
-            };
+            warnings.extend(unknown_file_type_warning(file, &resolved.name));
+
             let (relative_path, absolute_path) = match root {
                 OutputRoot::Components => {
                     let registry = resolved
@@ -716,34 +930,44 @@ pub async fn plan_add(
                         Some(name) => name,
                     };
 
-                    let relative_path = PathBuf::from(subdir).join(file_name);
+                    let relative_path =
+                        PathBuf::from(subdir).join(format!("{}.tsx", resolved.name));
                     let absolute_path = components_base_dir.join(&relative_path);
                     (relative_path, absolute_path)
                 }
                 OutputRoot::Lib => {
-                    let relative_path = PathBuf::from(file_name);
+                    let relative_path = PathBuf::from(format!("{}.ts", resolved.name));
                     let absolute_path = lib_base_dir.join(&relative_path);
                     (relative_path, absolute_path)
                 }
                 OutputRoot::Hooks => {
-                    let relative_path = PathBuf::from(file_name);
+                    let relative_path = PathBuf::from(format!("{}.ts", resolved.name));
                     let absolute_path = hooks_base_dir.join(&relative_path);
                     (relative_path, absolute_path)
                 }
+                OutputRoot::Routes => {
+                    let relative_path = PathBuf::from(format!("{}.tsx", resolved.name));
+                    let absolute_path = routes_base_dir.join(&relative_path);
+                    (relative_path, absolute_path)
+                }
+                // Themes and styles are applied to the CSS file (see collect_css_mutations)
+                OutputRoot::Theme => continue,
             };
 
             files_to_write.push(PlannedFile {
                 relative_path,
                 absolute_path,
-                content: rewrite_flattened_paths(
-                    &rewrite_registry_imports(&file.content),
-                    &path_map,
+                content: apply_import_aliases(
+                    &rewrite_flattened_paths(&rewrite_registry_imports(&file.content), &path_map),
+                    &cfg.aliases,
                 ),
                 source_component: resolved.name.clone(),
             });
         }
     }
 
+    warnings.extend(existing_style_var_warnings(&cfg.css_path(), &components));
+
     Ok(AddPlan {
         components,
         files_to_write,
@@ -752,17 +976,84 @@ pub async fn plan_add(
     })
 }
 
-/// Where a file ends up: components dir, lib dir, or hooks dir.
+/// Warnings for CSS variables of `components` that are already defined in the CSS
+/// file at `css_path`.
+///
+/// CSS updates never overwrite existing `:root` / `.dark` variables, so a
+/// `registry:style` item's keep their current values instead of the style's.
+/// `@theme` mappings with a new value are replaced, which is reported for every item.
+fn existing_style_var_warnings(css_path: &Path, components: &[ResolvedComponent]) -> Vec<String> {
+    let Ok(source) = std::fs::read_to_string(css_path) else {
+        return Vec::new();
+    };
+    let Ok(updater) = CssUpdater::new(&source) else {
+        return Vec::new();
+    };
+    let mut warnings = Vec::new();
+    for resolved in components {
+        let mutations = collect_css_mutations(std::slice::from_ref(resolved));
+        let defined = updater.defined_vars(&mutations);
+        if matches!(resolved.spec.item_type, RegistryItemType::Style) && !defined.is_empty() {
+            warnings.push(format!(
+                "Style {} sets variables already defined in {}, keeping the existing values: {}",
+                resolved.name,
+                css_path.display(),
+                defined.join(", ")
+            ));
+        }
+        let overwritten = updater.overwritten_theme_vars(&mutations);
+        if !overwritten.is_empty() {
+            warnings.push(format!(
+                "{} replaces theme mappings defined with other values in {}: {}",
+                resolved.name,
+                css_path.display(),
+                overwritten.join(", ")
+            ));
+        }
+    }
+    warnings
+}
+
+/// Registry file types with explicit handling; anything else falls back to components.
+const KNOWN_FILE_TYPES: &[&str] = &[
+    "registry:ui",
+    "registry:component",
+    "registry:block",
+    "registry:hook",
+    "registry:lib",
+    "registry:file",
+    "registry:page",
+    "registry:theme",
+    "registry:style",
+];
+
+/// Where a file ends up: components, lib, hooks or routes dir, or the CSS file for themes
+/// and styles.
 enum OutputRoot {
     Components,
     Lib,
     Hooks,
+    Routes,
+    Theme,
+}
+
+/// Warning for files whose `type` has no explicit handling (they are installed as components).
+fn unknown_file_type_warning(file: &RegistryFile, component: &str) -> Option<String> {
+    let file_type = file.file_type.as_deref()?;
+    (!KNOWN_FILE_TYPES.contains(&file_type)).then(|| {
+        format!(
+            "Unknown file type '{file_type}' for {} in {component}, installing it as a component",
+            file.path
+        )
+    })
 }
 
 fn determine_output_root(file_type: Option<&str>) -> OutputRoot {
     match file_type {
         Some("registry:hook") => OutputRoot::Hooks,
         Some("registry:lib" | "registry:file") => OutputRoot::Lib,
+        Some("registry:page") => OutputRoot::Routes,
+        Some("registry:theme" | "registry:style") => OutputRoot::Theme,
         _ => OutputRoot::Components,
     }
 }
@@ -770,8 +1061,9 @@ fn determine_output_root(file_type: Option<&str>) -> OutputRoot {
 /// Returns the `@/...` TypeScript import path where `file` will actually be saved.
 ///
 /// This reflects the *flattened* output name derived from `resolved.name`, not `file.path`.
-fn compute_saved_import_path(resolved: &ResolvedComponent, file: &RegistryFile) -> String {
-    match determine_output_root(file.file_type.as_deref()) {
+/// Theme files are merged into the CSS file and have no import path.
+fn compute_saved_import_path(resolved: &ResolvedComponent, file: &RegistryFile) -> Option<String> {
+    let path = match determine_output_root(file.file_type.as_deref()) {
         OutputRoot::Components => {
             let registry = resolved
                 .registry
@@ -782,7 +1074,10 @@ fn compute_saved_import_path(resolved: &ResolvedComponent, file: &RegistryFile)
         }
         OutputRoot::Hooks => format!("@/hooks/{}", resolved.name),
         OutputRoot::Lib => format!("@/lib/{}", resolved.name),
-    }
+        OutputRoot::Routes => format!("@/routes/{}", resolved.name),
+        OutputRoot::Theme => return None,
+    };
+    Some(path)
 }
 
 /// Converts a registry `file.path` (e.g. `"components/animate-ui/icons/icon.tsx"`)
@@ -805,8 +1100,10 @@ fn build_path_map(components: &[ResolvedComponent]) -> HashMap<String, String> {
     let mut map = HashMap::new();
     for resolved in components {
         for file in &resolved.spec.files {
+            let Some(saved) = compute_saved_import_path(resolved, file) else {
+                continue;
+            };
             let original = original_registry_import_path(&file.path);
-            let saved = compute_saved_import_path(resolved, file);
             if original != saved {
                 map.insert(original, saved);
             }
@@ -873,6 +1170,39 @@ fn rewrite_registry_imports(content: &str) -> String {
     tw_transform::transform_tailwind_v3_to_v4(&result)
 }
 
+/// Rewrite `@/...` import specifiers (as produced by `rewrite_registry_imports`) to the
+/// project's configured aliases.
+///
+/// Only specifiers directly after a quote character are rewritten, and each one is
+/// rewritten at most once (longest matching alias first).
+fn apply_import_aliases(content: &str, aliases: &ImportAliases) -> String {
+    if aliases.is_default() {
+        return content.to_string();
+    }
+
+    let rules = [
+        ("@/components/ui/", format!("{}/", aliases.ui)),
+        ("@/components/", format!("{}/", aliases.components)),
+        ("@/lib/", format!("{}/", aliases.lib)),
+        ("@/hooks/", format!("{}/", aliases.hooks)),
+        ("@/", aliases.prefix.clone()),
+    ];
+
+    let mut result = String::with_capacity(content.len());
+    let mut remaining = content;
+    while let Some(pos) = remaining.find(['"', '\'', '`']) {
+        let (before, after) = remaining.split_at(pos + 1);
+        result.push_str(before);
+        remaining = after;
+        if let Some((from, to)) = rules.iter().find(|(from, _)| remaining.starts_with(from)) {
+            result.push_str(to);
+            remaining = &remaining[from.len()..];
+        }
+    }
+    result.push_str(remaining);
+    result
+}
+
 // Reason: literal braces in code template, not format arguments
 #[allow(clippy::literal_string_with_formatting_args)]
 fn apply_placeholders(template: &str, name: &str, style: &str) -> Result<String, String> {
@@ -1278,16 +1608,213 @@ pub fn collect_css_mutations(components: &[ResolvedComponent]) -> Vec<CssMutatio
         if let Some(ref tailwind) = resolved.spec.tailwind {
             convert_tailwind_to_mutations(tailwind, &mut mutations);
         }
+
+        // Merge `registry:theme` and `registry:style` files into the CSS file instead of
+        // writing them out
+        for file in &resolved.spec.files {
+            if matches!(
+                determine_output_root(file.file_type.as_deref()),
+                OutputRoot::Theme
+            ) {
+                convert_theme_css_to_mutations(&file.content, &mut mutations);
+            }
+        }
     }
 
     mutations
 }
 
+/// Convert a `registry:theme` or `registry:style` stylesheet into CSS mutations.
+///
+/// Top-level blocks are mapped as follows:
+/// - `@theme` / `@theme inline { --x: ...; }` -> theme mappings
+/// - `:root { --x: ...; }`, `.dark { ... }` (custom properties only) -> CSS vars on that selector
+/// - other at-rules (`@layer`, `@keyframes`, ...) -> raw CSS blocks
+/// - selectors with regular declarations -> a rule inside `@layer base`
+fn convert_theme_css_to_mutations(content: &str, mutations: &mut Vec<CssMutation>) {
+    for (header, body) in split_top_level_css_blocks(&strip_css_comments(content)) {
+        if header.starts_with("@theme") {
+            let vars = parse_css_declarations(&body);
+            if !vars.is_empty() {
+                mutations.push(CssMutation::AddThemeMappings { vars });
+            }
+        } else if header.starts_with('@') {
+            mutations.push(CssMutation::AddCssBlock {
+                at_rule: header,
+                body: body.trim().to_string(),
+            });
+        } else {
+            let vars = parse_css_declarations(&body);
+            if vars.is_empty() {
+                continue;
+            }
+            if vars.iter().all(|(name, _)| name.starts_with("--")) {
+                mutations.push(CssMutation::AddCssVars {
+                    selector: header,
+                    vars,
+                });
+            } else {
+                mutations.push(CssMutation::AddCssBlock {
+                    at_rule: "@layer base".to_string(),
+                    body: format!("{header} {{\n{}\n}}", body.trim()),
+                });
+            }
+        }
+    }
+}
+
+/// Remove `/* ... */` comments from a stylesheet.
+fn strip_css_comments(css: &str) -> String {
+    let mut out = String::with_capacity(css.len());
+    let mut rest = css;
+    while let Some(start) = rest.find("/*") {
+        out.push_str(&rest[..start]);
+        match rest[start + 2..].find("*/") {
+            Some(end) => rest = &rest[start + 2 + end + 2..],
+            None => return out,
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Split a stylesheet into `(header, body)` pairs for each top-level `{ ... }` block.
+fn split_top_level_css_blocks(css: &str) -> Vec<(String, String)> {
+    let mut blocks = Vec::new();
+    let mut depth = 0usize;
+    let mut header_start = 0usize;
+    let mut body_start = 0usize;
+
+    for (i, c) in css.char_indices() {
+        match c {
+            '{' => {
+                if depth == 0 {
+                    body_start = i + 1;
+                }
+                depth += 1;
+            }
+            '}' if depth > 0 => {
+                depth -= 1;
+                if depth == 0 {
+                    let header = css[header_start..body_start - 1].trim();
+                    // Drop statements such as `@import "...";` preceding the block
+                    let header = header.rsplit(';').next().unwrap_or(header).trim();
+                    blocks.push((header.to_string(), css[body_start..i].to_string()));
+                    header_start = i + 1;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    blocks
+}
+
+/// Parse `name: value;` declarations from a flat rule body.
+fn parse_css_declarations(body: &str) -> Vec<(String, String)> {
+    body.split(';')
+        .filter_map(|decl| {
+            let (name, value) = decl.split_once(':')?;
+            let (name, value) = (name.trim(), value.trim());
+            (!name.is_empty() && !value.is_empty()).then(|| (name.to_string(), value.to_string()))
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::components::models::{RegistryFile, RegistryItem, RegistryItemType};
 
+    #[tokio::test]
+    // Reason: panicking on failure is idiomatic in tests
+    #[allow(clippy::unwrap_used)]
+    async fn test_registry_file_bypasses_network() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("registries.json");
+        std::fs::write(
+            &path,
+            r#"[{ "name": "@local", "url": "http://localhost:3000/r/button.json" }]"#,
+        )
+        .unwrap();
+
+        // Any network request through this client fails immediately
+        let offline = reqwest::Client::builder()
+            .proxy(reqwest::Proxy::all("http://127.0.0.1:9").unwrap())
+            .build()
+            .unwrap();
+
+        let catalog = fetch_registry_catalog_impl(&offline, true, Some(&path))
+            .await
+            .unwrap();
+        assert_eq!(catalog.len(), 1);
+        assert_eq!(catalog[0].name, "@local");
+        assert_eq!(catalog[0].url, "http://localhost:3000/r/button.json");
+
+        let missing = dir.path().join("missing.json");
+        assert!(
+            fetch_registry_catalog_impl(&offline, true, Some(&missing))
+                .await
+                .is_err()
+        );
+    }
+
+    #[test]
+    // Reason: panicking on failure is idiomatic in tests
+    #[allow(clippy::unwrap_used, clippy::panic)]
+    fn test_load_registry_file_configs() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("team-registries.json");
+        std::fs::write(
+            &path,
+            r#"{
+                "@team": "https://registry.example.com/r/{name}.json",
+                "@private": {
+                    "url": "https://private.example.com/r/{name}.json",
+                    "headers": { "Authorization": "Bearer token" }
+                }
+            }"#,
+        )
+        .unwrap();
+        let RegistryFileContents::Registries(registries) = load_registry_file(&path).unwrap()
+        else {
+            panic!("expected registry configs");
+        };
+        assert!(matches!(
+            &registries["@team"],
+            RegistryConfig::Template(url) if url == "https://registry.example.com/r/{name}.json"
+        ));
+        assert!(matches!(
+            &registries["@private"],
+            RegistryConfig::Advanced(adv) if adv.headers["Authorization"] == "Bearer token"
+        ));
+
+        // File configs win over project and catalog registries
+        let project = HashMap::from([(
+            "@team".to_string(),
+            RegistryConfig::Template("https://old.example.com/{name}.json".to_string()),
+        )]);
+        let mut merged = merge_registries(&project, &[]);
+        merged.extend(registries);
+        assert!(matches!(
+            &merged["@team"],
+            RegistryConfig::Template(url) if url.starts_with("https://registry.example.com")
+        ));
+
+        std::fs::write(
+            &path,
+            r#"[{ "name": "@local", "url": "http://localhost/{name}.json" }]"#,
+        )
+        .unwrap();
+        assert!(matches!(
+            load_registry_file(&path).unwrap(),
+            RegistryFileContents::Catalog(entries) if entries[0].name == "@local"
+        ));
+
+        std::fs::write(&path, r#"{ "@bad": 42 }"#).unwrap();
+        assert!(load_registry_file(&path).is_err());
+    }
+
     fn make_registry_file(path: &str, file_type: &str) -> RegistryFile {
         RegistryFile {
             path: path.to_string(),
@@ -1323,6 +1850,55 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_order_closure_is_topological_and_deterministic() {
+        let spec = |name: &str, registry: Option<&str>, deps: &[&str]| {
+            let mut resolved = make_resolved(name, registry, vec![]);
+            resolved.spec.registry_dependencies = deps.iter().map(|d| d.to_string()).collect();
+            (
+                closure_key(registry, name),
+                (resolved.spec, Vec::<String>::new()),
+            )
+        };
+        let specs = [
+            spec("dialog", None, &["button", "@acme/icon"]),
+            spec("button", None, &["utils"]),
+            spec("icon", Some("@acme"), &["utils"]),
+            spec("utils", None, &[]),
+        ];
+
+        // Insertion order models fetch completion order; it must not affect the result
+        let mut orders = Vec::new();
+        for rotation in 0..specs.len() {
+            let mut rotated = specs.to_vec();
+            rotated.rotate_left(rotation);
+            let fetched: HashMap<_, _> = rotated.into_iter().collect();
+            let ordered = order_closure(fetched, None, "dialog").unwrap_or_default();
+            orders.push(
+                ordered
+                    .iter()
+                    .map(|c| closure_key(c.registry.as_deref(), &c.name))
+                    .collect::<Vec<_>>(),
+            );
+        }
+
+        let expected = vec![
+            "_default::utils".to_string(),
+            "@acme::icon".to_string(),
+            "_default::button".to_string(),
+            "_default::dialog".to_string(),
+        ];
+        for order in orders {
+            assert_eq!(order, expected);
+        }
+    }
+
+    #[test]
+    fn test_order_closure_missing_spec_errors() {
+        let fetched = HashMap::new();
+        assert!(order_closure(fetched, None, "dialog").is_err());
+    }
+
     #[test]
     fn test_rewrite_flattened_paths_component() {
         let mut map = HashMap::new();
@@ -1436,6 +2012,258 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_build_path_map_page_and_theme() {
+        let components = vec![
+            make_resolved(
+                "dashboard",
+                None,
+                vec![make_registry_file(
+                    "app/dashboard/page.tsx",
+                    "registry:page",
+                )],
+            ),
+            make_resolved(
+                "theme-ocean",
+                None,
+                vec![make_registry_file("themes/ocean.css", "registry:theme")],
+            ),
+        ];
+        let map = build_path_map(&components);
+        assert_eq!(
+            map.get("@/app/dashboard/page"),
+            Some(&"@/routes/dashboard".to_string())
+        );
+        assert_eq!(map.len(), 1, "theme files must not produce import paths");
+    }
+
+    #[test]
+    fn test_unknown_file_type_warning() {
+        let page = make_registry_file("app/page.tsx", "registry:page");
+        assert_eq!(unknown_file_type_warning(&page, "dashboard"), None);
+
+        let example = make_registry_file("examples/demo.tsx", "registry:example");
+        let warning = unknown_file_type_warning(&example, "demo").unwrap_or_default();
+        assert!(warning.contains("registry:example"), "got: {warning}");
+    }
+
+    #[test]
+    // Reason: panicking on failure is idiomatic in tests
+    #[allow(clippy::unwrap_used)]
+    fn test_style_items_apply_to_css_and_warn_on_existing_vars() {
+        let mut style = make_resolved("new-york", None, vec![]);
+        style.spec.item_type = RegistryItemType::Style;
+        style.spec.files = vec![RegistryFile {
+            path: "styles/new-york.css".to_string(),
+            content: ".dark { --background: black; }".to_string(),
+            file_type: Some("registry:style".to_string()),
+        }];
+        style.spec.css_vars = Some(crate::components::models::CssVars {
+            theme: HashMap::from([("radius".to_string(), "0.5rem".to_string())]),
+            light: HashMap::from([
+                ("background".to_string(), "white".to_string()),
+                ("primary".to_string(), "blue".to_string()),
+            ]),
+            dark: HashMap::new(),
+        });
+
+        // Style files are merged into the CSS file, never written as components
+        assert!(matches!(
+            determine_output_root(Some("registry:style")),
+            OutputRoot::Theme
+        ));
+        assert_eq!(
+            unknown_file_type_warning(&style.spec.files[0], "new-york"),
+            None
+        );
+        let mutations = collect_css_mutations(std::slice::from_ref(&style));
+        assert!(mutations.iter().any(|m| matches!(
+            m,
+            CssMutation::AddCssVars { selector, vars } if selector == ".dark" && vars[0].0 == "--background"
+        )));
+
+        let dir = tempfile::tempdir().unwrap();
+        let css_path = dir.path().join("globals.css");
+        std::fs::write(
+            &css_path,
+            ":root {\n  --background: oklch(1 0 0);\n}\n\n.dark {\n  --background: oklch(0 0 0);\n}\n",
+        )
+        .unwrap();
+        let warnings = existing_style_var_warnings(&css_path, std::slice::from_ref(&style));
+        assert_eq!(warnings.len(), 1);
+        assert!(
+            warnings[0]
+                .ends_with("keeping the existing values: .dark --background, :root --background"),
+            "got: {}",
+            warnings[0]
+        );
+
+        // Theme mappings with another value are replaced, which is reported too
+        std::fs::write(
+            &css_path,
+            "@theme inline {\n  --radius: 1rem;\n}\n\n:root {\n  --background: oklch(1 0 0);\n}\n",
+        )
+        .unwrap();
+        let warnings = existing_style_var_warnings(&css_path, std::slice::from_ref(&style));
+        assert_eq!(warnings.len(), 2, "got: {warnings:?}");
+        assert_eq!(
+            warnings[1],
+            format!(
+                "new-york replaces theme mappings defined with other values in {}: @theme --radius",
+                css_path.display()
+            )
+        );
+
+        // Kept variables are only reported for style items
+        style.spec.item_type = RegistryItemType::Theme;
+        let warnings = existing_style_var_warnings(&css_path, &[style]);
+        assert_eq!(warnings.len(), 1, "got: {warnings:?}");
+        assert!(warnings[0].ends_with(": @theme --radius"));
+    }
+
+    #[test]
+    fn test_convert_theme_css_to_mutations() {
+        let css = r#"/* Ocean theme */
+:root {
+  --primary: oklch(0.5 0.1 240);
+  --radius: 0.75rem;
+}
+.dark {
+  --primary: oklch(0.7 0.1 240);
+}
+@theme inline {
+  --color-primary: var(--primary);
+}
+body {
+  letter-spacing: 0.01em;
+}"#;
+        let mut mutations = Vec::new();
+        convert_theme_css_to_mutations(css, &mut mutations);
+
+        assert_eq!(mutations.len(), 4);
+        assert!(matches!(
+            &mutations[0],
+            CssMutation::AddCssVars { selector, vars }
+                if selector == ":root" && vars.len() == 2 && vars[1].0 == "--radius"
+        ));
+        assert!(matches!(
+            &mutations[1],
+            CssMutation::AddCssVars { selector, .. } if selector == ".dark"
+        ));
+        assert!(matches!(
+            &mutations[2],
+            CssMutation::AddThemeMappings { vars } if vars[0].1 == "var(--primary)"
+        ));
+        assert!(matches!(
+            &mutations[3],
+            CssMutation::AddCssBlock { at_rule, body }
+                if at_rule == "@layer base" && body.starts_with("body {")
+        ));
+    }
+
+    #[test]
+    fn test_apply_import_aliases_custom_prefix() {
+        let content = r#"import { Button } from "@/components/ui/button";
+import { cn } from '@/lib/utils';
+import { useMobile } from "@/hooks/use-mobile";
+import { Icon } from "@/components/animate-ui/icons-icon";
+import config from "@/config";
+const label = "user@/home";"#;
+
+        let rewritten = apply_import_aliases(content, &ImportAliases::with_prefix("~/"));
+        assert_eq!(
+            rewritten,
+            r#"import { Button } from "~/components/ui/button";
+import { cn } from '~/lib/utils';
+import { useMobile } from "~/hooks/use-mobile";
+import { Icon } from "~/components/animate-ui/icons-icon";
+import config from "~/config";
+const label = "user@/home";"#
+        );
+    }
+
+    #[test]
+    fn test_apply_import_aliases_components_json() {
+        let components_json = serde_json::json!({
+            "aliases": {
+                "components": "@components",
+                "ui": "@ui",
+                "lib": "@lib/",
+                "hooks": "@hooks"
+            }
+        });
+        let aliases = ImportAliases::default().with_components_json(&components_json);
+
+        let rewritten = apply_import_aliases(
+            r#"import { Button } from "@/components/ui/button";
+import { Card } from "@/components/blocks/card";
+import { cn } from "@/lib/utils";
+import { config } from "@/config";"#,
+            &aliases,
+        );
+        // A bare alias doesn't turn `@/` into `@`
+        assert_eq!(
+            rewritten,
+            r#"import { Button } from "@ui/button";
+import { Card } from "@components/blocks/card";
+import { cn } from "@lib/utils";
+import { config } from "@/config";"#
+        );
+    }
+
+    #[test]
+    fn test_components_json_aliases_strip_trailing_glob() {
+        let components_json = serde_json::json!({
+            "aliases": { "components": "~/components/*", "lib": "~/lib/*" }
+        });
+        let aliases = ImportAliases::default().with_components_json(&components_json);
+        assert_eq!(aliases.prefix, "~/");
+        assert_eq!(aliases.components, "~/components");
+        assert_eq!(aliases.ui, "~/components/ui");
+        assert_eq!(aliases.lib, "~/lib");
+
+        let bare = ImportAliases::default().with_components_json(&serde_json::json!({
+            "aliases": { "components": "@components" }
+        }));
+        assert_eq!(bare.prefix, "@/");
+        assert_eq!(bare.components, "@components");
+    }
+
+    #[test]
+    fn test_apply_import_aliases_default_is_noop() {
+        let content = r#"import { Button } from "@/components/ui/button";"#;
+        assert_eq!(
+            apply_import_aliases(content, &ImportAliases::default()),
+            content
+        );
+    }
+
+    #[test]
+    fn test_import_alias_prefix_from_tsconfig() {
+        let tsconfig: Value = serde_json::from_str(&utils::strip_json_comments(
+            r#"{
+  // JSONC comments are allowed in tsconfig
+  "compilerOptions": {
+    /* Bundler mode */
+    "paths": {
+      "@shared/*": ["./src/shared/*"],
+      "~/*": ["./src/app/ui/*"]
+    }
+  }
+}"#,
+        ))
+        .unwrap_or_default();
+
+        assert_eq!(
+            ImportAliases::prefix_from_tsconfig(&tsconfig, Path::new("src/app/ui")),
+            Some("~/".to_string())
+        );
+        assert_eq!(
+            ImportAliases::prefix_from_tsconfig(&tsconfig, Path::new("src/other/ui")),
+            None
+        );
+    }
+
     #[test]
     fn test_detect_external_imports() {
         let files = vec![PlannedFile {
@@ -1534,4 +2362,51 @@ const x = require("not-matched");
         assert!(result.contains("@/components/animate-ui/icons-icon"));
         assert!(!result.contains("@/components/animate-ui/icons/icon\""));
     }
+
+    #[test]
+    fn test_parse_retry_after() {
+        assert_eq!(parse_retry_after("7"), Some(Duration::from_secs(7)));
+        assert_eq!(
+            parse_retry_after("Sun, 06 Nov 1994 08:49:37 GMT"),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(parse_retry_after("soon"), None);
+    }
+
+    #[tokio::test]
+    // Reason: panicking on failure is idiomatic in tests
+    #[allow(clippy::unwrap_used)]
+    async fn test_fetch_with_retry_only_retries_retryable_statuses() {
+        let attempts_for = |status: reqwest::StatusCode| async move {
+            let attempts = std::sync::atomic::AtomicU32::new(0);
+            let result: Result<(), String> = fetch_with_retry(
+                || {
+                    attempts.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                    async move {
+                        Err(FetchError {
+                            message: format!("returned {status}"),
+                            status: Some(status),
+                            retry_after: Some(Duration::ZERO),
+                        })
+                    }
+                },
+                "fetch component",
+            )
+            .await;
+            (attempts.into_inner(), result.unwrap_err())
+        };
+
+        let (attempts, err) = attempts_for(reqwest::StatusCode::NOT_FOUND).await;
+        assert_eq!(attempts, 1);
+        assert_eq!(err, "fetch component: returned 404 Not Found");
+
+        for status in [
+            reqwest::StatusCode::TOO_MANY_REQUESTS,
+            reqwest::StatusCode::SERVICE_UNAVAILABLE,
+        ] {
+            let (attempts, err) = attempts_for(status).await;
+            assert_eq!(attempts, MAX_RETRIES);
+            assert!(err.ends_with("(after 5 retries)"), "{err}");
+        }
+    }
 }
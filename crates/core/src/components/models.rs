@@ -4,6 +4,7 @@ use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
 use crate::common::ProjectMetadata;
+use crate::components::utils::strip_json_comments;
 
 /// UI configuration derived from pyproject.toml [tool.apx.ui]
 #[derive(Debug, Clone)]
@@ -12,6 +13,8 @@ pub struct UiConfig {
     pub root: PathBuf,
     /// Named component registries (local overrides and catalog entries).
     pub registries: HashMap<String, RegistryConfig>,
+    /// Import aliases used in files written to the project.
+    pub aliases: ImportAliases,
 }
 
 impl UiConfig {
@@ -34,7 +37,13 @@ impl UiConfig {
             })
             .unwrap_or_default();
 
-        Ok(Self { root, registries })
+        let aliases = ImportAliases::detect(app_dir, ui_root);
+
+        Ok(Self {
+            root,
+            registries,
+            aliases,
+        })
     }
 
     /// Hardcoded shadcn style
@@ -61,6 +70,140 @@ impl UiConfig {
     pub fn hooks_dir(&self) -> PathBuf {
         self.root.join("hooks")
     }
+
+    /// Routes dir: {root}/routes
+    pub fn routes_dir(&self) -> PathBuf {
+        self.root.join("routes")
+    }
+}
+
+/// Import aliases for the UI sources, mirroring the `aliases` section of shadcn's `components.json`.
+///
+/// Registry files are normalized to the default `@/` layout first and then
+/// rewritten to these aliases when written to the project.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportAliases {
+    /// Alias for the UI root (e.g. `@/` or `~/`).
+    pub prefix: String,
+    /// Alias for the components dir (e.g. `@/components`).
+    pub components: String,
+    /// Alias for the ui components dir (e.g. `@/components/ui`).
+    pub ui: String,
+    /// Alias for the lib dir (e.g. `@/lib`).
+    pub lib: String,
+    /// Alias for the hooks dir (e.g. `@/hooks`).
+    pub hooks: String,
+}
+
+impl Default for ImportAliases {
+    fn default() -> Self {
+        Self::with_prefix("@/")
+    }
+}
+
+impl ImportAliases {
+    /// Aliases using the standard subpaths under `prefix`.
+    pub fn with_prefix(prefix: &str) -> Self {
+        Self {
+            prefix: prefix.to_string(),
+            components: format!("{prefix}components"),
+            ui: format!("{prefix}components/ui"),
+            lib: format!("{prefix}lib"),
+            hooks: format!("{prefix}hooks"),
+        }
+    }
+
+    /// Detect aliases from `components.json` (app dir, then UI root), falling back to the
+    /// `compilerOptions.paths` entry in `tsconfig.json` that points at the UI root, then `@/`.
+    pub fn detect(app_dir: &Path, ui_root: &Path) -> Self {
+        let read_json = |path: PathBuf| -> Option<Value> {
+            let content = std::fs::read_to_string(path).ok()?;
+            serde_json::from_str(&strip_json_comments(&content)).ok()
+        };
+
+        let prefix = read_json(app_dir.join("tsconfig.json"))
+            .and_then(|tsconfig| Self::prefix_from_tsconfig(&tsconfig, ui_root));
+        let base = prefix
+            .as_deref()
+            .map_or_else(Self::default, Self::with_prefix);
+
+        let components_json = [
+            app_dir.join("components.json"),
+            app_dir.join(ui_root).join("components.json"),
+        ]
+        .into_iter()
+        .find_map(read_json);
+
+        match components_json {
+            Some(json) => base.with_components_json(&json),
+            None => base,
+        }
+    }
+
+    /// Find the tsconfig `paths` alias (e.g. `~/*`) mapped to the UI root and return its prefix.
+    pub fn prefix_from_tsconfig(tsconfig: &Value, ui_root: &Path) -> Option<String> {
+        let ui_root = ui_root.to_string_lossy();
+        let ui_root = ui_root.trim_start_matches("./").trim_end_matches('/');
+        let paths = tsconfig.get("compilerOptions")?.get("paths")?.as_object()?;
+
+        paths.iter().find_map(|(alias, targets)| {
+            let prefix = alias.strip_suffix('*')?;
+            let points_at_root = targets.as_array()?.iter().any(|target| {
+                target.as_str().is_some_and(|t| {
+                    t.trim_start_matches("./")
+                        .trim_end_matches('*')
+                        .trim_end_matches('/')
+                        == ui_root
+                })
+            });
+            points_at_root.then(|| prefix.to_string())
+        })
+    }
+
+    /// Override aliases with the `aliases` section of a `components.json`.
+    ///
+    /// Aliases are kept as written apart from a trailing `/*` or `/`. The root
+    /// prefix is only derived from a components alias ending in `/components`, so
+    /// a bare alias such as `@components` leaves other `@/` imports alone.
+    pub fn with_components_json(mut self, components_json: &Value) -> Self {
+        let Some(aliases) = components_json.get("aliases") else {
+            return self;
+        };
+        let get = |key: &str| {
+            aliases.get(key).and_then(Value::as_str).map(|v| {
+                v.strip_suffix("/*")
+                    .unwrap_or(v)
+                    .trim_end_matches('/')
+                    .to_string()
+            })
+        };
+
+        if let Some(components) = get("components") {
+            if let Some(prefix) = components
+                .strip_suffix("components")
+                .filter(|prefix| prefix.ends_with('/'))
+            {
+                self.prefix = prefix.to_string();
+            }
+            self.ui = format!("{components}/ui");
+            self.components = components;
+        }
+        if let Some(ui) = get("ui") {
+            self.ui = ui;
+        }
+        if let Some(lib) = get("lib") {
+            self.lib = lib;
+        }
+        if let Some(hooks) = get("hooks") {
+            self.hooks = hooks;
+        }
+        self
+    }
+
+    /// Whether these are the default `@/` aliases (no rewriting needed).
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
 }
 
 /// A component registry configuration, either a simple URL template or an advanced config.
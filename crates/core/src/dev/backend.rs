@@ -19,11 +19,13 @@ use tokio::sync::Mutex;
 use tokio::time::Duration;
 use tracing::{info, warn};
 
-use crate::dev::common::{DevProcess, ProbeResult, http_health_probe, stop_child_tree};
+use crate::dev::common::{
+    DevProcess, ProbeResult, StartupTiming, http_health_probe, stop_child_tree, watch_readiness,
+};
 use crate::dev::embedded_db::EmbeddedDb;
-use crate::dev::otel::forward_log_to_flux;
+use crate::dev::otel::FluxLogForwarder;
 use crate::dev::token;
-use crate::dotenv::DotenvFile;
+use crate::dotenv::{DotenvFile, UnresolvedVar};
 use crate::external::uv::UvTool;
 use crate::python_logging::{
     DevConfig, LogConfigResult, default_logging_config, resolve_log_config,
@@ -69,6 +71,7 @@ pub struct BackendConfig {
 /// `ProcessManager` interacts only through this API.
 pub struct Backend {
     child: Arc<Mutex<Option<Child>>>,
+    timing: Arc<StartupTiming>,
     cfg: BackendConfig,
 }
 
@@ -86,6 +89,7 @@ impl Backend {
     pub fn new(cfg: BackendConfig) -> Self {
         Self {
             child: Arc::new(Mutex::new(None)),
+            timing: Arc::default(),
             cfg,
         }
     }
@@ -95,8 +99,10 @@ impl Backend {
     }
 
     /// Spawn uvicorn. Resolves log config, builds the command, attaches log
-    /// forwarders, and stores the child handle.
+    /// forwarders, and stores the child handle. Startup is timed from here
+    /// until uvicorn first answers HTTP.
     pub async fn spawn(&self) -> Result<(), String> {
+        let generation = self.timing.start();
         let log_config = self.resolve_and_validate_log_config().await?;
         let tool_cmd = self.build_uvicorn_command(&log_config).await?;
 
@@ -105,9 +111,26 @@ impl Backend {
 
         let mut guard = self.child.lock().await;
         *guard = Some(child);
+        watch_readiness(
+            self.label(),
+            self.cfg.backend_port,
+            Arc::clone(&self.timing),
+            generation,
+        );
         Ok(())
     }
 
+    /// Time the current uvicorn took to answer HTTP after its (re)start, once it has.
+    pub fn ready_after(&self) -> Option<Duration> {
+        self.timing.ready_after()
+    }
+
+    /// Stop the current backend and respawn it with the same env vars.
+    pub async fn restart(&self) -> Result<(), String> {
+        self.stop_current().await;
+        self.spawn().await
+    }
+
     /// Stop the current backend, update env vars, and respawn.
     pub async fn restart_with_env(&self, new_vars: HashMap<String, String>) -> Result<(), String> {
         self.stop_current().await;
@@ -266,12 +289,13 @@ impl Backend {
 
     /// Spawn tasks to read stdout/stderr, prefix with source, and forward to flux.
     fn attach_log_forwarders(&self, child: &mut Child) {
-        let service_name = format!("{}_app", self.cfg.app_slug);
-        let app_path = self.cfg.app_dir.display().to_string();
+        let forwarder = FluxLogForwarder::spawn(
+            format!("{}_app", self.cfg.app_slug),
+            self.cfg.app_dir.display().to_string(),
+        );
 
         if let Some(stdout) = child.stdout.take() {
-            let svc = service_name.clone();
-            let path = app_path.clone();
+            let forwarder = forwarder.clone();
             tokio::spawn(async move {
                 let mut lines = BufReader::new(stdout).lines();
                 while let Ok(Some(line)) = lines.next_line().await {
@@ -279,7 +303,7 @@ impl Backend {
                         "{}",
                         apx_common::format::format_process_log_line("app", &line)
                     );
-                    forward_log_to_flux(&line, "INFO", &svc, &path).await;
+                    forwarder.forward(&line, "INFO");
                 }
             });
         }
@@ -293,7 +317,7 @@ impl Backend {
                         apx_common::format::format_process_log_line("app", &line)
                     );
                     let severity = apx_common::format::parse_python_severity(&line);
-                    forward_log_to_flux(&line, severity, &service_name, &app_path).await;
+                    forwarder.forward(&line, severity);
                 }
             });
         }
@@ -333,7 +357,10 @@ impl DevProcess for Backend {
         drop(guard);
 
         match http_health_probe(CLIENT_HOST, self.cfg.backend_port).await {
-            ProbeResult::Responded => "healthy",
+            ProbeResult::Responded => {
+                self.timing.mark_ready(self.label());
+                "healthy"
+            }
             ProbeResult::Failed => "starting",
         }
     }
@@ -428,13 +455,12 @@ async fn handle_file_change(backend: &Backend, file_name: &str) {
     }
 
     let new_vars = DotenvFile::read(&backend.cfg.app_dir.join(".env"))
-        .map(|d| d.get_vars())
-        .unwrap_or_default();
+        .and_then(|d| d.get_vars_interpolated(UnresolvedVar::Keep));
 
     backend.stop_current().await;
-    {
-        let mut vars = backend.cfg.dotenv_vars.lock().await;
-        *vars = new_vars;
+    match new_vars {
+        Ok(new_vars) => *backend.cfg.dotenv_vars.lock().await = new_vars,
+        Err(e) => warn!("Failed to reload .env, keeping the previous variables: {e}"),
     }
     if let Err(e) = backend.spawn().await {
         warn!("Failed to restart backend: {}", e);
@@ -9,13 +9,16 @@ use tracing::{debug, warn};
 
 use apx_common::hosts::CLIENT_HOST;
 
+use crate::dev::process::DevService;
 use crate::dev::token::DEV_TOKEN_HEADER;
 
 const DEFAULT_TIMEOUT_SECS: u64 = 5;
 const STOP_TIMEOUT_SECS: u64 = 10;
+/// Restarting the database waits for PGlite to accept connections again.
+const RESTART_TIMEOUT_SECS: u64 = 60;
 
 /// Shared HTTP client for dev server communication.
-/// Reused across health(), status(), and stop() to avoid creating a new client per call.
+/// Reused across health(), status(), stop() and restart_service() to avoid creating a new client per call.
 static DEV_CLIENT: LazyLock<reqwest::Client> = LazyLock::new(|| {
     reqwest::Client::builder()
         .no_gzip()
@@ -83,6 +86,15 @@ pub struct StatusResponse {
     pub db_status: String,
     /// True if any critical process (frontend/backend) has permanently failed and cannot recover.
     pub failed: bool,
+    /// Milliseconds the frontend took to become ready since its last start.
+    #[serde(default)]
+    pub frontend_ready_ms: Option<u64>,
+    /// Milliseconds the backend took to become ready since its last (re)start.
+    #[serde(default)]
+    pub backend_ready_ms: Option<u64>,
+    /// Milliseconds the embedded database took to become ready.
+    #[serde(default)]
+    pub db_ready_ms: Option<u64>,
 }
 
 fn build_url(host: &str, port: u16, path: &str) -> String {
@@ -191,3 +203,39 @@ pub async fn stop(port: u16, token: Option<&str>) -> Result<(), String> {
         ))
     }
 }
+
+/// Ask the dev server to restart a single process, waiting until it has been respawned.
+pub async fn restart_service(
+    port: u16,
+    token: Option<&str>,
+    service: DevService,
+) -> Result<(), String> {
+    let url = build_url(
+        CLIENT_HOST,
+        port,
+        &format!("/_apx/restart?service={}", service.as_str()),
+    );
+    debug!(%url, "Sending dev server restart request.");
+    let mut request = DEV_CLIENT
+        .post(&url)
+        .timeout(Duration::from_secs(RESTART_TIMEOUT_SECS));
+    if let Some(t) = token {
+        request = request.header(DEV_TOKEN_HEADER, t);
+    }
+    let response = request
+        .send()
+        .await
+        .map_err(|err| format!("Restart request failed: {err}"))?;
+    let status = response.status();
+    if status == StatusCode::OK {
+        debug!("Dev server restarted {}.", service.as_str());
+        return Ok(());
+    }
+    let body = response.text().await.unwrap_or_default();
+    warn!(%status, %body, "Dev server restart request failed.");
+    if body.is_empty() {
+        Err(format!("Restart request failed with status {status}"))
+    } else {
+        Err(format!("Failed to restart {}: {body}", service.as_str()))
+    }
+}
@@ -10,9 +10,10 @@ use sysinfo::{Pid, Signal, System};
 use tokio::process::Child;
 use tokio::sync::Mutex;
 use tokio::time::{Duration, timeout};
-use tracing::{debug, warn};
+use tracing::{debug, info, warn};
 
 use crate::common::ensure_dir;
+use apx_common::hosts::CLIENT_HOST;
 
 // ---------------------------------------------------------------------------
 // Health probe utilities (shared by process.rs and backend.rs)
@@ -70,6 +71,106 @@ pub(crate) async fn http_health_probe(host: &str, port: u16) -> ProbeResult {
     }
 }
 
+// ---------------------------------------------------------------------------
+// Startup timing (how long each process took to become ready)
+// ---------------------------------------------------------------------------
+
+/// Interval between readiness probes of a freshly spawned process.
+const READINESS_WATCH_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Give up watching a process that never becomes ready after this long.
+const READINESS_WATCH_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// When a dev process was last (re)started and how long it took to become ready.
+/// Every start clears the previous readiness, so restarts are timed afresh.
+#[derive(Debug, Default)]
+pub(crate) struct StartupTiming {
+    state: std::sync::Mutex<TimingState>,
+}
+
+#[derive(Debug, Default)]
+struct TimingState {
+    started_at: Option<std::time::Instant>,
+    ready_after: Option<Duration>,
+    /// Incremented on every start, so a watch on an older start stops.
+    generation: u64,
+}
+
+impl StartupTiming {
+    /// Record a (re)start now, returning its generation for [`watch_readiness`].
+    pub(crate) fn start(&self) -> u64 {
+        let mut state = self.lock();
+        state.started_at = Some(std::time::Instant::now());
+        state.ready_after = None;
+        state.generation += 1;
+        state.generation
+    }
+
+    /// Record the current start as ready and log the elapsed time.
+    /// Only the first call after a start counts.
+    pub(crate) fn mark_ready(&self, label: &str) {
+        let generation = self.lock().generation;
+        self.mark_generation_ready(generation, label);
+    }
+
+    /// Like [`Self::mark_ready`], but only while `generation` is the current start.
+    fn mark_generation_ready(&self, generation: u64, label: &str) {
+        let mut state = self.lock();
+        if state.generation != generation || state.ready_after.is_some() {
+            return;
+        }
+        if let Some(started_at) = state.started_at {
+            let elapsed = started_at.elapsed();
+            state.ready_after = Some(elapsed);
+            info!("{label} ready in {:.1}s", elapsed.as_secs_f64());
+        }
+    }
+
+    /// Time the current start took to become ready, if it has.
+    pub(crate) fn ready_after(&self) -> Option<Duration> {
+        self.lock().ready_after
+    }
+
+    fn is_waiting(&self, generation: u64) -> bool {
+        let state = self.lock();
+        state.generation == generation && state.ready_after.is_none()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, TimingState> {
+        self.state
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+/// Probe `port` in the background until it answers, then mark the start
+/// `generation` of `timing` as ready. Stops early once the process is
+/// restarted or marked ready elsewhere, and after [`READINESS_WATCH_TIMEOUT`].
+pub(crate) fn watch_readiness(
+    label: &'static str,
+    port: u16,
+    timing: Arc<StartupTiming>,
+    generation: u64,
+) {
+    tokio::spawn(async move {
+        let watch = async {
+            while timing.is_waiting(generation) {
+                if matches!(
+                    http_health_probe(CLIENT_HOST, port).await,
+                    ProbeResult::Responded
+                ) {
+                    timing.mark_generation_ready(generation, label);
+                    return;
+                }
+                tokio::time::sleep(READINESS_WATCH_INTERVAL).await;
+            }
+        };
+        if timeout(READINESS_WATCH_TIMEOUT, watch).await.is_err() {
+            warn!("{label} did not become ready within {READINESS_WATCH_TIMEOUT:?}");
+        }
+    });
+}
+
 /// Shutdown signal type for the dev server.
 /// Used as a single authority for coordinating shutdown across all components.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -82,6 +183,10 @@ pub enum Shutdown {
 pub const DEV_LOCK_DIR: &str = ".apx";
 /// Lock file name within the dev lock directory.
 pub const DEV_LOCK_FILE: &str = "dev.lock";
+/// Pinned subprocess ports file name within the dev lock directory.
+pub const DEV_PORTS_FILE: &str = "ports.json";
+/// Persisted dev server state file name within the dev lock directory.
+pub const DEV_STATE_FILE: &str = "dev.json";
 /// Start of the frontend port range.
 pub const FRONTEND_PORT_START: u16 = 5000;
 /// End of the frontend port range.
@@ -165,6 +270,162 @@ pub fn remove_lock(path: &Path) -> Result<(), String> {
     Ok(())
 }
 
+/// Subprocess ports pinned with `apx dev start --*-port`, persisted in `.apx/ports.json`.
+/// Unpinned services fall back to random allocation.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PinnedPorts {
+    /// Frontend (Vite) port.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub frontend: Option<u16>,
+    /// Backend (uvicorn) port.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub backend: Option<u16>,
+    /// Embedded database port.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub db: Option<u16>,
+}
+
+impl PinnedPorts {
+    /// Overlay `overrides` on top of these ports, keeping existing pins that are not overridden.
+    #[must_use]
+    pub fn merge(self, overrides: Self) -> Self {
+        Self {
+            frontend: overrides.frontend.or(self.frontend),
+            backend: overrides.backend.or(self.backend),
+            db: overrides.db.or(self.db),
+        }
+    }
+
+    /// Whether no port is pinned.
+    pub const fn is_empty(&self) -> bool {
+        self.frontend.is_none() && self.backend.is_none() && self.db.is_none()
+    }
+
+    fn entries(&self) -> impl Iterator<Item = (&'static str, u16)> {
+        [
+            ("frontend", self.frontend),
+            ("backend", self.backend),
+            ("db", self.db),
+        ]
+        .into_iter()
+        .filter_map(|(service, port)| port.map(|port| (service, port)))
+    }
+
+    /// Describe each port in `requested` that the running server, started with these pins,
+    /// is not using. Unpinned services ran on a random port, so any request for them differs.
+    pub fn mismatches(&self, requested: Self) -> Vec<String> {
+        let current: Vec<_> = self.entries().collect();
+        requested
+            .entries()
+            .filter_map(|(service, port)| {
+                match current.iter().find(|(s, _)| *s == service) {
+                    Some((_, running)) if *running == port => None,
+                    Some((_, running)) => Some(format!(
+                        "--{service}-port {port} ignored: the running server uses port {running}"
+                    )),
+                    None => Some(format!(
+                        "--{service}-port {port} ignored: the running server uses a random {service} port"
+                    )),
+                }
+            })
+            .collect()
+    }
+
+    /// Check that pinned ports are distinct, differ from `dev_port` and are free to bind.
+    pub fn ensure_available(&self, host: &str, dev_port: Option<u16>) -> Result<(), String> {
+        let entries: Vec<_> = self.entries().collect();
+        for (i, (service, port)) in entries.iter().enumerate() {
+            if let Some((other, _)) = entries.iter().take(i).find(|(_, p)| p == port) {
+                return Err(format!(
+                    "Port {port} is pinned for both {other} and {service}"
+                ));
+            }
+            if dev_port == Some(*port) {
+                return Err(format!(
+                    "Port {port} pinned for {service} is used by the dev server itself"
+                ));
+            }
+            if std::net::TcpListener::bind((host, *port)).is_err() {
+                return Err(format!(
+                    "Port {port} requested for {service} is already in use. \
+                     Free it or pick another with --{service}-port"
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Return the full path to the pinned ports file for the given app.
+pub fn ports_path(app_dir: &Path) -> PathBuf {
+    lock_dir(app_dir).join(DEV_PORTS_FILE)
+}
+
+/// Read pinned ports for the given app. A missing file means nothing is pinned.
+pub fn read_pinned_ports(app_dir: &Path) -> Result<PinnedPorts, String> {
+    let path = ports_path(app_dir);
+    if !path.exists() {
+        return Ok(PinnedPorts::default());
+    }
+    let contents =
+        fs::read_to_string(&path).map_err(|err| format!("Failed to read ports file: {err}"))?;
+    serde_json::from_str(&contents)
+        .map_err(|err| format!("Invalid ports file {}: {err}", path.display()))
+}
+
+/// Persist pinned ports for the given app, creating `.apx` if needed.
+pub fn write_pinned_ports(app_dir: &Path, ports: &PinnedPorts) -> Result<(), String> {
+    ensure_dir(&lock_dir(app_dir))?;
+    let contents = serde_json::to_string_pretty(ports)
+        .map_err(|err| format!("Ports file JSON error: {err}"))?;
+    fs::write(ports_path(app_dir), contents)
+        .map_err(|err| format!("Failed to write ports file: {err}"))
+}
+
+/// Dev server state kept across starts and restarts in `.apx/dev.json`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DevState {
+    /// Authentication token for control endpoints, reused until rotated.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+}
+
+/// Return the full path to the dev state file for the given app.
+pub fn dev_state_path(app_dir: &Path) -> PathBuf {
+    lock_dir(app_dir).join(DEV_STATE_FILE)
+}
+
+/// Read the dev state for the given app. A missing file means a fresh state.
+pub fn read_dev_state(app_dir: &Path) -> Result<DevState, String> {
+    let path = dev_state_path(app_dir);
+    if !path.exists() {
+        return Ok(DevState::default());
+    }
+    let contents =
+        fs::read_to_string(&path).map_err(|err| format!("Failed to read dev state: {err}"))?;
+    serde_json::from_str(&contents)
+        .map_err(|err| format!("Invalid dev state file {}: {err}", path.display()))
+}
+
+/// Persist the dev state for the given app, creating `.apx` if needed.
+pub fn write_dev_state(app_dir: &Path, state: &DevState) -> Result<(), String> {
+    ensure_dir(&lock_dir(app_dir))?;
+    let contents = serde_json::to_string_pretty(state)
+        .map_err(|err| format!("Dev state JSON error: {err}"))?;
+    fs::write(dev_state_path(app_dir), contents)
+        .map_err(|err| format!("Failed to write dev state: {err}"))
+}
+
+/// Use the pinned port if set, otherwise pick a random free port in the range.
+pub fn pinned_or_random_port(
+    host: &str,
+    pinned: Option<u16>,
+    start: u16,
+    end: u16,
+) -> Result<u16, String> {
+    pinned.map_or_else(|| find_random_port_in_range(host, start, end), Ok)
+}
+
 /// Check if a process with the given PID is still running.
 /// Uses sysinfo crate for cross-platform compatibility (Linux, macOS, Windows).
 pub fn is_process_running(pid: u32) -> bool {
@@ -238,6 +499,19 @@ pub(crate) async fn stop_child_tree(name: &str, child: &Arc<Mutex<Option<Child>>
     }
 }
 
+/// Ask a process to exit with `SIGTERM`, returning whether the signal was sent.
+///
+/// Returns `false` when the process is gone or the platform has no `SIGTERM`
+/// (Windows), so callers can fall back to [`kill_process_tree`]. Blocking, like it.
+pub(crate) fn terminate_process(pid: u32) -> bool {
+    let pid = Pid::from_u32(pid);
+    let mut sys = System::new();
+    sys.refresh_processes(sysinfo::ProcessesToUpdate::Some(&[pid]), true);
+    sys.process(pid)
+        .and_then(|process| process.kill_with(Signal::Term))
+        .unwrap_or(false)
+}
+
 /// Kill a process tree. This is a blocking operation that should be called
 /// from a blocking context or wrapped in `spawn_blocking`.
 pub(crate) fn kill_process_tree(pid: u32, label: &str) -> Result<(), String> {
@@ -315,3 +589,100 @@ fn kill_tree_with_guard(
         }
     }
 }
+
+#[cfg(test)]
+// Reason: panicking on failure is idiomatic in tests
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_startup_timing_resets_on_restart() {
+        let timing = StartupTiming::default();
+        timing.mark_ready("backend");
+        assert_eq!(timing.ready_after(), None, "not started yet");
+
+        let first = timing.start();
+        assert!(timing.is_waiting(first));
+        timing.mark_ready("backend");
+        let ready = timing.ready_after().unwrap();
+        assert!(!timing.is_waiting(first));
+
+        // Later readiness reports for the same start are ignored
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        timing.mark_ready("backend");
+        assert_eq!(timing.ready_after(), Some(ready));
+
+        let second = timing.start();
+        assert_eq!(timing.ready_after(), None);
+        assert!(!timing.is_waiting(first));
+        assert!(timing.is_waiting(second));
+    }
+
+    #[test]
+    fn test_pinned_ports_merge_and_persist() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(read_pinned_ports(dir.path()).unwrap().is_empty());
+
+        let stored = PinnedPorts {
+            backend: Some(8123),
+            db: Some(4123),
+            ..PinnedPorts::default()
+        };
+        let merged = stored.merge(PinnedPorts {
+            db: Some(4456),
+            ..PinnedPorts::default()
+        });
+        assert_eq!(merged.backend, Some(8123));
+        assert_eq!(merged.db, Some(4456));
+        assert_eq!(merged.frontend, None);
+
+        write_pinned_ports(dir.path(), &merged).unwrap();
+        assert_eq!(read_pinned_ports(dir.path()).unwrap(), merged);
+    }
+
+    #[test]
+    fn test_pinned_ports_reject_conflicts() {
+        let duplicate = PinnedPorts {
+            backend: Some(8123),
+            db: Some(8123),
+            ..PinnedPorts::default()
+        };
+        let err = duplicate.ensure_available("127.0.0.1", None).unwrap_err();
+        assert!(err.contains("both backend and db"), "{err}");
+
+        let listener = std::net::TcpListener::bind(("127.0.0.1", 0)).unwrap();
+        let taken = listener.local_addr().unwrap().port();
+        let busy = PinnedPorts {
+            backend: Some(taken),
+            ..PinnedPorts::default()
+        };
+        let err = busy.ensure_available("127.0.0.1", None).unwrap_err();
+        assert!(err.contains("already in use"), "{err}");
+        assert!(err.contains("--backend-port"), "{err}");
+    }
+
+    #[test]
+    fn test_pinned_ports_mismatches() {
+        let running = PinnedPorts {
+            backend: Some(8123),
+            db: Some(4123),
+            ..PinnedPorts::default()
+        };
+        assert!(running.mismatches(PinnedPorts::default()).is_empty());
+        assert!(running.mismatches(running).is_empty());
+
+        let mismatches = running.mismatches(PinnedPorts {
+            frontend: Some(5123),
+            backend: Some(8456),
+            db: Some(4123),
+        });
+        assert_eq!(
+            mismatches,
+            vec![
+                "--frontend-port 5123 ignored: the running server uses a random frontend port",
+                "--backend-port 8456 ignored: the running server uses port 8123",
+            ]
+        );
+    }
+}
@@ -15,10 +15,10 @@ use tokio::io::{AsyncBufReadExt, BufReader};
 use tokio::process::{Child, Command};
 use tokio::sync::Mutex;
 use tokio::time::{Duration, timeout};
-use tracing::{debug, warn};
+use tracing::{debug, info, warn};
 
-use crate::dev::common::DevProcess;
-use crate::dev::otel::forward_log_to_flux;
+use crate::dev::common::{DevProcess, stop_child_tree};
+use crate::dev::otel::FluxLogForwarder;
 use crate::dev::token;
 use crate::external::ExternalTool;
 use crate::external::bun::Bun;
@@ -42,6 +42,9 @@ const DEFAULT_USER: &str = "postgres";
 /// Default PGlite database name.
 const DEFAULT_DB: &str = "postgres";
 
+/// npm package that serves PGlite over the PostgreSQL wire protocol.
+const PGLITE_PACKAGE: &str = "@electric-sql/pglite-socket";
+
 /// Self-contained embedded database lifecycle manager.
 /// Encapsulates PGlite spawning, readiness polling, credential rotation,
 /// and health monitoring. ProcessManager interacts only through this API.
@@ -49,6 +52,7 @@ pub struct EmbeddedDb {
     child: Arc<Mutex<Option<Child>>>,
     port: u16,
     password: String,
+    ready_after: std::sync::Mutex<Duration>,
 }
 
 // `Child` does not implement `Debug`, so we provide a manual impl.
@@ -57,6 +61,7 @@ impl std::fmt::Debug for EmbeddedDb {
         f.debug_struct("EmbeddedDb")
             .field("port", &self.port)
             .field("password", &"<redacted>")
+            .field("ready_after", &self.ready_after())
             .finish()
     }
 }
@@ -73,10 +78,16 @@ impl EmbeddedDb {
         let bun = Bun::new().await?;
         let password = token::generate();
 
+        let started_at = std::time::Instant::now();
         let child = Self::spawn_pglite(&bun, app_dir, host, port, app_slug)?;
         let child = Arc::new(Mutex::new(Some(child)));
 
         Self::wait_for_ready(port).await?;
+        let ready_after = started_at.elapsed();
+        info!(
+            "Embedded database ready in {:.1}s",
+            ready_after.as_secs_f64()
+        );
         Self::rotate_password(port, &password).await?;
         debug!("Embedded database password rotated successfully");
 
@@ -86,13 +97,49 @@ impl EmbeddedDb {
             child,
             port,
             password,
+            ready_after: std::sync::Mutex::new(ready_after),
         })
     }
 
+    /// Replace PGlite with a fresh in-memory instance on the same port and password.
+    ///
+    /// Everything stored in the previous instance is lost.
+    pub async fn restart(&self, app_dir: &Path, host: &str, app_slug: &str) -> Result<(), String> {
+        stop_child_tree("db", &self.child).await;
+
+        let bun = Bun::new().await?;
+        let started_at = std::time::Instant::now();
+        let child = Self::spawn_pglite(&bun, app_dir, host, self.port, app_slug)?;
+        *self.child.lock().await = Some(child);
+
+        Self::wait_for_ready(self.port).await?;
+        let ready_after = started_at.elapsed();
+        info!(
+            "Embedded database ready in {:.1}s",
+            ready_after.as_secs_f64()
+        );
+        *self
+            .ready_after
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner) = ready_after;
+        Self::rotate_password(self.port, &self.password).await?;
+
+        Self::spawn_health_monitor(Arc::clone(&self.child));
+        Ok(())
+    }
+
     pub fn password(&self) -> &str {
         &self.password
     }
 
+    /// Time PGlite took to accept PostgreSQL connections after it was spawned.
+    pub fn ready_after(&self) -> Duration {
+        *self
+            .ready_after
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
     /// Access the child handle for parallel shutdown operations
     /// used by `ProcessManager::stop()`.
     pub fn child_handle(&self) -> &Arc<Mutex<Option<Child>>> {
@@ -112,6 +159,45 @@ impl EmbeddedDb {
         }
     }
 
+    /// Kill PGlite processes left behind for `app_dir` by a crashed or
+    /// force-killed dev server. Returns the number of processes killed.
+    ///
+    /// Only processes whose working directory is `app_dir` are touched, so
+    /// embedded databases belonging to other apps keep running.
+    pub(crate) fn kill_orphans(app_dir: &Path) -> usize {
+        use sysinfo::{ProcessRefreshKind, ProcessesToUpdate, Signal, System, UpdateKind};
+
+        let app_dir = app_dir
+            .canonicalize()
+            .unwrap_or_else(|_| app_dir.to_path_buf());
+        let mut sys = System::new();
+        sys.refresh_processes_specifics(
+            ProcessesToUpdate::All,
+            true,
+            ProcessRefreshKind::nothing()
+                .with_cmd(UpdateKind::Always)
+                .with_cwd(UpdateKind::Always),
+        );
+
+        let mut killed = 0;
+        for (pid, process) in sys.processes() {
+            let is_pglite = process
+                .cmd()
+                .iter()
+                .any(|arg| arg.to_string_lossy().contains(PGLITE_PACKAGE));
+            if !is_pglite || process.cwd() != Some(app_dir.as_path()) {
+                continue;
+            }
+            if process.kill_with(Signal::Kill) == Some(true) {
+                debug!(pid = ?pid, "Killed orphaned embedded database process");
+                killed += 1;
+            } else {
+                warn!(pid = ?pid, "Failed to kill orphaned embedded database process");
+            }
+        }
+        killed
+    }
+
     // -- private helpers --
 
     fn spawn_pglite(
@@ -124,7 +210,7 @@ impl EmbeddedDb {
         let mut cmd = Command::new(bun.binary_path());
         cmd.args([
             "x",
-            "@electric-sql/pglite-socket",
+            PGLITE_PACKAGE,
             "--db=memory://",
             &format!("--host={host}"),
             "--debug=0",
@@ -140,12 +226,11 @@ impl EmbeddedDb {
             .map_err(|err| format!("Failed to start embedded database: {err}"))?;
 
         // Forward stdout/stderr to flux with "db" source prefix
-        let service_name = format!("{app_slug}_db");
-        let app_path = app_dir.display().to_string();
+        let forwarder =
+            FluxLogForwarder::spawn(format!("{app_slug}_db"), app_dir.display().to_string());
 
         if let Some(stdout) = child.stdout.take() {
-            let svc = service_name.clone();
-            let path = app_path.clone();
+            let forwarder = forwarder.clone();
             tokio::spawn(async move {
                 let reader = BufReader::new(stdout);
                 let mut lines = reader.lines();
@@ -154,7 +239,7 @@ impl EmbeddedDb {
                         "{}",
                         apx_common::format::format_process_log_line("db", &line)
                     );
-                    forward_log_to_flux(&line, "INFO", &svc, &path).await;
+                    forwarder.forward(&line, "INFO");
                 }
             });
         }
@@ -169,7 +254,7 @@ impl EmbeddedDb {
                         apx_common::format::format_process_log_line("db", &line)
                     );
                     let severity = apx_common::format::parse_python_severity(&line);
-                    forward_log_to_flux(&line, severity, &service_name, &app_path).await;
+                    forwarder.forward(&line, severity);
                 }
             });
         }
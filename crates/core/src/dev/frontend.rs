@@ -10,7 +10,9 @@ use std::sync::Arc;
 use tokio::process::Child;
 use tokio::sync::Mutex;
 
-use crate::dev::common::{DevProcess, ProbeResult, http_health_probe};
+use crate::dev::common::{
+    DevProcess, ProbeResult, StartupTiming, http_health_probe, stop_child_tree, watch_readiness,
+};
 use crate::dev::token;
 use crate::external::uv::ApxTool;
 use apx_common::hosts::CLIENT_HOST;
@@ -39,6 +41,7 @@ pub struct FrontendConfig {
 /// `ProcessManager` interacts only through this API.
 pub struct Frontend {
     child: Arc<Mutex<Option<Child>>>,
+    timing: Arc<StartupTiming>,
     cfg: FrontendConfig,
 }
 
@@ -56,6 +59,7 @@ impl Frontend {
     pub fn new(cfg: FrontendConfig) -> Self {
         Self {
             child: Arc::new(Mutex::new(None)),
+            timing: Arc::default(),
             cfg,
         }
     }
@@ -64,22 +68,41 @@ impl Frontend {
     ///
     /// Frontend logs are NOT piped through apx stdout/stderr — the frontend
     /// process sends logs directly to flux via OTEL SDK. See entrypoint.ts.
+    /// Startup is timed from here until Vite first answers HTTP.
     pub async fn spawn(&self) -> Result<(), String> {
+        let generation = self.timing.start();
         let cmd = self.build_command().await?;
         let child = cmd.spawn().map_err(String::from)?;
 
         let mut guard = self.child.lock().await;
         *guard = Some(child);
+        watch_readiness(
+            self.label(),
+            self.cfg.frontend_port,
+            Arc::clone(&self.timing),
+            generation,
+        );
         Ok(())
     }
 
+    /// Stop the current frontend process tree and spawn a new one.
+    pub async fn restart(&self) -> Result<(), String> {
+        stop_child_tree(self.label(), &self.child).await;
+        self.spawn().await
+    }
+
+    /// Time the frontend took to answer HTTP after it was spawned, once it has.
+    pub fn ready_after(&self) -> Option<std::time::Duration> {
+        self.timing.ready_after()
+    }
+
     // -- private: command construction --
 
     /// Build the `apx frontend dev` command with all env vars and OTEL config.
     async fn build_command(&self) -> Result<crate::external::ToolCommand, String> {
         let cfg = &self.cfg;
 
-        let cmd = ApxTool::new_apx()
+        let mut cmd = ApxTool::new_apx()
             .await?
             .cmd()
             .args(["frontend", "dev"])
@@ -104,6 +127,10 @@ impl Frontend {
             )
             .env(apx_common::hosts::ENV_FRONTEND_HOST, CLIENT_HOST)
             .env("OTEL_SERVICE_NAME", format!("{}_ui", cfg.app_slug));
+        // The entrypoint's OTLP exporter sends it as a bearer token
+        if let Some(flux_token) = apx_common::flux_token() {
+            cmd = cmd.env(apx_common::FLUX_TOKEN_ENV, flux_token);
+        }
 
         Ok(cmd)
     }
@@ -135,7 +162,10 @@ impl DevProcess for Frontend {
         drop(guard);
 
         match http_health_probe(CLIENT_HOST, self.cfg.frontend_port).await {
-            ProbeResult::Responded => "healthy",
+            ProbeResult::Responded => {
+                self.timing.mark_ready(self.label());
+                "healthy"
+            }
             ProbeResult::Failed => "starting",
         }
     }
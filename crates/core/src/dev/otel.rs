@@ -4,9 +4,14 @@
 //! to the flux collector. Used by both subprocess log forwarding and browser log forwarding.
 
 use std::path::Path;
-use std::sync::LazyLock;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, LazyLock};
 use std::time::Duration;
 
+use tokio::sync::mpsc;
+use tokio::time::MissedTickBehavior;
+use tracing::warn;
+
 use apx_common::format::severity_to_number;
 use apx_common::hosts::CLIENT_HOST;
 
@@ -22,6 +27,9 @@ static FLUX_CLIENT: LazyLock<reqwest::Client> = LazyLock::new(|| {
         .unwrap_or_else(|_| reqwest::Client::new())
 });
 
+/// Bearer token sent to flux, read once from `APX_FLUX_TOKEN`.
+static FLUX_TOKEN: LazyLock<Option<String>> = LazyLock::new(apx_common::flux_token);
+
 /// Build an OTLP JSON log payload with nanosecond timestamp.
 pub fn build_otlp_log_payload(
     message: &str,
@@ -30,8 +38,63 @@ pub fn build_otlp_log_payload(
     service_name: &str,
     app_path: &str,
 ) -> serde_json::Value {
-    let severity_number = severity_to_number(level);
+    build_otlp_log_payload_with_attributes(
+        message,
+        level,
+        timestamp_ns,
+        service_name,
+        app_path,
+        &serde_json::Map::new(),
+    )
+}
+
+/// Build an OTLP JSON log payload carrying extra log record attributes.
+///
+/// Each attribute is encoded as an OTLP `AnyValue`; nested objects and arrays
+/// are kept as their JSON text.
+pub fn build_otlp_log_payload_with_attributes(
+    message: &str,
+    level: &str,
+    timestamp_ns: i64,
+    service_name: &str,
+    app_path: &str,
+    attributes: &serde_json::Map<String, serde_json::Value>,
+) -> serde_json::Value {
+    otlp_resource_logs(
+        service_name,
+        app_path,
+        vec![otlp_log_record(message, level, timestamp_ns, attributes)],
+    )
+}
+
+/// Build a single OTLP JSON `logRecord`.
+fn otlp_log_record(
+    message: &str,
+    level: &str,
+    timestamp_ns: i64,
+    attributes: &serde_json::Map<String, serde_json::Value>,
+) -> serde_json::Value {
+    let mut log_record = serde_json::json!({
+        "timeUnixNano": timestamp_ns.to_string(),
+        "severityNumber": severity_to_number(level),
+        "severityText": level.to_uppercase(),
+        "body": { "stringValue": message }
+    });
+    if !attributes.is_empty() {
+        log_record["attributes"] = attributes
+            .iter()
+            .map(|(key, value)| serde_json::json!({ "key": key, "value": to_any_value(value) }))
+            .collect();
+    }
+    log_record
+}
 
+/// Wrap log records in an OTLP JSON payload for one service of one app.
+fn otlp_resource_logs(
+    service_name: &str,
+    app_path: &str,
+    log_records: Vec<serde_json::Value>,
+) -> serde_json::Value {
     serde_json::json!({
         "resourceLogs": [{
             "resource": {
@@ -48,17 +111,119 @@ pub fn build_otlp_log_payload(
             },
             "scopeLogs": [{
                 "scope": {},
-                "logRecords": [{
-                    "timeUnixNano": timestamp_ns.to_string(),
-                    "severityNumber": severity_number,
-                    "severityText": level.to_uppercase(),
-                    "body": { "stringValue": message }
-                }]
+                "logRecords": log_records
             }]
         }]
     })
 }
 
+/// Encode a JSON value as an OTLP `AnyValue`.
+fn to_any_value(value: &serde_json::Value) -> serde_json::Value {
+    match value {
+        serde_json::Value::String(s) => serde_json::json!({ "stringValue": s }),
+        serde_json::Value::Bool(b) => serde_json::json!({ "boolValue": b }),
+        serde_json::Value::Number(n) if n.is_i64() || n.is_u64() => {
+            serde_json::json!({ "intValue": n.to_string() })
+        }
+        serde_json::Value::Number(n) => serde_json::json!({ "doubleValue": n }),
+        other => serde_json::json!({ "stringValue": other.to_string() }),
+    }
+}
+
+/// Keys recognized as the message of a JSON log line, in priority order.
+const MESSAGE_KEYS: [&str; 3] = ["message", "msg", "event"];
+/// Keys recognized as the severity of a JSON log line, in priority order.
+const LEVEL_KEYS: [&str; 4] = ["level", "levelname", "severity", "log.level"];
+/// Keys recognized as the timestamp of a JSON log line, in priority order.
+const TIMESTAMP_KEYS: [&str; 4] = ["timestamp", "time", "ts", "@timestamp"];
+
+/// A subprocess log line emitted as a JSON object (e.g. by a JSON uvicorn formatter).
+#[derive(Debug, Clone, PartialEq)]
+pub struct StructuredLogLine {
+    /// The log message.
+    pub message: String,
+    /// Severity from the line, if it had one.
+    pub level: Option<String>,
+    /// Event time from the line in nanoseconds, if it had a parseable one.
+    pub timestamp_ns: Option<i64>,
+    /// Remaining fields, forwarded as log record attributes.
+    pub attributes: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Parse a log line that is a JSON object with a recognizable message field.
+///
+/// Returns `None` for anything else, so callers keep treating the line as plain text.
+pub fn parse_structured_log_line(line: &str) -> Option<StructuredLogLine> {
+    let trimmed = line.trim();
+    if !trimmed.starts_with('{') {
+        return None;
+    }
+    let serde_json::Value::Object(fields) = serde_json::from_str(trimmed).ok()? else {
+        return None;
+    };
+
+    let (message_key, message) = MESSAGE_KEYS.iter().find_map(|key| match fields.get(*key) {
+        Some(serde_json::Value::String(s)) => Some((*key, s.clone())),
+        _ => None,
+    })?;
+    let level = LEVEL_KEYS.iter().find_map(|key| match fields.get(*key) {
+        Some(serde_json::Value::String(s)) if !s.is_empty() => Some((*key, s.clone())),
+        _ => None,
+    });
+    let timestamp = TIMESTAMP_KEYS
+        .iter()
+        .find_map(|key| Some((*key, parse_timestamp_ns(fields.get(*key)?)?)));
+
+    let consumed = [
+        Some(message_key),
+        level.as_ref().map(|(key, _)| *key),
+        timestamp.map(|(key, _)| key),
+    ];
+    let attributes = fields
+        .into_iter()
+        .filter(|(key, _)| !consumed.contains(&Some(key.as_str())))
+        .collect();
+
+    Some(StructuredLogLine {
+        message,
+        level: level.map(|(_, level)| level),
+        timestamp_ns: timestamp.map(|(_, ts)| ts),
+        attributes,
+    })
+}
+
+/// Parse an RFC 3339 string or a Unix epoch number (seconds, milliseconds,
+/// microseconds or nanoseconds, chosen by magnitude) into nanoseconds.
+///
+/// Returns `None` when the result does not fit in an `i64`.
+fn parse_timestamp_ns(value: &serde_json::Value) -> Option<i64> {
+    match value {
+        serde_json::Value::String(s) => chrono::DateTime::parse_from_rfc3339(s)
+            .ok()?
+            .timestamp_nanos_opt(),
+        serde_json::Value::Number(n) => {
+            let magnitude = n.as_f64()?.abs();
+            let (scale, scale_f64) = if magnitude >= 1e17 {
+                (1, 1.0)
+            } else if magnitude >= 1e14 {
+                (1_000, 1e3)
+            } else if magnitude >= 1e11 {
+                (1_000_000, 1e6)
+            } else {
+                (1_000_000_000, 1e9)
+            };
+            if let Some(int) = n.as_i64() {
+                return int.checked_mul(scale);
+            }
+            let ns = n.as_f64()? * scale_f64;
+            // Reason: the range check keeps the cast from saturating; only the fraction is dropped
+            #[allow(clippy::cast_possible_truncation)]
+            (ns.abs() < 2f64.powi(63)).then(|| ns as i64)
+        }
+        _ => None,
+    }
+}
+
 /// Build an OTLP JSON log payload from millisecond timestamp.
 /// Convenience wrapper for browser logs which use milliseconds.
 pub fn build_otlp_log_payload_from_ms(
@@ -78,22 +243,281 @@ pub fn build_otlp_log_payload_from_ms(
     )
 }
 
-/// Forward a log line to flux via OTLP HTTP.
-/// This is fire-and-forget; errors are silently ignored to avoid log loops.
-pub async fn forward_log_to_flux(message: &str, level: &str, service_name: &str, app_path: &str) {
+/// Turn a subprocess log line into an OTLP `logRecord`, or `None` if it is noise.
+///
+/// Lines that are JSON objects with a message field (see [`parse_structured_log_line`])
+/// use their own level and timestamp, with the remaining fields sent as attributes.
+/// `level` is the fallback when the line carries none.
+fn prepare_log_record(line: &str, level: &str) -> Option<serde_json::Value> {
+    let structured = parse_structured_log_line(line);
+    let message = structured.as_ref().map_or(line, |s| s.message.as_str());
+
     // Skip noisy internal logs
     if apx_common::should_skip_log_message(message) {
-        return;
+        return None;
     }
 
-    let timestamp_ns = chrono::Utc::now().timestamp_nanos_opt().unwrap_or(0);
-    let payload = build_otlp_log_payload(message, level, timestamp_ns, service_name, app_path);
+    let timestamp_ns = structured
+        .as_ref()
+        .and_then(|s| s.timestamp_ns)
+        .unwrap_or_else(|| chrono::Utc::now().timestamp_nanos_opt().unwrap_or(0));
+    Some(match &structured {
+        Some(s) => otlp_log_record(
+            message,
+            s.level.as_deref().unwrap_or(level),
+            timestamp_ns,
+            &s.attributes,
+        ),
+        None => otlp_log_record(message, level, timestamp_ns, &serde_json::Map::new()),
+    })
+}
+
+/// POST an OTLP JSON payload to flux, ignoring failures to avoid log loops.
+async fn send_to_flux(payload: &serde_json::Value) {
     let endpoint = format!("http://{CLIENT_HOST}:{FLUX_PORT}/v1/logs");
 
-    let _ = FLUX_CLIENT
+    let mut request = FLUX_CLIENT
         .post(&endpoint)
         .header("Content-Type", "application/json")
-        .json(&payload)
-        .send()
-        .await;
+        .json(payload);
+    if let Some(token) = FLUX_TOKEN.as_deref() {
+        request = request.bearer_auth(token);
+    }
+    let _ = request.send().await;
+}
+
+/// Forward a log line to flux via OTLP HTTP.
+/// This is fire-and-forget; errors are silently ignored to avoid log loops.
+///
+/// Sends one request per line; subprocess readers should use [`FluxLogForwarder`].
+pub async fn forward_log_to_flux(message: &str, level: &str, service_name: &str, app_path: &str) {
+    let Some(record) = prepare_log_record(message, level) else {
+        return;
+    };
+    send_to_flux(&otlp_resource_logs(service_name, app_path, vec![record])).await;
+}
+
+/// Maximum number of records sent to flux in one OTLP export.
+const BATCH_MAX_RECORDS: usize = 100;
+/// How long a partial batch may wait before it is flushed.
+const BATCH_FLUSH_INTERVAL: Duration = Duration::from_millis(200);
+/// Records buffered between the log readers and the flush task.
+const BATCH_CHANNEL_CAPACITY: usize = 4096;
+
+/// Batches subprocess log lines and forwards them to flux in order.
+///
+/// Lines are queued on a bounded channel and exported every
+/// [`BATCH_MAX_RECORDS`] records or [`BATCH_FLUSH_INTERVAL`], whichever comes
+/// first. Clones share one queue, so stdout and stderr of a process keep their
+/// relative order. When flux cannot keep up, new lines are dropped with a
+/// warning instead of blocking the reader.
+#[derive(Clone, Debug)]
+pub struct FluxLogForwarder {
+    tx: mpsc::Sender<serde_json::Value>,
+    dropped: Arc<AtomicU64>,
+}
+
+impl FluxLogForwarder {
+    /// Start a flush task exporting under the given service name and app path.
+    ///
+    /// The task ends, after flushing, once every clone has been dropped.
+    pub fn spawn(service_name: String, app_path: String) -> Self {
+        let (tx, rx) = mpsc::channel(BATCH_CHANNEL_CAPACITY);
+        tokio::spawn(run_batcher(rx, move |records| {
+            let payload = otlp_resource_logs(&service_name, &app_path, records);
+            async move { send_to_flux(&payload).await }
+        }));
+        Self {
+            tx,
+            dropped: Arc::default(),
+        }
+    }
+
+    /// Queue a log line, with `level` as the fallback severity.
+    pub fn forward(&self, line: &str, level: &str) {
+        let Some(record) = prepare_log_record(line, level) else {
+            return;
+        };
+        match self.tx.try_send(record) {
+            Ok(()) => {
+                let dropped = self.dropped.swap(0, Ordering::Relaxed);
+                if dropped > 0 {
+                    warn!("Resumed forwarding logs to flux after dropping {dropped} line(s)");
+                }
+            }
+            Err(mpsc::error::TrySendError::Full(_)) => {
+                if self.dropped.fetch_add(1, Ordering::Relaxed) == 0 {
+                    warn!("Flux is not keeping up; dropping forwarded log lines");
+                }
+            }
+            Err(mpsc::error::TrySendError::Closed(_)) => {}
+        }
+    }
+}
+
+/// Accumulate records from `rx` and hand them to `flush` in order, in batches.
+async fn run_batcher<F, Fut>(mut rx: mpsc::Receiver<serde_json::Value>, mut flush: F)
+where
+    F: FnMut(Vec<serde_json::Value>) -> Fut,
+    Fut: Future<Output = ()>,
+{
+    let mut batch = Vec::with_capacity(BATCH_MAX_RECORDS);
+    let mut ticker = tokio::time::interval(BATCH_FLUSH_INTERVAL);
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+    loop {
+        tokio::select! {
+            received = rx.recv() => {
+                let Some(record) = received else {
+                    break;
+                };
+                batch.push(record);
+                if batch.len() >= BATCH_MAX_RECORDS {
+                    flush(std::mem::take(&mut batch)).await;
+                }
+            }
+            _ = ticker.tick() => {
+                if !batch.is_empty() {
+                    flush(std::mem::take(&mut batch)).await;
+                }
+            }
+        }
+    }
+    if !batch.is_empty() {
+        flush(batch).await;
+    }
+}
+
+#[cfg(test)]
+// Reason: panicking on failure is idiomatic in tests
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_structured_log_line() {
+        let line = parse_structured_log_line(
+            r#"{"timestamp": "2026-01-01T00:00:00.5Z", "level": "warning", "message": "slow request", "duration": 1.5, "path": "/api/items", "status": 200}"#,
+        )
+        .unwrap();
+        assert_eq!(line.message, "slow request");
+        assert_eq!(line.level.as_deref(), Some("warning"));
+        assert_eq!(line.timestamp_ns, Some(1_767_225_600_500_000_000));
+        assert_eq!(
+            line.attributes.keys().collect::<Vec<_>>(),
+            vec!["duration", "path", "status"]
+        );
+
+        let line = parse_structured_log_line(r#"{"msg": "started", "ts": 1767225600}"#).unwrap();
+        assert_eq!(line.level, None);
+        assert_eq!(line.timestamp_ns, Some(1_767_225_600_000_000_000));
+
+        assert_eq!(
+            parse_structured_log_line("INFO:     Application startup complete."),
+            None
+        );
+        assert_eq!(parse_structured_log_line(r#"{"level": "info"}"#), None);
+        assert_eq!(parse_structured_log_line("{not json"), None);
+    }
+
+    #[test]
+    fn test_parse_timestamp_ns_units() {
+        let ns = |value: serde_json::Value| parse_timestamp_ns(&value);
+        let expected = Some(1_767_225_600_123_000_000);
+        assert_eq!(
+            ns(serde_json::json!(1_767_225_600)),
+            Some(1_767_225_600_000_000_000)
+        );
+        assert_eq!(
+            ns(serde_json::json!(1_767_225_600.5)),
+            Some(1_767_225_600_500_000_000)
+        );
+        assert_eq!(ns(serde_json::json!(1_767_225_600_123_i64)), expected);
+        assert_eq!(ns(serde_json::json!(1_767_225_600_123_000_i64)), expected);
+        assert_eq!(
+            ns(serde_json::json!(1_767_225_600_123_000_000_i64)),
+            expected
+        );
+
+        // Values that overflow nanoseconds are rejected instead of saturating
+        assert_eq!(ns(serde_json::json!(99_999_999_999_999_i64)), None);
+        assert_eq!(ns(serde_json::json!(u64::MAX)), None);
+        assert_eq!(ns(serde_json::json!(1e300)), None);
+    }
+
+    #[test]
+    fn test_payload_with_attributes() {
+        let line = parse_structured_log_line(
+            r#"{"level": "ERROR", "message": "boom", "ctx": {"a": 1}, "retry": false, "status": 500}"#,
+        )
+        .unwrap();
+        let payload = build_otlp_log_payload_with_attributes(
+            &line.message,
+            line.level.as_deref().unwrap(),
+            1,
+            "demo_app",
+            "/tmp/app",
+            &line.attributes,
+        );
+        let record = &payload["resourceLogs"][0]["scopeLogs"][0]["logRecords"][0];
+        assert_eq!(record["severityNumber"], 17);
+        assert_eq!(record["body"]["stringValue"], "boom");
+        assert_eq!(
+            record["attributes"],
+            serde_json::json!([
+                { "key": "ctx", "value": { "stringValue": "{\"a\":1}" } },
+                { "key": "retry", "value": { "boolValue": false } },
+                { "key": "status", "value": { "intValue": "500" } },
+            ])
+        );
+
+        let plain = build_otlp_log_payload("hi", "INFO", 1, "demo_app", "/tmp/app");
+        assert!(
+            plain["resourceLogs"][0]["scopeLogs"][0]["logRecords"][0]
+                .get("attributes")
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_batcher_flushes_in_order() {
+        let (tx, rx) = mpsc::channel(BATCH_CHANNEL_CAPACITY);
+        let batches = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let sink = Arc::clone(&batches);
+        let task = tokio::spawn(run_batcher(rx, move |records| {
+            sink.lock().unwrap().push(records);
+            async {}
+        }));
+
+        for i in 0..250 {
+            tx.send(serde_json::json!(i)).await.unwrap();
+        }
+        drop(tx);
+        task.await.unwrap();
+
+        let batches = std::mem::take(&mut *batches.lock().unwrap());
+        assert!(batches.iter().all(|b| b.len() <= BATCH_MAX_RECORDS));
+        let flat: Vec<_> = batches.iter().flatten().cloned().collect();
+        assert_eq!(
+            flat,
+            (0..250).map(|i| serde_json::json!(i)).collect::<Vec<_>>()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_batcher_flushes_partial_batch_on_interval() {
+        let (tx, rx) = mpsc::channel(BATCH_CHANNEL_CAPACITY);
+        let (flushed_tx, mut flushed_rx) = mpsc::unbounded_channel();
+        tokio::spawn(run_batcher(rx, move |records| {
+            let _ = flushed_tx.send(records);
+            async {}
+        }));
+
+        tx.send(serde_json::json!("only")).await.unwrap();
+        let batch = tokio::time::timeout(BATCH_FLUSH_INTERVAL * 5, flushed_rx.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(batch, vec![serde_json::json!("only")]);
+    }
 }
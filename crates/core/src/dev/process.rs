@@ -15,14 +15,51 @@ use sysinfo::{Pid, Signal, System};
 use tokio::process::Child;
 use tokio::sync::Mutex;
 use tokio::time::{Duration, timeout};
-use tracing::{debug, warn};
+use tracing::{debug, info, warn};
 
 use crate::common::read_project_metadata;
 use crate::dev::backend::{Backend, BackendConfig};
 use crate::dev::common::{DevProcess, build_parent_map};
 use crate::dev::embedded_db::EmbeddedDb;
 use crate::dev::frontend::{Frontend, FrontendConfig};
-use crate::dotenv::DotenvFile;
+use crate::dotenv::{DotenvFile, UnresolvedVar};
+
+/// Time each dev process took to become ready, as reported by
+/// [`ProcessManager::readiness`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ProcessReadiness {
+    /// Frontend (Vite) time to first successful HTTP probe.
+    pub frontend: Option<Duration>,
+    /// Backend (uvicorn) time to first successful HTTP probe.
+    pub backend: Option<Duration>,
+    /// Embedded database time to PostgreSQL protocol readiness.
+    pub db: Option<Duration>,
+}
+
+/// A dev process that can be restarted on its own (see [`ProcessManager::restart_service`]).
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize, clap::ValueEnum,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum DevService {
+    /// The Vite dev server.
+    Frontend,
+    /// The uvicorn backend.
+    Backend,
+    /// The embedded PGlite database. Its in-memory data does not survive a restart.
+    Db,
+}
+
+impl DevService {
+    /// Name used on the command line and in the restart endpoint.
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Frontend => "frontend",
+            Self::Backend => "backend",
+            Self::Db => "db",
+        }
+    }
+}
 
 /// Manages the lifecycle of dev server child processes (backend, frontend, db).
 #[derive(Debug)]
@@ -55,7 +92,9 @@ impl ProcessManager {
         let has_ui = metadata.has_ui();
 
         let dotenv = DotenvFile::read(&app_dir.join(".env"))?;
-        let dotenv_vars = Arc::new(Mutex::new(dotenv.get_vars()));
+        let dotenv_vars = Arc::new(Mutex::new(
+            dotenv.get_vars_interpolated(UnresolvedVar::Keep)?,
+        ));
         let app_slug = metadata.app_slug.clone();
         let app_entrypoint = metadata.app_entrypoint.clone();
         let dev_config = metadata.dev_config;
@@ -169,6 +208,22 @@ impl ProcessManager {
         });
     }
 
+    /// Restart a single process, leaving the others running.
+    pub async fn restart_service(&self, service: DevService) -> Result<(), String> {
+        info!("Restarting {}", service.as_str());
+        match service {
+            DevService::Frontend => match &self.frontend {
+                Some(frontend) => frontend.restart().await,
+                None => Err("This app has no frontend".to_string()),
+            },
+            DevService::Backend => self.backend.restart().await,
+            DevService::Db => match self.db.get() {
+                Some(db) => db.restart(&self.app_dir, &self.host, &self.app_slug).await,
+                None => Err("Embedded database is not running".to_string()),
+            },
+        }
+    }
+
     /// Return the dev authentication token.
     pub fn dev_token(&self) -> &str {
         self.backend.dev_token()
@@ -254,6 +309,16 @@ impl ProcessManager {
         (frontend_status, backend_status, db_status)
     }
 
+    /// How long each managed process took to become ready since its last
+    /// (re)start. `None` until the process has answered its first probe.
+    pub fn readiness(&self) -> ProcessReadiness {
+        ProcessReadiness {
+            frontend: self.frontend.as_ref().and_then(|f| f.ready_after()),
+            backend: self.backend.ready_after(),
+            db: self.db.get().map(EmbeddedDb::ready_after),
+        }
+    }
+
     /// Returns true if this project has a frontend (UI).
     pub fn has_ui(&self) -> bool {
         self.frontend.is_some()
@@ -2,7 +2,7 @@
 
 use axum::Json;
 use axum::Router;
-use axum::extract::State;
+use axum::extract::{Query, State};
 use axum::http::{HeaderMap, StatusCode};
 use axum::routing::get;
 use std::collections::HashMap;
@@ -19,7 +19,7 @@ use crate::api_generator::start_openapi_watcher;
 use crate::dev::common::{Shutdown, lock_path, remove_lock};
 use crate::dev::logging::BrowserLogPayload;
 use crate::dev::otel::build_otlp_log_payload_from_ms;
-use crate::dev::process::ProcessManager;
+use crate::dev::process::{DevService, ProcessManager};
 use crate::dev::proxy;
 use crate::dev::watcher::{PollingWatcher, spawn_polling_watcher};
 use crate::dotenv::DotenvFile;
@@ -45,6 +45,13 @@ struct HealthResponse {
     db_status: String,
     /// True if any critical process (frontend/backend) has permanently failed and cannot recover
     failed: bool,
+    /// Milliseconds each process took to become ready since its last (re)start
+    #[serde(skip_serializing_if = "Option::is_none")]
+    frontend_ready_ms: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    backend_ready_ms: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    db_ready_ms: Option<u64>,
 }
 
 /// All values needed to start the dev server's Axum instance + process manager.
@@ -76,7 +83,7 @@ pub async fn run_server(config: ServerConfig) -> Result<(), String> {
         dev_token,
     } = config;
     // Ensure flux is running for log collection
-    if let Err(e) = flux::ensure_running() {
+    if let Err(e) = flux::ensure_running_async().await {
         warn!(
             "Failed to start flux: {}. Logging may not work correctly.",
             e
@@ -205,6 +212,7 @@ pub async fn run_server(config: ServerConfig) -> Result<(), String> {
         .route("/health", get(health))
         .route("/logs", axum::routing::post(browser_logs))
         .route("/stop", get(stop))
+        .route("/restart", axum::routing::post(restart))
         .with_state(state);
 
     let base_router = Router::new()
@@ -366,6 +374,8 @@ async fn health(State(state): State<AppState>) -> (StatusCode, Json<HealthRespon
     let probe_start = std::time::Instant::now();
     let (frontend_status, backend_status, db_status) = state.process_manager.status().await;
     let probe_elapsed_ms = probe_start.elapsed().as_millis();
+    // Read after probing: a successful probe is what records readiness.
+    let readiness = state.process_manager.readiness();
     let has_ui = state.process_manager.has_ui();
 
     // Check if any critical process has permanently failed (crashed/exited)
@@ -401,10 +411,17 @@ async fn health(State(state): State<AppState>) -> (StatusCode, Json<HealthRespon
             backend_status,
             db_status,
             failed,
+            frontend_ready_ms: readiness.frontend.map(duration_ms),
+            backend_ready_ms: readiness.backend.map(duration_ms),
+            db_ready_ms: readiness.db.map(duration_ms),
         }),
     )
 }
 
+fn duration_ms(duration: Duration) -> u64 {
+    duration.as_millis() as u64
+}
+
 async fn browser_logs(
     State(state): State<AppState>,
     Json(payload): Json<BrowserLogPayload>,
@@ -462,6 +479,36 @@ async fn stop(headers: HeaderMap, State(state): State<AppState>) -> StatusCode {
     StatusCode::OK
 }
 
+/// Query of `POST /_apx/restart`.
+#[derive(serde::Deserialize)]
+struct RestartQuery {
+    service: DevService,
+}
+
+/// Restart a single dev process, answering once it has been respawned.
+async fn restart(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+    Query(query): Query<RestartQuery>,
+) -> (StatusCode, String) {
+    use crate::dev::token::DEV_TOKEN_HEADER;
+
+    let request_token = headers.get(DEV_TOKEN_HEADER).and_then(|v| v.to_str().ok());
+
+    if request_token != Some(state.process_manager.dev_token()) {
+        warn!("Unauthorized restart request (missing or invalid token)");
+        return (StatusCode::UNAUTHORIZED, String::new());
+    }
+
+    match state.process_manager.restart_service(query.service).await {
+        Ok(()) => (StatusCode::OK, String::new()),
+        Err(err) => {
+            warn!("Failed to restart {}: {err}", query.service.as_str());
+            (StatusCode::INTERNAL_SERVER_ERROR, err)
+        }
+    }
+}
+
 /// Resolve the Databricks profile name from env var or `.env` file.
 pub fn resolve_databricks_profile(app_dir: &std::path::Path) -> Option<String> {
     std::env::var("DATABRICKS_CONFIG_PROFILE").ok().or_else(|| {
@@ -20,3 +20,8 @@ pub fn generate() -> String {
         .map(char::from)
         .collect()
 }
+
+/// Whether `token` has the shape of a generated token.
+pub fn is_well_formed(token: &str) -> bool {
+    token.len() == TOKEN_LENGTH && token.chars().all(|c| c.is_ascii_alphanumeric())
+}
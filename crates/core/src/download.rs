@@ -8,7 +8,9 @@ use std::io::Read;
 use std::path::{Path, PathBuf};
 
 use sha2::{Digest, Sha256};
-use tracing::debug;
+use tracing::{debug, warn};
+
+use crate::external::bun::BUN_VERSION;
 
 // ---------------------------------------------------------------------------
 // Download: bun
@@ -31,7 +33,20 @@ const BUN_EXE: &str = "bun.exe";
 #[cfg(not(target_os = "windows"))]
 const BUN_EXE: &str = "bun";
 
-const BUN_VERSION: &str = "1.3.8";
+/// Pinned SHA-256 digests of the bun release archives, as
+/// `(bun version, platform tag, digest)`.
+///
+/// Copy the `bun-<platform>.zip` lines of the release's `SHASUMS256.txt` for every
+/// platform in [`bun_platform_tag`] when bumping `BUN_VERSION`. Versions without
+/// an entry are checked against the `SHASUMS256.txt` published with the release.
+const BUN_ARCHIVE_SHA256: &[(&str, &str, &str)] = &[];
+
+fn pinned_bun_sha256(version: &str, platform: &str) -> Option<&'static str> {
+    BUN_ARCHIVE_SHA256
+        .iter()
+        .find(|(v, p, _)| *v == version && *p == platform)
+        .map(|(_, _, digest)| *digest)
+}
 
 pub(crate) async fn download_bun() -> Result<PathBuf, String> {
     let platform = bun_platform_tag()?;
@@ -42,18 +57,28 @@ pub(crate) async fn download_bun() -> Result<PathBuf, String> {
     let bin_dir = ensure_apx_bin_dir()?;
     let dest = bin_dir.join(BUN_EXE);
 
-    debug!("downloading bun v{BUN_VERSION} from {url}");
-    let bytes = http_get(&url).await?;
-
-    // Verify SHA-256 checksum
+    // Verify the SHA-256 checksum before anything is extracted, re-downloading once
+    // on mismatch. The binary is not hashed again on later runs.
     let archive_name = format!("bun-{platform}.zip");
-    let checksums_url = format!(
-        "https://github.com/oven-sh/bun/releases/download/bun-v{BUN_VERSION}/SHASUMS256.txt"
-    );
-    let checksums = String::from_utf8(http_get(&checksums_url).await?)
-        .map_err(|e| format!("Invalid UTF-8 in bun checksums: {e}"))?;
-    let expected = parse_sha256_for_file(&checksums, &archive_name)?;
-    verify_sha256(&bytes, &expected, "bun archive")?;
+    let expected = match pinned_bun_sha256(BUN_VERSION, platform) {
+        Some(pinned) => pinned.to_string(),
+        None => {
+            let checksums_url = format!(
+                "https://github.com/oven-sh/bun/releases/download/bun-v{BUN_VERSION}/SHASUMS256.txt"
+            );
+            let checksums = String::from_utf8(http_get(&checksums_url).await?)
+                .map_err(|e| format!("Invalid UTF-8 in bun checksums: {e}"))?;
+            parse_sha256_for_file(&checksums, &archive_name)?
+        }
+    };
+
+    debug!("downloading bun v{BUN_VERSION} from {url}");
+    let mut bytes = http_get(&url).await?;
+    if let Err(e) = verify_sha256(&bytes, &expected, "bun archive") {
+        warn!("{e} — re-downloading bun v{BUN_VERSION} once");
+        bytes = http_get(&url).await?;
+        verify_sha256(&bytes, &expected, "bun archive")?;
+    }
 
     // Extract bun from the zip (archives have a subdirectory)
     let cursor = std::io::Cursor::new(&bytes);
@@ -306,3 +331,32 @@ async fn http_get(url: &str) -> Result<Vec<u8>, String> {
         .map(|b| b.to_vec())
         .map_err(|e| format!("Failed to read response body from {url}: {e}"))
 }
+
+#[cfg(test)]
+// Reason: panicking on failure is idiomatic in tests
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verify_sha256_reports_both_digests() {
+        let expected = hex::encode(Sha256::digest(b"bun"));
+        assert!(verify_sha256(b"bun", &expected, "bun archive").is_ok());
+
+        let err = verify_sha256(b"tampered", &expected, "bun archive").unwrap_err();
+        let actual = hex::encode(Sha256::digest(b"tampered"));
+        assert!(
+            err.contains(&format!("expected {expected}, got {actual}")),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn test_pinned_bun_sha256_matches_version_and_platform() {
+        assert_eq!(pinned_bun_sha256("0.0.0", "linux-x64"), None);
+        for (version, platform, digest) in BUN_ARCHIVE_SHA256 {
+            assert_eq!(pinned_bun_sha256(version, platform), Some(*digest));
+            assert_eq!(digest.len(), 64, "{version} {platform}");
+        }
+    }
+}
@@ -3,10 +3,11 @@
 use std::path::{Path, PathBuf};
 
 use tokio::sync::OnceCell;
+use tracing::warn;
 
 use super::{
     BinarySource, CommandError, CommandOutput, ExternalTool, Resolvable, ResolvedBinary,
-    ToolCommand, ToolInfo, ToolInfoEntry, get_version, resolve_local, resolve_with_download,
+    ToolCommand, ToolInfo, ToolInfoEntry, get_version, resolve_local,
 };
 
 #[cfg(target_os = "windows")]
@@ -14,7 +15,12 @@ const BUN_EXE: &str = "bun.exe";
 #[cfg(not(target_os = "windows"))]
 const BUN_EXE: &str = "bun";
 
-const BUN_VERSION: &str = "1.3.8";
+/// Pinned bun version: the one apx downloads and expects to find installed.
+pub const BUN_VERSION: &str = "1.3.8";
+
+/// When set to a truthy value, a bun whose version differs from [`BUN_VERSION`]
+/// is rejected instead of only producing a warning.
+const STRICT_VERSION_ENV: &str = "APX_BUN_STRICT_VERSION";
 
 static BUN_CELL: OnceCell<ResolvedBinary> = OnceCell::const_new();
 
@@ -28,9 +34,7 @@ pub struct Bun {
 impl Bun {
     /// Resolve bun binary (downloads if needed). Cached after first call.
     pub async fn new() -> Result<Self, String> {
-        let resolved = BUN_CELL
-            .get_or_try_init(resolve_with_download::<Self>)
-            .await?;
+        let resolved = BUN_CELL.get_or_try_init(resolve_checked).await?;
         tracing::debug!(
             "using {} bun: {}",
             resolved.source_label(),
@@ -39,6 +43,11 @@ impl Bun {
         Ok(Self::from_resolved(resolved.clone()))
     }
 
+    /// Run `bun --version` and return the detected version (or `"unknown"`).
+    pub async fn version(&self) -> String {
+        get_version(&self.path).await
+    }
+
     /// Build a PATH with the apx bin directory prepended.
     /// This ensures child processes spawned by bun also use the apx-bundled bun.
     fn patched_path(&self) -> std::ffi::OsString {
@@ -170,6 +179,63 @@ impl Bun {
     }
 }
 
+/// Resolve bun, downloading it if needed.
+///
+/// Downloads are verified in [`crate::download::download_bun`]; a user-provided
+/// binary is checked against [`BUN_VERSION`] instead.
+async fn resolve_checked() -> Result<ResolvedBinary, String> {
+    match resolve_local::<Bun>() {
+        Ok(resolved) => {
+            if !matches!(resolved.source, BinarySource::ApxManaged) {
+                check_pinned_version(
+                    &get_version(&resolved.path).await,
+                    Bun::PINNED_VERSION,
+                    &resolved,
+                    strict_version(),
+                )?;
+            }
+            Ok(resolved)
+        }
+        Err(_) => Bun::download().await,
+    }
+}
+
+/// Whether `APX_BUN_STRICT_VERSION` is set to a truthy value.
+fn strict_version() -> bool {
+    std::env::var(STRICT_VERSION_ENV).is_ok_and(|v| matches!(v.trim(), "1" | "true" | "yes"))
+}
+
+/// Compare a user-provided bun against the `pinned` version.
+///
+/// Warns on mismatch, or fails when `strict` is set. Without a pin any version
+/// is accepted.
+fn check_pinned_version(
+    found: &str,
+    pinned: Option<&str>,
+    resolved: &ResolvedBinary,
+    strict: bool,
+) -> Result<(), String> {
+    let Some(pinned) = pinned else {
+        return Ok(());
+    };
+    let found = found.trim_start_matches('v');
+    if found == pinned {
+        return Ok(());
+    }
+    let msg = format!(
+        "{} bun at {} is v{found}, but apx is pinned to v{pinned}; builds may differ across machines",
+        resolved.source_label(),
+        resolved.path.display()
+    );
+    if strict {
+        return Err(format!(
+            "{msg}. Install bun v{pinned} or unset {STRICT_VERSION_ENV}."
+        ));
+    }
+    warn!("{msg}");
+    Ok(())
+}
+
 impl ExternalTool for Bun {
     const NAME: &'static str = "bun";
 
@@ -237,3 +303,41 @@ impl ToolInfo for Bun {
         }
     }
 }
+
+#[cfg(test)]
+// Reason: panicking on failure is idiomatic in tests
+#[allow(clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    fn user_bun() -> ResolvedBinary {
+        ResolvedBinary {
+            path: PathBuf::from("/usr/local/bin/bun"),
+            source: BinarySource::SystemPath,
+        }
+    }
+
+    #[test]
+    fn test_matching_version_passes() {
+        let found = format!("v{BUN_VERSION}");
+        assert!(check_pinned_version(&found, Some(BUN_VERSION), &user_bun(), true).is_ok());
+        assert!(check_pinned_version(BUN_VERSION, Some(BUN_VERSION), &user_bun(), true).is_ok());
+    }
+
+    #[test]
+    fn test_mismatched_version_warns_or_fails() {
+        assert!(check_pinned_version("1.0.0", Some(BUN_VERSION), &user_bun(), false).is_ok());
+
+        let err = check_pinned_version("1.0.0", Some(BUN_VERSION), &user_bun(), true)
+            .expect_err("strict mode should reject a mismatched version");
+        assert!(err.contains("/usr/local/bin/bun is v1.0.0"), "{err}");
+        assert!(err.contains(&format!("pinned to v{BUN_VERSION}")), "{err}");
+        assert!(err.contains(STRICT_VERSION_ENV), "{err}");
+    }
+
+    #[test]
+    fn test_missing_pin_accepts_any_version() {
+        assert!(check_pinned_version("1.0.0", None, &user_bun(), true).is_ok());
+        assert!(check_pinned_version("unknown", None, &user_bun(), true).is_ok());
+    }
+}
@@ -21,6 +21,7 @@
 //! flux::stop()?;
 //! ```
 
+use serde::Serialize;
 use std::fs;
 use std::process::Stdio;
 use std::time::{Duration, Instant};
@@ -28,10 +29,23 @@ use tracing::{debug, info, warn};
 
 // Re-export from apx-common crate
 pub use apx_common::{
-    FLUX_PORT, FluxLock, flux_dir, is_flux_listening, is_running, log_path, read_lock, remove_lock,
-    write_lock,
+    FLUX_PORT, FluxLock, StartupLock, flux_dir, is_flux_healthy, is_flux_listening, is_running,
+    lock_startup, log_path, read_lock, remove_lock, try_lock_startup, write_lock,
 };
 
+/// How long a running daemon may fail `/healthz` before it is treated as wedged.
+const HEALTH_GRACE_PERIOD: Duration = Duration::from_secs(3);
+
+/// Delay between `/healthz` probes during [`HEALTH_GRACE_PERIOD`].
+const HEALTH_RETRY_INTERVAL: Duration = Duration::from_millis(250);
+
+/// How long [`restart`] waits for the old daemon process to exit.
+const EXIT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How long [`stop`] waits for the daemon to flush and exit after `SIGTERM`
+/// before killing it.
+const TERMINATE_TIMEOUT: Duration = Duration::from_secs(5);
+
 // ============================================================================
 // Daemon management
 // ============================================================================
@@ -99,6 +113,10 @@ pub fn start() -> Result<(), String> {
     let dir = flux_dir()?;
     fs::create_dir_all(&dir).map_err(|e| format!("Failed to create flux directory: {e}"))?;
 
+    // Wait out a compaction in progress, and keep one from starting until the
+    // daemon is up and its lock file written
+    let _startup = lock_startup()?;
+
     // Check if already running via lock file
     if let Some(lock) = read_lock()? {
         if is_flux_listening(lock.port) {
@@ -142,15 +160,17 @@ pub fn start() -> Result<(), String> {
 ///
 /// This is the main API for callers like `apx dev start` that need to ensure
 /// flux is running before proceeding. Also checks that the running daemon
-/// matches the current apx version — restarts on mismatch.
+/// matches the current apx version — restarts on mismatch — and that it passes
+/// `/healthz`. A daemon that is alive but keeps failing health checks for
+/// [`HEALTH_GRACE_PERIOD`] is killed and restarted.
 pub fn ensure_running() -> Result<(), String> {
     if is_running() {
-        // Check version from lock file
-        if let Some(lock) = read_lock()? {
-            if lock.version.as_deref() == Some(apx_common::VERSION) {
-                debug!("Flux is already running (version matches)");
-                return Ok(());
-            }
+        let Some(lock) = read_lock()? else {
+            debug!("Flux is already running (no lock file to check version)");
+            return Ok(());
+        };
+
+        if lock.version.as_deref() != Some(apx_common::VERSION) {
             // Version mismatch or old lock without version — restart
             info!(
                 "Flux version mismatch (running: {:?}, expected: {}), restarting",
@@ -158,15 +178,41 @@ pub fn ensure_running() -> Result<(), String> {
                 apx_common::VERSION
             );
             stop()?;
-            // Fall through to start()
+        } else if wait_for_healthy(lock.port) {
+            debug!("Flux is already running (version matches, healthy)");
+            return Ok(());
+        } else if crate::dev::common::is_process_running(lock.pid) {
+            warn!(
+                "Flux (pid={}, port={}) accepts connections but failed health checks for {:?}, killing and restarting",
+                lock.pid, lock.port, HEALTH_GRACE_PERIOD
+            );
+            stop()?;
         } else {
-            debug!("Flux is already running (no lock file to check version)");
+            warn!(
+                "Flux lock points at exited pid {} but port {} is held by another process, leaving it alone",
+                lock.pid, lock.port
+            );
             return Ok(());
         }
+        // Fall through to start()
     }
     start()
 }
 
+/// Probe `/healthz` until it succeeds or [`HEALTH_GRACE_PERIOD`] runs out.
+fn wait_for_healthy(port: u16) -> bool {
+    let deadline = Instant::now() + HEALTH_GRACE_PERIOD;
+    loop {
+        if is_flux_healthy(port) {
+            return true;
+        }
+        if Instant::now() >= deadline {
+            return false;
+        }
+        std::thread::sleep(HEALTH_RETRY_INTERVAL);
+    }
+}
+
 /// Stop flux daemon.
 ///
 /// Stops the running flux daemon and removes the lock file.
@@ -184,15 +230,175 @@ pub fn stop() -> Result<(), String> {
 
     info!("Stopping flux daemon (pid={})", lock.pid);
 
-    // Kill the process tree
-    if let Err(e) = crate::dev::common::kill_process_tree(lock.pid, "flux-daemon") {
-        warn!("Failed to kill flux process tree: {}", e);
-    }
+    // SIGTERM lets the daemon store the records it holds for collapsing
+    if crate::dev::common::terminate_process(lock.pid) && exits_within(lock.pid, TERMINATE_TIMEOUT)
+    {
+        debug!("Flux daemon exited after SIGTERM");
+    } else {
+        warn!(
+            "Flux daemon (pid={}) did not exit after SIGTERM, killing it",
+            lock.pid
+        );
+        if let Err(e) = crate::dev::common::kill_process_tree(lock.pid, "flux-daemon") {
+            warn!("Failed to kill flux process tree: {}", e);
+        }
 
-    // Wait a bit for the process to exit
-    std::thread::sleep(Duration::from_millis(500));
+        // Wait a bit for the process to exit
+        std::thread::sleep(Duration::from_millis(500));
+    }
 
     remove_lock()?;
     info!("Flux daemon stopped");
     Ok(())
 }
+
+/// Restart flux daemon.
+///
+/// Stops the running daemon, waits for its process (the lock PID) to exit and
+/// starts a new one, so the current apx binary takes over. Starts flux if it is
+/// not running. Fails if the old daemon is still alive after [`EXIT_TIMEOUT`].
+pub fn restart() -> Result<(), String> {
+    let old_pid = read_lock()?
+        .filter(|lock| is_flux_listening(lock.port))
+        .map(|lock| lock.pid);
+
+    stop()?;
+
+    if let Some(pid) = old_pid {
+        wait_for_exit(pid)?;
+    }
+    start()
+}
+
+/// Wait until `pid` exits or [`EXIT_TIMEOUT`] runs out.
+fn wait_for_exit(pid: u32) -> Result<(), String> {
+    if exits_within(pid, EXIT_TIMEOUT) {
+        return Ok(());
+    }
+    Err(format!(
+        "Flux daemon (pid={pid}) did not exit within {}s. Kill it manually and run `apx flux start`",
+        EXIT_TIMEOUT.as_secs()
+    ))
+}
+
+/// Whether `pid` exits within `timeout`.
+fn exits_within(pid: u32, timeout: Duration) -> bool {
+    let deadline = Instant::now() + timeout;
+    while crate::dev::common::is_process_running(pid) {
+        if Instant::now() >= deadline {
+            return false;
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+    true
+}
+
+// ============================================================================
+// Async wrappers
+// ============================================================================
+//
+// Starting and stopping flux sleeps between readiness, health and exit probes,
+// so async callers go through these instead of stalling a runtime worker.
+
+/// Run a blocking flux operation on a blocking thread.
+async fn run_blocking(op: fn() -> Result<(), String>) -> Result<(), String> {
+    tokio::task::spawn_blocking(op)
+        .await
+        .map_err(|err| format!("Failed to spawn blocking task: {err}"))?
+}
+
+/// Async wrapper for [`ensure_running`] that runs on a blocking thread.
+pub async fn ensure_running_async() -> Result<(), String> {
+    run_blocking(ensure_running).await
+}
+
+/// Async wrapper for [`start`] that runs on a blocking thread.
+pub async fn start_async() -> Result<(), String> {
+    run_blocking(start).await
+}
+
+/// Async wrapper for [`stop`] that runs on a blocking thread.
+pub async fn stop_async() -> Result<(), String> {
+    run_blocking(stop).await
+}
+
+/// Async wrapper for [`restart`] that runs on a blocking thread.
+pub async fn restart_async() -> Result<(), String> {
+    run_blocking(restart).await
+}
+
+// ============================================================================
+// Status
+// ============================================================================
+
+/// Whether the flux daemon is running and answering `/healthz`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FluxState {
+    /// Listening and passing health checks.
+    Healthy,
+    /// Listening but failing `/healthz`.
+    Unhealthy,
+    /// Not listening.
+    NotRunning,
+}
+
+/// Snapshot of the flux daemon, as shown by `apx flux status` and `apx dev status`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FluxStatus {
+    /// Daemon health.
+    pub state: FluxState,
+    /// Daemon PID, from the lock file of a running daemon.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pid: Option<u32>,
+    /// Port from the lock file, or [`FLUX_PORT`] without one.
+    pub port: u16,
+    /// Seconds since the running daemon started.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub uptime_secs: Option<i64>,
+    /// apx version of the running daemon.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+}
+
+impl FluxStatus {
+    /// Whether the daemon is listening, healthy or not.
+    #[must_use]
+    pub fn is_running(&self) -> bool {
+        self.state != FluxState::NotRunning
+    }
+}
+
+/// Probe the flux daemon described by the lock file.
+///
+/// A lock left behind by a crashed daemon says nothing about the current one,
+/// so its PID, uptime and version are only reported while the port is listening.
+/// An unreadable lock file is treated as missing.
+#[must_use]
+pub fn status() -> FluxStatus {
+    let lock = read_lock().unwrap_or_else(|e| {
+        warn!("Ignoring unreadable flux lock file: {e}");
+        None
+    });
+    let port = lock.as_ref().map_or(FLUX_PORT, |lock| lock.port);
+    let state = if !is_flux_listening(port) {
+        FluxState::NotRunning
+    } else if is_flux_healthy(port) {
+        FluxState::Healthy
+    } else {
+        FluxState::Unhealthy
+    };
+    let lock = lock.filter(|_| state != FluxState::NotRunning);
+
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs().cast_signed())
+        .unwrap_or(0);
+    FluxStatus {
+        state,
+        pid: lock.as_ref().map(|lock| lock.pid),
+        port,
+        uptime_secs: lock.as_ref().map(|lock| (now - lock.started_at).max(0)),
+        version: lock.and_then(|lock| lock.version),
+    }
+}
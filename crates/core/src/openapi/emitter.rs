@@ -7,40 +7,245 @@
 //! 3. Codegen: ApiIR -> swc_ecma_ast::Module
 //! 4. Emit: Module -> String (via SWC's Emitter)
 
+use std::collections::{BTreeMap, HashSet};
 use std::rc::Rc;
 
+use swc_common::DUMMY_SP;
 use swc_common::SourceMap;
+use swc_common::comments::SingleThreadedComments;
 use swc_common::sync::Lrc;
-use swc_ecma_ast::Module;
+use swc_ecma_ast::{Module, ModuleItem};
 use swc_ecma_codegen::{Config, Emitter as SwcEmitter, text_writer::JsWriter};
 
-use crate::openapi::ir::{codegen_module, normalize_spec};
-use crate::openapi::spec::OpenApiSpec;
+use crate::openapi::ir::builders::{DocComments, export_all, exported_names, import_named};
+use crate::openapi::ir::{
+    ModuleSection, check_required_properties, codegen_examples_module, codegen_items,
+    codegen_module, codegen_msw_module, normalize_server, normalize_spec,
+};
+use crate::openapi::options::{Banner, BaseUrl, GenerateOptions, ReadonlyProps};
+use crate::openapi::spec::{Info, OpenApiSpec};
+use crate::openapi::warnings::{GenerateWarning, collect_warnings};
+
+/// Generated TypeScript code together with non-fatal issues found along the way.
+#[derive(Debug, Clone)]
+pub struct GenerateResult {
+    /// The generated TypeScript module.
+    pub code: String,
+    /// Constructs that were ignored or approximated during generation.
+    pub warnings: Vec<GenerateWarning>,
+    /// Mock Service Worker handlers importing types from `./api`
+    /// (only with [`GenerateOptions::msw_handlers`]).
+    pub msw_handlers: Option<String>,
+    /// Names of the generated operations, in output order.
+    pub operations: Vec<String>,
+    /// The client split into `types.ts`, `client.ts` and `hooks.ts` plus an `index.ts`
+    /// barrel re-exporting them, keyed by file name
+    /// (only with [`GenerateOptions::split_modules`]).
+    pub modules: Option<BTreeMap<String, String>>,
+    /// Typed `example<Type>` constants importing types from `./api`
+    /// (only with [`GenerateOptions::examples`]).
+    pub examples: Option<String>,
+}
+
+/// Module specifier the MSW handlers and examples use to import the generated client's types.
+const MSW_CLIENT_MODULE: &str = "./api";
 
 /// Generate TypeScript code from an OpenAPI JSON string.
-pub fn generate(openapi_json: &str) -> Result<String, String> {
+pub fn generate(openapi_json: &str, options: &GenerateOptions) -> Result<String, String> {
+    generate_detailed(openapi_json, options).map(|result| result.code)
+}
+
+/// Generate TypeScript code and collect warnings for unsupported or approximated constructs.
+pub fn generate_detailed(
+    openapi_json: &str,
+    options: &GenerateOptions,
+) -> Result<GenerateResult, String> {
     // Parse OpenAPI spec
     let spec = OpenApiSpec::from_json(openapi_json)?;
 
+    let missing_required = check_required_properties(&spec);
+    if options.strict_required
+        && let Some(missing) = missing_required.first()
+    {
+        return Err(format!(
+            "Invalid OpenAPI spec at {}: {}. Declare the property or remove it from `required`.",
+            missing.location, missing.message
+        ));
+    }
+
     // Normalize to API IR (all OpenAPI logic resolved here)
-    let api_ir = normalize_spec(&spec)?;
+    let mut api_ir = normalize_spec(&spec)?;
+    api_ir.use_hook_naming(&options.hook_naming)?;
+    if options.bigint_int64 {
+        api_ir.use_bigint_int64();
+    }
+    if options.body_defaults {
+        api_ir.use_body_defaults();
+    }
+    if options.branded_formats {
+        api_ir.use_branded_formats();
+    }
+    if options.readonly_props != ReadonlyProps::Off {
+        api_ir.use_readonly_props(options.readonly_props == ReadonlyProps::Responses);
+    }
+    if options.base_url == BaseUrl::Server {
+        api_ir.server = normalize_server(&spec)?;
+    }
 
-    // Generate SWC AST
-    let module = codegen_module(&api_ir);
+    // Generate SWC AST, collecting JSDoc comments for the emitter
+    let docs = DocComments::default();
+    let module = codegen_module(&api_ir, options, &docs);
 
     // Emit to string
-    emit_module(&module)
+    let banner = banner_text(&options.banner, spec.info.as_ref());
+    let code = format!("{banner}{}", emit_module(&module, docs.comments())?);
+
+    let msw_handlers = if options.msw_handlers {
+        let module = codegen_msw_module(&api_ir, &spec, MSW_CLIENT_MODULE, &docs);
+        let code = emit_module(&module, docs.comments())?;
+        Some(format!("{banner}{code}"))
+    } else {
+        None
+    };
+
+    let examples = if options.examples {
+        let module = codegen_examples_module(&api_ir, &spec, MSW_CLIENT_MODULE);
+        let code = emit_module(&module, docs.comments())?;
+        Some(format!("{banner}{code}"))
+    } else {
+        None
+    };
+
+    let modules = if options.split_modules {
+        let items = codegen_items(&api_ir, options, &docs);
+        let files = emit_split_modules(items, docs.comments())?;
+        Some(
+            files
+                .into_iter()
+                .map(|(name, code)| (name, format!("{banner}{code}")))
+                .collect(),
+        )
+    } else {
+        None
+    };
+
+    // Warnings come from the raw JSON so keywords dropped by `OpenApiSpec` are still visible
+    let mut warnings: Vec<_> = serde_json::from_str(openapi_json)
+        .map(|raw| collect_warnings(&raw))
+        .unwrap_or_default();
+    warnings.extend(missing_required);
+
+    Ok(GenerateResult {
+        code,
+        warnings,
+        msw_handlers,
+        operations: api_ir.operations.iter().map(|op| op.name.clone()).collect(),
+        modules,
+        examples,
+    })
+}
+
+/// Comment lines prepended to every generated file, ending in a newline unless empty.
+fn banner_text(banner: &Banner, info: Option<&Info>) -> String {
+    match banner {
+        Banner::None => String::new(),
+        Banner::Custom(text) if text.ends_with('\n') => text.clone(),
+        Banner::Custom(text) => format!("{text}\n"),
+        Banner::Standard { generated_marker } => {
+            // Line breaks in the title would end the comment early
+            let source = info
+                .map(|info| format!("{} {}", info.title, info.version))
+                .map(|source| source.split_whitespace().collect::<Vec<_>>().join(" "))
+                .filter(|source| !source.is_empty())
+                .map(|source| format!(" Source: {source}"))
+                .unwrap_or_default();
+            let marker = if *generated_marker {
+                "// @generated\n"
+            } else {
+                ""
+            };
+            format!("/* eslint-disable */\n// Generated by apx — do not edit.{source}\n{marker}")
+        }
+    }
+}
+
+/// Emit tagged module items as one file per [`ModuleSection`] plus an `index.ts` barrel.
+///
+/// Each file imports the names it uses from the sections before it. Empty
+/// sections (e.g. hooks for a spec without operations) are left out.
+fn emit_split_modules(
+    mut items: Vec<(ModuleSection, ModuleItem)>,
+    comments: &SingleThreadedComments,
+) -> Result<BTreeMap<String, String>, String> {
+    let mut files = BTreeMap::new();
+    let mut exported: Vec<(String, Vec<(String, bool)>)> = Vec::new();
+    let mut index = Vec::new();
+    for section in ModuleSection::ALL {
+        let (body, rest): (Vec<_>, Vec<_>) = items.into_iter().partition(|(s, _)| *s == section);
+        items = rest;
+        let body: Vec<ModuleItem> = body.into_iter().map(|(_, item)| item).collect();
+        if body.is_empty() {
+            continue;
+        }
+        let specifier = format!("./{}", section.file_stem());
+        let own_names = exported_names(&body);
+        let code = emit_module(&module_of(body), comments)?;
+
+        // Import what this file references, split into type-only and value imports
+        let used = identifiers(&code);
+        let mut imports = Vec::new();
+        for (from, names) in &exported {
+            for type_only in [true, false] {
+                let wanted: Vec<_> = names
+                    .iter()
+                    .filter(|(name, is_type)| *is_type == type_only && used.contains(name.as_str()))
+                    .map(|(name, _)| (name.as_str(), None))
+                    .collect();
+                if !wanted.is_empty() {
+                    imports.push(import_named(wanted, from, type_only));
+                }
+            }
+        }
+        let imports = emit_module(&module_of(imports), comments)?;
+
+        files.insert(
+            format!("{}.ts", section.file_stem()),
+            format!("{imports}{code}"),
+        );
+        index.push(export_all(&specifier));
+        exported.push((specifier, own_names));
+    }
+    files.insert(
+        "index.ts".to_string(),
+        emit_module(&module_of(index), comments)?,
+    );
+    Ok(files)
+}
+
+fn module_of(body: Vec<ModuleItem>) -> Module {
+    Module {
+        span: DUMMY_SP,
+        body,
+        shebang: None,
+    }
+}
+
+/// Identifier-like words in emitted code, used to decide which names a file imports.
+fn identifiers(code: &str) -> HashSet<&str> {
+    code.split(|c: char| !(c.is_ascii_alphanumeric() || c == '_' || c == '$'))
+        .filter(|word| !word.is_empty())
+        .collect()
 }
 
 /// Emit a SWC Module to a TypeScript string.
-fn emit_module(module: &Module) -> Result<String, String> {
+fn emit_module(module: &Module, comments: &SingleThreadedComments) -> Result<String, String> {
     let cm: Lrc<SourceMap> = Rc::default();
     let mut buf = vec![];
     {
         let mut emitter = SwcEmitter {
             cfg: Config::default().with_ascii_only(false),
             cm: cm.clone(),
-            comments: None,
+            comments: Some(comments),
             wr: JsWriter::new(cm, "\n", &mut buf, None),
         };
         emitter
@@ -6,7 +6,11 @@
 //! - FetchIR: Fetch function representation
 //! - HookIR: React Query hook representation
 
-use super::types::{TsTypeDef, TypeRef};
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use super::types::{StringFormat, TsLiteral, TsPrimitive, TsType, TsTypeDef, TypeDefKind, TypeRef};
+use super::utils::capitalize_first;
+use crate::openapi::options::HookNaming;
 
 /// HTTP method
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -19,6 +23,15 @@ pub enum HttpMethod {
 }
 
 impl HttpMethod {
+    /// All supported methods, in route-table order
+    pub const ALL: [Self; 5] = [
+        HttpMethod::Get,
+        HttpMethod::Post,
+        HttpMethod::Put,
+        HttpMethod::Patch,
+        HttpMethod::Delete,
+    ];
+
     pub fn as_str(self) -> &'static str {
         match self {
             HttpMethod::Get => "GET",
@@ -48,6 +61,10 @@ pub enum OperationKind {
 pub struct OperationIR {
     /// Sanitized TypeScript identifier (e.g., "listItems")
     pub name: String,
+    /// Path template as written in the spec (e.g., "/items/{itemId}")
+    pub path: String,
+    /// First OpenAPI tag, used to group operations (None = untagged)
+    pub tag: Option<String>,
 
     /// Normalized parameters (None = no params)
     pub params: Option<ParamsIR>,
@@ -109,6 +126,12 @@ pub struct FetchIR {
     pub method: HttpMethod,
     /// Header parameters to include in fetch headers
     pub header_params: Vec<ParamIR>,
+    /// Schema paths of the JSON response values revived as `BigInt`, from the
+    /// root, with `None` standing for any array index or map key
+    /// (set by [`ApiIR::use_bigint_int64`])
+    pub bigint_paths: Vec<Vec<Option<String>>>,
+    /// Whether the JSON body may contain `bigint` values needing a replacer
+    pub bigint_body: bool,
 }
 
 /// Fetch function argument
@@ -152,8 +175,13 @@ pub enum ResponseContentType {
     Text,
     /// Binary/blob response - use res.blob()
     Blob,
+    /// Server-sent events (`text/event-stream`) - yield events parsed from res.body
+    EventStream,
     /// Unknown content type - return Response directly
     Unknown,
+    /// Several content types - branch on the `Content-Type` header at runtime
+    /// (see [`ResponseIR::variants`])
+    Negotiated,
 }
 
 /// Request body content type determines how to serialize the body
@@ -172,6 +200,47 @@ pub enum BodyContentType {
 pub struct BodyIR {
     pub ty: TypeRef,
     pub content_type: BodyContentType,
+    /// Top-level properties declaring a schema `default` (JSON bodies only)
+    pub defaults: Vec<BodyDefaultIR>,
+    /// Schema paths of the `bigint` values in a JSON body
+    /// (set by [`ApiIR::use_bigint_int64`])
+    pub bigint_paths: Vec<Vec<Option<String>>>,
+    /// Properties of an object `multipart/form-data` body, one part each
+    pub form_fields: Vec<FormFieldIR>,
+}
+
+/// One property of a `multipart/form-data` body
+#[derive(Debug, Clone)]
+pub struct FormFieldIR {
+    pub name: String,
+    /// Type accepted by the form builder (`Blob` for binary parts)
+    pub ty: TypeRef,
+    pub optional: bool,
+    /// Content type of a part sent as JSON; `None` appends the value as-is
+    pub json_content_type: Option<String>,
+}
+
+/// Schema `default` of one request body property
+#[derive(Debug, Clone)]
+pub struct BodyDefaultIR {
+    /// Property name as it appears in the JSON body
+    pub name: String,
+    /// Default value from the schema
+    pub value: serde_json::Value,
+    /// Whether the property is also listed as `required`
+    pub required: bool,
+}
+
+/// Body type with defaulted required properties made optional:
+/// `type CreateItemBody = Omit<Item, "status"> & Partial<Pick<Item, "status">>`
+#[derive(Debug, Clone)]
+pub struct BodyAliasIR {
+    /// Alias name (e.g., "CreateItemBody")
+    pub name: String,
+    /// Original body type
+    pub base: TypeRef,
+    /// Properties made optional because a default fills them in
+    pub optional_keys: Vec<String>,
 }
 
 /// Response IR with content type info
@@ -183,6 +252,19 @@ pub struct ResponseIR {
     pub content_type: ResponseContentType,
     /// Whether a void status (204) exists alongside content response
     pub has_void_status: bool,
+    /// Per-content-type parsing branches, in match order (only for `Negotiated`)
+    pub variants: Vec<ResponseVariantIR>,
+    /// Union of the 4xx/5xx JSON bodies, carried as the `ApiError` body type
+    pub error_ty: TypeRef,
+}
+
+/// One runtime parsing branch of a negotiated response
+#[derive(Debug, Clone)]
+pub struct ResponseVariantIR {
+    /// How to parse the response when this branch matches
+    pub content_type: ResponseContentType,
+    /// Media types selecting this branch (matched against the `Content-Type` header)
+    pub media_types: Vec<String>,
 }
 
 /// Hook kind
@@ -214,6 +296,8 @@ pub struct HookIR {
     pub response_content_type: ResponseContentType,
     /// Whether a 204 void status exists alongside content response
     pub response_has_void_status: bool,
+    /// `ApiError` body type used as the hook's error generic
+    pub error_type: TypeRef,
     /// For mutations: whether body argument comes before params in fetch function
     pub body_before_params: bool,
 }
@@ -221,14 +305,103 @@ pub struct HookIR {
 /// Query key function IR
 #[derive(Debug, Clone)]
 pub struct QueryKeyIR {
-    /// Function name (e.g., "listItemsKey")
+    /// Function name (e.g., "listItemsQueryKey")
     pub fn_name: String,
+    /// Deprecated pre-`QueryKey` name kept as an alias (None when it would clash)
+    pub legacy_fn_name: Option<String>,
     /// Base key string (e.g., "/items")
     pub base_key: String,
     /// Parameters type (if any)
     pub params_type: Option<TypeRef>,
 }
 
+/// Server URL template resolved from the spec's first `servers` entry
+#[derive(Debug, Clone)]
+pub struct ServerIR {
+    /// URL split into static text and variable references (trailing `/` trimmed)
+    pub template: Vec<UrlPart>,
+    /// Declared variables, sorted by name
+    pub variables: Vec<ServerVariableIR>,
+}
+
+/// Server URL variable
+#[derive(Debug, Clone)]
+pub struct ServerVariableIR {
+    /// Variable name as written in the URL template
+    pub name: String,
+    /// Value used until the client overrides it
+    pub default: String,
+    /// Allowed values (empty = any string)
+    pub enum_values: Vec<String>,
+}
+
+/// Type guard narrowing a discriminated union to one mapped member
+#[derive(Debug, Clone)]
+pub struct TypeGuardIR {
+    /// Guard function name (e.g., "isDog")
+    pub fn_name: String,
+    /// Union type being narrowed (e.g., "Pet")
+    pub union_type: String,
+    /// Discriminator property (e.g., "petType")
+    pub property: String,
+    /// Discriminator value selecting the member (e.g., "dog")
+    pub value: String,
+}
+
+/// Runtime check telling apart the members of a non-discriminated union,
+/// in the order the checks run (`null` and arrays before `typeof "object"`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum MatchKind {
+    /// `value === null`
+    Null,
+    /// `Array.isArray(value)`
+    Array,
+    /// `typeof value === "string"`
+    String,
+    /// `typeof value === "number"`
+    Number,
+    /// `typeof value === "bigint"`
+    BigInt,
+    /// `typeof value === "boolean"`
+    Boolean,
+    /// `typeof value === "object"`
+    Object,
+}
+
+/// How a union matcher selects the handler for a value
+#[derive(Debug, Clone)]
+pub enum MatchDispatchIR {
+    /// Compare a discriminator property; one handler per value
+    Discriminator {
+        /// Discriminator property (e.g., "petType")
+        property: String,
+        /// Discriminator values, one per union member (e.g., ["dog", "cat"])
+        values: Vec<String>,
+    },
+    /// Check the runtime kind of the value; one handler per kind
+    Kind(Vec<MatchKind>),
+}
+
+/// Exhaustive `matchXxx(value, handlers)` helper for a component union
+#[derive(Debug, Clone)]
+pub struct UnionMatcherIR {
+    /// Matcher function name (e.g., "matchPet")
+    pub fn_name: String,
+    /// Union type being matched (e.g., "Pet")
+    pub union_type: String,
+    /// How members are told apart
+    pub dispatch: MatchDispatchIR,
+}
+
+/// Inbound callback declared under the spec's `webhooks` (OpenAPI 3.1)
+#[derive(Debug, Clone)]
+pub struct WebhookIR {
+    /// Webhook name as written in the spec, used as the `WebhookEvent` discriminator
+    pub name: String,
+    /// Payload type (e.g., "NewPetWebhookPayload"), typed from the JSON request body
+    pub payload: TsTypeDef,
+}
+
 /// Normalized API specification
 #[derive(Debug)]
 pub struct ApiIR {
@@ -236,8 +409,517 @@ pub struct ApiIR {
     pub operations: Vec<OperationIR>,
     /// Component schemas as type definitions
     pub types: Vec<TsTypeDef>,
+    /// Type guards for discriminated unions among the component schemas
+    pub type_guards: Vec<TypeGuardIR>,
+    /// Exhaustive matchers for component unions (emitted only when enabled)
+    pub union_matchers: Vec<UnionMatcherIR>,
+    /// Webhooks, sorted by name
+    pub webhooks: Vec<WebhookIR>,
+    /// Relaxed request body types (set by [`ApiIR::use_body_defaults`])
+    pub body_aliases: Vec<BodyAliasIR>,
+    /// Branded string types referenced by the IR (set by [`ApiIR::use_branded_formats`])
+    pub brands: Vec<StringFormat>,
+    /// Templated server base URL (set when the base URL comes from `servers`)
+    pub server: Option<ServerIR>,
     /// Whether the spec has queries
     pub has_queries: bool,
     /// Whether the spec has mutations
     pub has_mutations: bool,
 }
+
+impl ApiIR {
+    /// Map `format: int64`/`uint64` integers to `bigint` throughout the IR.
+    ///
+    /// Also records, per operation, which JSON response properties must be
+    /// revived as `BigInt`, where the JSON request body holds `bigint` values
+    /// and whether it needs a `bigint`-aware `JSON.stringify` replacer.
+    pub fn use_bigint_int64(&mut self) {
+        self.types.iter_mut().for_each(TsTypeDef::int64_to_bigint);
+        for webhook in &mut self.webhooks {
+            webhook.payload.int64_to_bigint();
+        }
+
+        // int64 members are now checked with `typeof value === "bigint"`
+        for matcher in &mut self.union_matchers {
+            if let MatchDispatchIR::Kind(kinds) = &mut matcher.dispatch
+                && let Some(updated) = match_kinds(
+                    &TsType::Ref(matcher.union_type.clone()),
+                    &self.types,
+                    &mut HashSet::new(),
+                )
+            {
+                *kinds = updated;
+            }
+        }
+
+        for op in &mut self.operations {
+            if let Some(params) = &mut op.params {
+                params
+                    .fields
+                    .iter_mut()
+                    .for_each(|f| f.ty.int64_to_bigint());
+            }
+
+            let fetch = &mut op.fetch;
+            for arg in &mut fetch.args {
+                if let FetchArgIR::Params { ty, .. } | FetchArgIR::Body { ty, .. } = arg {
+                    ty.int64_to_bigint();
+                }
+            }
+            fetch.response.ty.int64_to_bigint();
+            fetch.response.error_ty.int64_to_bigint();
+            fetch
+                .url
+                .query_params
+                .iter_mut()
+                .for_each(|p| p.ty.int64_to_bigint());
+            fetch
+                .header_params
+                .iter_mut()
+                .for_each(|p| p.ty.int64_to_bigint());
+            if let Some(body) = &mut fetch.body {
+                body.ty.int64_to_bigint();
+                if body.content_type == BodyContentType::Json {
+                    let mut paths = BTreeSet::new();
+                    collect_bigint_paths(
+                        &body.ty.to_ts_type(),
+                        &mut Vec::new(),
+                        &self.types,
+                        &mut HashSet::new(),
+                        &mut paths,
+                    );
+                    body.bigint_paths = paths.into_iter().collect();
+                }
+            }
+
+            if matches!(
+                fetch.response.content_type,
+                ResponseContentType::Json | ResponseContentType::EventStream
+            ) {
+                let mut paths = BTreeSet::new();
+                collect_bigint_paths(
+                    &fetch.response.ty.to_ts_type(),
+                    &mut Vec::new(),
+                    &self.types,
+                    &mut HashSet::new(),
+                    &mut paths,
+                );
+                fetch.bigint_paths = paths.into_iter().collect();
+            }
+            fetch.bigint_body = fetch.body.as_ref().is_some_and(|body| {
+                body.content_type == BodyContentType::Json
+                    && contains_bigint(&body.ty.to_ts_type(), &self.types, &mut HashSet::new())
+            });
+
+            for hook in &mut op.hooks {
+                hook.response_type.int64_to_bigint();
+                hook.error_type.int64_to_bigint();
+                if let Some(vars) = &mut hook.vars_type {
+                    vars.int64_to_bigint();
+                }
+            }
+            if let Some(params) = op.query_key.as_mut().and_then(|qk| qk.params_type.as_mut()) {
+                params.int64_to_bigint();
+            }
+        }
+    }
+}
+
+impl ApiIR {
+    /// Replace `uuid`/`email`/`uri` strings with references to branded types.
+    ///
+    /// Only brands that are actually used are recorded in [`ApiIR::brands`]. A
+    /// format whose brand name clashes with a component schema stays `string`.
+    pub fn use_branded_formats(&mut self) {
+        let brands: Vec<StringFormat> = StringFormat::ALL
+            .into_iter()
+            .filter(|f| !self.types.iter().any(|td| td.name == f.type_name()))
+            .collect();
+        let mut used = BTreeSet::new();
+
+        for td in &mut self.types {
+            td.brand_formats(&brands, &mut used);
+        }
+        for webhook in &mut self.webhooks {
+            webhook.payload.brand_formats(&brands, &mut used);
+        }
+        for ty in self.type_refs_mut() {
+            ty.brand_formats(&brands, &mut used);
+        }
+        self.brands = used.into_iter().collect();
+    }
+}
+
+impl ApiIR {
+    /// Name every hook `{prefix}{Operation}{suffix}` per `naming`.
+    ///
+    /// # Errors
+    ///
+    /// Fails when `naming` is invalid or when two hooks end up with the same
+    /// name, e.g. `listItems` and `listItemsSuspense` with the default suffix.
+    pub fn use_hook_naming(&mut self, naming: &HookNaming) -> Result<(), String> {
+        naming.validate()?;
+        let mut owners: HashMap<String, String> = HashMap::new();
+        for op in &mut self.operations {
+            let capitalized = capitalize_first(&op.name);
+            for hook in &mut op.hooks {
+                let suffix = match hook.kind {
+                    HookKind::Query => "",
+                    HookKind::SuspenseQuery => &naming.suspense_suffix,
+                    HookKind::Mutation => &naming.mutation_suffix,
+                };
+                hook.name = format!("{}{capitalized}{suffix}", naming.prefix);
+                if let Some(other) = owners.insert(hook.name.clone(), op.name.clone()) {
+                    return Err(format!(
+                        "Duplicate hook name '{}' generated for operations '{other}' and '{}'. Rename one of the operations or change the hook naming.",
+                        hook.name, op.name
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl ApiIR {
+    /// Emit `readOnly` properties with the `readonly` modifier.
+    ///
+    /// With `responses` set, every property of a response type is `readonly`
+    /// too, including the component types it reaches through references.
+    pub fn use_readonly_props(&mut self, responses: bool) {
+        let response_types = if responses {
+            self.response_type_names()
+        } else {
+            HashSet::new()
+        };
+
+        for td in &mut self.types {
+            td.mark_readonly(response_types.contains(&td.name));
+        }
+        for webhook in &mut self.webhooks {
+            webhook.payload.mark_readonly(false);
+        }
+        for ty in self.type_refs_mut() {
+            ty.mark_readonly(false);
+        }
+        if responses {
+            for op in &mut self.operations {
+                op.fetch.response.ty.mark_readonly(true);
+                for hook in &mut op.hooks {
+                    hook.response_type.mark_readonly(true);
+                }
+            }
+        }
+    }
+
+    /// Component types reachable from an operation's success response
+    fn response_type_names(&self) -> HashSet<String> {
+        let mut pending = Vec::new();
+        for op in &self.operations {
+            op.fetch.response.ty.collect_refs(&mut pending);
+        }
+
+        let mut names = HashSet::new();
+        while let Some(name) = pending.pop() {
+            if let Some(td) = self.types.iter().find(|td| td.name == name)
+                && names.insert(name)
+            {
+                td.collect_refs(&mut pending);
+            }
+        }
+        names
+    }
+}
+
+impl ApiIR {
+    /// Rename component types and every reference to them.
+    ///
+    /// `names` maps component schema names to their TypeScript names.
+    pub fn rename_types(&mut self, names: &HashMap<String, String>) {
+        for td in &mut self.types {
+            td.rename_refs(names);
+        }
+        for webhook in &mut self.webhooks {
+            webhook.payload.rename_refs(names);
+        }
+        for ty in self.type_refs_mut() {
+            ty.rename_refs(names);
+        }
+    }
+
+    /// Every type used in operation signatures and body aliases.
+    fn type_refs_mut(&mut self) -> Vec<&mut TypeRef> {
+        let mut refs: Vec<&mut TypeRef> =
+            self.body_aliases.iter_mut().map(|a| &mut a.base).collect();
+        for op in &mut self.operations {
+            if let Some(params) = &mut op.params {
+                refs.extend(params.fields.iter_mut().map(|f| &mut f.ty));
+            }
+
+            let fetch = &mut op.fetch;
+            for arg in &mut fetch.args {
+                if let FetchArgIR::Params { ty, .. } | FetchArgIR::Body { ty, .. } = arg {
+                    refs.push(ty);
+                }
+            }
+            refs.push(&mut fetch.response.ty);
+            refs.push(&mut fetch.response.error_ty);
+            refs.extend(fetch.url.query_params.iter_mut().map(|p| &mut p.ty));
+            refs.extend(fetch.header_params.iter_mut().map(|p| &mut p.ty));
+            if let Some(body) = &mut fetch.body {
+                refs.push(&mut body.ty);
+                refs.extend(body.form_fields.iter_mut().map(|f| &mut f.ty));
+            }
+
+            for hook in &mut op.hooks {
+                refs.push(&mut hook.response_type);
+                refs.push(&mut hook.error_type);
+                if let Some(vars) = &mut hook.vars_type {
+                    refs.push(vars);
+                }
+            }
+            if let Some(params) = op.query_key.as_mut().and_then(|qk| qk.params_type.as_mut()) {
+                refs.push(params);
+            }
+        }
+        refs
+    }
+}
+
+impl ApiIR {
+    /// Make required JSON body properties with a schema `default` optional.
+    ///
+    /// Each affected body type is replaced by a named [`BodyAliasIR`] in the
+    /// fetch signature and the mutation hook variables. Properties without a
+    /// default keep their original optionality.
+    pub fn use_body_defaults(&mut self) {
+        for op in &mut self.operations {
+            let fetch = &mut op.fetch;
+            let Some(body) = &mut fetch.body else {
+                continue;
+            };
+            let optional_keys: Vec<String> = body
+                .defaults
+                .iter()
+                .filter(|d| d.required)
+                .map(|d| d.name.clone())
+                .collect();
+            if body.content_type != BodyContentType::Json || optional_keys.is_empty() {
+                continue;
+            }
+
+            let mut name = format!("{}Body", capitalize_first(&fetch.fn_name));
+            while self.types.iter().any(|td| td.name == name)
+                || self.body_aliases.iter().any(|a| a.name == name)
+            {
+                name.push('_');
+            }
+            self.body_aliases.push(BodyAliasIR {
+                name: name.clone(),
+                base: body.ty.clone(),
+                optional_keys,
+            });
+
+            body.ty = TypeRef::Named(name.clone());
+            for arg in &mut fetch.args {
+                if let FetchArgIR::Body { ty, .. } = arg {
+                    *ty = TypeRef::Named(name.clone());
+                }
+            }
+            // Mutation variables are `{ params, data }` when the operation has params
+            let has_params = op.params.is_some();
+            for hook in op.hooks.iter_mut().filter(|h| h.kind == HookKind::Mutation) {
+                match (&mut hook.vars_type, has_params) {
+                    (Some(TypeRef::Inline(vars)), true) => {
+                        if let TsType::Object(props) = &mut **vars {
+                            for prop in props.iter_mut().filter(|p| p.name == "data") {
+                                prop.ty = TsType::Ref(name.clone());
+                            }
+                        }
+                    }
+                    (Some(vars), false) => *vars = TypeRef::Named(name.clone()),
+                    _ => {}
+                }
+            }
+        }
+    }
+}
+
+/// Collect the schema paths of the values of `ty` that are `bigint`.
+///
+/// `path` leads from the root of the JSON document to `ty`: a property name per
+/// object level, and `None` per array or map level, whose keys are not known
+/// statically. Recursive types are followed once.
+fn collect_bigint_paths<'a>(
+    ty: &TsType,
+    path: &mut Vec<Option<String>>,
+    types: &'a [TsTypeDef],
+    visited: &mut HashSet<&'a str>,
+    out: &mut BTreeSet<Vec<Option<String>>>,
+) {
+    match ty {
+        TsType::Primitive(TsPrimitive::BigInt) => {
+            out.insert(path.clone());
+        }
+        TsType::Array(inner) => collect_nested_bigint_paths(inner, None, path, types, visited, out),
+        TsType::Union(members) | TsType::Intersection(members) => {
+            for member in members {
+                collect_bigint_paths(member, path, types, visited, out);
+            }
+        }
+        TsType::Object(props) => {
+            for prop in props {
+                collect_nested_bigint_paths(&prop.ty, Some(&prop.name), path, types, visited, out);
+            }
+        }
+        TsType::Record { value, .. } | TsType::IndexSignature { value, .. } => {
+            collect_nested_bigint_paths(value, None, path, types, visited, out);
+        }
+        TsType::Ref(name) => {
+            let Some(def) = types.iter().find(|td| td.name == *name) else {
+                return;
+            };
+            if !visited.insert(def.name.as_str()) {
+                return;
+            }
+            match &def.kind {
+                TypeDefKind::Interface { properties } => {
+                    for prop in properties {
+                        collect_nested_bigint_paths(
+                            &prop.ty,
+                            Some(&prop.name),
+                            path,
+                            types,
+                            visited,
+                            out,
+                        );
+                    }
+                }
+                TypeDefKind::TypeAlias { ty } => {
+                    collect_bigint_paths(ty, path, types, visited, out);
+                }
+                TypeDefKind::ConstEnum { .. } => {}
+            }
+            visited.remove(def.name.as_str());
+        }
+        TsType::Primitive(_) | TsType::Literal(_) => {}
+    }
+}
+
+/// [`collect_bigint_paths`] for a value nested under `key` (`None` for any key).
+fn collect_nested_bigint_paths<'a>(
+    ty: &TsType,
+    key: Option<&str>,
+    path: &mut Vec<Option<String>>,
+    types: &'a [TsTypeDef],
+    visited: &mut HashSet<&'a str>,
+    out: &mut BTreeSet<Vec<Option<String>>>,
+) {
+    path.push(key.map(str::to_string));
+    collect_bigint_paths(ty, path, types, visited, out);
+    path.pop();
+}
+
+/// Whether a value of this type can contain a `bigint` anywhere.
+fn contains_bigint<'a>(
+    ty: &TsType,
+    types: &'a [TsTypeDef],
+    visited: &mut HashSet<&'a str>,
+) -> bool {
+    match ty {
+        TsType::Primitive(p) => *p == TsPrimitive::BigInt,
+        TsType::Array(inner) => contains_bigint(inner, types, visited),
+        TsType::Union(members) | TsType::Intersection(members) => {
+            members.iter().any(|m| contains_bigint(m, types, visited))
+        }
+        TsType::Object(props) => props.iter().any(|p| contains_bigint(&p.ty, types, visited)),
+        TsType::Record { value, .. } | TsType::IndexSignature { value, .. } => {
+            contains_bigint(value, types, visited)
+        }
+        TsType::Ref(name) => {
+            let Some(def) = types.iter().find(|td| td.name == *name) else {
+                return false;
+            };
+            if !visited.insert(def.name.as_str()) {
+                return false;
+            }
+            match &def.kind {
+                TypeDefKind::Interface { properties } => properties
+                    .iter()
+                    .any(|p| contains_bigint(&p.ty, types, visited)),
+                TypeDefKind::TypeAlias { ty } => contains_bigint(ty, types, visited),
+                TypeDefKind::ConstEnum { .. } => false,
+            }
+        }
+        TsType::Literal(_) => false,
+    }
+}
+
+/// Runtime kinds a value of this type can have, sorted and deduplicated.
+///
+/// Returns `None` when the type includes `unknown`, `void` or a reference that
+/// can't be resolved, since such members can't be dispatched on.
+pub fn match_kinds<'a>(
+    ty: &TsType,
+    types: &'a [TsTypeDef],
+    visited: &mut HashSet<&'a str>,
+) -> Option<Vec<MatchKind>> {
+    let mut kinds = match ty {
+        TsType::Primitive(p) => vec![match p {
+            TsPrimitive::String | TsPrimitive::Format(_) => MatchKind::String,
+            TsPrimitive::Number | TsPrimitive::Int64 => MatchKind::Number,
+            TsPrimitive::BigInt => MatchKind::BigInt,
+            TsPrimitive::Boolean => MatchKind::Boolean,
+            TsPrimitive::Null => MatchKind::Null,
+            TsPrimitive::Void | TsPrimitive::Unknown | TsPrimitive::Never => return None,
+        }],
+        TsType::Literal(lit) => vec![literal_match_kind(lit)],
+        TsType::Array(_) => vec![MatchKind::Array],
+        TsType::Object(_) | TsType::Record { .. } | TsType::IndexSignature { .. } => {
+            vec![MatchKind::Object]
+        }
+        TsType::Union(members) => {
+            let mut kinds = Vec::new();
+            for member in members {
+                kinds.extend(match_kinds(member, types, visited)?);
+            }
+            kinds
+        }
+        // An intersection is only dispatchable when every part is an object
+        TsType::Intersection(parts) => {
+            for part in parts {
+                if match_kinds(part, types, visited)? != [MatchKind::Object] {
+                    return None;
+                }
+            }
+            vec![MatchKind::Object]
+        }
+        TsType::Ref(name) => {
+            let def = types.iter().find(|td| td.name == *name)?;
+            if !visited.insert(def.name.as_str()) {
+                return None;
+            }
+            let kinds = match &def.kind {
+                TypeDefKind::Interface { .. } => Some(vec![MatchKind::Object]),
+                TypeDefKind::TypeAlias { ty } => match_kinds(ty, types, visited),
+                TypeDefKind::ConstEnum { values } => {
+                    Some(values.iter().map(|(_, v)| literal_match_kind(v)).collect())
+                }
+            };
+            visited.remove(def.name.as_str());
+            kinds?
+        }
+    };
+    kinds.sort();
+    kinds.dedup();
+    Some(kinds)
+}
+
+const fn literal_match_kind(lit: &TsLiteral) -> MatchKind {
+    match lit {
+        TsLiteral::String(_) => MatchKind::String,
+        TsLiteral::Number(_) | TsLiteral::Int(_) => MatchKind::Number,
+        TsLiteral::Bool(_) => MatchKind::Boolean,
+        TsLiteral::Null => MatchKind::Null,
+    }
+}
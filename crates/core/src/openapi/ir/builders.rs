@@ -4,14 +4,53 @@
 //! when generating TypeScript code programmatically.
 #![allow(clippy::vec_box)] // SWC's TsTypeParamInstantiation/TsUnionType require Vec<Box<TsType>>
 
+use std::cell::Cell;
+
 use swc_atoms::Atom;
-use swc_common::{DUMMY_SP, SyntaxContext};
+use swc_common::comments::{Comment, CommentKind, Comments, SingleThreadedComments};
+use swc_common::{BytePos, DUMMY_SP, Span, SyntaxContext};
 // Reason: builder module uses most items from parent; explicit list would be unwieldy
 #[allow(clippy::wildcard_imports)]
 use swc_ecma_ast::*;
 
 use super::types::{self as ir, TypeRef};
 
+// =============================================================================
+// Comments
+// =============================================================================
+
+/// JSDoc comments registered during codegen, handed to the emitter with the module.
+///
+/// SWC attaches comments by byte position, so every comment gets its own synthetic
+/// position.
+#[derive(Default)]
+pub struct DocComments {
+    comments: SingleThreadedComments,
+    last_pos: Cell<u32>,
+}
+
+impl DocComments {
+    /// Register a `/** text */` comment and return the span to give the documented node.
+    pub fn jsdoc_span(&self, text: &str) -> Span {
+        let pos = BytePos(self.last_pos.get() + 1);
+        self.last_pos.set(pos.0);
+        self.comments.add_leading(
+            pos,
+            Comment {
+                kind: CommentKind::Block,
+                span: DUMMY_SP,
+                text: format!("* {text} ").into(),
+            },
+        );
+        Span::new(pos, pos)
+    }
+
+    /// The comments registered so far.
+    pub const fn comments(&self) -> &SingleThreadedComments {
+        &self.comments
+    }
+}
+
 // =============================================================================
 // Identifiers
 // =============================================================================
@@ -67,6 +106,21 @@ macro_rules! ts_kw {
     (unknown) => {
         $crate::openapi::ir::builders::ts_keyword_unknown()
     };
+    (bigint) => {
+        $crate::openapi::ir::builders::ts_keyword_bigint()
+    };
+    (any) => {
+        $crate::openapi::ir::builders::ts_keyword_any()
+    };
+    (undefined) => {
+        $crate::openapi::ir::builders::ts_keyword_undefined()
+    };
+    (never) => {
+        $crate::openapi::ir::builders::ts_keyword_never()
+    };
+    (object) => {
+        $crate::openapi::ir::builders::ts_keyword_object()
+    };
 }
 
 // These are pub so the macro can reference them from other modules.
@@ -90,6 +144,35 @@ pub fn ts_keyword_unknown() -> Box<TsType> {
     ts_keyword(TsKeywordTypeKind::TsUnknownKeyword)
 }
 
+pub fn ts_keyword_bigint() -> Box<TsType> {
+    ts_keyword(TsKeywordTypeKind::TsBigIntKeyword)
+}
+
+pub fn ts_keyword_any() -> Box<TsType> {
+    ts_keyword(TsKeywordTypeKind::TsAnyKeyword)
+}
+
+pub fn ts_keyword_undefined() -> Box<TsType> {
+    ts_keyword(TsKeywordTypeKind::TsUndefinedKeyword)
+}
+
+pub fn ts_keyword_never() -> Box<TsType> {
+    ts_keyword(TsKeywordTypeKind::TsNeverKeyword)
+}
+
+pub fn ts_keyword_object() -> Box<TsType> {
+    ts_keyword(TsKeywordTypeKind::TsObjectKeyword)
+}
+
+pub fn ts_type_predicate(param_name: &str, ty: Box<TsType>) -> Box<TsType> {
+    Box::new(TsType::TsTypePredicate(TsTypePredicate {
+        span: DUMMY_SP,
+        asserts: false,
+        param_name: TsThisTypeOrIdent::Ident(ident(param_name)),
+        type_ann: Some(ts_type_ann(ty)),
+    }))
+}
+
 pub fn ts_type_ref(name: &str) -> Box<TsType> {
     Box::new(TsType::TsTypeRef(TsTypeRef {
         span: DUMMY_SP,
@@ -109,6 +192,34 @@ pub fn ts_type_ref_with_params(name: &str, params: Vec<Box<TsType>>) -> Box<TsTy
     }))
 }
 
+/// `typeof name`
+pub fn ts_typeof(name: &str) -> Box<TsType> {
+    Box::new(TsType::TsTypeQuery(TsTypeQuery {
+        span: DUMMY_SP,
+        expr_name: TsTypeQueryExpr::TsEntityName(TsEntityName::Ident(ident(name))),
+        type_args: None,
+    }))
+}
+
+/// `readonly [A, B, ...]`
+pub fn ts_readonly_tuple(elems: Vec<Box<TsType>>) -> Box<TsType> {
+    Box::new(TsType::TsTypeOperator(TsTypeOperator {
+        span: DUMMY_SP,
+        op: TsTypeOperatorOp::ReadOnly,
+        type_ann: Box::new(TsType::TsTupleType(TsTupleType {
+            span: DUMMY_SP,
+            elem_types: elems
+                .into_iter()
+                .map(|ty| TsTupleElement {
+                    span: DUMMY_SP,
+                    label: None,
+                    ty,
+                })
+                .collect(),
+        })),
+    }))
+}
+
 pub fn ts_array(elem: Box<TsType>) -> Box<TsType> {
     Box::new(TsType::TsArrayType(TsArrayType {
         span: DUMMY_SP,
@@ -193,6 +304,21 @@ pub fn ts_property_sig(name: &str, ty: Box<TsType>, optional: bool) -> TsTypeEle
     })
 }
 
+/// `[key: string]: T` member of an object type.
+pub fn ts_index_sig(value: Box<TsType>, span: Span) -> TsTypeElement {
+    TsTypeElement::TsIndexSignature(TsIndexSignature {
+        span,
+        params: vec![TsFnParam::Ident(binding_ident(
+            "key",
+            Some(ts_kw!(string)),
+            false,
+        ))],
+        type_ann: Some(ts_type_ann(value)),
+        readonly: false,
+        is_static: false,
+    })
+}
+
 pub fn ts_type_ann(ty: Box<TsType>) -> Box<TsTypeAnn> {
     Box::new(TsTypeAnn {
         span: DUMMY_SP,
@@ -207,6 +333,22 @@ pub fn ts_paren(ty: Box<TsType>) -> Box<TsType> {
     }))
 }
 
+/// `(name: T) => R`
+pub fn ts_fn_type(param_name: &str, param_ty: Box<TsType>, ret: Box<TsType>) -> Box<TsType> {
+    Box::new(TsType::TsFnOrConstructorType(
+        TsFnOrConstructorType::TsFnType(TsFnType {
+            span: DUMMY_SP,
+            params: vec![TsFnParam::Ident(binding_ident(
+                param_name,
+                Some(param_ty),
+                false,
+            ))],
+            type_params: None,
+            type_ann: ts_type_ann(ret),
+        }),
+    ))
+}
+
 /// `Promise<T>`
 pub fn promise_type(inner: Box<TsType>) -> Box<TsType> {
     ts_type_ref_with_params("Promise", vec![inner])
@@ -243,6 +385,13 @@ pub fn ident_expr(name: &str) -> Expr {
     Expr::Ident(ident(name))
 }
 
+pub fn import_meta() -> Expr {
+    Expr::MetaProp(MetaPropExpr {
+        span: DUMMY_SP,
+        kind: MetaPropKind::ImportMeta,
+    })
+}
+
 pub fn str_lit(s: &str) -> Expr {
     Expr::Lit(Lit::Str(Str {
         span: DUMMY_SP,
@@ -259,6 +408,15 @@ pub fn num_lit(n: f64) -> Expr {
     }))
 }
 
+/// `5n`
+pub fn bigint_lit(value: BigIntValue) -> Expr {
+    Expr::Lit(Lit::BigInt(BigInt {
+        span: DUMMY_SP,
+        value: Box::new(value),
+        raw: None,
+    }))
+}
+
 pub fn bool_lit(b: bool) -> Expr {
     Expr::Lit(Lit::Bool(Bool {
         span: DUMMY_SP,
@@ -266,6 +424,15 @@ pub fn bool_lit(b: bool) -> Expr {
     }))
 }
 
+/// `/exp/flags`
+pub fn regex_lit(exp: &str, flags: &str) -> Expr {
+    Expr::Lit(Lit::Regex(Regex {
+        span: DUMMY_SP,
+        exp: exp.into(),
+        flags: flags.into(),
+    }))
+}
+
 pub fn null_lit() -> Expr {
     Expr::Lit(Lit::Null(Null { span: DUMMY_SP }))
 }
@@ -345,6 +512,57 @@ pub fn arrow_fn_expr(params: Vec<Pat>, body: Expr) -> Expr {
     })
 }
 
+pub fn arrow_fn_expr_typed(params: Vec<Pat>, ret: Option<Box<TsType>>, body: Expr) -> Expr {
+    Expr::Arrow(ArrowExpr {
+        span: DUMMY_SP,
+        ctxt: SyntaxContext::empty(),
+        params,
+        body: Box::new(BlockStmtOrExpr::Expr(Box::new(body))),
+        is_async: false,
+        is_generator: false,
+        type_params: None,
+        return_type: ret.map(ts_type_ann),
+    })
+}
+
+pub fn arrow_fn_block(params: Vec<Pat>, ret: Option<Box<TsType>>, body: BlockStmt) -> Expr {
+    Expr::Arrow(ArrowExpr {
+        span: DUMMY_SP,
+        ctxt: SyntaxContext::empty(),
+        params,
+        body: Box::new(BlockStmtOrExpr::BlockStmt(body)),
+        is_async: false,
+        is_generator: false,
+        type_params: None,
+        return_type: ret.map(ts_type_ann),
+    })
+}
+
+/// `function (params): ret { ... }`, for callbacks that receive their own `this`.
+pub fn fn_expr(params: Vec<Pat>, ret: Option<Box<TsType>>, body: BlockStmt) -> Expr {
+    Expr::Fn(FnExpr {
+        ident: None,
+        function: Box::new(Function {
+            params: params
+                .into_iter()
+                .map(|pat| Param {
+                    span: DUMMY_SP,
+                    decorators: vec![],
+                    pat,
+                })
+                .collect(),
+            decorators: vec![],
+            span: DUMMY_SP,
+            ctxt: SyntaxContext::empty(),
+            body: Some(body),
+            is_generator: false,
+            is_async: false,
+            type_params: None,
+            return_type: ret.map(ts_type_ann),
+        }),
+    })
+}
+
 pub fn await_expr(expr: Expr) -> Expr {
     Expr::Await(AwaitExpr {
         span: DUMMY_SP,
@@ -401,6 +619,74 @@ pub fn obj_lit(props: Vec<PropOrSpread>) -> Expr {
     })
 }
 
+/// Convert a JSON value to an equivalent object/array literal expression.
+pub fn json_lit(value: &serde_json::Value) -> Expr {
+    json_lit_with_bigints(value, &[])
+}
+
+/// Like [`json_lit`], with the integers at `bigint_paths` emitted as `bigint` literals.
+///
+/// Paths use the encoding of the generated bigint reviver: a property name,
+/// or `None` for any array index or map key.
+pub fn json_lit_with_bigints(
+    value: &serde_json::Value,
+    bigint_paths: &[Vec<Option<String>>],
+) -> Expr {
+    let paths: Vec<&[Option<String>]> = bigint_paths.iter().map(Vec::as_slice).collect();
+    json_lit_at(value, &paths)
+}
+
+/// `paths` are the remainders of the bigint paths that lead to `value`.
+fn json_lit_at(value: &serde_json::Value, paths: &[&[Option<String>]]) -> Expr {
+    match value {
+        serde_json::Value::Number(n) if paths.iter().any(|p| p.is_empty()) => n
+            .as_i64()
+            .map(BigIntValue::from)
+            .or_else(|| n.as_u64().map(BigIntValue::from))
+            .map_or_else(|| num_lit(n.as_f64().unwrap_or_default()), bigint_lit),
+        serde_json::Value::Null => null_lit(),
+        serde_json::Value::Bool(b) => bool_lit(*b),
+        serde_json::Value::Number(n) => num_lit(n.as_f64().unwrap_or_default()),
+        serde_json::Value::String(s) => str_lit(s),
+        serde_json::Value::Array(items) => {
+            let paths = descend_bigint_paths(paths, None);
+            array_lit(items.iter().map(|item| json_lit_at(item, &paths)).collect())
+        }
+        serde_json::Value::Object(map) => obj_lit(
+            map.iter()
+                .map(|(key, value)| {
+                    let value = json_lit_at(value, &descend_bigint_paths(paths, Some(key)));
+                    if super::utils::needs_bracket_notation(key) {
+                        kv_prop_str(key, value)
+                    } else {
+                        kv_prop(key, value)
+                    }
+                })
+                .collect(),
+        ),
+    }
+}
+
+/// The bigint paths continuing below property `key` (`None` for an array item).
+fn descend_bigint_paths<'p>(
+    paths: &[&'p [Option<String>]],
+    key: Option<&str>,
+) -> Vec<&'p [Option<String>]> {
+    paths
+        .iter()
+        .filter_map(|path| match path.split_first() {
+            Some((None, rest)) => Some(rest),
+            Some((Some(name), rest)) if Some(name.as_str()) == key => Some(rest),
+            _ => None,
+        })
+        .collect()
+}
+
+/// `name` shorthand property, as in `{ listItems }`.
+pub fn shorthand_prop(name: &str) -> PropOrSpread {
+    PropOrSpread::Prop(Box::new(Prop::Shorthand(ident(name))))
+}
+
 pub fn kv_prop(key: &str, value: Expr) -> PropOrSpread {
     PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
         key: PropName::Ident(ident_name(key)),
@@ -455,6 +741,15 @@ pub fn as_const(expr: Expr) -> Expr {
     })
 }
 
+/// `expr satisfies T`
+pub fn satisfies(expr: Expr, ty: Box<TsType>) -> Expr {
+    Expr::TsSatisfies(TsSatisfiesExpr {
+        span: DUMMY_SP,
+        expr: Box::new(expr),
+        type_ann: ty,
+    })
+}
+
 pub fn ts_as_expr(expr: Expr, ty: Box<TsType>) -> Expr {
     Expr::TsAs(TsAsExpr {
         span: DUMMY_SP,
@@ -463,6 +758,13 @@ pub fn ts_as_expr(expr: Expr, ty: Box<TsType>) -> Expr {
     })
 }
 
+pub fn paren_expr(expr: Expr) -> Expr {
+    Expr::Paren(ParenExpr {
+        span: DUMMY_SP,
+        expr: Box::new(expr),
+    })
+}
+
 pub fn cond_expr(test: Expr, cons: Expr, alt: Expr) -> Expr {
     Expr::Cond(CondExpr {
         span: DUMMY_SP,
@@ -493,6 +795,14 @@ pub fn unary_not(expr: Expr) -> Expr {
     })
 }
 
+pub fn typeof_expr(expr: Expr) -> Expr {
+    Expr::Unary(UnaryExpr {
+        span: DUMMY_SP,
+        op: UnaryOp::TypeOf,
+        arg: Box::new(expr),
+    })
+}
+
 pub fn assign_expr(target: Expr, value: Expr) -> Expr {
     Expr::Assign(AssignExpr {
         span: DUMMY_SP,
@@ -505,6 +815,16 @@ pub fn assign_expr(target: Expr, value: Expr) -> Expr {
     })
 }
 
+/// `name = value` for a plain variable.
+pub fn assign_ident(name: &str, value: Expr) -> Expr {
+    Expr::Assign(AssignExpr {
+        span: DUMMY_SP,
+        op: AssignOp::Assign,
+        left: AssignTarget::Simple(SimpleAssignTarget::Ident(binding_ident(name, None, false))),
+        right: Box::new(value),
+    })
+}
+
 // =============================================================================
 // Statements
 // =============================================================================
@@ -540,6 +860,55 @@ pub fn if_stmt(test: Expr, cons: Stmt, alt: Option<Stmt>) -> Stmt {
     })
 }
 
+/// `for (let counter = 0; ; counter++) { ... }`, left only by `return` or `throw`.
+pub fn counting_loop(counter: &str, body: BlockStmt) -> Stmt {
+    Stmt::For(ForStmt {
+        span: DUMMY_SP,
+        init: Some(VarDeclOrExpr::VarDecl(Box::new(VarDecl {
+            span: DUMMY_SP,
+            ctxt: SyntaxContext::empty(),
+            kind: VarDeclKind::Let,
+            declare: false,
+            decls: vec![VarDeclarator {
+                span: DUMMY_SP,
+                name: Pat::Ident(binding_ident(counter, None, false)),
+                init: Some(Box::new(num_lit(0.0))),
+                definite: false,
+            }],
+        }))),
+        test: None,
+        update: Some(Box::new(Expr::Update(UpdateExpr {
+            span: DUMMY_SP,
+            op: UpdateOp::PlusPlus,
+            prefix: false,
+            arg: Box::new(ident_expr(counter)),
+        }))),
+        body: Box::new(Stmt::Block(body)),
+    })
+}
+
+/// `for (const name of iterable) { ... }`
+pub fn for_of_const(name: &str, iterable: Expr, body: BlockStmt) -> Stmt {
+    Stmt::ForOf(ForOfStmt {
+        span: DUMMY_SP,
+        is_await: false,
+        left: ForHead::VarDecl(Box::new(VarDecl {
+            span: DUMMY_SP,
+            ctxt: SyntaxContext::empty(),
+            kind: VarDeclKind::Const,
+            declare: false,
+            decls: vec![VarDeclarator {
+                span: DUMMY_SP,
+                name: Pat::Ident(binding_ident(name, None, false)),
+                init: None,
+                definite: false,
+            }],
+        })),
+        right: Box::new(iterable),
+        body: Box::new(Stmt::Block(body)),
+    })
+}
+
 pub fn throw_stmt(expr: Expr) -> Stmt {
     Stmt::Throw(ThrowStmt {
         span: DUMMY_SP,
@@ -579,6 +948,24 @@ pub fn try_stmt(block_body: BlockStmt, catch_body: Option<BlockStmt>) -> Stmt {
     }))
 }
 
+/// `try { ... } catch (param) { ... } finally { ... }` with optional catch and finally blocks.
+pub fn try_catch_finally_stmt(
+    block_body: BlockStmt,
+    catch: Option<(&str, BlockStmt)>,
+    finalizer: Option<BlockStmt>,
+) -> Stmt {
+    Stmt::Try(Box::new(TryStmt {
+        span: DUMMY_SP,
+        block: block_body,
+        handler: catch.map(|(param, body)| CatchClause {
+            span: DUMMY_SP,
+            param: Some(pat_ident(param, None, false)),
+            body,
+        }),
+        finalizer,
+    }))
+}
+
 // =============================================================================
 // Module Items
 // =============================================================================
@@ -610,6 +997,54 @@ pub fn import_named(items: Vec<(&str, Option<&str>)>, from: &str, type_only: boo
     }))
 }
 
+/// `export * from "<from>";`
+pub fn export_all(from: &str) -> ModuleItem {
+    ModuleItem::ModuleDecl(ModuleDecl::ExportAll(ExportAll {
+        span: DUMMY_SP,
+        src: Box::new(Str {
+            span: DUMMY_SP,
+            value: from.into(),
+            raw: None,
+        }),
+        type_only: false,
+        with: None,
+    }))
+}
+
+/// Names declared by the `export` declarations among `items`, each flagged `true`
+/// when it only exists as a type (interfaces and type aliases).
+pub fn exported_names(items: &[ModuleItem]) -> Vec<(String, bool)> {
+    let mut names: Vec<(String, bool)> = Vec::new();
+    for item in items {
+        let ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export)) = item else {
+            continue;
+        };
+        let declared = match &export.decl {
+            Decl::Class(class) => vec![(class.ident.sym.to_string(), false)],
+            Decl::Fn(func) => vec![(func.ident.sym.to_string(), false)],
+            Decl::Var(var) => var
+                .decls
+                .iter()
+                .filter_map(|decl| match &decl.name {
+                    Pat::Ident(binding) => Some((binding.id.sym.to_string(), false)),
+                    _ => None,
+                })
+                .collect(),
+            Decl::TsInterface(interface) => vec![(interface.id.sym.to_string(), true)],
+            Decl::TsTypeAlias(alias) => vec![(alias.id.sym.to_string(), true)],
+            Decl::TsEnum(ts_enum) => vec![(ts_enum.id.sym.to_string(), false)],
+            _ => Vec::new(),
+        };
+        for (name, type_only) in declared {
+            // Function overloads declare the same name more than once
+            if !names.iter().any(|(existing, _)| *existing == name) {
+                names.push((name, type_only));
+            }
+        }
+    }
+    names
+}
+
 pub fn export_decl(decl: Decl) -> ModuleItem {
     ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
         span: DUMMY_SP,
@@ -617,6 +1052,39 @@ pub fn export_decl(decl: Decl) -> ModuleItem {
     }))
 }
 
+/// Attach a `/** text */` comment to an exported declaration.
+pub fn with_jsdoc(mut item: ModuleItem, text: &str, docs: &DocComments) -> ModuleItem {
+    if let ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(decl)) = &mut item {
+        decl.span = docs.jsdoc_span(text);
+    }
+    item
+}
+
+/// `const`/`let` declaration of a single, optionally typed binding.
+pub fn var_decl(kind: VarDeclKind, name: &str, ty: Option<Box<TsType>>, init: Expr) -> Decl {
+    Decl::Var(Box::new(VarDecl {
+        span: DUMMY_SP,
+        ctxt: SyntaxContext::empty(),
+        kind,
+        declare: false,
+        decls: vec![VarDeclarator {
+            span: DUMMY_SP,
+            name: Pat::Ident(binding_ident(name, ty, false)),
+            init: Some(Box::new(init)),
+            definite: false,
+        }],
+    }))
+}
+
+pub fn export_const(name: &str, ty: Option<Box<TsType>>, init: Expr) -> ModuleItem {
+    export_decl(var_decl(VarDeclKind::Const, name, ty, init))
+}
+
+/// `export let name: T = init;` (a live binding importers see reassignments of)
+pub fn export_let(name: &str, ty: Option<Box<TsType>>, init: Expr) -> ModuleItem {
+    export_decl(var_decl(VarDeclKind::Let, name, ty, init))
+}
+
 pub fn export_const_arrow(
     name: &str,
     params: Vec<Pat>,
@@ -696,16 +1164,35 @@ pub fn export_interface(name: &str, props: Vec<TsTypeElement>) -> ModuleItem {
 }
 
 pub fn export_type_alias(name: &str, ty: Box<TsType>) -> ModuleItem {
+    export_type_alias_with_params(name, vec![], ty)
+}
+
+/// `export type Name<Params> = ty;` (no angle brackets when `type_params` is empty).
+pub fn export_type_alias_with_params(
+    name: &str,
+    type_params: Vec<TsTypeParam>,
+    ty: Box<TsType>,
+) -> ModuleItem {
     export_decl(Decl::TsTypeAlias(Box::new(TsTypeAliasDecl {
         span: DUMMY_SP,
         declare: false,
         id: ident(name),
-        type_params: None,
+        type_params: (!type_params.is_empty()).then(|| {
+            Box::new(TsTypeParamDecl {
+                span: DUMMY_SP,
+                params: type_params,
+            })
+        }),
         type_ann: ty,
     })))
 }
 
-pub fn export_class(name: &str, super_class: Option<&str>, body: Vec<ClassMember>) -> ModuleItem {
+pub fn export_class(
+    name: &str,
+    type_params: Vec<TsTypeParam>,
+    super_class: Option<&str>,
+    body: Vec<ClassMember>,
+) -> ModuleItem {
     export_decl(Decl::Class(ClassDecl {
         ident: ident(name),
         declare: false,
@@ -716,7 +1203,12 @@ pub fn export_class(name: &str, super_class: Option<&str>, body: Vec<ClassMember
             body,
             super_class: super_class.map(|s| Box::new(ident_expr(s))),
             is_abstract: false,
-            type_params: None,
+            type_params: (!type_params.is_empty()).then(|| {
+                Box::new(TsTypeParamDecl {
+                    span: DUMMY_SP,
+                    params: type_params,
+                })
+            }),
             super_type_params: None,
             implements: vec![],
         }),
@@ -724,6 +1216,10 @@ pub fn export_class(name: &str, super_class: Option<&str>, body: Vec<ClassMember
 }
 
 pub fn class_prop(name: &str, ty: Box<TsType>) -> ClassMember {
+    typed_class_prop(name, ty, false)
+}
+
+fn typed_class_prop(name: &str, ty: Box<TsType>, declare: bool) -> ClassMember {
     ClassMember::ClassProp(ClassProp {
         span: DUMMY_SP,
         key: PropName::Ident(ident_name(name)),
@@ -736,11 +1232,16 @@ pub fn class_prop(name: &str, ty: Box<TsType>) -> ClassMember {
         is_optional: false,
         is_override: false,
         readonly: false,
-        declare: false,
+        declare,
         definite: false,
     })
 }
 
+/// `declare name: ty;`, narrowing an inherited property's type without emitting a field.
+pub fn declared_class_prop(name: &str, ty: Box<TsType>) -> ClassMember {
+    typed_class_prop(name, ty, true)
+}
+
 pub fn constructor(params: Vec<ParamOrTsParamProp>, body: BlockStmt) -> ClassMember {
     ClassMember::Constructor(Constructor {
         span: DUMMY_SP,
@@ -773,35 +1274,56 @@ pub fn constructor_param(name: &str, ty: Option<Box<TsType>>) -> ParamOrTsParamP
     ParamOrTsParamProp::Param(param(name, ty, false))
 }
 
+/// `name: ty = default` constructor parameter.
+pub fn constructor_param_with_default(
+    name: &str,
+    ty: Box<TsType>,
+    default: Expr,
+) -> ParamOrTsParamProp {
+    ParamOrTsParamProp::Param(Param {
+        span: DUMMY_SP,
+        decorators: vec![],
+        pat: Pat::Assign(AssignPat {
+            span: DUMMY_SP,
+            left: Box::new(pat_ident(name, Some(ty), false)),
+            right: Box::new(default),
+        }),
+    })
+}
+
 // =============================================================================
 // Conversion from custom IR types -> SWC types
 // =============================================================================
 
 /// Convert our IR `TsType` to an SWC `TsType`.
-pub fn ir_type_to_swc(ty: &ir::TsType) -> Box<TsType> {
+pub fn ir_type_to_swc(ty: &ir::TsType, docs: &DocComments) -> Box<TsType> {
     match ty {
         ir::TsType::Primitive(p) => match p {
-            ir::TsPrimitive::String => ts_kw!(string),
-            ir::TsPrimitive::Number => ts_kw!(number),
+            ir::TsPrimitive::String | ir::TsPrimitive::Format(_) => ts_kw!(string),
+            ir::TsPrimitive::Number | ir::TsPrimitive::Int64 => ts_kw!(number),
+            ir::TsPrimitive::BigInt => ts_kw!(bigint),
             ir::TsPrimitive::Boolean => ts_kw!(boolean),
             ir::TsPrimitive::Null => ts_kw!(null),
             ir::TsPrimitive::Void => ts_kw!(void),
             ir::TsPrimitive::Unknown => ts_kw!(unknown),
+            ir::TsPrimitive::Never => ts_kw!(never),
         },
         ir::TsType::Array(inner) => {
-            let elem = ir_type_to_swc(inner);
+            let elem = ir_type_to_swc(inner, docs);
             // Wrap union/intersection in parens: (A | B)[]
             match &**inner {
                 ir::TsType::Union(_) | ir::TsType::Intersection(_) => ts_array(ts_paren(elem)),
                 _ => ts_array(elem),
             }
         }
-        ir::TsType::Union(types) => ts_union(types.iter().map(ir_type_to_swc).collect()),
+        ir::TsType::Union(types) => {
+            ts_union(types.iter().map(|t| ir_type_to_swc(t, docs)).collect())
+        }
         ir::TsType::Intersection(types) => {
             let parts: Vec<_> = types
                 .iter()
                 .map(|t| {
-                    let swc_t = ir_type_to_swc(t);
+                    let swc_t = ir_type_to_swc(t, docs);
                     // Wrap union types in parens within intersection
                     if matches!(t, ir::TsType::Union(_)) {
                         ts_paren(swc_t)
@@ -813,17 +1335,54 @@ pub fn ir_type_to_swc(ty: &ir::TsType) -> Box<TsType> {
             ts_intersection(parts)
         }
         ir::TsType::Object(props) => {
-            let members = props
-                .iter()
-                .map(|p| ts_property_sig(&p.name, ir_type_to_swc(&p.ty), p.optional))
-                .collect();
-            ts_object_type(members)
-        }
-        ir::TsType::Record { key, value } => {
-            ts_type_ref_with_params("Record", vec![ir_type_to_swc(key), ir_type_to_swc(value)])
+            ts_object_type(props.iter().map(|p| ir_prop_to_swc(p, docs)).collect())
         }
+        ir::TsType::Record { key, value } => ts_type_ref_with_params(
+            "Record",
+            vec![ir_type_to_swc(key, docs), ir_type_to_swc(value, docs)],
+        ),
         ir::TsType::Literal(lit) => ir_literal_to_swc_type(lit),
         ir::TsType::Ref(name) => ts_type_ref(name),
+        ir::TsType::IndexSignature { value, doc } => {
+            let span = doc.as_deref().map_or(DUMMY_SP, |doc| docs.jsdoc_span(doc));
+            ts_object_type(vec![ts_index_sig(ir_type_to_swc(value, docs), span)])
+        }
+    }
+}
+
+/// Convert our IR `TsProp` to a property signature, documenting its constraints.
+fn ir_prop_to_swc(prop: &ir::TsProp, docs: &DocComments) -> TsTypeElement {
+    let ty = ir_type_to_swc(&prop.ty, docs);
+    let ty = if prop.readonly {
+        ts_readonly_arrays(ty)
+    } else {
+        ty
+    };
+    let mut sig = ts_property_sig(&prop.name, ty, prop.optional);
+    if let TsTypeElement::TsPropertySignature(s) = &mut sig {
+        s.readonly = prop.readonly;
+        if let Some(doc) = prop.constraints.jsdoc() {
+            s.span = docs.jsdoc_span(&doc);
+        }
+    }
+    sig
+}
+
+/// Turn an array type, or the array members of a union, into `readonly T[]`.
+fn ts_readonly_arrays(ty: Box<TsType>) -> Box<TsType> {
+    match *ty {
+        TsType::TsArrayType(array) => Box::new(TsType::TsTypeOperator(TsTypeOperator {
+            span: DUMMY_SP,
+            op: TsTypeOperatorOp::ReadOnly,
+            type_ann: Box::new(TsType::TsArrayType(array)),
+        })),
+        TsType::TsUnionOrIntersectionType(TsUnionOrIntersectionType::TsUnionType(mut union)) => {
+            union.types = union.types.into_iter().map(ts_readonly_arrays).collect();
+            Box::new(TsType::TsUnionOrIntersectionType(
+                TsUnionOrIntersectionType::TsUnionType(union),
+            ))
+        }
+        other => Box::new(other),
     }
 }
 
@@ -839,28 +1398,25 @@ fn ir_literal_to_swc_type(lit: &ir::TsLiteral) -> Box<TsType> {
 }
 
 /// Convert our IR `TypeRef` to an SWC `TsType`.
-pub fn ir_typeref_to_swc(tr: &TypeRef) -> Box<TsType> {
+pub fn ir_typeref_to_swc(tr: &TypeRef, docs: &DocComments) -> Box<TsType> {
     match tr {
         TypeRef::Named(name) => ts_type_ref(name),
-        TypeRef::Inline(t) => ir_type_to_swc(t),
+        TypeRef::Inline(t) => ir_type_to_swc(t, docs),
     }
 }
 
 /// Convert our IR `TsTypeDef` to SWC `ModuleItem`(s).
-pub fn ir_typedef_to_module_items(td: &ir::TsTypeDef) -> Vec<ModuleItem> {
+pub fn ir_typedef_to_module_items(td: &ir::TsTypeDef, docs: &DocComments) -> Vec<ModuleItem> {
     match &td.kind {
         ir::TypeDefKind::Interface { properties } => {
-            let props = properties
-                .iter()
-                .map(|p| ts_property_sig(&p.name, ir_type_to_swc(&p.ty), p.optional))
-                .collect();
+            let props = properties.iter().map(|p| ir_prop_to_swc(p, docs)).collect();
             vec![export_interface(&td.name, props)]
         }
         ir::TypeDefKind::TypeAlias { ty } => {
-            vec![export_type_alias(&td.name, ir_type_to_swc(ty))]
+            vec![export_type_alias(&td.name, ir_type_to_swc(ty, docs))]
         }
         ir::TypeDefKind::ConstEnum { values } => {
-            // export const Name = { key: value, ... } as const;
+            // export const Name = { key: value, ... } as const satisfies Record<string, V>;
             // Keys come from enum_value_to_key which may pre-quote them with "..."
             // We need to strip those quotes for SWC since SWC handles quoting itself.
             let props: Vec<PropOrSpread> = values
@@ -879,7 +1435,13 @@ pub fn ir_typedef_to_module_items(td: &ir::TsTypeDef) -> Vec<ModuleItem> {
                     }
                 })
                 .collect();
-            let const_obj = as_const(obj_lit(props));
+            let const_obj = satisfies(
+                as_const(obj_lit(props)),
+                ts_type_ref_with_params(
+                    "Record",
+                    vec![ts_kw!(string), const_enum_value_type(values)],
+                ),
+            );
             let const_decl_item = export_decl(Decl::Var(Box::new(VarDecl {
                 span: DUMMY_SP,
                 ctxt: SyntaxContext::empty(),
@@ -894,11 +1456,7 @@ pub fn ir_typedef_to_module_items(td: &ir::TsTypeDef) -> Vec<ModuleItem> {
             })));
 
             // export type Name = (typeof Name)[keyof typeof Name];
-            let typeof_name = Box::new(TsType::TsTypeQuery(TsTypeQuery {
-                span: DUMMY_SP,
-                expr_name: TsTypeQueryExpr::TsEntityName(TsEntityName::Ident(ident(&td.name))),
-                type_args: None,
-            }));
+            let typeof_name = ts_typeof(&td.name);
             let keyof_typeof = Box::new(TsType::TsTypeOperator(TsTypeOperator {
                 span: DUMMY_SP,
                 op: TsTypeOperatorOp::KeyOf,
@@ -918,6 +1476,29 @@ pub fn ir_typedef_to_module_items(td: &ir::TsTypeDef) -> Vec<ModuleItem> {
 }
 
 /// Convert our IR literal to an SWC expression.
+/// Union of the primitive types of an enum's values (e.g. `string | number`).
+fn const_enum_value_type(values: &[(String, ir::TsLiteral)]) -> Box<TsType> {
+    let has = |pred: fn(&ir::TsLiteral) -> bool| values.iter().any(|(_, lit)| pred(lit));
+    let mut types = Vec::new();
+    if has(|lit| matches!(lit, ir::TsLiteral::String(_))) {
+        types.push(ts_kw!(string));
+    }
+    if has(|lit| matches!(lit, ir::TsLiteral::Number(_) | ir::TsLiteral::Int(_))) {
+        types.push(ts_kw!(number));
+    }
+    if has(|lit| matches!(lit, ir::TsLiteral::Bool(_))) {
+        types.push(ts_kw!(boolean));
+    }
+    if has(|lit| matches!(lit, ir::TsLiteral::Null)) {
+        types.push(ts_kw!(null));
+    }
+    match types.len() {
+        0 => ts_kw!(unknown),
+        1 => types.remove(0),
+        _ => ts_union(types),
+    }
+}
+
 fn ir_literal_to_expr(lit: &ir::TsLiteral) -> Expr {
     match lit {
         ir::TsLiteral::String(s) => str_lit(s),
@@ -10,43 +10,1337 @@ use swc_common::DUMMY_SP;
 use swc_ecma_ast::*;
 
 use super::api::{
-    ApiIR, BodyContentType, FetchArgIR, FetchIR, HookIR, HookKind, OperationIR, ParamsIR,
-    QueryKeyIR, ResponseContentType, UrlPart,
+    ApiIR, BodyAliasIR, BodyContentType, BodyIR, FetchArgIR, FetchIR, HookIR, HookKind, HttpMethod,
+    MatchDispatchIR, MatchKind, OperationIR, ParamsIR, QueryKeyIR, ResponseContentType, ResponseIR,
+    ServerIR, TypeGuardIR, UnionMatcherIR, UrlPart, WebhookIR,
 };
 // Reason: codegen module uses most items from parent; explicit list would be unwieldy
 #[allow(clippy::wildcard_imports)]
 use super::builders::*;
-use super::types::{TsType as IrTsType, TypeRef};
-use super::utils::{escape_js_string, needs_bracket_notation};
+use super::types::{
+    StringFormat, TsConstraints, TsLiteral, TsPrimitive, TsProp, TsType as IrTsType, TypeRef,
+};
+use super::utils::{
+    capitalize_first, escape_js_string, needs_bracket_notation, sanitize_ts_identifier,
+};
+use crate::openapi::options::{BaseUrl, GenerateOptions};
+
+/// Name of the exported constant holding the API base URL.
+const BASE_URL_CONST: &str = "API_BASE_URL";
+
+/// Name of the module-level variable every request is sent through.
+const FETCHER_VAR: &str = "fetcher";
+
+/// Name of the module-level headers merged into every request, replaced by `setDefaultHeaders`.
+const DEFAULT_HEADERS_VAR: &str = "defaultHeaders";
+
+/// Name of the options type accepted by fetch functions.
+const REQUEST_INIT_TYPE: &str = "ApiRequestInit";
+
+/// Name of the helper applying `timeoutMs` around [`FETCHER_VAR`].
+const FETCH_WITH_TIMEOUT_FN: &str = "fetchWithTimeout";
+
+/// Name of the helper retrying [`FETCH_WITH_TIMEOUT_FN`] with exponential backoff.
+const FETCH_WITH_RETRY_FN: &str = "fetchWithRetry";
+
+/// Name of the helper turning a `Retry-After` header into a delay in milliseconds.
+const PARSE_RETRY_AFTER_FN: &str = "parseRetryAfter";
+
+/// Statuses retried when the caller doesn't pass `retryOn`.
+const DEFAULT_RETRY_STATUSES: [u16; 2] = [429, 503];
+
+/// Retries for idempotent methods when the caller doesn't pass `retries`.
+const DEFAULT_RETRIES: u8 = 2;
+
+/// First backoff delay when the caller doesn't pass `retryBaseMs`.
+const DEFAULT_RETRY_BASE_MS: u16 = 250;
+
+/// Name of the interface describing templated server URL variables.
+const SERVER_VARIABLES_TYPE: &str = "ServerVariables";
+
+/// File of the split client (see [`GenerateOptions::split_modules`]) a generated item goes to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModuleSection {
+    /// Component and params types, plus the brand, guard and matcher helpers built on them
+    Types,
+    /// Errors, fetch functions, query keys, the route table and link builders
+    Client,
+    /// React Query hooks, prefetch helpers and per-tag groups
+    Hooks,
+}
+
+impl ModuleSection {
+    /// Sections in import order: each one only imports from those before it.
+    pub const ALL: [Self; 3] = [Self::Types, Self::Client, Self::Hooks];
+
+    /// File name without extension, also the import specifier after `./`.
+    pub fn file_stem(self) -> &'static str {
+        match self {
+            Self::Types => "types",
+            Self::Client => "client",
+            Self::Hooks => "hooks",
+        }
+    }
+}
 
 /// Generate a complete SWC Module from API IR.
-pub fn codegen_module(api: &ApiIR) -> Module {
+pub fn codegen_module(api: &ApiIR, options: &GenerateOptions, docs: &DocComments) -> Module {
+    Module {
+        span: DUMMY_SP,
+        body: codegen_items(api, options, docs)
+            .into_iter()
+            .map(|(_, item)| item)
+            .collect(),
+        shebang: None,
+    }
+}
+
+/// Generate the module items of the client in output order, tagged with their section.
+pub fn codegen_items(
+    api: &ApiIR,
+    options: &GenerateOptions,
+    docs: &DocComments,
+) -> Vec<(ModuleSection, ModuleItem)> {
+    use ModuleSection::{Client, Hooks, Types};
+
     let mut body = Vec::new();
 
     // Generate imports
     if api.has_queries || api.has_mutations {
-        body.extend(codegen_imports(api.has_queries, api.has_mutations));
-        // Generate ApiError class
-        body.push(codegen_api_error_class());
+        body.extend(
+            codegen_imports(api.has_queries, api.has_mutations)
+                .into_iter()
+                .map(|item| (Hooks, item)),
+        );
+        // Generate ApiError and its failure kinds
+        body.push((Client, codegen_api_error_kind_type()));
+        body.push((Client, codegen_api_error_class()));
+        body.push((Client, codegen_api_timeout_error_class()));
+        body.push((Client, codegen_api_network_error_class()));
+        body.push((Client, codegen_api_parse_error_class()));
+        body.push((Client, codegen_api_failure_type()));
+        if options.safe_results {
+            body.push((Client, codegen_api_result_type()));
+        }
+    }
+
+    // Generate base URL constant
+    let base_url = codegen_base_url(&options.base_url, api.server.as_ref());
+    let has_base_url = !base_url.is_empty() && !api.operations.is_empty();
+    if has_base_url {
+        body.extend(base_url.into_iter().map(|item| (Client, item)));
+    }
+
+    // Generate the swappable fetch implementation and timeout support
+    if !api.operations.is_empty() {
+        body.extend(codegen_fetcher().into_iter().map(|item| (Client, item)));
+        body.push((Client, codegen_request_init_type(options.fetch_retry)));
+        body.push((Client, codegen_fetch_with_timeout()));
+        if options.fetch_retry {
+            body.push((Client, codegen_parse_retry_after()));
+            body.push((Client, codegen_fetch_with_retry()));
+        }
+    }
+
+    // Add the JSON body parser reporting unparsable responses as ApiParseError
+    let ops = &api.operations;
+    if ops.iter().any(|op| parses_json(&op.fetch.response)) {
+        body.push((Client, codegen_parse_json_body()));
+    }
+
+    // Add the server-sent event type and the stream parser behind event stream responses
+    if ops
+        .iter()
+        .any(|op| op.fetch.response.content_type == ResponseContentType::EventStream)
+    {
+        body.push((Types, codegen_server_sent_event_type(docs)));
+        body.push((Client, codegen_parse_server_sent_event()));
+        body.push((Client, codegen_parse_event_stream()));
+    }
+
+    // Add the FormData assembler behind the typed multipart builders
+    if ops.iter().any(has_form_fields) {
+        body.push((Client, codegen_to_form_data()));
+    }
+
+    // Add the check behind the per-operation required params guards
+    if options.param_guards && ops.iter().any(|op| guarded_params(op).is_some()) {
+        body.push((Client, codegen_assert_required_params()));
+    }
+
+    // Add bigint JSON helpers when int64 fields are emitted as bigint
+    if ops.iter().any(|op| !op.fetch.bigint_paths.is_empty()) {
+        body.push((Client, codegen_parse_bigint_json()));
+    }
+    if ops.iter().any(|op| op.fetch.bigint_body) {
+        body.push((Client, codegen_stringify_bigint_json()));
+    }
+
+    // Add branded string types and their cast helpers
+    for &format in &api.brands {
+        body.extend(codegen_brand(format).into_iter().map(|item| (Types, item)));
     }
 
     // Add component schema types
     for td in &api.types {
-        body.extend(ir_typedef_to_module_items(td));
+        body.extend(
+            ir_typedef_to_module_items(td, docs)
+                .into_iter()
+                .map(|item| (Types, item)),
+        );
+    }
+
+    // Add relaxed body types for defaulted required properties
+    for alias in &api.body_aliases {
+        body.push((Types, codegen_body_alias(alias, docs)));
+    }
+
+    // Add type guards for discriminated unions
+    for guard in &api.type_guards {
+        body.push((Types, codegen_type_guard(guard, docs)));
+    }
+
+    // Add exhaustive matchers for component unions
+    if options.union_matchers {
+        for matcher in &api.union_matchers {
+            body.push((Types, codegen_union_matcher(matcher, docs)));
+        }
+    }
+
+    // Add webhook payload types, the event union and its dispatcher
+    if !api.webhooks.is_empty() {
+        for webhook in &api.webhooks {
+            body.extend(
+                ir_typedef_to_module_items(&webhook.payload, docs)
+                    .into_iter()
+                    .map(|item| (Types, item)),
+            );
+        }
+        body.push((Types, codegen_webhook_event_type(&api.webhooks)));
+        if options.union_matchers {
+            body.push((Types, codegen_handle_webhook(&api.webhooks)));
+        }
     }
 
     // Generate operations
+    let fetch_fn = if options.fetch_retry {
+        FETCH_WITH_RETRY_FN
+    } else {
+        FETCH_WITH_TIMEOUT_FN
+    };
     for op in &api.operations {
-        body.extend(codegen_operation(op));
+        body.extend(codegen_operation(op, has_base_url, options, fetch_fn, docs));
     }
 
-    Module {
+    // Generate route table
+    if !api.operations.is_empty() {
+        body.extend(
+            codegen_route_table(&api.operations)
+                .into_iter()
+                .map(|item| (Client, item)),
+        );
+    }
+
+    // Generate link builders for GET operations
+    if api
+        .operations
+        .iter()
+        .any(|op| op.fetch.method == HttpMethod::Get)
+    {
+        body.push((Client, codegen_links(&api.operations, docs)));
+    }
+
+    // Generate per-tag namespace objects
+    if options.tag_groups {
+        body.extend(
+            codegen_tag_groups(api)
+                .into_iter()
+                .map(|item| (Hooks, item)),
+        );
+    }
+
+    body
+}
+
+/// Name of the helper reading a JSON response body.
+const PARSE_JSON_BODY_FN: &str = "parseJsonBody";
+
+/// Whether the fetch function may read its response as JSON.
+fn parses_json(response: &ResponseIR) -> bool {
+    match response.content_type {
+        ResponseContentType::Json => true,
+        ResponseContentType::Negotiated => response
+            .variants
+            .iter()
+            .any(|v| v.content_type == ResponseContentType::Json),
+        _ => false,
+    }
+}
+
+/// Generate the JSON body reader used by fetch functions:
+///
+/// ```ts
+/// const parseJsonBody = async (res: Response, parse: (text: string) => any = JSON.parse): Promise<any> => {
+///   const text = await res.text();
+///   try {
+///     return parse(text);
+///   } catch (error) {
+///     throw new ApiParseError(res.status, res.statusText, text, error);
+///   }
+/// };
+/// ```
+///
+/// `parse` lets the bigint reviver replace plain `JSON.parse`.
+fn codegen_parse_json_body() -> ModuleItem {
+    let stmts = vec![
+        const_decl(
+            "text",
+            await_expr(call(member(ident_expr("res"), "text"), vec![])),
+        ),
+        try_catch_finally_stmt(
+            block(vec![return_stmt(Some(call(
+                ident_expr("parse"),
+                vec![ident_expr("text")],
+            )))]),
+            Some((
+                "error",
+                block(vec![throw_stmt(new_expr(
+                    ident_expr("ApiParseError"),
+                    vec![
+                        member(ident_expr("res"), "status"),
+                        member(ident_expr("res"), "statusText"),
+                        ident_expr("text"),
+                        ident_expr("error"),
+                    ],
+                ))]),
+            )),
+            None,
+        ),
+    ];
+
+    let parse_param = Pat::Assign(AssignPat {
         span: DUMMY_SP,
-        body,
-        shebang: None,
+        left: Box::new(pat_ident(
+            "parse",
+            Some(ts_fn_type("text", ts_kw!(string), ts_kw!(any))),
+            false,
+        )),
+        right: Box::new(member(ident_expr("JSON"), "parse")),
+    });
+    let helper = ArrowExpr {
+        span: DUMMY_SP,
+        ctxt: swc_common::SyntaxContext::empty(),
+        params: vec![
+            pat_ident("res", Some(ts_type_ref("Response")), false),
+            parse_param,
+        ],
+        body: Box::new(BlockStmtOrExpr::BlockStmt(block(stmts))),
+        is_async: true,
+        is_generator: false,
+        type_params: None,
+        return_type: Some(ts_type_ann(promise_type(ts_kw!(any)))),
+    };
+    ModuleItem::Stmt(const_decl(PARSE_JSON_BODY_FN, Expr::Arrow(helper)))
+}
+
+/// Name of the generic type of one parsed server-sent event.
+const SERVER_SENT_EVENT_TYPE: &str = "ServerSentEvent";
+
+/// Name of the helper parsing one block of `field: value` lines into an event.
+const PARSE_SERVER_SENT_EVENT_FN: &str = "parseServerSentEvent";
+
+/// Name of the async generator yielding the events of a `text/event-stream` response.
+const PARSE_EVENT_STREAM_FN: &str = "parseEventStream";
+
+/// `(data: string) => T`, the event data parser taken by the stream helpers.
+fn event_data_parser_type() -> Box<TsType> {
+    ts_fn_type("data", ts_kw!(string), ts_type_ref("T"))
+}
+
+/// `ServerSentEvent<T>`
+fn server_sent_event_type(data: Box<TsType>) -> Box<TsType> {
+    ts_type_ref_with_params(SERVER_SENT_EVENT_TYPE, vec![data])
+}
+
+/// Generate the event type yielded by event stream responses:
+///
+/// ```ts
+/// export type ServerSentEvent<T> = { data: T; event?: string; id?: string; retry?: number };
+/// ```
+fn codegen_server_sent_event_type(docs: &DocComments) -> ModuleItem {
+    let ty = ts_object_type(vec![
+        ts_property_sig("data", ts_type_ref("T"), false),
+        ts_property_sig("event", ts_kw!(string), true),
+        ts_property_sig("id", ts_kw!(string), true),
+        ts_property_sig("retry", ts_kw!(number), true),
+    ]);
+    with_jsdoc(
+        export_type_alias_with_params(SERVER_SENT_EVENT_TYPE, vec![ts_type_param("T", None)], ty),
+        "One event of a `text/event-stream` response, with its data lines joined and parsed",
+        docs,
+    )
+}
+
+/// Generate the parser for one event block (the lines between two blank lines):
+///
+/// ```ts
+/// const parseServerSentEvent = <T>(block: string, parse: (data: string) => T): ServerSentEvent<T> | undefined => {
+///   const data: string[] = [];
+///   const fields: { event?: string; id?: string; retry?: number } = {};
+///   for (const line of block.split("\n")) {
+///     const colon = line.indexOf(":");
+///     const field = colon < 0 ? line : line.slice(0, colon);
+///     const value = colon < 0 ? "" : line.slice(colon + 1).replace(/^ /, "");
+///     if (field === "data") data.push(value);
+///     else if (field === "event") fields.event = value;
+///     else if (field === "id") fields.id = value;
+///     else if (field === "retry" && /^\d+$/.test(value)) fields.retry = Number(value);
+///   }
+///   return data.length === 0 ? undefined : { ...fields, data: parse(data.join("\n")) };
+/// };
+/// ```
+///
+/// Comment lines (starting with `:`) and unknown fields are ignored, and blocks
+/// without data lines yield no event, as in the browser's `EventSource`.
+fn codegen_parse_server_sent_event() -> ModuleItem {
+    let line = || ident_expr("line");
+    let colon = || ident_expr("colon");
+    let no_colon = || bin_expr(colon(), BinaryOp::Lt, num_lit(0.0));
+    let field_is = |name: &str| bin_expr(ident_expr("field"), BinaryOp::EqEqEq, str_lit(name));
+    let set_field =
+        |name: &str, value: Expr| expr_stmt(assign_expr(member(ident_expr("fields"), name), value));
+
+    let retry = if_stmt(
+        bin_expr(
+            field_is("retry"),
+            BinaryOp::LogicalAnd,
+            call(
+                member(regex_lit("^\\d+$", ""), "test"),
+                vec![ident_expr("value")],
+            ),
+        ),
+        set_field(
+            "retry",
+            call(ident_expr("Number"), vec![ident_expr("value")]),
+        ),
+        None,
+    );
+    let id = if_stmt(
+        field_is("id"),
+        set_field("id", ident_expr("value")),
+        Some(retry),
+    );
+    let event = if_stmt(
+        field_is("event"),
+        set_field("event", ident_expr("value")),
+        Some(id),
+    );
+    let dispatch = if_stmt(
+        field_is("data"),
+        expr_stmt(call(
+            member(ident_expr("data"), "push"),
+            vec![ident_expr("value")],
+        )),
+        Some(event),
+    );
+
+    let line_body = block(vec![
+        const_decl("colon", call(member(line(), "indexOf"), vec![str_lit(":")])),
+        const_decl(
+            "field",
+            cond_expr(
+                no_colon(),
+                line(),
+                call(member(line(), "slice"), vec![num_lit(0.0), colon()]),
+            ),
+        ),
+        const_decl(
+            "value",
+            cond_expr(
+                no_colon(),
+                str_lit(""),
+                call(
+                    member(
+                        call(
+                            member(line(), "slice"),
+                            vec![bin_expr(colon(), BinaryOp::Add, num_lit(1.0))],
+                        ),
+                        "replace",
+                    ),
+                    vec![regex_lit("^ ", ""), str_lit("")],
+                ),
+            ),
+        ),
+        dispatch,
+    ]);
+
+    let fields_type = ts_object_type(vec![
+        ts_property_sig("event", ts_kw!(string), true),
+        ts_property_sig("id", ts_kw!(string), true),
+        ts_property_sig("retry", ts_kw!(number), true),
+    ]);
+    let joined = call(member(ident_expr("data"), "join"), vec![str_lit("\n")]);
+    let stmts = vec![
+        Stmt::Decl(var_decl(
+            VarDeclKind::Const,
+            "data",
+            Some(ts_array(ts_kw!(string))),
+            array_lit(vec![]),
+        )),
+        Stmt::Decl(var_decl(
+            VarDeclKind::Const,
+            "fields",
+            Some(fields_type),
+            obj_lit(vec![]),
+        )),
+        for_of_const(
+            "line",
+            call(member(ident_expr("block"), "split"), vec![str_lit("\n")]),
+            line_body,
+        ),
+        return_stmt(Some(cond_expr(
+            bin_expr(
+                member(ident_expr("data"), "length"),
+                BinaryOp::EqEqEq,
+                num_lit(0.0),
+            ),
+            ident_expr("undefined"),
+            obj_lit(vec![
+                spread_prop(ident_expr("fields")),
+                kv_prop("data", call(ident_expr("parse"), vec![joined])),
+            ]),
+        ))),
+    ];
+
+    let helper = ArrowExpr {
+        span: DUMMY_SP,
+        ctxt: swc_common::SyntaxContext::empty(),
+        params: vec![
+            pat_ident("block", Some(ts_kw!(string)), false),
+            pat_ident("parse", Some(event_data_parser_type()), false),
+        ],
+        body: Box::new(BlockStmtOrExpr::BlockStmt(block(stmts))),
+        is_async: false,
+        is_generator: false,
+        type_params: Some(Box::new(TsTypeParamDecl {
+            span: DUMMY_SP,
+            params: vec![ts_type_param("T", None)],
+        })),
+        return_type: Some(ts_type_ann(ts_union(vec![
+            server_sent_event_type(ts_type_ref("T")),
+            ts_kw!(undefined),
+        ]))),
+    };
+    ModuleItem::Stmt(const_decl(PARSE_SERVER_SENT_EVENT_FN, Expr::Arrow(helper)))
+}
+
+/// Generate the async generator reading a `text/event-stream` response:
+///
+/// ```ts
+/// async function* parseEventStream<T>(res: Response, parse: (data: string) => T): AsyncGenerator<ServerSentEvent<T>, void, undefined> {
+///   if (!res.body) return;
+///   const reader = res.body.getReader();
+///   const decoder = new TextDecoder();
+///   let buffer = "";
+///   try {
+///     while (true) {
+///       const chunk = await reader.read();
+///       if (chunk.done) return;
+///       buffer = (buffer + decoder.decode(chunk.value, { stream: true })).replace(/\r\n?/g, "\n");
+///       const blocks = buffer.split("\n\n");
+///       buffer = blocks.pop() ?? "";
+///       for (const block of blocks) {
+///         const event = parseServerSentEvent(block, parse);
+///         if (event) yield event;
+///       }
+///     }
+///   } finally {
+///     reader.cancel().catch(() => undefined);
+///   }
+/// }
+/// ```
+///
+/// Leaving a `for await` loop early runs the `finally` block, which cancels the
+/// stream so the server stops sending. An unterminated event at the end of the
+/// stream is dropped, as in the browser's `EventSource`.
+fn codegen_parse_event_stream() -> ModuleItem {
+    let reader = || ident_expr("reader");
+    let decoded = call(
+        member(ident_expr("decoder"), "decode"),
+        vec![
+            member(ident_expr("chunk"), "value"),
+            obj_lit(vec![kv_prop("stream", bool_lit(true))]),
+        ],
+    );
+    let buffer = call(
+        member(
+            paren(bin_expr(ident_expr("buffer"), BinaryOp::Add, decoded)),
+            "replace",
+        ),
+        vec![regex_lit("\\r\\n?", "g"), str_lit("\n")],
+    );
+    let yield_event = Expr::Yield(YieldExpr {
+        span: DUMMY_SP,
+        arg: Some(Box::new(ident_expr("event"))),
+        delegate: false,
+    });
+    let read_loop = Stmt::While(WhileStmt {
+        span: DUMMY_SP,
+        test: Box::new(bool_lit(true)),
+        body: Box::new(block_stmt(vec![
+            const_decl("chunk", await_expr(call(member(reader(), "read"), vec![]))),
+            if_stmt(member(ident_expr("chunk"), "done"), return_stmt(None), None),
+            expr_stmt(assign_ident("buffer", buffer)),
+            const_decl(
+                "blocks",
+                call(member(ident_expr("buffer"), "split"), vec![str_lit("\n\n")]),
+            ),
+            expr_stmt(assign_ident(
+                "buffer",
+                bin_expr(
+                    call(member(ident_expr("blocks"), "pop"), vec![]),
+                    BinaryOp::NullishCoalescing,
+                    str_lit(""),
+                ),
+            )),
+            for_of_const(
+                "block",
+                ident_expr("blocks"),
+                block(vec![
+                    const_decl(
+                        "event",
+                        call(
+                            ident_expr(PARSE_SERVER_SENT_EVENT_FN),
+                            vec![ident_expr("block"), ident_expr("parse")],
+                        ),
+                    ),
+                    if_stmt(ident_expr("event"), expr_stmt(yield_event), None),
+                ]),
+            ),
+        ])),
+    });
+    let cancel = call(
+        member(call(member(reader(), "cancel"), vec![]), "catch"),
+        vec![arrow_fn_expr(vec![], ident_expr("undefined"))],
+    );
+
+    let body = member(ident_expr("res"), "body");
+    let stmts = vec![
+        if_stmt(unary_not(body.clone()), return_stmt(None), None),
+        const_decl("reader", call(member(body, "getReader"), vec![])),
+        const_decl("decoder", new_expr(ident_expr("TextDecoder"), vec![])),
+        Stmt::Decl(var_decl(VarDeclKind::Let, "buffer", None, str_lit(""))),
+        try_catch_finally_stmt(
+            block(vec![read_loop]),
+            None,
+            Some(block(vec![expr_stmt(cancel)])),
+        ),
+    ];
+
+    let return_type = ts_type_ref_with_params(
+        "AsyncGenerator",
+        vec![
+            server_sent_event_type(ts_type_ref("T")),
+            ts_kw!(void),
+            ts_kw!(undefined),
+        ],
+    );
+    ModuleItem::Stmt(Stmt::Decl(Decl::Fn(FnDecl {
+        ident: ident(PARSE_EVENT_STREAM_FN),
+        declare: false,
+        function: Box::new(Function {
+            params: vec![
+                param("res", Some(ts_type_ref("Response")), false),
+                param("parse", Some(event_data_parser_type()), false),
+            ],
+            decorators: vec![],
+            span: DUMMY_SP,
+            ctxt: swc_common::SyntaxContext::empty(),
+            body: Some(block(stmts)),
+            is_generator: true,
+            is_async: true,
+            type_params: Some(Box::new(TsTypeParamDecl {
+                span: DUMMY_SP,
+                params: vec![ts_type_param("T", None)],
+            })),
+            return_type: Some(ts_type_ann(return_type)),
+        }),
+    })))
+}
+
+/// Expression streaming the events of `res`, with each event's data parsed as `ty`:
+/// `parseEventStream(res, (data: string): T => JSON.parse(data))`.
+///
+/// String data is passed through as-is, and int64 values are revived as `BigInt`
+/// like in JSON responses.
+fn event_stream_expr(
+    ty: &TypeRef,
+    bigint_paths: &[Vec<Option<String>>],
+    docs: &DocComments,
+) -> Expr {
+    let data = || ident_expr("data");
+    let parsed = if matches!(ty.to_ts_type(), IrTsType::Primitive(TsPrimitive::String)) {
+        data()
+    } else if bigint_paths.is_empty() {
+        call(member(ident_expr("JSON"), "parse"), vec![data()])
+    } else {
+        call(
+            ident_expr(PARSE_BIGINT_JSON_FN),
+            vec![data(), bigint_paths_lit(bigint_paths)],
+        )
+    };
+    let parse = arrow_fn_expr_typed(
+        vec![pat_ident("data", Some(ts_kw!(string)), false)],
+        Some(ir_typeref_to_swc(ty, docs)),
+        parsed,
+    );
+    call(
+        ident_expr(PARSE_EVENT_STREAM_FN),
+        vec![ident_expr("res"), parse],
+    )
+}
+
+/// Name of the helper parsing JSON with the values at selected paths revived as `BigInt`.
+const PARSE_BIGINT_JSON_FN: &str = "parseJsonWithBigInt";
+
+/// Name of the helper serializing `bigint` values as JSON strings.
+pub(super) const STRINGIFY_BIGINT_JSON_FN: &str = "stringifyJsonWithBigInt";
+
+/// Schema paths as a `(string | null)[][]` literal, e.g. `[["id"], ["items", null, "id"]]`.
+fn bigint_paths_lit(paths: &[Vec<Option<String>>]) -> Expr {
+    array_lit(
+        paths
+            .iter()
+            .map(|path| {
+                array_lit(
+                    path.iter()
+                        .map(|key| key.as_deref().map_or_else(null_lit, str_lit))
+                        .collect(),
+                )
+            })
+            .collect(),
+    )
+}
+
+/// Generate the response parser used when int64 fields are typed as `bigint`:
+///
+/// ```ts
+/// const parseJsonWithBigInt = (text: string, paths: (string | null)[][]): any => {
+///   const sources: WeakMap<object, Record<string, string>> = new WeakMap();
+///   const root = JSON.parse(`{"":${text}}`, function (this: object, key: string, value: unknown, context?: { source?: string }) {
+///     if (typeof value === "number" && context?.source !== undefined) {
+///       const known: Record<string, string> = sources.get(this) ?? {};
+///       known[key] = context.source;
+///       sources.set(this, known);
+///     }
+///     return value;
+///   });
+///   const revive = (holder: any, key: string, path: (string | null)[]): void => {
+///     const value = holder[key];
+///     if (path.length === 0) {
+///       if (typeof value === "number") {
+///         holder[key] = BigInt(sources.get(holder)?.[key] ?? value);
+///       }
+///       return;
+///     }
+///     if (value === null || typeof value !== "object") {
+///       return;
+///     }
+///     const head = path[0];
+///     for (const child of head == null ? Object.keys(value) : [head]) {
+///       revive(value, child, path.slice(1));
+///     }
+///   };
+///   for (const path of paths) {
+///     revive(root, "", path);
+///   }
+///   return root[""];
+/// };
+/// ```
+///
+/// Only values at the given schema paths are revived, so a same-named number
+/// elsewhere in the document stays a `number`. `null` in a path matches any
+/// array index or map key. The reviver records each number's source text
+/// (JSON.parse source text access) by the object holding it, which keeps digits
+/// beyond 2^53; runtimes without it fall back to the already-rounded number.
+/// The document is parsed inside a wrapper object so the root value has a holder too.
+fn codegen_parse_bigint_json() -> ModuleItem {
+    let string_or_null = || ts_paren(ts_union(vec![ts_kw!(string), ts_kw!(null)]));
+    let this = || Expr::This(ThisExpr { span: DUMMY_SP });
+    let source = || member(ident_expr("context"), "source");
+
+    // Record the source text of every number by its holder and key
+    let record_source = if_stmt(
+        bin_expr(
+            bin_expr(
+                typeof_expr(ident_expr("value")),
+                BinaryOp::EqEqEq,
+                str_lit("number"),
+            ),
+            BinaryOp::LogicalAnd,
+            bin_expr(
+                opt_chain_member(ident_expr("context"), "source"),
+                BinaryOp::NotEqEq,
+                ident_expr("undefined"),
+            ),
+        ),
+        block_stmt(vec![
+            Stmt::Decl(var_decl(
+                VarDeclKind::Const,
+                "known",
+                Some(ts_type_ref_with_params(
+                    "Record",
+                    vec![ts_kw!(string), ts_kw!(string)],
+                )),
+                bin_expr(
+                    call(member(ident_expr("sources"), "get"), vec![this()]),
+                    BinaryOp::NullishCoalescing,
+                    obj_lit(vec![]),
+                ),
+            )),
+            expr_stmt(assign_expr(
+                computed_member(ident_expr("known"), ident_expr("key")),
+                source(),
+            )),
+            expr_stmt(call(
+                member(ident_expr("sources"), "set"),
+                vec![this(), ident_expr("known")],
+            )),
+        ]),
+        None,
+    );
+    let reviver = fn_expr(
+        vec![
+            pat_ident("this", Some(ts_kw!(object)), false),
+            pat_ident("key", Some(ts_kw!(string)), false),
+            pat_ident("value", Some(ts_kw!(unknown)), false),
+            pat_ident(
+                "context",
+                Some(ts_object_type(vec![ts_property_sig(
+                    "source",
+                    ts_kw!(string),
+                    true,
+                )])),
+                true,
+            ),
+        ],
+        None,
+        block(vec![record_source, return_stmt(Some(ident_expr("value")))]),
+    );
+
+    // Walk one schema path down from `holder[key]`, reviving the numbers at its end
+    let value = || ident_expr("value");
+    let holder_key = || computed_member(ident_expr("holder"), ident_expr("key"));
+    let revive_leaf = if_stmt(
+        bin_expr(
+            member(ident_expr("path"), "length"),
+            BinaryOp::EqEqEq,
+            num_lit(0.0),
+        ),
+        block_stmt(vec![
+            if_stmt(
+                bin_expr(typeof_expr(value()), BinaryOp::EqEqEq, str_lit("number")),
+                block_stmt(vec![expr_stmt(assign_expr(
+                    holder_key(),
+                    call(
+                        ident_expr("BigInt"),
+                        vec![bin_expr(
+                            opt_chain_computed(
+                                call(
+                                    member(ident_expr("sources"), "get"),
+                                    vec![ident_expr("holder")],
+                                ),
+                                ident_expr("key"),
+                            ),
+                            BinaryOp::NullishCoalescing,
+                            value(),
+                        )],
+                    ),
+                ))]),
+                None,
+            ),
+            return_stmt(None),
+        ]),
+        None,
+    );
+    let skip_scalar = if_stmt(
+        bin_expr(
+            bin_expr(value(), BinaryOp::EqEqEq, null_lit()),
+            BinaryOp::LogicalOr,
+            bin_expr(typeof_expr(value()), BinaryOp::NotEqEq, str_lit("object")),
+        ),
+        block_stmt(vec![return_stmt(None)]),
+        None,
+    );
+    let children = cond_expr(
+        bin_expr(ident_expr("head"), BinaryOp::EqEq, null_lit()),
+        call(member(ident_expr("Object"), "keys"), vec![value()]),
+        array_lit(vec![ident_expr("head")]),
+    );
+    let revive = arrow_fn_block(
+        vec![
+            pat_ident("holder", Some(ts_kw!(any)), false),
+            pat_ident("key", Some(ts_kw!(string)), false),
+            pat_ident("path", Some(ts_array(string_or_null())), false),
+        ],
+        Some(ts_kw!(void)),
+        block(vec![
+            const_decl("value", holder_key()),
+            revive_leaf,
+            skip_scalar,
+            const_decl("head", computed_member(ident_expr("path"), num_lit(0.0))),
+            for_of_const(
+                "child",
+                children,
+                block(vec![expr_stmt(call(
+                    ident_expr("revive"),
+                    vec![
+                        value(),
+                        ident_expr("child"),
+                        call(member(ident_expr("path"), "slice"), vec![num_lit(1.0)]),
+                    ],
+                ))]),
+            ),
+        ]),
+    );
+
+    let parser = arrow_fn_block(
+        vec![
+            pat_ident("text", Some(ts_kw!(string)), false),
+            pat_ident("paths", Some(ts_array(ts_array(string_or_null()))), false),
+        ],
+        Some(ts_kw!(any)),
+        block(vec![
+            Stmt::Decl(var_decl(
+                VarDeclKind::Const,
+                "sources",
+                Some(ts_type_ref_with_params(
+                    "WeakMap",
+                    vec![
+                        ts_kw!(object),
+                        ts_type_ref_with_params("Record", vec![ts_kw!(string), ts_kw!(string)]),
+                    ],
+                )),
+                new_expr(ident_expr("WeakMap"), vec![]),
+            )),
+            const_decl(
+                "root",
+                call(
+                    member(ident_expr("JSON"), "parse"),
+                    vec![tpl(vec!["{\"\":", "}"], vec![ident_expr("text")]), reviver],
+                ),
+            ),
+            const_decl("revive", revive),
+            for_of_const(
+                "path",
+                ident_expr("paths"),
+                block(vec![expr_stmt(call(
+                    ident_expr("revive"),
+                    vec![ident_expr("root"), str_lit(""), ident_expr("path")],
+                ))]),
+            ),
+            return_stmt(Some(computed_member(ident_expr("root"), str_lit("")))),
+        ]),
+    );
+    ModuleItem::Stmt(const_decl(PARSE_BIGINT_JSON_FN, parser))
+}
+
+/// Generate the body serializer used when a JSON request body may hold `bigint` values:
+///
+/// ```ts
+/// const stringifyJsonWithBigInt = (data: unknown): string =>
+///   JSON.stringify(data, (_key: string, value: unknown) =>
+///     typeof value === "bigint" ? value.toString() : value);
+/// ```
+pub(super) fn codegen_stringify_bigint_json() -> ModuleItem {
+    let replacer = arrow_fn_expr(
+        vec![
+            pat_ident("_key", Some(ts_kw!(string)), false),
+            pat_ident("value", Some(ts_kw!(unknown)), false),
+        ],
+        cond_expr(
+            bin_expr(
+                typeof_expr(ident_expr("value")),
+                BinaryOp::EqEqEq,
+                str_lit("bigint"),
+            ),
+            call(member(ident_expr("value"), "toString"), vec![]),
+            ident_expr("value"),
+        ),
+    );
+    let stringify = arrow_fn_expr_typed(
+        vec![pat_ident("data", Some(ts_kw!(unknown)), false)],
+        Some(ts_kw!(string)),
+        call(
+            member(ident_expr("JSON"), "stringify"),
+            vec![ident_expr("data"), replacer],
+        ),
+    );
+    ModuleItem::Stmt(const_decl(STRINGIFY_BIGINT_JSON_FN, stringify))
+}
+
+/// Generate a type guard narrowing a discriminated union by its discriminator.
+///
+/// The predicate uses `Extract<Union, { prop: "value" }>` rather than the bare
+/// member type, since members are intersected with the discriminator property.
+fn codegen_type_guard(guard: &TypeGuardIR, docs: &DocComments) -> ModuleItem {
+    let param_name = "value";
+    let narrowed =
+        discriminated_member_type(&guard.union_type, &guard.property, &guard.value, docs);
+
+    // return value.petType === "dog";
+    let check = bin_expr(
+        build_param_access_expr(param_name, &guard.property, true),
+        BinaryOp::EqEqEq,
+        str_lit(&guard.value),
+    );
+
+    export_function(
+        &guard.fn_name,
+        None,
+        vec![param(
+            param_name,
+            Some(ts_type_ref(&guard.union_type)),
+            false,
+        )],
+        Some(ts_type_predicate(param_name, narrowed)),
+        block(vec![return_stmt(Some(check))]),
+        false,
+    )
+}
+
+/// `Extract<Union, { prop: "value" }>`, the member of a discriminated union selected by `value`.
+fn discriminated_member_type(
+    union_type: &str,
+    property: &str,
+    value: &str,
+    docs: &DocComments,
+) -> Box<TsType> {
+    ts_type_ref_with_params(
+        "Extract",
+        vec![
+            ts_type_ref(union_type),
+            ir_type_to_swc(
+                &IrTsType::Object(vec![TsProp {
+                    name: property.to_string(),
+                    ty: IrTsType::Literal(TsLiteral::String(value.to_string())),
+                    optional: false,
+                    constraints: TsConstraints::default(),
+                    read_only: false,
+                    readonly: false,
+                }]),
+                docs,
+            ),
+        ],
+    )
+}
+
+/// Generate `matchXxx<R>(value, handlers): R`, calling the handler for the value's branch.
+///
+/// `handlers` requires one entry per branch, so omitting one is a type error.
+/// After the last branch `value` is `never`; data outside the spec throws.
+fn codegen_union_matcher(matcher: &UnionMatcherIR, docs: &DocComments) -> ModuleItem {
+    let union = &matcher.union_type;
+    let value = || ident_expr("value");
+
+    // (handler key, narrowed member type, runtime check)
+    let (branches, describe): (Vec<(String, Box<TsType>, Expr)>, Expr) = match &matcher.dispatch {
+        MatchDispatchIR::Discriminator { property, values } => (
+            values
+                .iter()
+                .map(|v| {
+                    (
+                        v.clone(),
+                        discriminated_member_type(union, property, v, docs),
+                        bin_expr(
+                            build_param_access_expr("value", property, true),
+                            BinaryOp::EqEqEq,
+                            str_lit(v),
+                        ),
+                    )
+                })
+                .collect(),
+            member(ident_expr("JSON"), "stringify"),
+        ),
+        MatchDispatchIR::Kind(kinds) => {
+            let typeof_check =
+                |name: &str| bin_expr(typeof_expr(value()), BinaryOp::EqEqEq, str_lit(name));
+            let extract = |ty| ts_type_ref_with_params("Extract", vec![ts_type_ref(union), ty]);
+            let branches = kinds
+                .iter()
+                .map(|kind| match kind {
+                    MatchKind::Null => (
+                        "null",
+                        extract(ts_kw!(null)),
+                        bin_expr(value(), BinaryOp::EqEqEq, null_lit()),
+                    ),
+                    MatchKind::Array => (
+                        "array",
+                        extract(ts_array(ts_kw!(unknown))),
+                        call(member(ident_expr("Array"), "isArray"), vec![value()]),
+                    ),
+                    MatchKind::String => {
+                        ("string", extract(ts_kw!(string)), typeof_check("string"))
+                    }
+                    MatchKind::Number => {
+                        ("number", extract(ts_kw!(number)), typeof_check("number"))
+                    }
+                    MatchKind::BigInt => {
+                        ("bigint", extract(ts_kw!(bigint)), typeof_check("bigint"))
+                    }
+                    MatchKind::Boolean => {
+                        ("boolean", extract(ts_kw!(boolean)), typeof_check("boolean"))
+                    }
+                    // Objects are what remains once null and arrays are handled
+                    MatchKind::Object => (
+                        "object",
+                        ts_type_ref_with_params(
+                            "Exclude",
+                            vec![
+                                ts_type_ref(union),
+                                ts_union(vec![
+                                    ts_kw!(null),
+                                    ts_array(ts_kw!(unknown)),
+                                    ts_kw!(string),
+                                    ts_kw!(number),
+                                    ts_kw!(bigint),
+                                    ts_kw!(boolean),
+                                ]),
+                            ],
+                        ),
+                        typeof_check("object"),
+                    ),
+                })
+                .map(|(key, ty, check)| (key.to_string(), ty, check))
+                .collect();
+            (branches, ident_expr("String"))
+        }
+    };
+
+    let handlers_type = ts_object_type(
+        branches
+            .iter()
+            .map(|(key, ty, _)| {
+                ts_property_sig(
+                    key,
+                    ts_fn_type("value", ty.clone(), ts_type_ref("R")),
+                    false,
+                )
+            })
+            .collect(),
+    );
+
+    // if (value.petType === "dog") return handlers.dog(value);
+    let mut stmts: Vec<Stmt> = branches
+        .into_iter()
+        .map(|(key, _, check)| {
+            if_stmt(
+                check,
+                return_stmt(Some(call(
+                    build_param_access_expr("handlers", &key, true),
+                    vec![value()],
+                ))),
+                None,
+            )
+        })
+        .collect();
+    // const unhandled: never = value;
+    stmts.push(Stmt::Decl(var_decl(
+        VarDeclKind::Const,
+        "unhandled",
+        Some(ts_kw!(never)),
+        value(),
+    )));
+    // throw new Error("Unhandled Pet: " + JSON.stringify(unhandled));
+    stmts.push(throw_stmt(new_expr(
+        ident_expr("Error"),
+        vec![bin_expr(
+            str_lit(&format!("Unhandled {union}: ")),
+            BinaryOp::Add,
+            call(describe, vec![ident_expr("unhandled")]),
+        )],
+    )));
+
+    export_function(
+        &matcher.fn_name,
+        Some(vec![ts_type_param("R", None)]),
+        vec![
+            param("value", Some(ts_type_ref(union)), false),
+            param("handlers", Some(handlers_type), false),
+        ],
+        Some(ts_type_ref("R")),
+        block(stmts),
+        false,
+    )
+}
+
+/// Name of the union of all webhook events.
+const WEBHOOK_EVENT_TYPE: &str = "WebhookEvent";
+
+/// Generate the union of webhook events, discriminated by the webhook name:
+///
+/// ```ts
+/// export type WebhookEvent = { webhook: "newPet"; payload: NewPetWebhookPayload; } | { webhook: "petSold"; payload: PetSoldWebhookPayload; };
+/// ```
+fn codegen_webhook_event_type(webhooks: &[WebhookIR]) -> ModuleItem {
+    let mut events: Vec<Box<TsType>> = webhooks
+        .iter()
+        .map(|webhook| {
+            ts_object_type(vec![
+                ts_property_sig("webhook", ts_lit_str(&webhook.name), false),
+                ts_property_sig("payload", ts_type_ref(&webhook.payload.name), false),
+            ])
+        })
+        .collect();
+    let ty = if events.len() == 1 {
+        events.remove(0)
+    } else {
+        ts_union(events)
+    };
+    export_type_alias(WEBHOOK_EVENT_TYPE, ty)
+}
+
+/// Generate `handleWebhook<R>(event, handlers): R`, calling the handler named after
+/// the event's webhook with its payload.
+///
+/// Like the union matchers, `handlers` requires one entry per webhook and an
+/// event outside the spec throws.
+fn codegen_handle_webhook(webhooks: &[WebhookIR]) -> ModuleItem {
+    let handlers_type = ts_object_type(
+        webhooks
+            .iter()
+            .map(|webhook| {
+                ts_property_sig(
+                    &webhook.name,
+                    ts_fn_type(
+                        "payload",
+                        ts_type_ref(&webhook.payload.name),
+                        ts_type_ref("R"),
+                    ),
+                    false,
+                )
+            })
+            .collect(),
+    );
+
+    // if (event.webhook === "newPet") return handlers.newPet(event.payload);
+    let mut stmts: Vec<Stmt> = webhooks
+        .iter()
+        .map(|webhook| {
+            if_stmt(
+                bin_expr(
+                    member(ident_expr("event"), "webhook"),
+                    BinaryOp::EqEqEq,
+                    str_lit(&webhook.name),
+                ),
+                return_stmt(Some(call(
+                    build_param_access_expr("handlers", &webhook.name, true),
+                    vec![member(ident_expr("event"), "payload")],
+                ))),
+                None,
+            )
+        })
+        .collect();
+    // const unhandled: never = event;
+    stmts.push(Stmt::Decl(var_decl(
+        VarDeclKind::Const,
+        "unhandled",
+        Some(ts_kw!(never)),
+        ident_expr("event"),
+    )));
+    // throw new Error("Unhandled webhook: " + JSON.stringify(unhandled));
+    stmts.push(throw_stmt(new_expr(
+        ident_expr("Error"),
+        vec![bin_expr(
+            str_lit("Unhandled webhook: "),
+            BinaryOp::Add,
+            call(
+                member(ident_expr("JSON"), "stringify"),
+                vec![ident_expr("unhandled")],
+            ),
+        )],
+    )));
+
+    export_function(
+        "handleWebhook",
+        Some(vec![ts_type_param("R", None)]),
+        vec![
+            param("event", Some(ts_type_ref(WEBHOOK_EVENT_TYPE)), false),
+            param("handlers", Some(handlers_type), false),
+        ],
+        Some(ts_type_ref("R")),
+        block(stmts),
+        false,
+    )
+}
+
+/// Name of the helper checking a string and casting it to its brand.
+fn brand_helper_name(format: StringFormat) -> String {
+    format!("as{}", format.type_name())
+}
+
+/// Pattern (and flags) a string must match to be cast to its brand.
+const fn brand_pattern(format: StringFormat) -> (&'static str, &'static str) {
+    match format {
+        StringFormat::Uuid => (
+            "^[0-9a-f]{8}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{12}$",
+            "i",
+        ),
+        StringFormat::Email => (r"^[^\s@]+@[^\s@]+$", ""),
+        StringFormat::Uri => (r"^[a-zA-Z][a-zA-Z\d+.-]*:\S*$", ""),
     }
 }
 
+/// Generate a branded string type and its checked cast helper.
+///
+/// ```typescript
+/// export type Uuid = string & { readonly __brand: "uuid" };
+/// export function asUuid(value: string): Uuid {
+///   if (!/^[0-9a-f]{8}-...$/i.test(value)) throw new Error("Invalid uuid: " + JSON.stringify(value));
+///   return value as Uuid;
+/// }
+/// ```
+fn codegen_brand(format: StringFormat) -> Vec<ModuleItem> {
+    let name = format.type_name();
+    let mut tag = ts_property_sig("__brand", ts_lit_str(format.as_str()), false);
+    if let TsTypeElement::TsPropertySignature(sig) = &mut tag {
+        sig.readonly = true;
+    }
+    let alias = export_type_alias(
+        name,
+        ts_intersection(vec![ts_kw!(string), ts_object_type(vec![tag])]),
+    );
+
+    let (pattern, flags) = brand_pattern(format);
+    let check = call(
+        member(regex_lit(pattern, flags), "test"),
+        vec![ident_expr("value")],
+    );
+    let invalid = throw_stmt(new_expr(
+        ident_expr("Error"),
+        vec![bin_expr(
+            str_lit(&format!("Invalid {}: ", format.as_str())),
+            BinaryOp::Add,
+            call(
+                member(ident_expr("JSON"), "stringify"),
+                vec![ident_expr("value")],
+            ),
+        )],
+    ));
+    let helper = export_function(
+        &brand_helper_name(format),
+        None,
+        vec![param("value", Some(ts_kw!(string)), false)],
+        Some(ts_type_ref(name)),
+        block(vec![
+            if_stmt(unary_not(check), invalid, None),
+            return_stmt(Some(ts_as_expr(ident_expr("value"), ts_type_ref(name)))),
+        ]),
+        false,
+    );
+    vec![alias, helper]
+}
+
 /// Generate import statements.
 fn codegen_imports(has_queries: bool, has_mutations: bool) -> Vec<ModuleItem> {
     let mut imports = Vec::new();
@@ -59,6 +1353,8 @@ fn codegen_imports(has_queries: bool, has_mutations: bool) -> Vec<ModuleItem> {
         runtime_items.push(("useSuspenseQuery", None));
         type_items.push(("UseQueryOptions", None));
         type_items.push(("UseSuspenseQueryOptions", None));
+        type_items.push(("QueryClient", None));
+        type_items.push(("FetchQueryOptions", None));
     }
 
     if has_mutations {
@@ -66,29 +1362,441 @@ fn codegen_imports(has_queries: bool, has_mutations: bool) -> Vec<ModuleItem> {
         type_items.push(("UseMutationOptions", None));
     }
 
-    if !runtime_items.is_empty() {
-        imports.push(import_named(runtime_items, "@tanstack/react-query", false));
-    }
+    if !runtime_items.is_empty() {
+        imports.push(import_named(runtime_items, "@tanstack/react-query", false));
+    }
+
+    if !type_items.is_empty() {
+        imports.push(import_named(type_items, "@tanstack/react-query", true));
+    }
+
+    imports
+}
+
+/// Generate the `HttpMethod` type, `ApiRoute` interface and `apiRoutes` table.
+///
+/// The table lists every operation as `{ operationId, method, pathTemplate }` so
+/// mock servers and coverage checks can enumerate endpoints programmatically.
+fn codegen_route_table(operations: &[OperationIR]) -> Vec<ModuleItem> {
+    let method_type = export_type_alias(
+        "HttpMethod",
+        ts_union(
+            HttpMethod::ALL
+                .iter()
+                .map(|m| ts_lit_str(m.as_str()))
+                .collect(),
+        ),
+    );
+
+    let route_interface = export_interface(
+        "ApiRoute",
+        vec![
+            ts_property_sig("operationId", ts_kw!(string), false),
+            ts_property_sig("method", ts_type_ref("HttpMethod"), false),
+            ts_property_sig("pathTemplate", ts_kw!(string), false),
+        ],
+    );
+
+    let routes = operations
+        .iter()
+        .map(|op| {
+            obj_lit(vec![
+                kv_prop("operationId", str_lit(&op.name)),
+                kv_prop("method", str_lit(op.fetch.method.as_str())),
+                kv_prop("pathTemplate", str_lit(&op.path)),
+            ])
+        })
+        .collect();
+
+    let route_table = export_const(
+        "apiRoutes",
+        Some(ts_array(ts_type_ref("ApiRoute"))),
+        array_lit(routes),
+    );
+
+    vec![method_type, route_interface, route_table]
+}
+
+/// Generate the `links` object with one URL builder per GET operation.
+///
+/// Each builder returns the relative path (without `API_BASE_URL`) and uses the
+/// same query serialization as the fetch function, so it can feed `<Link>`
+/// components or `href` attributes: `links.listItems({ limit: 10 })`
+/// evaluates to `"/items?limit=10"`.
+fn codegen_links(operations: &[OperationIR], docs: &DocComments) -> ModuleItem {
+    let props = operations
+        .iter()
+        .filter(|op| op.fetch.method == HttpMethod::Get)
+        .map(|op| {
+            let params = op
+                .fetch
+                .args
+                .iter()
+                .filter_map(|a| match a {
+                    FetchArgIR::Params { ty, optional } => Some(pat_ident(
+                        "params",
+                        Some(ir_typeref_to_swc(ty, docs)),
+                        *optional,
+                    )),
+                    FetchArgIR::Body { .. } | FetchArgIR::Options => None,
+                })
+                .collect();
+
+            let (mut stmts, url_expr) = codegen_url(&op.fetch, false);
+            stmts.push(return_stmt(Some(url_expr)));
+
+            kv_prop(
+                &op.name,
+                arrow_fn_block(params, Some(ts_kw!(string)), block(stmts)),
+            )
+        })
+        .collect();
+
+    export_const("links", None, obj_lit(props))
+}
+
+/// Generate one object per OpenAPI tag bundling its fetch functions and hooks:
+///
+/// ```ts
+/// export const users = { listUsers, useListUsers, useListUsersSuspense, createUser, useCreateUser };
+/// ```
+///
+/// Groups follow the operations' first tag, sorted by tag name; untagged
+/// operations land in `_default`. A group whose name clashes with another
+/// exported value gets an `Api` suffix.
+fn codegen_tag_groups(api: &ApiIR) -> Vec<ModuleItem> {
+    let mut groups: Vec<(&str, Vec<&OperationIR>)> = Vec::new();
+    for op in &api.operations {
+        let tag = op.tag.as_deref().unwrap_or("default");
+        match groups.iter_mut().find(|(name, _)| *name == tag) {
+            Some((_, ops)) => ops.push(op),
+            None => groups.push((tag, vec![op])),
+        }
+    }
+    groups.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    let mut taken: Vec<String> = [
+        BASE_URL_CONST,
+        "ApiError",
+        "apiRoutes",
+        "links",
+        "serverUrl",
+        "serverVariables",
+        "setServerVariables",
+        FETCHER_VAR,
+        "setFetcher",
+        FETCH_WITH_TIMEOUT_FN,
+        FETCH_WITH_RETRY_FN,
+        PARSE_RETRY_AFTER_FN,
+        PARSE_JSON_BODY_FN,
+        TO_FORM_DATA_FN,
+        "ApiTimeoutError",
+        "ApiNetworkError",
+        "ApiParseError",
+        API_ERROR_KIND_TYPE,
+        API_FAILURE_TYPE,
+        API_RESULT_TYPE,
+    ]
+    .iter()
+    .map(ToString::to_string)
+    .chain(api.types.iter().map(|td| td.name.clone()))
+    .chain(api.brands.iter().map(|&f| brand_helper_name(f)))
+    .chain(api.type_guards.iter().map(|g| g.fn_name.clone()))
+    .chain(api.operations.iter().flat_map(|op| {
+        std::iter::once(op.fetch.fn_name.clone())
+            .chain(std::iter::once(safe_fn_name(&op.fetch.fn_name)))
+            .chain(std::iter::once(format!("{}BodyDefaults", op.fetch.fn_name)))
+            .chain(std::iter::once(form_data_builder_name(&op.fetch.fn_name)))
+            .chain(op.hooks.iter().map(|h| h.name.clone()))
+            .chain(op.query_key.iter().flat_map(|qk| {
+                std::iter::once(qk.fn_name.clone()).chain(qk.legacy_fn_name.clone())
+            }))
+    }))
+    .collect();
+
+    groups
+        .into_iter()
+        .map(|(tag, ops)| {
+            let mut name = tag_group_name(tag);
+            while taken.contains(&name) {
+                name.push_str("Api");
+            }
+            taken.push(name.clone());
+
+            let props = ops
+                .iter()
+                .flat_map(|op| {
+                    std::iter::once(op.fetch.fn_name.as_str())
+                        .chain(op.hooks.iter().map(|h| h.name.as_str()))
+                })
+                .map(shorthand_prop)
+                .collect();
+            export_const(&name, None, obj_lit(props))
+        })
+        .collect()
+}
+
+/// Identifier for a tag group: `"User Accounts"` becomes `userAccounts`.
+fn tag_group_name(tag: &str) -> String {
+    let name = sanitize_ts_identifier(tag);
+    let mut chars = name.chars();
+    let name: String = match chars.next() {
+        Some(first) => first.to_lowercase().chain(chars).collect(),
+        None => name,
+    };
+    // Lowercasing may turn an escaped identifier back into a reserved word
+    sanitize_ts_identifier(&name)
+}
+
+/// Generate the fetch indirection and client-wide headers used by every fetch function:
+///
+/// ```ts
+/// let fetcher: typeof fetch = (input, init) => fetch(input, init);
+/// export const setFetcher = (fn: typeof fetch): void => { fetcher = fn; };
+/// let defaultHeaders: Record<string, string> = {};
+/// export const setDefaultHeaders = (headers: Record<string, string>): void => { defaultHeaders = headers; };
+/// ```
+///
+/// The default looks up the global `fetch` per call, so stubbing
+/// `globalThis.fetch` after import keeps working. Default headers are read per
+/// call too, and operation and per-call headers override them.
+fn codegen_fetcher() -> Vec<ModuleItem> {
+    let default_fetcher = arrow_fn_expr(
+        vec![
+            pat_ident("input", None, false),
+            pat_ident("init", None, false),
+        ],
+        call(
+            ident_expr("fetch"),
+            vec![ident_expr("input"), ident_expr("init")],
+        ),
+    );
+    let fetcher = ModuleItem::Stmt(Stmt::Decl(var_decl(
+        VarDeclKind::Let,
+        FETCHER_VAR,
+        Some(ts_typeof("fetch")),
+        default_fetcher,
+    )));
+
+    let setter = export_const_arrow(
+        "setFetcher",
+        vec![pat_ident("fn", Some(ts_typeof("fetch")), false)],
+        Some(ts_kw!(void)),
+        block(vec![expr_stmt(assign_ident(FETCHER_VAR, ident_expr("fn")))]),
+        false,
+    );
+
+    let headers_type = || ts_type_ref_with_params("Record", vec![ts_kw!(string), ts_kw!(string)]);
+    let default_headers = ModuleItem::Stmt(Stmt::Decl(var_decl(
+        VarDeclKind::Let,
+        DEFAULT_HEADERS_VAR,
+        Some(headers_type()),
+        obj_lit(vec![]),
+    )));
+    let headers_setter = export_const_arrow(
+        "setDefaultHeaders",
+        vec![pat_ident("headers", Some(headers_type()), false)],
+        Some(ts_kw!(void)),
+        block(vec![expr_stmt(assign_ident(
+            DEFAULT_HEADERS_VAR,
+            ident_expr("headers"),
+        ))]),
+        false,
+    );
+
+    vec![fetcher, setter, default_headers, headers_setter]
+}
+
+/// Generate `export const API_BASE_URL = ...` for a non-relative base URL.
+fn codegen_base_url(base_url: &BaseUrl, server: Option<&ServerIR>) -> Vec<ModuleItem> {
+    let init = match base_url {
+        BaseUrl::Relative => return Vec::new(),
+        BaseUrl::Server => return server.map(codegen_server_url).unwrap_or_default(),
+        BaseUrl::Fixed(url) => str_lit(url.trim_end_matches('/')),
+        BaseUrl::Env { var, fallback } => {
+            // import.meta.env.VITE_API_URL ?? "fallback"
+            let env = member(import_meta(), "env");
+            let value = if needs_bracket_notation(var) {
+                computed_member(env, str_lit(var))
+            } else {
+                member(env, var)
+            };
+            bin_expr(
+                value,
+                BinaryOp::NullishCoalescing,
+                str_lit(fallback.trim_end_matches('/')),
+            )
+        }
+    };
+    vec![export_const(BASE_URL_CONST, Some(ts_kw!(string)), init)]
+}
+
+/// Generate the base URL from a `servers` entry.
+///
+/// Without variables this is a plain constant. Otherwise the URL is rebuilt
+/// whenever the variables change:
+///
+/// ```ts
+/// export interface ServerVariables { region: "us" | "eu"; }
+/// export const serverUrl = (variables: ServerVariables): string =>
+///   `https://${encodeURIComponent(variables.region)}.api.example.com`;
+/// const serverVariables: ServerVariables = { region: "us" };
+/// export let API_BASE_URL: string = serverUrl(serverVariables);
+/// export const setServerVariables = (variables: Partial<ServerVariables>): void => {
+///   Object.assign(serverVariables, variables);
+///   API_BASE_URL = serverUrl(serverVariables);
+/// };
+/// ```
+fn codegen_server_url(server: &ServerIR) -> Vec<ModuleItem> {
+    if server.variables.is_empty() {
+        let url: String = server
+            .template
+            .iter()
+            .filter_map(|part| match part {
+                UrlPart::Static(s) => Some(s.as_str()),
+                UrlPart::Param(_) => None,
+            })
+            .collect();
+        return vec![export_const(
+            BASE_URL_CONST,
+            Some(ts_kw!(string)),
+            str_lit(&url),
+        )];
+    }
+
+    let vars_type = || ts_type_ref(SERVER_VARIABLES_TYPE);
+    let interface = export_interface(
+        SERVER_VARIABLES_TYPE,
+        server
+            .variables
+            .iter()
+            .map(|v| {
+                let ty = if v.enum_values.is_empty() {
+                    ts_kw!(string)
+                } else if v.enum_values.len() == 1 {
+                    ts_lit_str(&v.enum_values[0])
+                } else {
+                    ts_union(v.enum_values.iter().map(|e| ts_lit_str(e)).collect())
+                };
+                ts_property_sig(&v.name, ty, false)
+            })
+            .collect(),
+    );
+
+    // `https://${encodeURIComponent(variables.region)}.api.example.com`
+    let mut quasis = vec![String::new()];
+    let mut exprs = Vec::new();
+    for part in &server.template {
+        match part {
+            UrlPart::Static(s) => {
+                if let Some(last) = quasis.last_mut() {
+                    last.push_str(s);
+                }
+            }
+            UrlPart::Param(name) => {
+                exprs.push(call(
+                    ident_expr("encodeURIComponent"),
+                    vec![build_param_access_expr("variables", name, true)],
+                ));
+                quasis.push(String::new());
+            }
+        }
+    }
+    let url_builder = export_const_arrow(
+        "serverUrl",
+        vec![pat_ident("variables", Some(vars_type()), false)],
+        Some(ts_kw!(string)),
+        block(vec![return_stmt(Some(tpl(
+            quasis.iter().map(String::as_str).collect(),
+            exprs,
+        )))]),
+        false,
+    );
+
+    let defaults = ModuleItem::Stmt(Stmt::Decl(var_decl(
+        VarDeclKind::Const,
+        "serverVariables",
+        Some(vars_type()),
+        obj_lit(
+            server
+                .variables
+                .iter()
+                .map(|v| {
+                    if needs_bracket_notation(&v.name) {
+                        kv_prop_str(&v.name, str_lit(&v.default))
+                    } else {
+                        kv_prop(&v.name, str_lit(&v.default))
+                    }
+                })
+                .collect(),
+        ),
+    )));
+
+    let current_url = || call(ident_expr("serverUrl"), vec![ident_expr("serverVariables")]);
+    let base_url = export_let(BASE_URL_CONST, Some(ts_kw!(string)), current_url());
+
+    let setter = export_const_arrow(
+        "setServerVariables",
+        vec![pat_ident(
+            "variables",
+            Some(ts_type_ref_with_params("Partial", vec![vars_type()])),
+            false,
+        )],
+        Some(ts_kw!(void)),
+        block(vec![
+            expr_stmt(call(
+                member(ident_expr("Object"), "assign"),
+                vec![ident_expr("serverVariables"), ident_expr("variables")],
+            )),
+            expr_stmt(assign_ident(BASE_URL_CONST, current_url())),
+        ]),
+        false,
+    );
 
-    if !type_items.is_empty() {
-        imports.push(import_named(type_items, "@tanstack/react-query", true));
-    }
+    vec![interface, url_builder, defaults, base_url, setter]
+}
 
-    imports
+/// Name of the type listing the ways a request can fail.
+const API_ERROR_KIND_TYPE: &str = "ApiErrorKind";
+
+/// Name of the union of every error a fetch function throws, used by hooks.
+const API_FAILURE_TYPE: &str = "ApiFailure";
+
+/// `export type ApiErrorKind = "http" | "network" | "parse" | "timeout";`
+fn codegen_api_error_kind_type() -> ModuleItem {
+    export_type_alias(
+        API_ERROR_KIND_TYPE,
+        ts_union(
+            ["http", "network", "parse", "timeout"]
+                .into_iter()
+                .map(ts_lit_str)
+                .collect(),
+        ),
+    )
 }
 
 /// Generate the ApiError class as a proper SWC ClassDecl.
+///
+/// `TBody` is the parsed error body; fetch functions narrow it to the union of
+/// their declared 4xx/5xx schemas. `kind` tells HTTP error responses apart
+/// from the subclasses thrown for other failures.
 fn codegen_api_error_class() -> ModuleItem {
+    let kind_prop = class_prop("kind", ts_type_ref(API_ERROR_KIND_TYPE));
     let status_prop = class_prop("status", ts_kw!(number));
     let status_text_prop = class_prop("statusText", ts_kw!(string));
-    let body_prop = class_prop("body", ts_kw!(unknown));
+    let body_prop = class_prop("body", ts_type_ref("TBody"));
 
-    // constructor(status: number, statusText: string, body: unknown) { ... }
+    // constructor(status: number, statusText: string, body: TBody, kind: ApiErrorKind = "http") { ... }
     let ctor = constructor(
         vec![
             constructor_param("status", Some(ts_kw!(number))),
             constructor_param("statusText", Some(ts_kw!(string))),
-            constructor_param("body", Some(ts_kw!(unknown))),
+            constructor_param("body", Some(ts_type_ref("TBody"))),
+            constructor_param_with_default(
+                "kind",
+                ts_type_ref(API_ERROR_KIND_TYPE),
+                str_lit("http"),
+            ),
         ],
         block(vec![
             // super(`HTTP ${status}: ${statusText}`)
@@ -107,6 +1815,11 @@ fn codegen_api_error_class() -> ModuleItem {
                 member(Expr::This(ThisExpr { span: DUMMY_SP }), "name"),
                 str_lit("ApiError"),
             )),
+            // this.kind = kind
+            expr_stmt(assign_expr(
+                member(Expr::This(ThisExpr { span: DUMMY_SP }), "kind"),
+                ident_expr("kind"),
+            )),
             // this.status = status
             expr_stmt(assign_expr(
                 member(Expr::This(ThisExpr { span: DUMMY_SP }), "status"),
@@ -125,51 +1838,1011 @@ fn codegen_api_error_class() -> ModuleItem {
         ]),
     );
 
-    export_class(
-        "ApiError",
-        Some("Error"),
-        vec![status_prop, status_text_prop, body_prop, ctor],
+    export_class(
+        "ApiError",
+        vec![ts_type_param("TBody", Some(ts_kw!(unknown)))],
+        Some("Error"),
+        vec![kind_prop, status_prop, status_text_prop, body_prop, ctor],
+    )
+}
+
+/// Generate the error thrown when a request exceeds its `timeoutMs`:
+///
+/// ```ts
+/// export class ApiTimeoutError extends ApiError {
+///   declare kind: "timeout";
+///   timeoutMs: number;
+///   constructor(timeoutMs: number) {
+///     super(0, `Timed out after ${timeoutMs}ms`, undefined, "timeout");
+///     this.name = "ApiTimeoutError";
+///     this.timeoutMs = timeoutMs;
+///   }
+/// }
+/// ```
+fn codegen_api_timeout_error_class() -> ModuleItem {
+    let this = || Expr::This(ThisExpr { span: DUMMY_SP });
+    let ctor = constructor(
+        vec![constructor_param("timeoutMs", Some(ts_kw!(number)))],
+        block(vec![
+            expr_stmt(Expr::Call(CallExpr {
+                span: DUMMY_SP,
+                ctxt: swc_common::SyntaxContext::empty(),
+                callee: Callee::Super(Super { span: DUMMY_SP }),
+                args: vec![
+                    arg(num_lit(0.0)),
+                    arg(tpl(
+                        vec!["Timed out after ", "ms"],
+                        vec![ident_expr("timeoutMs")],
+                    )),
+                    arg(ident_expr("undefined")),
+                    arg(str_lit("timeout")),
+                ],
+                type_args: None,
+            })),
+            expr_stmt(assign_expr(
+                member(this(), "name"),
+                str_lit("ApiTimeoutError"),
+            )),
+            expr_stmt(assign_expr(
+                member(this(), "timeoutMs"),
+                ident_expr("timeoutMs"),
+            )),
+        ]),
+    );
+
+    export_class(
+        "ApiTimeoutError",
+        vec![],
+        Some("ApiError"),
+        vec![
+            declared_class_prop("kind", ts_lit_str("timeout")),
+            class_prop("timeoutMs", ts_kw!(number)),
+            ctor,
+        ],
+    )
+}
+
+/// Generate the error thrown when the request itself fails, e.g. on a DNS or
+/// connection error:
+///
+/// ```ts
+/// export class ApiNetworkError extends ApiError {
+///   declare kind: "network";
+///   cause: unknown;
+///   constructor(cause: unknown) {
+///     super(0, "Network error", undefined, "network");
+///     this.name = "ApiNetworkError";
+///     this.cause = cause;
+///   }
+/// }
+/// ```
+fn codegen_api_network_error_class() -> ModuleItem {
+    let this = || Expr::This(ThisExpr { span: DUMMY_SP });
+    let ctor = constructor(
+        vec![constructor_param("cause", Some(ts_kw!(unknown)))],
+        block(vec![
+            expr_stmt(Expr::Call(CallExpr {
+                span: DUMMY_SP,
+                ctxt: swc_common::SyntaxContext::empty(),
+                callee: Callee::Super(Super { span: DUMMY_SP }),
+                args: vec![
+                    arg(num_lit(0.0)),
+                    arg(str_lit("Network error")),
+                    arg(ident_expr("undefined")),
+                    arg(str_lit("network")),
+                ],
+                type_args: None,
+            })),
+            expr_stmt(assign_expr(
+                member(this(), "name"),
+                str_lit("ApiNetworkError"),
+            )),
+            expr_stmt(assign_expr(member(this(), "cause"), ident_expr("cause"))),
+        ]),
+    );
+
+    export_class(
+        "ApiNetworkError",
+        vec![],
+        Some("ApiError"),
+        vec![
+            declared_class_prop("kind", ts_lit_str("network")),
+            class_prop("cause", ts_kw!(unknown)),
+            ctor,
+        ],
+    )
+}
+
+/// Generate the error thrown when a successful response body isn't valid JSON:
+///
+/// ```ts
+/// export class ApiParseError extends ApiError {
+///   declare kind: "parse";
+///   text: string;
+///   cause: unknown;
+///   constructor(status: number, statusText: string, text: string, cause: unknown) {
+///     super(status, statusText, undefined, "parse");
+///     this.name = "ApiParseError";
+///     this.text = text;
+///     this.cause = cause;
+///   }
+/// }
+/// ```
+fn codegen_api_parse_error_class() -> ModuleItem {
+    let this = || Expr::This(ThisExpr { span: DUMMY_SP });
+    let ctor = constructor(
+        vec![
+            constructor_param("status", Some(ts_kw!(number))),
+            constructor_param("statusText", Some(ts_kw!(string))),
+            constructor_param("text", Some(ts_kw!(string))),
+            constructor_param("cause", Some(ts_kw!(unknown))),
+        ],
+        block(vec![
+            expr_stmt(Expr::Call(CallExpr {
+                span: DUMMY_SP,
+                ctxt: swc_common::SyntaxContext::empty(),
+                callee: Callee::Super(Super { span: DUMMY_SP }),
+                args: vec![
+                    arg(ident_expr("status")),
+                    arg(ident_expr("statusText")),
+                    arg(ident_expr("undefined")),
+                    arg(str_lit("parse")),
+                ],
+                type_args: None,
+            })),
+            expr_stmt(assign_expr(
+                member(this(), "name"),
+                str_lit("ApiParseError"),
+            )),
+            expr_stmt(assign_expr(member(this(), "text"), ident_expr("text"))),
+            expr_stmt(assign_expr(member(this(), "cause"), ident_expr("cause"))),
+        ]),
+    );
+
+    export_class(
+        "ApiParseError",
+        vec![],
+        Some("ApiError"),
+        vec![
+            declared_class_prop("kind", ts_lit_str("parse")),
+            class_prop("text", ts_kw!(string)),
+            class_prop("cause", ts_kw!(unknown)),
+            ctor,
+        ],
+    )
+}
+
+/// Generate the union hooks use as their error type, narrowable on `kind`:
+///
+/// ```ts
+/// export type ApiFailure<TBody = unknown> =
+///   | (ApiError<TBody> & { kind: "http" })
+///   | ApiNetworkError
+///   | ApiParseError
+///   | ApiTimeoutError;
+/// ```
+fn codegen_api_failure_type() -> ModuleItem {
+    let http = ts_intersection(vec![
+        ts_type_ref_with_params("ApiError", vec![ts_type_ref("TBody")]),
+        ts_object_type(vec![ts_property_sig("kind", ts_lit_str("http"), false)]),
+    ]);
+    let union = ts_union(vec![
+        ts_paren(http),
+        ts_type_ref("ApiNetworkError"),
+        ts_type_ref("ApiParseError"),
+        ts_type_ref("ApiTimeoutError"),
+    ]);
+    export_type_alias_with_params(
+        API_FAILURE_TYPE,
+        vec![ts_type_param("TBody", Some(ts_kw!(unknown)))],
+        union,
+    )
+}
+
+/// Name of the result type returned by the `safeXxx` fetch variants.
+const API_RESULT_TYPE: &str = "ApiResult";
+
+/// ```ts
+/// export type ApiResult<T, TError = ApiFailure> =
+///   | { ok: true; data: T }
+///   | { ok: false; error: TError };
+/// ```
+fn codegen_api_result_type() -> ModuleItem {
+    let ok = ts_object_type(vec![
+        ts_property_sig("ok", ts_lit_bool(true), false),
+        ts_property_sig("data", ts_type_ref("T"), false),
+    ]);
+    let failed = ts_object_type(vec![
+        ts_property_sig("ok", ts_lit_bool(false), false),
+        ts_property_sig("error", ts_type_ref("TError"), false),
+    ]);
+    export_type_alias_with_params(
+        API_RESULT_TYPE,
+        vec![
+            ts_type_param("T", None),
+            ts_type_param("TError", Some(ts_type_ref(API_FAILURE_TYPE))),
+        ],
+        ts_union(vec![ok, failed]),
+    )
+}
+
+/// `export type ApiRequestInit = RequestInit & { timeoutMs?: number };`
+///
+/// With retries enabled the options also take `retries`, `retryBaseMs` and `retryOn`.
+fn codegen_request_init_type(fetch_retry: bool) -> ModuleItem {
+    let mut members = vec![ts_property_sig("timeoutMs", ts_kw!(number), true)];
+    if fetch_retry {
+        members.extend([
+            ts_property_sig("retries", ts_kw!(number), true),
+            ts_property_sig("retryBaseMs", ts_kw!(number), true),
+            ts_property_sig("retryOn", ts_array(ts_kw!(number)), true),
+        ]);
+    }
+    export_type_alias(
+        REQUEST_INIT_TYPE,
+        ts_intersection(vec![ts_type_ref("RequestInit"), ts_object_type(members)]),
+    )
+}
+
+/// Generate the helper every fetch function sends its request through:
+///
+/// ```ts
+/// const fetchWithTimeout = async (input: string, init: ApiRequestInit): Promise<Response> => {
+///   const timeoutMs = init.timeoutMs;
+///   const signal = init.signal;
+///   if (timeoutMs === undefined) {
+///     try {
+///       return await fetcher(input, init);
+///     } catch (error) {
+///       throw signal?.aborted ? error : new ApiNetworkError(error);
+///     }
+///   }
+///   const controller = new AbortController();
+///   signal?.addEventListener("abort", () => controller.abort(), { once: true });
+///   if (signal?.aborted) controller.abort();
+///   let timedOut = false;
+///   const timer = setTimeout(() => { timedOut = true; controller.abort(); }, timeoutMs);
+///   try {
+///     return await fetcher(input, { ...init, signal: controller.signal });
+///   } catch (error) {
+///     if (timedOut) throw new ApiTimeoutError(timeoutMs);
+///     throw signal?.aborted ? error : new ApiNetworkError(error);
+///   } finally {
+///     clearTimeout(timer);
+///   }
+/// };
+/// ```
+///
+/// Without `timeoutMs` the request is passed to `fetcher` untouched. A failed
+/// request throws an `ApiNetworkError` wrapping the original error, except when
+/// the caller's own `signal` aborted it, which rethrows the original error.
+fn codegen_fetch_with_timeout() -> ModuleItem {
+    let abort = || expr_stmt(call(member(ident_expr("controller"), "abort"), vec![]));
+    let fetcher_call = |init: Expr| call(ident_expr(FETCHER_VAR), vec![ident_expr("input"), init]);
+    let throw_network_error = || {
+        throw_stmt(cond_expr(
+            opt_chain_member(ident_expr("signal"), "aborted"),
+            ident_expr("error"),
+            new_expr(ident_expr("ApiNetworkError"), vec![ident_expr("error")]),
+        ))
+    };
+
+    let stmts = vec![
+        const_decl("timeoutMs", member(ident_expr("init"), "timeoutMs")),
+        const_decl("signal", member(ident_expr("init"), "signal")),
+        if_stmt(
+            bin_expr(
+                ident_expr("timeoutMs"),
+                BinaryOp::EqEqEq,
+                ident_expr("undefined"),
+            ),
+            block_stmt(vec![try_catch_finally_stmt(
+                block(vec![return_stmt(Some(await_expr(fetcher_call(
+                    ident_expr("init"),
+                ))))]),
+                Some(("error", block(vec![throw_network_error()]))),
+                None,
+            )]),
+            None,
+        ),
+        const_decl(
+            "controller",
+            new_expr(ident_expr("AbortController"), vec![]),
+        ),
+        expr_stmt(call(
+            opt_chain_member(ident_expr("signal"), "addEventListener"),
+            vec![
+                str_lit("abort"),
+                arrow_fn_expr(
+                    vec![],
+                    call(member(ident_expr("controller"), "abort"), vec![]),
+                ),
+                obj_lit(vec![kv_prop("once", bool_lit(true))]),
+            ],
+        )),
+        if_stmt(
+            opt_chain_member(ident_expr("signal"), "aborted"),
+            abort(),
+            None,
+        ),
+        Stmt::Decl(var_decl(
+            VarDeclKind::Let,
+            "timedOut",
+            None,
+            bool_lit(false),
+        )),
+        const_decl(
+            "timer",
+            call(
+                ident_expr("setTimeout"),
+                vec![
+                    arrow_fn_block(
+                        vec![],
+                        None,
+                        block(vec![
+                            expr_stmt(assign_ident("timedOut", bool_lit(true))),
+                            abort(),
+                        ]),
+                    ),
+                    ident_expr("timeoutMs"),
+                ],
+            ),
+        ),
+        try_catch_finally_stmt(
+            block(vec![return_stmt(Some(await_expr(fetcher_call(obj_lit(
+                vec![
+                    spread_prop(ident_expr("init")),
+                    kv_prop("signal", member(ident_expr("controller"), "signal")),
+                ],
+            )))))]),
+            Some((
+                "error",
+                block(vec![
+                    if_stmt(
+                        ident_expr("timedOut"),
+                        throw_stmt(new_expr(
+                            ident_expr("ApiTimeoutError"),
+                            vec![ident_expr("timeoutMs")],
+                        )),
+                        None,
+                    ),
+                    throw_network_error(),
+                ]),
+            )),
+            Some(block(vec![expr_stmt(call(
+                ident_expr("clearTimeout"),
+                vec![ident_expr("timer")],
+            ))])),
+        ),
+    ];
+
+    let helper = ArrowExpr {
+        span: DUMMY_SP,
+        ctxt: swc_common::SyntaxContext::empty(),
+        params: vec![
+            pat_ident("input", Some(ts_kw!(string)), false),
+            pat_ident("init", Some(ts_type_ref(REQUEST_INIT_TYPE)), false),
+        ],
+        body: Box::new(BlockStmtOrExpr::BlockStmt(block(stmts))),
+        is_async: true,
+        is_generator: false,
+        type_params: None,
+        return_type: Some(ts_type_ann(promise_type(ts_type_ref("Response")))),
+    };
+    ModuleItem::Stmt(const_decl(FETCH_WITH_TIMEOUT_FN, Expr::Arrow(helper)))
+}
+
+/// Generate the `Retry-After` parser used by [`codegen_fetch_with_retry`]:
+///
+/// ```ts
+/// const parseRetryAfter = (value: string | null): number | undefined => {
+///   if (value === null) return undefined;
+///   const seconds = Number(value);
+///   const ms = Number.isNaN(seconds) ? Date.parse(value) - Date.now() : seconds * 1000;
+///   return Number.isNaN(ms) ? undefined : Math.max(0, ms);
+/// };
+/// ```
+///
+/// Both forms of the header are accepted: delay seconds and an HTTP date.
+fn codegen_parse_retry_after() -> ModuleItem {
+    let is_nan = |expr: Expr| call(member(ident_expr("Number"), "isNaN"), vec![expr]);
+    let stmts = vec![
+        if_stmt(
+            bin_expr(ident_expr("value"), BinaryOp::EqEqEq, null_lit()),
+            return_stmt(Some(ident_expr("undefined"))),
+            None,
+        ),
+        const_decl(
+            "seconds",
+            call(ident_expr("Number"), vec![ident_expr("value")]),
+        ),
+        const_decl(
+            "ms",
+            cond_expr(
+                is_nan(ident_expr("seconds")),
+                bin_expr(
+                    call(
+                        member(ident_expr("Date"), "parse"),
+                        vec![ident_expr("value")],
+                    ),
+                    BinaryOp::Sub,
+                    call(member(ident_expr("Date"), "now"), vec![]),
+                ),
+                bin_expr(ident_expr("seconds"), BinaryOp::Mul, num_lit(1000.0)),
+            ),
+        ),
+        return_stmt(Some(cond_expr(
+            is_nan(ident_expr("ms")),
+            ident_expr("undefined"),
+            call(
+                member(ident_expr("Math"), "max"),
+                vec![num_lit(0.0), ident_expr("ms")],
+            ),
+        ))),
+    ];
+    let parser = arrow_fn_block(
+        vec![pat_ident(
+            "value",
+            Some(ts_union(vec![ts_kw!(string), ts_kw!(null)])),
+            false,
+        )],
+        Some(ts_union(vec![ts_kw!(number), ts_kw!(undefined)])),
+        block(stmts),
+    );
+    ModuleItem::Stmt(const_decl(PARSE_RETRY_AFTER_FN, parser))
+}
+
+/// Generate the retrying wrapper around [`FETCH_WITH_TIMEOUT_FN`]:
+///
+/// ```ts
+/// const fetchWithRetry = async (input: string, init: ApiRequestInit): Promise<Response> => {
+///   const method = (init.method ?? "GET").toUpperCase();
+///   const idempotent = ["GET", "HEAD", "OPTIONS", "PUT", "DELETE"].includes(method);
+///   const retries = init.retries ?? (idempotent ? 2 : 0);
+///   const retryOn = init.retryOn ?? [429, 503];
+///   const retryBaseMs = init.retryBaseMs ?? 250;
+///   for (let attempt = 0; ; attempt++) {
+///     const res = await fetchWithTimeout(input, init);
+///     if (attempt >= retries || !retryOn.includes(res.status)) return res;
+///     const delayMs = parseRetryAfter(res.headers.get("Retry-After")) ?? retryBaseMs * 2 ** attempt;
+///     await new Promise((resolve) => setTimeout(resolve, delayMs));
+///   }
+/// };
+/// ```
+///
+/// `timeoutMs` applies to each attempt separately.
+fn codegen_fetch_with_retry() -> ModuleItem {
+    let option = |name: &str, fallback: Expr| {
+        bin_expr(
+            member(ident_expr("init"), name),
+            BinaryOp::NullishCoalescing,
+            fallback,
+        )
+    };
+    let method = call(
+        member(paren(option("method", str_lit("GET"))), "toUpperCase"),
+        vec![],
+    );
+    let idempotent = call(
+        member(
+            array_lit(
+                ["GET", "HEAD", "OPTIONS", "PUT", "DELETE"]
+                    .into_iter()
+                    .map(str_lit)
+                    .collect(),
+            ),
+            "includes",
+        ),
+        vec![ident_expr("method")],
+    );
+    let retry_statuses = array_lit(
+        DEFAULT_RETRY_STATUSES
+            .iter()
+            .map(|&status| num_lit(f64::from(status)))
+            .collect(),
+    );
+
+    let res = || ident_expr("res");
+    let give_up = bin_expr(
+        bin_expr(ident_expr("attempt"), BinaryOp::GtEq, ident_expr("retries")),
+        BinaryOp::LogicalOr,
+        unary_not(call(
+            member(ident_expr("retryOn"), "includes"),
+            vec![member(res(), "status")],
+        )),
+    );
+    let backoff = bin_expr(
+        ident_expr("retryBaseMs"),
+        BinaryOp::Mul,
+        bin_expr(num_lit(2.0), BinaryOp::Exp, ident_expr("attempt")),
+    );
+    let delay = bin_expr(
+        call(
+            ident_expr(PARSE_RETRY_AFTER_FN),
+            vec![call(
+                member(member(res(), "headers"), "get"),
+                vec![str_lit("Retry-After")],
+            )],
+        ),
+        BinaryOp::NullishCoalescing,
+        backoff,
+    );
+    let sleep = await_expr(new_expr(
+        ident_expr("Promise"),
+        vec![arrow_fn_expr(
+            vec![pat_ident("resolve", None, false)],
+            call(
+                ident_expr("setTimeout"),
+                vec![ident_expr("resolve"), ident_expr("delayMs")],
+            ),
+        )],
+    ));
+    let attempt = block(vec![
+        const_decl(
+            "res",
+            await_expr(call(
+                ident_expr(FETCH_WITH_TIMEOUT_FN),
+                vec![ident_expr("input"), ident_expr("init")],
+            )),
+        ),
+        if_stmt(give_up, return_stmt(Some(res())), None),
+        const_decl("delayMs", delay),
+        expr_stmt(sleep),
+    ]);
+
+    let stmts = vec![
+        const_decl("method", method),
+        const_decl("idempotent", idempotent),
+        const_decl(
+            "retries",
+            option(
+                "retries",
+                paren(cond_expr(
+                    ident_expr("idempotent"),
+                    num_lit(f64::from(DEFAULT_RETRIES)),
+                    num_lit(0.0),
+                )),
+            ),
+        ),
+        const_decl("retryOn", option("retryOn", retry_statuses)),
+        const_decl(
+            "retryBaseMs",
+            option("retryBaseMs", num_lit(f64::from(DEFAULT_RETRY_BASE_MS))),
+        ),
+        counting_loop("attempt", attempt),
+    ];
+
+    let helper = ArrowExpr {
+        span: DUMMY_SP,
+        ctxt: swc_common::SyntaxContext::empty(),
+        params: vec![
+            pat_ident("input", Some(ts_kw!(string)), false),
+            pat_ident("init", Some(ts_type_ref(REQUEST_INIT_TYPE)), false),
+        ],
+        body: Box::new(BlockStmtOrExpr::BlockStmt(block(stmts))),
+        is_async: true,
+        is_generator: false,
+        type_params: None,
+        return_type: Some(ts_type_ann(promise_type(ts_type_ref("Response")))),
+    };
+    ModuleItem::Stmt(const_decl(FETCH_WITH_RETRY_FN, Expr::Arrow(helper)))
+}
+
+/// Generate code for a single operation.
+///
+/// Fetch functions send their request through `fetch_fn`.
+fn codegen_operation(
+    op: &OperationIR,
+    has_base_url: bool,
+    options: &GenerateOptions,
+    fetch_fn: &str,
+    docs: &DocComments,
+) -> Vec<(ModuleSection, ModuleItem)> {
+    use ModuleSection::{Client, Hooks, Types};
+
+    let mut items = Vec::new();
+
+    // Generate params interface
+    if let Some(params) = &op.params {
+        items.push((Types, codegen_params_type(params, docs)));
+    }
+
+    // Generate body defaults constant
+    let defaults_const = op
+        .fetch
+        .body
+        .as_ref()
+        .filter(|body| options.body_defaults && !body.defaults.is_empty())
+        .map(|body| {
+            let name = format!("{}BodyDefaults", op.fetch.fn_name);
+            items.push((Client, codegen_body_defaults(&name, body, docs)));
+            name
+        });
+
+    // Generate runtime check of the required params
+    let guard_fn = guarded_params(op)
+        .filter(|_| options.param_guards)
+        .map(|params| {
+            let name = format!("assert{}", params.type_name);
+            items.push((
+                Client,
+                codegen_params_guard(&name, &op.fetch.fn_name, params),
+            ));
+            name
+        });
+
+    // Generate typed multipart/form-data builder
+    if has_form_fields(op) {
+        items.push((Client, codegen_form_data_builder(&op.fetch, docs)));
+    }
+
+    // Generate fetch function
+    items.push((
+        Client,
+        codegen_fetch_function(
+            &op.fetch,
+            has_base_url,
+            defaults_const.as_deref(),
+            guard_fn.as_deref(),
+            fetch_fn,
+            docs,
+        ),
+    ));
+    if options.safe_results {
+        items.push((Client, codegen_safe_fetch_function(&op.fetch, docs)));
+    }
+
+    // Generate query key function
+    if let Some(qk) = &op.query_key {
+        items.push((Client, codegen_query_key_function(qk, docs)));
+        if let Some(legacy) = &qk.legacy_fn_name {
+            items.push((
+                Client,
+                with_jsdoc(
+                    export_const(legacy, None, ident_expr(&qk.fn_name)),
+                    &format!("@deprecated Use `{}`", qk.fn_name),
+                    docs,
+                ),
+            ));
+        }
+    }
+
+    // Generate hooks, plus a cache prefetch helper next to the query hook
+    for hook in &op.hooks {
+        items.push((Hooks, codegen_hook(hook, docs)));
+        if hook.kind == HookKind::Query {
+            items.push((Hooks, codegen_prefetch(hook, docs)));
+        }
+    }
+
+    items
+}
+
+/// Name of the helper assembling `FormData` from a typed object.
+const TO_FORM_DATA_FN: &str = "toFormData";
+
+/// Whether the operation gets a typed `multipart/form-data` builder.
+fn has_form_fields(op: &OperationIR) -> bool {
+    op.fetch
+        .body
+        .as_ref()
+        .is_some_and(|body| !body.form_fields.is_empty())
+}
+
+/// Generate the helper behind the typed `multipart/form-data` builders:
+///
+/// ```ts
+/// const toFormData = (data: Record<string, unknown>, jsonParts: Record<string, string>): FormData => {
+///   const form = new FormData();
+///   for (const name of Object.keys(data)) {
+///     const value = data[name];
+///     const contentType = jsonParts[name];
+///     if (value != null) {
+///       if (contentType !== undefined) form.append(name, new Blob([JSON.stringify(value)], { type: contentType }));
+///       else for (const item of Array.isArray(value) ? value : [value]) form.append(name, item instanceof Blob ? item : String(item));
+///     }
+///   }
+///   return form;
+/// };
+/// ```
+///
+/// Parts listed in `jsonParts` are sent as JSON with their content type; other
+/// values are appended as-is (binary) or as strings, one part per array item.
+fn codegen_to_form_data() -> ModuleItem {
+    let append = |value: Expr| {
+        expr_stmt(call(
+            member(ident_expr("form"), "append"),
+            vec![ident_expr("name"), value],
+        ))
+    };
+    let json_part = new_expr(
+        ident_expr("Blob"),
+        vec![
+            array_lit(vec![call(
+                member(ident_expr("JSON"), "stringify"),
+                vec![ident_expr("value")],
+            )]),
+            obj_lit(vec![kv_prop("type", ident_expr("contentType"))]),
+        ],
+    );
+    let plain_parts = for_of_const(
+        "item",
+        cond_expr(
+            call(
+                member(ident_expr("Array"), "isArray"),
+                vec![ident_expr("value")],
+            ),
+            ident_expr("value"),
+            array_lit(vec![ident_expr("value")]),
+        ),
+        block(vec![append(cond_expr(
+            bin_expr(ident_expr("item"), BinaryOp::InstanceOf, ident_expr("Blob")),
+            ident_expr("item"),
+            call(ident_expr("String"), vec![ident_expr("item")]),
+        ))]),
+    );
+
+    let stmts = vec![
+        const_decl("form", new_expr(ident_expr("FormData"), vec![])),
+        for_of_const(
+            "name",
+            call(
+                member(ident_expr("Object"), "keys"),
+                vec![ident_expr("data")],
+            ),
+            block(vec![
+                const_decl(
+                    "value",
+                    computed_member(ident_expr("data"), ident_expr("name")),
+                ),
+                const_decl(
+                    "contentType",
+                    computed_member(ident_expr("jsonParts"), ident_expr("name")),
+                ),
+                if_stmt(
+                    not_null_check(ident_expr("value")),
+                    block_stmt(vec![if_stmt(
+                        bin_expr(
+                            ident_expr("contentType"),
+                            BinaryOp::NotEqEq,
+                            ident_expr("undefined"),
+                        ),
+                        append(json_part),
+                        Some(plain_parts),
+                    )]),
+                    None,
+                ),
+            ]),
+        ),
+        return_stmt(Some(ident_expr("form"))),
+    ];
+
+    let helper = arrow_fn_block(
+        vec![
+            pat_ident(
+                "data",
+                Some(ts_type_ref_with_params(
+                    "Record",
+                    vec![ts_kw!(string), ts_kw!(unknown)],
+                )),
+                false,
+            ),
+            pat_ident(
+                "jsonParts",
+                Some(ts_type_ref_with_params(
+                    "Record",
+                    vec![ts_kw!(string), ts_kw!(string)],
+                )),
+                false,
+            ),
+        ],
+        Some(ts_type_ref("FormData")),
+        block(stmts),
+    );
+    ModuleItem::Stmt(const_decl(TO_FORM_DATA_FN, helper))
+}
+
+/// Generate the typed builder for a `multipart/form-data` body:
+///
+/// ```ts
+/// export const uploadFileFormData = (data: { file: Blob; meta?: Meta; }): FormData =>
+///   toFormData(data, { meta: "application/json" });
+/// ```
+///
+/// The fetch function still takes any `FormData`, so hand-built forms keep working.
+fn codegen_form_data_builder(fetch: &FetchIR, docs: &DocComments) -> ModuleItem {
+    let fields = fetch
+        .body
+        .as_ref()
+        .map_or(&[][..], |body| body.form_fields.as_slice());
+    let data_type = ts_object_type(
+        fields
+            .iter()
+            .map(|f| ts_property_sig(&f.name, ir_typeref_to_swc(&f.ty, docs), f.optional))
+            .collect(),
+    );
+    let json_parts = obj_lit(
+        fields
+            .iter()
+            .filter_map(|f| {
+                let content_type = str_lit(f.json_content_type.as_deref()?);
+                Some(if needs_bracket_notation(&f.name) {
+                    kv_prop_str(&f.name, content_type)
+                } else {
+                    kv_prop(&f.name, content_type)
+                })
+            })
+            .collect(),
+    );
+
+    export_const_arrow(
+        &form_data_builder_name(&fetch.fn_name),
+        vec![pat_ident("data", Some(data_type), false)],
+        Some(ts_type_ref("FormData")),
+        block(vec![return_stmt(Some(call(
+            ident_expr(TO_FORM_DATA_FN),
+            vec![ident_expr("data"), json_parts],
+        )))]),
+        false,
+    )
+}
+
+/// `uploadFile` -> `uploadFileFormData`
+fn form_data_builder_name(fetch_fn: &str) -> String {
+    format!("{fetch_fn}FormData")
+}
+
+/// `export const createItemBodyDefaults: Partial<Item> = { status: "active" };`
+fn codegen_body_defaults(name: &str, body: &BodyIR, docs: &DocComments) -> ModuleItem {
+    let defaults: serde_json::Map<_, _> = body
+        .defaults
+        .iter()
+        .map(|d| (d.name.clone(), d.value.clone()))
+        .collect();
+    export_const(
+        name,
+        Some(ts_type_ref_with_params(
+            "Partial",
+            vec![ir_typeref_to_swc(&body.ty, docs)],
+        )),
+        json_lit_with_bigints(&serde_json::Value::Object(defaults), &body.bigint_paths),
     )
 }
 
-/// Generate code for a single operation.
-fn codegen_operation(op: &OperationIR) -> Vec<ModuleItem> {
-    let mut items = Vec::new();
+/// `export type CreateItemBody = Omit<Item, "status"> & Partial<Pick<Item, "status">>;`
+fn codegen_body_alias(alias: &BodyAliasIR, docs: &DocComments) -> ModuleItem {
+    let keys = || ts_union(alias.optional_keys.iter().map(|k| ts_lit_str(k)).collect());
+    let base = || ir_typeref_to_swc(&alias.base, docs);
+    export_type_alias(
+        &alias.name,
+        ts_intersection(vec![
+            ts_omit(base(), keys()),
+            ts_type_ref_with_params(
+                "Partial",
+                vec![ts_type_ref_with_params("Pick", vec![base(), keys()])],
+            ),
+        ]),
+    )
+}
 
-    // Generate params interface
-    if let Some(params) = &op.params {
-        items.push(codegen_params_type(params));
-    }
+/// Name of the helper checking that required params are present.
+const ASSERT_REQUIRED_PARAMS_FN: &str = "assertRequiredParams";
 
-    // Generate fetch function
-    items.push(codegen_fetch_function(&op.fetch));
+/// Params of an operation that has at least one required param, which get a guard.
+fn guarded_params(op: &OperationIR) -> Option<&ParamsIR> {
+    op.params
+        .as_ref()
+        .filter(|params| params.fields.iter().any(|field| field.required))
+}
 
-    // Generate query key function
-    if let Some(qk) = &op.query_key {
-        items.push(codegen_query_key_function(qk));
-    }
+/// Generate the helper behind the per-operation params guards:
+///
+/// ```ts
+/// const assertRequiredParams = (operation: string, params: object | undefined, keys: string[]): void => {
+///   for (const key of keys) {
+///     const value = (params as Record<string, unknown> | undefined)?.[key];
+///     if (value == null || value === "") throw new TypeError(`${operation}: required param "${key}" is missing or empty`);
+///   }
+/// };
+/// ```
+fn codegen_assert_required_params() -> ModuleItem {
+    let value = opt_chain_computed(
+        paren(ts_as_expr(
+            ident_expr("params"),
+            ts_union(vec![
+                ts_type_ref_with_params("Record", vec![ts_kw!(string), ts_kw!(unknown)]),
+                ts_kw!(undefined),
+            ]),
+        )),
+        ident_expr("key"),
+    );
+    let is_missing = bin_expr(
+        bin_expr(ident_expr("value"), BinaryOp::EqEq, null_lit()),
+        BinaryOp::LogicalOr,
+        bin_expr(ident_expr("value"), BinaryOp::EqEqEq, str_lit("")),
+    );
+    let error = new_expr(
+        ident_expr("TypeError"),
+        vec![tpl(
+            vec!["", ": required param \"", "\" is missing or empty"],
+            vec![ident_expr("operation"), ident_expr("key")],
+        )],
+    );
 
-    // Generate hooks
-    for hook in &op.hooks {
-        items.push(codegen_hook(hook));
-    }
+    let helper = arrow_fn_block(
+        vec![
+            pat_ident("operation", Some(ts_kw!(string)), false),
+            pat_ident(
+                "params",
+                Some(ts_union(vec![ts_type_ref("object"), ts_kw!(undefined)])),
+                false,
+            ),
+            pat_ident("keys", Some(ts_array(ts_kw!(string))), false),
+        ],
+        Some(ts_kw!(void)),
+        block(vec![for_of_const(
+            "key",
+            ident_expr("keys"),
+            block(vec![
+                const_decl("value", value),
+                if_stmt(is_missing, throw_stmt(error), None),
+            ]),
+        )]),
+    );
+    ModuleItem::Stmt(const_decl(ASSERT_REQUIRED_PARAMS_FN, helper))
+}
 
-    items
+/// Generate the guard checking an operation's required params at runtime:
+///
+/// ```ts
+/// export const assertGetItemParams = (params: GetItemParams): void =>
+///   assertRequiredParams("getItem", params, ["itemId"]);
+/// ```
+fn codegen_params_guard(name: &str, fetch_fn: &str, params: &ParamsIR) -> ModuleItem {
+    let keys = params
+        .fields
+        .iter()
+        .filter(|field| field.required)
+        .map(|field| str_lit(&field.name))
+        .collect();
+    let check = call(
+        ident_expr(ASSERT_REQUIRED_PARAMS_FN),
+        vec![str_lit(fetch_fn), ident_expr("params"), array_lit(keys)],
+    );
+    export_const(
+        name,
+        None,
+        arrow_fn_expr_typed(
+            vec![pat_ident(
+                "params",
+                Some(ts_type_ref(&params.type_name)),
+                false,
+            )],
+            Some(ts_kw!(void)),
+            check,
+        ),
+    )
 }
 
 /// Generate a params interface type.
-fn codegen_params_type(params: &ParamsIR) -> ModuleItem {
+fn codegen_params_type(params: &ParamsIR, docs: &DocComments) -> ModuleItem {
     let properties = params
         .fields
         .iter()
-        .map(|field| ts_property_sig(&field.name, ir_typeref_to_swc(&field.ty), !field.required))
+        .map(|field| {
+            ts_property_sig(
+                &field.name,
+                ir_typeref_to_swc(&field.ty, docs),
+                !field.required,
+            )
+        })
         .collect();
 
     export_interface(&params.type_name, properties)
 }
 
-/// Generate a fetch function.
-fn codegen_fetch_function(fetch: &FetchIR) -> ModuleItem {
+/// Parameters of a fetch function, with the content type of its body argument.
+fn fetch_fn_params(fetch: &FetchIR, docs: &DocComments) -> (Vec<Pat>, Option<BodyContentType>) {
     let mut params = Vec::new();
     let mut body_content_type = None;
 
@@ -177,25 +2850,45 @@ fn codegen_fetch_function(fetch: &FetchIR) -> ModuleItem {
     for a in &fetch.args {
         match a {
             FetchArgIR::Params { ty, optional } => {
-                let swc_ty = ir_typeref_to_swc(ty);
+                let swc_ty = ir_typeref_to_swc(ty, docs);
                 params.push(pat_ident("params", Some(swc_ty), *optional));
             }
             FetchArgIR::Body { ty, content_type } => {
                 body_content_type = Some(*content_type);
                 let ty_type = match content_type {
                     BodyContentType::FormData => ts_type_ref("FormData"),
-                    BodyContentType::UrlEncoded | BodyContentType::Json => ir_typeref_to_swc(ty),
+                    BodyContentType::UrlEncoded | BodyContentType::Json => {
+                        ir_typeref_to_swc(ty, docs)
+                    }
                 };
                 params.push(pat_ident("data", Some(ty_type), false));
             }
             FetchArgIR::Options => {
-                params.push(pat_ident("options", Some(ts_type_ref("RequestInit")), true));
+                params.push(pat_ident(
+                    "options",
+                    Some(ts_type_ref(REQUEST_INIT_TYPE)),
+                    true,
+                ));
             }
         }
     }
+    (params, body_content_type)
+}
+
+/// Generate a fetch function.
+fn codegen_fetch_function(
+    fetch: &FetchIR,
+    has_base_url: bool,
+    defaults_const: Option<&str>,
+    guard_fn: Option<&str>,
+    fetch_fn: &str,
+    docs: &DocComments,
+) -> ModuleItem {
+    let (params, body_content_type) = fetch_fn_params(fetch, docs);
 
     // Build return type
-    let response_swc_type = resolve_content_type(fetch.response.content_type, &fetch.response.ty);
+    let response_swc_type =
+        resolve_content_type(fetch.response.content_type, &fetch.response.ty, docs);
     let is_void_response = is_void_type(&response_swc_type);
 
     let return_type = if is_void_response {
@@ -210,7 +2903,16 @@ fn codegen_fetch_function(fetch: &FetchIR) -> ModuleItem {
     };
 
     // Build function body
-    let body_stmts = codegen_fetch_body(fetch, body_content_type, is_void_response);
+    let body_stmts = codegen_fetch_body(
+        fetch,
+        body_content_type,
+        is_void_response,
+        has_base_url,
+        defaults_const,
+        guard_fn,
+        fetch_fn,
+        docs,
+    );
 
     export_const_arrow(
         &fetch.fn_name,
@@ -221,14 +2923,191 @@ fn codegen_fetch_function(fetch: &FetchIR) -> ModuleItem {
     )
 }
 
+/// Name of the non-throwing variant of a fetch function: `getItem` becomes `safeGetItem`.
+fn safe_fn_name(fetch_fn: &str) -> String {
+    format!("safe{}", capitalize_first(fetch_fn))
+}
+
+/// Generate the non-throwing variant of a fetch function:
+///
+/// ```ts
+/// export const safeGetItem = async (params: GetItemParams, options?: ApiRequestInit): Promise<ApiResult<Item, ApiFailure<ErrorBody>>> => {
+///   try {
+///     const res = await getItem(params, options);
+///     return { ok: true, data: res.data };
+///   } catch (error) {
+///     if (error instanceof ApiError) return { ok: false, error: error as ApiFailure<ErrorBody> };
+///     throw error;
+///   }
+/// };
+/// ```
+///
+/// Void responses resolve with `data: undefined`. Errors other than `ApiError`,
+/// such as a caller aborting the request, are rethrown.
+fn codegen_safe_fetch_function(fetch: &FetchIR, docs: &DocComments) -> ModuleItem {
+    let (params, _) = fetch_fn_params(fetch, docs);
+    let args = fetch
+        .args
+        .iter()
+        .map(|a| match a {
+            FetchArgIR::Params { .. } => ident_expr("params"),
+            FetchArgIR::Body { .. } => ident_expr("data"),
+            FetchArgIR::Options => ident_expr("options"),
+        })
+        .collect();
+    let request = await_expr(call(ident_expr(&fetch.fn_name), args));
+
+    let response_swc_type =
+        resolve_content_type(fetch.response.content_type, &fetch.response.ty, docs);
+    let ok = |data: Expr| {
+        return_stmt(Some(obj_lit(vec![
+            kv_prop("ok", bool_lit(true)),
+            kv_prop("data", data),
+        ])))
+    };
+    let (data_type, try_stmts) = if is_void_type(&response_swc_type) {
+        (
+            ts_kw!(void),
+            vec![expr_stmt(request), ok(ident_expr("undefined"))],
+        )
+    } else if fetch.response.has_void_status {
+        // res ? res.data : undefined
+        let data = cond_expr(
+            ident_expr("res"),
+            member(ident_expr("res"), "data"),
+            ident_expr("undefined"),
+        );
+        (
+            ts_union(vec![response_swc_type, ts_kw!(undefined)]),
+            vec![const_decl("res", request), ok(data)],
+        )
+    } else {
+        (
+            response_swc_type,
+            vec![
+                const_decl("res", request),
+                ok(member(ident_expr("res"), "data")),
+            ],
+        )
+    };
+
+    let error_type = api_error_type(&fetch.response.error_ty, docs);
+    let failed = return_stmt(Some(obj_lit(vec![
+        kv_prop("ok", bool_lit(false)),
+        kv_prop("error", ts_as_expr(ident_expr("error"), error_type.clone())),
+    ])));
+    let catch_body = block(vec![
+        if_stmt(
+            bin_expr(
+                ident_expr("error"),
+                BinaryOp::InstanceOf,
+                ident_expr("ApiError"),
+            ),
+            failed,
+            None,
+        ),
+        throw_stmt(ident_expr("error")),
+    ]);
+
+    export_const_arrow(
+        &safe_fn_name(&fetch.fn_name),
+        params,
+        Some(promise_type(ts_type_ref_with_params(
+            API_RESULT_TYPE,
+            vec![data_type, error_type],
+        ))),
+        block(vec![try_catch_finally_stmt(
+            block(try_stmts),
+            Some(("error", catch_body)),
+            None,
+        )]),
+        true,
+    )
+}
+
 /// Generate the body of a fetch function.
+// Reason: the body depends on every per-operation setting of the fetch function
+#[allow(clippy::too_many_arguments)]
 fn codegen_fetch_body(
     fetch: &FetchIR,
     body_content_type: Option<BodyContentType>,
     is_void_response: bool,
+    has_base_url: bool,
+    defaults_const: Option<&str>,
+    guard_fn: Option<&str>,
+    fetch_fn: &str,
+    docs: &DocComments,
 ) -> Vec<Stmt> {
     let mut stmts = Vec::new();
 
+    // assertGetItemParams(params);
+    if let Some(guard) = guard_fn {
+        stmts.push(expr_stmt(call(
+            ident_expr(guard),
+            vec![ident_expr("params")],
+        )));
+    }
+
+    let (url_stmts, url_expr) = codegen_url(fetch, has_base_url);
+    stmts.extend(url_stmts);
+
+    // const payload = { ...createItemBodyDefaults, ...data };
+    let json_body = if let Some(defaults) = defaults_const {
+        stmts.push(const_decl(
+            "payload",
+            obj_lit(vec![
+                spread_prop(ident_expr(defaults)),
+                spread_prop(ident_expr("data")),
+            ]),
+        ));
+        "payload"
+    } else {
+        "data"
+    };
+    stmts.push(codegen_fetch_call_stmt(
+        url_expr,
+        fetch,
+        body_content_type,
+        json_body,
+        fetch_fn,
+    ));
+
+    // Error handling: if (!res.ok) { ... }
+    stmts.push(codegen_error_handling(&fetch.response.error_ty, docs));
+
+    // Return statement based on response type
+    if is_void_response {
+        stmts.push(return_stmt(None));
+    } else if fetch.response.has_void_status {
+        // if (res.status === 204) return;
+        stmts.push(if_stmt(
+            bin_expr(
+                member(ident_expr("res"), "status"),
+                BinaryOp::EqEqEq,
+                num_lit(204.0),
+            ),
+            return_stmt(None),
+            None,
+        ));
+        stmts.extend(codegen_content_type_decl(&fetch.response));
+        let data_expr = response_data_expr(&fetch.response, &fetch.bigint_paths, docs);
+        stmts.push(return_stmt(Some(obj_lit(vec![kv_prop("data", data_expr)]))));
+    } else {
+        stmts.extend(codegen_content_type_decl(&fetch.response));
+        let data_expr = response_data_expr(&fetch.response, &fetch.bigint_paths, docs);
+        stmts.push(return_stmt(Some(obj_lit(vec![kv_prop("data", data_expr)]))));
+    }
+
+    stmts
+}
+
+/// Generate statements that build the request URL, plus the expression evaluating to it.
+///
+/// Shared by fetch functions and the `links` builders so both serialize query
+/// parameters identically.
+fn codegen_url(fetch: &FetchIR, has_base_url: bool) -> (Vec<Stmt>, Expr) {
+    let mut stmts = Vec::new();
+
     // URL building
     let has_path_params = fetch
         .url
@@ -237,88 +3116,82 @@ fn codegen_fetch_body(
         .any(|p| matches!(p, UrlPart::Param(_)));
     let has_query_params = !fetch.url.query_params.is_empty();
 
-    if has_path_params || has_query_params {
-        if has_query_params {
-            // const searchParams = new URLSearchParams()
-            stmts.push(const_decl(
-                "searchParams",
-                new_expr(ident_expr("URLSearchParams"), vec![]),
-            ));
+    if has_query_params {
+        // const searchParams = new URLSearchParams()
+        stmts.push(const_decl(
+            "searchParams",
+            new_expr(ident_expr("URLSearchParams"), vec![]),
+        ));
 
-            // Add query params
-            for qp in &fetch.url.query_params {
-                let access_expr = build_param_access_expr("params", &qp.name, qp.required);
-
-                if qp.ty.is_array() {
-                    // if (access != null) access.forEach((v) => searchParams.append("name", String(v)));
-                    let check_expr = not_null_check(access_expr.clone());
-                    let foreach_call = call(
-                        member(access_expr, "forEach"),
-                        vec![arrow_fn_expr(
-                            vec![pat_ident("v", None, false)],
-                            call(
-                                member(ident_expr("searchParams"), "append"),
-                                vec![
-                                    str_lit(&qp.original_name),
-                                    call(ident_expr("String"), vec![ident_expr("v")]),
-                                ],
-                            ),
-                        )],
-                    );
-                    stmts.push(if_stmt(check_expr, expr_stmt(foreach_call), None));
-                } else {
-                    // if (access != null) searchParams.set("name", String(access));
-                    let check_expr = not_null_check(access_expr.clone());
-                    let set_call = call(
-                        member(ident_expr("searchParams"), "set"),
-                        vec![
-                            str_lit(&qp.original_name),
-                            call(ident_expr("String"), vec![access_expr]),
-                        ],
-                    );
-                    stmts.push(if_stmt(check_expr, expr_stmt(set_call), None));
-                }
+        // Add query params
+        for qp in &fetch.url.query_params {
+            let access_expr = build_param_access_expr("params", &qp.name, qp.required);
+
+            if qp.ty.is_array() {
+                // if (access != null) access.forEach((v) => searchParams.append("name", String(v)));
+                let check_expr = not_null_check(access_expr.clone());
+                let foreach_call = call(
+                    member(access_expr, "forEach"),
+                    vec![arrow_fn_expr(
+                        vec![pat_ident("v", None, false)],
+                        call(
+                            member(ident_expr("searchParams"), "append"),
+                            vec![
+                                str_lit(&qp.original_name),
+                                call(ident_expr("String"), vec![ident_expr("v")]),
+                            ],
+                        ),
+                    )],
+                );
+                stmts.push(if_stmt(check_expr, expr_stmt(foreach_call), None));
+            } else {
+                // if (access != null) searchParams.set("name", String(access));
+                let check_expr = not_null_check(access_expr.clone());
+                let set_call = call(
+                    member(ident_expr("searchParams"), "set"),
+                    vec![
+                        str_lit(&qp.original_name),
+                        call(ident_expr("String"), vec![access_expr]),
+                    ],
+                );
+                stmts.push(if_stmt(check_expr, expr_stmt(set_call), None));
             }
+        }
 
-            // const queryString = searchParams.toString()
-            stmts.push(const_decl(
-                "queryString",
-                call(member(ident_expr("searchParams"), "toString"), vec![]),
-            ));
+        // const queryString = searchParams.toString()
+        stmts.push(const_decl(
+            "queryString",
+            call(member(ident_expr("searchParams"), "toString"), vec![]),
+        ));
 
-            // const url = queryString ? `path?${queryString}` : `path`
-            let path_template = build_path_template(&fetch.url.template);
-            let (path_quasis_q, path_exprs_q) =
-                build_tpl_parts_with_suffix(&fetch.url.template, Some("queryString"));
-            let (path_quasis, path_exprs) = build_tpl_parts_with_suffix(&fetch.url.template, None);
+        // const url = queryString ? `path?${queryString}` : `path`
+        let path_template = build_path_template(&fetch.url.template);
+        let (path_quasis_q, path_exprs_q) =
+            build_tpl_parts_with_suffix(&fetch.url.template, Some("queryString"), has_base_url);
+        let (path_quasis, path_exprs) =
+            build_tpl_parts_with_suffix(&fetch.url.template, None, has_base_url);
 
-            let url_with_qs = tpl(
-                path_quasis_q.iter().map(|s| s.as_str()).collect(),
-                path_exprs_q,
-            );
-            let url_without_qs = if path_exprs.is_empty() {
-                str_lit(&path_template)
-            } else {
-                tpl(path_quasis.iter().map(|s| s.as_str()).collect(), path_exprs)
-            };
+        let url_with_qs = tpl(
+            path_quasis_q.iter().map(|s| s.as_str()).collect(),
+            path_exprs_q,
+        );
+        let url_without_qs = if path_exprs.is_empty() {
+            str_lit(&path_template)
+        } else {
+            tpl(path_quasis.iter().map(|s| s.as_str()).collect(), path_exprs)
+        };
 
-            stmts.push(const_decl(
-                "url",
-                cond_expr(ident_expr("queryString"), url_with_qs, url_without_qs),
-            ));
+        stmts.push(const_decl(
+            "url",
+            cond_expr(ident_expr("queryString"), url_with_qs, url_without_qs),
+        ));
 
-            // Fetch call with url variable
-            stmts.push(codegen_fetch_call_stmt(
-                ident_expr("url"),
-                fetch,
-                body_content_type,
-            ));
-        } else {
-            // Just path params, use template literal directly
-            let (quasis, exprs) = build_tpl_parts_with_suffix(&fetch.url.template, None);
-            let url_expr = tpl(quasis.iter().map(|s| s.as_str()).collect(), exprs);
-            stmts.push(codegen_fetch_call_stmt(url_expr, fetch, body_content_type));
-        }
+        (stmts, ident_expr("url"))
+    } else if has_path_params {
+        // Just path params, use template literal directly
+        let (quasis, exprs) = build_tpl_parts_with_suffix(&fetch.url.template, None, has_base_url);
+        let url_expr = tpl(quasis.iter().map(|s| s.as_str()).collect(), exprs);
+        (stmts, url_expr)
     } else {
         // No params at all - static URL
         let path = fetch
@@ -331,138 +3204,110 @@ fn codegen_fetch_body(
             })
             .collect::<Vec<_>>()
             .join("");
-        stmts.push(codegen_fetch_call_stmt(
-            str_lit(&path),
-            fetch,
-            body_content_type,
-        ));
-    }
-
-    // Error handling: if (!res.ok) { ... }
-    stmts.push(codegen_error_handling());
-
-    // Return statement based on response type
-    if is_void_response {
-        stmts.push(return_stmt(None));
-    } else if fetch.response.has_void_status {
-        // if (res.status === 204) return;
-        stmts.push(if_stmt(
-            bin_expr(
-                member(ident_expr("res"), "status"),
-                BinaryOp::EqEqEq,
-                num_lit(204.0),
-            ),
-            return_stmt(None),
-            None,
-        ));
-        let data_expr = response_data_expr(fetch.response.content_type);
-        stmts.push(return_stmt(Some(obj_lit(vec![kv_prop("data", data_expr)]))));
-    } else {
-        let data_expr = response_data_expr(fetch.response.content_type);
-        stmts.push(return_stmt(Some(obj_lit(vec![kv_prop("data", data_expr)]))));
+        let url_expr = if has_base_url {
+            tpl(vec!["", &path], vec![ident_expr(BASE_URL_CONST)])
+        } else {
+            str_lit(&path)
+        };
+        (stmts, url_expr)
     }
-
-    stmts
 }
 
-/// Generate the `const res = await fetch(url, { ... })` statement.
+/// Generate the `const res = await fetchWithTimeout(url, { ... })` statement.
 fn codegen_fetch_call_stmt(
     url_expr: Expr,
     fetch: &FetchIR,
     body_content_type: Option<BodyContentType>,
+    json_body: &str,
+    fetch_fn: &str,
 ) -> Stmt {
-    let has_header_params = !fetch.header_params.is_empty();
-    let has_body = fetch.body.is_some();
-
     let mut fetch_props: Vec<PropOrSpread> = vec![
         spread_prop(ident_expr("options")),
         kv_prop("method", str_lit(fetch.method.as_str())),
     ];
 
-    if has_body || has_header_params {
-        let mut header_props: Vec<PropOrSpread> = Vec::new();
+    // Client-wide defaults come first so operation and per-call headers override them
+    let mut header_props: Vec<PropOrSpread> = vec![spread_prop(ident_expr(DEFAULT_HEADERS_VAR))];
 
-        // Add content-type header
-        if let Some(content_type) = body_content_type {
-            match content_type {
-                BodyContentType::Json => {
-                    header_props.push(kv_prop_str("Content-Type", str_lit("application/json")));
-                }
-                BodyContentType::UrlEncoded => {
-                    header_props.push(kv_prop_str(
-                        "Content-Type",
-                        str_lit("application/x-www-form-urlencoded"),
-                    ));
-                }
-                BodyContentType::FormData => {
-                    // Don't set Content-Type for FormData - browser sets it with boundary
-                }
+    // Add content-type header
+    if let Some(content_type) = body_content_type {
+        match content_type {
+            BodyContentType::Json => {
+                header_props.push(kv_prop_str("Content-Type", str_lit("application/json")));
+            }
+            BodyContentType::UrlEncoded => {
+                header_props.push(kv_prop_str(
+                    "Content-Type",
+                    str_lit("application/x-www-form-urlencoded"),
+                ));
+            }
+            BodyContentType::FormData => {
+                // Don't set Content-Type for FormData - browser sets it with boundary
             }
         }
+    }
 
-        // Add header params
-        for hp in &fetch.header_params {
-            if hp.required {
-                let access = build_param_access_expr("params", &hp.name, true);
-                header_props.push(kv_prop_str(&hp.original_name, access));
-            } else {
-                // ...( access != null && { "name": direct_access } )
-                let access = build_param_access_expr("params", &hp.name, false);
-                let direct_access = build_param_access_expr("params", &hp.name, true);
-                let conditional = bin_expr(
-                    not_null_check(access),
-                    BinaryOp::LogicalAnd,
-                    obj_lit(vec![kv_prop_str(&hp.original_name, direct_access)]),
-                );
-                header_props.push(spread_prop(paren(conditional)));
-            }
+    // Add header params
+    for hp in &fetch.header_params {
+        if hp.required {
+            let access = build_param_access_expr("params", &hp.name, true);
+            header_props.push(kv_prop_str(&hp.original_name, access));
+        } else {
+            // ...( access != null && { "name": direct_access } )
+            let access = build_param_access_expr("params", &hp.name, false);
+            let direct_access = build_param_access_expr("params", &hp.name, true);
+            let conditional = bin_expr(
+                not_null_check(access),
+                BinaryOp::LogicalAnd,
+                obj_lit(vec![kv_prop_str(&hp.original_name, direct_access)]),
+            );
+            header_props.push(spread_prop(paren(conditional)));
         }
+    }
 
-        // ...options?.headers
-        header_props.push(spread_prop(opt_chain_member(
-            ident_expr("options"),
-            "headers",
-        )));
+    // ...options?.headers
+    header_props.push(spread_prop(opt_chain_member(
+        ident_expr("options"),
+        "headers",
+    )));
 
-        fetch_props.push(kv_prop("headers", obj_lit(header_props)));
+    fetch_props.push(kv_prop("headers", obj_lit(header_props)));
 
-        // Add body
-        if has_body {
-            match body_content_type {
-                Some(BodyContentType::Json) => {
-                    fetch_props.push(kv_prop(
-                        "body",
-                        call(
-                            member(ident_expr("JSON"), "stringify"),
-                            vec![ident_expr("data")],
-                        ),
-                    ));
-                }
-                Some(BodyContentType::UrlEncoded) => {
-                    fetch_props.push(kv_prop(
-                        "body",
-                        new_expr(
-                            ident_expr("URLSearchParams"),
-                            vec![ts_as_expr(
-                                ident_expr("data"),
-                                ts_type_ref_with_params(
-                                    "Record",
-                                    vec![ts_kw!(string), ts_kw!(string)],
-                                ),
-                            )],
-                        ),
-                    ));
-                }
-                Some(BodyContentType::FormData) => {
-                    fetch_props.push(kv_prop("body", ident_expr("data")));
-                }
-                None => {}
+    // Add body
+    if fetch.body.is_some() {
+        match body_content_type {
+            Some(BodyContentType::Json) => {
+                let stringify = if fetch.bigint_body {
+                    ident_expr(STRINGIFY_BIGINT_JSON_FN)
+                } else {
+                    member(ident_expr("JSON"), "stringify")
+                };
+                fetch_props.push(kv_prop(
+                    "body",
+                    call(stringify, vec![ident_expr(json_body)]),
+                ));
+            }
+            Some(BodyContentType::UrlEncoded) => {
+                fetch_props.push(kv_prop(
+                    "body",
+                    new_expr(
+                        ident_expr("URLSearchParams"),
+                        vec![ts_as_expr(
+                            ident_expr("data"),
+                            ts_type_ref_with_params("Record", vec![ts_kw!(string), ts_kw!(string)]),
+                        )],
+                    ),
+                ));
             }
+            Some(BodyContentType::FormData) => {
+                fetch_props.push(kv_prop("body", ident_expr("data")));
+            }
+            None => {}
         }
     }
 
     let fetch_call = await_expr(call(
-        ident_expr("fetch"),
+        ident_expr(fetch_fn),
         vec![url_expr, obj_lit(fetch_props)],
     ));
 
@@ -475,10 +3320,12 @@ fn codegen_fetch_call_stmt(
 ///   const body = await res.text();
 ///   let parsed: unknown;
 ///   try { parsed = JSON.parse(body); } catch { parsed = body; }
-///   throw new ApiError(res.status, res.statusText, parsed);
+///   throw new ApiError(res.status, res.statusText, parsed as ErrorResponse);
 /// }
 /// ```
-fn codegen_error_handling() -> Stmt {
+///
+/// The cast is omitted when the operation declares no error body.
+fn codegen_error_handling(error_ty: &TypeRef, docs: &DocComments) -> Stmt {
     let body_decl = const_decl(
         "body",
         await_expr(call(member(ident_expr("res"), "text"), vec![])),
@@ -525,7 +3372,11 @@ fn codegen_error_handling() -> Stmt {
         vec![
             member(ident_expr("res"), "status"),
             member(ident_expr("res"), "statusText"),
-            ident_expr("parsed"),
+            if is_unknown(error_ty) {
+                ident_expr("parsed")
+            } else {
+                ts_as_expr(ident_expr("parsed"), ir_typeref_to_swc(error_ty, docs))
+            },
         ],
     ));
 
@@ -537,22 +3388,107 @@ fn codegen_error_handling() -> Stmt {
 }
 
 /// Get response data expression based on content type.
-fn response_data_expr(content_type: ResponseContentType) -> Expr {
+fn response_data_expr(
+    response: &ResponseIR,
+    bigint_paths: &[Vec<Option<String>>],
+    docs: &DocComments,
+) -> Expr {
+    if response.content_type == ResponseContentType::EventStream {
+        return event_stream_expr(&response.ty, bigint_paths, docs);
+    }
+    if response.content_type == ResponseContentType::Json && !bigint_paths.is_empty() {
+        // await parseJsonBody(res, (text) => parseJsonWithBigInt(text, [["id"], ...]))
+        let revive = arrow_fn_expr(
+            vec![pat_ident("text", None, false)],
+            call(
+                ident_expr(PARSE_BIGINT_JSON_FN),
+                vec![ident_expr("text"), bigint_paths_lit(bigint_paths)],
+            ),
+        );
+        return await_expr(call(
+            ident_expr(PARSE_JSON_BODY_FN),
+            vec![ident_expr("res"), revive],
+        ));
+    }
+    if response.content_type != ResponseContentType::Negotiated {
+        return parse_response_expr(response.content_type);
+    }
+
+    // ct.includes("a") || ct.includes("b") ? parseA : ... : parseLast
+    let mut variants = response.variants.iter().rev();
+    let fallback = variants.next().map_or_else(
+        || ident_expr("res"),
+        |v| parse_response_expr(v.content_type),
+    );
+    variants.fold(fallback, |alt, variant| {
+        let test = variant
+            .media_types
+            .iter()
+            .map(|mt| {
+                call(
+                    member(ident_expr("ct"), "includes"),
+                    vec![str_lit(&mt.to_ascii_lowercase())],
+                )
+            })
+            .reduce(|a, b| bin_expr(a, BinaryOp::LogicalOr, b))
+            .unwrap_or_else(|| bool_lit(false));
+        cond_expr(test, parse_response_expr(variant.content_type), alt)
+    })
+}
+
+/// Expression parsing `res` for a single content type.
+fn parse_response_expr(content_type: ResponseContentType) -> Expr {
     match content_type {
-        ResponseContentType::Json => await_expr(call(member(ident_expr("res"), "json"), vec![])),
+        ResponseContentType::Json => await_expr(call(
+            ident_expr(PARSE_JSON_BODY_FN),
+            vec![ident_expr("res")],
+        )),
         ResponseContentType::Text => await_expr(call(member(ident_expr("res"), "text"), vec![])),
         ResponseContentType::Blob => await_expr(call(member(ident_expr("res"), "blob"), vec![])),
-        ResponseContentType::Unknown => ident_expr("res"),
+        ResponseContentType::Unknown
+        | ResponseContentType::EventStream
+        | ResponseContentType::Negotiated => ident_expr("res"),
     }
 }
 
+/// `const ct = (res.headers.get("content-type") ?? "").toLowerCase();` for negotiated responses.
+fn codegen_content_type_decl(response: &ResponseIR) -> Option<Stmt> {
+    if response.content_type != ResponseContentType::Negotiated {
+        return None;
+    }
+    let header = bin_expr(
+        call(
+            member(member(ident_expr("res"), "headers"), "get"),
+            vec![str_lit("content-type")],
+        ),
+        BinaryOp::NullishCoalescing,
+        str_lit(""),
+    );
+    Some(const_decl(
+        "ct",
+        call(member(paren_expr(header), "toLowerCase"), vec![]),
+    ))
+}
+
 /// Resolve the SWC type for a response based on content type.
-fn resolve_content_type(content_type: ResponseContentType, ty: &TypeRef) -> Box<TsType> {
+fn resolve_content_type(
+    content_type: ResponseContentType,
+    ty: &TypeRef,
+    docs: &DocComments,
+) -> Box<TsType> {
     match content_type {
         ResponseContentType::Text => ts_kw!(string),
         ResponseContentType::Blob => ts_type_ref("Blob"),
         ResponseContentType::Unknown => ts_type_ref("Response"),
-        ResponseContentType::Json => ir_typeref_to_swc(ty),
+        ResponseContentType::EventStream => ts_type_ref_with_params(
+            "AsyncGenerator",
+            vec![
+                server_sent_event_type(ir_typeref_to_swc(ty, docs)),
+                ts_kw!(void),
+                ts_kw!(undefined),
+            ],
+        ),
+        ResponseContentType::Json | ResponseContentType::Negotiated => ir_typeref_to_swc(ty, docs),
     }
 }
 
@@ -585,13 +3521,20 @@ fn build_path_template(template: &[UrlPart]) -> String {
 }
 
 /// Build template literal quasis and expressions for a URL template.
+/// Path params are interpolated as `${encodeURIComponent(String(params.x))}`.
 /// If `suffix_var` is provided, appends `?${suffix_var}` to the template.
+/// If `base_url` is set, prefixes the template with `${API_BASE_URL}`.
 fn build_tpl_parts_with_suffix(
     template: &[UrlPart],
     suffix_var: Option<&str>,
+    base_url: bool,
 ) -> (Vec<String>, Vec<Expr>) {
     let mut quasis = Vec::new();
     let mut exprs = Vec::new();
+    if base_url {
+        quasis.push(String::new());
+        exprs.push(ident_expr(BASE_URL_CONST));
+    }
     let mut current_static = String::new();
 
     for part in template {
@@ -602,7 +3545,13 @@ fn build_tpl_parts_with_suffix(
             UrlPart::Param(name) => {
                 quasis.push(current_static.clone());
                 current_static.clear();
-                exprs.push(build_param_access_expr("params", name, true));
+                exprs.push(call(
+                    ident_expr("encodeURIComponent"),
+                    vec![call(
+                        ident_expr("String"),
+                        vec![build_param_access_expr("params", name, true)],
+                    )],
+                ));
             }
         }
     }
@@ -636,57 +3585,86 @@ fn build_param_access_expr(obj: &str, prop: &str, required: bool) -> Expr {
 }
 
 /// Generate a query key function.
-fn codegen_query_key_function(qk: &QueryKeyIR) -> ModuleItem {
+fn codegen_query_key_function(qk: &QueryKeyIR, docs: &DocComments) -> ModuleItem {
     let base_key = str_lit(&qk.base_key);
 
-    let (params, body_expr) = if let Some(params_type) = &qk.params_type {
+    let (params, body_expr, key_type) = if let Some(params_type) = &qk.params_type {
         let params = vec![pat_ident(
             "params",
-            Some(ir_typeref_to_swc(params_type)),
+            Some(ir_typeref_to_swc(params_type, docs)),
             true,
         )];
         let body_expr = as_const(array_lit(vec![base_key, ident_expr("params")]));
-        (params, body_expr)
+        let key_type = ts_readonly_tuple(vec![
+            ts_lit_str(&qk.base_key),
+            ts_union(vec![
+                ir_typeref_to_swc(params_type, docs),
+                ts_kw!(undefined),
+            ]),
+        ]);
+        (params, body_expr, key_type)
     } else {
         let body_expr = as_const(array_lit(vec![base_key]));
-        (vec![], body_expr)
+        let key_type = ts_readonly_tuple(vec![ts_lit_str(&qk.base_key)]);
+        (vec![], body_expr, key_type)
     };
 
     export_const_arrow(
         &qk.fn_name,
         params,
-        None,
+        Some(key_type),
         block(vec![return_stmt(Some(body_expr))]),
         false,
     )
 }
 
-/// Generate a React Query hook.
-fn codegen_hook(hook: &HookIR) -> ModuleItem {
-    let response_swc_type = resolve_content_type(hook.response_content_type, &hook.response_type);
-    let is_void = is_void_type(&response_swc_type);
-
-    let wrapped_type: Box<TsType> = if is_void {
+/// Data type a hook's query or mutation resolves with.
+fn hook_data_type(hook: &HookIR, docs: &DocComments) -> Box<TsType> {
+    let response_swc_type =
+        resolve_content_type(hook.response_content_type, &hook.response_type, docs);
+    if is_void_type(&response_swc_type) {
         ts_kw!(void)
     } else if hook.response_has_void_status {
         ts_union(vec![data_wrapper_type(response_swc_type), ts_kw!(void)])
     } else {
         data_wrapper_type(response_swc_type)
-    };
+    }
+}
 
+/// Generate a React Query hook.
+fn codegen_hook(hook: &HookIR, docs: &DocComments) -> ModuleItem {
+    let wrapped_type = hook_data_type(hook, docs);
     match hook.kind {
-        HookKind::Query | HookKind::SuspenseQuery => codegen_query_hook(hook, wrapped_type),
-        HookKind::Mutation => codegen_mutation_hook(hook, wrapped_type),
+        HookKind::Query | HookKind::SuspenseQuery => codegen_query_hook(hook, wrapped_type, docs),
+        HookKind::Mutation => codegen_mutation_hook(hook, wrapped_type, docs),
+    }
+}
+
+/// `ApiFailure<ErrorBody>`, or bare `ApiFailure` (body `unknown`) when no error body is declared.
+fn api_error_type(error_ty: &TypeRef, docs: &DocComments) -> Box<TsType> {
+    if is_unknown(error_ty) {
+        ts_type_ref(API_FAILURE_TYPE)
+    } else {
+        ts_type_ref_with_params(API_FAILURE_TYPE, vec![ir_typeref_to_swc(error_ty, docs)])
     }
 }
 
-/// Build `Omit<OptionsType<Wrapped, ApiError, TData>, "queryKey" | "queryFn">`.
-fn omit_query_opts(options_type: &str, wrapped: &TsType) -> Box<TsType> {
+fn is_unknown(ty: &TypeRef) -> bool {
+    matches!(ty, TypeRef::Inline(t) if matches!(**t, IrTsType::Primitive(TsPrimitive::Unknown)))
+}
+
+/// Build `Omit<OptionsType<Wrapped, ApiFailure<E>, TData>, "queryKey" | "queryFn">`.
+fn omit_query_opts(
+    options_type: &str,
+    wrapped: &TsType,
+    error_ty: &TypeRef,
+    docs: &DocComments,
+) -> Box<TsType> {
     let opts = ts_type_ref_with_params(
         options_type,
         vec![
             Box::new(wrapped.clone()),
-            ts_type_ref("ApiError"),
+            api_error_type(error_ty, docs),
             ts_type_ref("TData"),
         ],
     );
@@ -699,7 +3677,7 @@ fn omit_query_opts(options_type: &str, wrapped: &TsType) -> Box<TsType> {
 /// Generate a query hook (useQuery or useSuspenseQuery).
 // Reason: template rendering is infallible for known-good templates
 #[allow(clippy::expect_used)]
-fn codegen_query_hook(hook: &HookIR, wrapped_type: Box<TsType>) -> ModuleItem {
+fn codegen_query_hook(hook: &HookIR, wrapped_type: Box<TsType>, docs: &DocComments) -> ModuleItem {
     let key_fn = hook
         .query_key_fn
         .as_ref()
@@ -716,11 +3694,11 @@ fn codegen_query_hook(hook: &HookIR, wrapped_type: Box<TsType>) -> ModuleItem {
     };
 
     let (options_param_type, body_stmt, options_optional) = if let Some(vars) = &hook.vars_type {
-        let vars_swc = ir_typeref_to_swc(vars);
+        let vars_swc = ir_typeref_to_swc(vars, docs);
         let params_prop = ts_property_sig("params", vars_swc, !hook.params_required);
         let query_prop = ts_property_sig(
             "query",
-            omit_query_opts(options_type_name, &wrapped_type),
+            omit_query_opts(options_type_name, &wrapped_type, &hook.error_type, docs),
             true,
         );
         let opts_type = ts_object_type(vec![params_prop, query_prop]);
@@ -754,7 +3732,7 @@ fn codegen_query_hook(hook: &HookIR, wrapped_type: Box<TsType>) -> ModuleItem {
     } else {
         let query_prop = ts_property_sig(
             "query",
-            omit_query_opts(options_type_name, &wrapped_type),
+            omit_query_opts(options_type_name, &wrapped_type, &hook.error_type, docs),
             true,
         );
         let opts_type = ts_object_type(vec![query_prop]);
@@ -789,12 +3767,76 @@ fn codegen_query_hook(hook: &HookIR, wrapped_type: Box<TsType>) -> ModuleItem {
     )
 }
 
+/// Generate `prefetchXxx(queryClient, params?, options?)` warming the cache of a query hook.
+///
+/// Uses the same key and fetch function as the hook, so a later render finds the data cached.
+// Reason: template rendering is infallible for known-good templates
+#[allow(clippy::expect_used)]
+fn codegen_prefetch(hook: &HookIR, docs: &DocComments) -> ModuleItem {
+    let key_fn = hook
+        .query_key_fn
+        .as_ref()
+        .expect("query_key_fn must be set for query hooks");
+    let options_type = ts_omit(
+        ts_type_ref_with_params(
+            "FetchQueryOptions",
+            vec![
+                hook_data_type(hook, docs),
+                api_error_type(&hook.error_type, docs),
+            ],
+        ),
+        ts_union(vec![ts_lit_str("queryKey"), ts_lit_str("queryFn")]),
+    );
+
+    let mut params = vec![param(
+        "queryClient",
+        Some(ts_type_ref("QueryClient")),
+        false,
+    )];
+    let args = if let Some(vars) = &hook.vars_type {
+        params.push(param(
+            "params",
+            Some(ir_typeref_to_swc(vars, docs)),
+            !hook.params_required,
+        ));
+        vec![ident_expr("params")]
+    } else {
+        vec![]
+    };
+    params.push(param("options", Some(options_type), true));
+
+    let prefetch_call = call(
+        member(ident_expr("queryClient"), "prefetchQuery"),
+        vec![obj_lit(vec![
+            kv_prop("queryKey", call(ident_expr(key_fn), args.clone())),
+            kv_prop(
+                "queryFn",
+                arrow_fn_expr(vec![], call(ident_expr(&hook.fetch_fn), args)),
+            ),
+            spread_prop(ident_expr("options")),
+        ])],
+    );
+
+    export_function(
+        &format!("prefetch{}", capitalize_first(&hook.fetch_fn)),
+        None,
+        params,
+        Some(ts_type_ref_with_params("Promise", vec![ts_kw!(void)])),
+        block(vec![return_stmt(Some(prefetch_call))]),
+        false,
+    )
+}
+
 /// Generate a mutation hook.
-fn codegen_mutation_hook(hook: &HookIR, wrapped_type: Box<TsType>) -> ModuleItem {
+fn codegen_mutation_hook(
+    hook: &HookIR,
+    wrapped_type: Box<TsType>,
+    docs: &DocComments,
+) -> ModuleItem {
     let vars_swc_type = hook
         .vars_type
         .as_ref()
-        .map_or_else(|| ts_kw!(void), ir_typeref_to_swc);
+        .map_or_else(|| ts_kw!(void), |ty| ir_typeref_to_swc(ty, docs));
 
     // Build mutation function expression
     let mutation_fn = if let Some(vars) = &hook.vars_type {
@@ -856,10 +3898,14 @@ fn codegen_mutation_hook(hook: &HookIR, wrapped_type: Box<TsType>) -> ModuleItem
         arrow_fn_expr(vec![], call(ident_expr(&hook.fetch_fn), vec![]))
     };
 
-    // { mutation?: UseMutationOptions<WrappedType, ApiError, VarsType> }
+    // { mutation?: UseMutationOptions<WrappedType, ApiFailure<E>, VarsType> }
     let mutation_opts = ts_type_ref_with_params(
         "UseMutationOptions",
-        vec![wrapped_type, ts_type_ref("ApiError"), vars_swc_type],
+        vec![
+            wrapped_type,
+            api_error_type(&hook.error_type, docs),
+            vars_swc_type,
+        ],
     );
     let options_type = ts_object_type(vec![ts_property_sig("mutation", mutation_opts, true)]);
 
@@ -932,11 +3978,12 @@ mod tests {
     #[test]
     fn test_codegen_query_key_no_params() {
         let qk = QueryKeyIR {
-            fn_name: "listItemsKey".into(),
+            fn_name: "listItemsQueryKey".into(),
+            legacy_fn_name: None,
             base_key: "/items".into(),
             params_type: None,
         };
-        let item = codegen_query_key_function(&qk);
+        let item = codegen_query_key_function(&qk, &DocComments::default());
 
         // Should be an export const arrow
         if let ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
@@ -947,7 +3994,7 @@ mod tests {
             assert_eq!(var_decl.kind, VarDeclKind::Const);
             let declarator = &var_decl.decls[0];
             if let Pat::Ident(bi) = &declarator.name {
-                assert_eq!(bi.id.sym.as_ref(), "listItemsKey");
+                assert_eq!(bi.id.sym.as_ref(), "listItemsQueryKey");
             }
             // Check it's an arrow function with no params
             if let Some(init) = &declarator.init {
@@ -965,11 +4012,12 @@ mod tests {
     #[test]
     fn test_codegen_query_key_with_params() {
         let qk = QueryKeyIR {
-            fn_name: "getItemKey".into(),
+            fn_name: "getItemQueryKey".into(),
+            legacy_fn_name: None,
             base_key: "/items/{id}".into(),
             params_type: Some(TypeRef::Named("GetItemParams".into())),
         };
-        let item = codegen_query_key_function(&qk);
+        let item = codegen_query_key_function(&qk, &DocComments::default());
 
         if let ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
             decl: Decl::Var(var_decl),
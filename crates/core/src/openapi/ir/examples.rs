@@ -0,0 +1,62 @@
+//! Typed example fixtures for component schemas.
+//!
+//! Emits a standalone module exporting one `example<Type>` constant per component
+//! schema, each holding the value built by [`crate::openapi::mock::example_for_schema`]
+//! and annotated with the generated type. Types are imported from the generated
+//! client module.
+
+use std::collections::HashMap;
+
+use swc_common::DUMMY_SP;
+use swc_ecma_ast::Module;
+
+use super::api::ApiIR;
+use super::builders::{export_const, import_named, json_lit, ts_type_ref};
+use super::normalize::component_type_names;
+use super::utils::capitalize_first;
+use crate::openapi::mock::example_for_schema;
+use crate::openapi::spec::OpenApiSpec;
+
+/// Generate the example fixtures module for `api`, importing types from `client_module`.
+///
+/// `export const exampleItem: Item = { ... };`
+pub fn codegen_examples_module(api: &ApiIR, spec: &OpenApiSpec, client_module: &str) -> Module {
+    let no_schemas = HashMap::new();
+    let schemas = spec
+        .components
+        .as_ref()
+        .and_then(|c| c.schemas.as_ref())
+        .unwrap_or(&no_schemas);
+    let type_names = component_type_names(schemas);
+
+    let mut names: Vec<_> = schemas.keys().collect();
+    names.sort();
+    let examples: Vec<_> = names
+        .into_iter()
+        .map(|name| {
+            let type_name = type_names.get(name).unwrap_or(name);
+            let value = example_for_schema(spec, &format!("#/components/schemas/{name}"));
+            (type_name.as_str(), value)
+        })
+        .filter(|(type_name, _)| api.types.iter().any(|td| td.name == *type_name))
+        .collect();
+
+    let mut body = Vec::new();
+    if !examples.is_empty() {
+        let imports = examples.iter().map(|(name, _)| (*name, None)).collect();
+        body.push(import_named(imports, client_module, true));
+    }
+    for (type_name, value) in &examples {
+        body.push(export_const(
+            &format!("example{}", capitalize_first(type_name)),
+            Some(ts_type_ref(type_name)),
+            json_lit(value),
+        ));
+    }
+
+    Module {
+        span: DUMMY_SP,
+        body,
+        shebang: None,
+    }
+}
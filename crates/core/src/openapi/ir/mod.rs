@@ -15,7 +15,9 @@
 //! - `types`: Type-only IR (TsType, TsProp, TsTypeDef, TypeRef, TsLiteral)
 //! - `api`: API-level IR (OperationIR, ParamsIR, FetchIR, HookIR)
 //! - `normalize`: OpenAPI spec -> API IR conversion
-//! - `codegen`: API IR -> swc_ecma_ast::Module
+//! - `codegen`: API IR -> swc_ecma_ast::Module (or one per file of the split client)
+//! - `msw`: API IR -> Mock Service Worker handlers module
+//! - `examples`: component schemas -> typed example fixtures module
 //! - `builders`: Ergonomic helpers for SWC AST construction
 //! - `utils`: Common utilities shared across modules
 
@@ -23,10 +25,14 @@ mod api;
 #[macro_use]
 pub mod builders;
 mod codegen;
+mod examples;
+mod msw;
 mod normalize;
 mod types;
 pub mod utils;
 
 // Re-export the main entry points
-pub use codegen::codegen_module;
-pub use normalize::normalize_spec;
+pub use codegen::{ModuleSection, codegen_items, codegen_module};
+pub use examples::codegen_examples_module;
+pub use msw::codegen_msw_module;
+pub use normalize::{check_required_properties, normalize_server, normalize_spec};
@@ -0,0 +1,195 @@
+//! Mock Service Worker handler generation from API IR.
+//!
+//! Emits a standalone module exporting `handlers`: one `http.<method>(path, resolver)`
+//! per operation, each answering with an example response synthesized by
+//! [`crate::openapi::mock`]. Types are imported from the generated client module.
+
+use swc_common::DUMMY_SP;
+use swc_ecma_ast::{Expr, Module};
+
+use super::api::{ApiIR, HttpMethod, OperationIR, ResponseContentType};
+use super::builders::{
+    DocComments, array_lit, arrow_fn_expr, call, export_const, ident_expr, import_named,
+    ir_typeref_to_swc, json_lit_with_bigints, kv_prop, kv_prop_str, member, new_expr, null_lit,
+    num_lit, obj_lit, str_lit, ts_as_expr,
+};
+use super::codegen::{STRINGIFY_BIGINT_JSON_FN, codegen_stringify_bigint_json};
+use crate::openapi::mock::{MockDirection, mock_value, success_response};
+use crate::openapi::spec::{MediaType, OpenApiSpec, Operation};
+
+/// Generate the MSW handlers module for `api`, importing types from `client_module`.
+pub fn codegen_msw_module(
+    api: &ApiIR,
+    spec: &OpenApiSpec,
+    client_module: &str,
+    docs: &DocComments,
+) -> Module {
+    let mut body = vec![import_named(
+        vec![("http", None), ("HttpResponse", None)],
+        "msw",
+        false,
+    )];
+
+    if !api.types.is_empty() {
+        let names = api
+            .types
+            .iter()
+            .map(|td| (td.name.as_str(), None))
+            .collect();
+        body.push(import_named(names, client_module, true));
+    }
+
+    // Mocks holding `bigint` values cannot go through `HttpResponse.json`
+    if api
+        .operations
+        .iter()
+        .any(|op| !op.fetch.bigint_paths.is_empty())
+    {
+        body.push(codegen_stringify_bigint_json());
+    }
+
+    let handlers = api
+        .operations
+        .iter()
+        .map(|op| {
+            let spec_op = spec
+                .paths
+                .get(&op.path)
+                .and_then(|item| match op.fetch.method {
+                    HttpMethod::Get => item.get.as_ref(),
+                    HttpMethod::Post => item.post.as_ref(),
+                    HttpMethod::Put => item.put.as_ref(),
+                    HttpMethod::Patch => item.patch.as_ref(),
+                    HttpMethod::Delete => item.delete.as_ref(),
+                });
+            codegen_handler(op, spec_op, spec, docs)
+        })
+        .collect();
+    body.push(export_const("handlers", None, array_lit(handlers)));
+
+    Module {
+        span: DUMMY_SP,
+        body,
+        shebang: None,
+    }
+}
+
+/// `http.get("*/items/:itemId", () => HttpResponse.json(example as Item, { status: 200 }))`
+///
+/// The example is cast rather than checked with `satisfies`: response mocks
+/// omit `writeOnly` properties, which the client types still declare.
+/// Examples holding `bigint` values are serialized with the client's bigint
+/// replacer and answered as `new HttpResponse(text, init)` instead.
+fn codegen_handler(
+    op: &OperationIR,
+    spec_op: Option<&Operation>,
+    spec: &OpenApiSpec,
+    docs: &DocComments,
+) -> Expr {
+    let components = spec.components.as_ref();
+    let (status, response) = spec_op
+        .and_then(success_response)
+        .map_or((200, None), |(code, response)| {
+            (code.parse::<u16>().unwrap_or(200), Some(response))
+        });
+    let init = obj_lit(vec![kv_prop("status", num_lit(f64::from(status)))]);
+
+    let content = response.and_then(|r| r.content.as_ref());
+    let media_type = |accept: fn(&str) -> bool| {
+        content.and_then(|c| {
+            let mut media_types: Vec<_> = c.iter().filter(|(mt, _)| accept(mt)).collect();
+            media_types.sort_by_key(|(mt, _)| *mt);
+            media_types.first().map(|(_, media)| *media)
+        })
+    };
+    let json = media_type(|mt| mt == "application/json" || mt.ends_with("+json"));
+    let text = media_type(|mt| mt.starts_with("text/"));
+    let mock = |media: &MediaType| {
+        media
+            .schema
+            .as_ref()
+            .map_or(serde_json::Value::Null, |schema| {
+                mock_value(schema, components, MockDirection::Response)
+            })
+    };
+
+    let response_expr = match (json, &op.fetch.response) {
+        (Some(media), response)
+            if media.schema.is_some()
+                && matches!(
+                    response.content_type,
+                    ResponseContentType::Json | ResponseContentType::Negotiated
+                ) =>
+        {
+            let bigint_paths = &op.fetch.bigint_paths;
+            let example = json_lit_with_bigints(&mock(media), bigint_paths);
+            let example = ts_as_expr(example, ir_typeref_to_swc(&response.ty, docs));
+            if bigint_paths.is_empty() {
+                call(
+                    member(ident_expr("HttpResponse"), "json"),
+                    vec![example, init],
+                )
+            } else {
+                let init = obj_lit(vec![
+                    kv_prop("status", num_lit(f64::from(status))),
+                    kv_prop(
+                        "headers",
+                        obj_lit(vec![kv_prop_str(
+                            "Content-Type",
+                            str_lit("application/json"),
+                        )]),
+                    ),
+                ]);
+                new_expr(
+                    ident_expr("HttpResponse"),
+                    vec![
+                        call(ident_expr(STRINGIFY_BIGINT_JSON_FN), vec![example]),
+                        init,
+                    ],
+                )
+            }
+        }
+        _ => match text {
+            Some(media) => {
+                let text = match mock(media) {
+                    serde_json::Value::String(s) => s,
+                    serde_json::Value::Null => String::new(),
+                    other => other.to_string(),
+                };
+                call(
+                    member(ident_expr("HttpResponse"), "text"),
+                    vec![str_lit(&text), init],
+                )
+            }
+            None => new_expr(ident_expr("HttpResponse"), vec![null_lit(), init]),
+        },
+    };
+
+    call(
+        member(
+            ident_expr("http"),
+            &op.fetch.method.as_str().to_ascii_lowercase(),
+        ),
+        vec![
+            str_lit(&msw_path(&op.path)),
+            arrow_fn_expr(vec![], response_expr),
+        ],
+    )
+}
+
+/// Convert an OpenAPI path template to an MSW path matching any origin.
+///
+/// `/items/{itemId}` becomes `*/items/:itemId`; parameter names that are not
+/// valid MSW identifiers match as a wildcard segment instead.
+fn msw_path(path: &str) -> String {
+    let segments = path.split('/').map(|segment| {
+        match segment.strip_prefix('{').and_then(|s| s.strip_suffix('}')) {
+            Some(name) if name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') => {
+                format!(":{name}")
+            }
+            Some(_) => "*".to_string(),
+            None => segment.to_string(),
+        }
+    });
+    format!("*{}", segments.collect::<Vec<_>>().join("/"))
+}
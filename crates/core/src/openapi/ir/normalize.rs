@@ -8,16 +8,21 @@
 use std::collections::HashMap;
 
 use crate::openapi::spec::{
-    AdditionalProperties, Discriminator, EnumValue, OpenApiSpec, Operation, Parameter, Schema,
-    SchemaType,
+    AdditionalProperties, Components, Discriminator, Encoding, EnumValue, OpenApiSpec, Operation,
+    Parameter, PathItem, Schema, SchemaType,
 };
+use crate::openapi::warnings::{GenerateWarning, WarningKind, escape_pointer};
 
 use super::api::{
-    ApiIR, BodyContentType, BodyIR, FetchArgIR, FetchIR, HookIR, HookKind, HttpMethod, OperationIR,
-    OperationKind, ParamIR, ParamLocation, ParamsIR, QueryKeyIR, ResponseContentType, ResponseIR,
-    UrlIR, UrlPart,
+    ApiIR, BodyContentType, BodyDefaultIR, BodyIR, FetchArgIR, FetchIR, FormFieldIR, HookIR,
+    HookKind, HttpMethod, MatchDispatchIR, OperationIR, OperationKind, ParamIR, ParamLocation,
+    ParamsIR, QueryKeyIR, ResponseContentType, ResponseIR, ResponseVariantIR, ServerIR,
+    ServerVariableIR, TypeGuardIR, UnionMatcherIR, UrlIR, UrlPart, WebhookIR, match_kinds,
+};
+use super::types::{
+    StringFormat, TsConstraints, TsLiteral, TsPrimitive, TsProp, TsType, TsTypeDef, TypeDefKind,
+    TypeRef,
 };
-use super::types::{TsLiteral, TsPrimitive, TsProp, TsType, TsTypeDef, TypeDefKind, TypeRef};
 use super::utils::{
     capitalize_first, enum_value_to_key, enum_value_to_literal, make_string_record,
     make_unknown_record, sanitize_ts_identifier, to_snake_case,
@@ -29,11 +34,12 @@ fn process_operation(
     method: HttpMethod,
     op: Option<&Operation>,
     path_params: Option<&Vec<Parameter>>,
+    components: Option<&Components>,
     operations: &mut Vec<OperationIR>,
     operation_names: &mut std::collections::HashSet<String>,
 ) -> Result<bool, String> {
     if let Some(op) = op {
-        let op_ir = normalize_operation(path, method, op, path_params)?;
+        let op_ir = normalize_operation(path, method, op, path_params, components)?;
 
         // Check for operationId collision
         if !operation_names.insert(op_ir.name.clone()) {
@@ -57,6 +63,8 @@ pub fn normalize_spec(spec: &OpenApiSpec) -> Result<ApiIR, String> {
     let mut has_mutations = false;
     let mut operation_names = std::collections::HashSet::new();
 
+    check_all_of_conflicts(spec)?;
+
     // Sort paths for deterministic output
     let mut paths: Vec<_> = spec.paths.iter().collect();
     paths.sort_by_key(|(path, _)| *path);
@@ -70,6 +78,7 @@ pub fn normalize_spec(spec: &OpenApiSpec) -> Result<ApiIR, String> {
             HttpMethod::Get,
             item.get.as_ref(),
             path_params,
+            spec.components.as_ref(),
             &mut operations,
             &mut operation_names,
         )? {
@@ -88,6 +97,7 @@ pub fn normalize_spec(spec: &OpenApiSpec) -> Result<ApiIR, String> {
                 method,
                 op,
                 path_params,
+                spec.components.as_ref(),
                 &mut operations,
                 &mut operation_names,
             )? {
@@ -96,23 +106,99 @@ pub fn normalize_spec(spec: &OpenApiSpec) -> Result<ApiIR, String> {
         }
     }
 
-    // Normalize component schemas
-    let types = if let Some(components) = &spec.components {
-        if let Some(schemas) = &components.schemas {
-            normalize_schemas(schemas)?
-        } else {
-            Vec::new()
+    // Drop legacy key aliases that would redeclare another operation's key function
+    let key_names: std::collections::HashSet<_> = operations
+        .iter()
+        .filter_map(|op| Some(op.query_key.as_ref()?.fn_name.clone()))
+        .collect();
+    for qk in operations.iter_mut().filter_map(|op| op.query_key.as_mut()) {
+        if qk
+            .legacy_fn_name
+            .as_ref()
+            .is_some_and(|n| key_names.contains(n))
+        {
+            qk.legacy_fn_name = None;
         }
+    }
+
+    // Normalize component schemas
+    let schemas = spec
+        .components
+        .as_ref()
+        .and_then(|c| c.schemas.as_ref())
+        .map(with_inferred_discriminators);
+    let schemas = schemas.as_ref();
+    let type_names = schemas.map(component_type_names).unwrap_or_default();
+    let (types, type_guards, union_matchers) = if let Some(schemas) = schemas {
+        let types = normalize_schemas(schemas)?;
+        let union_matchers = normalize_union_matchers(schemas, &types, &type_names);
+        let type_guards = normalize_type_guards(schemas, &type_names);
+        (types, type_guards, union_matchers)
     } else {
-        Vec::new()
+        (Vec::new(), Vec::new(), Vec::new())
+    };
+    let webhooks = match &spec.webhooks {
+        Some(webhooks) => normalize_webhooks(webhooks, &types, &type_names)?,
+        None => Vec::new(),
     };
 
-    Ok(ApiIR {
+    let mut ir = ApiIR {
         operations,
         types,
+        type_guards,
+        union_matchers,
+        webhooks,
+        body_aliases: Vec::new(),
+        brands: Vec::new(),
+        server: None,
         has_queries,
         has_mutations,
-    })
+    };
+    if !type_names.is_empty() {
+        ir.rename_types(&type_names);
+    }
+    Ok(ir)
+}
+
+/// Map component schema names that are not valid TypeScript identifiers to
+/// unique identifiers.
+///
+/// Names that are already valid keep their spelling and win over sanitized
+/// ones; the rest get a numeric suffix when sanitizing makes them collide
+/// (`Foo-Bar` and `Foo.Bar` become `FooBar` and `FooBar2`).
+pub(super) fn component_type_names(schemas: &HashMap<String, Schema>) -> HashMap<String, String> {
+    let mut names: Vec<_> = schemas.keys().collect();
+    names.sort();
+
+    let mut taken: std::collections::HashSet<String> = names
+        .iter()
+        .filter(|name| sanitize_ts_identifier(name) == **name)
+        .map(|name| (*name).clone())
+        .collect();
+    let mut renamed = HashMap::new();
+    for name in names {
+        let base = sanitize_ts_identifier(name);
+        if base == *name {
+            continue;
+        }
+        let mut candidate = base.clone();
+        let mut suffix = 2;
+        while taken.contains(&candidate) {
+            candidate = format!("{base}{suffix}");
+            suffix += 1;
+        }
+        taken.insert(candidate.clone());
+        renamed.insert(name.clone(), candidate);
+    }
+    renamed
+}
+
+/// TypeScript name of a component schema (see [`component_type_names`])
+fn type_name_of(type_names: &HashMap<String, String>, name: &str) -> String {
+    type_names
+        .get(name)
+        .cloned()
+        .unwrap_or_else(|| name.to_string())
 }
 
 /// Normalize component schemas into type definitions
@@ -134,6 +220,178 @@ fn normalize_schemas(schemas: &HashMap<String, Schema>) -> Result<Vec<TsTypeDef>
     Ok(type_defs)
 }
 
+/// Type the JSON request body of each webhook as a `<Name>WebhookPayload` definition.
+///
+/// The body is taken from the first of `post`, `put`, `patch`, `get` and `delete`
+/// that the webhook declares; webhooks without a JSON body get an `unknown` payload.
+/// Payload names that clash with a component type get a trailing `_`.
+fn normalize_webhooks(
+    webhooks: &HashMap<String, PathItem>,
+    types: &[TsTypeDef],
+    type_names: &HashMap<String, String>,
+) -> Result<Vec<WebhookIR>, String> {
+    let mut names: Vec<_> = webhooks.keys().collect();
+    names.sort();
+
+    let mut result: Vec<WebhookIR> = Vec::new();
+    for name in names {
+        let Some(item) = webhooks.get(name) else {
+            continue;
+        };
+        let schema = [&item.post, &item.put, &item.patch, &item.get, &item.delete]
+            .into_iter()
+            .flatten()
+            .next()
+            .and_then(|op| op.request_body.as_ref()?.content.as_ref())
+            .and_then(|content| content.get("application/json")?.schema.as_ref());
+
+        let mut type_name = format!(
+            "{}WebhookPayload",
+            capitalize_first(&sanitize_ts_identifier(name))
+        );
+        while types
+            .iter()
+            .any(|td| type_name_of(type_names, &td.name) == type_name)
+            || result.iter().any(|w| w.payload.name == type_name)
+        {
+            type_name.push('_');
+        }
+
+        let payload = match schema {
+            Some(schema) => normalize_schema_to_typedef(&type_name, schema)?,
+            None => TsTypeDef {
+                name: type_name,
+                kind: TypeDefKind::TypeAlias {
+                    ty: TsType::Primitive(TsPrimitive::Unknown),
+                },
+            },
+        };
+        result.push(WebhookIR {
+            name: name.clone(),
+            payload,
+        });
+    }
+
+    Ok(result)
+}
+
+/// Build one type guard per discriminator mapping entry of `oneOf` component unions.
+///
+/// Unions without a discriminator mapping get no guards.
+fn normalize_type_guards(
+    schemas: &HashMap<String, Schema>,
+    type_names: &HashMap<String, String>,
+) -> Vec<TypeGuardIR> {
+    let mut names: Vec<_> = schemas.keys().collect();
+    names.sort();
+
+    let mut guards: Vec<TypeGuardIR> = Vec::new();
+    for name in names {
+        let Some(schema) = schemas.get(name) else {
+            continue;
+        };
+        if schema.one_of.is_none() || schema.any_of.is_some() || schema.properties.is_some() {
+            continue;
+        }
+        let Some(disc) = &schema.discriminator else {
+            continue;
+        };
+        let Some(mapping) = &disc.mapping else {
+            continue;
+        };
+
+        let mut entries: Vec<_> = mapping.iter().collect();
+        entries.sort();
+        for (value, ref_path) in entries {
+            let target = type_name_of(type_names, &ref_to_type_name(ref_path));
+            let fn_name = format!("is{}", capitalize_first(&sanitize_ts_identifier(&target)));
+            // Two unions mapping to the same member would clash; keep the first
+            if guards.iter().any(|g| g.fn_name == fn_name) {
+                continue;
+            }
+            guards.push(TypeGuardIR {
+                fn_name,
+                union_type: type_name_of(type_names, name),
+                property: disc.property_name.clone(),
+                value: value.clone(),
+            });
+        }
+    }
+
+    guards
+}
+
+/// Build one exhaustive matcher per `oneOf`/`anyOf` component union.
+///
+/// Discriminated `oneOf` unions dispatch on the discriminator and need a value
+/// for every member. Other unions dispatch on the runtime kind of the value and
+/// need at least two kinds (e.g. `string` and `object`); unions of several
+/// objects without a discriminator get no matcher.
+fn normalize_union_matchers(
+    schemas: &HashMap<String, Schema>,
+    types: &[TsTypeDef],
+    type_names: &HashMap<String, String>,
+) -> Vec<UnionMatcherIR> {
+    let mut names: Vec<_> = schemas.keys().collect();
+    names.sort();
+
+    let mut matchers = Vec::new();
+    for name in names {
+        let Some(schema) = schemas.get(name) else {
+            continue;
+        };
+        let Some(members) = schema.one_of.as_ref().or(schema.any_of.as_ref()) else {
+            continue;
+        };
+        // Only bare unions normalize to a union type alias
+        if schema.properties.is_some()
+            || schema.ref_path.is_some()
+            || schema.const_value.is_some()
+            || schema.all_of.is_some()
+            || schema.enum_values.is_some()
+        {
+            continue;
+        }
+
+        let dispatch = if let (Some(disc), None) = (&schema.discriminator, &schema.any_of) {
+            if schema.nullable == Some(true) {
+                continue;
+            }
+            let Some(mut values) = members
+                .iter()
+                .map(|member| discriminator_value(member, disc))
+                .collect::<Option<Vec<_>>>()
+            else {
+                continue;
+            };
+            let mut seen = std::collections::HashSet::new();
+            values.retain(|v| seen.insert(v.clone()));
+            MatchDispatchIR::Discriminator {
+                property: disc.property_name.clone(),
+                values,
+            }
+        } else {
+            match match_kinds(
+                &TsType::Ref(name.clone()),
+                types,
+                &mut std::collections::HashSet::new(),
+            ) {
+                Some(kinds) if kinds.len() >= 2 => MatchDispatchIR::Kind(kinds),
+                _ => continue,
+            }
+        };
+
+        let union_type = type_name_of(type_names, name);
+        matchers.push(UnionMatcherIR {
+            fn_name: format!("match{}", capitalize_first(&union_type)),
+            union_type,
+            dispatch,
+        });
+    }
+
+    matchers
+}
+
 /// Convert a schema to a type definition
 fn normalize_schema_to_typedef(name: &str, schema: &Schema) -> Result<TsTypeDef, String> {
     // Check for enum
@@ -146,9 +404,15 @@ fn normalize_schema_to_typedef(name: &str, schema: &Schema) -> Result<TsTypeDef,
         });
     }
 
-    // Check for plain object (interface candidate)
+    // Check for plain object (interface candidate); closed objects stay interfaces.
+    // Properties next to an `allOf` extend it, so those schemas become intersections.
     if schema.properties.is_some()
-        && schema.additional_properties.is_none()
+        && schema.all_of.is_none()
+        && matches!(
+            schema.additional_properties,
+            None | Some(AdditionalProperties::Bool(false))
+        )
+        && schema.pattern_properties.is_none()
         && let Some(properties) = &schema.properties
     {
         let props = normalize_properties(properties, schema.required.as_ref())?;
@@ -196,14 +460,63 @@ fn normalize_properties(
             name: name.clone(),
             ty,
             optional: !required_set.contains(name),
+            constraints: schema_constraints(schema),
+            read_only: schema.read_only == Some(true),
+            readonly: false,
         });
     }
 
     Ok(props)
 }
 
+/// Validation keywords of a property schema.
+///
+/// A nullable `anyOf`/`oneOf` (e.g. FastAPI's `Optional[constr(...)]`) takes the
+/// keywords of its single non-null member.
+fn schema_constraints(schema: &Schema) -> TsConstraints {
+    let constraints = TsConstraints {
+        min_length: schema.min_length,
+        max_length: schema.max_length,
+        pattern: schema.pattern.clone(),
+        minimum: schema.minimum,
+        maximum: schema.maximum,
+    };
+    if !constraints.is_empty() {
+        return constraints;
+    }
+
+    let is_null =
+        |s: &&Schema| matches!(&s.schema_type, Some(SchemaType::Single(t)) if t == "null");
+    let mut members = schema
+        .any_of
+        .iter()
+        .chain(schema.one_of.iter())
+        .flatten()
+        .filter(|s| !is_null(s));
+    match (members.next(), members.next()) {
+        (Some(member), None) => schema_constraints(member),
+        _ => constraints,
+    }
+}
+
 /// Convert a Schema to TsType
+///
+/// Nullability is applied per schema, so `array<string | null>` becomes
+/// `(string | null)[]` while a nullable `array<string>` becomes `string[] | null`.
 pub fn schema_to_ts_type(schema: &Schema) -> Result<TsType, String> {
+    let ty = schema_to_non_null_ts_type(schema)?;
+    // OpenAPI 3.0 `nullable: true` (3.1 expresses this via `type` arrays / anyOf)
+    if schema.nullable == Some(true) {
+        return Ok(flatten_union(vec![
+            ty,
+            TsType::Primitive(TsPrimitive::Null),
+        ]));
+    }
+    Ok(ty)
+}
+
+/// Convert a Schema to TsType, ignoring the OpenAPI 3.0 `nullable` flag.
+fn schema_to_non_null_ts_type(schema: &Schema) -> Result<TsType, String> {
     // Handle $ref first
     if let Some(ref_path) = &schema.ref_path {
         return Ok(TsType::Ref(ref_to_type_name(ref_path)));
@@ -214,9 +527,14 @@ pub fn schema_to_ts_type(schema: &Schema) -> Result<TsType, String> {
         return Ok(json_value_to_ts_type(const_value));
     }
 
-    // Handle allOf (intersection)
+    // Handle allOf (intersection), including object keywords declared next to it
     if let Some(all_of) = &schema.all_of {
-        return normalize_intersection(all_of);
+        let siblings = if has_object_keywords(schema) {
+            Some(normalize_object_type(schema)?)
+        } else {
+            None
+        };
+        return normalize_intersection(all_of, siblings);
     }
 
     // Handle anyOf (union, often nullable)
@@ -233,31 +551,21 @@ pub fn schema_to_ts_type(schema: &Schema) -> Result<TsType, String> {
     match &schema.schema_type {
         Some(SchemaType::Single(t)) => schema_type_to_ts(t, schema),
         Some(SchemaType::Multiple(types)) => {
-            let non_null: Vec<_> = types.iter().filter(|t| *t != "null").collect();
-            if non_null.len() == 1 {
-                let base_type = schema_type_to_ts(non_null[0], schema)?;
-                if types.contains(&"null".to_string()) {
-                    Ok(TsType::Union(vec![
-                        base_type,
-                        TsType::Primitive(TsPrimitive::Null),
-                    ]))
-                } else {
-                    Ok(base_type)
-                }
-            } else {
-                let mut ts_types: Vec<_> = non_null
-                    .iter()
-                    .map(|t| schema_type_to_ts(t, schema))
-                    .collect::<Result<Vec<_>, _>>()?;
-                if types.contains(&"null".to_string()) {
-                    ts_types.push(TsType::Primitive(TsPrimitive::Null));
-                }
-                Ok(TsType::Union(ts_types))
+            let mut ts_types: Vec<_> = types
+                .iter()
+                .filter(|t| *t != "null")
+                .map(|t| schema_type_to_ts(t, schema))
+                .collect::<Result<Vec<_>, _>>()?;
+            if types.iter().any(|t| t == "null") {
+                ts_types.push(TsType::Primitive(TsPrimitive::Null));
             }
+            Ok(flatten_union(ts_types))
         }
         None => {
-            // No type specified - check for additionalProperties or default to unknown
-            if schema.additional_properties.is_some() {
+            // No type specified - check for patternProperties/additionalProperties or default to unknown
+            if schema.pattern_properties.is_some() {
+                normalize_object_type(schema)
+            } else if schema.additional_properties.is_some() {
                 normalize_additional_properties(schema)
             } else {
                 Ok(TsType::Primitive(TsPrimitive::Unknown))
@@ -283,12 +591,16 @@ fn json_value_to_ts_type(value: &serde_json::Value) -> TsType {
     }
 }
 
-/// Normalize intersection type (allOf)
-fn normalize_intersection(schemas: &[Schema]) -> Result<TsType, String> {
+/// Normalize intersection type (allOf), with `siblings` typing the object keywords
+/// next to the `allOf` as one more member.
+///
+/// A single member is returned as-is, so `allOf: [{ $ref }]` stays a plain reference.
+fn normalize_intersection(schemas: &[Schema], siblings: Option<TsType>) -> Result<TsType, String> {
     let mut types: Vec<_> = schemas
         .iter()
         .map(schema_to_ts_type)
         .collect::<Result<Vec<_>, _>>()?;
+    types.extend(siblings);
 
     if types.is_empty() {
         return Ok(TsType::Primitive(TsPrimitive::Unknown));
@@ -302,6 +614,417 @@ fn normalize_intersection(schemas: &[Schema]) -> Result<TsType, String> {
     Ok(TsType::Intersection(types))
 }
 
+/// Whether a schema declares object members of its own (`properties`,
+/// `patternProperties` or an open `additionalProperties`).
+fn has_object_keywords(schema: &Schema) -> bool {
+    schema.properties.is_some()
+        || schema.pattern_properties.is_some()
+        || !matches!(
+            schema.additional_properties,
+            None | Some(AdditionalProperties::Bool(false))
+        )
+}
+
+/// Resolve the first `servers` entry into a URL template with typed variables.
+///
+/// Returns `Ok(None)` when the spec declares no servers. Every `{variable}` in
+/// the URL must be declared with a `default`, and the default must be one of
+/// its `enum` values when those are given.
+pub fn normalize_server(spec: &OpenApiSpec) -> Result<Option<ServerIR>, String> {
+    let Some(server) = spec.servers.as_ref().and_then(|servers| servers.first()) else {
+        return Ok(None);
+    };
+    let url = server.url.trim_end_matches('/');
+
+    let mut template = Vec::new();
+    let mut rest = url;
+    while let Some(start) = rest.find('{') {
+        let end = rest[start..]
+            .find('}')
+            .map(|i| start + i)
+            .ok_or_else(|| format!("Server URL '{url}' has an unclosed '{{' variable"))?;
+        if start > 0 {
+            template.push(UrlPart::Static(rest[..start].to_string()));
+        }
+        template.push(UrlPart::Param(rest[start + 1..end].to_string()));
+        rest = &rest[end + 1..];
+    }
+    if !rest.is_empty() {
+        template.push(UrlPart::Static(rest.to_string()));
+    }
+
+    let declared = server.variables.as_ref();
+    let mut variables = Vec::new();
+    for part in &template {
+        let UrlPart::Param(name) = part else {
+            continue;
+        };
+        if variables.iter().any(|v: &ServerVariableIR| &v.name == name) {
+            continue;
+        }
+        let variable = declared.and_then(|vars| vars.get(name)).ok_or_else(|| {
+            format!(
+                "Server URL '{url}' uses variable '{name}', which is not declared in `variables`"
+            )
+        })?;
+        let default = variable.default.clone().ok_or_else(|| {
+            format!("Server variable '{name}' in '{url}' is missing its required `default` value")
+        })?;
+        let enum_values = variable.enum_values.clone().unwrap_or_default();
+        if !enum_values.is_empty() && !enum_values.contains(&default) {
+            return Err(format!(
+                "Server variable '{name}' in '{url}' has default '{default}', which is not one of its `enum` values"
+            ));
+        }
+        variables.push(ServerVariableIR {
+            name: name.clone(),
+            default,
+            enum_values,
+        });
+    }
+    variables.sort_by(|a, b| a.name.cmp(&b.name));
+
+    Ok(Some(ServerIR {
+        template,
+        variables,
+    }))
+}
+
+/// Reject `allOf` compositions whose branches declare the same property with
+/// incompatible types, which would otherwise silently intersect to `never`.
+fn check_all_of_conflicts(spec: &OpenApiSpec) -> Result<(), String> {
+    let components = spec.components.as_ref();
+
+    if let Some(schemas) = components.and_then(|c| c.schemas.as_ref()) {
+        let mut names: Vec<_> = schemas.keys().collect();
+        names.sort();
+        for name in names {
+            if let Some(schema) = schemas.get(name) {
+                check_schema_all_of(schema, &format!("schema '{name}'"), components)?;
+            }
+        }
+    }
+
+    let mut paths: Vec<_> = spec.paths.iter().collect();
+    paths.sort_by_key(|(path, _)| *path);
+    for (path, item) in paths {
+        let path_context = format!("path '{path}'");
+        for param in item.parameters.iter().flatten() {
+            if let Some(schema) = &param.schema {
+                check_schema_all_of(schema, &path_context, components)?;
+            }
+        }
+
+        for (method, op) in [
+            (HttpMethod::Get, item.get.as_ref()),
+            (HttpMethod::Post, item.post.as_ref()),
+            (HttpMethod::Put, item.put.as_ref()),
+            (HttpMethod::Patch, item.patch.as_ref()),
+            (HttpMethod::Delete, item.delete.as_ref()),
+        ] {
+            let Some(op) = op else {
+                continue;
+            };
+            let context = op.operation_id.as_ref().map_or_else(
+                || format!("operation '{} {path}'", method.as_str()),
+                |id| format!("operation '{id}'"),
+            );
+
+            let param_schemas = op
+                .parameters
+                .iter()
+                .flatten()
+                .filter_map(|p| p.schema.as_ref());
+            let mut responses: Vec<_> = op.responses.iter().collect();
+            responses.sort_by_key(|(status, _)| *status);
+            let contents = op
+                .request_body
+                .iter()
+                .filter_map(|body| body.content.as_ref())
+                .chain(
+                    responses
+                        .into_iter()
+                        .filter_map(|(_, r)| r.content.as_ref()),
+                );
+            let media_schemas = contents.flat_map(|content| {
+                let mut media_types: Vec<_> = content.iter().collect();
+                media_types.sort_by_key(|(media_type, _)| *media_type);
+                media_types
+                    .into_iter()
+                    .filter_map(|(_, mt)| mt.schema.as_ref())
+            });
+
+            for schema in param_schemas.chain(media_schemas) {
+                check_schema_all_of(schema, &context, components)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Find `required` entries of component schemas that name no property of their schema.
+///
+/// Properties contributed by `allOf` (and `anyOf`/`oneOf`) branches count, and
+/// schemas allowing additional or pattern properties are skipped, since a required
+/// name may legitimately be one of those.
+pub fn check_required_properties(spec: &OpenApiSpec) -> Vec<GenerateWarning> {
+    let components = spec.components.as_ref();
+    let mut warnings = Vec::new();
+
+    if let Some(schemas) = components.and_then(|c| c.schemas.as_ref()) {
+        let mut names: Vec<_> = schemas.keys().collect();
+        names.sort();
+        for name in names {
+            if let Some(schema) = schemas.get(name) {
+                let pointer = format!("/components/schemas/{}", escape_pointer(name));
+                check_schema_required(schema, name, &pointer, components, &mut warnings);
+            }
+        }
+    }
+    warnings
+}
+
+/// Check `required` of `schema` and its subschemas (`$ref`s are checked at their definition).
+fn check_schema_required(
+    schema: &Schema,
+    name: &str,
+    pointer: &str,
+    components: Option<&Components>,
+    warnings: &mut Vec<GenerateWarning>,
+) {
+    let open = schema.pattern_properties.is_some()
+        || matches!(
+            schema.additional_properties,
+            Some(AdditionalProperties::Bool(true) | AdditionalProperties::Schema(_))
+        );
+    if let Some(required) = schema.required.as_ref().filter(|_| !open) {
+        let mut visited = Vec::new();
+        let mut props = Vec::new();
+        for branch in schema
+            .all_of
+            .iter()
+            .chain(&schema.any_of)
+            .chain(&schema.one_of)
+            .flatten()
+        {
+            collect_all_of_properties(branch, components, &mut visited, &mut props);
+        }
+        let declared = |prop: &String| {
+            schema
+                .properties
+                .as_ref()
+                .is_some_and(|p| p.contains_key(prop))
+                || props.iter().any(|(other, _)| *other == prop)
+        };
+        for prop in required.iter().filter(|&prop| !declared(prop)) {
+            warnings.push(GenerateWarning {
+                kind: WarningKind::MissingRequiredProperty,
+                location: pointer.to_string(),
+                message: format!(
+                    "schema '{name}' requires property '{prop}', which is not declared in its properties"
+                ),
+            });
+        }
+    }
+
+    let mut properties: Vec<_> = schema.properties.iter().flatten().collect();
+    properties.sort_by_key(|(prop, _)| *prop);
+    for (prop, child) in properties {
+        let child_pointer = format!("{pointer}/properties/{}", escape_pointer(prop));
+        check_schema_required(child, name, &child_pointer, components, warnings);
+    }
+    if let Some(items) = &schema.items {
+        check_schema_required(
+            items,
+            name,
+            &format!("{pointer}/items"),
+            components,
+            warnings,
+        );
+    }
+    if let Some(AdditionalProperties::Schema(inner)) = &schema.additional_properties {
+        let child_pointer = format!("{pointer}/additionalProperties");
+        check_schema_required(inner, name, &child_pointer, components, warnings);
+    }
+    for (key, branches) in [
+        ("allOf", &schema.all_of),
+        ("anyOf", &schema.any_of),
+        ("oneOf", &schema.one_of),
+    ] {
+        for (index, child) in branches.iter().flatten().enumerate() {
+            let child_pointer = format!("{pointer}/{key}/{index}");
+            check_schema_required(child, name, &child_pointer, components, warnings);
+        }
+    }
+}
+
+/// Check every `allOf` in `schema` and its subschemas (`$ref`s are checked at their definition).
+fn check_schema_all_of(
+    schema: &Schema,
+    context: &str,
+    components: Option<&Components>,
+) -> Result<(), String> {
+    if let Some(all_of) = &schema.all_of {
+        let mut visited = Vec::new();
+        let mut props = Vec::new();
+        for branch in all_of {
+            collect_all_of_properties(branch, components, &mut visited, &mut props);
+        }
+        // Properties declared next to the allOf are merged into it too
+        if let Some(properties) = &schema.properties {
+            let mut own: Vec<_> = properties.iter().collect();
+            own.sort_by_key(|(name, _)| *name);
+            props.extend(own);
+        }
+
+        for (i, (name, prop)) in props.iter().enumerate() {
+            for (other_name, other) in &props[..i] {
+                if name != other_name {
+                    continue;
+                }
+                let (ty, other_ty) = (schema_to_ts_type(prop)?, schema_to_ts_type(other)?);
+                if !is_assignable(&ty, &other_ty) && !is_assignable(&other_ty, &ty) {
+                    return Err(format!(
+                        "Conflicting types for property '{name}' in allOf of {context}: '{}' vs '{}'. \
+                         allOf branches must declare compatible property types.",
+                        type_label(&other_ty),
+                        type_label(&ty)
+                    ));
+                }
+            }
+        }
+    }
+
+    let children = schema
+        .properties
+        .iter()
+        .flat_map(HashMap::values)
+        .chain(schema.pattern_properties.iter().flat_map(HashMap::values))
+        .chain(schema.items.as_deref())
+        .chain(match &schema.additional_properties {
+            Some(AdditionalProperties::Schema(inner)) => Some(inner.as_ref()),
+            _ => None,
+        })
+        .chain(schema.all_of.iter().flatten())
+        .chain(schema.any_of.iter().flatten())
+        .chain(schema.one_of.iter().flatten());
+    for child in children {
+        check_schema_all_of(child, context, components)?;
+    }
+    Ok(())
+}
+
+/// Collect the properties an `allOf` branch contributes, following `$ref`s and nested `allOf`s.
+fn collect_all_of_properties<'a>(
+    schema: &'a Schema,
+    components: Option<&'a Components>,
+    visited: &mut Vec<&'a str>,
+    props: &mut Vec<(&'a String, &'a Schema)>,
+) {
+    if let Some(ref_path) = &schema.ref_path {
+        if visited.contains(&ref_path.as_str()) {
+            return;
+        }
+        visited.push(ref_path.as_str());
+        if let Some(resolved) = ref_path
+            .strip_prefix("#/components/schemas/")
+            .and_then(|name| components?.schemas.as_ref()?.get(name))
+        {
+            collect_all_of_properties(resolved, components, visited, props);
+        }
+        return;
+    }
+
+    if let Some(properties) = &schema.properties {
+        let mut names: Vec<_> = properties.iter().collect();
+        names.sort_by_key(|(name, _)| *name);
+        props.extend(names);
+    }
+    for branch in schema.all_of.iter().flatten() {
+        collect_all_of_properties(branch, components, visited, props);
+    }
+}
+
+/// Whether every value of `from` is also a value of `to`.
+///
+/// Named references, intersections and object shapes are not resolved here, so they are
+/// treated as compatible with anything rather than risk a false conflict.
+fn is_assignable(from: &TsType, to: &TsType) -> bool {
+    let numeric = |p: &TsPrimitive| matches!(p, TsPrimitive::Number | TsPrimitive::Int64);
+    let string = |p: &TsPrimitive| matches!(p, TsPrimitive::String | TsPrimitive::Format(_));
+    match (from, to) {
+        (TsType::Primitive(TsPrimitive::Unknown) | TsType::Ref(_) | TsType::Intersection(_), _)
+        | (_, TsType::Primitive(TsPrimitive::Unknown) | TsType::Ref(_) | TsType::Intersection(_)) => {
+            true
+        }
+        (TsType::Union(types), _) => types.iter().all(|t| is_assignable(t, to)),
+        (_, TsType::Union(types)) => types.iter().any(|t| is_assignable(from, t)),
+        (TsType::Primitive(a), TsType::Primitive(b)) => {
+            a == b || (numeric(a) && numeric(b)) || (string(a) && string(b))
+        }
+        (TsType::Literal(lit), TsType::Primitive(p)) => matches!(
+            (lit, p),
+            (
+                TsLiteral::String(_),
+                TsPrimitive::String | TsPrimitive::Format(_)
+            ) | (
+                TsLiteral::Number(_) | TsLiteral::Int(_),
+                TsPrimitive::Number | TsPrimitive::Int64
+            ) | (TsLiteral::Bool(_), TsPrimitive::Boolean)
+                | (TsLiteral::Null, TsPrimitive::Null)
+        ),
+        (TsType::Literal(a), TsType::Literal(b)) => match (a, b) {
+            (TsLiteral::String(a), TsLiteral::String(b)) => a == b,
+            (TsLiteral::Int(a), TsLiteral::Int(b)) => a == b,
+            (TsLiteral::Bool(a), TsLiteral::Bool(b)) => a == b,
+            (TsLiteral::Null, TsLiteral::Null) => true,
+            (TsLiteral::Number(a), TsLiteral::Number(b)) => (a - b).abs() < f64::EPSILON,
+            _ => false,
+        },
+        (TsType::Array(a), TsType::Array(b)) => is_assignable(a, b),
+        (
+            TsType::Object(_) | TsType::Record { .. } | TsType::IndexSignature { .. },
+            TsType::Object(_) | TsType::Record { .. } | TsType::IndexSignature { .. },
+        ) => true,
+        _ => false,
+    }
+}
+
+/// Short TypeScript-like rendering of `ty` for error messages.
+fn type_label(ty: &TsType) -> String {
+    match ty {
+        TsType::Primitive(p) => match p {
+            TsPrimitive::String | TsPrimitive::Format(_) => "string",
+            TsPrimitive::Number | TsPrimitive::Int64 => "number",
+            TsPrimitive::BigInt => "bigint",
+            TsPrimitive::Boolean => "boolean",
+            TsPrimitive::Null => "null",
+            TsPrimitive::Void => "void",
+            TsPrimitive::Unknown => "unknown",
+            TsPrimitive::Never => "never",
+        }
+        .to_string(),
+        TsType::Literal(lit) => match lit {
+            TsLiteral::String(s) => format!("\"{s}\""),
+            TsLiteral::Number(n) => n.to_string(),
+            TsLiteral::Int(i) => i.to_string(),
+            TsLiteral::Bool(b) => b.to_string(),
+            TsLiteral::Null => "null".to_string(),
+        },
+        TsType::Ref(name) => name.clone(),
+        TsType::Array(inner) => match inner.as_ref() {
+            TsType::Union(_) | TsType::Intersection(_) => format!("({})[]", type_label(inner)),
+            _ => format!("{}[]", type_label(inner)),
+        },
+        TsType::Union(types) => types.iter().map(type_label).collect::<Vec<_>>().join(" | "),
+        TsType::Intersection(types) => types.iter().map(type_label).collect::<Vec<_>>().join(" & "),
+        TsType::Object(_) | TsType::Record { .. } | TsType::IndexSignature { .. } => {
+            "object".to_string()
+        }
+    }
+}
+
 /// Normalize union type (anyOf/oneOf)
 fn normalize_union(
     schemas: &[Schema],
@@ -313,29 +1036,15 @@ fn normalize_union(
         for schema in schemas {
             let base_type = schema_to_ts_type(schema)?;
 
-            // Determine discriminator value
-            let disc_value = if let Some(mapping) = &disc.mapping {
-                if let Some(ref_path) = &schema.ref_path {
-                    mapping
-                        .iter()
-                        .find(|(_, v)| *v == ref_path)
-                        .map(|(k, _)| k.clone())
-                } else {
-                    None
-                }
-            } else {
-                schema
-                    .ref_path
-                    .as_ref()
-                    .map(|ref_path| ref_to_type_name(ref_path))
-            };
-
-            if let Some(value) = disc_value {
+            if let Some(value) = discriminator_value(schema, disc) {
                 // Create intersection: { petType: "dog" } & Dog
                 let disc_prop = TsProp {
                     name: disc.property_name.clone(),
                     ty: TsType::Literal(TsLiteral::String(value)),
                     optional: false,
+                    constraints: TsConstraints::default(),
+                    read_only: false,
+                    readonly: false,
                 };
                 union_types.push(TsType::Intersection(vec![
                     TsType::Object(vec![disc_prop]),
@@ -354,7 +1063,121 @@ fn normalize_union(
         .map(schema_to_ts_type)
         .collect::<Result<Vec<_>, _>>()?;
 
-    Ok(TsType::Union(types))
+    Ok(flatten_union(types))
+}
+
+/// Discriminator value selecting a `oneOf` member: its mapping key, or the
+/// referenced type name when the discriminator has no mapping.
+fn discriminator_value(member: &Schema, disc: &Discriminator) -> Option<String> {
+    let ref_path = member.ref_path.as_ref()?;
+    match &disc.mapping {
+        Some(mapping) => mapping
+            .iter()
+            .find(|(_, v)| *v == ref_path)
+            .map(|(k, _)| k.clone()),
+        None => Some(ref_to_type_name(ref_path)),
+    }
+}
+
+/// Copy of the component `schemas` where `oneOf` unions without a `discriminator`
+/// get one inferred from their members (see [`infer_discriminator`]).
+fn with_inferred_discriminators(schemas: &HashMap<String, Schema>) -> HashMap<String, Schema> {
+    schemas
+        .iter()
+        .map(|(name, schema)| {
+            let mut schema = schema.clone();
+            if schema.discriminator.is_none()
+                && schema.any_of.is_none()
+                && let Some(members) = &schema.one_of
+            {
+                schema.discriminator = infer_discriminator(members, schemas);
+            }
+            (name.clone(), schema)
+        })
+        .collect()
+}
+
+/// Discriminator implied by a `oneOf` whose members all reference component objects
+/// declaring the same property as distinct string literals, as Pydantic emits for
+/// `Literal` tag fields without a `discriminator` object.
+///
+/// The first such property by name wins; `None` keeps the union plain.
+fn infer_discriminator(
+    members: &[Schema],
+    schemas: &HashMap<String, Schema>,
+) -> Option<Discriminator> {
+    let resolved = members
+        .iter()
+        .map(|member| {
+            let ref_path = member.ref_path.as_ref()?;
+            let target = schemas.get(&ref_to_type_name(ref_path))?;
+            Some((ref_path, target.properties.as_ref()?))
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    let (_, first) = resolved.first()?;
+    let mut candidates: Vec<_> = first.keys().collect();
+    candidates.sort();
+
+    candidates.into_iter().find_map(|property| {
+        let mut mapping = HashMap::new();
+        for (ref_path, properties) in &resolved {
+            let value = string_literal(properties.get(property)?)?;
+            if mapping.insert(value, (*ref_path).clone()).is_some() {
+                return None;
+            }
+        }
+        Some(Discriminator {
+            property_name: property.clone(),
+            mapping: Some(mapping),
+        })
+    })
+}
+
+/// The only string a schema allows: its `const`, or the value of a single-value `enum`.
+fn string_literal(schema: &Schema) -> Option<String> {
+    if let Some(value) = &schema.const_value {
+        return value.as_str().map(str::to_string);
+    }
+    match schema.enum_values.as_deref()? {
+        [EnumValue::String(value)] => Some(value.clone()),
+        _ => None,
+    }
+}
+
+/// Build a union, inlining nested unions and keeping at most one `null` member.
+///
+/// Nested unions come from members that are themselves nullable (e.g. `anyOf`
+/// of a `nullable: true` schema and `null`); a single remaining member is
+/// returned as-is.
+fn flatten_union(types: Vec<TsType>) -> TsType {
+    let mut members = Vec::with_capacity(types.len());
+    let mut has_null = false;
+    for ty in types {
+        let nested = match ty {
+            TsType::Union(inner) => inner,
+            other => vec![other],
+        };
+        for member in nested {
+            if matches!(
+                member,
+                TsType::Primitive(TsPrimitive::Null) | TsType::Literal(TsLiteral::Null)
+            ) {
+                if has_null {
+                    continue;
+                }
+                has_null = true;
+            }
+            members.push(member);
+        }
+    }
+
+    if members.len() == 1
+        && let Some(only) = members.pop()
+    {
+        return only;
+    }
+    TsType::Union(members)
 }
 
 /// Convert single schema type to TsType
@@ -363,6 +1186,10 @@ fn schema_type_to_ts(schema_type: &str, schema: &Schema) -> Result<TsType, Strin
         "string" => {
             if let Some(enum_values) = &schema.enum_values {
                 Ok(enum_to_union_type(enum_values))
+            } else if let Some(format) =
+                schema.format.as_deref().and_then(StringFormat::from_format)
+            {
+                Ok(TsType::Primitive(TsPrimitive::Format(format)))
             } else {
                 Ok(TsType::Primitive(TsPrimitive::String))
             }
@@ -370,6 +1197,10 @@ fn schema_type_to_ts(schema_type: &str, schema: &Schema) -> Result<TsType, Strin
         "number" | "integer" => {
             if let Some(enum_values) = &schema.enum_values {
                 Ok(enum_to_union_type(enum_values))
+            } else if schema_type == "integer"
+                && matches!(schema.format.as_deref(), Some("int64" | "uint64"))
+            {
+                Ok(TsType::Primitive(TsPrimitive::Int64))
             } else {
                 Ok(TsType::Primitive(TsPrimitive::Number))
             }
@@ -402,8 +1233,16 @@ fn enum_to_union_type(values: &[EnumValue]) -> TsType {
 
 /// Normalize object type
 fn normalize_object_type(schema: &Schema) -> Result<TsType, String> {
+    if let Some(patterns) = &schema.pattern_properties {
+        return normalize_pattern_properties(schema, patterns);
+    }
+
     let has_properties = schema.properties.is_some();
-    let has_additional = schema.additional_properties.is_some();
+    // `additionalProperties: false` only closes the object; it adds no index signature
+    let has_additional = !matches!(
+        schema.additional_properties,
+        None | Some(AdditionalProperties::Bool(false))
+    );
 
     match (has_properties, has_additional) {
         (true, true) => {
@@ -426,15 +1265,78 @@ fn normalize_object_type(schema: &Schema) -> Result<TsType, String> {
             Ok(TsType::Object(props))
         }
         (false, true) => normalize_additional_properties(schema),
+        (false, false) if schema.additional_properties.is_some() => {
+            normalize_additional_properties(schema)
+        }
         (false, false) => Ok(make_unknown_record()),
     }
 }
 
+/// Approximate `patternProperties` with a string index signature.
+///
+/// TypeScript cannot constrain keys by regex, so all patterns (and an
+/// `additionalProperties` schema, if any) share one `[key: string]` signature
+/// whose value is the union of their types, documented with the patterns.
+/// Named properties are intersected alongside it, and their types join the
+/// union so the intersection doesn't narrow them to `never`.
+fn normalize_pattern_properties(
+    schema: &Schema,
+    patterns: &HashMap<String, Schema>,
+) -> Result<TsType, String> {
+    let mut sorted: Vec<_> = patterns.iter().collect();
+    sorted.sort_by(|a, b| a.0.cmp(b.0));
+
+    let mut values = Vec::new();
+    for (_, value_schema) in &sorted {
+        values.push(schema_to_ts_type(value_schema)?);
+    }
+    match &schema.additional_properties {
+        Some(AdditionalProperties::Schema(s)) => values.push(schema_to_ts_type(s)?),
+        Some(AdditionalProperties::Bool(true)) => {
+            values.push(TsType::Primitive(TsPrimitive::Unknown));
+        }
+        Some(AdditionalProperties::Bool(false)) | None => {}
+    }
+    let props = schema
+        .properties
+        .as_ref()
+        .map(|properties| normalize_properties(properties, schema.required.as_ref()))
+        .transpose()?;
+    if let Some(props) = &props {
+        values.extend(props.iter().map(|prop| prop.ty.clone()));
+    }
+
+    // Identical value schemas collapse into a single union member
+    let mut seen = std::collections::HashSet::new();
+    values.retain(|ty| seen.insert(format!("{ty:?}")));
+    if values.is_empty() {
+        values.push(TsType::Primitive(TsPrimitive::Unknown));
+    }
+
+    let described = sorted
+        .iter()
+        .map(|(pattern, _)| format!("`{}`", pattern.replace("*/", "*\\/")))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let index = TsType::IndexSignature {
+        value: Box::new(flatten_union(values)),
+        doc: Some(format!("Keys matching {described}")),
+    };
+
+    match props {
+        Some(props) => Ok(TsType::Intersection(vec![TsType::Object(props), index])),
+        None => Ok(index),
+    }
+}
+
 /// Normalize additional properties to a Record type
 fn normalize_additional_properties(schema: &Schema) -> Result<TsType, String> {
     match &schema.additional_properties {
         Some(AdditionalProperties::Bool(true)) | None => Ok(make_unknown_record()),
-        Some(AdditionalProperties::Bool(false)) => Ok(TsType::Object(Vec::new())),
+        // No keys allowed: `{}` would accept any non-null value
+        Some(AdditionalProperties::Bool(false)) => {
+            Ok(make_string_record(TsType::Primitive(TsPrimitive::Never)))
+        }
         Some(AdditionalProperties::Schema(s)) => {
             let value_type = schema_to_ts_type(s)?;
             Ok(make_string_record(value_type))
@@ -456,6 +1358,7 @@ fn normalize_operation(
     method: HttpMethod,
     op: &Operation,
     path_params: Option<&Vec<Parameter>>,
+    components: Option<&Components>,
 ) -> Result<OperationIR, String> {
     let name = get_operation_name(path, method, op);
     let kind = if method.is_query() {
@@ -468,7 +1371,7 @@ fn normalize_operation(
     let params = normalize_params(&name, op, path_params)?;
 
     // Normalize body
-    let body = normalize_body(op)?;
+    let body = normalize_body(op, components)?;
 
     // Normalize response
     let response = normalize_response(op)?;
@@ -509,6 +1412,8 @@ fn normalize_operation(
 
     Ok(OperationIR {
         name,
+        path: path.to_string(),
+        tag: op.tags.as_ref().and_then(|tags| tags.first()).cloned(),
         params,
         fetch,
         hooks,
@@ -532,7 +1437,7 @@ fn get_operation_name(path: &str, method: HttpMethod, op: &Operation) -> String
     sanitize_ts_identifier(&format!("{}_{}", method.as_str().to_lowercase(), base))
 }
 
-/// Check for duplicate parameter names within a list
+/// Check for parameters declared twice with the same name and location within a list
 fn check_duplicate_params(params: &[Parameter], location: &str) -> Result<(), String> {
     let mut seen = std::collections::HashSet::new();
     for p in params {
@@ -540,10 +1445,10 @@ fn check_duplicate_params(params: &[Parameter], location: &str) -> Result<(), St
         if p.location == "cookie" {
             continue;
         }
-        if !seen.insert(&p.name) {
+        if !seen.insert((&p.name, &p.location)) {
             return Err(format!(
-                "Duplicate parameter name '{}' in {} parameters",
-                p.name, location
+                "Duplicate parameter '{}' (in: {}) in {} parameters",
+                p.name, p.location, location
             ));
         }
     }
@@ -551,45 +1456,47 @@ fn check_duplicate_params(params: &[Parameter], location: &str) -> Result<(), St
 }
 
 /// Normalize parameters - includes path, query, and header params; skips cookie params
+///
+/// Operation-level params replace path-level ones with the same name and location;
+/// all others are combined.
 fn normalize_params(
     op_name: &str,
     op: &Operation,
     path_params: Option<&Vec<Parameter>>,
 ) -> Result<Option<ParamsIR>, String> {
-    let mut fields = Vec::new();
+    let mut merged: Vec<&Parameter> = Vec::new();
 
     // Check for duplicates within path-level params
     if let Some(pp) = path_params {
         check_duplicate_params(pp, "path-level")?;
-        for p in pp {
-            // Skip cookie params
-            if p.location == "cookie" {
-                continue;
-            }
-            fields.push(normalize_param(p));
-        }
+        merged.extend(pp.iter().filter(|p| p.location != "cookie"));
     }
 
     // Check for duplicates within operation-level params
     if let Some(op_params) = &op.parameters {
         check_duplicate_params(op_params, "operation-level")?;
-        for p in op_params {
-            // Skip cookie params
-            if p.location == "cookie" {
-                continue;
-            }
-            // Remove any existing param with same name (op-level overrides path-level)
-            fields.retain(|f: &ParamIR| f.original_name != p.name);
-            fields.push(normalize_param(p));
+        for p in op_params.iter().filter(|p| p.location != "cookie") {
+            merged.retain(|m| m.name != p.name || m.location != p.location);
+            merged.push(p);
+        }
+    }
+
+    // All params share one object, so a name can't be reused across locations
+    for (i, p) in merged.iter().enumerate() {
+        if let Some(other) = merged[..i].iter().find(|other| other.name == p.name) {
+            return Err(format!(
+                "Duplicate parameter name '{}' in {} and {} parameters of operation '{op_name}'",
+                p.name, other.location, p.location
+            ));
         }
     }
 
-    if fields.is_empty() {
+    if merged.is_empty() {
         Ok(None)
     } else {
         Ok(Some(ParamsIR {
             type_name: format!("{}Params", capitalize_first(op_name)),
-            fields,
+            fields: merged.into_iter().map(normalize_param).collect(),
         }))
     }
 }
@@ -628,7 +1535,10 @@ fn normalize_param(p: &Parameter) -> ParamIR {
 }
 
 /// Normalize request body - detects content type and returns BodyIR
-fn normalize_body(op: &Operation) -> Result<Option<BodyIR>, String> {
+fn normalize_body(
+    op: &Operation,
+    components: Option<&Components>,
+) -> Result<Option<BodyIR>, String> {
     if let Some(body) = &op.request_body
         && let Some(content) = &body.content
     {
@@ -640,6 +1550,13 @@ fn normalize_body(op: &Operation) -> Result<Option<BodyIR>, String> {
             return Ok(Some(BodyIR {
                 ty: TypeRef::Inline(Box::new(ty)),
                 content_type: BodyContentType::FormData,
+                defaults: Vec::new(),
+                bigint_paths: Vec::new(),
+                form_fields: normalize_form_fields(
+                    schema,
+                    media_type.encoding.as_ref(),
+                    components,
+                )?,
             }));
         }
 
@@ -651,6 +1568,9 @@ fn normalize_body(op: &Operation) -> Result<Option<BodyIR>, String> {
             return Ok(Some(BodyIR {
                 ty: TypeRef::Inline(Box::new(ty)),
                 content_type: BodyContentType::UrlEncoded,
+                defaults: Vec::new(),
+                bigint_paths: Vec::new(),
+                form_fields: Vec::new(),
             }));
         }
 
@@ -662,16 +1582,149 @@ fn normalize_body(op: &Operation) -> Result<Option<BodyIR>, String> {
             return Ok(Some(BodyIR {
                 ty: TypeRef::Inline(Box::new(ty)),
                 content_type: BodyContentType::Json,
+                defaults: normalize_body_defaults(schema, components),
+                bigint_paths: Vec::new(),
+                form_fields: Vec::new(),
             }));
         }
     }
     Ok(None)
 }
 
+/// Collect the top-level properties of a JSON body schema that declare a `default`.
+///
+/// `$ref`s are resolved one level against the component schemas; `readOnly`
+/// properties are skipped since clients never send them.
+fn normalize_body_defaults(schema: &Schema, components: Option<&Components>) -> Vec<BodyDefaultIR> {
+    let resolved = resolve_component_ref(schema, components);
+    let Some(properties) = &resolved.properties else {
+        return Vec::new();
+    };
+
+    let mut names: Vec<_> = properties.keys().collect();
+    names.sort();
+    names
+        .into_iter()
+        .filter_map(|name| {
+            let prop = properties.get(name)?;
+            if prop.read_only == Some(true) {
+                return None;
+            }
+            Some(BodyDefaultIR {
+                name: name.clone(),
+                value: prop.default.clone()?,
+                required: resolved.required.as_ref().is_some_and(|r| r.contains(name)),
+            })
+        })
+        .collect()
+}
+
+/// Follow a `$ref` to a component schema, or return `schema` itself.
+fn resolve_component_ref<'a>(schema: &'a Schema, components: Option<&'a Components>) -> &'a Schema {
+    schema
+        .ref_path
+        .as_deref()
+        .and_then(|r| r.strip_prefix("#/components/schemas/"))
+        .and_then(|name| components?.schemas.as_ref()?.get(name))
+        .unwrap_or(schema)
+}
+
+/// Normalize the properties of an object `multipart/form-data` body into form parts.
+///
+/// A property is sent as a JSON part when its `encoding.contentType` is a JSON
+/// media type, or when it has no `contentType` and holds an object (the
+/// OpenAPI default). Binary strings are typed as `Blob` and appended as-is.
+fn normalize_form_fields(
+    schema: &Schema,
+    encoding: Option<&HashMap<String, Encoding>>,
+    components: Option<&Components>,
+) -> Result<Vec<FormFieldIR>, String> {
+    let resolved = resolve_component_ref(schema, components);
+    let Some(properties) = &resolved.properties else {
+        return Ok(Vec::new());
+    };
+
+    let mut names: Vec<_> = properties.keys().collect();
+    names.sort();
+    let mut fields = Vec::new();
+    for name in names {
+        let Some(prop) = properties.get(name) else {
+            continue;
+        };
+        if prop.read_only == Some(true) {
+            continue;
+        }
+
+        let blob = || TsType::Ref("Blob".into());
+        let ty = if is_binary_schema(prop) {
+            blob()
+        } else if prop.items.as_deref().is_some_and(is_binary_schema) {
+            TsType::Array(Box::new(blob()))
+        } else {
+            schema_to_ts_type(prop)?
+        };
+        let content_type = encoding
+            .and_then(|e| e.get(name))
+            .and_then(|e| e.content_type.as_deref());
+        let json_content_type = match content_type {
+            Some(ct) => {
+                let ct = ct.trim();
+                let is_json = detect_response_content_type(&ct.to_ascii_lowercase())
+                    == ResponseContentType::Json;
+                is_json.then(|| ct.to_string())
+            }
+            None => is_object_schema(prop, components).then(|| "application/json".to_string()),
+        };
+
+        fields.push(FormFieldIR {
+            name: name.clone(),
+            ty: TypeRef::Inline(Box::new(ty)),
+            optional: !resolved.required.as_ref().is_some_and(|r| r.contains(name)),
+            json_content_type,
+        });
+    }
+    Ok(fields)
+}
+
+/// `type: string, format: binary`
+fn is_binary_schema(schema: &Schema) -> bool {
+    matches!(&schema.schema_type, Some(SchemaType::Single(t)) if t == "string")
+        && schema.format.as_deref() == Some("binary")
+}
+
+/// Whether a schema describes an object, or an array of objects.
+fn is_object_schema(schema: &Schema, components: Option<&Components>) -> bool {
+    let schema = resolve_component_ref(schema.unwrap_nullable().unwrap_or(schema), components);
+    if matches!(&schema.schema_type, Some(SchemaType::Single(t)) if t == "array") {
+        return schema
+            .items
+            .as_deref()
+            .is_some_and(|items| is_object_schema(items, components));
+    }
+    matches!(&schema.schema_type, Some(SchemaType::Single(t)) if t == "object")
+        || schema.properties.is_some()
+        || schema.additional_properties.is_some()
+        || schema.all_of.is_some()
+}
+
+/// Order in which a response's media types are considered: `application/json`
+/// first, then other JSON types, then everything else.
+fn media_type_rank(media_type: &str) -> u8 {
+    if media_type == "application/json" {
+        0
+    } else if detect_response_content_type(media_type) == ResponseContentType::Json {
+        1
+    } else {
+        2
+    }
+}
+
 /// Determine response content type from media type string
 fn detect_response_content_type(media_type: &str) -> ResponseContentType {
     if media_type == "application/json" || media_type.ends_with("+json") {
         ResponseContentType::Json
+    } else if media_type == "text/event-stream" {
+        ResponseContentType::EventStream
     } else if media_type == "text/plain"
         || media_type.starts_with("text/")
         || media_type == "application/xml"
@@ -694,6 +1747,7 @@ fn detect_response_content_type(media_type: &str) -> ResponseContentType {
 fn normalize_response(op: &Operation) -> Result<ResponseIR, String> {
     // Check if 204 (No Content) exists
     let has_void_status = op.responses.contains_key("204");
+    let error_ty = normalize_error_type(op)?;
 
     // Priority order for success responses: 200, 201, 202, 203, 206, 207, then default, then 2XX
     let status_codes = ["200", "201", "202", "203", "206", "207", "default", "2XX"];
@@ -702,19 +1756,49 @@ fn normalize_response(op: &Operation) -> Result<ResponseIR, String> {
         if let Some(response) = op.responses.get(status)
             && let Some(content) = &response.content
         {
-            // Find the first content type that has a schema
-            for (media_type_str, media_type) in content {
-                if let Some(schema) = &media_type.schema {
-                    let ty = schema_to_ts_type(schema)?;
-                    let content_type = detect_response_content_type(media_type_str);
-
-                    return Ok(ResponseIR {
-                        ty: TypeRef::Inline(Box::new(ty)),
-                        content_type,
-                        has_void_status,
-                    });
+            // Event streams often declare no schema; their event data is then a plain string
+            let mut media_types: Vec<_> = content
+                .iter()
+                .filter(|(name, media_type)| {
+                    media_type.schema.is_some()
+                        || detect_response_content_type(name) == ResponseContentType::EventStream
+                })
+                .collect();
+            // `application/json` supplies the JSON schema even when a vendor type such as
+            // `application/hal+json` sorts before it; other types follow alphabetically
+            media_types.sort_by(|a, b| {
+                media_type_rank(a.0)
+                    .cmp(&media_type_rank(b.0))
+                    .then_with(|| a.0.cmp(b.0))
+            });
+
+            // Group media types by how they are parsed, keeping the first schema per group
+            let mut groups: Vec<(ResponseContentType, Vec<String>, TsType)> = Vec::new();
+            for (media_type_str, media_type) in media_types {
+                let content_type = detect_response_content_type(media_type_str);
+                if let Some(group) = groups.iter_mut().find(|g| g.0 == content_type) {
+                    group.1.push(media_type_str.clone());
+                } else {
+                    let ty = match &media_type.schema {
+                        Some(schema) => schema_to_ts_type(schema)?,
+                        None => TsType::Primitive(TsPrimitive::String),
+                    };
+                    groups.push((content_type, vec![media_type_str.clone()], ty));
                 }
             }
+
+            if groups.len() > 1 {
+                return Ok(negotiated_response(groups, has_void_status, error_ty));
+            }
+            if let Some((content_type, _, ty)) = groups.pop() {
+                return Ok(ResponseIR {
+                    ty: TypeRef::Inline(Box::new(ty)),
+                    content_type,
+                    has_void_status,
+                    variants: Vec::new(),
+                    error_ty,
+                });
+            }
         }
     }
 
@@ -724,6 +1808,8 @@ fn normalize_response(op: &Operation) -> Result<ResponseIR, String> {
             ty: TypeRef::Inline(Box::new(TsType::Primitive(TsPrimitive::Void))),
             content_type: ResponseContentType::Json, // doesn't matter for void
             has_void_status: false,                  // no need to check, it's always void
+            variants: Vec::new(),
+            error_ty,
         });
     }
 
@@ -732,9 +1818,96 @@ fn normalize_response(op: &Operation) -> Result<ResponseIR, String> {
         ty: TypeRef::Inline(Box::new(TsType::Primitive(TsPrimitive::Unknown))),
         content_type: ResponseContentType::Unknown,
         has_void_status: false,
+        variants: Vec::new(),
+        error_ty,
     })
 }
 
+/// Union of the JSON bodies declared for the 4xx/5xx responses of an operation.
+///
+/// Identical schemas are listed once; operations without an error body get `unknown`.
+fn normalize_error_type(op: &Operation) -> Result<TypeRef, String> {
+    let mut codes: Vec<_> = op
+        .responses
+        .iter()
+        .filter(|(code, _)| code.starts_with('4') || code.starts_with('5'))
+        .collect();
+    codes.sort_by_key(|(code, _)| *code);
+
+    let mut members: Vec<TsType> = Vec::new();
+    let mut seen = std::collections::HashSet::new();
+    for (_, response) in codes {
+        let Some(schema) = response
+            .content
+            .iter()
+            .flatten()
+            .filter(|(mt, _)| detect_response_content_type(mt) == ResponseContentType::Json)
+            .min_by_key(|(mt, _)| *mt)
+            .and_then(|(_, media_type)| media_type.schema.as_ref())
+        else {
+            continue;
+        };
+        let ty = schema_to_ts_type(schema)?;
+        if seen.insert(format!("{ty:?}")) {
+            members.push(ty);
+        }
+    }
+
+    let ty = if members.is_empty() {
+        TsType::Primitive(TsPrimitive::Unknown)
+    } else {
+        flatten_union(members)
+    };
+    Ok(TypeRef::Inline(Box::new(ty)))
+}
+
+/// Build a response that picks its parser from the runtime `Content-Type` header.
+///
+/// Branches are ordered JSON, text, unknown, then binary so the most generic
+/// parser is the fallback; the data type is the union of all branches. An event
+/// stream among several content types is returned as the raw `Response`.
+fn negotiated_response(
+    mut groups: Vec<(ResponseContentType, Vec<String>, TsType)>,
+    has_void_status: bool,
+    error_ty: TypeRef,
+) -> ResponseIR {
+    let rank = |ct: ResponseContentType| match ct {
+        ResponseContentType::Json => 0,
+        ResponseContentType::Text => 1,
+        ResponseContentType::Unknown
+        | ResponseContentType::EventStream
+        | ResponseContentType::Negotiated => 2,
+        ResponseContentType::Blob => 3,
+    };
+    groups.sort_by_key(|g| rank(g.0));
+
+    let members = groups
+        .iter()
+        .map(|(content_type, _, ty)| match content_type {
+            ResponseContentType::Json => ty.clone(),
+            ResponseContentType::Text => TsType::Primitive(TsPrimitive::String),
+            ResponseContentType::Blob => TsType::Ref("Blob".into()),
+            ResponseContentType::Unknown
+            | ResponseContentType::EventStream
+            | ResponseContentType::Negotiated => TsType::Ref("Response".into()),
+        })
+        .collect();
+
+    ResponseIR {
+        ty: TypeRef::Inline(Box::new(flatten_union(members))),
+        content_type: ResponseContentType::Negotiated,
+        has_void_status,
+        variants: groups
+            .into_iter()
+            .map(|(content_type, media_types, _)| ResponseVariantIR {
+                content_type,
+                media_types,
+            })
+            .collect(),
+        error_ty,
+    }
+}
+
 /// Build fetch function IR
 fn build_fetch_ir(
     name: &str,
@@ -804,6 +1977,8 @@ fn build_fetch_ir(
         body: body.cloned(),
         method,
         header_params,
+        bigint_paths: Vec::new(),
+        bigint_body: false,
     }
 }
 
@@ -890,7 +2065,8 @@ fn find_matching_param(placeholder: &str, params: &[&ParamIR]) -> Option<String>
 /// Build query key IR
 fn build_query_key_ir(name: &str, path: &str, params: Option<&ParamsIR>) -> QueryKeyIR {
     QueryKeyIR {
-        fn_name: format!("{name}Key"),
+        fn_name: format!("{name}QueryKey"),
+        legacy_fn_name: Some(format!("{name}Key")),
         base_key: path.to_string(),
         params_type: params.map(|p| TypeRef::Named(p.type_name.clone())),
     }
@@ -927,6 +2103,7 @@ fn build_hooks(
                 params_required,
                 response_content_type: response.content_type,
                 response_has_void_status: response.has_void_status,
+                error_type: response.error_ty.clone(),
                 body_before_params: false,
             });
 
@@ -941,6 +2118,7 @@ fn build_hooks(
                 params_required,
                 response_content_type: response.content_type,
                 response_has_void_status: response.has_void_status,
+                error_type: response.error_ty.clone(),
                 body_before_params: false,
             });
         }
@@ -963,11 +2141,17 @@ fn build_hooks(
                             name: "params".to_string(),
                             ty: TsType::Ref(p.type_name.clone()),
                             optional: false,
+                            constraints: TsConstraints::default(),
+                            read_only: false,
+                            readonly: false,
                         },
                         TsProp {
                             name: "data".to_string(),
                             ty: data_ty,
                             optional: false,
+                            constraints: TsConstraints::default(),
+                            read_only: false,
+                            readonly: false,
                         },
                     ]))))
                 }
@@ -975,6 +2159,9 @@ fn build_hooks(
                     name: "params".to_string(),
                     ty: TsType::Ref(p.type_name.clone()),
                     optional: false,
+                    constraints: TsConstraints::default(),
+                    read_only: false,
+                    readonly: false,
                 }])))),
                 (None, Some(b)) => {
                     // For FormData, use FormData type
@@ -997,6 +2184,7 @@ fn build_hooks(
                 params_required: false, // Mutations handle params through vars_type
                 response_content_type: response.content_type,
                 response_has_void_status: response.has_void_status,
+                error_type: response.error_ty.clone(),
                 body_before_params,
             });
         }
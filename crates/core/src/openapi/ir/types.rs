@@ -5,6 +5,8 @@
 //! all code-level AST (expressions, statements, functions) is now handled
 //! directly by SWC's `swc_ecma_ast`.
 
+use std::collections::{BTreeSet, HashMap};
+
 /// Reference to a type - either inline or named
 #[derive(Debug, Clone)]
 pub enum TypeRef {
@@ -30,6 +32,47 @@ impl TypeRef {
             TypeRef::Inline(t) => (**t).clone(),
         }
     }
+
+    /// Rewrite every inline `Int64` primitive to `BigInt` (named types are rewritten at their definition)
+    pub fn int64_to_bigint(&mut self) {
+        if let TypeRef::Inline(t) = self {
+            t.int64_to_bigint();
+        }
+    }
+
+    /// Rename references to types listed in `names` (component schema name to TS name)
+    pub fn rename_refs(&mut self, names: &HashMap<String, String>) {
+        match self {
+            TypeRef::Named(name) => {
+                if let Some(renamed) = names.get(name) {
+                    name.clone_from(renamed);
+                }
+            }
+            TypeRef::Inline(t) => t.rename_refs(names),
+        }
+    }
+
+    /// Rewrite every inline brandable string to its brand reference (see [`TsType::brand_formats`])
+    pub fn brand_formats(&mut self, brands: &[StringFormat], used: &mut BTreeSet<StringFormat>) {
+        if let TypeRef::Inline(t) = self {
+            t.brand_formats(brands, used);
+        }
+    }
+
+    /// Mark inline object properties `readonly` (see [`TsType::mark_readonly`])
+    pub fn mark_readonly(&mut self, all: bool) {
+        if let TypeRef::Inline(t) = self {
+            t.mark_readonly(all);
+        }
+    }
+
+    /// Push the names of the types this reference points to onto `out`
+    pub fn collect_refs(&self, out: &mut Vec<String>) {
+        match self {
+            TypeRef::Named(name) => out.push(name.clone()),
+            TypeRef::Inline(t) => t.collect_refs(out),
+        }
+    }
 }
 
 impl TsType {
@@ -46,6 +89,104 @@ impl TsType {
             _ => false,
         }
     }
+
+    /// Rewrite every `Int64` primitive in this type to `BigInt`
+    pub fn int64_to_bigint(&mut self) {
+        match self {
+            TsType::Primitive(p @ TsPrimitive::Int64) => *p = TsPrimitive::BigInt,
+            TsType::Array(inner) => inner.int64_to_bigint(),
+            TsType::Union(types) | TsType::Intersection(types) => {
+                types.iter_mut().for_each(TsType::int64_to_bigint);
+            }
+            TsType::Object(props) => props.iter_mut().for_each(|p| p.ty.int64_to_bigint()),
+            TsType::Record { key, value } => {
+                key.int64_to_bigint();
+                value.int64_to_bigint();
+            }
+            TsType::IndexSignature { value, .. } => value.int64_to_bigint(),
+            TsType::Primitive(_) | TsType::Literal(_) | TsType::Ref(_) => {}
+        }
+    }
+
+    /// Rename references to types listed in `names` (component schema name to TS name)
+    pub fn rename_refs(&mut self, names: &HashMap<String, String>) {
+        match self {
+            TsType::Ref(name) => {
+                if let Some(renamed) = names.get(name) {
+                    name.clone_from(renamed);
+                }
+            }
+            TsType::Array(inner) => inner.rename_refs(names),
+            TsType::Union(types) | TsType::Intersection(types) => {
+                types.iter_mut().for_each(|t| t.rename_refs(names));
+            }
+            TsType::Object(props) => props.iter_mut().for_each(|p| p.ty.rename_refs(names)),
+            TsType::Record { key, value } => {
+                key.rename_refs(names);
+                value.rename_refs(names);
+            }
+            TsType::IndexSignature { value, .. } => value.rename_refs(names),
+            TsType::Primitive(_) | TsType::Literal(_) => {}
+        }
+    }
+
+    /// Rewrite every `Format` primitive listed in `brands` to a reference to its brand type,
+    /// recording the formats that were found in `used`
+    pub fn brand_formats(&mut self, brands: &[StringFormat], used: &mut BTreeSet<StringFormat>) {
+        match self {
+            TsType::Primitive(TsPrimitive::Format(format)) if brands.contains(format) => {
+                used.insert(*format);
+                *self = TsType::Ref(format.type_name().to_string());
+            }
+            TsType::Array(inner) => inner.brand_formats(brands, used),
+            TsType::Union(types) | TsType::Intersection(types) => {
+                types.iter_mut().for_each(|t| t.brand_formats(brands, used));
+            }
+            TsType::Object(props) => props
+                .iter_mut()
+                .for_each(|p| p.ty.brand_formats(brands, used)),
+            TsType::Record { key, value } => {
+                key.brand_formats(brands, used);
+                value.brand_formats(brands, used);
+            }
+            TsType::IndexSignature { value, .. } => value.brand_formats(brands, used),
+            TsType::Primitive(_) | TsType::Literal(_) | TsType::Ref(_) => {}
+        }
+    }
+
+    /// Mark object properties `readonly`: every one when `all` is set, otherwise
+    /// only those declared `readOnly`
+    pub fn mark_readonly(&mut self, all: bool) {
+        match self {
+            TsType::Array(inner) => inner.mark_readonly(all),
+            TsType::Union(types) | TsType::Intersection(types) => {
+                types.iter_mut().for_each(|t| t.mark_readonly(all));
+            }
+            TsType::Object(props) => props.iter_mut().for_each(|p| p.mark_readonly(all)),
+            TsType::Record { value, .. } | TsType::IndexSignature { value, .. } => {
+                value.mark_readonly(all);
+            }
+            TsType::Primitive(_) | TsType::Literal(_) | TsType::Ref(_) => {}
+        }
+    }
+
+    /// Push the names of the types referenced in this type onto `out`
+    pub fn collect_refs(&self, out: &mut Vec<String>) {
+        match self {
+            TsType::Ref(name) => out.push(name.clone()),
+            TsType::Array(inner) => inner.collect_refs(out),
+            TsType::Union(types) | TsType::Intersection(types) => {
+                types.iter().for_each(|t| t.collect_refs(out));
+            }
+            TsType::Object(props) => props.iter().for_each(|p| p.ty.collect_refs(out)),
+            TsType::Record { key, value } => {
+                key.collect_refs(out);
+                value.collect_refs(out);
+            }
+            TsType::IndexSignature { value, .. } => value.collect_refs(out),
+            TsType::Primitive(_) | TsType::Literal(_) => {}
+        }
+    }
 }
 
 /// TypeScript type representation
@@ -70,6 +211,11 @@ pub enum TsType {
     Literal(TsLiteral),
     /// Named type reference (shorthand for TypeRef::Named in type position)
     Ref(String),
+    /// String index signature: { [key: string]: T }, with an optional JSDoc note
+    IndexSignature {
+        value: Box<TsType>,
+        doc: Option<String>,
+    },
 }
 
 /// TypeScript primitive types
@@ -77,10 +223,54 @@ pub enum TsType {
 pub enum TsPrimitive {
     String,
     Number,
+    /// `integer` with `format: int64`/`uint64`; rendered as `number` unless
+    /// rewritten to [`TsPrimitive::BigInt`]
+    Int64,
+    BigInt,
+    /// `string` with a `format` that has a brand; rendered as `string` unless
+    /// rewritten to a reference to the brand type
+    Format(StringFormat),
     Boolean,
     Null,
     Void,
     Unknown,
+    /// Value type of closed objects (`additionalProperties: false`)
+    Never,
+}
+
+/// String `format`s that can be emitted as branded types
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum StringFormat {
+    Uuid,
+    Email,
+    Uri,
+}
+
+impl StringFormat {
+    pub const ALL: [Self; 3] = [Self::Uuid, Self::Email, Self::Uri];
+
+    /// Brandable format for a schema `format` value, if any
+    pub fn from_format(format: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|f| f.as_str() == format)
+    }
+
+    /// The `format` value, also used as the brand tag
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Uuid => "uuid",
+            Self::Email => "email",
+            Self::Uri => "uri",
+        }
+    }
+
+    /// Name of the branded type alias
+    pub const fn type_name(self) -> &'static str {
+        match self {
+            Self::Uuid => "Uuid",
+            Self::Email => "Email",
+            Self::Uri => "Uri",
+        }
+    }
 }
 
 /// Object property definition
@@ -89,6 +279,61 @@ pub struct TsProp {
     pub name: String,
     pub ty: TsType,
     pub optional: bool,
+    /// Validation keywords rendered as JSDoc tags on the property
+    pub constraints: TsConstraints,
+    /// Declared `readOnly: true` in the schema
+    pub read_only: bool,
+    /// Rendered with the `readonly` modifier, arrays as `readonly T[]`
+    /// (set by `ApiIR::use_readonly_props`)
+    pub readonly: bool,
+}
+
+impl TsProp {
+    /// Set [`TsProp::readonly`] when `all` is set or the property is `readOnly`,
+    /// then mark nested object properties the same way
+    pub fn mark_readonly(&mut self, all: bool) {
+        self.readonly = all || self.read_only;
+        self.ty.mark_readonly(all);
+    }
+}
+
+/// Schema validation keywords TypeScript types can't express
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TsConstraints {
+    pub min_length: Option<u64>,
+    pub max_length: Option<u64>,
+    pub pattern: Option<String>,
+    pub minimum: Option<f64>,
+    pub maximum: Option<f64>,
+}
+
+impl TsConstraints {
+    /// Whether no keyword is set
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// JSDoc text such as `@minLength 1 @maxLength 50`, or `None` when unconstrained
+    pub fn jsdoc(&self) -> Option<String> {
+        let mut tags = Vec::new();
+        if let Some(v) = self.min_length {
+            tags.push(format!("@minLength {v}"));
+        }
+        if let Some(v) = self.max_length {
+            tags.push(format!("@maxLength {v}"));
+        }
+        if let Some(pattern) = &self.pattern {
+            // `*/` would end the comment early
+            tags.push(format!("@pattern {}", pattern.replace("*/", "*\\/")));
+        }
+        if let Some(v) = self.minimum {
+            tags.push(format!("@minimum {v}"));
+        }
+        if let Some(v) = self.maximum {
+            tags.push(format!("@maximum {v}"));
+        }
+        (!tags.is_empty()).then(|| tags.join(" "))
+    }
 }
 
 /// TypeScript literal values
@@ -108,7 +353,7 @@ pub enum TypeDefKind {
     Interface { properties: Vec<TsProp> },
     /// type Foo = ...
     TypeAlias { ty: TsType },
-    /// const Foo = { ... } as const; type Foo = ...
+    /// const Foo = { ... } as const satisfies Record<string, ...>; type Foo = ...
     ConstEnum { values: Vec<(String, TsLiteral)> },
 }
 
@@ -118,3 +363,65 @@ pub struct TsTypeDef {
     pub name: String,
     pub kind: TypeDefKind,
 }
+
+impl TsTypeDef {
+    /// Rewrite every `Int64` primitive in this definition to `BigInt`
+    pub fn int64_to_bigint(&mut self) {
+        match &mut self.kind {
+            TypeDefKind::Interface { properties } => {
+                properties.iter_mut().for_each(|p| p.ty.int64_to_bigint());
+            }
+            TypeDefKind::TypeAlias { ty } => ty.int64_to_bigint(),
+            TypeDefKind::ConstEnum { .. } => {}
+        }
+    }
+
+    /// Rename this definition and the references in it per `names`
+    pub fn rename_refs(&mut self, names: &HashMap<String, String>) {
+        if let Some(renamed) = names.get(&self.name) {
+            self.name.clone_from(renamed);
+        }
+        match &mut self.kind {
+            TypeDefKind::Interface { properties } => {
+                properties.iter_mut().for_each(|p| p.ty.rename_refs(names));
+            }
+            TypeDefKind::TypeAlias { ty } => ty.rename_refs(names),
+            TypeDefKind::ConstEnum { .. } => {}
+        }
+    }
+
+    /// Rewrite every brandable string in this definition to its brand reference
+    pub fn brand_formats(&mut self, brands: &[StringFormat], used: &mut BTreeSet<StringFormat>) {
+        match &mut self.kind {
+            TypeDefKind::Interface { properties } => {
+                properties
+                    .iter_mut()
+                    .for_each(|p| p.ty.brand_formats(brands, used));
+            }
+            TypeDefKind::TypeAlias { ty } => ty.brand_formats(brands, used),
+            TypeDefKind::ConstEnum { .. } => {}
+        }
+    }
+
+    /// Mark the properties of this definition `readonly` (see [`TsType::mark_readonly`])
+    pub fn mark_readonly(&mut self, all: bool) {
+        match &mut self.kind {
+            TypeDefKind::Interface { properties } => {
+                properties.iter_mut().for_each(|p| p.mark_readonly(all));
+            }
+            TypeDefKind::TypeAlias { ty } => ty.mark_readonly(all),
+            TypeDefKind::ConstEnum { .. } => {}
+        }
+    }
+
+    /// Push the names of the types referenced in this definition onto `out`
+    pub fn collect_refs(&self, out: &mut Vec<String>) {
+        match &self.kind {
+            TypeDefKind::Interface { properties } => {
+                properties.iter().for_each(|p| p.ty.collect_refs(out));
+            }
+            TypeDefKind::TypeAlias { ty } => ty.collect_refs(out),
+            TypeDefKind::ConstEnum { .. } => {}
+        }
+    }
+}
@@ -0,0 +1,450 @@
+//! Mock value synthesis from OpenAPI schemas.
+//!
+//! Produces realistic JSON example data for request bodies and responses.
+//! Explicit `example`/`examples`, `default`, `const` and `enum` values are preferred;
+//! everything else falls back to a deterministic placeholder for its type.
+//!
+//! Request and response mocks differ: `readOnly` properties are omitted from
+//! request mocks and `writeOnly` properties are omitted from response mocks.
+//!
+//! [`example_for_schema`] instead builds the smallest value matching a component's
+//! generated type, for typed fixtures.
+
+use serde_json::{Map, Value};
+
+use crate::openapi::spec::{
+    AdditionalProperties, Components, EnumValue, OpenApiSpec, Operation, Response, Schema,
+    SchemaType,
+};
+
+/// Maximum `$ref` nesting depth before recursive schemas are cut off.
+const MAX_DEPTH: usize = 8;
+
+/// Which side of the HTTP exchange a mock is generated for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MockDirection {
+    /// Data sent by the client (request bodies, parameters).
+    Request,
+    /// Data sent by the server (response bodies).
+    Response,
+}
+
+/// Generate a mock JSON value for `schema`, resolving `$ref`s against `components`.
+pub fn mock_value(
+    schema: &Schema,
+    components: Option<&Components>,
+    direction: MockDirection,
+) -> Value {
+    MockBuilder {
+        components,
+        fill: Fill::Full(direction),
+    }
+    .value(schema, 0)
+}
+
+/// Build an example value for the component schema at `ref_path` (`#/components/schemas/Item`).
+///
+/// Explicit `example`/`examples`, `const`, `default` and `enum` values are preferred.
+/// Otherwise the smallest value of the schema's type is synthesized: empty arrays,
+/// and objects holding only their required properties. Unknown references yield `null`.
+pub fn example_for_schema(spec: &OpenApiSpec, ref_path: &str) -> Value {
+    let builder = MockBuilder {
+        components: spec.components.as_ref(),
+        fill: Fill::Minimal,
+    };
+    builder
+        .resolve(ref_path)
+        .map_or(Value::Null, |schema| builder.value(schema, 1))
+}
+
+/// Generate a mock JSON request body for an operation, if it accepts one.
+pub fn mock_request_body(op: &Operation, components: Option<&Components>) -> Option<Value> {
+    let content = op.request_body.as_ref()?.content.as_ref()?;
+    let schema = content.get("application/json")?.schema.as_ref()?;
+    Some(mock_value(schema, components, MockDirection::Request))
+}
+
+/// The first successful response of an operation, with its status code as written in the spec.
+///
+/// `204` is only picked when no other success response is declared, since it has no body to mock.
+pub fn success_response(op: &Operation) -> Option<(&str, &Response)> {
+    [
+        "200", "201", "202", "203", "206", "207", "default", "2XX", "204",
+    ]
+    .iter()
+    .find_map(|code| op.responses.get_key_value(*code))
+    .map(|(code, response)| (code.as_str(), response))
+}
+
+/// Generate a mock JSON body for the first successful response of an operation.
+pub fn mock_response_body(op: &Operation, components: Option<&Components>) -> Option<Value> {
+    let (_, response) = success_response(op)?;
+    let schema = response
+        .content
+        .as_ref()?
+        .get("application/json")?
+        .schema
+        .as_ref()?;
+    Some(mock_value(schema, components, MockDirection::Response))
+}
+
+/// How much of a schema a [`MockBuilder`] fills in.
+#[derive(Clone, Copy)]
+enum Fill {
+    /// Every property and one item per array, minus those hidden in the direction.
+    Full(MockDirection),
+    /// Required properties only and empty arrays.
+    Minimal,
+}
+
+struct MockBuilder<'a> {
+    components: Option<&'a Components>,
+    fill: Fill,
+}
+
+impl MockBuilder<'_> {
+    fn resolve(&self, ref_path: &str) -> Option<&Schema> {
+        let name = ref_path.strip_prefix("#/components/schemas/")?;
+        self.components?.schemas.as_ref()?.get(name)
+    }
+
+    /// Whether a property should be left out for the current direction.
+    fn is_excluded(&self, schema: &Schema) -> bool {
+        let resolved = schema
+            .ref_path
+            .as_deref()
+            .and_then(|r| self.resolve(r))
+            .unwrap_or(schema);
+        let flagged = |s: &Schema| match self.fill {
+            Fill::Full(MockDirection::Request) => s.read_only == Some(true),
+            Fill::Full(MockDirection::Response) => s.write_only == Some(true),
+            Fill::Minimal => false,
+        };
+        flagged(schema) || flagged(resolved)
+    }
+
+    fn value(&self, schema: &Schema, depth: usize) -> Value {
+        if let Some(example) = schema
+            .example
+            .as_ref()
+            .or_else(|| schema.examples.as_ref()?.first())
+        {
+            return example.clone();
+        }
+        if let Some(const_value) = &schema.const_value {
+            return const_value.clone();
+        }
+        if let Some(default) = &schema.default {
+            return default.clone();
+        }
+        if let Some(first) = schema.enum_values.as_ref().and_then(|v| v.first()) {
+            return enum_value_to_json(first);
+        }
+
+        if let Some(ref_path) = &schema.ref_path {
+            if depth >= MAX_DEPTH {
+                return Value::Null;
+            }
+            return self
+                .resolve(ref_path)
+                .map_or(Value::Null, |resolved| self.value(resolved, depth + 1));
+        }
+
+        if let Some(all_of) = &schema.all_of {
+            let mut merged = Map::new();
+            for part in all_of {
+                match self.value(part, depth) {
+                    Value::Object(obj) => merged.extend(obj),
+                    other if all_of.len() == 1 => return other,
+                    _ => {}
+                }
+            }
+            return Value::Object(merged);
+        }
+
+        if let Some(variants) = schema.any_of.as_ref().or(schema.one_of.as_ref()) {
+            return variants
+                .iter()
+                .find(|s| !is_null_schema(s))
+                .map_or(Value::Null, |s| self.value(s, depth));
+        }
+
+        let schema_type = match &schema.schema_type {
+            Some(SchemaType::Single(t)) => Some(t.as_str()),
+            Some(SchemaType::Multiple(types)) => {
+                types.iter().map(String::as_str).find(|t| *t != "null")
+            }
+            None if schema.properties.is_some() || schema.additional_properties.is_some() => {
+                Some("object")
+            }
+            None => None,
+        };
+
+        match schema_type {
+            Some("string") => Value::String(mock_string(schema.format.as_deref())),
+            Some("integer") => Value::from(0),
+            Some("number") => Value::from(0.0),
+            Some("boolean") => Value::Bool(false),
+            Some("array") if matches!(self.fill, Fill::Minimal) => Value::Array(Vec::new()),
+            Some("array") => {
+                let item = schema
+                    .items
+                    .as_ref()
+                    .map_or(Value::Null, |items| self.value(items, depth));
+                Value::Array(vec![item])
+            }
+            Some("object") => self.object(schema, depth),
+            _ => Value::Null,
+        }
+    }
+
+    fn object(&self, schema: &Schema, depth: usize) -> Value {
+        let mut obj = Map::new();
+
+        if let Some(properties) = &schema.properties {
+            let mut names: Vec<_> = properties.keys().collect();
+            names.sort();
+            for name in names {
+                let Some(prop) = properties.get(name) else {
+                    continue;
+                };
+                if self.is_excluded(prop) {
+                    continue;
+                }
+                if matches!(self.fill, Fill::Minimal)
+                    && !schema.required.as_ref().is_some_and(|r| r.contains(name))
+                {
+                    continue;
+                }
+                obj.insert(name.clone(), self.value(prop, depth));
+            }
+        } else if let Some(AdditionalProperties::Schema(value_schema)) =
+            &schema.additional_properties
+            && matches!(self.fill, Fill::Full(_))
+        {
+            obj.insert("key".to_string(), self.value(value_schema, depth));
+        }
+
+        Value::Object(obj)
+    }
+}
+
+fn is_null_schema(schema: &Schema) -> bool {
+    matches!(&schema.schema_type, Some(SchemaType::Single(t)) if t == "null")
+}
+
+fn enum_value_to_json(v: &EnumValue) -> Value {
+    match v {
+        EnumValue::String(s) => Value::String(s.clone()),
+        EnumValue::Integer(n) => Value::from(*n),
+        EnumValue::Float(f) => Value::from(*f),
+        EnumValue::Bool(b) => Value::Bool(*b),
+        EnumValue::Null => Value::Null,
+    }
+}
+
+/// Placeholder string for a given `format`.
+fn mock_string(format: Option<&str>) -> String {
+    match format {
+        Some("date-time") => "2024-01-01T00:00:00Z",
+        Some("date") => "2024-01-01",
+        Some("time") => "00:00:00",
+        Some("email") => "user@example.com",
+        Some("uuid") => "00000000-0000-0000-0000-000000000000",
+        Some("uri" | "url") => "https://example.com",
+        Some("password") => "password",
+        _ => "string",
+    }
+    .to_string()
+}
+
+#[cfg(test)]
+// Reason: panicking on failure is idiomatic in tests
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use crate::openapi::spec::OpenApiSpec;
+
+    const USER_SPEC: &str = r##"{
+  "openapi": "3.1.0",
+  "info": { "title": "Users", "version": "1.0.0" },
+  "paths": {
+    "/users": {
+      "post": {
+        "operationId": "createUser",
+        "requestBody": { "required": true, "content": { "application/json": { "schema": { "$ref": "#/components/schemas/User" } } } },
+        "responses": {
+          "201": { "description": "Created", "content": { "application/json": { "schema": { "$ref": "#/components/schemas/User" } } } }
+        }
+      }
+    }
+  },
+  "components": {
+    "schemas": {
+      "User": {
+        "type": "object",
+        "required": ["id", "email", "password"],
+        "properties": {
+          "id": { "type": "string", "format": "uuid", "readOnly": true },
+          "email": { "type": "string", "format": "email" },
+          "password": { "type": "string", "writeOnly": true },
+          "role": { "type": "string", "enum": ["admin", "member"] }
+        }
+      }
+    }
+  }
+}"##;
+
+    fn create_user() -> (OpenApiSpec, Operation) {
+        let spec = OpenApiSpec::from_json(USER_SPEC).unwrap();
+        let op = spec.paths["/users"].post.clone().unwrap();
+        (spec, op)
+    }
+
+    #[test]
+    fn test_write_only_only_in_request_mock() {
+        let (spec, op) = create_user();
+        let request = mock_request_body(&op, spec.components.as_ref()).unwrap();
+        let response = mock_response_body(&op, spec.components.as_ref()).unwrap();
+
+        assert_eq!(request["password"], "password");
+        assert!(
+            response.get("password").is_none(),
+            "writeOnly field leaked into response mock: {response}"
+        );
+    }
+
+    #[test]
+    fn test_read_only_only_in_response_mock() {
+        let (spec, op) = create_user();
+        let request = mock_request_body(&op, spec.components.as_ref()).unwrap();
+        let response = mock_response_body(&op, spec.components.as_ref()).unwrap();
+
+        assert!(
+            request.get("id").is_none(),
+            "readOnly field leaked into request mock: {request}"
+        );
+        assert_eq!(response["id"], "00000000-0000-0000-0000-000000000000");
+        assert_eq!(response["email"], "user@example.com");
+        assert_eq!(response["role"], "admin");
+    }
+
+    #[test]
+    fn test_success_response_prefers_a_body_over_204() {
+        let spec = OpenApiSpec::from_json(
+            r##"{
+  "paths": {
+    "/items": {
+      "delete": {
+        "responses": {
+          "204": { "description": "Deleted" },
+          "default": { "description": "Result", "content": { "application/json": { "schema": { "type": "integer" } } } }
+        }
+      },
+      "post": {
+        "responses": {
+          "204": { "description": "Created" },
+          "400": { "description": "Bad request" }
+        }
+      }
+    }
+  }
+}"##,
+        )
+        .unwrap();
+        let item = &spec.paths["/items"];
+        let delete = item.delete.as_ref().unwrap();
+        let post = item.post.as_ref().unwrap();
+
+        assert_eq!(
+            success_response(delete).map(|(code, _)| code),
+            Some("default")
+        );
+        assert_eq!(mock_response_body(delete, None), Some(Value::from(0)));
+        assert_eq!(success_response(post).map(|(code, _)| code), Some("204"));
+        assert_eq!(mock_response_body(post, None), None);
+    }
+
+    #[test]
+    fn test_recursive_schema_terminates() {
+        let spec = OpenApiSpec::from_json(
+            r##"{
+  "paths": {},
+  "components": {
+    "schemas": {
+      "Node": {
+        "type": "object",
+        "properties": { "child": { "$ref": "#/components/schemas/Node" } }
+      }
+    }
+  }
+}"##,
+        )
+        .unwrap();
+        let schema = &spec.components.as_ref().unwrap().schemas.as_ref().unwrap()["Node"];
+        let value = mock_value(schema, spec.components.as_ref(), MockDirection::Response);
+        assert!(value["child"]["child"].is_object());
+    }
+
+    #[test]
+    fn test_example_for_schema_is_minimal() {
+        let spec = OpenApiSpec::from_json(
+            r##"{
+  "paths": {},
+  "components": {
+    "schemas": {
+      "Item": {
+        "type": "object",
+        "required": ["id", "tags", "owner", "kind", "size"],
+        "properties": {
+          "id": { "type": "string", "readOnly": true },
+          "tags": { "type": "array", "items": { "type": "string" } },
+          "owner": { "$ref": "#/components/schemas/Owner" },
+          "kind": { "type": "string", "enum": ["small", "large"] },
+          "size": { "type": "integer", "default": 3 },
+          "note": { "type": "string" }
+        }
+      },
+      "Owner": { "type": "object", "properties": { "name": { "type": "string" } } },
+      "Point": { "type": "object", "example": { "x": 1, "y": 2 } },
+      "Node": {
+        "type": "object",
+        "required": ["next"],
+        "properties": { "next": { "$ref": "#/components/schemas/Node" } }
+      }
+    }
+  }
+}"##,
+        )
+        .unwrap();
+
+        assert_eq!(
+            example_for_schema(&spec, "#/components/schemas/Item"),
+            serde_json::json!({
+                "id": "string",
+                "tags": [],
+                "owner": {},
+                "kind": "small",
+                "size": 3
+            })
+        );
+        assert_eq!(
+            example_for_schema(&spec, "#/components/schemas/Point"),
+            serde_json::json!({ "x": 1, "y": 2 })
+        );
+        assert_eq!(
+            example_for_schema(&spec, "#/components/schemas/Missing"),
+            Value::Null
+        );
+
+        // A required self-reference is cut off at the depth limit
+        let mut node = &example_for_schema(&spec, "#/components/schemas/Node");
+        let mut depth = 0;
+        while let Some(next) = node.get("next") {
+            node = next;
+            depth += 1;
+        }
+        assert!(node.is_null());
+        assert!(depth < MAX_DEPTH);
+    }
+}
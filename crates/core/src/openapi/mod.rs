@@ -4,13 +4,19 @@
 //! - Type definitions from component schemas
 //! - Fetch-based API client functions
 //! - React Query hooks (useQuery, useSuspenseQuery, useMutation)
+//! - Mock request/response data and typed example fixtures for tests
 
 mod emitter;
 mod ir;
+pub mod mock;
+mod options;
 pub mod spec;
+mod warnings;
 
-pub use emitter::generate;
+pub use emitter::{GenerateResult, generate, generate_detailed};
 pub use ir::utils::capitalize_first;
+pub use options::{Banner, BaseUrl, GenerateOptions, HookNaming, ReadonlyProps};
+pub use warnings::{GenerateWarning, WarningKind};
 
 #[cfg(test)]
 // Reason: panicking on failure is idiomatic in tests
@@ -194,29 +200,2059 @@ mod tests {
         println!("Generated TypeScript code length: {} bytes", ts_code.len());
     }
 
+    #[test]
+    fn test_route_table_lists_all_operations() {
+        let ts_code = generate_and_verify(TEST_OPENAPI_JSON);
+        let normalized = normalize_ws(&ts_code);
+
+        assert!(
+            normalized.contains(
+                "export type HttpMethod = \"GET\" | \"POST\" | \"PUT\" | \"PATCH\" | \"DELETE\";"
+            ),
+            "Missing HttpMethod type"
+        );
+        assert!(
+            normalized.contains("export const apiRoutes: ApiRoute[] = ["),
+            "Missing apiRoutes table"
+        );
+
+        for (operation_id, method, path) in [
+            ("listItems", "GET", "/items"),
+            ("createItem", "POST", "/items"),
+            ("getItem", "GET", "/items/{itemId}"),
+            ("replaceItem", "PUT", "/items/{itemId}"),
+            ("patchItem", "PATCH", "/items/{itemId}"),
+            ("deleteItem", "DELETE", "/items/{itemId}"),
+            ("search", "POST", "/search"),
+        ] {
+            let entry = format!(
+                "operationId: \"{operation_id}\", method: \"{method}\", pathTemplate: \"{path}\""
+            );
+            assert!(normalized.contains(&entry), "Missing route entry: {entry}");
+        }
+    }
+
+    #[test]
+    fn test_links_build_urls_without_fetch() {
+        let openapi_json = r#"{
+  "openapi": "3.1.0",
+  "info": { "title": "Links API", "version": "1.0.0" },
+  "paths": {
+    "/items": {
+      "get": {
+        "operationId": "listItems",
+        "parameters": [
+          { "name": "limit", "in": "query", "schema": { "type": "integer" } },
+          { "name": "tags", "in": "query", "schema": { "type": "array", "items": { "type": "string" } } },
+          { "name": "cursor", "in": "query", "schema": { "type": ["string", "null"] } }
+        ],
+        "responses": { "200": { "description": "OK" } }
+      },
+      "post": {
+        "operationId": "createItem",
+        "responses": { "201": { "description": "Created" } }
+      }
+    },
+    "/items/{itemId}": {
+      "get": {
+        "operationId": "getItem",
+        "parameters": [
+          { "name": "itemId", "in": "path", "required": true, "schema": { "type": "string" } }
+        ],
+        "responses": { "200": { "description": "OK" } }
+      }
+    }
+  }
+}"#;
+        let ts_code = generate_and_verify(openapi_json);
+        let normalized = normalize_ws(&ts_code);
+        assert!(
+            normalized.contains("export const links = {"),
+            "Missing links object"
+        );
+        assert!(
+            !normalized.contains("createItem: ("),
+            "Non-GET operations should not get a link builder"
+        );
+
+        let script = r#"
+console.log(links.listItems({ limit: 10 }));
+console.log(links.listItems({ tags: ["a", "b"] }));
+console.log(links.listItems({ cursor: null }));
+console.log(links.listItems());
+console.log(links.getItem({ itemId: "42" }));
+"#;
+        let output = run_generated_code(&ts_code, script);
+        assert!(output.is_ok(), "Running links failed: {:?}", output.err());
+        let lines: Vec<String> = output
+            .unwrap_or_default()
+            .lines()
+            .map(str::to_string)
+            .collect();
+        assert_eq!(
+            lines,
+            vec![
+                "/items?limit=10",
+                "/items?tags=a&tags=b",
+                "/items",
+                "/items",
+                "/items/42",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_env_base_url_with_relative_fallback() {
+        let options = GenerateOptions {
+            base_url: BaseUrl::Env {
+                var: "VITE_API_URL".into(),
+                fallback: String::new(),
+            },
+            ..GenerateOptions::default()
+        };
+        let ts_code = generate(TEST_OPENAPI_JSON, &options).unwrap();
+        let normalized = normalize_ws(&ts_code);
+
+        assert!(
+            normalized.contains(
+                "export const API_BASE_URL: string = import.meta.env.VITE_API_URL ?? \"\";"
+            ),
+            "Missing env-aware base URL. Generated:\n{ts_code}"
+        );
+        assert!(
+            normalized.contains("fetcher(`${API_BASE_URL}/search`"),
+            "Static URL should be prefixed. Generated:\n{ts_code}"
+        );
+        assert!(
+            normalized.contains("`${API_BASE_URL}/items?${queryString}` : `${API_BASE_URL}/items`"),
+            "Query URL should be prefixed. Generated:\n{ts_code}"
+        );
+
+        // The default options keep requests relative.
+        let relative = generate(TEST_OPENAPI_JSON, &GenerateOptions::default()).unwrap();
+        assert!(!relative.contains("API_BASE_URL"));
+    }
+
+    #[test]
+    fn test_fixed_base_url_compiles() {
+        let options = GenerateOptions {
+            base_url: BaseUrl::Fixed("https://api.example.com/".into()),
+            ..GenerateOptions::default()
+        };
+        let ts_code = generate(TEST_OPENAPI_JSON, &options).unwrap();
+        assert!(
+            normalize_ws(&ts_code)
+                .contains("export const API_BASE_URL: string = \"https://api.example.com\";"),
+            "Missing fixed base URL. Generated:\n{ts_code}"
+        );
+        typecheck_generated_code(&ts_code).expect("generated code should compile");
+    }
+
+    #[test]
+    fn test_server_variables_base_url() {
+        let openapi_json = r##"{
+  "openapi": "3.1.0",
+  "info": { "title": "Server API", "version": "1.0.0" },
+  "servers": [{
+    "url": "https://{region}.api.example.com/{basePath}/",
+    "variables": {
+      "region": { "default": "us", "enum": ["us", "eu"] },
+      "basePath": { "default": "v1" }
+    }
+  }],
+  "paths": {
+    "/ping": {
+      "get": {
+        "operationId": "ping",
+        "responses": { "200": { "description": "OK", "content": { "application/json": { "schema": { "type": "string" } } } } }
+      }
+    }
+  }
+}"##;
+
+        // Servers are ignored unless requested
+        assert!(
+            !generate(openapi_json, &GenerateOptions::default())
+                .unwrap()
+                .contains("API_BASE_URL")
+        );
+
+        let options = GenerateOptions {
+            base_url: BaseUrl::Server,
+            ..GenerateOptions::default()
+        };
+        let ts_code = generate(openapi_json, &options).unwrap();
+        // Compare without whitespace so the check is independent of SWC's formatting
+        let compact: String = ts_code.split_whitespace().collect();
+        for expected in [
+            r#"exportinterfaceServerVariables{basePath:string;region:"us"|"eu";}"#,
+            "${encodeURIComponent(variables.region)}.api.example.com/${encodeURIComponent(variables.basePath)}`",
+            r#"constserverVariables:ServerVariables={basePath:"v1",region:"us"};"#,
+            "exportletAPI_BASE_URL:string=serverUrl(serverVariables);",
+            "exportconstsetServerVariables=(variables:Partial<ServerVariables>):void=>",
+        ] {
+            assert!(
+                compact.contains(expected),
+                "Missing `{expected}`. Generated:\n{ts_code}"
+            );
+        }
+        typecheck_generated_code(&ts_code).expect("server base URL should compile");
+
+        let output = run_generated_code(
+            &ts_code,
+            r#"globalThis.fetch = (async (url: string) => {
+  console.log(url);
+  return new Response("\"ok\"", { headers: { "content-type": "application/json" } });
+}) as typeof fetch;
+await ping();
+setServerVariables({ region: "eu", basePath: "v 2" });
+await ping();"#,
+        )
+        .unwrap();
+        assert_eq!(
+            output.trim(),
+            "https://us.api.example.com/v1/ping\nhttps://eu.api.example.com/v%202/ping"
+        );
+
+        // Undeclared and default-less variables are rejected
+        let undeclared = openapi_json.replace(
+            r#""basePath": { "default": "v1" }"#,
+            r#""other": { "default": "v1" }"#,
+        );
+        let err = generate(&undeclared, &options).unwrap_err();
+        assert!(err.contains("'basePath'"), "Got: {err}");
+        let no_default =
+            openapi_json.replace(r#""basePath": { "default": "v1" }"#, r#""basePath": {}"#);
+        let err = generate(&no_default, &options).unwrap_err();
+        assert!(
+            err.contains("'basePath'") && err.contains("default"),
+            "Got: {err}"
+        );
+    }
+
+    #[test]
+    fn test_set_fetcher_routes_requests() {
+        let ts_code = generate_and_verify(TEST_OPENAPI_JSON);
+        let normalized = normalize_ws(&ts_code);
+        assert!(
+            normalized.contains("let fetcher: typeof fetch = (input, init) => fetch(input, init);"),
+            "Missing fetcher. Generated:\n{ts_code}"
+        );
+        assert!(
+            normalized.contains("export const setFetcher = (fn: typeof fetch): void =>"),
+            "Missing setFetcher. Generated:\n{ts_code}"
+        );
+        assert!(!normalized.contains("await fetch("));
+
+        let output = run_generated_code(
+            &ts_code,
+            r#"setFetcher((async (input: RequestInfo | URL, init?: RequestInit) => {
+  console.log(`${init?.method} ${input}`);
+  return new Response("{}", { headers: { "content-type": "application/json" } });
+}) as typeof fetch);
+await getItem({ itemId: "42" });"#,
+        )
+        .unwrap();
+        assert_eq!(output.trim(), "GET /items/42");
+    }
+
+    #[test]
+    fn test_default_headers_merge_with_call_headers() {
+        let ts_code = generate_and_verify(TEST_OPENAPI_JSON);
+        let compact: String = ts_code.split_whitespace().collect();
+        for expected in [
+            "letdefaultHeaders:Record<string,string>={};",
+            "exportconstsetDefaultHeaders=(headers:Record<string,string>):void=>",
+            r#"headers:{...defaultHeaders,"Content-Type":"application/json",...options?.headers}"#,
+        ] {
+            assert!(
+                compact.contains(expected),
+                "Missing `{expected}`. Generated:\n{ts_code}"
+            );
+        }
+
+        let script = r#"setFetcher((async (_input: RequestInfo | URL, init?: RequestInit) => {
+  const headers: Record<string, string> = { ...(init?.headers as Record<string, string>) };
+  console.log(JSON.stringify(headers));
+  return new Response("{}", { headers: { "content-type": "application/json" } });
+}) as typeof fetch);
+setDefaultHeaders({ Prefer: "return=representation", "X-Client-Version": "1.2.0" });
+await getItem({ itemId: "1" }, { headers: { Prefer: "return=minimal" } });
+await createItem({ name: "pen" });"#;
+        typecheck_generated_code(&format!(
+            "{ts_code}\nexport const demo = async () => {{\n{script}\n}};"
+        ))
+        .expect("default headers should typecheck");
+        let output = run_generated_code(&ts_code, script).unwrap();
+        assert_eq!(
+            output.trim(),
+            r#"{"Prefer":"return=minimal","X-Client-Version":"1.2.0"}
+{"Prefer":"return=representation","X-Client-Version":"1.2.0","Content-Type":"application/json"}"#
+        );
+    }
+
+    #[test]
+    fn test_request_timeout_aborts_with_timeout_error() {
+        let ts_code = generate_and_verify(TEST_OPENAPI_JSON);
+        let normalized = normalize_ws(&ts_code);
+        assert!(
+            normalized.contains("export type ApiRequestInit = RequestInit & {"),
+            "Missing ApiRequestInit. Generated:\n{ts_code}"
+        );
+        assert!(
+            normalized.contains("options?: ApiRequestInit"),
+            "Fetch functions should accept timeoutMs. Generated:\n{ts_code}"
+        );
+        assert!(
+            normalized.contains("export class ApiTimeoutError extends ApiError"),
+            "Missing ApiTimeoutError. Generated:\n{ts_code}"
+        );
+
+        let output = run_generated_code(
+            &ts_code,
+            r#"setFetcher(((_input: RequestInfo | URL, init?: RequestInit) =>
+  new Promise<Response>((_resolve, reject) => {
+    init?.signal?.addEventListener("abort", () => reject(new DOMException("Aborted", "AbortError")));
+  })) as typeof fetch);
+try {
+  await getItem({ itemId: "1" }, { timeoutMs: 10 });
+} catch (e) {
+  console.log(e instanceof ApiTimeoutError, e instanceof ApiError, (e as ApiTimeoutError).timeoutMs);
+}
+const caller = new AbortController();
+const pending = getItem({ itemId: "1" }, { timeoutMs: 60000, signal: caller.signal });
+caller.abort();
+try {
+  await pending;
+} catch (e) {
+  console.log(e instanceof ApiTimeoutError, (e as Error).name);
+}"#,
+        )
+        .unwrap();
+        assert_eq!(output.trim(), "true true 10\nfalse AbortError");
+    }
+
+    #[test]
+    fn test_api_error_kind_classifies_failures() {
+        let ts_code = generate_and_verify(TEST_OPENAPI_JSON);
+        let normalized = normalize_ws(&ts_code);
+        for expected in [
+            r#"export type ApiErrorKind = "http" | "network" | "parse" | "timeout";"#,
+            r#"declare kind: "network";"#,
+            r#"export type ApiFailure<TBody = unknown> = (ApiError<TBody> & { kind: "http"; }) | ApiNetworkError | ApiParseError | ApiTimeoutError;"#,
+        ] {
+            assert!(
+                normalized.contains(expected),
+                "Missing `{expected}`. Generated:\n{ts_code}"
+            );
+        }
+
+        // Hooks' error type narrows on `kind`
+        let describe = r#"
+export const describeFailure = (e: ApiFailure<{ detail: string }>): string => {
+  switch (e.kind) {
+    case "http": return `http ${e.status} ${e.body.detail}`;
+    case "network": return `network ${String(e.cause)}`;
+    case "parse": return `parse ${e.status} ${e.text}`;
+    case "timeout": return `timeout ${e.timeoutMs}`;
+  }
+};"#;
+        typecheck_generated_code(&format!("{ts_code}\n{describe}"))
+            .expect("failure kinds should narrow");
+
+        let output = run_generated_code(
+            &ts_code,
+            r#"const report = (e: unknown) => {
+  const err = e as ApiFailure;
+  console.log(err.kind, err instanceof ApiError, err.kind === "parse" ? err.text : err.status);
+};
+setFetcher((async () => { throw new TypeError("fetch failed"); }) as typeof fetch);
+try { await getItem({ itemId: "1" }); } catch (e) { report(e); console.log(String((e as ApiNetworkError).cause)); }
+setFetcher((async () => new Response("not json", { headers: { "content-type": "application/json" } })) as typeof fetch);
+try { await getItem({ itemId: "1" }); } catch (e) { report(e); }
+setFetcher((async () => new Response("{}", { status: 404 })) as typeof fetch);
+try { await getItem({ itemId: "1" }); } catch (e) { report(e); }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            output.trim(),
+            "network true 0\nTypeError: fetch failed\nparse true not json\nhttp true 404"
+        );
+    }
+
+    #[test]
+    fn test_fetch_retry_backs_off_on_retryable_status() {
+        let default_code = generate_and_verify(TEST_OPENAPI_JSON);
+        assert!(!default_code.contains("fetchWithRetry"));
+
+        let options = GenerateOptions {
+            fetch_retry: true,
+            ..GenerateOptions::default()
+        };
+        let ts_code = generate(TEST_OPENAPI_JSON, &options).unwrap();
+        let normalized = normalize_ws(&ts_code);
+        assert!(
+            normalized.contains("retries?: number; retryBaseMs?: number; retryOn?: number[];"),
+            "ApiRequestInit should take retry options. Generated:\n{ts_code}"
+        );
+        assert!(normalized.contains("const res = await fetchWithRetry(url, {"));
+        typecheck_generated_code(&ts_code).expect("retrying client should compile");
+
+        let output = run_generated_code(
+            &ts_code,
+            r#"let calls = 0;
+let busy = 0;
+setFetcher((async () => {
+  calls++;
+  if (busy > 0) {
+    busy--;
+    return new Response("busy", { status: 503, headers: { "retry-after": "0" } });
+  }
+  return new Response("{}", { headers: { "content-type": "application/json" } });
+}) as typeof fetch);
+busy = 1; calls = 0;
+await getItem({ itemId: "1" });
+console.log("get", calls);
+busy = 5; calls = 0;
+try { await getItem({ itemId: "1" }, { retries: 1 }); } catch (e) { console.log("exhausted", calls, (e as ApiError).status); }
+busy = 1; calls = 0;
+try { await createItem({ name: "a" }); } catch (e) { console.log("post", calls, (e as ApiError).status); }
+busy = 1; calls = 0;
+await createItem({ name: "a" }, { retries: 1 });
+console.log("post opt-in", calls);
+console.log(parseRetryAfter("2"), parseRetryAfter(null), parseRetryAfter("soon"));"#,
+        )
+        .unwrap();
+        assert_eq!(
+            output.trim(),
+            "get 2\nexhausted 2 503\npost 1 503\npost opt-in 2\n2000 undefined undefined"
+        );
+    }
+
+    #[test]
+    fn test_bigint_int64_option() {
+        let openapi_json = r##"{
+  "openapi": "3.1.0",
+  "info": { "title": "BigInt API", "version": "1.0.0" },
+  "paths": {
+    "/items/{itemId}": {
+      "get": {
+        "operationId": "getItem",
+        "parameters": [
+          { "name": "itemId", "in": "path", "required": true, "schema": { "type": "integer", "format": "int64" } },
+          { "name": "after", "in": "query", "schema": { "type": "integer", "format": "uint64" } }
+        ],
+        "responses": { "200": { "description": "OK", "content": { "application/json": { "schema": { "$ref": "#/components/schemas/Item" } } } } }
+      }
+    },
+    "/items": {
+      "post": {
+        "operationId": "createItem",
+        "requestBody": { "required": true, "content": { "application/json": { "schema": { "$ref": "#/components/schemas/Item" } } } },
+        "responses": { "200": { "description": "OK", "content": { "application/json": { "schema": { "type": "array", "items": { "$ref": "#/components/schemas/Item" } } } } } }
+      }
+    }
+  },
+  "components": {
+    "schemas": {
+      "Item": {
+        "type": "object",
+        "required": ["id", "count"],
+        "properties": {
+          "id": { "type": "integer", "format": "int64" },
+          "count": { "type": "integer", "format": "int32" },
+          "owner": { "$ref": "#/components/schemas/Owner" },
+          "tags": { "type": "object", "additionalProperties": { "type": "integer", "format": "int64" } }
+        }
+      },
+      "Owner": {
+        "type": "object",
+        "required": ["id"],
+        "properties": { "id": { "type": "integer", "format": "int32" } }
+      }
+    }
+  }
+}"##;
+
+        // Default: int64 stays `number` and responses use plain JSON.parse
+        let default_code = generate(openapi_json, &GenerateOptions::default()).unwrap();
+        let normalized = normalize_ws(&default_code);
+        assert!(
+            normalized.contains("id: number;"),
+            "Generated:\n{default_code}"
+        );
+        assert!(!default_code.contains("parseJsonWithBigInt"));
+        assert!(!default_code.contains("stringifyJsonWithBigInt"));
+
+        let options = GenerateOptions {
+            bigint_int64: true,
+            ..GenerateOptions::default()
+        };
+        let ts_code = generate(openapi_json, &options).unwrap();
+        let normalized = normalize_ws(&ts_code);
+        for expected in [
+            "id: bigint;",
+            "count: number;",
+            "itemId: bigint;",
+            "after?: bigint;",
+            "searchParams.set(\"after\", String(params?.after))",
+            "data: await parseJsonBody(res, (text) => parseJsonWithBigInt(text, [[\"id\"], [\"tags\", null]]))",
+            "data: await parseJsonBody(res, (text) => parseJsonWithBigInt(text, [[null, \"id\"], [null, \"tags\", null]]))",
+            "body: stringifyJsonWithBigInt(data)",
+        ] {
+            assert!(
+                normalized.contains(&normalize_ws(expected)),
+                "Missing `{expected}`. Generated:\n{ts_code}"
+            );
+        }
+        typecheck_generated_code(&ts_code).expect("bigint fields should compile");
+
+        let output = run_generated_code(
+            &ts_code,
+            r#"const item = parseJsonWithBigInt('{"id": 42, "count": 1, "owner": {"id": 7}, "tags": {"a": 3}}', [["id"], ["tags", null]]);
+console.log(typeof item.id, typeof item.count, typeof item.owner.id, typeof item.tags.a);
+const items = parseJsonWithBigInt('[{"id": 1}, {"id": 2, "tags": null}]', [[null, "id"], [null, "tags", null]]);
+console.log(items.map((i: { id: unknown }) => typeof i.id).join(), parseJsonWithBigInt("7", [[]]));
+console.log(stringifyJsonWithBigInt({ id: 5n }));"#,
+        )
+        .unwrap();
+        assert_eq!(
+            output.trim(),
+            "bigint number number bigint\nbigint,bigint 7n\n{\"id\":\"5\"}"
+        );
+
+        // MSW mocks hold `bigint` literals at the int64 paths only
+        let options = GenerateOptions {
+            bigint_int64: true,
+            msw_handlers: true,
+            ..GenerateOptions::default()
+        };
+        let result = generate_detailed(openapi_json, &options).unwrap();
+        let handlers = result.msw_handlers.expect("handlers should be generated");
+        let compact: String = handlers.split_whitespace().collect();
+        for expected in [
+            "conststringifyJsonWithBigInt=",
+            r#"newHttpResponse(stringifyJsonWithBigInt({count:0,id:0n,owner:{id:0},tags:{key:0n}}asItem),{status:200,headers:{"Content-Type":"application/json"}})"#,
+            "stringifyJsonWithBigInt([{count:0,id:0n,owner:{id:0},tags:{key:0n}}]asItem[])",
+        ] {
+            assert!(
+                compact.contains(expected),
+                "Missing `{expected}`. Generated:\n{handlers}"
+            );
+        }
+        typecheck_generated_files(&[("api.ts", &result.code), ("handlers.ts", &handlers)])
+            .expect("bigint mocks should compile against the generated client");
+    }
+
+    #[test]
+    fn test_msw_handlers_compile_against_client() {
+        let openapi_json = r##"{
+  "openapi": "3.1.0",
+  "info": { "title": "Mock API", "version": "1.0.0" },
+  "paths": {
+    "/items": {
+      "get": {
+        "operationId": "listItems",
+        "responses": { "200": { "description": "OK", "content": { "application/json": { "schema": { "type": "array", "items": { "$ref": "#/components/schemas/Item" } } } } } }
+      },
+      "post": {
+        "operationId": "createItem",
+        "requestBody": { "required": true, "content": { "application/json": { "schema": { "$ref": "#/components/schemas/Item" } } } },
+        "responses": { "201": { "description": "Created", "content": { "application/json": { "schema": { "$ref": "#/components/schemas/Item" } } } } }
+      }
+    },
+    "/items/{itemId}": {
+      "delete": {
+        "operationId": "deleteItem",
+        "parameters": [{ "name": "itemId", "in": "path", "required": true, "schema": { "type": "string" } }],
+        "responses": { "204": { "description": "Deleted" } }
+      }
+    },
+    "/health": {
+      "get": {
+        "operationId": "health",
+        "responses": { "200": { "description": "OK", "content": { "text/plain": { "schema": { "type": "string" } } } } }
+      }
+    },
+    "/version": {
+      "get": {
+        "operationId": "version",
+        "responses": { "200": { "description": "OK", "content": { "text/plain": { "schema": { "type": "string", "example": "1.2.3" } } } } }
+      }
+    },
+    "/count": {
+      "get": {
+        "operationId": "count",
+        "responses": { "200": { "description": "OK", "content": { "text/plain": { "schema": { "type": "integer" } } } } }
+      }
+    }
+  },
+  "components": {
+    "schemas": {
+      "Item": {
+        "type": "object",
+        "required": ["id", "name", "status"],
+        "properties": {
+          "id": { "type": "string", "format": "uuid" },
+          "name": { "type": "string", "examples": ["Widget"] },
+          "status": { "type": "string", "enum": ["active", "archived"] },
+          "price": { "type": ["number", "null"] }
+        }
+      }
+    }
+  }
+}"##;
+
+        let default = generate_detailed(openapi_json, &GenerateOptions::default()).unwrap();
+        assert!(default.msw_handlers.is_none());
+
+        let options = GenerateOptions {
+            msw_handlers: true,
+            ..GenerateOptions::default()
+        };
+        let result = generate_detailed(openapi_json, &options).unwrap();
+        let handlers = result.msw_handlers.expect("handlers should be generated");
+        // Compare without whitespace so the check is independent of SWC's formatting
+        let compact: String = handlers.split_whitespace().collect();
+        for expected in [
+            r#"import{http,HttpResponse}from"msw";"#,
+            r#"importtype{Item}from"./api";"#,
+            r#"http.get("*/items",()=>HttpResponse.json([{"#,
+            r#"id:"00000000-0000-0000-0000-000000000000""#,
+            r#"name:"Widget""#,
+            r#"status:"active""#,
+            r#"asItem[],{status:200})"#,
+            r#"asItem,{status:201})"#,
+            r#"http.delete("*/items/:itemId",()=>newHttpResponse(null,{status:204}))"#,
+            r#"http.get("*/health",()=>HttpResponse.text("string",{status:200}))"#,
+            r#"http.get("*/version",()=>HttpResponse.text("1.2.3",{status:200}))"#,
+            r#"http.get("*/count",()=>HttpResponse.text("0",{status:200}))"#,
+        ] {
+            assert!(
+                compact.contains(expected),
+                "Missing `{expected}`. Generated:\n{handlers}"
+            );
+        }
+
+        typecheck_generated_files(&[("api.ts", &result.code), ("handlers.ts", &handlers)])
+            .expect("handlers should compile against the generated client");
+    }
+
+    #[test]
+    fn test_examples_compile_against_client() {
+        let openapi_json = r##"{
+  "openapi": "3.1.0",
+  "info": { "title": "Examples API", "version": "1.0.0" },
+  "paths": {
+    "/items": {
+      "get": {
+        "operationId": "listItems",
+        "responses": { "200": { "description": "OK", "content": { "application/json": { "schema": { "type": "array", "items": { "$ref": "#/components/schemas/Item" } } } } } }
+      }
+    }
+  },
+  "components": {
+    "schemas": {
+      "Item": {
+        "type": "object",
+        "required": ["id", "name", "status", "tags", "parent"],
+        "properties": {
+          "id": { "type": "string", "format": "uuid", "readOnly": true },
+          "name": { "type": "string", "examples": ["Widget"] },
+          "status": { "$ref": "#/components/schemas/Status" },
+          "tags": { "type": "array", "items": { "type": "string" } },
+          "parent": { "anyOf": [{ "$ref": "#/components/schemas/Item" }, { "type": "null" }] },
+          "price": { "type": "number" }
+        }
+      },
+      "Status": { "type": "string", "enum": ["active", "archived"] },
+      "Point-2D": { "type": "object", "example": { "x": 1, "y": 2 }, "properties": { "x": { "type": "number" }, "y": { "type": "number" } } }
+    }
+  }
+}"##;
+
+        let default = generate_detailed(openapi_json, &GenerateOptions::default()).unwrap();
+        assert!(default.examples.is_none());
+
+        let options = GenerateOptions {
+            examples: true,
+            ..GenerateOptions::default()
+        };
+        let result = generate_detailed(openapi_json, &options).unwrap();
+        let examples = result.examples.expect("examples should be generated");
+        // Compare without whitespace so the check is independent of SWC's formatting
+        let compact: String = examples.split_whitespace().collect();
+        for expected in [
+            r#"importtype{Item,Point2D,Status}from"./api";"#,
+            r#"exportconstexampleItem:Item={"#,
+            r#"name:"Widget""#,
+            r#"status:"active""#,
+            "tags:[]",
+            r#"exportconstexamplePoint2D:Point2D={x:1,y:2};"#,
+            r#"exportconstexampleStatus:Status="active";"#,
+        ] {
+            assert!(
+                compact.contains(expected),
+                "Missing `{expected}`. Generated:\n{examples}"
+            );
+        }
+        assert!(
+            !compact.contains("price"),
+            "optional property emitted:\n{examples}"
+        );
+
+        typecheck_generated_files(&[("api.ts", &result.code), ("examples.ts", &examples)])
+            .expect("examples should compile against the generated client");
+    }
+
+    #[test]
+    fn test_banner_names_spec_and_can_be_replaced() {
+        let options = GenerateOptions {
+            msw_handlers: true,
+            ..GenerateOptions::default()
+        };
+        let result = generate_detailed(TEST_OPENAPI_JSON, &options).unwrap();
+        let banner = "/* eslint-disable */\n// Generated by apx — do not edit. Source: Test API 1.0.0\n// @generated\n";
+        assert!(
+            result.code.starts_with(banner),
+            "Unexpected banner:\n{}",
+            result.code
+        );
+        assert!(result.msw_handlers.unwrap().starts_with(banner));
+
+        let unmarked = GenerateOptions {
+            banner: Banner::Standard {
+                generated_marker: false,
+            },
+            ..GenerateOptions::default()
+        };
+        let code = generate(TEST_OPENAPI_JSON, &unmarked).unwrap();
+        assert!(!code.contains("@generated"));
+        assert!(code.contains("do not edit"));
+
+        let custom = GenerateOptions {
+            banner: Banner::Custom("// Copyright Acme".into()),
+            ..GenerateOptions::default()
+        };
+        let code = generate(TEST_OPENAPI_JSON, &custom).unwrap();
+        assert!(code.starts_with("// Copyright Acme\n"));
+        assert!(!code.contains("eslint-disable"));
+
+        let none = GenerateOptions {
+            banner: Banner::None,
+            ..GenerateOptions::default()
+        };
+        let bare = generate(TEST_OPENAPI_JSON, &none).unwrap();
+        assert_eq!(
+            code.strip_prefix("// Copyright Acme\n"),
+            Some(bare.as_str())
+        );
+    }
+
+    #[test]
+    fn test_split_modules_import_each_other() {
+        let default = generate_detailed(TEST_OPENAPI_JSON, &GenerateOptions::default()).unwrap();
+        assert!(default.modules.is_none());
+
+        let options = GenerateOptions {
+            split_modules: true,
+            tag_groups: true,
+            banner: Banner::None,
+            ..GenerateOptions::default()
+        };
+        let result = generate_detailed(TEST_OPENAPI_JSON, &options).unwrap();
+        // The single-file client is still generated
+        assert_eq!(result.code, generate(TEST_OPENAPI_JSON, &options).unwrap());
+
+        let modules = result.modules.expect("modules should be generated");
+        assert_eq!(
+            modules.keys().collect::<Vec<_>>(),
+            ["client.ts", "hooks.ts", "index.ts", "types.ts"]
+        );
+        // Compare without whitespace so the check is independent of SWC's formatting
+        let compact = |name: &str| -> String { modules[name].split_whitespace().collect() };
+        assert_eq!(
+            compact("index.ts"),
+            r#"export*from"./types";export*from"./client";export*from"./hooks";"#
+        );
+        assert!(!compact("types.ts").contains("import"));
+        assert!(compact("types.ts").contains("exportinterfaceItem{"));
+        let client_import = modules["client.ts"].lines().next().unwrap_or_default();
+        assert!(
+            client_import.starts_with("import type {")
+                && client_import.contains(" Item")
+                && client_import.ends_with(r#"from "./types";"#),
+            "Unexpected client import: {client_import}"
+        );
+        assert!(!compact("client.ts").contains("@tanstack/react-query"));
+        assert!(compact("hooks.ts").contains(r#"}from"./client";"#));
+        assert!(compact("hooks.ts").contains("@tanstack/react-query"));
+
+        let usage = r#"import { ApiError, getItem, useListItems, type Item } from "./index";
+export const load = async (): Promise<Item> => (await getItem({ itemId: "1" })).data;
+export const useItems = () => useListItems();
+export const isApiError = (e: unknown): boolean => e instanceof ApiError;
+"#;
+        let mut files: Vec<(&str, &str)> = modules
+            .iter()
+            .map(|(name, code)| (name.as_str(), code.as_str()))
+            .collect();
+        files.push(("usage.ts", usage));
+        typecheck_generated_files(&files).expect("split modules should compile together");
+    }
+
+    #[test]
+    fn test_safe_results_alongside_throwing_functions() {
+        let default = generate(TEST_OPENAPI_JSON, &GenerateOptions::default()).unwrap();
+        assert!(!default.contains("safeGetItem"));
+
+        let options = GenerateOptions {
+            safe_results: true,
+            ..GenerateOptions::default()
+        };
+        let ts_code = generate(TEST_OPENAPI_JSON, &options).unwrap();
+        // Compare without whitespace so the check is independent of SWC's formatting
+        let compact: String = ts_code.split_whitespace().collect();
+        for expected in [
+            "exporttypeApiResult<T,TError=ApiFailure>={ok:true;data:T;}|{ok:false;error:TError;};",
+            "exportconstgetItem=async(",
+            "exportconstsafeGetItem=async(params:GetItemParams,options?:ApiRequestInit):Promise<ApiResult<Item,",
+            "constres=awaitgetItem(params,options);return{ok:true,data:res.data};",
+            "if(errorinstanceofApiError)return{ok:false,error:errorasApiFailure",
+            "throwerror;",
+            "awaitdeleteItem(params,options);return{ok:true,data:undefined};",
+        ] {
+            assert!(
+                compact.contains(expected),
+                "Missing `{expected}`. Generated:\n{ts_code}"
+            );
+        }
+
+        let usage = r#"import { getItem, safeDeleteItem, safeGetItem, type Item } from "./api";
+export const thrown = async (): Promise<Item> => (await getItem({ itemId: "1" })).data;
+export const safe = async (): Promise<Item | number> => {
+  const result = await safeGetItem({ itemId: "1" });
+  if (result.ok) return result.data;
+  return result.error.kind === "http" ? result.error.status : 0;
+};
+export const removed = async (): Promise<boolean> => (await safeDeleteItem({ itemId: "1" })).ok;
+"#;
+        typecheck_generated_files(&[("api.ts", &ts_code), ("usage.ts", usage)])
+            .expect("safe and throwing variants should compile under strict tsc");
+    }
+
+    #[test]
+    fn test_param_guards_check_required_params() {
+        let default = generate(TEST_OPENAPI_JSON, &GenerateOptions::default()).unwrap();
+        assert!(!default.contains("assertRequiredParams"));
+
+        let options = GenerateOptions {
+            param_guards: true,
+            ..GenerateOptions::default()
+        };
+        let ts_code = generate(TEST_OPENAPI_JSON, &options).unwrap();
+        // Compare without whitespace so the check is independent of SWC's formatting
+        let compact: String = ts_code.split_whitespace().collect();
+        for expected in [
+            "constassertRequiredParams=(operation:string,params:object|undefined,keys:string[]):void=>",
+            "constvalue=(paramsasRecord<string,unknown>|undefined)?.[key];",
+            r#"if(value==null||value==="")thrownewTypeError(`${operation}:requiredparam"${key}"ismissingorempty`);"#,
+            r#"exportconstassertGetItemParams=(params:GetItemParams):void=>assertRequiredParams("getItem",params,["itemId"]);"#,
+            "=>{assertGetItemParams(params);",
+        ] {
+            assert!(
+                compact.contains(expected),
+                "Missing `{expected}`. Generated:\n{ts_code}"
+            );
+        }
+        // Operations whose params are all optional get no guard
+        assert!(!ts_code.contains("assertListItemsParams"));
+
+        let usage = r#"import { assertGetItemParams, getItem } from "./api";
+export const load = (itemId: string) => {
+  const params = { itemId };
+  assertGetItemParams(params);
+  return getItem(params);
+};
+"#;
+        typecheck_generated_files(&[("api.ts", &ts_code), ("usage.ts", usage)])
+            .expect("params guards should compile under strict tsc");
+    }
+
+    #[test]
+    fn test_body_defaults_merged_into_payload() {
+        let openapi_json = r##"{
+  "openapi": "3.1.0",
+  "info": { "title": "Config API", "version": "1.0.0" },
+  "paths": {
+    "/configs": {
+      "post": {
+        "operationId": "createConfig",
+        "requestBody": { "required": true, "content": { "application/json": { "schema": { "$ref": "#/components/schemas/Config" } } } },
+        "responses": { "200": { "description": "OK", "content": { "application/json": { "schema": { "$ref": "#/components/schemas/Config" } } } } }
+      }
+    },
+    "/configs/{configId}": {
+      "put": {
+        "operationId": "replaceConfig",
+        "parameters": [{ "name": "configId", "in": "path", "required": true, "schema": { "type": "string" } }],
+        "requestBody": { "required": true, "content": { "application/json": { "schema": { "$ref": "#/components/schemas/Config" } } } },
+        "responses": { "204": { "description": "Replaced" } }
+      }
+    }
+  },
+  "components": {
+    "schemas": {
+      "Config": {
+        "type": "object",
+        "required": ["name", "retries"],
+        "properties": {
+          "name": { "type": "string" },
+          "retries": { "type": "integer", "default": 3 },
+          "limit": { "type": "integer", "format": "int64", "default": 100 },
+          "mode": { "type": "string", "enum": ["fast", "safe"], "default": "fast" },
+          "tags": { "type": "array", "items": { "type": "string" }, "default": [] }
+        }
+      }
+    }
+  }
+}"##;
+
+        // Default: bodies are sent as given
+        let default_code = generate(openapi_json, &GenerateOptions::default()).unwrap();
+        assert!(!default_code.contains("BodyDefaults"));
+        assert!(!default_code.contains("CreateConfigBody"));
+
+        let options = GenerateOptions {
+            body_defaults: true,
+            ..GenerateOptions::default()
+        };
+        let ts_code = generate(openapi_json, &options).unwrap();
+        // Compare without whitespace so the check is independent of SWC's formatting
+        let compact: String = ts_code.split_whitespace().collect();
+        for expected in [
+            r#"exporttypeCreateConfigBody=Omit<Config,"retries">&Partial<Pick<Config,"retries">>;"#,
+            r#"exportconstcreateConfigBodyDefaults:Partial<CreateConfigBody>={limit:100,mode:"fast",retries:3,tags:[]"#,
+            "constpayload={...createConfigBodyDefaults,...data};",
+            "body:JSON.stringify(payload)",
+            "createConfig=async(data:CreateConfigBody",
+            "data:ReplaceConfigBody",
+        ] {
+            assert!(
+                compact.contains(expected),
+                "Missing `{expected}`. Generated:\n{ts_code}"
+            );
+        }
+        typecheck_generated_code(&ts_code).expect("defaulted bodies should compile");
+
+        let output = run_generated_code(
+            &ts_code,
+            r#"globalThis.fetch = (async (_url: string, init: RequestInit) => {
+  console.log(init.body);
+  return new Response("{}", { headers: { "content-type": "application/json" } });
+}) as typeof fetch;
+await createConfig({ name: "nightly" });
+await createConfig({ name: "manual", retries: 0 });"#,
+        )
+        .unwrap();
+        assert_eq!(
+            output.trim(),
+            concat!(
+                r#"{"limit":100,"mode":"fast","retries":3,"tags":[],"name":"nightly"}"#,
+                "\n",
+                r#"{"limit":100,"mode":"fast","retries":0,"tags":[],"name":"manual"}"#
+            )
+        );
+
+        // int64 defaults are `bigint` literals when int64 is typed as `bigint`
+        let options = GenerateOptions {
+            body_defaults: true,
+            bigint_int64: true,
+            ..GenerateOptions::default()
+        };
+        let ts_code = generate(openapi_json, &options).unwrap();
+        let compact: String = ts_code.split_whitespace().collect();
+        for expected in [
+            r#"={limit:100n,mode:"fast",retries:3,tags:[]"#,
+            "body:stringifyJsonWithBigInt(payload)",
+        ] {
+            assert!(
+                compact.contains(expected),
+                "Missing `{expected}`. Generated:\n{ts_code}"
+            );
+        }
+        typecheck_generated_code(&ts_code).expect("bigint defaults should compile");
+    }
+
+    #[test]
+    fn test_tag_groups_bundle_operations() {
+        let openapi_json = r##"{
+  "openapi": "3.1.0",
+  "info": { "title": "Tagged API", "version": "1.0.0" },
+  "paths": {
+    "/users": {
+      "get": {
+        "operationId": "listUsers",
+        "tags": ["users", "admin"],
+        "responses": { "200": { "description": "OK", "content": { "application/json": { "schema": { "type": "array", "items": { "type": "string" } } } } } }
+      },
+      "post": {
+        "operationId": "createUser",
+        "tags": ["users"],
+        "requestBody": { "required": true, "content": { "application/json": { "schema": { "type": "object", "properties": { "name": { "type": "string" } } } } } },
+        "responses": { "201": { "description": "Created" } }
+      }
+    },
+    "/health": {
+      "get": {
+        "operationId": "health",
+        "responses": { "200": { "description": "OK", "content": { "text/plain": { "schema": { "type": "string" } } } } }
+      }
+    },
+    "/links": {
+      "get": {
+        "operationId": "getLinks",
+        "tags": ["Links"],
+        "responses": { "200": { "description": "OK", "content": { "application/json": { "schema": { "type": "string" } } } } }
+      }
+    }
+  }
+}"##;
+
+        // Default: no group objects
+        let default_code = generate(openapi_json, &GenerateOptions::default()).unwrap();
+        assert!(!default_code.contains("export const users"));
+
+        let options = GenerateOptions {
+            tag_groups: true,
+            ..GenerateOptions::default()
+        };
+        let ts_code = generate(openapi_json, &options).unwrap();
+        // Compare without whitespace so the check is independent of SWC's formatting
+        let compact: String = ts_code.split_whitespace().collect();
+        for expected in [
+            "exportconstusers={listUsers,useListUsers,useListUsersSuspense,createUser,useCreateUser};",
+            // Untagged operations; `default` is reserved
+            "exportconst_default={health,useHealth,useHealthSuspense};",
+            // `links` is taken by the link builders
+            "exportconstlinksApi={getLinks,useGetLinks,useGetLinksSuspense};",
+            // The underlying functions stay exported
+            "exportconstlistUsers=async",
+        ] {
+            assert!(
+                compact.contains(expected),
+                "Missing `{expected}`. Generated:\n{ts_code}"
+            );
+        }
+        assert!(!compact.contains("exportconstadmin="));
+        typecheck_generated_code(&ts_code).expect("tag groups should compile");
+    }
+
+    #[test]
+    fn test_query_key_factories_exported() {
+        let openapi_json = r##"{
+  "openapi": "3.1.0",
+  "info": { "title": "Query Key API", "version": "1.0.0" },
+  "paths": {
+    "/items": {
+      "get": {
+        "operationId": "listItems",
+        "parameters": [{ "name": "limit", "in": "query", "required": false, "schema": { "type": "integer" } }],
+        "responses": { "200": { "description": "OK", "content": { "application/json": { "schema": { "type": "array", "items": { "type": "string" } } } } } }
+      },
+      "post": {
+        "operationId": "createItem",
+        "requestBody": { "required": true, "content": { "application/json": { "schema": { "type": "string" } } } },
+        "responses": { "201": { "description": "Created" } }
+      }
+    },
+    "/search": {
+      "get": {
+        "operationId": "search",
+        "responses": { "200": { "description": "OK", "content": { "application/json": { "schema": { "type": "string" } } } } }
+      }
+    },
+    "/search/query": {
+      "get": {
+        "operationId": "searchQuery",
+        "responses": { "200": { "description": "OK", "content": { "application/json": { "schema": { "type": "string" } } } } }
+      }
+    }
+  }
+}"##;
+
+        let ts_code = generate_and_verify(openapi_json);
+        // Compare without whitespace so the check is independent of SWC's formatting
+        let compact: String = ts_code.split_whitespace().collect();
+        for expected in [
+            r#"exportconstlistItemsQueryKey=(params?:ListItemsParams):readonly["/items",ListItemsParams|undefined]=>"#,
+            r#"exportconstsearchQueryKey=():readonly["/search"]=>"#,
+            "queryKey:listItemsQueryKey(",
+            "/**@deprecatedUse`listItemsQueryKey`*/exportconstlistItemsKey=listItemsQueryKey;",
+        ] {
+            assert!(
+                compact.contains(expected),
+                "Missing `{expected}`. Generated:\n{ts_code}"
+            );
+        }
+        // `searchQuery`'s legacy alias would redeclare `searchQueryKey`
+        assert_eq!(compact.matches("exportconstsearchQueryKey=").count(), 1);
+
+        typecheck_generated_code(&format!(
+            r#"{ts_code}
+export const refreshItems = (queryClient: QueryClient) =>
+  queryClient.invalidateQueries({{ queryKey: listItemsQueryKey() }});
+const prefix: "/items" = listItemsQueryKey({{ limit: 1 }})[0];
+export {{ prefix }};
+"#
+        ))
+        .expect("query keys should be usable for invalidation");
+    }
+
+    #[test]
+    fn test_prefetch_helpers_for_queries() {
+        let ts_code = generate_and_verify(TEST_OPENAPI_JSON);
+        let compact: String = ts_code.split_whitespace().collect();
+        for expected in [
+            "importtype{UseQueryOptions,UseSuspenseQueryOptions,QueryClient,FetchQueryOptions}from\"@tanstack/react-query\";",
+            "exportfunctionprefetchListItems(queryClient:QueryClient,params?:ListItemsParams,options?:Omit<FetchQueryOptions<",
+            "returnqueryClient.prefetchQuery({queryKey:listItemsQueryKey(params),queryFn:()=>listItems(params),...options});",
+            "exportfunctionprefetchGetItem(queryClient:QueryClient,params:GetItemParams,",
+        ] {
+            assert!(
+                compact.contains(expected),
+                "Missing `{expected}`. Generated:\n{ts_code}"
+            );
+        }
+        // Mutations only get hooks
+        assert!(!compact.contains("prefetchCreateItem"));
+        assert!(!compact.contains("prefetchSearch"));
+
+        typecheck_generated_code(&format!(
+            r#"{ts_code}
+export const warmCache = async (queryClient: QueryClient) => {{
+  await prefetchListItems(queryClient, {{ limit: 10 }}, {{ staleTime: 30_000 }});
+  await prefetchGetItem(queryClient, {{ itemId: "1" }});
+}};
+"#
+        ))
+        .expect("prefetch helpers should accept a QueryClient");
+    }
+
+    #[test]
+    fn test_hook_naming_options() {
+        let options = GenerateOptions {
+            hook_naming: HookNaming {
+                prefix: "useApi".into(),
+                suspense_suffix: "Suspended".into(),
+                mutation_suffix: "Mutation".into(),
+            },
+            tag_groups: true,
+            ..GenerateOptions::default()
+        };
+        let ts_code = generate(TEST_OPENAPI_JSON, &options).unwrap();
+        for expected in [
+            "export function useApiListItems(",
+            "export function useApiListItemsSuspended(",
+            "export function useApiCreateItemMutation(",
+            "export function useApiDeleteItemMutation(",
+        ] {
+            assert!(
+                ts_code.contains(expected),
+                "Missing `{expected}`. Generated:\n{ts_code}"
+            );
+        }
+        assert!(!ts_code.contains("function useListItems"));
+        assert!(!ts_code.contains("useApiListItemsSuspense("));
+        typecheck_generated_code(&ts_code).expect("renamed hooks should compile");
+
+        // Two queries whose default hook names clash
+        let openapi_json = r#"{
+  "openapi": "3.1.0",
+  "info": { "title": "Clash", "version": "1.0.0" },
+  "paths": {
+    "/items": { "get": { "operationId": "listItems", "responses": { "204": { "description": "No content" } } } },
+    "/items/suspense": { "get": { "operationId": "listItemsSuspense", "responses": { "204": { "description": "No content" } } } }
+  }
+}"#;
+        let err = generate(openapi_json, &GenerateOptions::default()).unwrap_err();
+        assert!(
+            err.contains("Duplicate hook name 'useListItemsSuspense'"),
+            "unexpected error: {err}"
+        );
+        let options = GenerateOptions {
+            hook_naming: HookNaming {
+                suspense_suffix: "Suspended".into(),
+                ..HookNaming::default()
+            },
+            ..GenerateOptions::default()
+        };
+        assert!(generate(openapi_json, &options).is_ok());
+
+        // An empty suspense suffix makes each query's two hooks clash
+        let options = GenerateOptions {
+            hook_naming: HookNaming {
+                suspense_suffix: String::new(),
+                ..HookNaming::default()
+            },
+            ..GenerateOptions::default()
+        };
+        let err = generate(TEST_OPENAPI_JSON, &options).unwrap_err();
+        assert!(
+            err.contains("Duplicate hook name"),
+            "unexpected error: {err}"
+        );
+    }
+
+    #[test]
+    fn test_unsupported_keyword_yields_warning() {
+        let openapi_json = r##"{
+  "openapi": "3.1.0",
+  "info": { "title": "Warnings API", "version": "1.0.0" },
+  "paths": {
+    "/legacy": {
+      "get": {
+        "operationId": "getLegacy",
+        "deprecated": true,
+        "responses": { "200": { "description": "OK", "content": { "application/json": { "schema": { "$ref": "#/components/schemas/Legacy" } } } } }
+      },
+      "head": { "responses": { "200": { "description": "OK" } } }
+    }
+  },
+  "components": {
+    "schemas": {
+      "Legacy": {
+        "type": "object",
+        "properties": {
+          "code": { "type": "string", "not": { "const": "" } },
+          "blob": { "type": "binary" }
+        }
+      }
+    }
+  }
+}"##;
+        let result = generate_detailed(openapi_json, &GenerateOptions::default()).unwrap();
+        assert!(result.code.contains("export interface Legacy"));
+
+        let find = |kind: WarningKind| result.warnings.iter().find(|w| w.kind == kind);
+        let unsupported = find(WarningKind::UnsupportedKeyword).expect("missing keyword warning");
+        assert_eq!(
+            unsupported.location,
+            "/components/schemas/Legacy/properties/code"
+        );
+        assert!(unsupported.message.contains("'not'"));
+        assert_eq!(
+            find(WarningKind::UnknownFallback).map(|w| w.location.as_str()),
+            Some("/components/schemas/Legacy/properties/blob")
+        );
+        assert_eq!(
+            find(WarningKind::Deprecated).map(|w| w.location.as_str()),
+            Some("/paths/~1legacy/get")
+        );
+        assert_eq!(
+            find(WarningKind::IgnoredOperation).map(|w| w.location.as_str()),
+            Some("/paths/~1legacy/head")
+        );
+
+        // Specs using only supported constructs produce no warnings.
+        let clean = generate_detailed(TEST_OPENAPI_JSON, &GenerateOptions::default()).unwrap();
+        assert!(clean.warnings.is_empty(), "{:?}", clean.warnings);
+    }
+
+    #[test]
+    fn test_required_names_missing_from_properties() {
+        let openapi_json = r##"{
+  "openapi": "3.1.0",
+  "info": { "title": "Test", "version": "1.0.0" },
+  "paths": {},
+  "components": {
+    "schemas": {
+      "Base": {
+        "type": "object",
+        "properties": { "id": { "type": "string" } }
+      },
+      "User": {
+        "allOf": [{ "$ref": "#/components/schemas/Base" }],
+        "type": "object",
+        "properties": {
+          "email": { "type": "string" },
+          "address": {
+            "type": "object",
+            "properties": { "city": { "type": "string" } },
+            "required": ["city", "zip"]
+          }
+        },
+        "required": ["id", "email", "emial"]
+      },
+      "Labels": {
+        "type": "object",
+        "additionalProperties": { "type": "string" },
+        "required": ["env"]
+      }
+    }
+  }
+}"##;
+        let result = generate_detailed(openapi_json, &GenerateOptions::default()).unwrap();
+        let missing: Vec<_> = result
+            .warnings
+            .iter()
+            .filter(|w| w.kind == WarningKind::MissingRequiredProperty)
+            .map(|w| (w.location.as_str(), w.message.as_str()))
+            .collect();
+        assert_eq!(
+            missing,
+            [
+                (
+                    "/components/schemas/User",
+                    "schema 'User' requires property 'emial', which is not declared in its properties"
+                ),
+                (
+                    "/components/schemas/User/properties/address",
+                    "schema 'User' requires property 'zip', which is not declared in its properties"
+                ),
+            ]
+        );
+
+        let strict = GenerateOptions {
+            strict_required: true,
+            ..Default::default()
+        };
+        let err = generate_detailed(openapi_json, &strict).unwrap_err();
+        assert!(err.contains("/components/schemas/User"), "{err}");
+        assert!(err.contains("'emial'"), "{err}");
+        assert!(generate_detailed(TEST_OPENAPI_JSON, &strict).is_ok());
+    }
+
     #[test]
     fn test_special_characters_in_enum_values() {
         // Test case for URN-style enum values (like SCIM schemas) that contain colons
         let openapi_json = r##"{
   "openapi": "3.1.0",
-  "info": { "title": "SCIM API", "version": "1.0.0" },
+  "info": { "title": "SCIM API", "version": "1.0.0" },
+  "paths": {},
+  "components": {
+    "schemas": {
+      "UserSchema": {
+        "type": "string",
+        "enum": [
+          "urn:ietf:params:scim:schemas:core:2.0:User",
+          "urn:ietf:params:scim:schemas:extension:workspace:2.0:User"
+        ]
+      },
+      "SpecialProps": {
+        "type": "object",
+        "properties": {
+          "normal-prop": { "type": "string" },
+          "prop.with.dots": { "type": "number" },
+          "prop:with:colons": { "type": "boolean" },
+          "123startsWithNumber": { "type": "string" }
+        }
+      }
+    }
+  }
+}"##;
+
+        let ts_code = generate_and_verify(openapi_json);
+        println!("=== SPECIAL CHARS CODE ===\n{ts_code}\n=== END ===");
+
+        // Verify URN enum values are properly quoted
+        // SWC may use single quotes for keys, so check with normalize_ws
+        let norm = normalize_ws(&ts_code);
+        assert!(
+            norm.contains(r#""urn:ietf:params:scim:schemas:core:2.0:User": "urn:ietf:params:scim:schemas:core:2.0:User""#),
+            "URN enum key should be quoted. Generated:\n{ts_code}"
+        );
+        assert!(
+            ts_code.contains(r#""urn:ietf:params:scim:schemas:extension:workspace:2.0:User""#),
+            "URN enum value should be quoted as key"
+        );
+
+        // Verify special property names are quoted
+        assert!(
+            ts_code.contains(r#""normal-prop"?"#) || ts_code.contains(r#""normal-prop":"#),
+            "Property with dash should be quoted"
+        );
+        assert!(
+            ts_code.contains(r#""prop.with.dots"?"#) || ts_code.contains(r#""prop.with.dots":"#),
+            "Property with dots should be quoted"
+        );
+        assert!(
+            ts_code.contains(r#""prop:with:colons"?"#)
+                || ts_code.contains(r#""prop:with:colons":"#),
+            "Property with colons should be quoted"
+        );
+        assert!(
+            ts_code.contains(r#""123startsWithNumber"?"#)
+                || ts_code.contains(r#""123startsWithNumber":"#),
+            "Property starting with number should be quoted"
+        );
+    }
+
+    #[test]
+    fn test_conditional_imports_no_mutations() {
+        // Test case for a spec with only GET endpoints - should NOT import useMutation
+        let openapi_json = r##"{
+  "openapi": "3.1.0",
+  "info": { "title": "Read-Only API", "version": "1.0.0" },
+  "paths": {
+    "/items": {
+      "get": {
+        "operationId": "listItems",
+        "responses": {
+          "200": { "description": "OK", "content": { "application/json": { "schema": { "type": "array", "items": { "$ref": "#/components/schemas/Item" } } } } }
+        }
+      }
+    },
+    "/items/{id}": {
+      "get": {
+        "operationId": "getItem",
+        "parameters": [{ "name": "id", "in": "path", "required": true, "schema": { "type": "string" } }],
+        "responses": {
+          "200": { "description": "OK", "content": { "application/json": { "schema": { "$ref": "#/components/schemas/Item" } } } }
+        }
+      }
+    }
+  },
+  "components": {
+    "schemas": {
+      "Item": {
+        "type": "object",
+        "required": ["id", "name"],
+        "properties": {
+          "id": { "type": "string" },
+          "name": { "type": "string" }
+        }
+      }
+    }
+  }
+}"##;
+
+        let ts_code = generate_and_verify(openapi_json);
+        println!("=== GET-ONLY SPEC CODE ===\n{ts_code}\n=== END ===");
+
+        // Should have useQuery and useSuspenseQuery
+        assert!(ts_code.contains("useQuery"), "Missing useQuery import");
+        assert!(
+            ts_code.contains("useSuspenseQuery"),
+            "Missing useSuspenseQuery import"
+        );
+
+        // Should NOT have useMutation or UseMutationOptions (unused imports cause TS errors)
+        assert!(
+            !ts_code.contains("useMutation"),
+            "useMutation should NOT be imported for GET-only specs"
+        );
+        assert!(
+            !ts_code.contains("UseMutationOptions"),
+            "UseMutationOptions should NOT be imported for GET-only specs"
+        );
+    }
+
+    #[test]
+    fn test_conditional_imports_no_queries() {
+        // Test case for a spec with only POST/mutation endpoints - should NOT import useQuery
+        let openapi_json = r##"{
+  "openapi": "3.1.0",
+  "info": { "title": "Write-Only API", "version": "1.0.0" },
+  "paths": {
+    "/items": {
+      "post": {
+        "operationId": "createItem",
+        "requestBody": { "required": true, "content": { "application/json": { "schema": { "$ref": "#/components/schemas/CreateItemInput" } } } },
+        "responses": {
+          "201": { "description": "Created", "content": { "application/json": { "schema": { "$ref": "#/components/schemas/Item" } } } }
+        }
+      }
+    },
+    "/items/{id}": {
+      "delete": {
+        "operationId": "deleteItem",
+        "parameters": [{ "name": "id", "in": "path", "required": true, "schema": { "type": "string" } }],
+        "responses": { "204": { "description": "Deleted" } }
+      }
+    }
+  },
+  "components": {
+    "schemas": {
+      "Item": {
+        "type": "object",
+        "required": ["id", "name"],
+        "properties": {
+          "id": { "type": "string" },
+          "name": { "type": "string" }
+        }
+      },
+      "CreateItemInput": {
+        "type": "object",
+        "required": ["name"],
+        "properties": { "name": { "type": "string" } }
+      }
+    }
+  }
+}"##;
+
+        let ts_code = generate_and_verify(openapi_json);
+        println!("=== MUTATION-ONLY SPEC CODE ===\n{ts_code}\n=== END ===");
+
+        // Should have useMutation and UseMutationOptions
+        assert!(
+            ts_code.contains("useMutation"),
+            "Missing useMutation import"
+        );
+        assert!(
+            ts_code.contains("UseMutationOptions"),
+            "Missing UseMutationOptions import"
+        );
+
+        // Should NOT have useQuery or useSuspenseQuery (unused imports cause TS errors)
+        assert!(
+            !ts_code.contains("useQuery"),
+            "useQuery should NOT be imported for mutation-only specs"
+        );
+        assert!(
+            !ts_code.contains("useSuspenseQuery"),
+            "useSuspenseQuery should NOT be imported for mutation-only specs"
+        );
+        assert!(
+            !ts_code.contains("UseQueryOptions"),
+            "UseQueryOptions should NOT be imported for mutation-only specs"
+        );
+        assert!(
+            !ts_code.contains("UseSuspenseQueryOptions"),
+            "UseSuspenseQueryOptions should NOT be imported for mutation-only specs"
+        );
+    }
+
+    #[test]
+    fn test_allof_composition() {
+        // Test allOf for schema composition (common with Pydantic inheritance)
+        let openapi_json = r##"{
+  "openapi": "3.1.0",
+  "info": { "title": "AllOf Test API", "version": "1.0.0" },
+  "paths": {},
+  "components": {
+    "schemas": {
+      "BaseEntity": {
+        "type": "object",
+        "required": ["id", "createdAt"],
+        "properties": {
+          "id": { "type": "string" },
+          "createdAt": { "type": "string", "format": "date-time" }
+        }
+      },
+      "User": {
+        "allOf": [
+          { "$ref": "#/components/schemas/BaseEntity" },
+          {
+            "type": "object",
+            "required": ["email"],
+            "properties": {
+              "email": { "type": "string" },
+              "name": { "type": "string" }
+            }
+          }
+        ]
+      },
+      "Admin": {
+        "allOf": [
+          { "$ref": "#/components/schemas/User" },
+          {
+            "type": "object",
+            "required": ["role"],
+            "properties": {
+              "role": { "type": "string", "const": "admin" },
+              "permissions": { "type": "array", "items": { "type": "string" } }
+            }
+          }
+        ]
+      }
+    }
+  }
+}"##;
+
+        let ts_code = generate_and_verify(openapi_json);
+        println!("=== ALLOF CODE ===\n{ts_code}\n=== END ===");
+
+        // Verify BaseEntity is a regular interface
+        assert!(
+            ts_code.contains("export interface BaseEntity"),
+            "Missing BaseEntity interface"
+        );
+
+        // Verify User uses intersection type with BaseEntity
+        assert!(
+            ts_code.contains("export type User = BaseEntity &"),
+            "User should be intersection with BaseEntity"
+        );
+
+        // Verify Admin uses intersection type
+        assert!(
+            ts_code.contains("export type Admin = User &"),
+            "Admin should be intersection with User"
+        );
+
+        // Verify const keyword generates literal type
+        assert!(
+            ts_code.contains("\"admin\""),
+            "const: admin should generate literal type"
+        );
+    }
+
+    #[test]
+    fn test_oneof_with_discriminator() {
+        // Test oneOf with discriminator for discriminated unions
+        let openapi_json = r##"{
+  "openapi": "3.1.0",
+  "info": { "title": "Discriminator Test API", "version": "1.0.0" },
   "paths": {},
   "components": {
     "schemas": {
-      "UserSchema": {
-        "type": "string",
-        "enum": [
-          "urn:ietf:params:scim:schemas:core:2.0:User",
-          "urn:ietf:params:scim:schemas:extension:workspace:2.0:User"
+      "Dog": {
+        "type": "object",
+        "required": ["breed"],
+        "properties": {
+          "breed": { "type": "string" },
+          "barkVolume": { "type": "integer" }
+        }
+      },
+      "Cat": {
+        "type": "object",
+        "required": ["huntingSkill"],
+        "properties": {
+          "huntingSkill": { "type": "string", "enum": ["lazy", "aggressive", "expert"] }
+        }
+      },
+      "Pet": {
+        "oneOf": [
+          { "$ref": "#/components/schemas/Dog" },
+          { "$ref": "#/components/schemas/Cat" }
+        ],
+        "discriminator": {
+          "propertyName": "petType",
+          "mapping": {
+            "dog": "#/components/schemas/Dog",
+            "cat": "#/components/schemas/Cat"
+          }
+        }
+      }
+    }
+  }
+}"##;
+
+        let ts_code = generate_and_verify(openapi_json);
+        println!("=== DISCRIMINATOR CODE ===\n{ts_code}\n=== END ===");
+
+        // Verify Dog and Cat are regular interfaces
+        assert!(
+            ts_code.contains("export interface Dog"),
+            "Missing Dog interface"
+        );
+        assert!(
+            ts_code.contains("export interface Cat"),
+            "Missing Cat interface"
+        );
+
+        // Verify Pet is a discriminated union with petType field
+        assert!(
+            ts_code.contains("export type Pet ="),
+            "Missing Pet type alias"
+        );
+        assert!(
+            ts_code.contains("petType: \"dog\"") && ts_code.contains("& Dog"),
+            "Pet should include discriminated Dog branch"
+        );
+        assert!(
+            ts_code.contains("petType: \"cat\"") && ts_code.contains("& Cat"),
+            "Pet should include discriminated Cat branch"
+        );
+
+        // One type guard per mapping entry
+        let normalized = normalize_ws(&ts_code);
+        for (guard, value) in [("isDog", "dog"), ("isCat", "cat")] {
+            assert!(
+                normalized.contains(&format!(
+                    "export function {guard}(value: Pet): value is Extract<Pet, {{ petType: \"{value}\"; }}>"
+                )),
+                "Missing {guard} type guard"
+            );
+            assert!(
+                normalized.contains(&format!("return value.petType === \"{value}\";")),
+                "{guard} should check the discriminator"
+            );
+        }
+    }
+
+    #[test]
+    fn test_oneof_without_discriminator_has_no_type_guards() {
+        let openapi_json = r##"{
+  "openapi": "3.1.0",
+  "info": { "title": "Plain Union API", "version": "1.0.0" },
+  "paths": {},
+  "components": {
+    "schemas": {
+      "Dog": { "type": "object", "properties": { "breed": { "type": "string" } } },
+      "Cat": { "type": "object", "properties": { "lives": { "type": "integer" } } },
+      "Pet": {
+        "oneOf": [
+          { "$ref": "#/components/schemas/Dog" },
+          { "$ref": "#/components/schemas/Cat" }
+        ]
+      }
+    }
+  }
+}"##;
+
+        let ts_code = generate_and_verify(openapi_json);
+        assert!(
+            ts_code.contains("export type Pet ="),
+            "Missing Pet type alias"
+        );
+        assert!(
+            !ts_code.contains("export function is"),
+            "Unions without a discriminator should not get type guards"
+        );
+    }
+
+    #[test]
+    fn test_oneof_with_const_property_is_discriminated() {
+        // Pydantic tags `Literal` fields with `const` (or a single-value `enum`) and
+        // may omit the `discriminator` object
+        let openapi_json = r##"{
+  "openapi": "3.1.0",
+  "info": { "title": "Const Union API", "version": "1.0.0" },
+  "paths": {},
+  "components": {
+    "schemas": {
+      "Dog": {
+        "type": "object",
+        "required": ["breed"],
+        "properties": {
+          "type": { "type": "string", "const": "dog", "default": "dog" },
+          "breed": { "type": "string" }
+        }
+      },
+      "Cat": {
+        "type": "object",
+        "required": ["type", "lives"],
+        "properties": {
+          "type": { "type": "string", "enum": ["cat"] },
+          "lives": { "type": "integer" }
+        }
+      },
+      "Kitten": {
+        "type": "object",
+        "required": ["type"],
+        "properties": { "type": { "type": "string", "const": "cat" } }
+      },
+      "Pet": {
+        "oneOf": [
+          { "$ref": "#/components/schemas/Dog" },
+          { "$ref": "#/components/schemas/Cat" }
         ]
       },
-      "SpecialProps": {
+      "Litter": {
+        "oneOf": [
+          { "$ref": "#/components/schemas/Cat" },
+          { "$ref": "#/components/schemas/Kitten" }
+        ]
+      }
+    }
+  }
+}"##;
+
+        let options = GenerateOptions {
+            union_matchers: true,
+            ..GenerateOptions::default()
+        };
+        let ts_code = generate(openapi_json, &options).unwrap();
+        let normalized = normalize_ws(&ts_code);
+        for (guard, value) in [("isDog", "dog"), ("isCat", "cat")] {
+            assert!(
+                normalized.contains(&format!(
+                    "export function {guard}(value: Pet): value is Extract<Pet, {{ type: \"{value}\"; }}>"
+                )),
+                "Missing {guard} type guard: {ts_code}"
+            );
+        }
+        assert!(normalized.contains("export function matchPet<R>(value: Pet, handlers: {"));
+
+        // Members sharing a value can't be told apart: plain union
+        assert!(!normalized.contains("isKitten"));
+        assert!(!normalized.contains("matchLitter"));
+
+        let usage = r#"
+declare const pet: Pet;
+export const label: string = isDog(pet) ? pet.breed : String(pet.lives);
+"#;
+        typecheck_generated_code(&format!("{ts_code}{usage}"))
+            .expect("inferred discriminated union should compile");
+
+        let output = run_generated_code(
+            &ts_code,
+            r#"console.log(matchPet({ type: "cat", lives: 9 }, { dog: () => "dog", cat: (cat) => `cat:${cat.lives}` }));"#,
+        )
+        .unwrap();
+        assert_eq!(output.trim(), "cat:9");
+    }
+
+    #[test]
+    fn test_union_matchers_are_exhaustive() {
+        let openapi_json = r##"{
+  "openapi": "3.1.0",
+  "info": { "title": "Union Matcher API", "version": "1.0.0" },
+  "paths": {},
+  "components": {
+    "schemas": {
+      "Dog": { "type": "object", "required": ["breed"], "properties": { "breed": { "type": "string" } } },
+      "Cat": { "type": "object", "required": ["lives"], "properties": { "lives": { "type": "integer" } } },
+      "Pet": {
+        "oneOf": [
+          { "$ref": "#/components/schemas/Dog" },
+          { "$ref": "#/components/schemas/Cat" }
+        ],
+        "discriminator": {
+          "propertyName": "petType",
+          "mapping": { "dog": "#/components/schemas/Dog", "cat": "#/components/schemas/Cat" }
+        }
+      },
+      "Lookup": {
+        "anyOf": [
+          { "$ref": "#/components/schemas/Dog" },
+          { "type": "string" },
+          { "type": "array", "items": { "type": "string" } },
+          { "type": "null" }
+        ]
+      },
+      "Animal": {
+        "oneOf": [
+          { "$ref": "#/components/schemas/Dog" },
+          { "$ref": "#/components/schemas/Cat" }
+        ]
+      }
+    }
+  }
+}"##;
+
+        // Default: no matchers
+        let default_code = generate(openapi_json, &GenerateOptions::default()).unwrap();
+        assert!(!default_code.contains("export function match"));
+
+        let options = GenerateOptions {
+            union_matchers: true,
+            ..GenerateOptions::default()
+        };
+        let ts_code = generate(openapi_json, &options).unwrap();
+        let normalized = normalize_ws(&ts_code);
+        assert!(normalized.contains("export function matchPet<R>(value: Pet, handlers: {"));
+        assert!(normalized.contains("return handlers.dog(value);"));
+        assert!(normalized.contains("export function matchLookup<R>(value: Lookup, handlers: {"));
+        assert!(normalized.contains("const unhandled: never = value;"));
+        // Two object members without a discriminator can't be told apart
+        assert!(!normalized.contains("matchAnimal"));
+
+        let usage = r#"
+declare const pet: Pet;
+declare const lookup: Lookup;
+export const petLabel: string = matchPet(pet, {
+  dog: (dog) => dog.breed,
+  cat: (cat) => String(cat.lives),
+});
+export const lookupSize: number = matchLookup(lookup, {
+  null: () => 0,
+  array: (ids) => ids.length,
+  string: (id) => id.length,
+  object: (dog) => dog.breed.length,
+});
+"#;
+        typecheck_generated_code(&format!("{ts_code}{usage}"))
+            .expect("union matchers should compile");
+
+        let missing = typecheck_generated_code(&format!(
+            "{ts_code}\ndeclare const pet: Pet;\nexport const label = matchPet(pet, {{ dog: (dog) => dog.breed }});\n"
+        ))
+        .expect_err("omitting a branch should not compile");
+        assert!(missing.contains("cat"), "Unexpected tsc output: {missing}");
+
+        let output = run_generated_code(
+            &ts_code,
+            r#"const size = (value: Lookup) =>
+  matchLookup(value, { null: () => "none", array: (ids) => `ids:${ids.length}`, string: (id) => `id:${id}`, object: (dog) => `dog:${dog.breed}` });
+console.log(size(null), size(["a", "b"]), size("x"), size({ breed: "lab" }));
+console.log(matchPet({ petType: "cat", lives: 9 }, { dog: () => "dog", cat: (cat) => `cat:${cat.lives}` }));
+try {
+  matchPet({ petType: "fish" } as unknown as Pet, { dog: () => "dog", cat: () => "cat" });
+} catch (e) {
+  console.log((e as Error).message);
+}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            output.trim(),
+            "none ids:2 id:x dog:lab\ncat:9\nUnhandled Pet: {\"petType\":\"fish\"}"
+        );
+    }
+
+    #[test]
+    fn test_webhooks_emit_event_union() {
+        let openapi_json = r##"{
+  "openapi": "3.1.0",
+  "info": { "title": "Pet Store Webhooks", "version": "1.0.0" },
+  "webhooks": {
+    "newPet": {
+      "post": {
+        "requestBody": { "content": { "application/json": { "schema": { "$ref": "#/components/schemas/Pet" } } } },
+        "responses": { "200": { "description": "Received" } }
+      }
+    },
+    "pet-sold": {
+      "post": {
+        "requestBody": {
+          "content": {
+            "application/json": {
+              "schema": {
+                "type": "object",
+                "required": ["petId"],
+                "properties": { "petId": { "type": "string" }, "price": { "type": "number" } }
+              }
+            }
+          }
+        },
+        "responses": { "200": { "description": "Received" } }
+      }
+    },
+    "ping": {
+      "post": { "responses": { "200": { "description": "Received" } } }
+    }
+  },
+  "components": {
+    "schemas": {
+      "Pet": { "type": "object", "required": ["name"], "properties": { "name": { "type": "string" } } }
+    }
+  }
+}"##;
+
+        // Specs without webhooks are unaffected
+        let default = generate(TEST_OPENAPI_JSON, &GenerateOptions::default()).unwrap();
+        assert!(!default.contains("Webhook"));
+
+        let ts_code = generate_and_verify(openapi_json);
+        let compact: String = ts_code.split_whitespace().collect();
+        for expected in [
+            "exporttypeNewPetWebhookPayload=Pet;",
+            "exportinterfacePetSoldWebhookPayload{petId:string;price?:number;}",
+            "exporttypePingWebhookPayload=unknown;",
+            r#"exporttypeWebhookEvent={webhook:"newPet";payload:NewPetWebhookPayload;}|{webhook:"pet-sold";payload:PetSoldWebhookPayload;}|{webhook:"ping";payload:PingWebhookPayload;};"#,
+        ] {
+            assert!(
+                compact.contains(expected),
+                "Missing `{expected}`. Generated:\n{ts_code}"
+            );
+        }
+        // The dispatcher comes with the union matchers
+        assert!(!ts_code.contains("handleWebhook"));
+
+        let options = GenerateOptions {
+            union_matchers: true,
+            ..GenerateOptions::default()
+        };
+        let ts_code = generate(openapi_json, &options).unwrap();
+        let normalized = normalize_ws(&ts_code);
+        assert!(
+            normalized
+                .contains("export function handleWebhook<R>(event: WebhookEvent, handlers: {")
+        );
+        assert!(normalized.contains(
+            r#"if (event.webhook === "pet-sold") return handlers["pet-sold"](event.payload);"#
+        ));
+
+        let usage = r#"
+export const describe = (event: WebhookEvent): string =>
+  handleWebhook(event, {
+    newPet: (pet) => pet.name,
+    "pet-sold": (sale) => `${sale.petId}: ${sale.price ?? 0}`,
+    ping: () => "pong",
+  });
+"#;
+        typecheck_generated_code(&format!("{ts_code}{usage}"))
+            .expect("webhook handlers should compile");
+
+        let output = run_generated_code(
+            &ts_code,
+            r#"console.log(handleWebhook({ webhook: "pet-sold", payload: { petId: "7" } }, {
+  newPet: (pet) => pet.name,
+  "pet-sold": (sale) => `sold:${sale.petId}`,
+  ping: () => "pong",
+}));"#,
+        )
+        .unwrap();
+        assert_eq!(output.trim(), "sold:7");
+    }
+
+    #[test]
+    fn test_validation_keywords_become_jsdoc_tags() {
+        let openapi_json = r##"{
+  "openapi": "3.1.0",
+  "info": { "title": "Constraints API", "version": "1.0.0" },
+  "paths": {},
+  "components": {
+    "schemas": {
+      "User": {
         "type": "object",
+        "required": ["name", "age"],
         "properties": {
-          "normal-prop": { "type": "string" },
-          "prop.with.dots": { "type": "number" },
-          "prop:with:colons": { "type": "boolean" },
-          "123startsWithNumber": { "type": "string" }
+          "name": { "type": "string", "minLength": 1, "maxLength": 50, "pattern": "^[a-z]*/[0-9]+$" },
+          "age": { "type": "integer", "minimum": 0, "maximum": 130.5 },
+          "nickname": {
+            "anyOf": [{ "type": "string", "maxLength": 20 }, { "type": "null" }]
+          },
+          "bio": { "type": "string" }
+        }
+      }
+    }
+  }
+}"##;
+
+        let ts_code = generate_and_verify(openapi_json);
+        let normalized = normalize_ws(&ts_code);
+        for expected in [
+            r"/** @minLength 1 @maxLength 50 @pattern ^[a-z]*\/[0-9]+$ */ name: string;",
+            "/** @minimum 0 @maximum 130.5 */ age: number;",
+            // Nullable unions take the constraints of their non-null member
+            "/** @maxLength 20 */ nickname?: string | null;",
+        ] {
+            assert!(
+                normalized.contains(expected),
+                "Missing `{expected}`. Generated:\n{ts_code}"
+            );
+        }
+        assert!(
+            !normalized.contains("*/ bio"),
+            "Unconstrained properties get no JSDoc"
+        );
+    }
+
+    #[test]
+    fn test_pattern_properties_index_signature() {
+        let openapi_json = r##"{
+  "openapi": "3.1.0",
+  "info": { "title": "Pattern Properties API", "version": "1.0.0" },
+  "paths": {},
+  "components": {
+    "schemas": {
+      "Extensible": {
+        "type": "object",
+        "required": ["name"],
+        "properties": { "name": { "type": "string" } },
+        "patternProperties": {
+          "^x-": { "type": "string" },
+          "^y-": { "type": "integer" }
+        }
+      },
+      "Labels": {
+        "type": "object",
+        "patternProperties": {
+          "^a": { "type": "string" },
+          "^b": { "type": "string" }
         }
       }
     }
@@ -224,285 +2260,185 @@ mod tests {
 }"##;
 
         let ts_code = generate_and_verify(openapi_json);
-        println!("=== SPECIAL CHARS CODE ===\n{ts_code}\n=== END ===");
+        println!("=== PATTERN PROPERTIES ===\n{ts_code}\n=== END ===");
+        let normalized = normalize_ws(&ts_code);
 
-        // Verify URN enum values are properly quoted
-        // SWC may use single quotes for keys, so check with normalize_ws
-        let norm = normalize_ws(&ts_code);
+        // Named properties intersected with a documented index signature
         assert!(
-            norm.contains(r#""urn:ietf:params:scim:schemas:core:2.0:User": "urn:ietf:params:scim:schemas:core:2.0:User""#),
-            "URN enum key should be quoted. Generated:\n{ts_code}"
+            normalized.contains("export type Extensible = { name: string; } & {"),
+            "Named properties should be kept alongside the index signature"
         );
         assert!(
-            ts_code.contains(r#""urn:ietf:params:scim:schemas:extension:workspace:2.0:User""#),
-            "URN enum value should be quoted as key"
+            normalized
+                .contains("/** Keys matching `^x-`, `^y-` */ [key: string]: string | number;"),
+            "Differing pattern value types should be unioned"
         );
 
-        // Verify special property names are quoted
-        assert!(
-            ts_code.contains(r#""normal-prop"?"#) || ts_code.contains(r#""normal-prop":"#),
-            "Property with dash should be quoted"
-        );
+        // Identical value schemas are not repeated
         assert!(
-            ts_code.contains(r#""prop.with.dots"?"#) || ts_code.contains(r#""prop.with.dots":"#),
-            "Property with dots should be quoted"
-        );
-        assert!(
-            ts_code.contains(r#""prop:with:colons"?"#)
-                || ts_code.contains(r#""prop:with:colons":"#),
-            "Property with colons should be quoted"
-        );
-        assert!(
-            ts_code.contains(r#""123startsWithNumber"?"#)
-                || ts_code.contains(r#""123startsWithNumber":"#),
-            "Property starting with number should be quoted"
+            normalized.contains("/** Keys matching `^a`, `^b` */ [key: string]: string;"),
+            "Identical pattern value types should collapse"
         );
     }
 
     #[test]
-    fn test_conditional_imports_no_mutations() {
-        // Test case for a spec with only GET endpoints - should NOT import useMutation
+    fn test_pattern_properties_keep_mismatched_named_properties() {
         let openapi_json = r##"{
   "openapi": "3.1.0",
-  "info": { "title": "Read-Only API", "version": "1.0.0" },
-  "paths": {
-    "/items": {
-      "get": {
-        "operationId": "listItems",
-        "responses": {
-          "200": { "description": "OK", "content": { "application/json": { "schema": { "type": "array", "items": { "$ref": "#/components/schemas/Item" } } } } }
-        }
-      }
-    },
-    "/items/{id}": {
-      "get": {
-        "operationId": "getItem",
-        "parameters": [{ "name": "id", "in": "path", "required": true, "schema": { "type": "string" } }],
-        "responses": {
-          "200": { "description": "OK", "content": { "application/json": { "schema": { "$ref": "#/components/schemas/Item" } } } }
-        }
-      }
-    }
-  },
+  "info": { "title": "Pattern Properties API", "version": "1.0.0" },
+  "paths": {},
   "components": {
     "schemas": {
-      "Item": {
+      "Counters": {
         "type": "object",
-        "required": ["id", "name"],
-        "properties": {
-          "id": { "type": "string" },
-          "name": { "type": "string" }
-        }
+        "required": ["total"],
+        "properties": { "total": { "type": "integer" }, "label": { "type": "string" } },
+        "patternProperties": { "^x-": { "type": "boolean" } }
       }
     }
   }
 }"##;
 
         let ts_code = generate_and_verify(openapi_json);
-        println!("=== GET-ONLY SPEC CODE ===\n{ts_code}\n=== END ===");
-
-        // Should have useQuery and useSuspenseQuery
-        assert!(ts_code.contains("useQuery"), "Missing useQuery import");
-        assert!(
-            ts_code.contains("useSuspenseQuery"),
-            "Missing useSuspenseQuery import"
-        );
+        let normalized = normalize_ws(&ts_code);
 
-        // Should NOT have useMutation or UseMutationOptions (unused imports cause TS errors)
+        // `total: number & boolean` would be `never`, so the index signature
+        // also admits the named property types
         assert!(
-            !ts_code.contains("useMutation"),
-            "useMutation should NOT be imported for GET-only specs"
+            normalized.contains("export type Counters = { label?: string; total: number; } & {"),
+            "Named properties should keep their own types: {normalized}"
         );
         assert!(
-            !ts_code.contains("UseMutationOptions"),
-            "UseMutationOptions should NOT be imported for GET-only specs"
+            normalized
+                .contains("/** Keys matching `^x-` */ [key: string]: boolean | string | number;"),
+            "Index signature should union the pattern and named property types: {normalized}"
         );
     }
 
     #[test]
-    fn test_conditional_imports_no_queries() {
-        // Test case for a spec with only POST/mutation endpoints - should NOT import useQuery
+    fn test_integer_enum() {
+        // Test integer enum values (HTTP status codes, error codes)
         let openapi_json = r##"{
   "openapi": "3.1.0",
-  "info": { "title": "Write-Only API", "version": "1.0.0" },
-  "paths": {
-    "/items": {
-      "post": {
-        "operationId": "createItem",
-        "requestBody": { "required": true, "content": { "application/json": { "schema": { "$ref": "#/components/schemas/CreateItemInput" } } } },
-        "responses": {
-          "201": { "description": "Created", "content": { "application/json": { "schema": { "$ref": "#/components/schemas/Item" } } } }
-        }
-      }
-    },
-    "/items/{id}": {
-      "delete": {
-        "operationId": "deleteItem",
-        "parameters": [{ "name": "id", "in": "path", "required": true, "schema": { "type": "string" } }],
-        "responses": { "204": { "description": "Deleted" } }
-      }
-    }
-  },
+  "info": { "title": "Integer Enum Test API", "version": "1.0.0" },
+  "paths": {},
   "components": {
     "schemas": {
-      "Item": {
-        "type": "object",
-        "required": ["id", "name"],
-        "properties": {
-          "id": { "type": "string" },
-          "name": { "type": "string" }
-        }
+      "HttpStatusCode": {
+        "type": "integer",
+        "enum": [200, 201, 400, 404, 500]
       },
-      "CreateItemInput": {
-        "type": "object",
-        "required": ["name"],
-        "properties": { "name": { "type": "string" } }
+      "Priority": {
+        "type": "integer",
+        "enum": [1, 2, 3, 4, 5]
       }
     }
   }
 }"##;
 
         let ts_code = generate_and_verify(openapi_json);
-        println!("=== MUTATION-ONLY SPEC CODE ===\n{ts_code}\n=== END ===");
+        println!("=== INTEGER ENUM CODE ===\n{ts_code}\n=== END ===");
 
-        // Should have useMutation and UseMutationOptions
-        assert!(
-            ts_code.contains("useMutation"),
-            "Missing useMutation import"
-        );
+        // Verify HttpStatusCode enum with integer values
         assert!(
-            ts_code.contains("UseMutationOptions"),
-            "Missing UseMutationOptions import"
+            ts_code.contains("export const HttpStatusCode"),
+            "Missing HttpStatusCode const"
         );
-
-        // Should NOT have useQuery or useSuspenseQuery (unused imports cause TS errors)
         assert!(
-            !ts_code.contains("useQuery"),
-            "useQuery should NOT be imported for mutation-only specs"
+            ts_code.contains("VALUE_200: 200"),
+            "Should have VALUE_200: 200"
         );
         assert!(
-            !ts_code.contains("useSuspenseQuery"),
-            "useSuspenseQuery should NOT be imported for mutation-only specs"
+            ts_code.contains("VALUE_404: 404"),
+            "Should have VALUE_404: 404"
         );
+
+        // Verify Priority enum
         assert!(
-            !ts_code.contains("UseQueryOptions"),
-            "UseQueryOptions should NOT be imported for mutation-only specs"
+            ts_code.contains("export const Priority"),
+            "Missing Priority const"
         );
+        assert!(ts_code.contains("VALUE_1: 1"), "Should have VALUE_1: 1");
+
+        // Members keep their literal types, so narrowing against them is exact
+        let compact: String = ts_code.split_whitespace().collect();
         assert!(
-            !ts_code.contains("UseSuspenseQueryOptions"),
-            "UseSuspenseQueryOptions should NOT be imported for mutation-only specs"
+            compact.contains("VALUE_500:500}asconstsatisfiesRecord<string,number>;"),
+            "Enum object should be `as const satisfies Record<string, number>`"
         );
+        typecheck_generated_code(&format!(
+            "{ts_code}\nconst notFound: 404 = HttpStatusCode.VALUE_404;\nconst status: HttpStatusCode = notFound;\nexport {{ status }};\n"
+        ))
+        .expect("enum members should keep literal types");
     }
 
     #[test]
-    fn test_allof_composition() {
-        // Test allOf for schema composition (common with Pydantic inheritance)
+    fn test_mixed_enum() {
+        // Test mixed enum values (strings and other types)
         let openapi_json = r##"{
   "openapi": "3.1.0",
-  "info": { "title": "AllOf Test API", "version": "1.0.0" },
+  "info": { "title": "Mixed Enum Test API", "version": "1.0.0" },
   "paths": {},
   "components": {
     "schemas": {
-      "BaseEntity": {
-        "type": "object",
-        "required": ["id", "createdAt"],
-        "properties": {
-          "id": { "type": "string" },
-          "createdAt": { "type": "string", "format": "date-time" }
-        }
-      },
-      "User": {
-        "allOf": [
-          { "$ref": "#/components/schemas/BaseEntity" },
-          {
-            "type": "object",
-            "required": ["email"],
-            "properties": {
-              "email": { "type": "string" },
-              "name": { "type": "string" }
-            }
-          }
-        ]
-      },
-      "Admin": {
-        "allOf": [
-          { "$ref": "#/components/schemas/User" },
-          {
-            "type": "object",
-            "required": ["role"],
-            "properties": {
-              "role": { "type": "string", "const": "admin" },
-              "permissions": { "type": "array", "items": { "type": "string" } }
-            }
-          }
-        ]
+      "MixedValue": {
+        "enum": ["auto", "manual", 0, 1, true, false, null]
       }
     }
   }
 }"##;
 
         let ts_code = generate_and_verify(openapi_json);
-        println!("=== ALLOF CODE ===\n{ts_code}\n=== END ===");
+        println!("=== MIXED ENUM CODE ===\n{ts_code}\n=== END ===");
 
-        // Verify BaseEntity is a regular interface
+        // Verify MixedValue enum has various types
         assert!(
-            ts_code.contains("export interface BaseEntity"),
-            "Missing BaseEntity interface"
+            ts_code.contains("export const MixedValue"),
+            "Missing MixedValue const"
         );
-
-        // Verify User uses intersection type with BaseEntity
         assert!(
-            ts_code.contains("export type User = BaseEntity &"),
-            "User should be intersection with BaseEntity"
+            ts_code.contains("auto: \"auto\""),
+            "Should have string auto"
         );
-
-        // Verify Admin uses intersection type
         assert!(
-            ts_code.contains("export type Admin = User &"),
-            "Admin should be intersection with User"
+            ts_code.contains("VALUE_0: 0") || ts_code.contains("VALUE_1: 1"),
+            "Should have integer values"
+        );
+        assert!(
+            ts_code.contains("TRUE: true") || ts_code.contains("FALSE: false"),
+            "Should have boolean values"
         );
+        assert!(ts_code.contains("NULL: null"), "Should have null value");
 
-        // Verify const keyword generates literal type
+        let compact: String = ts_code.split_whitespace().collect();
         assert!(
-            ts_code.contains("\"admin\""),
-            "const: admin should generate literal type"
+            compact.contains("}asconstsatisfiesRecord<string,string|number|boolean|null>;"),
+            "Mixed enum should satisfy a record of all its value kinds"
         );
     }
 
     #[test]
-    fn test_oneof_with_discriminator() {
-        // Test oneOf with discriminator for discriminated unions
+    fn test_additional_properties_false_is_closed() {
         let openapi_json = r##"{
   "openapi": "3.1.0",
-  "info": { "title": "Discriminator Test API", "version": "1.0.0" },
+  "info": { "title": "Closed Objects API", "version": "1.0.0" },
   "paths": {},
   "components": {
     "schemas": {
-      "Dog": {
+      "Point": {
         "type": "object",
-        "required": ["breed"],
-        "properties": {
-          "breed": { "type": "string" },
-          "barkVolume": { "type": "integer" }
-        }
+        "required": ["x", "y"],
+        "properties": { "x": { "type": "number" }, "y": { "type": "number" } },
+        "additionalProperties": false
       },
-      "Cat": {
+      "Empty": { "type": "object", "additionalProperties": false },
+      "Shape": {
         "type": "object",
-        "required": ["huntingSkill"],
         "properties": {
-          "huntingSkill": { "type": "string", "enum": ["lazy", "aggressive", "expert"] }
-        }
-      },
-      "Pet": {
-        "oneOf": [
-          { "$ref": "#/components/schemas/Dog" },
-          { "$ref": "#/components/schemas/Cat" }
-        ],
-        "discriminator": {
-          "propertyName": "petType",
-          "mapping": {
-            "dog": "#/components/schemas/Dog",
-            "cat": "#/components/schemas/Cat"
+          "origin": {
+            "type": "object",
+            "properties": { "x": { "type": "number" } },
+            "additionalProperties": false
           }
         }
       }
@@ -511,116 +2447,261 @@ mod tests {
 }"##;
 
         let ts_code = generate_and_verify(openapi_json);
-        println!("=== DISCRIMINATOR CODE ===\n{ts_code}\n=== END ===");
-
-        // Verify Dog and Cat are regular interfaces
-        assert!(
-            ts_code.contains("export interface Dog"),
-            "Missing Dog interface"
-        );
+        let normalized = normalize_ws(&ts_code);
         assert!(
-            ts_code.contains("export interface Cat"),
-            "Missing Cat interface"
+            normalized.contains("export interface Point {"),
+            "Closed objects should be plain interfaces. Generated:\n{ts_code}"
         );
+        assert!(normalized.contains("export type Empty = Record<string, never>;"));
+        assert!(normalized.contains("origin?: { x?: number; };"));
+        assert!(!normalized.contains("& {}"));
 
-        // Verify Pet is a discriminated union with petType field
+        let extra = typecheck_generated_code(&format!(
+            "{ts_code}\nexport const point: Point = {{ x: 1, y: 2, z: 3 }};\n"
+        ))
+        .expect_err("extra keys on a closed type should not compile");
         assert!(
-            ts_code.contains("export type Pet ="),
-            "Missing Pet type alias"
-        );
-        assert!(
-            ts_code.contains("petType: \"dog\"") && ts_code.contains("& Dog"),
-            "Pet should include discriminated Dog branch"
+            extra.contains("Object literal may only specify known properties"),
+            "Unexpected tsc output: {extra}"
         );
+        let empty = typecheck_generated_code(&format!(
+            "{ts_code}\nexport const empty: Empty = {{ key: 1 }};\n"
+        ));
         assert!(
-            ts_code.contains("petType: \"cat\"") && ts_code.contains("& Cat"),
-            "Pet should include discriminated Cat branch"
+            empty.is_err(),
+            "Keys on an empty closed object should not compile"
         );
+        typecheck_generated_code(&format!(
+            "{ts_code}\nexport const point: Point = {{ x: 1, y: 2 }};\nexport const empty: Empty = {{}};\n"
+        ))
+        .expect("known keys should compile");
     }
 
     #[test]
-    fn test_integer_enum() {
-        // Test integer enum values (HTTP status codes, error codes)
+    fn test_branded_string_formats() {
         let openapi_json = r##"{
   "openapi": "3.1.0",
-  "info": { "title": "Integer Enum Test API", "version": "1.0.0" },
-  "paths": {},
+  "info": { "title": "Branded API", "version": "1.0.0" },
+  "paths": {
+    "/users/{user_id}": {
+      "get": {
+        "operationId": "getUser",
+        "parameters": [
+          { "name": "user_id", "in": "path", "required": true, "schema": { "type": "string", "format": "uuid" } }
+        ],
+        "responses": {
+          "200": {
+            "description": "OK",
+            "content": { "application/json": { "schema": { "$ref": "#/components/schemas/User" } } }
+          }
+        }
+      }
+    },
+    "/users": {
+      "post": {
+        "operationId": "createUser",
+        "requestBody": {
+          "required": true,
+          "content": { "application/json": { "schema": { "$ref": "#/components/schemas/NewUser" } } }
+        },
+        "responses": {
+          "200": {
+            "description": "OK",
+            "content": { "application/json": { "schema": { "$ref": "#/components/schemas/User" } } }
+          }
+        }
+      }
+    }
+  },
   "components": {
     "schemas": {
-      "HttpStatusCode": {
-        "type": "integer",
-        "enum": [200, 201, 400, 404, 500]
+      "User": {
+        "type": "object",
+        "required": ["id", "email"],
+        "properties": {
+          "id": { "type": "string", "format": "uuid" },
+          "email": { "type": "string", "format": "email" },
+          "homepage": { "type": ["string", "null"], "format": "uri" },
+          "created": { "type": "string", "format": "date-time" }
+        }
       },
-      "Priority": {
-        "type": "integer",
-        "enum": [1, 2, 3, 4, 5]
+      "NewUser": {
+        "type": "object",
+        "required": ["email"],
+        "properties": { "email": { "type": "string", "format": "email" } }
       }
     }
   }
 }"##;
 
-        let ts_code = generate_and_verify(openapi_json);
-        println!("=== INTEGER ENUM CODE ===\n{ts_code}\n=== END ===");
+        // Default: formats are plain strings
+        let default_code = generate_and_verify(openapi_json);
+        assert!(normalize_ws(&default_code).contains("id: string;"));
+        assert!(!default_code.contains("__brand"));
+
+        let options = GenerateOptions {
+            branded_formats: true,
+            ..GenerateOptions::default()
+        };
+        let ts_code = generate(openapi_json, &options).unwrap();
+        let normalized = normalize_ws(&ts_code);
+        for expected in [
+            r#"export type Uuid = string & { readonly __brand: "uuid"; };"#,
+            r#"export type Email = string & { readonly __brand: "email"; };"#,
+            r#"export type Uri = string & { readonly __brand: "uri"; };"#,
+            "export function asUuid(value: string): Uuid {",
+            "id: Uuid;",
+            "email: Email;",
+            "homepage?: Uri | null;",
+            "created?: string;",
+            "user_id: Uuid;",
+        ] {
+            assert!(
+                normalized.contains(expected),
+                "Missing `{expected}`. Generated:\n{ts_code}"
+            );
+        }
+        typecheck_generated_code(&ts_code).expect("branded types should compile");
 
-        // Verify HttpStatusCode enum with integer values
-        assert!(
-            ts_code.contains("export const HttpStatusCode"),
-            "Missing HttpStatusCode const"
-        );
-        assert!(
-            ts_code.contains("VALUE_200: 200"),
-            "Should have VALUE_200: 200"
-        );
-        assert!(
-            ts_code.contains("VALUE_404: 404"),
-            "Should have VALUE_404: 404"
-        );
+        let unbranded = typecheck_generated_code(&format!(
+            "{ts_code}\nexport const body: NewUser = {{ email: \"a@b.co\" }};\n"
+        ));
+        assert!(unbranded.is_err(), "Plain strings should need a cast");
+        typecheck_generated_code(&format!(
+            "{ts_code}\nexport const body: NewUser = {{ email: asEmail(\"a@b.co\") }};\n"
+        ))
+        .expect("cast helpers should produce branded values");
 
-        // Verify Priority enum
-        assert!(
-            ts_code.contains("export const Priority"),
-            "Missing Priority const"
+        let output = run_generated_code(
+            &ts_code,
+            r#"console.log(asUuid("123E4567-E89B-12D3-A456-426614174000"), asUri("https://example.com"));
+try { asEmail("not an email"); } catch (e) { console.log((e as Error).message); }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            output.trim(),
+            "123E4567-E89B-12D3-A456-426614174000 https://example.com\nInvalid email: \"not an email\""
         );
-        assert!(ts_code.contains("VALUE_1: 1"), "Should have VALUE_1: 1");
     }
 
     #[test]
-    fn test_mixed_enum() {
-        // Test mixed enum values (strings and other types)
+    fn test_readonly_props() {
         let openapi_json = r##"{
   "openapi": "3.1.0",
-  "info": { "title": "Mixed Enum Test API", "version": "1.0.0" },
-  "paths": {},
+  "info": { "title": "Readonly API", "version": "1.0.0" },
+  "paths": {
+    "/items": {
+      "get": {
+        "operationId": "listItems",
+        "responses": {
+          "200": {
+            "description": "OK",
+            "content": { "application/json": { "schema": { "type": "array", "items": { "$ref": "#/components/schemas/Item" } } } }
+          }
+        }
+      },
+      "post": {
+        "operationId": "createItem",
+        "requestBody": {
+          "required": true,
+          "content": { "application/json": { "schema": { "$ref": "#/components/schemas/NewItem" } } }
+        },
+        "responses": {
+          "200": {
+            "description": "OK",
+            "content": { "application/json": { "schema": { "$ref": "#/components/schemas/Item" } } }
+          }
+        }
+      }
+    }
+  },
   "components": {
     "schemas": {
-      "MixedValue": {
-        "enum": ["auto", "manual", 0, 1, true, false, null]
+      "Item": {
+        "type": "object",
+        "required": ["id", "tags", "owner"],
+        "properties": {
+          "id": { "type": "string", "readOnly": true },
+          "tags": { "type": "array", "items": { "type": "string" } },
+          "labels": { "type": ["array", "null"], "items": { "type": "string" }, "readOnly": true },
+          "owner": { "$ref": "#/components/schemas/Owner" }
+        }
+      },
+      "Owner": {
+        "type": "object",
+        "required": ["name"],
+        "properties": { "name": { "type": "string" } }
+      },
+      "NewItem": {
+        "type": "object",
+        "required": ["tags"],
+        "properties": { "tags": { "type": "array", "items": { "type": "string" } } }
       }
     }
   }
 }"##;
 
-        let ts_code = generate_and_verify(openapi_json);
-        println!("=== MIXED ENUM CODE ===\n{ts_code}\n=== END ===");
-
-        // Verify MixedValue enum has various types
-        assert!(
-            ts_code.contains("export const MixedValue"),
-            "Missing MixedValue const"
-        );
-        assert!(
-            ts_code.contains("auto: \"auto\""),
-            "Should have string auto"
-        );
-        assert!(
-            ts_code.contains("VALUE_0: 0") || ts_code.contains("VALUE_1: 1"),
-            "Should have integer values"
-        );
-        assert!(
-            ts_code.contains("TRUE: true") || ts_code.contains("FALSE: false"),
-            "Should have boolean values"
-        );
-        assert!(ts_code.contains("NULL: null"), "Should have null value");
+        // Default: every property stays mutable
+        let default_code = generate_and_verify(openapi_json);
+        assert!(normalize_ws(&default_code).contains("id: string;"));
+        assert!(!default_code.contains("readonly id"));
+
+        let options = GenerateOptions {
+            readonly_props: ReadonlyProps::ReadOnly,
+            ..GenerateOptions::default()
+        };
+        let ts_code = generate(openapi_json, &options).unwrap();
+        let normalized = normalize_ws(&ts_code);
+        for expected in [
+            "readonly id: string;",
+            "readonly labels?: readonly string[] | null;",
+            "tags: string[];",
+            "name: string;",
+        ] {
+            assert!(
+                normalized.contains(expected),
+                "Missing `{expected}`. Generated:\n{ts_code}"
+            );
+        }
+        assert!(!normalized.contains("readonly tags"));
+        typecheck_generated_code(&format!(
+            "{ts_code}\nexport function touch(item: Item) {{ item.tags.push(\"new\"); }}\n"
+        ))
+        .expect("readonly output should compile");
+        let mutation = typecheck_generated_code(&format!(
+            "{ts_code}\nexport function touch(item: Item) {{ item.id = \"other\"; }}\n"
+        ));
+        assert!(
+            mutation.is_err(),
+            "Assigning a readOnly property should fail"
+        );
+
+        let options = GenerateOptions {
+            readonly_props: ReadonlyProps::Responses,
+            ..GenerateOptions::default()
+        };
+        let ts_code = generate(openapi_json, &options).unwrap();
+        let normalized = normalize_ws(&ts_code);
+        for expected in [
+            "export interface Item { readonly id: string; readonly labels?: readonly string[] | null; readonly owner: Owner; readonly tags: readonly string[]; }",
+            "export interface Owner { readonly name: string; }",
+            "export interface NewItem { tags: string[]; }",
+        ] {
+            assert!(
+                normalized.contains(expected),
+                "Missing `{expected}`. Generated:\n{ts_code}"
+            );
+        }
+        typecheck_generated_code(&format!(
+            "{ts_code}\nexport const body: NewItem = {{ tags: [\"a\"] }};\nbody.tags.push(\"b\");\n"
+        ))
+        .expect("request-only types should stay mutable");
+        for mutation in ["item.tags.push(\"new\");", "item.owner.name = \"other\";"] {
+            let result = typecheck_generated_code(&format!(
+                "{ts_code}\nexport function touch(item: Item) {{ {mutation} }}\n"
+            ));
+            assert!(result.is_err(), "`{mutation}` should not compile");
+        }
     }
 
     #[test]
@@ -748,6 +2829,119 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_nullable_ref_to_plain_object() {
+        // Nullable refs to non-recursive schemas keep the referenced type name
+        let openapi_json = r##"{
+  "openapi": "3.1.0",
+  "info": { "title": "Nullable Ref Test API", "version": "1.0.0" },
+  "paths": {},
+  "components": {
+    "schemas": {
+      "Address": {
+        "type": "object",
+        "required": ["street"],
+        "properties": {
+          "street": { "type": "string" },
+          "city": { "type": "string" }
+        }
+      },
+      "Person": {
+        "type": "object",
+        "required": ["home"],
+        "properties": {
+          "home": {
+            "anyOf": [
+              { "$ref": "#/components/schemas/Address" },
+              { "type": "null" }
+            ]
+          },
+          "work": {
+            "anyOf": [
+              { "type": "null" },
+              { "$ref": "#/components/schemas/Address", "description": "Office address" }
+            ],
+            "default": null
+          },
+          "billing": {
+            "allOf": [{ "$ref": "#/components/schemas/Address" }],
+            "nullable": true
+          }
+        }
+      }
+    }
+  }
+}"##;
+
+        let ts_code = generate_and_verify(openapi_json);
+        println!("=== NULLABLE REF CODE ===\n{ts_code}\n=== END ===");
+
+        assert!(
+            ts_code.contains("home: Address | null"),
+            "home should be Address | null"
+        );
+        assert!(
+            ts_code.contains("work?: null | Address"),
+            "work should keep Address when null comes first"
+        );
+        assert!(
+            ts_code.contains("billing?: Address | null"),
+            "billing should be Address | null"
+        );
+        assert!(
+            !ts_code.contains("unknown | null"),
+            "nullable refs must not degrade to unknown"
+        );
+    }
+
+    #[test]
+    fn test_allof_with_sibling_keywords() {
+        let openapi_json = r##"{
+  "openapi": "3.0.3",
+  "info": { "title": "AllOf Siblings Test API", "version": "1.0.0" },
+  "paths": {},
+  "components": {
+    "schemas": {
+      "Base": {
+        "type": "object",
+        "required": ["id"],
+        "properties": { "id": { "type": "string" } }
+      },
+      "Holder": {
+        "type": "object",
+        "required": ["base"],
+        "properties": {
+          "base": {
+            "allOf": [{ "$ref": "#/components/schemas/Base" }],
+            "nullable": true,
+            "description": "Nullable reference with extra keywords"
+          }
+        }
+      },
+      "Extended": {
+        "allOf": [{ "$ref": "#/components/schemas/Base" }],
+        "type": "object",
+        "required": ["label"],
+        "properties": { "label": { "type": "string" } }
+      }
+    }
+  }
+}"##;
+
+        let ts_code = generate_and_verify(openapi_json);
+        println!("=== ALLOF SIBLINGS CODE ===\n{ts_code}\n=== END ===");
+
+        assert!(
+            ts_code.contains("base: Base | null"),
+            "single-member allOf with nullable should be Base | null"
+        );
+        let compact: String = ts_code.split_whitespace().collect();
+        assert!(
+            compact.contains("exporttypeExtended=Base&{label:string;};"),
+            "sibling properties should extend the allOf"
+        );
+    }
+
     #[test]
     fn test_const_keyword() {
         // Test const keyword for literal types
@@ -815,12 +3009,74 @@ mod tests {
         let ts_code = generate_and_verify(openapi_json);
         println!("=== NULLABLE 3.0 CODE ===\n{ts_code}\n=== END ===");
 
-        // Note: The current implementation parses nullable but doesn't explicitly
-        // emit | null for OpenAPI 3.0 style. This test documents current behavior.
-        // A future enhancement could add | null when nullable: true is set.
         assert!(
-            ts_code.contains("NullableString") || ts_code.contains("string"),
-            "Should handle nullable string"
+            ts_code.contains("export type NullableString = string | null"),
+            "NullableString should be string | null"
+        );
+        assert!(
+            ts_code.contains("description?: string | null"),
+            "description should be string | null"
+        );
+    }
+
+    #[test]
+    fn test_array_of_nullable_vs_nullable_array() {
+        let openapi_json = r##"{
+  "openapi": "3.1.0",
+  "info": { "title": "Nested Nullability Test API", "version": "1.0.0" },
+  "paths": {},
+  "components": {
+    "schemas": {
+      "Tagged": {
+        "type": "object",
+        "required": ["nullableTags", "tagsOfNullable", "both", "legacy"],
+        "properties": {
+          "nullableTags": {
+            "anyOf": [{ "type": "array", "items": { "type": "string" } }, { "type": "null" }]
+          },
+          "tagsOfNullable": {
+            "type": "array",
+            "items": { "anyOf": [{ "type": "string" }, { "type": "null" }] }
+          },
+          "both": {
+            "type": ["array", "null"],
+            "items": { "type": ["string", "null"] }
+          },
+          "legacy": {
+            "type": "array",
+            "nullable": true,
+            "items": { "type": "string", "nullable": true }
+          },
+          "doubleNull": {
+            "anyOf": [{ "type": "string", "nullable": true }, { "type": "null" }]
+          }
+        }
+      }
+    }
+  }
+}"##;
+
+        let ts_code = normalize_ws(&generate_and_verify(openapi_json));
+
+        assert!(
+            ts_code.contains("nullableTags: string[] | null;"),
+            "Nullable array should be string[] | null:\n{ts_code}"
+        );
+        assert!(
+            ts_code.contains("tagsOfNullable: (string | null)[];"),
+            "Array of nullable should be (string | null)[]:\n{ts_code}"
+        );
+        assert!(
+            ts_code.contains("both: (string | null)[] | null;"),
+            "Nullable array of nullable should be (string | null)[] | null:\n{ts_code}"
+        );
+        assert!(
+            ts_code.contains("legacy: (string | null)[] | null;"),
+            "OpenAPI 3.0 nullable should apply at each level:\n{ts_code}"
+        );
+        assert!(
+            ts_code.contains("doubleNull?: string | null;"),
+            "Nested nullable unions should keep a single null:\n{ts_code}"
         );
     }
 
@@ -1281,21 +3537,79 @@ mod tests {
         }
       }
     }
-  },
-  "components": { "schemas": {} }
+  },
+  "components": { "schemas": {} }
+}"##;
+
+        let result = generate(openapi_json, &GenerateOptions::default());
+        // Should return an error for duplicate param names
+        assert!(
+            result.is_err(),
+            "Should fail with duplicate param names, got: {result:?}"
+        );
+        let err = result.unwrap_err();
+        assert!(
+            err.contains("duplicate") || err.contains("Duplicate"),
+            "Error should mention duplicate: {err}"
+        );
+    }
+
+    #[test]
+    fn test_operation_params_override_path_params() {
+        let openapi_json = r##"{
+  "openapi": "3.1.0",
+  "info": { "title": "Override API", "version": "1.0.0" },
+  "paths": {
+    "/items/{itemId}": {
+      "parameters": [
+        { "name": "itemId", "in": "path", "required": true, "schema": { "type": "string" } },
+        { "name": "verbose", "in": "query", "required": false, "schema": { "type": "boolean" } }
+      ],
+      "get": {
+        "operationId": "getItem",
+        "parameters": [
+          { "name": "itemId", "in": "path", "required": true, "schema": { "type": "integer" } },
+          { "name": "trace", "in": "header", "required": false, "schema": { "type": "string" } }
+        ],
+        "responses": { "200": { "description": "OK", "content": { "application/json": { "schema": { "type": "string" } } } } }
+      },
+      "delete": {
+        "operationId": "deleteItem",
+        "responses": { "204": { "description": "Deleted" } }
+      }
+    }
+  }
 }"##;
+        let ts_code = generate_and_verify(openapi_json);
+        let compact: String = ts_code.split_whitespace().collect();
+        for expected in [
+            "exportinterfaceGetItemParams{verbose?:boolean;itemId:number;trace?:string;}",
+            "exportinterfaceDeleteItemParams{itemId:string;verbose?:boolean;}",
+        ] {
+            assert!(
+                compact.contains(expected),
+                "Missing `{expected}`. Generated:\n{ts_code}"
+            );
+        }
 
-        let result = generate(openapi_json);
-        // Should return an error for duplicate param names
-        assert!(
-            result.is_err(),
-            "Should fail with duplicate param names, got: {result:?}"
+        // Reusing a path-level name in another location can't share one params object
+        let clash = openapi_json.replace(
+            r#"{ "name": "trace", "in": "header""#,
+            r#"{ "name": "verbose", "in": "header""#,
         );
-        let err = result.unwrap_err();
+        let err = generate(&clash, &GenerateOptions::default()).unwrap_err();
         assert!(
-            err.contains("duplicate") || err.contains("Duplicate"),
-            "Error should mention duplicate: {err}"
+            err.contains("'verbose'") && err.contains("query and header"),
+            "Got: {err}"
         );
+
+        // The same name and location twice within one level is a true duplicate
+        let repeated = openapi_json.replace(
+            r#"{ "name": "trace", "in": "header""#,
+            r#"{ "name": "itemId", "in": "path""#,
+        );
+        let err = generate(&repeated, &GenerateOptions::default()).unwrap_err();
+        assert!(err.contains("operation-level"), "Got: {err}");
     }
 
     #[test]
@@ -1325,7 +3639,7 @@ mod tests {
   "components": { "schemas": {} }
 }"##;
 
-        let result = generate(openapi_json);
+        let result = generate(openapi_json, &GenerateOptions::default());
         // Should return an error for duplicate operationIds
         assert!(
             result.is_err(),
@@ -1574,6 +3888,118 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_url_params_are_encoded() {
+        let openapi_json = r##"{
+  "openapi": "3.1.0",
+  "info": { "title": "Encoding Test API", "version": "1.0.0" },
+  "paths": {
+    "/items/{itemId}": {
+      "get": {
+        "operationId": "getItem",
+        "parameters": [
+          { "name": "itemId", "in": "path", "required": true, "schema": { "type": "string" } },
+          { "name": "q", "in": "query", "required": false, "schema": { "type": "string" } },
+          { "name": "tags", "in": "query", "required": false, "schema": { "type": "array", "items": { "type": "string" } } }
+        ],
+        "responses": { "200": { "description": "OK", "content": { "application/json": { "schema": { "type": "string" } } } } }
+      }
+    }
+  },
+  "components": { "schemas": {} }
+}"##;
+
+        let ts_code = generate_and_verify(openapi_json);
+        assert!(
+            normalize_ws(&ts_code).contains("${encodeURIComponent(String(params.itemId))}"),
+            "Path params should be encoded: {ts_code}"
+        );
+
+        let output = run_generated_code(
+            &ts_code,
+            r#"globalThis.fetch = (async (url: string) => {
+  console.log(url);
+  return new Response("\"ok\"", { headers: { "content-type": "application/json" } });
+}) as typeof fetch;
+await getItem({ itemId: "a/b?c" });
+await getItem({ itemId: "a/b?c", q: "a/b?c", tags: ["a/b?c", "d&e"] });"#,
+        )
+        .unwrap();
+        assert_eq!(
+            output.trim(),
+            "/items/a%2Fb%3Fc\n/items/a%2Fb%3Fc?q=a%2Fb%3Fc&tags=a%2Fb%3Fc&tags=d%26e"
+        );
+    }
+
+    #[test]
+    fn test_event_stream_response() {
+        let openapi_json = r##"{
+  "openapi": "3.1.0",
+  "info": { "title": "Streaming Test API", "version": "1.0.0" },
+  "paths": {
+    "/chat": {
+      "post": {
+        "operationId": "agentChat",
+        "requestBody": { "required": true, "content": { "application/json": { "schema": { "$ref": "#/components/schemas/ChatIn" } } } },
+        "responses": { "200": { "description": "OK", "content": { "text/event-stream": { "schema": { "$ref": "#/components/schemas/ChatChunk" } } } } }
+      }
+    },
+    "/ticks": {
+      "get": {
+        "operationId": "streamTicks",
+        "responses": { "200": { "description": "OK", "content": { "text/event-stream": {} } } }
+      }
+    }
+  },
+  "components": {
+    "schemas": {
+      "ChatIn": { "type": "object", "required": ["message"], "properties": { "message": { "type": "string" } } },
+      "ChatChunk": { "type": "object", "required": ["text"], "properties": { "text": { "type": "string" } } }
+    }
+  }
+}"##;
+
+        let ts_code = generate_and_verify(openapi_json);
+        let normalized = normalize_ws(&ts_code);
+        assert!(
+            normalized.contains(
+                "Promise<{ data: AsyncGenerator<ServerSentEvent<ChatChunk>, void, undefined>; }>"
+            ),
+            "Event stream should return an async generator of typed events: {ts_code}"
+        );
+        assert!(
+            normalized.contains("parseEventStream(res, (data: string): string => data)"),
+            "Schema-less event stream should yield string data: {ts_code}"
+        );
+        assert!(
+            !normalized.contains("await res.json()") && !normalized.contains("parseJsonBody"),
+            "Event stream should not be read as JSON: {ts_code}"
+        );
+
+        // Chunks split events mid-line; comments, `\r\n` and trailing partial events are handled
+        let output = run_generated_code(
+            &ts_code,
+            r#"const chunks = [": keep-alive\n\nevent: token\nda", "ta: {\"text\":\"Hel\"}\r\n\r\nid: 2\nretry: 500\n", "data: {\"text\":\"lo\"}\n\ndata: {\"text\":"];
+globalThis.fetch = (async () => new Response(new ReadableStream({
+  start(controller) {
+    for (const chunk of chunks) controller.enqueue(new TextEncoder().encode(chunk));
+    controller.close();
+  },
+}), { headers: { "content-type": "text/event-stream" } })) as typeof fetch;
+const { data } = await agentChat({ message: "hi" });
+for await (const event of data) console.log(JSON.stringify(event));"#,
+        )
+        .unwrap();
+        assert_eq!(
+            output.trim(),
+            [
+                r#"{"event":"token","data":{"text":"Hel"}}"#,
+                r#"{"id":"2","retry":500,"data":{"text":"lo"}}"#,
+            ]
+            .join("\n")
+        );
+    }
+
     #[test]
     fn test_query_param_null_handling() {
         // Test case: null values should be excluded from query params
@@ -1678,6 +4104,60 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_error_response_types_parameterize_api_error() {
+        let openapi_json = r##"{
+  "openapi": "3.1.0",
+  "info": { "title": "Typed Errors API", "version": "1.0.0" },
+  "paths": {
+    "/items/{itemId}": {
+      "get": {
+        "operationId": "getItem",
+        "parameters": [{ "name": "itemId", "in": "path", "required": true, "schema": { "type": "string" } }],
+        "responses": {
+          "200": { "description": "OK", "content": { "application/json": { "schema": { "$ref": "#/components/schemas/Item" } } } },
+          "404": { "description": "Not Found", "content": { "application/json": { "schema": { "$ref": "#/components/schemas/ErrorResponse" } } } },
+          "422": { "description": "Invalid", "content": { "application/json": { "schema": { "$ref": "#/components/schemas/HTTPValidationError" } } } },
+          "500": { "description": "Server Error", "content": { "application/json": { "schema": { "$ref": "#/components/schemas/ErrorResponse" } } } }
+        }
+      }
+    },
+    "/items": {
+      "post": {
+        "operationId": "createItem",
+        "requestBody": { "required": true, "content": { "application/json": { "schema": { "$ref": "#/components/schemas/Item" } } } },
+        "responses": { "201": { "description": "Created", "content": { "application/json": { "schema": { "$ref": "#/components/schemas/Item" } } } } }
+      }
+    }
+  },
+  "components": {
+    "schemas": {
+      "Item": { "type": "object", "required": ["id"], "properties": { "id": { "type": "string" } } },
+      "ErrorResponse": { "type": "object", "required": ["detail"], "properties": { "detail": { "type": "string" } } },
+      "HTTPValidationError": { "type": "object", "properties": { "detail": { "type": "array", "items": { "type": "object" } } } }
+    }
+  }
+}"##;
+
+        let ts_code = generate_and_verify(openapi_json);
+        // Compare without whitespace so the check is independent of SWC's formatting
+        let compact: String = ts_code.split_whitespace().collect();
+        for expected in [
+            "exportclassApiError<TBody=unknown>extendsError{",
+            "body:TBody;",
+            "thrownewApiError(res.status,res.statusText,parsedasErrorResponse|HTTPValidationError)",
+            "ApiFailure<ErrorResponse|HTTPValidationError>,TData>",
+            // No declared error body: the body stays `unknown`
+            "thrownewApiError(res.status,res.statusText,parsed)",
+            ",ApiFailure,Item>",
+        ] {
+            assert!(
+                compact.contains(expected),
+                "Missing `{expected}`. Generated:\n{ts_code}"
+            );
+        }
+    }
+
     #[test]
     fn test_hook_error_type() {
         // Test case: hooks should use ApiError type instead of Error
@@ -2156,20 +4636,22 @@ mod tests {
 
                 // Check if node_modules already has working dependencies (skip bun install if so)
                 let react_query_path = temp_dir.join("node_modules/@tanstack/react-query");
+                let msw_path = temp_dir.join("node_modules/msw");
                 let tsc_path = temp_dir.join("node_modules/typescript/lib/tsc.js");
-                if react_query_path.exists() && tsc_path.exists() {
+                if react_query_path.exists() && msw_path.exists() && tsc_path.exists() {
                     return Ok(temp_dir);
                 }
 
                 // Remove possibly corrupted node_modules before reinstalling
                 let _ = std::fs::remove_dir_all(temp_dir.join("node_modules"));
 
-                // Write package.json with @tanstack/react-query
+                // Write package.json with @tanstack/react-query and msw
                 let package_json = r#"{
   "name": "apx-ts-typecheck",
   "private": true,
   "dependencies": {
     "@tanstack/react-query": "^5",
+    "msw": "^2",
     "typescript": "^5"
   }
 }
@@ -2208,20 +4690,27 @@ mod tests {
 
     /// Helper to run TypeScript type checking on generated code
     fn typecheck_generated_code(code: &str) -> Result<(), String> {
-        use std::io::Write;
+        typecheck_generated_files(&[("api.ts", code)])
+    }
 
+    /// Typecheck several generated modules together, so relative imports between them resolve.
+    fn typecheck_generated_files(files: &[(&str, &str)]) -> Result<(), String> {
         let test_env = get_ts_test_env()?;
 
-        // Generate unique filename to avoid race conditions when tests run in parallel
+        // Generate unique directory to avoid race conditions when tests run in parallel
         let unique_id = std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)
             .map(|d| d.as_nanos())
             .unwrap_or(0);
-        let test_filename = format!("_test_{unique_id}.ts");
-        let test_file = test_env.join(&test_filename);
-
-        let mut file = std::fs::File::create(&test_file).map_err(|e| e.to_string())?;
-        file.write_all(code.as_bytes()).map_err(|e| e.to_string())?;
+        let test_dirname = format!("_test_{unique_id}");
+        let test_dir = test_env.join(&test_dirname);
+        std::fs::create_dir_all(&test_dir).map_err(|e| e.to_string())?;
+
+        let mut test_filenames = Vec::new();
+        for (name, code) in files {
+            std::fs::write(test_dir.join(name), code).map_err(|e| e.to_string())?;
+            test_filenames.push(format!("{test_dirname}/{name}"));
+        }
 
         // Run tsc from the test environment directory with explicit compiler options
         // Using `bun x` which is equivalent to `bunx`
@@ -2240,14 +4729,14 @@ mod tests {
                 "ESNext",
                 "--moduleResolution",
                 "bundler",
-                &test_filename,
             ])
+            .args(&test_filenames)
             .current_dir(&test_env)
             .output()
             .map_err(|e| format!("Failed to run bun x tsc: {e}"))?;
 
-        // Cleanup test file
-        std::fs::remove_file(&test_file).ok();
+        // Cleanup test files
+        std::fs::remove_dir_all(&test_dir).ok();
 
         if output.status.success() {
             Ok(())
@@ -2258,6 +4747,35 @@ mod tests {
         }
     }
 
+    /// Helper to execute generated code followed by `script` with bun, returning stdout.
+    fn run_generated_code(code: &str, script: &str) -> Result<String, String> {
+        let test_env = get_ts_test_env()?;
+
+        let unique_id = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or(0);
+        let test_filename = format!("_run_{unique_id}.ts");
+        let test_file = test_env.join(&test_filename);
+
+        std::fs::write(&test_file, format!("{code}\n{script}")).map_err(|e| e.to_string())?;
+
+        let output = Command::new("bun")
+            .args(["run", &test_filename])
+            .current_dir(&test_env)
+            .output()
+            .map_err(|e| format!("Failed to run bun: {e}"))?;
+
+        std::fs::remove_file(&test_file).ok();
+
+        if output.status.success() {
+            Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+        } else {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            Err(format!("bun run failed:\n{stderr}"))
+        }
+    }
+
     /// Normalize whitespace in generated code for format-resilient assertions.
     /// Collapses all whitespace sequences (including newlines) into single spaces.
     fn normalize_ws(s: &str) -> String {
@@ -2269,7 +4787,7 @@ mod tests {
     // Reason: panicking on failure is idiomatic in tests
     #[allow(clippy::panic)]
     fn generate_and_verify(openapi_json: &str) -> String {
-        let result = generate(openapi_json);
+        let result = generate(openapi_json, &GenerateOptions::default());
         assert!(result.is_ok(), "Generation failed: {:?}", result.err());
 
         let ts_code = result.unwrap();
@@ -3084,6 +5602,46 @@ export function useGetCatBuggy<TData = { data: { meow: string } }>(
         );
     }
 
+    #[test]
+    fn test_allof_conflicting_property_types_error() {
+        let openapi_json = r##"{
+  "openapi": "3.1.0",
+  "info": { "title": "AllOf Conflict API", "version": "1.0.0" },
+  "paths": {},
+  "components": {
+    "schemas": {
+      "BaseEntity": {
+        "type": "object",
+        "properties": { "id": { "type": "string" }, "kind": { "type": "string" } }
+      },
+      "Widget": {
+        "allOf": [
+          { "$ref": "#/components/schemas/BaseEntity" },
+          { "type": "object", "properties": { "id": { "type": "integer" } } }
+        ]
+      }
+    }
+  }
+}"##;
+
+        let err = generate(openapi_json, &GenerateOptions::default()).unwrap_err();
+        assert!(
+            err.contains("Conflicting types for property 'id'"),
+            "Error should name the property. Got: {err}"
+        );
+        assert!(
+            err.contains("schema 'Widget'") && err.contains("'string' vs 'number'"),
+            "Error should name the schema and both types. Got: {err}"
+        );
+
+        // Narrowing (literal, enum) and widening (nullable) merges are compatible
+        let compatible = openapi_json.replace(
+            r#"{ "id": { "type": "integer" } }"#,
+            r#"{ "id": { "type": ["string", "null"] }, "kind": { "type": "string", "enum": ["widget"] } }"#,
+        );
+        generate_and_verify(&compatible);
+    }
+
     // =========================================================================
     // Identifier sanitization tests
     // =========================================================================
@@ -3533,12 +6091,71 @@ export function useGetCatBuggy<TData = { data: { meow: string } }>(
 
         let ts_code = generate_and_verify(openapi_json);
         println!("=== MULTIPLE CONTENT TYPES ===\n{ts_code}\n=== END ===");
+        let normalized = normalize_ws(&ts_code);
+
+        // Parser is chosen from the runtime Content-Type header
+        assert!(
+            normalized
+                .contains(r#"const ct = (res.headers.get("content-type") ?? "").toLowerCase();"#),
+            "Should read the Content-Type header"
+        );
+        assert!(
+            normalized.contains(
+                r#"data: ct.includes("application/json") ? await parseJsonBody(res) : await res.text()"#
+            ),
+            "Should branch between json and text parsing"
+        );
+        assert!(
+            normalized.contains("Promise<{ data: { value?: string; } | string; }>"),
+            "Return type should be the union of all content types"
+        );
+    }
+
+    #[test]
+    fn test_application_json_schema_preferred() {
+        // `application/hal+json` sorts first, but `application/json` supplies the type
+        let openapi_json = r##"{
+  "openapi": "3.1.0",
+  "info": { "title": "Multi Content API", "version": "1.0.0" },
+  "paths": {
+    "/data": {
+      "get": {
+        "operationId": "getData",
+        "responses": {
+          "200": {
+            "description": "OK",
+            "content": {
+              "application/hal+json": { "schema": { "type": "object", "properties": { "_links": { "type": "object" } } } },
+              "application/json": { "schema": { "type": "object", "properties": { "value": { "type": "string" } } } },
+              "application/xml": { "schema": { "type": "string" } }
+            }
+          }
+        }
+      }
+    }
+  },
+  "components": { "schemas": {} }
+}"##;
+
+        let ts_code = generate_and_verify(openapi_json);
+        let normalized = normalize_ws(&ts_code);
+
+        assert!(
+            normalized.contains("Promise<{ data: { value?: string; } | string; }>"),
+            "The application/json schema should type the JSON variant: {normalized}"
+        );
+        assert!(
+            !normalized.contains("_links"),
+            "The vendor JSON schema should not be used: {normalized}"
+        );
+    }
 
-        // Should use first content type (alphabetically or in order)
-        // The generated code should compile either way
+    #[test]
+    fn test_single_content_type_has_no_runtime_branch() {
+        let ts_code = generate_and_verify(TEST_OPENAPI_JSON);
         assert!(
-            ts_code.contains("res.json()") || ts_code.contains("res.text()"),
-            "Should have appropriate response parsing"
+            !ts_code.contains("res.headers.get(\"content-type\")"),
+            "Single content type responses should parse directly"
         );
     }
 
@@ -3695,6 +6312,88 @@ export function useGetCatBuggy<TData = { data: { meow: string } }>(
         );
     }
 
+    #[test]
+    fn test_formdata_builder_honors_encoding() {
+        let openapi_json = r##"{
+  "openapi": "3.1.0",
+  "info": { "title": "FormData Builder API", "version": "1.0.0" },
+  "paths": {
+    "/upload": {
+      "post": {
+        "operationId": "uploadFile",
+        "requestBody": {
+          "required": true,
+          "content": {
+            "multipart/form-data": {
+              "schema": { "$ref": "#/components/schemas/UploadForm" },
+              "encoding": {
+                "config": { "contentType": "application/vnd.api+json" },
+                "note": { "contentType": "text/plain" }
+              }
+            }
+          }
+        },
+        "responses": { "204": { "description": "Uploaded" } }
+      }
+    }
+  },
+  "components": {
+    "schemas": {
+      "Meta": { "type": "object", "required": ["title"], "properties": { "title": { "type": "string" } } },
+      "UploadForm": {
+        "type": "object",
+        "required": ["file"],
+        "properties": {
+          "file": { "type": "string", "format": "binary" },
+          "attachments": { "type": "array", "items": { "type": "string", "format": "binary" } },
+          "meta": { "$ref": "#/components/schemas/Meta" },
+          "config": { "type": "object", "additionalProperties": true },
+          "note": { "type": "string" },
+          "tags": { "type": "array", "items": { "type": "string" } }
+        }
+      }
+    }
+  }
+}"##;
+
+        let ts_code = generate_and_verify(openapi_json);
+        let normalized = normalize_ws(&ts_code);
+        for expected in [
+            "export const uploadFileFormData = (data: {",
+            "attachments?: Blob[];",
+            "file: Blob;",
+            "meta?: Meta;",
+            r#"return toFormData(data, { config: "application/vnd.api+json", meta: "application/json" });"#,
+            // The fetch function still accepts hand-built FormData
+            "data: FormData",
+        ] {
+            assert!(
+                normalized.contains(expected),
+                "Missing `{expected}`. Generated:\n{ts_code}"
+            );
+        }
+
+        let output = run_generated_code(
+            &ts_code,
+            r#"const form = uploadFileFormData({
+  file: new Blob(["abc"]),
+  meta: { title: "t" },
+  tags: ["a", "b"],
+  note: "hi",
+  config: { x: 1 },
+  attachments: undefined,
+});
+for (const [name, value] of (form as any).entries()) {
+  console.log(name, value instanceof Blob ? `${value.type || "blob"}:${await value.text()}` : value);
+}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            output.trim(),
+            "file blob:abc\nmeta application/json:{\"title\":\"t\"}\ntags a\ntags b\nnote hi\nconfig application/vnd.api+json:{\"x\":1}"
+        );
+    }
+
     #[test]
     fn test_urlencoded_body() {
         // URL-encoded body should use URLSearchParams
@@ -3856,7 +6555,7 @@ export function useGetCatBuggy<TData = { data: { meow: string } }>(
   "components": { "schemas": {} }
 }"##;
 
-        let result = generate(openapi_json);
+        let result = generate(openapi_json, &GenerateOptions::default());
 
         assert!(
             result.is_err(),
@@ -3884,7 +6583,7 @@ export function useGetCatBuggy<TData = { data: { meow: string } }>(
 }"##;
 
         // Should not error - just produce empty output
-        let result = generate(openapi_json);
+        let result = generate(openapi_json, &GenerateOptions::default());
         assert!(
             result.is_ok(),
             "Empty paths should not error: {:?}",
@@ -3923,7 +6622,7 @@ export function useGetCatBuggy<TData = { data: { meow: string } }>(
   }
 }"##;
 
-        let result = generate(openapi_json);
+        let result = generate(openapi_json, &GenerateOptions::default());
         assert!(
             result.is_ok(),
             "Types-only spec should not error: {:?}",
@@ -3941,12 +6640,63 @@ export function useGetCatBuggy<TData = { data: { meow: string } }>(
         assert!(ts_code.contains("Status"), "Should generate Status type");
     }
 
+    #[test]
+    fn test_schema_names_sanitized_to_unique_identifiers() {
+        let openapi_json = r##"{
+  "openapi": "3.1.0",
+  "info": { "title": "Names API", "version": "1.0.0" },
+  "paths": {
+    "/items": {
+      "get": {
+        "operationId": "listItems",
+        "responses": {
+          "200": {
+            "description": "OK",
+            "content": {
+              "application/json": { "schema": { "$ref": "#/components/schemas/Foo.Bar" } }
+            }
+          }
+        }
+      }
+    }
+  },
+  "components": {
+    "schemas": {
+      "Foo-Bar": {
+        "type": "object",
+        "properties": { "dash": { "type": "string" } },
+        "required": ["dash"]
+      },
+      "Foo.Bar": {
+        "type": "object",
+        "properties": { "other": { "$ref": "#/components/schemas/Foo-Bar" } },
+        "required": ["other"]
+      },
+      "FooBar": {
+        "type": "object",
+        "properties": { "plain": { "type": "boolean" } }
+      }
+    }
+  }
+}"##;
+
+        let ts_code = generate_and_verify(openapi_json);
+        let normalized = normalize_ws(&ts_code);
+
+        // The valid name keeps its spelling; sanitized names get suffixes in sorted order
+        assert!(normalized.contains("export interface FooBar { plain?: boolean;"));
+        assert!(normalized.contains("export interface FooBar2 { dash: string;"));
+        assert!(normalized.contains("export interface FooBar3 { other: FooBar2;"));
+        assert!(normalized.contains("Promise<{ data: FooBar3; }>"));
+        assert!(!ts_code.contains("Foo-Bar") && !ts_code.contains("Foo.Bar"));
+    }
+
     #[test]
     fn test_invalid_json_error() {
         // Invalid JSON should produce helpful error
         let invalid_json = r#"{ "openapi": "3.1.0", invalid }"#;
 
-        let result = generate(invalid_json);
+        let result = generate(invalid_json, &GenerateOptions::default());
         assert!(result.is_err(), "Invalid JSON should produce an error");
 
         let err = result.unwrap_err();
@@ -3964,7 +6714,7 @@ export function useGetCatBuggy<TData = { data: { meow: string } }>(
   "info": { "title": "Incomplete API", "version": "1.0.0" }
 }"##;
 
-        let result = generate(incomplete_json);
+        let result = generate(incomplete_json, &GenerateOptions::default());
         assert!(result.is_err(), "Missing paths should produce an error");
     }
 
@@ -3989,7 +6739,7 @@ export function useGetCatBuggy<TData = { data: { meow: string } }>(
 
         // This should still generate code (with NonExistent as type name)
         // The TypeScript compiler will catch if the type doesn't exist
-        let result = generate(openapi_json);
+        let result = generate(openapi_json, &GenerateOptions::default());
         assert!(
             result.is_ok(),
             "Non-existent ref should generate (TS catches error): {:?}",
@@ -0,0 +1,441 @@
+//! Options controlling TypeScript client generation.
+
+/// Comment written at the top of every generated file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Banner {
+    /// `/* eslint-disable */` and a do-not-edit notice naming the spec's `info.title`
+    /// and `info.version`.
+    Standard {
+        /// Also add a `// @generated` line, which review tools use to collapse
+        /// generated files in diffs.
+        generated_marker: bool,
+    },
+    /// Text written verbatim, so it must be valid TypeScript comments.
+    Custom(String),
+    /// No banner.
+    None,
+}
+
+impl Default for Banner {
+    fn default() -> Self {
+        Self::Standard {
+            generated_marker: true,
+        }
+    }
+}
+
+/// How generated fetch functions resolve the API base URL.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum BaseUrl {
+    /// Request paths as-is, relative to the page origin.
+    #[default]
+    Relative,
+    /// Prefix every request with a fixed URL.
+    Fixed(String),
+    /// Read `import.meta.env.<var>` at runtime, falling back to `fallback` when unset.
+    Env {
+        /// Name of the Vite env var, e.g. `VITE_API_URL`.
+        var: String,
+        /// Value used when the env var is not defined (`""` keeps requests relative).
+        fallback: String,
+    },
+    /// Use the spec's first `servers` URL, substituting variable defaults.
+    ///
+    /// Variables can be changed at runtime with the generated `setServerVariables`.
+    /// Falls back to [`BaseUrl::Relative`] when the spec declares no servers.
+    Server,
+}
+
+/// Which properties are emitted with the TypeScript `readonly` modifier.
+///
+/// Array-typed properties also become `readonly T[]`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ReadonlyProps {
+    /// Every property is mutable.
+    #[default]
+    Off,
+    /// Properties declared `readOnly: true`.
+    ReadOnly,
+    /// Properties declared `readOnly: true`, plus every property of a success
+    /// response type and of the component types it references.
+    Responses,
+}
+
+/// How React Query hooks are named: `{prefix}{Operation}{suffix}`.
+///
+/// The defaults give `useListItems`, `useListItemsSuspense` and `useCreateItem`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HookNaming {
+    /// Prefix of every hook name. Must start with `use`, which React relies on
+    /// to recognize hooks.
+    pub prefix: String,
+    /// Suffix of suspense query hooks.
+    pub suspense_suffix: String,
+    /// Suffix of mutation hooks.
+    pub mutation_suffix: String,
+}
+
+impl Default for HookNaming {
+    fn default() -> Self {
+        Self {
+            prefix: "use".to_string(),
+            suspense_suffix: "Suspense".to_string(),
+            mutation_suffix: String::new(),
+        }
+    }
+}
+
+impl HookNaming {
+    /// Check that the parts form valid hook names.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error naming the offending part.
+    pub fn validate(&self) -> Result<(), String> {
+        if !self.prefix.starts_with("use") {
+            return Err(format!(
+                "Hook prefix '{}' must start with 'use' so React treats the functions as hooks",
+                self.prefix
+            ));
+        }
+        for (part, value) in [
+            ("prefix", &self.prefix),
+            ("suspense suffix", &self.suspense_suffix),
+            ("mutation suffix", &self.mutation_suffix),
+        ] {
+            if !value
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+            {
+                return Err(format!(
+                    "Hook {part} '{value}' may only contain letters, digits, '_' and '$'"
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Options for [`generate`](super::generate).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GenerateOptions {
+    /// Base URL prepended to every request path.
+    pub base_url: BaseUrl,
+    /// Emit `bigint` instead of `number` for `format: int64`/`uint64` integers.
+    ///
+    /// Responses are parsed so those fields arrive as `BigInt`, and JSON bodies
+    /// serialize them as strings. Off by default.
+    pub bigint_int64: bool,
+    /// Also emit a Mock Service Worker handlers module (see [`GenerateResult::msw_handlers`]).
+    ///
+    /// [`GenerateResult::msw_handlers`]: super::GenerateResult::msw_handlers
+    pub msw_handlers: bool,
+    /// Merge schema `default`s into JSON request bodies before sending.
+    ///
+    /// Defaults are emitted as a typed `<operation>BodyDefaults` constant, and
+    /// required properties with a default become optional in the body type.
+    pub body_defaults: bool,
+    /// Also export one object per OpenAPI tag bundling its fetch functions and hooks.
+    ///
+    /// Operations are grouped by their first tag; untagged ones go into `_default`.
+    pub tag_groups: bool,
+    /// Also export a `matchXxx(value, handlers)` helper per component union.
+    ///
+    /// Discriminated unions dispatch on the discriminator; other unions dispatch on
+    /// the value's runtime kind (`string`, `object`, ...). Unions whose members
+    /// can't be told apart at runtime get no helper.
+    pub union_matchers: bool,
+    /// Emit `format: uuid`/`email`/`uri` strings as branded types (`Uuid`, `Email`, `Uri`).
+    ///
+    /// Each brand gets an `asXxx(value)` helper that checks the string and casts it.
+    /// Other formats stay plain `string`.
+    pub branded_formats: bool,
+    /// Retry requests answered with a retryable status, backing off exponentially.
+    ///
+    /// Idempotent methods retry by default; other methods only when the caller sets
+    /// `retries`. A `Retry-After` header overrides the computed delay.
+    pub fetch_retry: bool,
+    /// Also emit the client as separate modules (see [`GenerateResult::modules`]).
+    ///
+    /// Hooks import from the client, which imports from the types; `index.ts`
+    /// re-exports all three.
+    ///
+    /// [`GenerateResult::modules`]: super::GenerateResult::modules
+    pub split_modules: bool,
+    /// Also emit a module of typed example values, one per component schema
+    /// (see [`GenerateResult::examples`]).
+    ///
+    /// [`GenerateResult::examples`]: super::GenerateResult::examples
+    pub examples: bool,
+    /// Also export a `safeXxx` variant per fetch function that resolves with an
+    /// `ApiResult` (`{ ok: true, data }` or `{ ok: false, error }`) instead of throwing.
+    ///
+    /// Hooks keep using the throwing functions, since React Query expects rejections.
+    pub safe_results: bool,
+    /// Also export an `assertXxxParams(params)` guard per operation with required
+    /// path, query or header params, and call it first in the fetch function.
+    ///
+    /// The guard throws a `TypeError` naming the operation and the param when a
+    /// required one is `undefined`, `null` or `""`, instead of sending a request
+    /// to a URL like `/items/undefined`.
+    pub param_guards: bool,
+    /// Fail generation when a schema's `required` array names a property that is
+    /// neither in its `properties` nor contributed by an `allOf` branch.
+    ///
+    /// Off by default, which reports those names as
+    /// [`WarningKind::MissingRequiredProperty`](super::WarningKind::MissingRequiredProperty)
+    /// warnings instead.
+    pub strict_required: bool,
+    /// Emit `readonly` properties so fetched data can't be mutated by accident.
+    ///
+    /// Only types change: `readonly` properties still accept object literals,
+    /// so request bodies are built the same way.
+    pub readonly_props: ReadonlyProps,
+    /// Names of the generated hooks.
+    pub hook_naming: HookNaming,
+    /// Comment prepended to every generated file.
+    pub banner: Banner,
+}
+
+impl GenerateOptions {
+    /// Read options from the `[tool.apx.api]` section of a parsed `pyproject.toml`.
+    ///
+    /// A missing section gives the defaults. A fixed `base-url` is used as the
+    /// fallback when `base-url-env` is also set; `base-url-server` applies only
+    /// when neither is set.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error for values of the wrong type.
+    pub fn from_pyproject(pyproject: &toml::Value) -> Result<Self, String> {
+        let Some(api) = pyproject
+            .get("tool")
+            .and_then(|tool| tool.get("apx"))
+            .and_then(|apx| apx.get("api"))
+        else {
+            return Ok(Self::default());
+        };
+        let table = api.as_table().ok_or("[tool.apx.api] must be a table")?;
+
+        let text = |key: &str| -> Result<Option<&str>, String> {
+            table
+                .get(key)
+                .map(|value| {
+                    value
+                        .as_str()
+                        .ok_or_else(|| format!("[tool.apx.api] {key} must be a string"))
+                })
+                .transpose()
+        };
+        let flag = |key: &str| -> Result<Option<bool>, String> {
+            table
+                .get(key)
+                .map(|value| {
+                    value
+                        .as_bool()
+                        .ok_or_else(|| format!("[tool.apx.api] {key} must be true or false"))
+                })
+                .transpose()
+        };
+        let is_enabled = |key: &str| flag(key).map(|value| value == Some(true));
+        let non_empty = |key: &str| {
+            text(key).map(|value| value.map(str::trim).filter(|value| !value.is_empty()))
+        };
+
+        let base_url = match (non_empty("base-url-env")?, non_empty("base-url")?) {
+            (Some(var), fallback) => BaseUrl::Env {
+                var: var.to_string(),
+                fallback: fallback.unwrap_or_default().to_string(),
+            },
+            (None, Some(url)) => BaseUrl::Fixed(url.to_string()),
+            (None, None) if is_enabled("base-url-server")? => BaseUrl::Server,
+            (None, None) => BaseUrl::Relative,
+        };
+        let readonly_props = match table.get("readonly-props") {
+            None => ReadonlyProps::Off,
+            Some(toml::Value::Boolean(true)) => ReadonlyProps::ReadOnly,
+            Some(toml::Value::Boolean(false)) => ReadonlyProps::Off,
+            Some(toml::Value::String(value)) if value.trim() == "responses" => {
+                ReadonlyProps::Responses
+            }
+            Some(_) => {
+                return Err(
+                    "[tool.apx.api] readonly-props must be true, false or \"responses\"".into(),
+                );
+            }
+        };
+        let defaults = HookNaming::default();
+        let hook_naming = HookNaming {
+            prefix: non_empty("hook-prefix")?.map_or(defaults.prefix, str::to_string),
+            suspense_suffix: non_empty("hook-suspense-suffix")?
+                .map_or(defaults.suspense_suffix, str::to_string),
+            mutation_suffix: non_empty("hook-mutation-suffix")?
+                .map_or(defaults.mutation_suffix, str::to_string),
+        };
+        let standard = Banner::Standard {
+            generated_marker: flag("generated-marker")?.unwrap_or(true),
+        };
+        let banner = match table.get("banner") {
+            None | Some(toml::Value::Boolean(true)) => standard,
+            Some(toml::Value::Boolean(false)) => Banner::None,
+            Some(toml::Value::String(text)) if text.trim().is_empty() => standard,
+            Some(toml::Value::String(text)) => Banner::Custom(text.clone()),
+            Some(_) => return Err("[tool.apx.api] banner must be a string or false".into()),
+        };
+        Ok(Self {
+            base_url,
+            bigint_int64: is_enabled("bigint-int64")?,
+            msw_handlers: is_enabled("msw-handlers")?,
+            body_defaults: is_enabled("body-defaults")?,
+            tag_groups: is_enabled("tag-groups")?,
+            union_matchers: is_enabled("union-matchers")?,
+            branded_formats: is_enabled("branded-formats")?,
+            fetch_retry: is_enabled("fetch-retry")?,
+            split_modules: is_enabled("split-modules")?,
+            examples: is_enabled("examples")?,
+            safe_results: is_enabled("safe-results")?,
+            param_guards: is_enabled("param-guards")?,
+            strict_required: is_enabled("strict-required")?,
+            readonly_props,
+            hook_naming,
+            banner,
+        })
+    }
+}
+
+#[cfg(test)]
+// Reason: panicking on failure is idiomatic in tests
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    fn from_config(api: &str) -> Result<GenerateOptions, String> {
+        let pyproject: toml::Value = format!("[tool.apx.api]\n{api}").parse().unwrap();
+        GenerateOptions::from_pyproject(&pyproject)
+    }
+
+    fn options(api: &str) -> GenerateOptions {
+        from_config(api).unwrap()
+    }
+
+    #[test]
+    fn test_missing_section_gives_defaults() {
+        let pyproject: toml::Value = "[tool.apx.metadata]\napp-name = \"demo\"".parse().unwrap();
+        assert_eq!(
+            GenerateOptions::from_pyproject(&pyproject).unwrap(),
+            GenerateOptions::default()
+        );
+        assert_eq!(options(""), GenerateOptions::default());
+    }
+
+    #[test]
+    fn test_base_url_from_config() {
+        assert_eq!(
+            options("base-url = \"https://api.example.com\"\nbase-url-env = \" \"").base_url,
+            BaseUrl::Fixed("https://api.example.com".into())
+        );
+        assert_eq!(
+            options("base-url-env = \"VITE_API_URL\"").base_url,
+            BaseUrl::Env {
+                var: "VITE_API_URL".into(),
+                fallback: String::new(),
+            }
+        );
+        assert_eq!(
+            options("base-url = \"/api\"\nbase-url-env = \"VITE_API_URL\"").base_url,
+            BaseUrl::Env {
+                var: "VITE_API_URL".into(),
+                fallback: "/api".into(),
+            }
+        );
+        assert_eq!(options("base-url-server = true").base_url, BaseUrl::Server);
+        assert_eq!(
+            options("base-url = \"/api\"\nbase-url-server = true").base_url,
+            BaseUrl::Fixed("/api".into())
+        );
+    }
+
+    #[test]
+    fn test_flags_from_config() {
+        assert!(!options("bigint-int64 = false").bigint_int64);
+        assert!(options("bigint-int64 = true").bigint_int64);
+        assert!(options("msw-handlers = true").msw_handlers);
+        assert!(options("body-defaults = true").body_defaults);
+        assert!(options("tag-groups = true").tag_groups);
+        assert!(options("union-matchers = true").union_matchers);
+        assert!(options("branded-formats = true").branded_formats);
+        assert!(options("fetch-retry = true").fetch_retry);
+        assert!(options("split-modules = true").split_modules);
+        assert!(options("examples = true").examples);
+        assert!(options("safe-results = true").safe_results);
+        assert!(options("param-guards = true").param_guards);
+        assert!(options("strict-required = true").strict_required);
+        assert_eq!(
+            options("readonly-props = true").readonly_props,
+            ReadonlyProps::ReadOnly
+        );
+        assert_eq!(
+            options("readonly-props = \"responses\"").readonly_props,
+            ReadonlyProps::Responses
+        );
+        assert_eq!(
+            options("readonly-props = false").readonly_props,
+            ReadonlyProps::Off
+        );
+    }
+
+    #[test]
+    fn test_invalid_config_is_rejected() {
+        let err = from_config("base-url = 1").unwrap_err();
+        assert!(err.contains("base-url must be a string"), "{err}");
+        let err = from_config("fetch-retry = \"1\"").unwrap_err();
+        assert!(err.contains("fetch-retry must be true or false"), "{err}");
+        assert!(from_config("readonly-props = \"all\"").is_err());
+        assert!(from_config("banner = 0").is_err());
+    }
+
+    #[test]
+    fn test_hook_naming_from_config() {
+        assert_eq!(
+            options(
+                "hook-prefix = \"useApi\"\nhook-suspense-suffix = \" \"\nhook-mutation-suffix = \"Mutation\""
+            )
+            .hook_naming,
+            HookNaming {
+                prefix: "useApi".into(),
+                suspense_suffix: "Suspense".into(),
+                mutation_suffix: "Mutation".into(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_hook_naming_validation() {
+        assert!(HookNaming::default().validate().is_ok());
+        let naming = |prefix: &str, suffix: &str| HookNaming {
+            prefix: prefix.into(),
+            suspense_suffix: suffix.into(),
+            ..HookNaming::default()
+        };
+        assert!(naming("useApi", "S").validate().is_ok());
+        assert!(naming("api", "Suspense").validate().is_err());
+        assert!(naming("use-api", "Suspense").validate().is_err());
+        assert!(naming("use", "Suspense!").validate().is_err());
+    }
+
+    #[test]
+    fn test_banner_from_config() {
+        assert_eq!(
+            options("generated-marker = false").banner,
+            Banner::Standard {
+                generated_marker: false
+            }
+        );
+        assert_eq!(options("banner = false").banner, Banner::None);
+        assert_eq!(options("banner = \"\"").banner, Banner::default());
+        assert_eq!(
+            options("banner = \"// Copyright Acme\"").banner,
+            Banner::Custom("// Copyright Acme".into())
+        );
+    }
+}
@@ -9,11 +9,53 @@ use std::collections::HashMap;
 /// Root OpenAPI specification.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OpenApiSpec {
-    /// Map of URL paths to their operations.
+    /// API metadata; its title and version are named in the generated banner.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub info: Option<Info>,
+    /// Map of URL paths to their operations (optional in 3.1 when `webhooks` is set).
+    #[serde(default)]
     pub paths: HashMap<String, PathItem>,
+    /// Inbound callbacks the API sends to its consumers, keyed by webhook name (3.1).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub webhooks: Option<HashMap<String, PathItem>>,
     /// Reusable schema components.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub components: Option<Components>,
+    /// Servers hosting the API; only the first is used for the base URL.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub servers: Option<Vec<Server>>,
+}
+
+/// Metadata about the API.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Info {
+    /// Title of the API.
+    #[serde(default)]
+    pub title: String,
+    /// Version of the API document (not of the OpenAPI specification).
+    #[serde(default)]
+    pub version: String,
+}
+
+/// A server hosting the API, possibly templated with `{variable}`s.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Server {
+    /// Server URL, e.g. `https://{region}.api.example.com`.
+    pub url: String,
+    /// Values substituted into the URL template.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub variables: Option<HashMap<String, ServerVariable>>,
+}
+
+/// A server URL template variable.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServerVariable {
+    /// Value used when none is supplied (required by OpenAPI).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default: Option<String>,
+    /// Allowed values.
+    #[serde(rename = "enum", skip_serializing_if = "Option::is_none")]
+    pub enum_values: Option<Vec<String>>,
 }
 
 /// Components section containing reusable schemas.
@@ -66,6 +108,9 @@ pub struct Operation {
     /// Detailed description.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+    /// Tags grouping the operation (e.g. by resource).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<String>>,
     /// Operation-level parameters.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub parameters: Option<Vec<Parameter>>,
@@ -124,6 +169,18 @@ pub struct MediaType {
     /// Schema for this media type.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub schema: Option<Schema>,
+    /// Per-property serialization of `multipart/form-data` bodies.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub encoding: Option<HashMap<String, Encoding>>,
+}
+
+/// Serialization of one request body property (an entry of [`MediaType::encoding`]).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Encoding {
+    /// Content type of the property's part (e.g. `application/json`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
 }
 
 /// JSON Schema definition used in OpenAPI.
@@ -170,6 +227,10 @@ pub struct Schema {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub additional_properties: Option<AdditionalProperties>,
 
+    /// Value schemas for property names matching a regex (keyed by pattern).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pattern_properties: Option<HashMap<String, Schema>>,
+
     /// Discriminator for polymorphic oneOf schemas.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub discriminator: Option<Discriminator>,
@@ -190,6 +251,18 @@ pub struct Schema {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub example: Option<serde_json::Value>,
 
+    /// Example values for the schema (JSON Schema 2020-12 / OpenAPI 3.1).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub examples: Option<Vec<serde_json::Value>>,
+
+    /// Property is only ever sent by the server (e.g. `id`, `createdAt`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub read_only: Option<bool>,
+
+    /// Property is only ever sent by the client (e.g. `password`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub write_only: Option<bool>,
+
     // --- Validation keywords (parsed but not directly emitted as types) ---
     /// Constant value - schema matches only this exact value.
     #[serde(rename = "const", skip_serializing_if = "Option::is_none")]
@@ -324,20 +397,12 @@ impl Schema {
     }
 
     /// Get the non-null schema from an anyOf that includes null.
+    ///
+    /// Any member shape counts, so `$ref`s and `allOf`-wrapped refs are found
+    /// wherever the `null` member sits.
     pub fn unwrap_nullable(&self) -> Option<&Schema> {
-        if let Some(any_of) = &self.any_of {
-            for schema in any_of {
-                if let Some(SchemaType::Single(t)) = &schema.schema_type
-                    && t != "null"
-                {
-                    return Some(schema);
-                }
-                // If it's a ref or complex schema, return it
-                if schema.ref_path.is_some() || schema.properties.is_some() {
-                    return Some(schema);
-                }
-            }
-        }
-        None
+        self.any_of.as_ref()?.iter().find(
+            |schema| !matches!(&schema.schema_type, Some(SchemaType::Single(t)) if t == "null"),
+        )
     }
 }
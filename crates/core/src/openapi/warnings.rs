@@ -0,0 +1,241 @@
+//! Non-fatal issues found while generating a TypeScript client.
+//!
+//! The generator only understands a subset of OpenAPI/JSON Schema. Rather than
+//! silently dropping what it cannot represent, a separate pass over the raw spec
+//! reports those constructs as [`GenerateWarning`]s alongside the generated code.
+
+use std::fmt;
+
+use serde_json::Value;
+
+/// JSON Schema keywords the generator ignores when building types.
+const UNSUPPORTED_KEYWORDS: &[&str] = &[
+    "not",
+    "if",
+    "then",
+    "else",
+    "prefixItems",
+    "contains",
+    "propertyNames",
+    "dependentSchemas",
+    "dependentRequired",
+    "unevaluatedProperties",
+    "unevaluatedItems",
+    "$dynamicRef",
+];
+
+/// Schema `type` values the generator maps to TypeScript types.
+const KNOWN_TYPES: &[&str] = &[
+    "string", "number", "integer", "boolean", "array", "object", "null",
+];
+
+/// HTTP methods present in OpenAPI path items that produce no client code.
+const IGNORED_METHODS: &[&str] = &["head", "options", "trace"];
+
+/// Category of a [`GenerateWarning`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WarningKind {
+    /// A schema keyword was ignored because the generator does not support it.
+    UnsupportedKeyword,
+    /// A schema could not be mapped and was emitted as `unknown`.
+    UnknownFallback,
+    /// An operation or schema is marked `deprecated`.
+    Deprecated,
+    /// An operation was skipped (e.g. `HEAD`/`OPTIONS`).
+    IgnoredOperation,
+    /// A schema's `required` array names a property it does not declare.
+    ///
+    /// Reported as an error instead when
+    /// [`GenerateOptions::strict_required`](super::GenerateOptions::strict_required) is set.
+    MissingRequiredProperty,
+}
+
+/// A non-fatal issue found while generating the client.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GenerateWarning {
+    /// Category of the issue.
+    pub kind: WarningKind,
+    /// JSON pointer to the offending node (e.g. `/components/schemas/Item`).
+    pub location: String,
+    /// Human-readable description.
+    pub message: String,
+}
+
+impl fmt::Display for GenerateWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.location, self.message)
+    }
+}
+
+/// Collect warnings for constructs in `spec` the generator cannot represent faithfully.
+pub fn collect_warnings(spec: &Value) -> Vec<GenerateWarning> {
+    let mut warnings = Vec::new();
+
+    if let Some(paths) = spec.get("paths").and_then(Value::as_object) {
+        let mut sorted: Vec<_> = paths.iter().collect();
+        sorted.sort_by_key(|(path, _)| *path);
+        for (path, item) in sorted {
+            let pointer = format!("/paths/{}", escape_pointer(path));
+            collect_path_item(item, &pointer, &mut warnings);
+        }
+    }
+
+    if let Some(schemas) = spec
+        .pointer("/components/schemas")
+        .and_then(Value::as_object)
+    {
+        let mut sorted: Vec<_> = schemas.iter().collect();
+        sorted.sort_by_key(|(name, _)| *name);
+        for (name, schema) in sorted {
+            let pointer = format!("/components/schemas/{}", escape_pointer(name));
+            collect_schema(schema, &pointer, &mut warnings);
+        }
+    }
+
+    warnings
+}
+
+fn collect_path_item(item: &Value, pointer: &str, warnings: &mut Vec<GenerateWarning>) {
+    let Some(item) = item.as_object() else {
+        return;
+    };
+
+    for (method, op) in item {
+        let op_pointer = format!("{pointer}/{method}");
+        if IGNORED_METHODS.contains(&method.as_str()) {
+            warnings.push(GenerateWarning {
+                kind: WarningKind::IgnoredOperation,
+                location: op_pointer,
+                message: format!(
+                    "{} operations are not generated",
+                    method.to_ascii_uppercase()
+                ),
+            });
+            continue;
+        }
+        if method == "parameters" {
+            collect_parameters(op, &op_pointer, warnings);
+            continue;
+        }
+        let Some(op) = op.as_object() else {
+            continue;
+        };
+
+        if op.get("deprecated").and_then(Value::as_bool) == Some(true) {
+            let name = op
+                .get("operationId")
+                .and_then(Value::as_str)
+                .unwrap_or(method);
+            warnings.push(GenerateWarning {
+                kind: WarningKind::Deprecated,
+                location: op_pointer.clone(),
+                message: format!("operation '{name}' is deprecated"),
+            });
+        }
+
+        if let Some(params) = op.get("parameters") {
+            collect_parameters(params, &format!("{op_pointer}/parameters"), warnings);
+        }
+        if let Some(body) = op.get("requestBody") {
+            collect_content(body, &format!("{op_pointer}/requestBody"), warnings);
+        }
+        if let Some(responses) = op.get("responses").and_then(Value::as_object) {
+            for (status, response) in responses {
+                collect_content(
+                    response,
+                    &format!("{op_pointer}/responses/{status}"),
+                    warnings,
+                );
+            }
+        }
+    }
+}
+
+fn collect_parameters(params: &Value, pointer: &str, warnings: &mut Vec<GenerateWarning>) {
+    for (index, param) in params.as_array().into_iter().flatten().enumerate() {
+        if let Some(schema) = param.get("schema") {
+            collect_schema(schema, &format!("{pointer}/{index}/schema"), warnings);
+        }
+    }
+}
+
+fn collect_content(node: &Value, pointer: &str, warnings: &mut Vec<GenerateWarning>) {
+    let Some(content) = node.get("content").and_then(Value::as_object) else {
+        return;
+    };
+    for (media_type, media) in content {
+        if let Some(schema) = media.get("schema") {
+            let pointer = format!("{pointer}/content/{}/schema", escape_pointer(media_type));
+            collect_schema(schema, &pointer, warnings);
+        }
+    }
+}
+
+fn collect_schema(schema: &Value, pointer: &str, warnings: &mut Vec<GenerateWarning>) {
+    let Some(obj) = schema.as_object() else {
+        return;
+    };
+
+    for keyword in UNSUPPORTED_KEYWORDS {
+        if obj.contains_key(*keyword) {
+            warnings.push(GenerateWarning {
+                kind: WarningKind::UnsupportedKeyword,
+                location: pointer.to_string(),
+                message: format!("unsupported keyword '{keyword}' is ignored"),
+            });
+        }
+    }
+
+    let types: Vec<&str> = match obj.get("type") {
+        Some(Value::String(ty)) => vec![ty.as_str()],
+        Some(Value::Array(tys)) => tys.iter().filter_map(Value::as_str).collect(),
+        _ => Vec::new(),
+    };
+    for ty in types {
+        if !KNOWN_TYPES.contains(&ty) {
+            warnings.push(GenerateWarning {
+                kind: WarningKind::UnknownFallback,
+                location: pointer.to_string(),
+                message: format!("unrecognized type '{ty}' generated as unknown"),
+            });
+        }
+    }
+
+    if obj.get("deprecated").and_then(Value::as_bool) == Some(true) {
+        warnings.push(GenerateWarning {
+            kind: WarningKind::Deprecated,
+            location: pointer.to_string(),
+            message: "schema is deprecated".to_string(),
+        });
+    }
+
+    if let Some(properties) = obj.get("properties").and_then(Value::as_object) {
+        let mut sorted: Vec<_> = properties.iter().collect();
+        sorted.sort_by_key(|(name, _)| *name);
+        for (name, property) in sorted {
+            let pointer = format!("{pointer}/properties/{}", escape_pointer(name));
+            collect_schema(property, &pointer, warnings);
+        }
+    }
+    for key in ["items", "additionalProperties"] {
+        if let Some(child) = obj.get(key) {
+            collect_schema(child, &format!("{pointer}/{key}"), warnings);
+        }
+    }
+    for key in ["anyOf", "oneOf", "allOf"] {
+        for (index, child) in obj
+            .get(key)
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .enumerate()
+        {
+            collect_schema(child, &format!("{pointer}/{key}/{index}"), warnings);
+        }
+    }
+}
+
+/// Escape a JSON pointer segment (RFC 6901).
+pub(crate) fn escape_pointer(segment: &str) -> String {
+    segment.replace('~', "~0").replace('/', "~1")
+}
@@ -1,13 +1,26 @@
 use std::path::Path;
 
+use crate::api_generator::generate_openapi_client;
 use crate::common::{OutputMode, emit, ensure_entrypoint_deps, run_preflight_checks, spinner};
-use crate::external::bun::Bun;
+use crate::components::add::typecheck_files;
+use crate::dotenv::DotenvFile;
+use crate::external::ExternalTool;
+use crate::external::bun::{BUN_VERSION, Bun};
 use crate::external::uv::UvTool;
 use crate::frontend::prepare_frontend_args;
 use tracing::debug;
 
 /// Run type checking (tsc + ty) in parallel for the given app directory.
-pub async fn run_check(app_dir: &Path, mode: OutputMode) -> Result<(), String> {
+///
+/// When the app has an `.env.example`, its keys are checked against `.env` first.
+/// With `client` set, the API client is regenerated from the app's OpenAPI spec
+/// and type-checked on its own before the project-wide checks (UI projects only).
+pub async fn run_check(app_dir: &Path, mode: OutputMode, client: bool) -> Result<(), String> {
+    let mut errors = Vec::new();
+    if let Err(err) = check_env_file(app_dir, mode) {
+        errors.push(err);
+    }
+
     // Run preflight checks (installs deps if needed)
     let preflight = run_preflight_checks(app_dir).await?;
     let has_ui = preflight.has_ui;
@@ -17,6 +30,19 @@ pub async fn run_check(app_dir: &Path, mode: OutputMode) -> Result<(), String> {
         generate_route_tree(app_dir, mode).await?;
     }
 
+    if client {
+        if has_ui {
+            if let Err(err) = check_client(app_dir, mode).await {
+                errors.push(err);
+            }
+        } else {
+            emit(
+                mode,
+                "⚠️  [client] Skipped: the app has no UI to generate a client for",
+            );
+        }
+    }
+
     // Spinner for the parallel type-check phase (CLI only)
     let check_spinner = if mode == OutputMode::Interactive {
         let sp = spinner("Running type checks...");
@@ -29,6 +55,14 @@ pub async fn run_check(app_dir: &Path, mode: OutputMode) -> Result<(), String> {
     // Run tsc -b --incremental in one tokio thread — only for UI projects
     let tsc_task = if has_ui {
         let bun = Bun::new().await?;
+        emit(
+            mode,
+            &format!(
+                "🍞 [bun] v{} ({}, pinned v{BUN_VERSION})",
+                bun.version().await,
+                bun.source().source_label()
+            ),
+        );
         let app_dir_clone = app_dir.to_path_buf();
         Some(tokio::spawn(async move {
             debug!("Running tsc -b --incremental.");
@@ -83,8 +117,6 @@ pub async fn run_check(app_dir: &Path, mode: OutputMode) -> Result<(), String> {
         sp.finish_and_clear();
     }
 
-    let mut errors = Vec::new();
-
     if let Some(tsc_result) = tsc_result {
         let tsc_result = tsc_result?;
         if tsc_result.0 {
@@ -152,6 +184,89 @@ pub async fn run_check(app_dir: &Path, mode: OutputMode) -> Result<(), String> {
     Ok(())
 }
 
+/// Check that `.env` defines every key declared in `.env.example` with a value.
+///
+/// Missing or empty keys are errors; keys not declared in `.env.example` are only
+/// reported as warnings. Apps without an `.env.example` are not checked.
+fn check_env_file(app_dir: &Path, mode: OutputMode) -> Result<(), String> {
+    let example_path = app_dir.join(".env.example");
+    if !example_path.exists() {
+        debug!("No .env.example found, skipping env check.");
+        return Ok(());
+    }
+    let example = DotenvFile::read(&example_path)?;
+    let report = DotenvFile::read(&app_dir.join(".env"))?.check_keys(&example);
+
+    if !report.unexpected.is_empty() {
+        emit(
+            mode,
+            &format!(
+                "⚠️  [env] Keys not declared in .env.example: {}",
+                report.unexpected.join(", ")
+            ),
+        );
+    }
+    if report.is_satisfied() {
+        emit(mode, "✅ [env] All keys from .env.example are set");
+        return Ok(());
+    }
+
+    let mut problems = Vec::new();
+    if !report.missing.is_empty() {
+        problems.push(format!("missing {}", report.missing.join(", ")));
+    }
+    if !report.empty.is_empty() {
+        problems.push(format!("empty {}", report.empty.join(", ")));
+    }
+    let message = format!(
+        "[env] Required keys from .env.example are not set in .env: {}",
+        problems.join("; ")
+    );
+    emit(mode, &format!("❌ {message}"));
+    Err(message)
+}
+
+/// Regenerate the API client and type-check the generated files with `tsc --noEmit`.
+///
+/// Catches backend schema changes that produce a client which doesn't compile.
+async fn check_client(app_dir: &Path, mode: OutputMode) -> Result<(), String> {
+    let client_spinner = if mode == OutputMode::Interactive {
+        Some(spinner("Checking generated API client..."))
+    } else {
+        eprintln!("Checking generated API client...");
+        None
+    };
+
+    let result = match generate_openapi_client(app_dir).await {
+        Ok(client) => {
+            debug!(files = ?client.files, "Type-checking generated API client.");
+            typecheck_files(app_dir, &client.files, "the generated API client")
+                .await
+                .map(|()| client.warnings)
+        }
+        Err(err) => Err(format!("Failed to generate the API client: {err}")),
+    };
+
+    if let Some(sp) = client_spinner {
+        sp.finish_and_clear();
+    }
+
+    match result {
+        Ok(warnings) => {
+            for warning in &warnings {
+                emit(mode, &format!("⚠️  [client] {warning}"));
+            }
+            emit(mode, "✅ [client] Generated API client type-checks");
+            Ok(())
+        }
+        Err(err) => {
+            emit(mode, "❌ [client] Generated API client check failed");
+            emit(mode, &err);
+            Err(format!("[client] {err}"))
+        }
+    }
+}
+
 async fn generate_route_tree(app_dir: &Path, mode: OutputMode) -> Result<(), String> {
     let route_spinner = if mode == OutputMode::Interactive {
         Some(spinner("Generating route tree..."))
@@ -8,12 +8,14 @@ use crate::common::{
     OutputMode, emit, ensure_dir, format_elapsed_ms, read_project_metadata, run_preflight_checks,
     spinner_for_mode,
 };
-use crate::dev::client::{HealthCheckConfig, health, stop as stop_server};
+use crate::dev::client::{HealthCheckConfig, health, restart_service, stop as stop_server};
 use crate::dev::common::{
     BACKEND_PORT_END, BACKEND_PORT_START, DB_PORT_END, DB_PORT_START, DevLock, FRONTEND_PORT_END,
-    FRONTEND_PORT_START, find_random_port_in_range, is_process_running, lock_path, read_lock,
-    remove_lock, write_lock,
+    FRONTEND_PORT_START, PinnedPorts, is_process_running, lock_path, pinned_or_random_port,
+    read_dev_state, read_lock, read_pinned_ports, remove_lock, write_dev_state, write_lock,
+    write_pinned_ports,
 };
+use crate::dev::process::DevService;
 use crate::dev::server::{ServerConfig, run_server};
 use crate::dev::token;
 use crate::external::uv::ApxTool;
@@ -163,6 +165,42 @@ pub struct PreparedServer {
     pub command_display: String,
 }
 
+/// Merge `overrides` into the app's pinned subprocess ports and persist them.
+/// Fails without persisting anything if a requested port is already in use.
+pub fn pin_ports(app_dir: &Path, overrides: PinnedPorts) -> Result<PinnedPorts, String> {
+    let current = read_pinned_ports(app_dir)?;
+    if overrides.is_empty() {
+        return Ok(current);
+    }
+    overrides.ensure_available(BIND_HOST, None)?;
+    let pinned = current.merge(overrides);
+    if pinned != current {
+        write_pinned_ports(app_dir, &pinned)?;
+    }
+    Ok(pinned)
+}
+
+/// Replace the app's persisted dev token with a fresh one, used from the next start on.
+pub fn rotate_dev_token(app_dir: &Path) -> Result<(), String> {
+    let mut state = read_dev_state(app_dir)?;
+    state.token = Some(token::generate());
+    write_dev_state(app_dir, &state)
+}
+
+/// Return the app's persisted dev token, generating and saving one if there is none.
+///
+/// Reusing the token keeps tooling and browser sessions valid across restarts.
+fn persisted_dev_token(app_dir: &Path) -> Result<String, String> {
+    let mut state = read_dev_state(app_dir)?;
+    if let Some(token) = state.token.as_deref().filter(|t| token::is_well_formed(t)) {
+        return Ok(token.to_string());
+    }
+    let token = token::generate();
+    state.token = Some(token.clone());
+    write_dev_state(app_dir, &state)?;
+    Ok(token)
+}
+
 /// Run preflight checks, start flux, allocate a stable port.
 /// Returns a `PreparedServer` ready for any launch mode.
 pub async fn prepare_server_launch(
@@ -175,7 +213,7 @@ pub async fn prepare_server_launch(
 
     emit(mode, "🚀 Starting dev server...");
 
-    if let Err(e) = flux::ensure_running() {
+    if let Err(e) = flux::ensure_running_async().await {
         debug!("Failed to start flux: {e}. Logs may not be collected.");
     }
 
@@ -189,8 +227,9 @@ pub async fn prepare_server_launch(
     registry.save()?;
 
     wait_for_port_available(port, mode).await?;
+    read_pinned_ports(app_dir)?.ensure_available(BIND_HOST, Some(port))?;
 
-    let dev_token = token::generate();
+    let dev_token = persisted_dev_token(app_dir)?;
     let canonical_app_dir = app_dir
         .canonicalize()
         .unwrap_or_else(|_| app_dir.to_path_buf());
@@ -532,7 +571,7 @@ async fn validate_credentials(app_dir: &Path) {
     }
 }
 
-/// Bind listener and pick random subprocess ports for one attempt.
+/// Bind listener and pick pinned or random subprocess ports for one attempt.
 fn build_attached_server_config(
     app_dir: &Path,
     server: &PreparedServer,
@@ -546,13 +585,20 @@ fn build_attached_server_config(
     let listener = tokio::net::TcpListener::from_std(std_listener)
         .map_err(|e| format!("Failed to convert to tokio listener: {e}"))?;
 
-    let backend_port = find_random_port_in_range(BIND_HOST, BACKEND_PORT_START, BACKEND_PORT_END)?;
-    let db_port = find_random_port_in_range(BIND_HOST, DB_PORT_START, DB_PORT_END)?;
+    let pinned = read_pinned_ports(app_dir)?;
+    let backend_port = pinned_or_random_port(
+        BIND_HOST,
+        pinned.backend,
+        BACKEND_PORT_START,
+        BACKEND_PORT_END,
+    )?;
+    let db_port = pinned_or_random_port(BIND_HOST, pinned.db, DB_PORT_START, DB_PORT_END)?;
 
     let metadata = read_project_metadata(app_dir)?;
     let frontend_port = if metadata.has_ui() {
-        Some(find_random_port_in_range(
+        Some(pinned_or_random_port(
             BIND_HOST,
+            pinned.frontend,
             FRONTEND_PORT_START,
             FRONTEND_PORT_END,
         )?)
@@ -672,33 +718,397 @@ pub async fn stop_dev_server(app_dir: &Path, mode: OutputMode) -> Result<bool, S
     }
 }
 
+/// How thoroughly `apx dev restart` resets the development environment.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RestartMode {
+    /// Restart only the dev server and the processes it owns.
+    #[default]
+    Soft,
+    /// Also restart flux and make sure no embedded database survives.
+    Hard {
+        /// Delete this app's stored logs while flux is down.
+        clear_logs: bool,
+    },
+}
+
+/// A single step performed by [`restart_dev_server`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RestartStep {
+    /// Stop the running dev server and the processes it owns.
+    StopDevServer,
+    /// Kill embedded database processes orphaned by a previous server.
+    StopEmbeddedDb,
+    /// Stop the flux log collector daemon.
+    StopFlux,
+    /// Delete this app's logs from flux storage.
+    ClearLogs,
+    /// Start the flux log collector daemon.
+    StartFlux,
+    /// Spawn a new dev server (with a fresh in-memory database).
+    StartDevServer,
+}
+
+impl RestartMode {
+    /// The ordered steps this mode performs.
+    pub fn steps(self) -> Vec<RestartStep> {
+        match self {
+            Self::Soft => vec![RestartStep::StopDevServer, RestartStep::StartDevServer],
+            Self::Hard { clear_logs } => {
+                let mut steps = vec![
+                    RestartStep::StopDevServer,
+                    RestartStep::StopEmbeddedDb,
+                    RestartStep::StopFlux,
+                ];
+                if clear_logs {
+                    steps.push(RestartStep::ClearLogs);
+                }
+                steps.extend([RestartStep::StartFlux, RestartStep::StartDevServer]);
+                steps
+            }
+        }
+    }
+}
+
 /// Restart the dev server for the given app directory.
 /// Preserves the port if an existing server is found.
+///
+/// With [`RestartMode::Hard`], flux is restarted as well and any orphaned
+/// embedded database for this app is killed before the new server starts.
 pub async fn restart_dev_server(
     app_dir: &Path,
+    restart_mode: RestartMode,
     skip_healthcheck: bool,
     mode: OutputMode,
 ) -> Result<u16, String> {
-    let lock_path = lock_path(app_dir);
-    let preferred_port = if lock_path.exists() {
+    let mut actions = LiveRestart {
+        app_dir,
+        skip_healthcheck,
+        mode,
+    };
+    run_restart(&mut actions, app_dir, restart_mode, mode).await
+}
+
+/// The side effects of each [`RestartStep`], separated from the executor so
+/// tests can run it without touching real processes or the logs database.
+trait RestartActions: Send {
+    /// Stop the running dev server, returning the port it listened on.
+    fn stop_dev_server(&mut self) -> impl Future<Output = Result<Option<u16>, String>> + Send;
+
+    /// Kill orphaned embedded database processes, returning how many were killed.
+    fn kill_orphaned_dbs(&mut self) -> usize;
+
+    /// Stop the flux daemon.
+    fn stop_flux(&mut self) -> impl Future<Output = Result<(), String>> + Send;
+
+    /// Delete the logs recorded for `app_path`, returning how many were deleted.
+    fn clear_logs(&mut self, app_path: &str) -> impl Future<Output = Result<usize, String>> + Send;
+
+    /// Start the flux daemon.
+    fn start_flux(&mut self) -> impl Future<Output = Result<(), String>> + Send;
+
+    /// Spawn a new dev server, returning its port.
+    fn start_dev_server(
+        &mut self,
+        preferred_port: Option<u16>,
+    ) -> impl Future<Output = Result<u16, String>> + Send;
+}
+
+/// [`RestartActions`] acting on the real dev server, flux and logs database.
+#[derive(Debug)]
+struct LiveRestart<'a> {
+    app_dir: &'a Path,
+    skip_healthcheck: bool,
+    mode: OutputMode,
+}
+
+impl RestartActions for LiveRestart<'_> {
+    async fn stop_dev_server(&mut self) -> Result<Option<u16>, String> {
+        let lock_path = lock_path(self.app_dir);
+        if !lock_path.exists() {
+            return Ok(None);
+        }
         let lock = read_lock(&lock_path)?;
         emit(
-            mode,
+            self.mode,
             &format!(
                 "Found existing dev server at http://{BROWSER_HOST}:{port}",
                 port = lock.port
             ),
         );
-        stop_dev_server(app_dir, mode).await?;
-        Some(lock.port)
-    } else {
-        None
-    };
+        stop_dev_server(self.app_dir, self.mode).await?;
+        Ok(Some(lock.port))
+    }
+
+    fn kill_orphaned_dbs(&mut self) -> usize {
+        crate::dev::embedded_db::EmbeddedDb::kill_orphans(self.app_dir)
+    }
+
+    async fn stop_flux(&mut self) -> Result<(), String> {
+        flux::stop_async().await
+    }
+
+    async fn clear_logs(&mut self, app_path: &str) -> Result<usize, String> {
+        apx_db::LogsDb::open()
+            .await?
+            .delete_logs_for_app(app_path)
+            .await
+    }
+
+    async fn start_flux(&mut self) -> Result<(), String> {
+        flux::start_async().await
+    }
+
+    async fn start_dev_server(&mut self, preferred_port: Option<u16>) -> Result<u16, String> {
+        spawn_server(
+            self.app_dir,
+            preferred_port,
+            false,
+            60,
+            self.skip_healthcheck,
+            self.mode,
+        )
+        .await
+    }
+}
+
+/// Run the steps of `restart_mode` in order through `actions`.
+async fn run_restart<A: RestartActions>(
+    actions: &mut A,
+    app_dir: &Path,
+    restart_mode: RestartMode,
+    mode: OutputMode,
+) -> Result<u16, String> {
+    let mut preferred_port = None;
+    let mut port = None;
 
-    let port = spawn_server(app_dir, preferred_port, false, 60, skip_healthcheck, mode).await?;
+    for step in restart_mode.steps() {
+        match step {
+            RestartStep::StopDevServer => {
+                preferred_port = actions.stop_dev_server().await?;
+            }
+            RestartStep::StopEmbeddedDb => {
+                let killed = actions.kill_orphaned_dbs();
+                if killed > 0 {
+                    emit(
+                        mode,
+                        &format!("🧹 Killed {killed} orphaned embedded database process(es)"),
+                    );
+                }
+            }
+            RestartStep::StopFlux => {
+                emit(mode, "Stopping flux...");
+                actions.stop_flux().await?;
+            }
+            RestartStep::ClearLogs => {
+                let canonical = app_dir
+                    .canonicalize()
+                    .unwrap_or_else(|_| app_dir.to_path_buf());
+                let deleted = actions.clear_logs(&canonical.display().to_string()).await?;
+                emit(mode, &format!("🗑️  Cleared {deleted} stored log records"));
+            }
+            RestartStep::StartFlux => {
+                emit(mode, "Starting flux...");
+                actions.start_flux().await?;
+            }
+            RestartStep::StartDevServer => {
+                port = Some(actions.start_dev_server(preferred_port).await?);
+            }
+        }
+    }
+
+    let port = port.ok_or("Restart plan did not start a dev server")?;
     emit(
         mode,
         &format!("✅ Dev server restarted at http://{BROWSER_HOST}:{port}\n"),
     );
     Ok(port)
 }
+
+/// Restart one process of the running dev server, leaving the others untouched.
+///
+/// Unlike [`restart_dev_server`], the dev server itself keeps running on its port.
+pub async fn restart_dev_service(
+    app_dir: &Path,
+    service: DevService,
+    skip_healthcheck: bool,
+    mode: OutputMode,
+) -> Result<u16, String> {
+    let lock_path = lock_path(app_dir);
+    if !lock_path.exists() {
+        return Err("No dev server running. Start it with `apx dev start`.".to_string());
+    }
+    let lock = read_lock(&lock_path)?;
+
+    if service == DevService::Db {
+        emit(
+            mode,
+            "⚠️  The embedded database is in-memory: all of its data will be lost",
+        );
+    }
+
+    let start_time = Instant::now();
+    let spinner = spinner_for_mode(&format!("Restarting {}...", service.as_str()), mode);
+    let result = restart_service(lock.port, lock.token.as_deref(), service).await;
+    spinner.finish_and_clear();
+    result?;
+
+    if !skip_healthcheck {
+        let config = HealthCheckConfig {
+            initial_delay_ms: 0,
+            ..HealthCheckConfig::default()
+        };
+        wait_for_healthy_with_logs(lock.port, &config, app_dir, mode).await?;
+    }
+
+    emit(
+        mode,
+        &format!(
+            "✅ Restarted {} in {}\n",
+            service.as_str(),
+            format_elapsed_ms(start_time)
+        ),
+    );
+    Ok(lock.port)
+}
+
+#[cfg(test)]
+// Reason: panicking on failure is idiomatic in tests
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::dev::common::DevState;
+
+    #[test]
+    fn test_dev_token_persists_until_rotated() {
+        let dir = tempfile::tempdir().unwrap();
+        let first = persisted_dev_token(dir.path()).unwrap();
+        assert!(token::is_well_formed(&first));
+        assert_eq!(persisted_dev_token(dir.path()).unwrap(), first);
+
+        rotate_dev_token(dir.path()).unwrap();
+        let rotated = persisted_dev_token(dir.path()).unwrap();
+        assert_ne!(rotated, first);
+        assert_eq!(persisted_dev_token(dir.path()).unwrap(), rotated);
+
+        // A hand-edited or truncated token is replaced
+        write_dev_state(
+            dir.path(),
+            &DevState {
+                token: Some("short".to_string()),
+            },
+        )
+        .unwrap();
+        let replaced = persisted_dev_token(dir.path()).unwrap();
+        assert!(token::is_well_formed(&replaced));
+        assert_eq!(read_dev_state(dir.path()).unwrap().token, Some(replaced));
+    }
+
+    #[test]
+    fn test_soft_restart_leaves_db_and_flux_alone() {
+        let steps = RestartMode::Soft.steps();
+        assert_eq!(
+            steps,
+            vec![RestartStep::StopDevServer, RestartStep::StartDevServer]
+        );
+    }
+
+    #[test]
+    fn test_hard_restart_restarts_db_and_flux() {
+        let steps = RestartMode::Hard { clear_logs: false }.steps();
+        assert!(steps.contains(&RestartStep::StopEmbeddedDb));
+        assert!(steps.contains(&RestartStep::StopFlux));
+        assert!(steps.contains(&RestartStep::StartFlux));
+        assert!(!steps.contains(&RestartStep::ClearLogs));
+        assert_eq!(steps.last(), Some(&RestartStep::StartDevServer));
+    }
+
+    #[test]
+    fn test_hard_restart_clears_logs_only_while_flux_is_down() {
+        let steps = RestartMode::Hard { clear_logs: true }.steps();
+        let position = |step| steps.iter().position(|s| *s == step).unwrap();
+        let clear = position(RestartStep::ClearLogs);
+        assert!(position(RestartStep::StopFlux) < clear);
+        assert!(clear < position(RestartStep::StartFlux));
+    }
+
+    /// Records the [`RestartActions`] calls instead of performing them.
+    #[derive(Debug, Default)]
+    struct RecordingActions {
+        running_port: Option<u16>,
+        calls: Vec<String>,
+    }
+
+    impl RestartActions for RecordingActions {
+        async fn stop_dev_server(&mut self) -> Result<Option<u16>, String> {
+            self.calls.push("stop dev server".to_string());
+            Ok(self.running_port)
+        }
+
+        fn kill_orphaned_dbs(&mut self) -> usize {
+            self.calls.push("kill orphaned dbs".to_string());
+            1
+        }
+
+        async fn stop_flux(&mut self) -> Result<(), String> {
+            self.calls.push("stop flux".to_string());
+            Ok(())
+        }
+
+        async fn clear_logs(&mut self, app_path: &str) -> Result<usize, String> {
+            self.calls.push(format!("clear logs {app_path}"));
+            Ok(3)
+        }
+
+        async fn start_flux(&mut self) -> Result<(), String> {
+            self.calls.push("start flux".to_string());
+            Ok(())
+        }
+
+        async fn start_dev_server(&mut self, preferred_port: Option<u16>) -> Result<u16, String> {
+            self.calls
+                .push(format!("start dev server {preferred_port:?}"));
+            Ok(preferred_port.unwrap_or(9000))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_restart_executor_runs_the_actions() {
+        let dir = tempfile::tempdir().unwrap();
+        let canonical = dir.path().canonicalize().unwrap();
+
+        let mut soft = RecordingActions {
+            running_port: Some(9001),
+            ..RecordingActions::default()
+        };
+        let port = run_restart(&mut soft, dir.path(), RestartMode::Soft, OutputMode::Quiet)
+            .await
+            .unwrap();
+        assert_eq!(port, 9001);
+        assert_eq!(
+            soft.calls,
+            ["stop dev server", "start dev server Some(9001)"]
+        );
+
+        let mut hard = RecordingActions::default();
+        let port = run_restart(
+            &mut hard,
+            dir.path(),
+            RestartMode::Hard { clear_logs: true },
+            OutputMode::Quiet,
+        )
+        .await
+        .unwrap();
+        assert_eq!(port, 9000);
+        assert_eq!(
+            hard.calls,
+            [
+                "stop dev server".to_string(),
+                "kill orphaned dbs".to_string(),
+                "stop flux".to_string(),
+                format!("clear logs {}", canonical.display()),
+                "start flux".to_string(),
+                "start dev server None".to_string(),
+            ]
+        );
+    }
+}
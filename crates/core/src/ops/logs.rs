@@ -35,7 +35,7 @@ async fn query_filtered_logs(app_dir: &Path, duration: &str) -> Result<Vec<LogRe
     let since_ns = since_timestamp_nanos(duration);
 
     let records = storage
-        .query_logs(Some(&app_path_canonical), since_ns, None)
+        .query_logs(&app_path_canonical, since_ns, None)
         .await?;
     Ok(records
         .into_iter()
@@ -130,7 +130,7 @@ fn log_record_to_entry(record: &LogRecord) -> LogEntry {
     LogEntry {
         timestamp: format_timestamp(record.effective_timestamp_ms()),
         source: record.source_label().to_string(),
-        severity: record.severity_text.clone(),
+        severity: Some(record.severity_label().to_string()),
         message: record.body.as_deref().unwrap_or("").to_string(),
     }
 }
@@ -182,6 +182,24 @@ pub fn parse_duration(input: &str) -> Result<Duration, String> {
     Ok(Duration::from_secs(seconds))
 }
 
+/// Parse a time bound given either as a duration ago (e.g. `"5m"`) or as an
+/// RFC 3339 timestamp (e.g. `"2026-01-01T12:00:00Z"`), in nanoseconds since the epoch.
+pub fn parse_time_bound(input: &str) -> Result<i64, String> {
+    let trimmed = input.trim();
+    if let Ok(ts) = chrono::DateTime::parse_from_rfc3339(trimmed) {
+        return ts
+            .timestamp_nanos_opt()
+            .ok_or_else(|| format!("Timestamp out of range: {input}"));
+    }
+    parse_duration(trimmed)
+        .map(since_timestamp_nanos)
+        .map_err(|_| {
+            format!(
+                "Invalid time '{input}'. Use a duration (e.g. 30s, 5m, 1h) or an RFC 3339 timestamp (e.g. 2026-01-01T12:00:00Z)."
+            )
+        })
+}
+
 /// Compute a nanosecond-precision UNIX timestamp for `now - duration`.
 pub fn since_timestamp_nanos(duration: Duration) -> i64 {
     let now_ms = Utc::now().timestamp_millis() as u64;
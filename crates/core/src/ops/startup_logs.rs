@@ -60,7 +60,7 @@ impl StartupLogStreamer {
 
         // Query logs since last ID
         let Ok(records) = storage
-            .query_logs_after_id(Some(&self.app_path), self.last_log_id)
+            .query_logs_after_id(&self.app_path, self.last_log_id)
             .await
         else {
             return 0;
@@ -5,9 +5,9 @@ use sqlx::sqlite::SqlitePool;
 use std::collections::HashSet;
 
 use crate::components::cache::get_all_registry_indexes;
-use apx_db::fts::{Fts5Column, Fts5Table, sanitize_fts5_query};
+use apx_db::fts::{Fts5Column, Fts5Filter, Fts5Table, sanitize_fts5_query};
 
-const TABLE_NAME: &str = "components_fts_v1";
+const TABLE_NAME: &str = "components_fts_v2"; // v2: item_type + categories columns
 
 /// Component record for FTS storage
 #[derive(Debug, Clone)]
@@ -20,6 +20,42 @@ pub struct ComponentRecord {
     pub registry: String,
     /// Full searchable text (name + description)
     pub text: String,
+    /// Item type, e.g. "registry:ui" (empty when the registry index omits it)
+    pub item_type: String,
+    /// Space-separated registry categories
+    pub categories: String,
+}
+
+/// Restricts [`ComponentIndex::search`] results; unset fields match everything.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ComponentFilter {
+    /// Registry name, with or without `@` (`"ui"` or `""` for the default shadcn/ui registry).
+    pub registry: Option<String>,
+    /// Item type, with or without the `registry:` prefix (e.g. `"hook"`, `"registry:ui"`).
+    pub item_type: Option<String>,
+    /// Registry category.
+    pub category: Option<String>,
+}
+
+impl ComponentFilter {
+    /// Registry name as stored in the index (no `@`, empty for the default registry).
+    fn registry_value(&self) -> Option<&str> {
+        self.registry.as_deref().map(|registry| {
+            let name = registry.trim_start_matches('@');
+            if name == "ui" { "" } else { name }
+        })
+    }
+
+    /// Item type as stored in the index (always `registry:`-prefixed).
+    fn item_type_value(&self) -> Option<String> {
+        self.item_type.as_deref().map(|ty| {
+            if ty.starts_with("registry:") {
+                ty.to_string()
+            } else {
+                format!("registry:{ty}")
+            }
+        })
+    }
 }
 
 /// Search result with component details
@@ -54,6 +90,14 @@ fn component_fts_columns() -> Vec<Fts5Column> {
             name: "text",
             indexed: true,
         },
+        Fts5Column {
+            name: "item_type",
+            indexed: false,
+        },
+        Fts5Column {
+            name: "categories",
+            indexed: false,
+        },
     ]
 }
 
@@ -119,6 +163,8 @@ impl ComponentIndex {
                     name: item.name,
                     registry,
                     text,
+                    item_type: item.item_type.unwrap_or_default(),
+                    categories: item.categories.join(" "),
                 });
             }
         }
@@ -134,7 +180,14 @@ impl ComponentIndex {
             self.fts
                 .insert_str(
                     &mut tx,
-                    &[&record.id, &record.name, &record.registry, &record.text],
+                    &[
+                        &record.id,
+                        &record.name,
+                        &record.registry,
+                        &record.text,
+                        &record.item_type,
+                        &record.categories,
+                    ],
                 )
                 .await?;
         }
@@ -150,11 +203,13 @@ impl ComponentIndex {
     /// Search for components using FTS5.
     /// When `configured_registries` is provided, components from those registries
     /// receive a scoring boost to improve discoverability of project-relevant components.
+    /// When `filter` is provided, only components matching all of its fields are returned.
     pub async fn search(
         &self,
         query: &str,
         limit: usize,
         configured_registries: Option<&HashSet<String>>,
+        filter: Option<&ComponentFilter>,
     ) -> Result<Vec<SearchResult>, String> {
         tracing::debug!("search: Starting search for query '{}'", query);
 
@@ -173,9 +228,38 @@ impl ComponentIndex {
         // Fetch more results for reranking
         let fts_limit = (limit * 3).max(30);
 
+        let registry = filter.and_then(ComponentFilter::registry_value);
+        let item_type = filter.and_then(ComponentFilter::item_type_value);
+        let category = filter.and_then(|f| f.category.as_deref());
+
+        let mut predicates = Vec::new();
+        if let Some(value) = registry {
+            predicates.push(Fts5Filter::Eq {
+                column: "registry",
+                value,
+            });
+        }
+        if let Some(value) = item_type.as_deref() {
+            predicates.push(Fts5Filter::Eq {
+                column: "item_type",
+                value,
+            });
+        }
+        if let Some(value) = category {
+            predicates.push(Fts5Filter::HasToken {
+                column: "categories",
+                value,
+            });
+        }
+
         let rows = self
             .fts
-            .search(&sanitized, fts_limit, &["id", "name", "registry", "rank"])
+            .search_filtered(
+                &sanitized,
+                &predicates,
+                fts_limit,
+                &["id", "name", "registry", "rank"],
+            )
             .await?;
 
         let mut results = Vec::new();
@@ -256,12 +340,24 @@ mod tests {
 
     /// Helper: create the FTS table and insert test data via the Fts5Table API.
     async fn seed_test_data(fts: &Fts5Table, rows: &[(&str, &str, &str, &str)]) {
+        let rows: Vec<_> = rows
+            .iter()
+            .map(|(id, name, registry, text)| (*id, *name, *registry, *text, "registry:ui", ""))
+            .collect();
+        seed_typed_test_data(fts, &rows).await;
+    }
+
+    /// Helper: like [`seed_test_data`] with explicit item type and categories.
+    async fn seed_typed_test_data(fts: &Fts5Table, rows: &[(&str, &str, &str, &str, &str, &str)]) {
         fts.create_or_replace().await.unwrap();
         let mut tx = fts.begin().await.unwrap();
-        for (id, name, registry, text) in rows {
-            fts.insert_str(&mut tx, &[*id, *name, *registry, *text])
-                .await
-                .unwrap();
+        for (id, name, registry, text, item_type, categories) in rows {
+            fts.insert_str(
+                &mut tx,
+                &[*id, *name, *registry, *text, *item_type, *categories],
+            )
+            .await
+            .unwrap();
         }
         tx.commit().await.map_err(|e| format!("{e}")).unwrap();
     }
@@ -275,7 +371,7 @@ mod tests {
     #[tokio::test]
     async fn test_search_no_index() {
         let index = test_index().await;
-        let result = index.search("button", 10, None).await;
+        let result = index.search("button", 10, None, None).await;
         assert!(result.is_err());
     }
 
@@ -303,7 +399,7 @@ mod tests {
         )
         .await;
 
-        let results = index.search("button", 10, None).await.unwrap();
+        let results = index.search("button", 10, None, None).await.unwrap();
         assert!(!results.is_empty());
 
         // Both buttons should be in results, card should not
@@ -347,7 +443,7 @@ mod tests {
         .await;
 
         // Single-term query should find the component
-        let results = index.search("animate", 10, None).await.unwrap();
+        let results = index.search("animate", 10, None, None).await.unwrap();
         assert!(
             results.iter().any(|r| r.id == "@animate-ui/number-ticker"),
             "Single-term 'animate' should match. Got: {results:?}"
@@ -356,7 +452,7 @@ mod tests {
         // Multi-term query with a term NOT in the document ("ticker") should
         // still return partial matches, not empty.
         let results = index
-            .search("@animate-ui number counter ticker", 10, None)
+            .search("@animate-ui number counter ticker", 10, None, None)
             .await
             .unwrap();
         assert!(
@@ -393,14 +489,14 @@ mod tests {
         .await;
 
         // Without configured registries: default gets boosted
-        let results = index.search("message", 10, None).await.unwrap();
+        let results = index.search("message", 10, None, None).await.unwrap();
         let ai_msg = results.iter().find(|r| r.id == "@ai-elements/message");
         assert!(ai_msg.is_some(), "Should find ai-elements message");
 
         // With configured registries: ai-elements gets 1.0 boost
         let configured: HashSet<String> = ["ai-elements".to_string()].into();
         let results = index
-            .search("message", 10, Some(&configured))
+            .search("message", 10, Some(&configured), None)
             .await
             .unwrap();
         let ai_msg = results
@@ -413,4 +509,80 @@ mod tests {
             ai_msg.score
         );
     }
+
+    #[tokio::test]
+    async fn test_search_with_filter() {
+        let index = test_index().await;
+
+        seed_typed_test_data(
+            &index.fts,
+            &[
+                (
+                    "toast",
+                    "toast",
+                    "",
+                    "toast notification component shadcn",
+                    "registry:ui",
+                    "feedback overlay",
+                ),
+                (
+                    "use-toast",
+                    "use-toast",
+                    "",
+                    "use-toast toast notification hook",
+                    "registry:hook",
+                    "feedback",
+                ),
+                (
+                    "@acme/toast",
+                    "toast",
+                    "acme",
+                    "toast animated notification component",
+                    "registry:ui",
+                    "animation",
+                ),
+            ],
+        )
+        .await;
+
+        let ids = |results: Vec<SearchResult>| -> Vec<String> {
+            let mut ids: Vec<String> = results.into_iter().map(|r| r.id).collect();
+            ids.sort();
+            ids
+        };
+
+        let all = index.search("toast", 10, None, None).await.unwrap();
+        assert_eq!(all.len(), 3);
+
+        let by_registry = ComponentFilter {
+            registry: Some("@acme".to_string()),
+            ..ComponentFilter::default()
+        };
+        let results = index
+            .search("toast", 10, None, Some(&by_registry))
+            .await
+            .unwrap();
+        assert_eq!(ids(results), ["@acme/toast"]);
+
+        let default_hooks = ComponentFilter {
+            registry: Some("ui".to_string()),
+            item_type: Some("hook".to_string()),
+            ..ComponentFilter::default()
+        };
+        let results = index
+            .search("toast", 10, None, Some(&default_hooks))
+            .await
+            .unwrap();
+        assert_eq!(ids(results), ["use-toast"]);
+
+        let by_category = ComponentFilter {
+            category: Some("feedback".to_string()),
+            ..ComponentFilter::default()
+        };
+        let results = index
+            .search("toast", 10, None, Some(&by_category))
+            .await
+            .unwrap();
+        assert_eq!(ids(results), ["toast", "use-toast"]);
+    }
 }
@@ -1,4 +1,10 @@
 //! SDK documentation indexing and search using SQLite FTS5.
+//!
+//! When an [`Embedder`] is configured, chunk embeddings are stored in a companion
+//! vector table to support semantic and hybrid (FTS + vector) search.
+
+use std::collections::HashMap;
+use std::sync::Arc;
 
 use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
@@ -7,11 +13,19 @@ use sqlx::sqlite::SqlitePool;
 
 use crate::common::Timer;
 use crate::databricks_sdk_doc::{SDKSource, download_and_extract_sdk, load_doc_files};
+use crate::sources::databricks_sdk::ParsedDocFile;
 use apx_db::fts::{Fts5Column, Fts5Table, enhance_fts5_query, sanitize_fts5_terms};
+use apx_db::{ContentHashTable, VectorTable};
+
+use super::embedder::Embedder;
 
 const CHUNK_SIZE: usize = 2000; // characters (no tokenizer needed for FTS)
 const CHUNK_OVERLAP: usize = 200; // characters overlap
-const SCHEMA_VERSION: u32 = 1; // v1: Pure FTS (no embeddings)
+const SCHEMA_VERSION: u32 = 1; // v1: FTS chunks; embeddings live in a separate `_vec` table
+const EMBED_BATCH_SIZE: usize = 64; // chunks per embedder call
+const RRF_K: f32 = 60.0; // reciprocal rank fusion damping constant
+/// Columns stored next to each chunk embedding.
+const VECTOR_COLUMNS: [&str; 3] = ["id", "text", "source_file"];
 
 /// Documentation chunk record for storage
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -35,13 +49,13 @@ pub struct DocChunk {
 }
 
 /// Search result with score
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct DocSearchResult {
     /// Matched documentation text.
     pub text: String,
     /// Path of the source file containing this result.
     pub source_file: String,
-    /// FTS5 relevance score (lower is more relevant).
+    /// Relevance score (higher is more relevant).
     pub score: f32,
 }
 
@@ -168,11 +182,51 @@ fn chunk_text(
     chunks
 }
 
+/// Chunk a parsed doc file into [`DocChunk`]s.
+fn chunk_doc(doc: &ParsedDocFile) -> Vec<DocChunk> {
+    chunk_text(
+        &doc.text,
+        &doc.relative_path,
+        &doc.service,
+        &doc.entity,
+        &doc.operation,
+        &doc.symbols,
+    )
+    .into_iter()
+    .map(
+        |(id, chunk_text, chunk_index, svc, ent, op, syms)| DocChunk {
+            id,
+            text: chunk_text,
+            source_file: doc.relative_path.clone(),
+            chunk_index,
+            service: svc,
+            entity: ent,
+            operation: op,
+            symbols: syms,
+        },
+    )
+    .collect()
+}
+
+/// Counts of documents touched by [`SDKDocsIndex::reindex_incremental`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ReindexStats {
+    /// Documents not present in the previous index.
+    pub added: usize,
+    /// Documents whose content hash changed.
+    pub updated: usize,
+    /// Documents no longer present in the SDK docs.
+    pub removed: usize,
+    /// Documents left untouched.
+    pub unchanged: usize,
+}
+
 /// SDK documentation index using SQLite FTS5
 #[derive(Debug, Clone)]
 pub struct SDKDocsIndex {
     pool: SqlitePool,
     version: Option<String>,
+    embedder: Option<Arc<dyn Embedder>>,
 }
 
 impl SDKDocsIndex {
@@ -181,9 +235,17 @@ impl SDKDocsIndex {
         Self {
             pool,
             version: None,
+            embedder: None,
         }
     }
 
+    /// Enable semantic search, embedding chunks and queries with `embedder`.
+    #[must_use]
+    pub fn with_embedder(mut self, embedder: Arc<dyn Embedder>) -> Self {
+        self.embedder = Some(embedder);
+        self
+    }
+
     /// Get table name for a version
     pub fn table_name(version: &str) -> String {
         format!(
@@ -198,6 +260,71 @@ impl SDKDocsIndex {
         Fts5Table::new(self.pool.clone(), table_name, docs_fts_columns())
     }
 
+    /// Build a [`ContentHashTable`] handle paired with the given FTS table.
+    fn hash_table(&self, fts: &Fts5Table) -> Result<ContentHashTable, String> {
+        ContentHashTable::new(self.pool.clone(), &format!("{}_hashes", fts.table_name()))
+    }
+
+    /// Build a [`VectorTable`] handle paired with the given FTS table.
+    fn vector_table(
+        &self,
+        fts: &Fts5Table,
+        embedder: &dyn Embedder,
+    ) -> Result<VectorTable, String> {
+        VectorTable::new(
+            self.pool.clone(),
+            &format!("{}_vec", fts.table_name()),
+            VECTOR_COLUMNS.to_vec(),
+            embedder.dimensions(),
+        )
+    }
+
+    /// Embed every chunk of `fts` into a fresh vector table (no-op without an embedder).
+    ///
+    /// Failures are logged rather than returned: search falls back to FTS.
+    async fn index_embeddings(&self, fts: &Fts5Table, rebuild: bool) {
+        let Some(embedder) = self.embedder.as_deref() else {
+            return;
+        };
+        if let Err(e) = self.build_vectors(fts, embedder, rebuild).await {
+            tracing::warn!("Failed to build SDK docs embeddings, using FTS only: {e}");
+        }
+    }
+
+    async fn build_vectors(
+        &self,
+        fts: &Fts5Table,
+        embedder: &dyn Embedder,
+        rebuild: bool,
+    ) -> Result<(), String> {
+        let vectors = self.vector_table(fts, embedder)?;
+        if !rebuild && vectors.exists().await? {
+            return Ok(());
+        }
+
+        let timer = Timer::start("build_vectors");
+        let rows = fts.fetch_all(&VECTOR_COLUMNS).await?;
+        let chunks: Vec<[String; 3]> = rows
+            .iter()
+            .map(|row| VECTOR_COLUMNS.map(|col| row.get::<String, _>(col)))
+            .collect();
+
+        vectors.create_or_replace().await?;
+        let mut tx = vectors.begin().await?;
+        insert_embeddings(&vectors, embedder, &mut tx, &chunks).await?;
+        tx.commit()
+            .await
+            .map_err(|e| format!("Commit error: {e}"))?;
+        timer.finish();
+
+        tracing::info!(
+            "SDK docs embeddings built: {} chunks in table '{}'",
+            chunks.len(),
+            vectors.table_name()
+        );
+        Ok(())
+    }
+
     /// Bootstrap with a pre-computed SDK version
     pub async fn bootstrap_with_version(
         &mut self,
@@ -215,6 +342,7 @@ impl SDKDocsIndex {
                 // Check if already indexed
                 if fts.exists().await? {
                     tracing::info!("SDK docs already indexed for version {}", version);
+                    self.index_embeddings(&fts, false).await;
                     return Ok(false);
                 }
 
@@ -223,6 +351,7 @@ impl SDKDocsIndex {
 
                 // Build index (async)
                 self.build_index(&fts, &docs_path).await?;
+                self.index_embeddings(&fts, true).await;
 
                 Ok(true)
             }
@@ -259,33 +388,7 @@ impl SDKDocsIndex {
             );
         }
 
-        let doc_chunks: Vec<DocChunk> = files
-            .par_iter()
-            .flat_map(|doc| {
-                chunk_text(
-                    &doc.text,
-                    &doc.relative_path,
-                    &doc.service,
-                    &doc.entity,
-                    &doc.operation,
-                    &doc.symbols,
-                )
-                .into_iter()
-                .map(
-                    |(id, chunk_text, chunk_index, svc, ent, op, syms)| DocChunk {
-                        id,
-                        text: chunk_text,
-                        source_file: doc.relative_path.clone(),
-                        chunk_index,
-                        service: svc,
-                        entity: ent,
-                        operation: op,
-                        symbols: syms,
-                    },
-                )
-                .collect::<Vec<_>>()
-            })
-            .collect();
+        let doc_chunks: Vec<DocChunk> = files.par_iter().flat_map(chunk_doc).collect();
 
         chunk_timer.lap(&format!("Created {} text chunks", doc_chunks.len()));
 
@@ -293,6 +396,8 @@ impl SDKDocsIndex {
         let db_timer = Timer::start("database_operations");
 
         fts.create_or_replace().await?;
+        let hashes = self.hash_table(fts)?;
+        hashes.create_or_replace().await?;
 
         db_timer.lap("Created FTS5 table");
 
@@ -301,22 +406,11 @@ impl SDKDocsIndex {
 
         let mut tx = fts.begin().await?;
 
-        for chunk in &doc_chunks {
-            let chunk_idx = chunk.chunk_index.to_string();
-            fts.insert_str(
-                &mut tx,
-                &[
-                    &chunk.id,
-                    &chunk.text,
-                    &chunk.source_file,
-                    &chunk_idx,
-                    &chunk.service,
-                    &chunk.entity,
-                    &chunk.operation,
-                    &chunk.symbols,
-                ],
-            )
-            .await?;
+        insert_chunks(fts, &mut tx, &doc_chunks).await?;
+        for doc in &files {
+            hashes
+                .upsert(&mut tx, doc.id(), &doc.content_hash())
+                .await?;
         }
 
         tx.commit()
@@ -335,6 +429,166 @@ impl SDKDocsIndex {
         Ok(())
     }
 
+    /// Refresh the index for `version`, re-chunking only docs whose content changed.
+    ///
+    /// Unchanged docs (by content hash) keep their chunks and embeddings, new or
+    /// changed docs are replaced, and docs that disappeared are deleted. When
+    /// `version` has no index yet, it is seeded from `base_version`'s index (if
+    /// any) so an SDK bump only pays for the pages that differ.
+    pub async fn reindex_incremental(
+        &mut self,
+        source: &SDKSource,
+        version: &str,
+        base_version: Option<&str>,
+    ) -> Result<ReindexStats, String> {
+        match source {
+            SDKSource::DatabricksSdkPython => {
+                let docs_path = download_and_extract_sdk(version).await?;
+                let files = load_doc_files(&docs_path)?;
+                self.version = Some(version.to_string());
+
+                let fts = self.fts_table(&Self::table_name(version))?;
+                if !fts.exists().await? {
+                    self.seed_from(&fts, base_version).await?;
+                }
+
+                let stats = self.apply_doc_changes(&fts, &files).await?;
+                // Backfill embeddings when the table was never built (e.g. no base index)
+                self.index_embeddings(&fts, false).await;
+
+                tracing::info!(
+                    "SDK docs reindexed for version {}: {} added, {} updated, {} removed, {} unchanged",
+                    version,
+                    stats.added,
+                    stats.updated,
+                    stats.removed,
+                    stats.unchanged
+                );
+                Ok(stats)
+            }
+        }
+    }
+
+    /// Create empty tables for `fts`, copying rows from `base_version`'s index when it exists.
+    async fn seed_from(&self, fts: &Fts5Table, base_version: Option<&str>) -> Result<(), String> {
+        fts.create_or_replace().await?;
+        let hashes = self.hash_table(fts)?;
+        hashes.create_or_replace().await?;
+
+        let Some(base_version) = base_version else {
+            return Ok(());
+        };
+        let base = self.fts_table(&Self::table_name(base_version))?;
+        if !base.exists().await? {
+            tracing::info!(
+                "No SDK docs index for base version {base_version}, indexing from scratch"
+            );
+            return Ok(());
+        }
+
+        let base_hashes = self.hash_table(&base)?;
+        let vectors = match self.embedder.as_deref() {
+            Some(embedder) => {
+                let base_vectors = self.vector_table(&base, embedder)?;
+                if base_vectors.exists().await? {
+                    let vectors = self.vector_table(fts, embedder)?;
+                    vectors.create_or_replace().await?;
+                    Some((vectors, base_vectors))
+                } else {
+                    None
+                }
+            }
+            None => None,
+        };
+
+        let mut tx = fts.begin().await?;
+        fts.copy_rows_from(&mut tx, &base).await?;
+        if base_hashes.exists().await? {
+            hashes.copy_rows_from(&mut tx, &base_hashes).await?;
+        }
+        if let Some((vectors, base_vectors)) = &vectors {
+            vectors.copy_rows_from(&mut tx, base_vectors).await?;
+        }
+        tx.commit()
+            .await
+            .map_err(|e| format!("Commit error: {e}"))?;
+
+        tracing::info!(
+            "Seeded SDK docs index '{}' from version {base_version}",
+            fts.table_name()
+        );
+        Ok(())
+    }
+
+    /// Diff `files` against stored content hashes and apply the changes in one transaction.
+    async fn apply_doc_changes(
+        &self,
+        fts: &Fts5Table,
+        files: &[ParsedDocFile],
+    ) -> Result<ReindexStats, String> {
+        let timer = Timer::start("apply_doc_changes");
+        let hashes = self.hash_table(fts)?;
+        if !hashes.exists().await? {
+            hashes.create_or_replace().await?;
+        }
+        let mut stored = hashes.load_all().await?;
+
+        let embedding = match self.embedder.as_deref() {
+            Some(embedder) => {
+                let vectors = self.vector_table(fts, embedder)?;
+                vectors.exists().await?.then_some((embedder, vectors))
+            }
+            None => None,
+        };
+
+        let mut stats = ReindexStats::default();
+        let mut tx = fts.begin().await?;
+
+        for doc in files {
+            let hash = doc.content_hash();
+            match stored.remove(doc.id()) {
+                Some(previous) if previous == hash => {
+                    stats.unchanged += 1;
+                    continue;
+                }
+                Some(_) => stats.updated += 1,
+                None => stats.added += 1,
+            }
+
+            // Also clears chunks of docs indexed before hashes were tracked
+            fts.delete_where(&mut tx, "source_file", doc.id()).await?;
+            let chunks = chunk_doc(doc);
+            insert_chunks(fts, &mut tx, &chunks).await?;
+            if let Some((embedder, vectors)) = &embedding {
+                vectors
+                    .delete_where(&mut tx, "source_file", doc.id())
+                    .await?;
+                let rows: Vec<[String; 3]> = chunks
+                    .into_iter()
+                    .map(|c| [c.id, c.text, c.source_file])
+                    .collect();
+                insert_embeddings(vectors, *embedder, &mut tx, &rows).await?;
+            }
+            hashes.upsert(&mut tx, doc.id(), &hash).await?;
+        }
+
+        // Whatever is left in `stored` no longer exists in the docs
+        for doc_id in stored.keys() {
+            fts.delete_where(&mut tx, "source_file", doc_id).await?;
+            if let Some((_, vectors)) = &embedding {
+                vectors.delete_where(&mut tx, "source_file", doc_id).await?;
+            }
+            hashes.delete(&mut tx, doc_id).await?;
+            stats.removed += 1;
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| format!("Commit error: {e}"))?;
+        timer.finish();
+        Ok(stats)
+    }
+
     /// Switch to a different SDK version, bootstrapping it if needed.
     ///
     /// This is cheap when the version is already indexed (just a `table_exists` check),
@@ -351,6 +605,38 @@ impl SDKDocsIndex {
         Ok(())
     }
 
+    /// Return the FTS table for the bootstrapped version, erroring if it is not indexed.
+    async fn indexed_fts(&self) -> Result<Fts5Table, String> {
+        let version = self.version.as_ref().ok_or_else(|| {
+            "SDK docs index not initialized. No version has been bootstrapped.".to_string()
+        })?;
+
+        let table_name = Self::table_name(version);
+        let fts = self.fts_table(&table_name)?;
+
+        if !fts.exists().await? {
+            return Err(format!(
+                "SDK docs not indexed for version {version}. Index will be built on next server start."
+            ));
+        }
+        Ok(fts)
+    }
+
+    /// Return the embedder and vector table when semantic search is available.
+    async fn indexed_vectors(
+        &self,
+        fts: &Fts5Table,
+    ) -> Result<Option<(&dyn Embedder, VectorTable)>, String> {
+        let Some(embedder) = self.embedder.as_deref() else {
+            return Ok(None);
+        };
+        let vectors = self.vector_table(fts, embedder)?;
+        if !vectors.exists().await? {
+            return Ok(None);
+        }
+        Ok(Some((embedder, vectors)))
+    }
+
     /// Search for relevant documentation chunks using FTS5
     pub async fn search(
         &self,
@@ -360,63 +646,213 @@ impl SDKDocsIndex {
     ) -> Result<Vec<DocSearchResult>, String> {
         match source {
             SDKSource::DatabricksSdkPython => {
-                let version = self.version.as_ref().ok_or_else(|| {
-                    "SDK docs index not initialized. No version has been bootstrapped.".to_string()
-                })?;
-
-                let table_name = Self::table_name(version);
-                let fts = self.fts_table(&table_name)?;
-
-                if !fts.exists().await? {
-                    return Err(format!(
-                        "SDK docs not indexed for version {version}. Index will be built on next server start."
-                    ));
-                }
+                let fts = self.indexed_fts().await?;
+                let results = fts_ranked(&fts, query, limit).await?;
+                tracing::info!("FTS5 search for '{}': {} results", query, results.len());
+                Ok(results.into_iter().map(|(_, result)| result).collect())
+            }
+        }
+    }
 
-                let terms = sanitize_fts5_terms(query);
-                if terms.is_empty() {
-                    return Ok(Vec::new());
-                }
+    /// Search by embedding similarity, falling back to FTS when no embedder is configured.
+    pub async fn search_semantic(
+        &self,
+        source: &SDKSource,
+        query: &str,
+        k: usize,
+    ) -> Result<Vec<DocSearchResult>, String> {
+        match source {
+            SDKSource::DatabricksSdkPython => {
+                let fts = self.indexed_fts().await?;
+                let Some((embedder, vectors)) = self.indexed_vectors(&fts).await? else {
+                    return self.search(source, query, k).await;
+                };
+                let results = vector_ranked(embedder, &vectors, query, k).await?;
+                tracing::info!("Vector search for '{}': {} results", query, results.len());
+                Ok(results.into_iter().map(|(_, result)| result).collect())
+            }
+        }
+    }
 
-                // Enhance query: if it contains a PascalCase term, boost entity column
-                let sanitized = enhance_fts5_query(&terms);
+    /// Fuse FTS and vector results with reciprocal rank fusion.
+    ///
+    /// Falls back to FTS when no embedder is configured.
+    pub async fn search_hybrid(
+        &self,
+        source: &SDKSource,
+        query: &str,
+        k: usize,
+    ) -> Result<Vec<DocSearchResult>, String> {
+        match source {
+            SDKSource::DatabricksSdkPython => {
+                let fts = self.indexed_fts().await?;
+                let Some((embedder, vectors)) = self.indexed_vectors(&fts).await? else {
+                    return self.search(source, query, k).await;
+                };
+                // Over-fetch from each ranker so fusion can promote items ranked lower in one list
+                let candidates = k.saturating_mul(2);
+                let keyword = fts_ranked(&fts, query, candidates).await?;
+                let semantic = vector_ranked(embedder, &vectors, query, candidates).await?;
+                let results = reciprocal_rank_fusion(&[keyword, semantic], k);
+                tracing::info!("Hybrid search for '{}': {} results", query, results.len());
+                Ok(results)
+            }
+        }
+    }
+}
 
-                tracing::debug!(
-                    "search: Executing FTS5 query '{}' (original: '{}')",
-                    sanitized,
-                    query
-                );
+/// Insert chunks into the FTS table within `tx`.
+async fn insert_chunks(
+    fts: &Fts5Table,
+    tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+    chunks: &[DocChunk],
+) -> Result<(), String> {
+    for chunk in chunks {
+        let chunk_idx = chunk.chunk_index.to_string();
+        fts.insert_str(
+            tx,
+            &[
+                &chunk.id,
+                &chunk.text,
+                &chunk.source_file,
+                &chunk_idx,
+                &chunk.service,
+                &chunk.entity,
+                &chunk.operation,
+                &chunk.symbols,
+            ],
+        )
+        .await?;
+    }
+    Ok(())
+}
 
-                // bm25 indexed-column weights (column order):
-                //   text(1.0), service(1.0), entity(5.0), operation(1.0), symbols(3.0)
-                let rows = fts
-                    .search_bm25(
-                        &sanitized,
-                        &[1.0, 1.0, 5.0, 1.0, 3.0],
-                        limit,
-                        &["text", "source_file"],
-                    )
-                    .await?;
+/// Embed `[id, text, source_file]` rows in batches and insert them within `tx`.
+async fn insert_embeddings(
+    vectors: &VectorTable,
+    embedder: &dyn Embedder,
+    tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+    rows: &[[String; 3]],
+) -> Result<(), String> {
+    for batch in rows.chunks(EMBED_BATCH_SIZE) {
+        let texts: Vec<&str> = batch.iter().map(|[_, text, _]| text.as_str()).collect();
+        let embeddings = embedder.embed(&texts)?;
+        if embeddings.len() != batch.len() {
+            return Err(format!(
+                "Embedder returned {} embeddings for {} texts",
+                embeddings.len(),
+                batch.len()
+            ));
+        }
+        for ([id, text, source_file], embedding) in batch.iter().zip(&embeddings) {
+            vectors
+                .insert(tx, &[id, text, source_file], embedding)
+                .await?;
+        }
+    }
+    Ok(())
+}
 
-                let mut results = Vec::new();
+/// Run a bm25-ranked FTS5 query, returning `(chunk id, result)` pairs in rank order.
+async fn fts_ranked(
+    fts: &Fts5Table,
+    query: &str,
+    limit: usize,
+) -> Result<Vec<(String, DocSearchResult)>, String> {
+    let terms = sanitize_fts5_terms(query);
+    if terms.is_empty() {
+        return Ok(Vec::new());
+    }
 
-                for (rank, row) in rows.iter().enumerate() {
-                    let text: String = row.get("text");
-                    let source_file: String = row.get("source_file");
+    // Enhance query: if it contains a PascalCase term, boost entity column
+    let sanitized = enhance_fts5_query(&terms);
+
+    tracing::debug!(
+        "search: Executing FTS5 query '{}' (original: '{}')",
+        sanitized,
+        query
+    );
+
+    // bm25 indexed-column weights (column order):
+    //   text(1.0), service(1.0), entity(5.0), operation(1.0), symbols(3.0)
+    let rows = fts
+        .search_bm25(
+            &sanitized,
+            &[1.0, 1.0, 5.0, 1.0, 3.0],
+            limit,
+            &["id", "text", "source_file"],
+        )
+        .await?;
+
+    Ok(rows
+        .iter()
+        .enumerate()
+        .map(|(rank, row)| {
+            let result = DocSearchResult {
+                text: row.get("text"),
+                source_file: row.get("source_file"),
+                score: 1.0 / (1.0 + rank as f32),
+            };
+            (row.get("id"), result)
+        })
+        .collect())
+}
 
-                    let score = 1.0 / (1.0 + rank as f32);
-                    results.push(DocSearchResult {
-                        text,
-                        source_file,
-                        score,
-                    });
-                }
+/// Embed `query` and return the `limit` nearest chunks as `(chunk id, result)` pairs.
+async fn vector_ranked(
+    embedder: &dyn Embedder,
+    vectors: &VectorTable,
+    query: &str,
+    limit: usize,
+) -> Result<Vec<(String, DocSearchResult)>, String> {
+    let embedding = embedder
+        .embed(&[query])?
+        .into_iter()
+        .next()
+        .ok_or_else(|| "Embedder returned no embedding for the query".to_string())?;
+
+    let matches = vectors.search_cosine(&embedding, limit).await?;
+    Ok(matches
+        .into_iter()
+        .filter_map(|m| {
+            let [id, text, source_file] = <[String; 3]>::try_from(m.values).ok()?;
+            Some((
+                id,
+                DocSearchResult {
+                    text,
+                    source_file,
+                    score: m.score,
+                },
+            ))
+        })
+        .collect())
+}
 
-                tracing::info!("FTS5 search for '{}': {} results", query, results.len());
-                Ok(results)
-            }
+/// Combine ranked lists: each item scores `sum(1 / (RRF_K + rank))` across lists.
+fn reciprocal_rank_fusion(
+    rankings: &[Vec<(String, DocSearchResult)>],
+    k: usize,
+) -> Vec<DocSearchResult> {
+    let mut fused: HashMap<&str, (f32, &DocSearchResult)> = HashMap::new();
+    for ranking in rankings {
+        for (rank, (id, result)) in ranking.iter().enumerate() {
+            let entry = fused.entry(id.as_str()).or_insert((0.0, result));
+            entry.0 += 1.0 / (RRF_K + rank as f32 + 1.0);
         }
     }
+
+    let mut ordered: Vec<_> = fused.into_iter().collect();
+    ordered.sort_by(|(a_id, (a_score, _)), (b_id, (b_score, _))| {
+        b_score.total_cmp(a_score).then_with(|| a_id.cmp(b_id))
+    });
+    ordered
+        .into_iter()
+        .take(k)
+        .map(|(_, (score, result))| DocSearchResult {
+            score,
+            ..result.clone()
+        })
+        .collect()
 }
 
 #[cfg(test)]
@@ -454,6 +890,7 @@ mod tests {
             Self {
                 pool,
                 version: None,
+                embedder: None,
             }
         }
     }
@@ -565,4 +1002,168 @@ mod tests {
 
         assert_eq!(rows.len(), 1);
     }
+
+    /// Deterministic embedder mapping known words (and synonyms) onto fixed axes.
+    #[derive(Debug)]
+    struct FakeEmbedder;
+
+    impl Embedder for FakeEmbedder {
+        fn dimensions(&self) -> usize {
+            3
+        }
+
+        fn embed(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>, String> {
+            Ok(texts
+                .iter()
+                .map(|text| {
+                    let mut v = vec![0.0; 3];
+                    for word in text.to_lowercase().split_whitespace() {
+                        match word {
+                            "list" | "enumerate" => v[0] += 1.0,
+                            "jobs" => v[1] += 1.0,
+                            "clusters" | "create" => v[2] += 1.0,
+                            _ => {}
+                        }
+                    }
+                    v
+                })
+                .collect())
+        }
+    }
+
+    /// Index two chunks under version `0.0.0`, embedding them when `embedder` is set.
+    async fn indexed_docs(embedder: Option<Arc<dyn Embedder>>) -> SDKDocsIndex {
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        let mut index = SDKDocsIndex::with_pool(pool);
+        if let Some(embedder) = embedder {
+            index = index.with_embedder(embedder);
+        }
+        let version = "0.0.0";
+        index.version = Some(version.to_string());
+
+        let fts = index.fts_table(&SDKDocsIndex::table_name(version)).unwrap();
+        fts.create_or_replace().await.unwrap();
+        let mut tx = fts.begin().await.unwrap();
+        for (id, text, service) in [
+            ("jobs.rst:0", "enumerate jobs in the workspace", "jobs"),
+            ("clusters.rst:0", "create clusters for compute", "clusters"),
+        ] {
+            let source_file = id.split(':').next().unwrap();
+            fts.insert_str(
+                &mut tx,
+                &[id, text, source_file, "0", service, "", "", service],
+            )
+            .await
+            .unwrap();
+        }
+        tx.commit().await.unwrap();
+
+        index.index_embeddings(&fts, true).await;
+        index
+    }
+
+    #[tokio::test]
+    async fn test_search_semantic_matches_synonyms() {
+        let index = indexed_docs(Some(Arc::new(FakeEmbedder))).await;
+        let source = SDKSource::DatabricksSdkPython;
+
+        // "list" never appears in the docs, so FTS finds nothing
+        assert!(index.search(&source, "list", 5).await.unwrap().is_empty());
+
+        let results = index.search_semantic(&source, "list", 1).await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].source_file, "jobs.rst");
+    }
+
+    #[tokio::test]
+    async fn test_search_hybrid_fuses_fts_and_vectors() {
+        let index = indexed_docs(Some(Arc::new(FakeEmbedder))).await;
+        let source = SDKSource::DatabricksSdkPython;
+
+        let results = index
+            .search_hybrid(&source, "list clusters", 2)
+            .await
+            .unwrap();
+        let files: Vec<&str> = results.iter().map(|r| r.source_file.as_str()).collect();
+        // clusters ranks first in both lists; jobs only via the vector match on "list"
+        assert_eq!(files, ["clusters.rst", "jobs.rst"]);
+        assert!(results[0].score > results[1].score);
+    }
+
+    #[tokio::test]
+    async fn test_semantic_search_falls_back_to_fts_without_embedder() {
+        let index = indexed_docs(None).await;
+        let source = SDKSource::DatabricksSdkPython;
+
+        let fts = index.search(&source, "clusters", 5).await.unwrap();
+        let semantic = index.search_semantic(&source, "clusters", 5).await.unwrap();
+        let hybrid = index.search_hybrid(&source, "clusters", 5).await.unwrap();
+        assert_eq!(semantic.len(), 1);
+        assert_eq!(semantic, fts);
+        assert_eq!(hybrid, fts);
+    }
+
+    fn doc(path: &str, text: &str) -> ParsedDocFile {
+        ParsedDocFile {
+            relative_path: path.to_string(),
+            text: text.to_string(),
+            service: String::new(),
+            entity: String::new(),
+            operation: String::new(),
+            symbols: String::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_apply_doc_changes_is_incremental() {
+        let index = indexed_docs(Some(Arc::new(FakeEmbedder))).await;
+        let source = SDKSource::DatabricksSdkPython;
+        let fts = index.indexed_fts().await.unwrap();
+
+        // Chunks indexed before hashes were tracked are replaced on first pass
+        let first = index
+            .apply_doc_changes(
+                &fts,
+                &[
+                    doc("jobs.rst", "enumerate jobs"),
+                    doc("clusters.rst", "create clusters"),
+                ],
+            )
+            .await
+            .unwrap();
+        assert_eq!(first.added, 2);
+
+        let second = index
+            .apply_doc_changes(
+                &fts,
+                &[
+                    doc("jobs.rst", "enumerate jobs"),
+                    doc("clusters.rst", "create clusters with autoscaling"),
+                    doc("warehouses.rst", "list warehouses"),
+                ],
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            second,
+            ReindexStats {
+                added: 1,
+                updated: 1,
+                removed: 0,
+                unchanged: 1,
+            }
+        );
+
+        let third = index
+            .apply_doc_changes(&fts, &[doc("warehouses.rst", "list warehouses")])
+            .await
+            .unwrap();
+        assert_eq!(third.removed, 2);
+        assert_eq!(third.unchanged, 1);
+
+        assert!(index.search(&source, "jobs", 5).await.unwrap().is_empty());
+        let semantic = index.search_semantic(&source, "list", 5).await.unwrap();
+        let files: Vec<&str> = semantic.iter().map(|r| r.source_file.as_str()).collect();
+        assert_eq!(files, ["warehouses.rst"]);
+    }
 }
@@ -0,0 +1,133 @@
+//! Pluggable text embedding for semantic search.
+
+use std::fmt::Debug;
+
+/// Turns text into fixed-size vectors for semantic (vector) search.
+///
+/// Implementations must be deterministic for a given input so stored document
+/// embeddings stay comparable with query embeddings.
+pub trait Embedder: Debug + Send + Sync {
+    /// Number of dimensions of every produced embedding.
+    fn dimensions(&self) -> usize;
+
+    /// Embed a batch of texts, returning one vector per input in the same order.
+    fn embed(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>, String>;
+}
+
+/// Dependency-free [`Embedder`] hashing words and their character trigrams into
+/// a fixed number of buckets.
+///
+/// Texts sharing words or word stems ("list" and "listing") end up close, so it
+/// catches inflections and partial matches that exact FTS tokens miss. It knows
+/// nothing about synonyms; plug in a model-backed [`Embedder`] for that.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HashingEmbedder {
+    dimensions: usize,
+}
+
+impl Default for HashingEmbedder {
+    fn default() -> Self {
+        Self { dimensions: 256 }
+    }
+}
+
+impl HashingEmbedder {
+    /// Create an embedder producing vectors of `dimensions` entries (at least 1).
+    pub fn new(dimensions: usize) -> Self {
+        Self {
+            dimensions: dimensions.max(1),
+        }
+    }
+
+    fn embed_one(&self, text: &str) -> Vec<f32> {
+        let mut vector = vec![0.0_f32; self.dimensions];
+        let lower = text.to_lowercase();
+        for word in lower
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|w| !w.is_empty())
+        {
+            self.add_feature(&mut vector, word.as_bytes(), 1.0);
+            let padded: Vec<char> = format!("^{word}$").chars().collect();
+            for trigram in padded.windows(3) {
+                let trigram: String = trigram.iter().collect();
+                self.add_feature(&mut vector, trigram.as_bytes(), 0.5);
+            }
+        }
+
+        let norm = vector.iter().map(|v| v * v).sum::<f32>().sqrt();
+        if norm > 0.0 {
+            for v in &mut vector {
+                *v /= norm;
+            }
+        }
+        vector
+    }
+
+    /// Add `weight` to the bucket of `feature`, with a hash-derived sign so
+    /// colliding features tend to cancel out instead of piling up.
+    fn add_feature(&self, vector: &mut [f32], feature: &[u8], weight: f32) {
+        let hash = fnv1a(feature);
+        // Reason: the modulo keeps the bucket below `dimensions`, a usize
+        #[allow(clippy::cast_possible_truncation)]
+        let bucket = (hash % self.dimensions as u64) as usize;
+        let sign = if hash >> 63 == 0 { 1.0 } else { -1.0 };
+        vector[bucket] += sign * weight;
+    }
+}
+
+impl Embedder for HashingEmbedder {
+    fn dimensions(&self) -> usize {
+        self.dimensions
+    }
+
+    fn embed(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>, String> {
+        Ok(texts.iter().map(|text| self.embed_one(text)).collect())
+    }
+}
+
+/// 64-bit FNV-1a, stable across platforms and Rust versions so stored
+/// embeddings stay valid.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+#[cfg(test)]
+// Reason: panicking on failure is idiomatic in tests
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    fn cosine(a: &[f32], b: &[f32]) -> f32 {
+        a.iter().zip(b).map(|(x, y)| x * y).sum()
+    }
+
+    #[test]
+    fn test_hashing_embedder_is_deterministic_and_normalized() {
+        let embedder = HashingEmbedder::default();
+        let first = embedder.embed(&["List all jobs"]).unwrap();
+        let second = embedder.embed(&["List all jobs"]).unwrap();
+        assert_eq!(first, second);
+        assert_eq!(first[0].len(), embedder.dimensions());
+        assert!((cosine(&first[0], &first[0]) - 1.0).abs() < 1e-5);
+
+        let empty = embedder.embed(&[""]).unwrap();
+        assert!(empty[0].iter().all(|v| *v == 0.0));
+    }
+
+    #[test]
+    fn test_hashing_embedder_ranks_related_text_closer() {
+        let embedder = HashingEmbedder::default();
+        let vectors = embedder
+            .embed(&[
+                "listing jobs",
+                "w.jobs.list() returns every job in the workspace",
+                "create a cluster with autoscaling",
+            ])
+            .unwrap();
+        let related = cosine(&vectors[0], &vectors[1]);
+        let unrelated = cosine(&vectors[0], &vectors[2]);
+        assert!(related > unrelated, "{related} <= {unrelated}");
+    }
+}
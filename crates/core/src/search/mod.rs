@@ -1,13 +1,16 @@
 //! Search functionality for SDK docs and components.
 //!
-//! Uses SQLite FTS5 for all search operations.
+//! Uses SQLite FTS5 for keyword search, optionally combined with embedding-based
+//! semantic search for SDK docs.
 
 pub mod common;
 pub mod component_index;
 pub mod docs_index;
+pub mod embedder;
 
-pub use component_index::ComponentIndex;
+pub use component_index::{ComponentFilter, ComponentIndex};
+pub use embedder::{Embedder, HashingEmbedder};
 
 // Re-export for external use
 #[allow(unused_imports)]
-pub use docs_index::{DocSearchResult, SDKDocsIndex};
+pub use docs_index::{DocSearchResult, ReindexStats, SDKDocsIndex};
@@ -6,6 +6,7 @@
 use crate::common::Timer;
 use rayon::prelude::*;
 use serde::Deserialize;
+use sha2::{Digest, Sha256};
 use std::fs;
 use std::io::Cursor;
 use std::path::{Path, PathBuf};
@@ -32,6 +33,29 @@ pub struct ParsedDocFile {
     pub symbols: String,
 }
 
+impl ParsedDocFile {
+    /// Stable document ID (the path relative to `docs/`), unchanged across SDK versions.
+    pub const fn id(&self) -> &str {
+        self.relative_path.as_str()
+    }
+
+    /// SHA-256 of the text and metadata, used to detect changed docs when reindexing.
+    pub fn content_hash(&self) -> String {
+        let mut hasher = Sha256::new();
+        for part in [
+            &self.text,
+            &self.service,
+            &self.entity,
+            &self.operation,
+            &self.symbols,
+        ] {
+            hasher.update(part.as_bytes());
+            hasher.update([0]);
+        }
+        hex::encode(hasher.finalize())
+    }
+}
+
 /// Get cache path for SDK documentation
 fn get_cache_path(version: &str) -> Result<PathBuf, String> {
     Ok(dirs::home_dir()
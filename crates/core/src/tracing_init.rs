@@ -149,7 +149,7 @@ fn init_tracing_with_otel(
     app_dir: Option<&str>,
 ) -> Result<(), String> {
     use opentelemetry::KeyValue;
-    use opentelemetry_otlp::WithExportConfig;
+    use opentelemetry_otlp::{WithExportConfig, WithHttpConfig};
     use opentelemetry_sdk::Resource;
     use opentelemetry_sdk::logs::SdkLoggerProvider;
 
@@ -159,9 +159,15 @@ fn init_tracing_with_otel(
         crate::flux::FLUX_PORT
     );
 
+    let mut headers = std::collections::HashMap::new();
+    if let Some(token) = apx_common::flux_token() {
+        headers.insert("Authorization".to_string(), format!("Bearer {token}"));
+    }
+
     let exporter = opentelemetry_otlp::LogExporter::builder()
         .with_http()
         .with_endpoint(&endpoint)
+        .with_headers(headers)
         .build()
         .map_err(|e| format!("Failed to create OTLP exporter: {e}"))?;
 
@@ -0,0 +1,188 @@
+//! Per-document content hashes for incremental reindexing.
+//!
+//! [`ContentHashTable`] maps a stable document ID to a hash of its content so
+//! indexers can tell which documents changed since the last build.
+
+use std::collections::HashMap;
+
+use sqlx::Row;
+use sqlx::sqlite::SqlitePool;
+
+use crate::fts::validate_identifier;
+
+/// Builder/handle for a `(doc_id, content_hash)` table.
+#[derive(Debug, Clone)]
+pub struct ContentHashTable {
+    pool: SqlitePool,
+    table_name: String,
+}
+
+impl ContentHashTable {
+    /// Construct with a validated table name (alphanumeric + underscore only).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the table name contains invalid characters.
+    pub fn new(pool: SqlitePool, table_name: &str) -> Result<Self, String> {
+        validate_identifier(table_name)?;
+        Ok(Self {
+            pool,
+            table_name: table_name.to_string(),
+        })
+    }
+
+    /// Get the table name.
+    #[must_use]
+    pub fn table_name(&self) -> &str {
+        &self.table_name
+    }
+
+    /// Check if the table exists in the database.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the existence check query fails.
+    pub async fn exists(&self) -> Result<bool, String> {
+        super::dev::table_exists(&self.pool, &self.table_name).await
+    }
+
+    /// `DROP TABLE IF EXISTS` + `CREATE TABLE`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the drop or create query fails.
+    pub async fn create_or_replace(&self) -> Result<(), String> {
+        let drop_sql = format!("DROP TABLE IF EXISTS \"{}\"", self.table_name);
+        sqlx::query(&drop_sql)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to drop table '{}': {e}", self.table_name))?;
+
+        let create_sql = format!(
+            "CREATE TABLE \"{}\" (doc_id TEXT PRIMARY KEY, content_hash TEXT NOT NULL)",
+            self.table_name
+        );
+        sqlx::query(&create_sql)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to create hash table '{}': {e}", self.table_name))?;
+
+        Ok(())
+    }
+
+    /// Load all stored hashes keyed by document ID (empty if the table is missing).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub async fn load_all(&self) -> Result<HashMap<String, String>, String> {
+        if !self.exists().await? {
+            return Ok(HashMap::new());
+        }
+
+        let sql = format!("SELECT doc_id, content_hash FROM \"{}\"", self.table_name);
+        let rows = sqlx::query(&sql)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| format!("Query error: {e}"))?;
+
+        Ok(rows
+            .iter()
+            .map(|row| (row.get("doc_id"), row.get("content_hash")))
+            .collect())
+    }
+
+    /// Insert or replace the hash for `doc_id`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the upsert fails.
+    pub async fn upsert(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+        doc_id: &str,
+        content_hash: &str,
+    ) -> Result<(), String> {
+        let sql = format!(
+            "INSERT OR REPLACE INTO \"{}\" (doc_id, content_hash) VALUES (?1, ?2)",
+            self.table_name
+        );
+        sqlx::query(&sql)
+            .bind(doc_id)
+            .bind(content_hash)
+            .execute(&mut **tx)
+            .await
+            .map_err(|e| format!("Upsert error: {e}"))?;
+
+        Ok(())
+    }
+
+    /// Remove the hash for `doc_id`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the delete fails.
+    pub async fn delete(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+        doc_id: &str,
+    ) -> Result<(), String> {
+        let sql = format!("DELETE FROM \"{}\" WHERE doc_id = ?1", self.table_name);
+        sqlx::query(&sql)
+            .bind(doc_id)
+            .execute(&mut **tx)
+            .await
+            .map_err(|e| format!("Delete error: {e}"))?;
+
+        Ok(())
+    }
+
+    /// Copy every stored hash from `source` into this table.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the copy fails.
+    pub async fn copy_rows_from(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+        source: &Self,
+    ) -> Result<(), String> {
+        let sql = format!(
+            "INSERT OR REPLACE INTO \"{}\" (doc_id, content_hash) \
+             SELECT doc_id, content_hash FROM \"{}\"",
+            self.table_name, source.table_name
+        );
+        sqlx::query(&sql)
+            .execute(&mut **tx)
+            .await
+            .map_err(|e| format!("Copy error: {e}"))?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+// Reason: panicking on failure is idiomatic in tests
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_content_hash_round_trip() {
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        let table = ContentHashTable::new(pool, "hashes_test").unwrap();
+        assert!(table.load_all().await.unwrap().is_empty());
+
+        table.create_or_replace().await.unwrap();
+        let mut tx = table.pool.begin().await.unwrap();
+        table.upsert(&mut tx, "a.rst", "1").await.unwrap();
+        table.upsert(&mut tx, "b.rst", "2").await.unwrap();
+        table.upsert(&mut tx, "a.rst", "3").await.unwrap();
+        table.delete(&mut tx, "b.rst").await.unwrap();
+        tx.commit().await.unwrap();
+
+        let hashes = table.load_all().await.unwrap();
+        assert_eq!(hashes.len(), 1);
+        assert_eq!(hashes["a.rst"], "3");
+    }
+}
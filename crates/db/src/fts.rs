@@ -15,6 +15,33 @@ pub struct Fts5Column {
     pub indexed: bool,
 }
 
+/// Predicate on an FTS5 column, ANDed with the `MATCH` expression.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fts5Filter<'a> {
+    /// `column = value`.
+    Eq {
+        /// Column name.
+        column: &'a str,
+        /// Value to compare against.
+        value: &'a str,
+    },
+    /// The whitespace-separated list stored in `column` contains `value`.
+    HasToken {
+        /// Column name.
+        column: &'a str,
+        /// Token to look for.
+        value: &'a str,
+    },
+}
+
+impl Fts5Filter<'_> {
+    const fn column(&self) -> &str {
+        match self {
+            Self::Eq { column, .. } | Self::HasToken { column, .. } => column,
+        }
+    }
+}
+
 /// Builder/handle for an FTS5 virtual table.
 #[derive(Debug, Clone)]
 pub struct Fts5Table {
@@ -166,6 +193,63 @@ impl Fts5Table {
         Ok(())
     }
 
+    /// Delete every row whose `column` equals `value`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the column is unknown or the delete fails.
+    pub async fn delete_where(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+        column: &str,
+        value: &str,
+    ) -> Result<(), String> {
+        if !self.columns.iter().any(|c| c.name == column) {
+            return Err(format!("Unknown column '{column}'"));
+        }
+
+        let sql = format!("DELETE FROM \"{}\" WHERE {column} = ?1", self.table_name);
+        sqlx::query(&sql)
+            .bind(value)
+            .execute(&mut **tx)
+            .await
+            .map_err(|e| format!("Delete error: {e}"))?;
+
+        Ok(())
+    }
+
+    /// Copy every row of `source` (which must share this table's columns) into this table.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the column layouts differ or the copy fails.
+    pub async fn copy_rows_from(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+        source: &Self,
+    ) -> Result<(), String> {
+        let col_names: Vec<&str> = self.columns.iter().map(|c| c.name).collect();
+        let source_names: Vec<&str> = source.columns.iter().map(|c| c.name).collect();
+        if col_names != source_names {
+            return Err(format!(
+                "Cannot copy rows from '{}': column layouts differ",
+                source.table_name
+            ));
+        }
+
+        let cols = col_names.join(", ");
+        let sql = format!(
+            "INSERT INTO \"{}\" ({cols}) SELECT {cols} FROM \"{}\"",
+            self.table_name, source.table_name
+        );
+        sqlx::query(&sql)
+            .execute(&mut **tx)
+            .await
+            .map_err(|e| format!("Copy error: {e}"))?;
+
+        Ok(())
+    }
+
     /// FTS5 MATCH search with explicit `bm25()` ranking.
     ///
     /// `bm25_weights` must have one entry per **indexed** column (in column
@@ -238,6 +322,30 @@ impl Fts5Table {
             .map_err(|e| format!("Query error: {e}"))
     }
 
+    /// Fetch the given columns for every row in the table.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if column names are unknown or the query fails.
+    pub async fn fetch_all(&self, result_columns: &[&str]) -> Result<Vec<SqliteRow>, String> {
+        for col in result_columns {
+            if !self.columns.iter().any(|c| c.name == *col) {
+                return Err(format!("Unknown column '{col}' in result_columns"));
+            }
+        }
+
+        let sql = format!(
+            "SELECT {} FROM \"{}\"",
+            result_columns.join(", "),
+            self.table_name
+        );
+
+        sqlx::query(&sql)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| format!("Query error: {e}"))
+    }
+
     /// FTS5 MATCH search using the built-in `rank` column.
     ///
     /// # Errors
@@ -248,6 +356,22 @@ impl Fts5Table {
         match_expr: &str,
         limit: usize,
         result_columns: &[&str],
+    ) -> Result<Vec<SqliteRow>, String> {
+        self.search_filtered(match_expr, &[], limit, result_columns)
+            .await
+    }
+
+    /// FTS5 MATCH search restricted by column `filters`, ranked by the built-in `rank` column.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if result or filter column names are unknown, or the query fails.
+    pub async fn search_filtered(
+        &self,
+        match_expr: &str,
+        filters: &[Fts5Filter<'_>],
+        limit: usize,
+        result_columns: &[&str],
     ) -> Result<Vec<SqliteRow>, String> {
         for col in result_columns {
             if *col != "rank" && !self.columns.iter().any(|c| c.name == *col) {
@@ -255,24 +379,48 @@ impl Fts5Table {
             }
         }
 
+        let mut predicates = Vec::with_capacity(filters.len());
+        for (i, filter) in filters.iter().enumerate() {
+            let column = filter.column();
+            if !self.columns.iter().any(|c| c.name == column) {
+                return Err(format!("Unknown column '{column}' in filters"));
+            }
+            // ?1 and ?2 are the MATCH expression and limit
+            let param = i + 3;
+            predicates.push(match filter {
+                Fts5Filter::Eq { .. } => format!(" AND {column} = ?{param}"),
+                Fts5Filter::HasToken { .. } => {
+                    format!(" AND instr(' ' || {column} || ' ', ?{param}) > 0")
+                }
+            });
+        }
+
         let select_cols = result_columns.join(", ");
 
         let sql = format!(
             "SELECT {select_cols} \
              FROM \"{}\" \
-             WHERE \"{}\" MATCH ?1 \
+             WHERE \"{}\" MATCH ?1{} \
              ORDER BY rank \
              LIMIT ?2",
-            self.table_name, self.table_name,
+            self.table_name,
+            self.table_name,
+            predicates.concat(),
         );
 
         // Reason: row count fits in i64
         #[allow(clippy::cast_possible_wrap)]
         let limit_i64 = limit as i64;
 
-        sqlx::query(&sql)
-            .bind(match_expr)
-            .bind(limit_i64)
+        let mut query = sqlx::query(&sql).bind(match_expr).bind(limit_i64);
+        for filter in filters {
+            query = match filter {
+                Fts5Filter::Eq { value, .. } => query.bind(*value),
+                Fts5Filter::HasToken { value, .. } => query.bind(format!(" {value} ")),
+            };
+        }
+
+        query
             .fetch_all(&self.pool)
             .await
             .map_err(|e| format!("Query error: {e}"))
@@ -361,7 +509,7 @@ pub fn is_pascal_case(s: &str) -> bool {
 }
 
 /// Validate that an identifier contains only alphanumeric characters and underscores.
-fn validate_identifier(name: &str) -> Result<(), String> {
+pub(crate) fn validate_identifier(name: &str) -> Result<(), String> {
     if name.is_empty() {
         return Err("Identifier cannot be empty".to_string());
     }
@@ -2,16 +2,20 @@
 //!
 //! Provides async connection pools for two databases:
 //! - **Logs DB** (`~/.apx/logs/db`) — OTLP log storage
-//! - **Dev DB** (`~/.apx/dev/db`) — search indexes and future dev-related tables
+//! - **Dev DB** (`~/.apx/dev/db`) — search indexes (FTS5 and embeddings) and future dev-related tables
 
+pub mod content_hash;
 pub mod dev;
 pub mod fts;
 pub mod logs;
+pub mod vector;
 
+pub use content_hash::ContentHashTable;
 pub use dev::DevDb;
-pub use fts::{Fts5Column, Fts5Table};
-pub use logs::LogsDb;
+pub use fts::{Fts5Column, Fts5Filter, Fts5Table};
+pub use logs::{LogFilter, LogStats, LogsDb, canonical_app_path};
 pub use sqlx::sqlite::SqlitePool;
+pub use vector::{VectorMatch, VectorTable};
 
 use std::path::PathBuf;
 
@@ -8,16 +8,186 @@ use sqlx::Row;
 use sqlx::sqlite::{
     SqliteConnectOptions, SqliteJournalMode, SqlitePool, SqlitePoolOptions, SqliteSynchronous,
 };
+use std::collections::HashMap;
 use std::path::Path;
+use tokio::sync::broadcast;
 use tracing::debug;
 
 /// Retention period in seconds (7 days).
 const RETENTION_SECONDS: i64 = 7 * 24 * 60 * 60;
 
+/// SQL predicate matching rows recorded for the app path bound to `?1`.
+///
+/// The flux daemon stores logs from every project on the machine in one
+/// database, so every query is scoped to a single app. Paths are stored and
+/// bound in their [canonical form](canonical_app_path), so an exact comparison
+/// matches `/tmp/app` against `/private/tmp/app` on macOS and can use
+/// `idx_logs_app_path`. Rows without an `apx.app_path` never match.
+const APP_PATH_MATCH: &str = "app_path = ?1";
+
+/// Canonical form of an app path, as stored in and compared against the `app_path` column.
+///
+/// Resolves symlinks such as `/tmp` -> `/private/tmp` on macOS, so the path an
+/// app reports and the path a query passes compare equal. Paths that cannot be
+/// resolved, e.g. of a deleted project, are kept as given.
+#[must_use]
+pub fn canonical_app_path(path: &str) -> String {
+    std::fs::canonicalize(path)
+        .map_or_else(|_| path.to_string(), |p| p.to_string_lossy().into_owned())
+}
+
+/// SQL of [`EFFECTIVE_TS`] as a literal, so `concat!` can build it into other
+/// query constants.
+macro_rules! effective_ts {
+    () => {
+        "COALESCE(NULLIF(timestamp_ns, 0), observed_timestamp_ns)"
+    };
+}
+
+/// Event time of a row, falling back to the observed time when the event time is unset.
+///
+/// Queries order by this and then by `id`, so records sharing a timestamp keep
+/// their ingest order.
+const EFFECTIVE_TS: &str = effective_ts!();
+
+/// SQL predicate applying a [`LogFilter`] bound to `?2`..`?7` by [`bind_filter`].
+///
+/// Rows without a severity number count as INFO (9). `?6` holds the attribute
+/// filters as a JSON array of `[key, value]` pairs, each of which must match a
+/// log or resource attribute. `?7` matches the severity text case-insensitively,
+/// falling back to the level name of the severity number, as in [`SEVERITY_LEVEL`].
+const FILTER_MATCH: &str = concat!(
+    "(",
+    effective_ts!(),
+    " >= ?2
+    AND (?3 IS NULL OR ",
+    effective_ts!(),
+    " <= ?3)
+    AND (?4 IS NULL OR service_name = ?4)
+    AND (?5 IS NULL OR COALESCE(severity_number, 9) >= ?5)
+    AND (?7 IS NULL OR COALESCE(NULLIF(severity_text, ''), CASE
+        WHEN COALESCE(severity_number, 9) >= 21 THEN 'FATAL'
+        WHEN COALESCE(severity_number, 9) >= 17 THEN 'ERROR'
+        WHEN COALESCE(severity_number, 9) >= 13 THEN 'WARN'
+        WHEN COALESCE(severity_number, 9) >= 9 THEN 'INFO'
+        WHEN COALESCE(severity_number, 9) >= 5 THEN 'DEBUG'
+        ELSE 'TRACE'
+    END) = ?7 COLLATE NOCASE)
+    AND (?6 IS NULL OR NOT EXISTS (
+        SELECT 1 FROM json_each(?6) AS wanted
+        WHERE NOT EXISTS (
+            SELECT 1
+            FROM json_each(json_array(
+                json(COALESCE(logs.log_attributes, '[]')),
+                json(COALESCE(logs.resource_attributes, '[]'))
+            )) AS source, json_each(source.value) AS attr
+            WHERE json_extract(attr.value, '$.key') = json_extract(wanted.value, '$[0]')
+              AND CASE json_type(attr.value, '$.value.boolValue')
+                    WHEN 'true' THEN 'true'
+                    WHEN 'false' THEN 'false'
+                    ELSE CAST(COALESCE(
+                        json_extract(attr.value, '$.value.stringValue'),
+                        json_extract(attr.value, '$.value.intValue'),
+                        json_extract(attr.value, '$.value.doubleValue')
+                    ) AS TEXT)
+                  END = json_extract(wanted.value, '$[1]')
+        )
+    )))"
+);
+
+/// Filters narrowing a log query beyond the app path.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LogFilter {
+    /// Only logs at or after this time, in nanoseconds since the epoch.
+    pub since_ns: i64,
+    /// Only logs at or before this time, in nanoseconds since the epoch.
+    pub until_ns: Option<i64>,
+    /// Only logs from this `service.name`.
+    pub service: Option<String>,
+    /// Only logs at or above this OTLP severity number (e.g. 13 for WARN).
+    pub min_severity: Option<i32>,
+    /// Only logs whose severity text equals this, ignoring case (e.g. `WARNING`
+    /// or a custom level). Logs without a text match the level name of their number.
+    pub severity_text: Option<String>,
+    /// Only logs carrying every one of these `(key, value)` log or resource
+    /// attributes. Values compare as text, so `("thread.id", "7")` matches an
+    /// integer attribute and `("cached", "true")` a boolean one.
+    pub attributes: Vec<(String, String)>,
+}
+
+/// Aggregate counts over the logs matching a [`LogFilter`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LogStats {
+    /// Number of matching logs. Collapsed repeats count once per occurrence.
+    pub total: i64,
+    /// Counts per severity level (`FATAL`, `ERROR`, `WARN`, `INFO`, `DEBUG`, `TRACE`),
+    /// most severe first. Levels without logs are omitted.
+    pub by_severity: Vec<(String, i64)>,
+    /// Counts per `service.name`, largest first. Logs without a service are not counted here.
+    pub by_service: Vec<(String, i64)>,
+}
+
+/// SQL expression bucketing a row's severity number into its level name.
+///
+/// Rows without a severity number count as INFO (9), as in [`FILTER_MATCH`].
+const SEVERITY_LEVEL: &str = "CASE
+    WHEN COALESCE(severity_number, 9) >= 21 THEN 'FATAL'
+    WHEN COALESCE(severity_number, 9) >= 17 THEN 'ERROR'
+    WHEN COALESCE(severity_number, 9) >= 13 THEN 'WARN'
+    WHEN COALESCE(severity_number, 9) >= 9 THEN 'INFO'
+    WHEN COALESCE(severity_number, 9) >= 5 THEN 'DEBUG'
+    ELSE 'TRACE'
+END";
+
+/// Bind the app path to `?1` and `filter` to `?2`..`?7` of a query using
+/// [`APP_PATH_MATCH`] and [`FILTER_MATCH`].
+fn bind_filter<'q>(
+    query: sqlx::query::Query<'q, sqlx::Sqlite, sqlx::sqlite::SqliteArguments<'q>>,
+    app_path: &'q str,
+    filter: &'q LogFilter,
+) -> sqlx::query::Query<'q, sqlx::Sqlite, sqlx::sqlite::SqliteArguments<'q>> {
+    query
+        .bind(app_path)
+        .bind(filter.since_ns)
+        .bind(filter.until_ns)
+        .bind(filter.service.as_deref())
+        .bind(filter.min_severity)
+        .bind(attributes_json(&filter.attributes))
+        .bind(filter.severity_text.as_deref())
+}
+
+/// Encode attribute filters as the JSON array of `[key, value]` pairs expected
+/// by [`FILTER_MATCH`], or `None` when there are none.
+fn attributes_json(attributes: &[(String, String)]) -> Option<String> {
+    (!attributes.is_empty()).then(|| serde_json::json!(attributes).to_string())
+}
+
+/// Version recorded in `PRAGMA user_version` once rows written by older
+/// versions have been migrated by [`LogsDb::init_schema`].
+const SCHEMA_VERSION: i64 = 1;
+
+/// Number of rows fetched per query by [`LogsDb::export_logs`].
+const EXPORT_BATCH_SIZE: i64 = 1000;
+
+/// Number of pooled read-only connections serving queries.
+const READ_CONNECTIONS: u32 = 4;
+
+/// Records buffered for live subscribers before the slowest one starts missing records.
+const LIVE_CHANNEL_CAPACITY: usize = 4096;
+
 /// Async logs database handle.
+///
+/// Writes go through a single dedicated connection, so concurrent ingest
+/// batches queue in the pool instead of contending for SQLite's write lock.
+/// Queries draw from a separate pool of read-only connections, which WAL mode
+/// lets run alongside the writer, so a slow query never holds up ingest.
+///
+/// Stored records are also published to in-process subscribers, see [`LogsDb::subscribe`].
 #[derive(Clone, Debug)]
 pub struct LogsDb {
-    pool: SqlitePool,
+    writer: SqlitePool,
+    readers: SqlitePool,
+    live: broadcast::Sender<LogRecord>,
 }
 
 impl LogsDb {
@@ -50,17 +220,53 @@ impl LogsDb {
             .journal_mode(SqliteJournalMode::Wal)
             .synchronous(SqliteSynchronous::Normal);
 
-        let pool = SqlitePoolOptions::new()
-            .max_connections(5)
-            .connect_with(opts)
+        let writer = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect_with(opts.clone())
             .await
             .map_err(|e| format!("Failed to open database: {e}"))?;
 
-        let db = Self { pool };
+        // Opened after the writer, which creates the file
+        let readers = SqlitePoolOptions::new()
+            .max_connections(READ_CONNECTIONS)
+            .connect_with(opts.create_if_missing(false).read_only(true))
+            .await
+            .map_err(|e| format!("Failed to open database for reading: {e}"))?;
+
+        let (live, _) = broadcast::channel(LIVE_CHANNEL_CAPACITY);
+        let db = Self {
+            writer,
+            readers,
+            live,
+        };
         db.init_schema().await?;
         Ok(db)
     }
 
+    /// Open an existing database for queries only.
+    ///
+    /// Unlike [`LogsDb::open_at`], the file is neither created nor migrated and no
+    /// write connection is opened, so a running daemon is not contended with.
+    /// Writes through the returned handle fail.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database does not exist or cannot be opened.
+    pub async fn open_read_only(path: &Path) -> Result<Self, String> {
+        let readers = SqlitePoolOptions::new()
+            .max_connections(READ_CONNECTIONS)
+            .connect_with(SqliteConnectOptions::new().filename(path).read_only(true))
+            .await
+            .map_err(|e| format!("Failed to open database for reading: {e}"))?;
+
+        let (live, _) = broadcast::channel(LIVE_CHANNEL_CAPACITY);
+        Ok(Self {
+            writer: readers.clone(),
+            readers,
+            live,
+        })
+    }
+
     /// Initialize the database schema.
     async fn init_schema(&self) -> Result<(), String> {
         sqlx::query(
@@ -77,10 +283,11 @@ impl LogsDb {
                 log_attributes TEXT,
                 trace_id TEXT,
                 span_id TEXT,
+                repeat_count INTEGER NOT NULL DEFAULT 1,
                 created_at INTEGER DEFAULT (strftime('%s', 'now'))
             )",
         )
-        .execute(&self.pool)
+        .execute(&self.writer)
         .await
         .map_err(|e| format!("Failed to initialize schema: {e}"))?;
 
@@ -89,17 +296,90 @@ impl LogsDb {
             "CREATE INDEX IF NOT EXISTS idx_logs_app_path ON logs(app_path)",
             "CREATE INDEX IF NOT EXISTS idx_logs_service ON logs(service_name)",
             "CREATE INDEX IF NOT EXISTS idx_logs_created ON logs(created_at)",
+            "CREATE INDEX IF NOT EXISTS idx_logs_app_service ON logs(app_path, service_name)",
+            "CREATE INDEX IF NOT EXISTS idx_logs_trace ON logs(trace_id)",
         ] {
             sqlx::query(idx_sql)
-                .execute(&self.pool)
+                .execute(&self.writer)
                 .await
                 .map_err(|e| format!("Index error: {e}"))?;
         }
 
+        // Databases created before repeats were collapsed lack the count column
+        let has_repeat_count: bool = sqlx::query_scalar(
+            "SELECT COUNT(*) > 0 FROM pragma_table_info('logs') WHERE name = 'repeat_count'",
+        )
+        .fetch_one(&self.writer)
+        .await
+        .map_err(|e| format!("Migration error: {e}"))?;
+        if !has_repeat_count {
+            sqlx::query("ALTER TABLE logs ADD COLUMN repeat_count INTEGER NOT NULL DEFAULT 1")
+                .execute(&self.writer)
+                .await
+                .map_err(|e| format!("Migration error: {e}"))?;
+        }
+
+        let version: i64 = sqlx::query_scalar("PRAGMA user_version")
+            .fetch_one(&self.writer)
+            .await
+            .map_err(|e| format!("Migration error: {e}"))?;
+        if version < SCHEMA_VERSION {
+            self.migrate_legacy_rows().await?;
+            sqlx::query(&format!("PRAGMA user_version = {SCHEMA_VERSION}"))
+                .execute(&self.writer)
+                .await
+                .map_err(|e| format!("Migration error: {e}"))?;
+        }
+
         debug!("Flux storage schema initialized");
         Ok(())
     }
 
+    /// One-off migration of rows written by older versions, run once per database.
+    async fn migrate_legacy_rows(&self) -> Result<(), String> {
+        // Backfill the indexed columns from the stored OTLP resource attributes
+        // for rows written before they were extracted
+        for (column, key) in [
+            ("service_name", "service.name"),
+            ("app_path", "apx.app_path"),
+        ] {
+            let sql = format!(
+                r"UPDATE logs SET {column} = (
+                    SELECT json_extract(attr.value, '$.value.stringValue')
+                    FROM json_each(logs.resource_attributes) AS attr
+                    WHERE json_extract(attr.value, '$.key') = ?1
+                )
+                WHERE {column} IS NULL
+                  AND resource_attributes IS NOT NULL
+                  AND json_valid(resource_attributes)"
+            );
+            sqlx::query(&sql)
+                .bind(key)
+                .execute(&self.writer)
+                .await
+                .map_err(|e| format!("Migration error: {e}"))?;
+        }
+
+        // Rows written before paths were canonicalized on insert
+        let stored_paths: Vec<String> =
+            sqlx::query_scalar("SELECT DISTINCT app_path FROM logs WHERE app_path IS NOT NULL")
+                .fetch_all(&self.writer)
+                .await
+                .map_err(|e| format!("Migration error: {e}"))?;
+        for path in stored_paths {
+            let canonical = canonical_app_path(&path);
+            if canonical != path {
+                sqlx::query("UPDATE logs SET app_path = ?1 WHERE app_path = ?2")
+                    .bind(&canonical)
+                    .bind(&path)
+                    .execute(&self.writer)
+                    .await
+                    .map_err(|e| format!("Migration error: {e}"))?;
+            }
+        }
+        Ok(())
+    }
+
     /// Insert a batch of log records.
     ///
     /// # Errors
@@ -111,19 +391,26 @@ impl LogsDb {
         }
 
         let mut tx = self
-            .pool
+            .writer
             .begin()
             .await
             .map_err(|e| format!("Transaction error: {e}"))?;
 
-        let mut count = 0;
+        let mut canonical_paths: HashMap<&str, String> = HashMap::new();
+        let mut stored = Vec::with_capacity(records.len());
         for record in records {
-            sqlx::query(
+            let app_path = record.app_path.as_deref().map(|path| {
+                canonical_paths
+                    .entry(path)
+                    .or_insert_with(|| canonical_app_path(path))
+                    .clone()
+            });
+            let result = sqlx::query(
                 r"INSERT INTO logs (
                     timestamp_ns, observed_timestamp_ns, severity_number, severity_text,
                     body, service_name, app_path, resource_attributes, log_attributes,
-                    trace_id, span_id
-                ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+                    trace_id, span_id, repeat_count
+                ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
             )
             .bind(record.timestamp_ns)
             .bind(record.observed_timestamp_ns)
@@ -131,21 +418,47 @@ impl LogsDb {
             .bind(record.severity_text.as_deref())
             .bind(record.body.as_deref())
             .bind(record.service_name.as_deref())
-            .bind(record.app_path.as_deref())
+            .bind(app_path.as_deref())
             .bind(record.resource_attributes.as_deref())
             .bind(record.log_attributes.as_deref())
             .bind(record.trace_id.as_deref())
             .bind(record.span_id.as_deref())
+            .bind(record.repeat_count)
             .execute(&mut *tx)
             .await
             .map_err(|e| format!("Insert error: {e}"))?;
-            count += 1;
+            stored.push((result.last_insert_rowid(), app_path));
         }
 
         tx.commit()
             .await
             .map_err(|e| format!("Commit error: {e}"))?;
-        Ok(count)
+
+        if self.live.receiver_count() > 0 {
+            for (record, (seq, app_path)) in records.iter().zip(&stored) {
+                // Fails only when every subscriber has gone away meanwhile
+                let _ = self.live.send(LogRecord {
+                    seq: *seq,
+                    app_path: app_path.clone(),
+                    ..record.clone()
+                });
+            }
+        }
+        Ok(stored.len())
+    }
+
+    /// Subscribe to records as this process stores them.
+    ///
+    /// Each successful [`insert_logs`](Self::insert_logs) on this handle or any of
+    /// its clones publishes its records once committed, with their
+    /// [`seq`](LogRecord::seq) set and the app path in the
+    /// [canonical form](canonical_app_path) it was stored in. Publishing never waits for
+    /// subscribers: one that falls too far behind gets
+    /// [`RecvError::Lagged`](broadcast::error::RecvError::Lagged) with the number of
+    /// records it missed, then continues from the oldest record still buffered.
+    /// Records written by other processes are not published.
+    pub fn subscribe(&self) -> broadcast::Receiver<LogRecord> {
+        self.live.subscribe()
     }
 
     /// Query logs for a specific app path since a given timestamp.
@@ -155,67 +468,113 @@ impl LogsDb {
     /// Returns an error if the database query fails.
     pub async fn query_logs(
         &self,
-        app_path: Option<&str>,
+        app_path: &str,
         since_ns: i64,
         limit: Option<usize>,
     ) -> Result<Vec<LogRecord>, String> {
-        let effective_ts = "COALESCE(NULLIF(timestamp_ns, 0), observed_timestamp_ns)";
-
-        let sql = match (app_path, limit) {
-            (Some(_), Some(lim)) => format!(
-                r"SELECT timestamp_ns, observed_timestamp_ns, severity_number, severity_text,
-                       body, service_name, app_path, resource_attributes, log_attributes,
-                       trace_id, span_id
-                FROM logs
-                WHERE (app_path LIKE ?1 OR ?1 LIKE '%' || app_path || '%')
-                  AND {effective_ts} >= ?2
-                ORDER BY {effective_ts} ASC
-                LIMIT {lim}"
-            ),
-            (Some(_), None) => format!(
-                r"SELECT timestamp_ns, observed_timestamp_ns, severity_number, severity_text,
-                       body, service_name, app_path, resource_attributes, log_attributes,
-                       trace_id, span_id
-                FROM logs
-                WHERE (app_path LIKE ?1 OR ?1 LIKE '%' || app_path || '%')
-                  AND {effective_ts} >= ?2
-                ORDER BY {effective_ts} ASC"
-            ),
-            (None, Some(lim)) => format!(
-                r"SELECT timestamp_ns, observed_timestamp_ns, severity_number, severity_text,
-                       body, service_name, app_path, resource_attributes, log_attributes,
-                       trace_id, span_id
-                FROM logs
-                WHERE {effective_ts} >= ?1
-                ORDER BY {effective_ts} ASC
-                LIMIT {lim}"
-            ),
-            (None, None) => format!(
-                r"SELECT timestamp_ns, observed_timestamp_ns, severity_number, severity_text,
-                       body, service_name, app_path, resource_attributes, log_attributes,
-                       trace_id, span_id
-                FROM logs
-                WHERE {effective_ts} >= ?1
-                ORDER BY {effective_ts} ASC"
-            ),
+        let filter = LogFilter {
+            since_ns,
+            ..LogFilter::default()
         };
+        self.query_logs_filtered(app_path, &filter, limit).await
+    }
 
-        let rows = if let Some(path) = app_path {
-            let pattern = format!("%{path}%");
-            sqlx::query(&sql)
-                .bind(&pattern)
-                .bind(since_ns)
-                .fetch_all(&self.pool)
-                .await
-        } else {
-            sqlx::query(&sql).bind(since_ns).fetch_all(&self.pool).await
-        }
-        .map_err(|e| format!("Query error: {e}"))?;
+    /// Query logs for a specific app path matching `filter`, oldest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn query_logs_filtered(
+        &self,
+        app_path: &str,
+        filter: &LogFilter,
+        limit: Option<usize>,
+    ) -> Result<Vec<LogRecord>, String> {
+        let app_path = canonical_app_path(app_path);
+        let limit = limit.map(|lim| format!("LIMIT {lim}")).unwrap_or_default();
+
+        let sql = format!(
+            r"SELECT id, timestamp_ns, observed_timestamp_ns, severity_number, severity_text,
+                   body, service_name, app_path, resource_attributes, log_attributes,
+                   trace_id, span_id, repeat_count
+            FROM logs
+            WHERE {APP_PATH_MATCH} AND {FILTER_MATCH}
+            ORDER BY {EFFECTIVE_TS} ASC, id ASC
+            {limit}"
+        );
+
+        let rows = bind_filter(sqlx::query(&sql), &app_path, filter)
+            .fetch_all(&self.readers)
+            .await
+            .map_err(|e| format!("Query error: {e}"))?;
 
         let records = rows.iter().map(row_to_log_record).collect();
         Ok(records)
     }
 
+    /// Query the newest `count` logs for a specific app path matching `filter`,
+    /// returned oldest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn query_recent_logs_filtered(
+        &self,
+        app_path: &str,
+        filter: &LogFilter,
+        count: usize,
+    ) -> Result<Vec<LogRecord>, String> {
+        let app_path = canonical_app_path(app_path);
+        let sql = format!(
+            r"SELECT id, timestamp_ns, observed_timestamp_ns, severity_number, severity_text,
+                   body, service_name, app_path, resource_attributes, log_attributes,
+                   trace_id, span_id, repeat_count
+            FROM logs
+            WHERE {APP_PATH_MATCH} AND {FILTER_MATCH}
+            ORDER BY {EFFECTIVE_TS} DESC, id DESC
+            LIMIT {count}"
+        );
+
+        let rows = bind_filter(sqlx::query(&sql), &app_path, filter)
+            .fetch_all(&self.readers)
+            .await
+            .map_err(|e| format!("Query error: {e}"))?;
+
+        Ok(rows.iter().rev().map(row_to_log_record).collect())
+    }
+
+    /// Query the logs of an app that belong to one trace, oldest first.
+    ///
+    /// Stored trace IDs match in lowercase or uppercase hex, since exporters differ in casing.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn query_logs_by_trace(
+        &self,
+        app_path: &str,
+        trace_id: &str,
+    ) -> Result<Vec<LogRecord>, String> {
+        let app_path = canonical_app_path(app_path);
+        let sql = format!(
+            r"SELECT id, timestamp_ns, observed_timestamp_ns, severity_number, severity_text,
+                   body, service_name, app_path, resource_attributes, log_attributes,
+                   trace_id, span_id, repeat_count
+            FROM logs
+            WHERE {APP_PATH_MATCH} AND trace_id IN (lower(?2), upper(?2))
+            ORDER BY {EFFECTIVE_TS} ASC, id ASC"
+        );
+
+        let rows = sqlx::query(&sql)
+            .bind(&app_path)
+            .bind(trace_id)
+            .fetch_all(&self.readers)
+            .await
+            .map_err(|e| format!("Query error: {e}"))?;
+
+        Ok(rows.iter().map(row_to_log_record).collect())
+    }
+
     /// Get the latest log ID for change detection in follow mode.
     ///
     /// # Errors
@@ -223,67 +582,189 @@ impl LogsDb {
     /// Returns an error if the database query fails.
     pub async fn get_latest_id(&self) -> Result<i64, String> {
         let row = sqlx::query("SELECT COALESCE(MAX(id), 0) as max_id FROM logs")
-            .fetch_one(&self.pool)
+            .fetch_one(&self.readers)
             .await
             .map_err(|e| format!("Query error: {e}"))?;
         let id: i64 = row.get("max_id");
         Ok(id)
     }
 
-    /// Query logs newer than a given ID (for follow mode).
+    /// Query logs for a specific app path newer than a given ID (for follow mode).
     ///
     /// # Errors
     ///
     /// Returns an error if the database query fails.
     pub async fn query_logs_after_id(
         &self,
-        app_path: Option<&str>,
+        app_path: &str,
         after_id: i64,
     ) -> Result<Vec<LogRecord>, String> {
-        let effective_ts = "COALESCE(NULLIF(timestamp_ns, 0), observed_timestamp_ns)";
-
-        let (sql, has_app_path) = if app_path.is_some() {
-            (
-                format!(
-                    r"SELECT timestamp_ns, observed_timestamp_ns, severity_number, severity_text,
-                           body, service_name, app_path, resource_attributes, log_attributes,
-                           trace_id, span_id
-                    FROM logs
-                    WHERE id > ?1 AND (app_path LIKE ?2 OR ?2 LIKE '%' || app_path || '%')
-                    ORDER BY {effective_ts} ASC"
-                ),
-                true,
-            )
-        } else {
-            (
-                format!(
-                    r"SELECT timestamp_ns, observed_timestamp_ns, severity_number, severity_text,
-                           body, service_name, app_path, resource_attributes, log_attributes,
-                           trace_id, span_id
-                    FROM logs
-                    WHERE id > ?1
-                    ORDER BY {effective_ts} ASC"
-                ),
-                false,
-            )
-        };
+        self.query_logs_after_id_filtered(app_path, after_id, &LogFilter::default())
+            .await
+    }
 
-        let rows = if has_app_path {
-            let pattern = format!("%{}%", app_path.unwrap_or(""));
-            sqlx::query(&sql)
-                .bind(after_id)
-                .bind(&pattern)
-                .fetch_all(&self.pool)
-                .await
-        } else {
-            sqlx::query(&sql).bind(after_id).fetch_all(&self.pool).await
-        }
-        .map_err(|e| format!("Query error: {e}"))?;
+    /// Query logs newer than a given ID that also match `filter` (for follow mode).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn query_logs_after_id_filtered(
+        &self,
+        app_path: &str,
+        after_id: i64,
+        filter: &LogFilter,
+    ) -> Result<Vec<LogRecord>, String> {
+        let app_path = canonical_app_path(app_path);
+        let sql = format!(
+            r"SELECT id, timestamp_ns, observed_timestamp_ns, severity_number, severity_text,
+                   body, service_name, app_path, resource_attributes, log_attributes,
+                   trace_id, span_id, repeat_count
+            FROM logs
+            WHERE {APP_PATH_MATCH} AND {FILTER_MATCH} AND id > ?8
+            ORDER BY {EFFECTIVE_TS} ASC, id ASC"
+        );
+
+        let rows = bind_filter(sqlx::query(&sql), &app_path, filter)
+            .bind(after_id)
+            .fetch_all(&self.readers)
+            .await
+            .map_err(|e| format!("Query error: {e}"))?;
 
         let records = rows.iter().map(row_to_log_record).collect();
         Ok(records)
     }
 
+    /// Stream every log for an app matching `filter`, in insertion order.
+    ///
+    /// Rows are fetched in batches of a fixed size and handed to `on_batch`, so
+    /// exports never hold the whole table in memory. Returns the number of
+    /// records exported.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a database query fails or `on_batch` returns one.
+    pub async fn export_logs(
+        &self,
+        app_path: &str,
+        filter: &LogFilter,
+        mut on_batch: impl FnMut(&[LogRecord]) -> Result<(), String>,
+    ) -> Result<usize, String> {
+        let mut last_seq = 0_i64;
+        let mut total = 0;
+        loop {
+            let records = self.export_batch(app_path, filter, last_seq).await?;
+            let Some(last) = records.last() else {
+                break;
+            };
+            last_seq = last.seq;
+            on_batch(&records)?;
+            total += records.len();
+        }
+        Ok(total)
+    }
+
+    /// Fetch the next batch of an export: logs for an app matching `filter`
+    /// stored after sequence number `after_seq`, in insertion order.
+    ///
+    /// Pass `0` for the first batch and the `seq` of the last record returned for
+    /// the next one. An empty batch ends the export.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn export_batch(
+        &self,
+        app_path: &str,
+        filter: &LogFilter,
+        after_seq: i64,
+    ) -> Result<Vec<LogRecord>, String> {
+        let app_path = canonical_app_path(app_path);
+        let sql = format!(
+            r"SELECT id, timestamp_ns, observed_timestamp_ns, severity_number, severity_text,
+                   body, service_name, app_path, resource_attributes, log_attributes,
+                   trace_id, span_id, repeat_count
+            FROM logs
+            WHERE {APP_PATH_MATCH} AND {FILTER_MATCH} AND id > ?8
+            ORDER BY id ASC
+            LIMIT {EXPORT_BATCH_SIZE}"
+        );
+
+        let rows = bind_filter(sqlx::query(&sql), &app_path, filter)
+            .bind(after_seq)
+            .fetch_all(&self.readers)
+            .await
+            .map_err(|e| format!("Query error: {e}"))?;
+        Ok(rows.iter().map(row_to_log_record).collect())
+    }
+
+    /// List the distinct `service.name` values an app has logged with, sorted.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn distinct_services(&self, app_path: &str) -> Result<Vec<String>, String> {
+        let app_path = canonical_app_path(app_path);
+        let sql = format!(
+            r"SELECT DISTINCT service_name FROM logs
+            WHERE {APP_PATH_MATCH} AND service_name IS NOT NULL
+            ORDER BY service_name"
+        );
+        let rows = sqlx::query(&sql)
+            .bind(&app_path)
+            .fetch_all(&self.readers)
+            .await
+            .map_err(|e| format!("Query error: {e}"))?;
+
+        Ok(rows.iter().map(|row| row.get("service_name")).collect())
+    }
+
+    /// Count the logs of an app matching `filter`, in total and per severity and service.
+    ///
+    /// Tallies are computed with `GROUP BY` in the database, so no rows are loaded.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub async fn log_stats(&self, app_path: &str, filter: &LogFilter) -> Result<LogStats, String> {
+        let app_path = canonical_app_path(app_path);
+        let severity_sql = format!(
+            r"SELECT {SEVERITY_LEVEL} AS level, SUM(repeat_count) AS cnt FROM logs
+            WHERE {APP_PATH_MATCH} AND {FILTER_MATCH}
+            GROUP BY level
+            ORDER BY MAX(COALESCE(severity_number, 9)) DESC"
+        );
+        let by_severity: Vec<(String, i64)> =
+            bind_filter(sqlx::query(&severity_sql), &app_path, filter)
+                .fetch_all(&self.readers)
+                .await
+                .map_err(|e| format!("Query error: {e}"))?
+                .iter()
+                .map(|row| (row.get("level"), row.get("cnt")))
+                .collect();
+
+        let service_sql = format!(
+            r"SELECT service_name, SUM(repeat_count) AS cnt FROM logs
+            WHERE {APP_PATH_MATCH} AND {FILTER_MATCH} AND service_name IS NOT NULL
+            GROUP BY service_name
+            ORDER BY cnt DESC, service_name"
+        );
+        let by_service = bind_filter(sqlx::query(&service_sql), &app_path, filter)
+            .fetch_all(&self.readers)
+            .await
+            .map_err(|e| format!("Query error: {e}"))?
+            .iter()
+            .map(|row| (row.get("service_name"), row.get("cnt")))
+            .collect();
+
+        // Every row falls in exactly one severity bucket
+        let total = by_severity.iter().map(|(_, count)| count).sum();
+        Ok(LogStats {
+            total,
+            by_severity,
+            by_service,
+        })
+    }
+
     /// Delete logs older than the retention period (7 days).
     ///
     /// # Errors
@@ -297,7 +778,7 @@ impl LogsDb {
 
         let result = sqlx::query("DELETE FROM logs WHERE created_at < ?")
             .bind(cutoff)
-            .execute(&self.pool)
+            .execute(&self.writer)
             .await
             .map_err(|e| format!("Delete error: {e}"))?;
 
@@ -309,19 +790,67 @@ impl LogsDb {
         }
         Ok(deleted)
     }
-}
 
-#[cfg(test)]
-impl LogsDb {
-    /// Get the total count of logs.
+    /// Delete all logs recorded for a specific app path.
+    ///
+    /// Uses the same path matching as [`LogsDb::query_logs`], so only the logs
+    /// of this exact app are removed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the delete query fails.
+    pub async fn delete_logs_for_app(&self, app_path: &str) -> Result<usize, String> {
+        let app_path = canonical_app_path(app_path);
+        let result = sqlx::query(&format!("DELETE FROM logs WHERE {APP_PATH_MATCH}"))
+            .bind(&app_path)
+            .execute(&self.writer)
+            .await
+            .map_err(|e| format!("Delete error: {e}"))?;
+
+        #[allow(clippy::cast_possible_truncation)]
+        let deleted = result.rows_affected() as usize;
+        debug!("Deleted {} log records for {}", deleted, app_path);
+        Ok(deleted)
+    }
+
+    /// Get the total count of stored logs across all apps.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
     pub async fn count_logs(&self) -> Result<i64, String> {
         let row = sqlx::query("SELECT COUNT(*) as cnt FROM logs")
-            .fetch_one(&self.pool)
+            .fetch_one(&self.readers)
             .await
             .map_err(|e| format!("Query error: {e}"))?;
         let count: i64 = row.get("cnt");
         Ok(count)
     }
+
+    /// Rebuild indexes, refresh planner statistics and reclaim free pages.
+    ///
+    /// Runs `REINDEX`, `ANALYZE` and `VACUUM`, then truncates the WAL so the
+    /// file size on disk reflects the compacted database. Must not run while
+    /// the flux daemon is writing to the same file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any maintenance statement fails.
+    pub async fn compact(&self) -> Result<(), String> {
+        for sql in [
+            "REINDEX",
+            "ANALYZE",
+            "VACUUM",
+            "PRAGMA wal_checkpoint(TRUNCATE)",
+        ] {
+            sqlx::query(sql)
+                .execute(&self.writer)
+                .await
+                .map_err(|e| format!("Compaction error ({sql}): {e}"))?;
+        }
+        debug!("Compacted logs database");
+        Ok(())
+    }
 }
 
 /// Map a `SQLx` row to a `LogRecord`.
@@ -338,6 +867,8 @@ fn row_to_log_record(row: &sqlx::sqlite::SqliteRow) -> LogRecord {
         log_attributes: row.get("log_attributes"),
         trace_id: row.get("trace_id"),
         span_id: row.get("span_id"),
+        repeat_count: row.get("repeat_count"),
+        seq: row.get("id"),
     }
 }
 
@@ -347,7 +878,7 @@ fn row_to_log_record(row: &sqlx::sqlite::SqliteRow) -> LogRecord {
 mod tests {
     use super::*;
 
-    async fn temp_db() -> LogsDb {
+    fn temp_dir() -> std::path::PathBuf {
         let dir = std::env::temp_dir().join(format!(
             "apx-db-test-{}-{}",
             std::process::id(),
@@ -357,7 +888,11 @@ mod tests {
                 .as_nanos()
         ));
         std::fs::create_dir_all(&dir).unwrap();
-        LogsDb::open_at(&dir.join("test.db")).await.unwrap()
+        dir
+    }
+
+    async fn temp_db() -> LogsDb {
+        LogsDb::open_at(&temp_dir().join("test.db")).await.unwrap()
     }
 
     #[tokio::test]
@@ -372,10 +907,7 @@ mod tests {
             body: Some("Test log message".to_string()),
             service_name: Some("test_app".to_string()),
             app_path: Some("/tmp/test".to_string()),
-            resource_attributes: None,
-            log_attributes: None,
-            trace_id: None,
-            span_id: None,
+            ..LogRecord::default()
         };
 
         let count = db.insert_logs(&[record]).await.unwrap();
@@ -385,6 +917,100 @@ mod tests {
         assert_eq!(total, 1);
     }
 
+    #[tokio::test]
+    async fn test_open_read_only() {
+        let dir = temp_dir();
+        let path = dir.join("test.db");
+        assert!(LogsDb::open_read_only(&path).await.is_err());
+        assert!(!path.exists(), "read-only open must not create the file");
+
+        let record = LogRecord {
+            timestamp_ns: 1_234_567_890_000_000_000,
+            observed_timestamp_ns: 1_234_567_890_000_000_000,
+            severity_number: Some(9),
+            severity_text: Some("INFO".to_string()),
+            body: Some("Test log message".to_string()),
+            service_name: Some("test_app".to_string()),
+            app_path: Some("/tmp/test".to_string()),
+            ..LogRecord::default()
+        };
+        LogsDb::open_at(&path)
+            .await
+            .unwrap()
+            .insert_logs(std::slice::from_ref(&record))
+            .await
+            .unwrap();
+
+        let db = LogsDb::open_read_only(&path).await.unwrap();
+        assert_eq!(db.count_logs().await.unwrap(), 1);
+        assert!(db.insert_logs(&[record]).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_repeat_count_round_trip() {
+        let db = temp_db().await;
+
+        let record = |offset: i64, body: &str, repeat_count: u32| LogRecord {
+            timestamp_ns: 1_000 + offset,
+            observed_timestamp_ns: 1_000 + offset,
+            severity_number: Some(17),
+            body: Some(body.to_string()),
+            service_name: Some("test_app".to_string()),
+            app_path: Some("/tmp/test".to_string()),
+            repeat_count,
+            ..LogRecord::default()
+        };
+        db.insert_logs(&[record(0, "retrying", 142), record(10, "gave up", 1)])
+            .await
+            .unwrap();
+
+        let logs = db.query_logs("/tmp/test", 0, None).await.unwrap();
+        let counts: Vec<u32> = logs.iter().map(|r| r.repeat_count).collect();
+        assert_eq!(counts, vec![142, 1]);
+
+        // Stats count every occurrence, not stored rows
+        let stats = db
+            .log_stats("/tmp/test", &LogFilter::default())
+            .await
+            .unwrap();
+        assert_eq!(stats.total, 143);
+    }
+
+    #[tokio::test]
+    async fn test_subscribers_receive_stored_records() {
+        let db = temp_db().await;
+        let record = |i: usize| LogRecord {
+            timestamp_ns: 1_000,
+            observed_timestamp_ns: 1_000,
+            severity_number: Some(9),
+            body: Some(format!("line {i}")),
+            service_name: Some("test_app".to_string()),
+            app_path: Some("/tmp/test".to_string()),
+            ..LogRecord::default()
+        };
+
+        // Inserting without subscribers does not fail
+        db.insert_logs(&[record(0)]).await.unwrap();
+
+        let mut live = db.clone().subscribe();
+        db.insert_logs(&[record(1), record(2)]).await.unwrap();
+        let first = live.recv().await.unwrap();
+        assert_eq!(first.body.as_deref(), Some("line 1"));
+        assert_eq!(first.seq, 2);
+        let second = live.recv().await.unwrap();
+        assert_eq!(second.body.as_deref(), Some("line 2"));
+        assert_eq!(second.seq, 3);
+
+        // A subscriber that falls behind learns how many records it missed
+        let burst: Vec<_> = (0..LIVE_CHANNEL_CAPACITY + 10).map(record).collect();
+        db.insert_logs(&burst).await.unwrap();
+        assert!(matches!(
+            live.recv().await,
+            Err(broadcast::error::RecvError::Lagged(10))
+        ));
+        assert_eq!(live.recv().await.unwrap().body.as_deref(), Some("line 10"));
+    }
+
     #[tokio::test]
     async fn test_query() {
         let db = temp_db().await;
@@ -397,19 +1023,59 @@ mod tests {
             body: Some("Test log message".to_string()),
             service_name: Some("test_app".to_string()),
             app_path: Some("/tmp/test".to_string()),
-            resource_attributes: None,
-            log_attributes: None,
-            trace_id: None,
-            span_id: None,
+            ..LogRecord::default()
         };
 
         db.insert_logs(&[record]).await.unwrap();
 
-        let records = db.query_logs(Some("/tmp/test"), 0, None).await.unwrap();
+        let records = db.query_logs("/tmp/test", 0, None).await.unwrap();
         assert_eq!(records.len(), 1);
         assert_eq!(records[0].body, Some("Test log message".to_string()));
     }
 
+    #[tokio::test]
+    async fn test_same_timestamp_keeps_ingest_order() {
+        let db = temp_db().await;
+        let record = |timestamp_ns: i64, body: &str| LogRecord {
+            timestamp_ns,
+            observed_timestamp_ns: timestamp_ns,
+            severity_number: Some(9),
+            body: Some(body.to_string()),
+            service_name: Some("test_app".to_string()),
+            app_path: Some("/tmp/test".to_string()),
+            ..LogRecord::default()
+        };
+
+        // A burst within one millisecond, then an earlier line arriving late
+        let burst: Vec<_> = ["zeta", "alpha", "mid"]
+            .iter()
+            .map(|body| record(5_000_000, body))
+            .collect();
+        db.insert_logs(&burst).await.unwrap();
+        db.insert_logs(&[record(1_000_000, "early"), record(5_000_000, "last")])
+            .await
+            .unwrap();
+
+        let bodies = |records: &[LogRecord]| -> Vec<String> {
+            records.iter().filter_map(|r| r.body.clone()).collect()
+        };
+        let expected = vec!["early", "zeta", "alpha", "mid", "last"];
+
+        let logs = db.query_logs("/tmp/test", 0, None).await.unwrap();
+        assert_eq!(bodies(&logs), expected);
+        let seqs: Vec<i64> = logs.iter().map(|r| r.seq).collect();
+        assert_eq!(seqs, vec![4, 1, 2, 3, 5]);
+
+        let after = db.query_logs_after_id("/tmp/test", 0).await.unwrap();
+        assert_eq!(bodies(&after), expected);
+
+        let recent = db
+            .query_recent_logs_filtered("/tmp/test", &LogFilter::default(), 3)
+            .await
+            .unwrap();
+        assert_eq!(bodies(&recent), vec!["alpha", "mid", "last"]);
+    }
+
     #[tokio::test]
     async fn test_query_after_id() {
         let db = temp_db().await;
@@ -422,10 +1088,7 @@ mod tests {
             body: Some("First".to_string()),
             service_name: Some("test_app".to_string()),
             app_path: Some("/tmp/test".to_string()),
-            resource_attributes: None,
-            log_attributes: None,
-            trace_id: None,
-            span_id: None,
+            ..LogRecord::default()
         };
 
         db.insert_logs(&[record]).await.unwrap();
@@ -439,16 +1102,680 @@ mod tests {
             body: Some("Second".to_string()),
             service_name: Some("test_app".to_string()),
             app_path: Some("/tmp/test".to_string()),
-            resource_attributes: None,
-            log_attributes: None,
-            trace_id: None,
-            span_id: None,
+            ..LogRecord::default()
         };
 
         db.insert_logs(&[record2]).await.unwrap();
 
-        let records = db.query_logs_after_id(Some("/tmp/test"), id).await.unwrap();
+        let records = db.query_logs_after_id("/tmp/test", id).await.unwrap();
         assert_eq!(records.len(), 1);
         assert_eq!(records[0].body, Some("Second".to_string()));
     }
+
+    #[tokio::test]
+    async fn test_distinct_services() {
+        let db = temp_db().await;
+
+        let record = |service: &str, app: &str| LogRecord {
+            timestamp_ns: 1_234_567_890_000_000_000,
+            observed_timestamp_ns: 1_234_567_890_000_000_000,
+            severity_number: Some(9),
+            severity_text: Some("INFO".to_string()),
+            body: Some("Test log message".to_string()),
+            service_name: Some(service.to_string()),
+            app_path: Some(app.to_string()),
+            ..LogRecord::default()
+        };
+
+        db.insert_logs(&[
+            record("web", "/tmp/a"),
+            record("api", "/tmp/a"),
+            record("web", "/tmp/a"),
+            record("worker", "/tmp/b"),
+        ])
+        .await
+        .unwrap();
+
+        assert_eq!(
+            db.distinct_services("/tmp/a").await.unwrap(),
+            vec!["api", "web"]
+        );
+        assert_eq!(
+            db.distinct_services("/tmp/b").await.unwrap(),
+            vec!["worker"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_backfill_from_resource_attributes() {
+        let db = temp_db().await;
+
+        let record = LogRecord {
+            timestamp_ns: 1_234_567_890_000_000_000,
+            observed_timestamp_ns: 1_234_567_890_000_000_000,
+            severity_number: Some(9),
+            severity_text: Some("INFO".to_string()),
+            body: Some("Legacy row".to_string()),
+            resource_attributes: Some(
+                r#"[{"key":"service.name","value":{"stringValue":"legacy"}},{"key":"apx.app_path","value":{"stringValue":"/tmp/legacy"}}]"#
+                    .to_string(),
+            ),
+            ..LogRecord::default()
+        };
+        db.insert_logs(std::slice::from_ref(&record)).await.unwrap();
+
+        // A database from before the migration fills the missing columns on open
+        sqlx::query("PRAGMA user_version = 0")
+            .execute(&db.writer)
+            .await
+            .unwrap();
+        db.init_schema().await.unwrap();
+        let version: i64 = sqlx::query_scalar("PRAGMA user_version")
+            .fetch_one(&db.writer)
+            .await
+            .unwrap();
+        assert_eq!(version, SCHEMA_VERSION);
+
+        // Later opens no longer rescan the table
+        db.insert_logs(&[record]).await.unwrap();
+        db.init_schema().await.unwrap();
+
+        let records = db.query_logs("/tmp/legacy", 0, None).await.unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].service_name, Some("legacy".to_string()));
+        assert_eq!(
+            db.distinct_services("/tmp/legacy").await.unwrap(),
+            vec!["legacy"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_queries_isolated_per_app_path() {
+        let db = temp_db().await;
+
+        let record = |app: &str| LogRecord {
+            timestamp_ns: 1_234_567_890_000_000_000,
+            observed_timestamp_ns: 1_234_567_890_000_000_000,
+            severity_number: Some(9),
+            severity_text: Some("INFO".to_string()),
+            body: Some(format!("from {app}")),
+            service_name: Some("test_app".to_string()),
+            app_path: Some(app.to_string()),
+            ..LogRecord::default()
+        };
+
+        db.insert_logs(&[
+            record("/tmp/app"),
+            record("/tmp/app2"),
+            record("/home/u/tmp/app"),
+            record("/private/tmp/app"),
+        ])
+        .await
+        .unwrap();
+
+        // A path never matches one it is merely a suffix of
+        let bodies: Vec<_> = db
+            .query_logs("/tmp/app", 0, None)
+            .await
+            .unwrap()
+            .into_iter()
+            .filter_map(|r| r.body)
+            .collect();
+        assert_eq!(bodies, vec!["from /tmp/app"]);
+
+        assert_eq!(
+            db.query_logs_after_id("/tmp/app2", 0).await.unwrap().len(),
+            1
+        );
+
+        assert_eq!(db.delete_logs_for_app("/tmp/app").await.unwrap(), 1);
+        assert_eq!(db.count_logs().await.unwrap(), 3);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_app_path_matches_through_symlinks() {
+        let db = temp_db().await;
+
+        // `link` resolves to `real`, like `/tmp` -> `/private/tmp` on macOS
+        let root = temp_dir();
+        let real = root.join("real");
+        std::fs::create_dir_all(real.join("app")).unwrap();
+        std::os::unix::fs::symlink(&real, root.join("link")).unwrap();
+        let app = real.join("app").to_string_lossy().into_owned();
+        let linked = root.join("link/app").to_string_lossy().into_owned();
+
+        let record = LogRecord {
+            timestamp_ns: 1_234_567_890_000_000_000,
+            observed_timestamp_ns: 1_234_567_890_000_000_000,
+            severity_number: Some(9),
+            severity_text: Some("INFO".to_string()),
+            body: Some("linked".to_string()),
+            service_name: Some("test_app".to_string()),
+            app_path: Some(linked.clone()),
+            ..LogRecord::default()
+        };
+        db.insert_logs(&[record]).await.unwrap();
+
+        let records = db.query_logs(&app, 0, None).await.unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].app_path, Some(app.clone()));
+        assert_eq!(db.query_logs(&linked, 0, None).await.unwrap().len(), 1);
+        assert_eq!(db.delete_logs_for_app(&linked).await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_compact() {
+        let path = temp_dir().join("test.db");
+        let db = LogsDb::open_at(&path).await.unwrap();
+        // Database file plus write-ahead log, as reported by `apx flux compact`
+        let size_on_disk = || -> u64 {
+            ["", "-wal"]
+                .iter()
+                .filter_map(|suffix| std::fs::metadata(format!("{}{suffix}", path.display())).ok())
+                .map(|meta| meta.len())
+                .sum()
+        };
+
+        let records: Vec<_> = (0..200)
+            .map(|i| LogRecord {
+                timestamp_ns: 1_234_567_890_000_000_000 + i,
+                observed_timestamp_ns: 1_234_567_890_000_000_000 + i,
+                severity_number: Some(9),
+                severity_text: Some("INFO".to_string()),
+                body: Some("x".repeat(1024)),
+                service_name: Some("test_app".to_string()),
+                app_path: Some("/tmp/test".to_string()),
+                ..LogRecord::default()
+            })
+            .collect();
+        db.insert_logs(&records).await.unwrap();
+        db.delete_logs_for_app("/tmp/test").await.unwrap();
+        let before = size_on_disk();
+
+        db.compact().await.unwrap();
+        let after = size_on_disk();
+        assert!(
+            after < before,
+            "{after} bytes after compacting, {before} before"
+        );
+        db.compact().await.unwrap();
+
+        assert_eq!(db.count_logs().await.unwrap(), 0);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_concurrent_ingest_and_queries() {
+        let db = temp_db().await;
+
+        let mut tasks = Vec::new();
+        for batch in 0..8 {
+            let db = db.clone();
+            tasks.push(tokio::spawn(async move {
+                let records: Vec<_> = (0..50)
+                    .map(|i| LogRecord {
+                        timestamp_ns: 1_234_567_890_000_000_000 + batch * 50 + i,
+                        observed_timestamp_ns: 1_234_567_890_000_000_000,
+                        severity_number: Some(9),
+                        severity_text: Some("INFO".to_string()),
+                        body: Some(format!("batch {batch} record {i}")),
+                        service_name: Some("test_app".to_string()),
+                        app_path: Some("/tmp/test".to_string()),
+                        ..LogRecord::default()
+                    })
+                    .collect();
+                db.insert_logs(&records).await.map(|_| ())
+            }));
+            let db = db.clone();
+            tasks.push(tokio::spawn(async move {
+                db.query_logs("/tmp/test", 0, Some(100)).await.map(|_| ())
+            }));
+        }
+        for task in tasks {
+            task.await.unwrap().unwrap();
+        }
+
+        assert_eq!(db.count_logs().await.unwrap(), 400);
+        // Queries run on read-only connections
+        assert!(
+            sqlx::query("DELETE FROM logs")
+                .execute(&db.readers)
+                .await
+                .is_err()
+        );
+    }
+
+    /// Ingest and query contention with the writer/reader split, against one shared
+    /// pool as used before the split. Run with
+    /// `cargo test -p apx-db --release -- --ignored bench_pool_split --nocapture`.
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    #[ignore = "benchmark"]
+    #[allow(clippy::print_stdout)]
+    async fn bench_pool_split() {
+        const TASKS: u64 = 8;
+        const ROUNDS: u64 = 25;
+        const BATCH: u64 = 200;
+
+        async fn run(db: LogsDb) -> (std::time::Duration, std::time::Duration) {
+            let start = std::time::Instant::now();
+            let mut writers = Vec::new();
+            let mut readers = Vec::new();
+            for task in 0..TASKS {
+                let writer = db.clone();
+                writers.push(tokio::spawn(async move {
+                    for round in 0..ROUNDS {
+                        let records: Vec<_> = (0..BATCH)
+                            .map(|i| LogRecord {
+                                timestamp_ns: ((task * ROUNDS + round) * BATCH + i)
+                                    .try_into()
+                                    .unwrap(),
+                                body: Some(format!("task {task} round {round} record {i}")),
+                                service_name: Some("bench".to_string()),
+                                app_path: Some("/tmp/bench".to_string()),
+                                ..LogRecord::default()
+                            })
+                            .collect();
+                        writer.insert_logs(&records).await.unwrap();
+                    }
+                }));
+                let reader = db.clone();
+                readers.push(tokio::spawn(async move {
+                    let mut latencies = Vec::new();
+                    for _ in 0..ROUNDS {
+                        let query = std::time::Instant::now();
+                        reader.query_logs("/tmp/bench", 0, Some(500)).await.unwrap();
+                        latencies.push(query.elapsed());
+                    }
+                    latencies
+                }));
+            }
+            for writer in writers {
+                writer.await.unwrap();
+            }
+            let ingest = start.elapsed();
+            let mut latencies = Vec::new();
+            for reader in readers {
+                latencies.extend(reader.await.unwrap());
+            }
+            latencies.sort();
+            (ingest, latencies[latencies.len() * 95 / 100])
+        }
+
+        let split = temp_db().await;
+
+        // The pre-split layout: ingest and queries share five connections
+        let path = temp_dir().join("shared.db");
+        let created = LogsDb::open_at(&path).await.unwrap();
+        let pool = SqlitePoolOptions::new()
+            .max_connections(5)
+            .connect_with(
+                SqliteConnectOptions::new()
+                    .filename(&path)
+                    .journal_mode(SqliteJournalMode::Wal)
+                    .synchronous(SqliteSynchronous::Normal),
+            )
+            .await
+            .unwrap();
+        let shared = LogsDb {
+            writer: pool.clone(),
+            readers: pool,
+            live: created.live.clone(),
+        };
+
+        for (name, db) in [("shared pool", shared), ("split pools", split)] {
+            let (ingest, p95) = run(db).await;
+            println!(
+                "{name}: {} records ingested in {ingest:?}, p95 query latency {p95:?}",
+                TASKS * ROUNDS * BATCH
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_export_logs_in_batches() {
+        let db = temp_db().await;
+
+        let records: Vec<_> = (0..2500)
+            .map(|i| LogRecord {
+                timestamp_ns: 1_234_567_890_000_000_000 + i,
+                observed_timestamp_ns: 1_234_567_890_000_000_000 + i,
+                severity_number: Some(9),
+                severity_text: Some("INFO".to_string()),
+                body: Some(format!("message {i}")),
+                service_name: Some(if i % 2 == 0 { "web" } else { "api" }.to_string()),
+                app_path: Some("/tmp/test".to_string()),
+                ..LogRecord::default()
+            })
+            .collect();
+        db.insert_logs(&records).await.unwrap();
+
+        let mut batches = Vec::new();
+        let total = db
+            .export_logs("/tmp/test", &LogFilter::default(), |batch| {
+                batches.push(batch.len());
+                Ok(())
+            })
+            .await
+            .unwrap();
+        assert_eq!(total, 2500);
+        assert_eq!(batches, vec![1000, 1000, 500]);
+
+        let mut bodies = Vec::new();
+        let total = db
+            .export_logs(
+                "/tmp/test",
+                &LogFilter {
+                    since_ns: 1_234_567_890_000_002_000,
+                    service: Some("web".to_string()),
+                    ..LogFilter::default()
+                },
+                |batch| {
+                    bodies.extend(batch.iter().filter_map(|r| r.body.clone()));
+                    Ok(())
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(total, 250);
+        assert_eq!(bodies.first().map(String::as_str), Some("message 2000"));
+
+        let err = db
+            .export_logs("/tmp/test", &LogFilter::default(), |_| {
+                Err("disk full".to_string())
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(err, "disk full");
+    }
+
+    #[tokio::test]
+    async fn test_query_logs_filtered() {
+        let db = temp_db().await;
+
+        let record = |offset: i64, service: &str, severity: Option<i32>| LogRecord {
+            timestamp_ns: 1_000 + offset,
+            observed_timestamp_ns: 1_000 + offset,
+            severity_number: severity,
+            body: Some(format!("{service} {offset}")),
+            service_name: Some(service.to_string()),
+            app_path: Some("/tmp/test".to_string()),
+            ..LogRecord::default()
+        };
+        db.insert_logs(&[
+            record(0, "web", Some(9)),
+            record(10, "api", Some(17)),
+            record(20, "web", None),
+            record(30, "web", Some(13)),
+        ])
+        .await
+        .unwrap();
+
+        let bodies = |records: Vec<LogRecord>| -> Vec<String> {
+            records.into_iter().filter_map(|r| r.body).collect()
+        };
+
+        let range = LogFilter {
+            since_ns: 1_010,
+            until_ns: Some(1_020),
+            ..LogFilter::default()
+        };
+        assert_eq!(
+            bodies(
+                db.query_logs_filtered("/tmp/test", &range, None)
+                    .await
+                    .unwrap()
+            ),
+            vec!["api 10", "web 20"]
+        );
+
+        let web_warnings = LogFilter {
+            service: Some("web".to_string()),
+            min_severity: Some(13),
+            ..LogFilter::default()
+        };
+        assert_eq!(
+            bodies(
+                db.query_logs_filtered("/tmp/test", &web_warnings, None)
+                    .await
+                    .unwrap()
+            ),
+            vec!["web 30"]
+        );
+        assert_eq!(
+            bodies(
+                db.query_logs_after_id_filtered("/tmp/test", 0, &web_warnings)
+                    .await
+                    .unwrap()
+            ),
+            vec!["web 30"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_query_logs_by_severity_text() {
+        let db = temp_db().await;
+
+        let record = |offset: i64, number: Option<i32>, text: Option<&str>| LogRecord {
+            timestamp_ns: 1_000 + offset,
+            observed_timestamp_ns: 1_000 + offset,
+            severity_number: number,
+            severity_text: text.map(ToString::to_string),
+            body: Some(offset.to_string()),
+            app_path: Some("/tmp/test".to_string()),
+            ..LogRecord::default()
+        };
+        db.insert_logs(&[
+            record(0, Some(13), Some("WARNING")),
+            record(1, Some(14), None),
+            record(2, Some(13), Some("AUDIT")),
+            record(3, None, None),
+        ])
+        .await
+        .unwrap();
+
+        let matching = |text: &str, min_severity: Option<i32>| {
+            let filter = LogFilter {
+                severity_text: Some(text.to_string()),
+                min_severity,
+                ..LogFilter::default()
+            };
+            let db = db.clone();
+            async move {
+                db.query_logs_filtered("/tmp/test", &filter, None)
+                    .await
+                    .unwrap()
+                    .into_iter()
+                    .filter_map(|r| r.body)
+                    .collect::<Vec<_>>()
+            }
+        };
+
+        // Stored text matches exactly (ignoring case), missing text falls back to the number
+        assert_eq!(matching("warning", None).await, vec!["0"]);
+        assert_eq!(matching("WARN", None).await, vec!["1"]);
+        assert_eq!(matching("audit", None).await, vec!["2"]);
+        assert_eq!(matching("INFO", None).await, vec!["3"]);
+        assert!(matching("INFO", Some(13)).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_query_logs_by_attribute() {
+        let db = temp_db().await;
+
+        let record = |offset: i64, body: &str, log_attributes: Option<&str>| LogRecord {
+            timestamp_ns: 1_000 + offset,
+            observed_timestamp_ns: 1_000 + offset,
+            body: Some(body.to_string()),
+            app_path: Some("/tmp/test".to_string()),
+            resource_attributes: Some(
+                r#"[{"key":"host.name","value":{"stringValue":"box"}}]"#.to_string(),
+            ),
+            log_attributes: log_attributes.map(ToString::to_string),
+            ..LogRecord::default()
+        };
+        db.insert_logs(&[
+            record(
+                0,
+                "a",
+                Some(
+                    r#"[{"key":"code.filepath","value":{"stringValue":"app.py"}},{"key":"thread.id","value":{"intValue":"7"}}]"#,
+                ),
+            ),
+            record(
+                1,
+                "b",
+                Some(r#"[{"key":"cached","value":{"boolValue":true}},{"key":"thread.id","value":{"intValue":8}}]"#),
+            ),
+            record(2, "c", None),
+        ])
+        .await
+        .unwrap();
+
+        let matching = |attributes: &[(&str, &str)]| {
+            let filter = LogFilter {
+                attributes: attributes
+                    .iter()
+                    .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+                    .collect(),
+                ..LogFilter::default()
+            };
+            let db = db.clone();
+            async move {
+                db.query_logs_filtered("/tmp/test", &filter, None)
+                    .await
+                    .unwrap()
+                    .into_iter()
+                    .filter_map(|r| r.body)
+                    .collect::<Vec<_>>()
+            }
+        };
+
+        assert_eq!(matching(&[("code.filepath", "app.py")]).await, vec!["a"]);
+        assert_eq!(matching(&[("thread.id", "8")]).await, vec!["b"]);
+        assert_eq!(matching(&[("cached", "true")]).await, vec!["b"]);
+        // Resource attributes match too, and every pair must match
+        assert_eq!(matching(&[("host.name", "box")]).await, vec!["a", "b", "c"]);
+        assert_eq!(
+            matching(&[("host.name", "box"), ("thread.id", "7")]).await,
+            vec!["a"]
+        );
+        assert!(matching(&[("thread.id", "9")]).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_attribute_filter_narrows_by_app_path_index() {
+        let db = temp_db().await;
+
+        let filter = LogFilter {
+            attributes: vec![("http.route".to_string(), "/items".to_string())],
+            ..LogFilter::default()
+        };
+        let sql = format!(
+            "EXPLAIN QUERY PLAN SELECT id FROM logs WHERE {APP_PATH_MATCH} AND {FILTER_MATCH}"
+        );
+        let plan: Vec<String> = bind_filter(sqlx::query(&sql), "/tmp/app", &filter)
+            .fetch_all(&db.readers)
+            .await
+            .unwrap()
+            .iter()
+            .map(|row| row.get("detail"))
+            .collect();
+
+        // The json_each predicate only runs on the rows of one app
+        assert!(
+            plan.iter()
+                .any(|step| step.starts_with("SEARCH logs USING INDEX idx_logs_app_")),
+            "{plan:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_log_stats() {
+        let db = temp_db().await;
+
+        let record = |offset: i64, service: Option<&str>, severity: Option<i32>| LogRecord {
+            timestamp_ns: 1_000 + offset,
+            observed_timestamp_ns: 1_000 + offset,
+            severity_number: severity,
+            service_name: service.map(ToString::to_string),
+            app_path: Some("/tmp/test".to_string()),
+            ..LogRecord::default()
+        };
+        db.insert_logs(&[
+            record(0, Some("web"), Some(9)),
+            record(10, Some("api"), Some(17)),
+            record(20, Some("web"), None),
+            record(30, Some("api"), Some(18)),
+            record(40, Some("web"), Some(13)),
+            record(50, None, Some(1)),
+        ])
+        .await
+        .unwrap();
+
+        let owned = |pairs: &[(&str, i64)]| -> Vec<(String, i64)> {
+            pairs.iter().map(|(k, v)| ((*k).to_string(), *v)).collect()
+        };
+
+        let all = db
+            .log_stats("/tmp/test", &LogFilter::default())
+            .await
+            .unwrap();
+        assert_eq!(all.total, 6);
+        assert_eq!(
+            all.by_severity,
+            owned(&[("ERROR", 2), ("WARN", 1), ("INFO", 2), ("TRACE", 1)])
+        );
+        assert_eq!(all.by_service, owned(&[("web", 3), ("api", 2)]));
+
+        let window = LogFilter {
+            since_ns: 1_010,
+            until_ns: Some(1_030),
+            service: Some("api".to_string()),
+            ..LogFilter::default()
+        };
+        let api = db.log_stats("/tmp/test", &window).await.unwrap();
+        assert_eq!(api.total, 2);
+        assert_eq!(api.by_severity, owned(&[("ERROR", 2)]));
+        assert_eq!(api.by_service, owned(&[("api", 2)]));
+
+        let other = db
+            .log_stats("/tmp/other", &LogFilter::default())
+            .await
+            .unwrap();
+        assert_eq!(other, LogStats::default());
+    }
+
+    #[tokio::test]
+    async fn test_query_logs_by_trace() {
+        let db = temp_db().await;
+
+        let record = |offset: i64, trace_id: Option<&str>, app_path: &str| LogRecord {
+            timestamp_ns: 2_000 - offset,
+            observed_timestamp_ns: 2_000 - offset,
+            severity_number: Some(9),
+            body: Some(format!("log {offset}")),
+            service_name: Some("web".to_string()),
+            app_path: Some(app_path.to_string()),
+            trace_id: trace_id.map(ToString::to_string),
+            ..LogRecord::default()
+        };
+        db.insert_logs(&[
+            record(0, Some("abc123"), "/tmp/test"),
+            record(10, Some("ABC123"), "/tmp/test"),
+            record(20, Some("def456"), "/tmp/test"),
+            record(30, None, "/tmp/test"),
+            record(40, Some("abc123"), "/tmp/other"),
+        ])
+        .await
+        .unwrap();
+
+        let bodies: Vec<String> = db
+            .query_logs_by_trace("/tmp/test", "ABC123")
+            .await
+            .unwrap()
+            .into_iter()
+            .filter_map(|r| r.body)
+            .collect();
+        assert_eq!(bodies, vec!["log 10", "log 0"]);
+    }
 }
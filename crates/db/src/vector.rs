@@ -0,0 +1,346 @@
+//! Embedding storage and brute-force nearest-neighbour search on `SQLite`.
+//!
+//! [`VectorTable`] is the vector counterpart of [`Fts5Table`](crate::fts::Fts5Table):
+//! a regular table holding a few text columns plus an `embedding` BLOB of
+//! little-endian `f32` values. Search loads all embeddings and ranks them by
+//! cosine similarity, which is fast enough for indexes of a few thousand rows.
+
+use sqlx::Row;
+use sqlx::sqlite::SqlitePool;
+
+use crate::fts::validate_identifier;
+
+/// A row returned by [`VectorTable::search_cosine`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct VectorMatch {
+    /// Text column values, in column definition order.
+    pub values: Vec<String>,
+    /// Cosine similarity to the query (higher is more similar).
+    pub score: f32,
+}
+
+/// Builder/handle for a table of embeddings.
+#[derive(Debug, Clone)]
+pub struct VectorTable {
+    pool: SqlitePool,
+    table_name: String,
+    columns: Vec<&'static str>,
+    dimensions: usize,
+}
+
+impl VectorTable {
+    /// Construct with validated table and column names (alphanumeric + underscore only).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the table name or any column name contains invalid
+    /// characters, if a column is named `embedding`, or if `dimensions` is zero.
+    pub fn new(
+        pool: SqlitePool,
+        table_name: &str,
+        columns: Vec<&'static str>,
+        dimensions: usize,
+    ) -> Result<Self, String> {
+        validate_identifier(table_name)?;
+        for col in &columns {
+            validate_identifier(col)?;
+            if *col == "embedding" {
+                return Err("Column name 'embedding' is reserved".to_string());
+            }
+        }
+        if dimensions == 0 {
+            return Err("Vector table must have at least one dimension".to_string());
+        }
+        Ok(Self {
+            pool,
+            table_name: table_name.to_string(),
+            columns,
+            dimensions,
+        })
+    }
+
+    /// Get the table name.
+    #[must_use]
+    pub fn table_name(&self) -> &str {
+        &self.table_name
+    }
+
+    /// Check if the table exists in the database.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the existence check query fails.
+    pub async fn exists(&self) -> Result<bool, String> {
+        super::dev::table_exists(&self.pool, &self.table_name).await
+    }
+
+    /// `DROP TABLE IF EXISTS` + `CREATE TABLE`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the drop or create query fails.
+    pub async fn create_or_replace(&self) -> Result<(), String> {
+        let drop_sql = format!("DROP TABLE IF EXISTS \"{}\"", self.table_name);
+        sqlx::query(&drop_sql)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to drop table '{}': {e}", self.table_name))?;
+
+        let mut col_defs: Vec<String> = self
+            .columns
+            .iter()
+            .map(|c| format!("{c} TEXT NOT NULL"))
+            .collect();
+        col_defs.push("embedding BLOB NOT NULL".to_string());
+
+        let create_sql = format!(
+            "CREATE TABLE \"{}\" ({})",
+            self.table_name,
+            col_defs.join(", ")
+        );
+        sqlx::query(&create_sql)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to create vector table '{}': {e}", self.table_name))?;
+
+        Ok(())
+    }
+
+    /// Begin a transaction on the underlying pool.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the transaction cannot be started.
+    pub async fn begin(&self) -> Result<sqlx::Transaction<'_, sqlx::Sqlite>, String> {
+        self.pool
+            .begin()
+            .await
+            .map_err(|e| format!("Transaction error: {e}"))
+    }
+
+    /// Insert a row of text values together with its embedding.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the value count or embedding size mismatches, or the insert fails.
+    pub async fn insert(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+        values: &[&str],
+        embedding: &[f32],
+    ) -> Result<(), String> {
+        if values.len() != self.columns.len() {
+            return Err(format!(
+                "Expected {} values, got {}",
+                self.columns.len(),
+                values.len()
+            ));
+        }
+        self.check_dimensions(embedding)?;
+
+        let placeholders: Vec<String> = (1..=values.len() + 1).map(|i| format!("?{i}")).collect();
+        let sql = format!(
+            "INSERT INTO \"{}\" ({}, embedding) VALUES ({})",
+            self.table_name,
+            self.columns.join(", "),
+            placeholders.join(", "),
+        );
+
+        let mut query = sqlx::query(&sql);
+        for val in values {
+            query = query.bind(*val);
+        }
+        query
+            .bind(encode_embedding(embedding))
+            .execute(&mut **tx)
+            .await
+            .map_err(|e| format!("Insert error: {e}"))?;
+
+        Ok(())
+    }
+
+    /// Delete every row whose `column` equals `value`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the column is unknown or the delete fails.
+    pub async fn delete_where(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+        column: &str,
+        value: &str,
+    ) -> Result<(), String> {
+        if !self.columns.contains(&column) {
+            return Err(format!("Unknown column '{column}'"));
+        }
+
+        let sql = format!("DELETE FROM \"{}\" WHERE {column} = ?1", self.table_name);
+        sqlx::query(&sql)
+            .bind(value)
+            .execute(&mut **tx)
+            .await
+            .map_err(|e| format!("Delete error: {e}"))?;
+
+        Ok(())
+    }
+
+    /// Copy every row of `source` (same columns and dimensions) into this table.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the layouts differ or the copy fails.
+    pub async fn copy_rows_from(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+        source: &Self,
+    ) -> Result<(), String> {
+        if self.columns != source.columns || self.dimensions != source.dimensions {
+            return Err(format!(
+                "Cannot copy rows from '{}': table layouts differ",
+                source.table_name
+            ));
+        }
+
+        let cols = self.columns.join(", ");
+        let sql = format!(
+            "INSERT INTO \"{}\" ({cols}, embedding) SELECT {cols}, embedding FROM \"{}\"",
+            self.table_name, source.table_name
+        );
+        sqlx::query(&sql)
+            .execute(&mut **tx)
+            .await
+            .map_err(|e| format!("Copy error: {e}"))?;
+
+        Ok(())
+    }
+
+    /// Return the `limit` rows most similar to `query` by cosine similarity.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query embedding has the wrong size or the query fails.
+    pub async fn search_cosine(
+        &self,
+        query: &[f32],
+        limit: usize,
+    ) -> Result<Vec<VectorMatch>, String> {
+        self.check_dimensions(query)?;
+
+        let sql = format!(
+            "SELECT {}, embedding FROM \"{}\"",
+            self.columns.join(", "),
+            self.table_name
+        );
+        let rows = sqlx::query(&sql)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| format!("Query error: {e}"))?;
+
+        let mut matches: Vec<VectorMatch> = rows
+            .iter()
+            .filter_map(|row| {
+                let blob: Vec<u8> = row.get("embedding");
+                let embedding = decode_embedding(&blob);
+                if embedding.len() != self.dimensions {
+                    return None;
+                }
+                let values = self
+                    .columns
+                    .iter()
+                    .map(|c| row.get::<String, _>(*c))
+                    .collect();
+                Some(VectorMatch {
+                    values,
+                    score: cosine_similarity(query, &embedding),
+                })
+            })
+            .collect();
+
+        matches.sort_by(|a, b| b.score.total_cmp(&a.score));
+        matches.truncate(limit);
+        Ok(matches)
+    }
+
+    fn check_dimensions(&self, embedding: &[f32]) -> Result<(), String> {
+        if embedding.len() == self.dimensions {
+            Ok(())
+        } else {
+            Err(format!(
+                "Expected embedding with {} dimensions, got {}",
+                self.dimensions,
+                embedding.len()
+            ))
+        }
+    }
+}
+
+fn encode_embedding(embedding: &[f32]) -> Vec<u8> {
+    embedding.iter().flat_map(|v| v.to_le_bytes()).collect()
+}
+
+fn decode_embedding(blob: &[u8]) -> Vec<f32> {
+    blob.chunks_exact(4)
+        .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .collect()
+}
+
+/// Cosine similarity of two equally sized vectors (0.0 if either is all zeros).
+#[must_use]
+pub fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm_a = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norm_b = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm_a <= f32::EPSILON || norm_b <= f32::EPSILON {
+        0.0
+    } else {
+        dot / (norm_a * norm_b)
+    }
+}
+
+#[cfg(test)]
+// Reason: panicking on failure is idiomatic in tests
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_search_cosine_orders_by_similarity() {
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        let table = VectorTable::new(pool, "vec_test", vec!["id", "text"], 2).unwrap();
+        table.create_or_replace().await.unwrap();
+
+        let mut tx = table.begin().await.unwrap();
+        table
+            .insert(&mut tx, &["a", "east"], &[1.0, 0.0])
+            .await
+            .unwrap();
+        table
+            .insert(&mut tx, &["b", "north"], &[0.0, 1.0])
+            .await
+            .unwrap();
+        table
+            .insert(&mut tx, &["c", "north-east"], &[0.7, 0.7])
+            .await
+            .unwrap();
+        tx.commit().await.unwrap();
+
+        let matches = table.search_cosine(&[0.1, 1.0], 2).await.unwrap();
+        let ids: Vec<&str> = matches.iter().map(|m| m.values[0].as_str()).collect();
+        assert_eq!(ids, ["b", "c"]);
+        assert!(matches[0].score > matches[1].score);
+
+        assert!(table.search_cosine(&[1.0], 1).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_vector_table_rejects_reserved_column() {
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        assert!(VectorTable::new(pool, "vec_test", vec!["embedding"], 2).is_err());
+    }
+
+    #[test]
+    fn test_embedding_round_trip() {
+        let embedding = [0.25_f32, -1.5, 3.0];
+        assert_eq!(decode_embedding(&encode_embedding(&embedding)), embedding);
+    }
+}
@@ -1,7 +1,8 @@
 use crate::context::{AppContext, SdkIndexParams};
 use apx_core::databricks_sdk_doc::SDKSource;
-use apx_core::search::ComponentIndex;
+use apx_core::search::{ComponentIndex, HashingEmbedder};
 use apx_db::SqlitePool;
+use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::Duration;
 use tokio::sync::Notify;
@@ -65,7 +66,8 @@ pub fn init_all_indexes(
             tracing::debug!("Using SDK version: {}", version);
 
             // Create SDK docs index (async)
-            let mut index = apx_core::search::docs_index::SDKDocsIndex::new(pool.clone());
+            let mut index = apx_core::search::docs_index::SDKDocsIndex::new(pool.clone())
+                .with_embedder(Arc::new(HashingEmbedder::default()));
             tracing::debug!("SDKDocsIndex created successfully");
 
             // Bootstrap the index (async: download + sync: build)
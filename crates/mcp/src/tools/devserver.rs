@@ -58,9 +58,9 @@ impl ApxServer {
         let path = validated_app_path(&args.app_path)?;
 
         use apx_core::common::OutputMode;
-        use apx_core::ops::dev::restart_dev_server;
+        use apx_core::ops::dev::{RestartMode, restart_dev_server};
 
-        match restart_dev_server(&path, false, OutputMode::Quiet).await {
+        match restart_dev_server(&path, RestartMode::Soft, false, OutputMode::Quiet).await {
             Ok(port) => Ok(CallToolResult::success(vec![Content::text(format!(
                 "Dev server restarted at http://{}:{port}",
                 apx_common::hosts::BROWSER_HOST
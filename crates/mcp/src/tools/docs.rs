@@ -83,7 +83,7 @@ impl ApxServer {
         }
 
         match index
-            .search(&args.source, &args.query, args.num_results)
+            .search_hybrid(&args.source, &args.query, args.num_results)
             .await
         {
             Ok(results) => {
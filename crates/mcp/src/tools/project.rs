@@ -35,7 +35,7 @@ impl ApxServer {
             }
         }
 
-        let response = match run_check(&path, OutputMode::Quiet).await {
+        let response = match run_check(&path, OutputMode::Quiet, false).await {
             Ok(()) => CheckResponse {
                 status: "passed".to_string(),
                 errors: None,
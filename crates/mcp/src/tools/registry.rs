@@ -7,7 +7,7 @@ use crate::validation::validated_app_path;
 use apx_core::components::{
     get_all_registry_indexes, needs_registry_refresh, sync_registry_indexes,
 };
-use apx_core::search::ComponentIndex;
+use apx_core::search::{ComponentFilter, ComponentIndex};
 use rmcp::model::{CallToolResult, ErrorData};
 use rmcp::schemars;
 
@@ -21,6 +21,15 @@ pub struct SearchRegistryComponentsArgs {
     /// Maximum number of results (default: 10)
     #[serde(default = "default_search_limit")]
     pub limit: usize,
+    /// Only return components from this registry (e.g. "@animate-ui"; "ui" for default shadcn)
+    #[serde(default)]
+    pub registry: Option<String>,
+    /// Only return items of this type (e.g. "ui", "hook", "block")
+    #[serde(default)]
+    pub item_type: Option<String>,
+    /// Only return items in this registry category
+    #[serde(default)]
+    pub category: Option<String>,
 }
 
 fn default_search_limit() -> usize {
@@ -81,8 +90,19 @@ impl ApxServer {
                     .into_result();
             }
         };
+        let filter = ComponentFilter {
+            registry: args.registry,
+            item_type: args.item_type,
+            category: args.category,
+        };
+        let filter = (filter != ComponentFilter::default()).then_some(filter);
         let search_results = match index
-            .search(&args.query, args.limit, configured_registries.as_ref())
+            .search(
+                &args.query,
+                args.limit,
+                configured_registries.as_ref(),
+                filter.as_ref(),
+            )
             .await
         {
             Ok(results) => results,
@@ -149,7 +169,7 @@ impl ApxServer {
             ComponentInput::new(args.component_id.clone())
         };
 
-        match add_components(&path, &[input], args.force).await {
+        match add_components(&path, &[input], args.force, false, None).await {
             Ok(result) => {
                 tracing::info!("Component {} added successfully", args.component_id);
 
@@ -36,6 +36,8 @@ function initOtelLogging() {
   const otelEndpoint = process.env.OTEL_EXPORTER_OTLP_ENDPOINT;
   const serviceName = process.env.OTEL_SERVICE_NAME;
   const appPath = process.env.APX_APP_PATH;
+  // Flux rejects OTLP exports without it when APX_FLUX_TOKEN is set
+  const fluxToken = process.env.APX_FLUX_TOKEN?.trim();
 
   if (!otelEndpoint || !serviceName) {
     // OTEL not configured (e.g., during build), skip initialization
@@ -49,6 +51,7 @@ function initOtelLogging() {
 
   const logExporter = new OTLPLogExporter({
     url: `${otelEndpoint}/v1/logs`,
+    headers: fluxToken ? { Authorization: `Bearer ${fluxToken}` } : {},
   });
 
   const loggerProvider = new LoggerProvider({